@@ -0,0 +1,20 @@
+-- OpenSASE HR Platform - Effective-dated employee changes
+
+-- Scheduled field updates, applied by the pending-changes job on effective_date
+CREATE TABLE IF NOT EXISTS employee_pending_changes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL,
+    employee_id UUID NOT NULL REFERENCES employees(id),
+    
+    field VARCHAR(50) NOT NULL,          -- job_title, address, department, manager, work_email, phone
+    new_value JSONB NOT NULL,
+    effective_date DATE NOT NULL,
+    
+    requested_by UUID,
+    applied_at TIMESTAMPTZ,              -- NULL until the job applies it
+    
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_pending_changes_due ON employee_pending_changes(effective_date) WHERE applied_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_pending_changes_employee ON employee_pending_changes(employee_id);
@@ -0,0 +1,19 @@
+-- OpenSASE HR Platform - Department transfer history
+
+-- One row per department move; payroll cost-center allocation reads the
+-- department in effect on each day of the period from this table
+CREATE TABLE IF NOT EXISTS employee_department_transfers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL,
+    employee_id UUID NOT NULL REFERENCES employees(id),
+    
+    from_department_id UUID REFERENCES departments(id),
+    to_department_id UUID NOT NULL REFERENCES departments(id),
+    effective_date DATE NOT NULL,
+    reason TEXT NOT NULL,
+    
+    recorded_by UUID,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_department_transfers_employee ON employee_department_transfers(employee_id, effective_date);
@@ -0,0 +1,9 @@
+-- Payroll run status transitions are compare-and-set:
+--   UPDATE payroll_runs SET status = $3, updated_at = NOW()
+--   WHERE id = $1 AND status = $2 RETURNING *
+-- Allowed transitions are enforced in PayrollRunStatus::allowed_transitions;
+-- the CHECK only rejects unknown statuses.
+
+ALTER TABLE payroll_runs
+    ADD CONSTRAINT payroll_runs_status_check
+    CHECK (status IN ('draft', 'processing', 'pending_approval', 'approved', 'paid', 'failed', 'cancelled'));
@@ -0,0 +1,6 @@
+-- Audit log search: filters by actor and action within a time window,
+-- keyset-paginated on (created_at, id)
+
+CREATE INDEX IF NOT EXISTS idx_audit_logs_created_id ON audit_logs(created_at, id);
+CREATE INDEX IF NOT EXISTS idx_audit_logs_actor_created ON audit_logs(actor_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_audit_logs_action_created ON audit_logs(action, created_at);
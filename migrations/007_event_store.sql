@@ -0,0 +1,11 @@
+-- Durable domain event log for integrator catch-up and webhook replay
+
+CREATE TABLE IF NOT EXISTS event_store (
+    sequence BIGSERIAL PRIMARY KEY,
+    tenant_id UUID NOT NULL,
+    event_type VARCHAR(100) NOT NULL,   -- 'employee.hired', 'payroll.completed', ...
+    payload JSONB NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_store_tenant_sequence ON event_store(tenant_id, sequence);
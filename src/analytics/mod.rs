@@ -0,0 +1,290 @@
+//! Workforce Analytics
+//!
+//! Aggregate figures for the live workforce (headcount by department,
+//! average tenure, pay deciles) that can leave HR without exposing anyone.
+//! Every bucket is k-anonymous: a department, currency, or tenure average
+//! drawn from fewer than `k` employees is suppressed rather than reported,
+//! so no figure can be traced back to a handful of people. A pay decile is
+//! withheld unless at least `k` people are paid at or below it and at
+//! least `k` at or above it. Suppression is complementary: if the totals
+//! minus the reported departments would single out fewer than `k`
+//! people, further departments are suppressed (smallest first), or the
+//! total tenure is withheld.
+
+use std::collections::BTreeMap;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::aggregates::{tenure_years, Employee, EmploymentStatus};
+use crate::domain::services::EmployeeService;
+use crate::payroll::MoneyRounding;
+
+/// Bucket for employees without a department
+pub const UNASSIGNED_DEPARTMENT: &str = "unassigned";
+
+/// Analytics export settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyticsConfig {
+    /// Smallest group reported; anything below is suppressed
+    pub k: usize,
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self { k: 5 }
+    }
+}
+
+impl AnalyticsConfig {
+    fn reportable(&self, members: usize) -> bool {
+        members > 0 && members >= self.k
+    }
+}
+
+/// Headcount and tenure for one department
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepartmentHeadcount {
+    pub department_id: String,
+    pub headcount: usize,
+    /// `None` when fewer than `k` members have a hire date
+    pub average_tenure_years: Option<Decimal>,
+}
+
+/// Annual pay deciles for employees paid in one currency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayDistribution {
+    pub currency: String,
+    pub employees: usize,
+    /// P10 through P90, nearest rank; `None` where fewer than `k` people
+    /// are paid at or below, or at or above, the decile
+    pub deciles: Vec<Option<Decimal>>,
+}
+
+/// k-anonymous workforce figures as of a date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkforceAnalytics {
+    pub as_of: NaiveDate,
+    pub k: usize,
+    /// `None` when the whole live workforce is smaller than `k`
+    pub headcount: Option<usize>,
+    pub departments: Vec<DepartmentHeadcount>,
+    /// Departments left out for having fewer than `k` employees
+    pub suppressed_departments: usize,
+    pub average_tenure_years: Option<Decimal>,
+    pub pay_distributions: Vec<PayDistribution>,
+    /// Currencies left out for having fewer than `k` paid employees
+    pub suppressed_currencies: usize,
+}
+
+/// Still on the books: not terminated or retired
+fn is_live(employee: &Employee) -> bool {
+    !matches!(employee.status(), EmploymentStatus::Terminated | EmploymentStatus::Retired)
+}
+
+fn average_tenure(tenures: &[Decimal], config: &AnalyticsConfig) -> Option<Decimal> {
+    if !config.reportable(tenures.len()) {
+        return None;
+    }
+    let total: Decimal = tenures.iter().sum();
+    Some((total / Decimal::from(tenures.len())).round_dp(1))
+}
+
+/// P10..P90 of `values` by nearest rank; `values` must be sorted. A
+/// nearest-rank decile is one person's pay, so it is only given when `k`
+/// people sit on each side of it.
+fn deciles(values: &[Decimal], config: &AnalyticsConfig) -> Vec<Option<Decimal>> {
+    let n = values.len();
+    (1..=9)
+        .map(|decile| {
+            let rank = (decile * n).div_ceil(10).max(1);
+            let (at_or_below, at_or_above) = (rank, n - rank + 1);
+            (config.reportable(at_or_below) && config.reportable(at_or_above))
+                .then(|| MoneyRounding::default().round(values[rank - 1]))
+        })
+        .collect()
+}
+
+/// Analytics over the live members of `employees`
+pub fn workforce_analytics<'a>(
+    employees: impl IntoIterator<Item = &'a Employee>,
+    as_of: NaiveDate,
+    config: &AnalyticsConfig,
+) -> WorkforceAnalytics {
+    let mut headcount = 0;
+    let mut tenures = Vec::new();
+    let mut departments: BTreeMap<String, (usize, Vec<Decimal>)> = BTreeMap::new();
+    let mut pay: BTreeMap<String, Vec<Decimal>> = BTreeMap::new();
+
+    for employee in employees.into_iter().filter(|e| is_live(e)) {
+        headcount += 1;
+        let tenure = employee.employment().hire_date.map(|hired| tenure_years(hired, as_of));
+
+        let department = employee
+            .employment()
+            .department_id
+            .clone()
+            .unwrap_or_else(|| UNASSIGNED_DEPARTMENT.to_string());
+        let bucket = departments.entry(department).or_default();
+        bucket.0 += 1;
+        if let Some(tenure) = tenure {
+            bucket.1.push(tenure);
+            tenures.push(tenure);
+        }
+
+        if let (Some(rate), Some(annual)) = (employee.compensation().pay_rate.as_ref(), employee.annual_pay()) {
+            pay.entry(rate.currency().to_string()).or_default().push(annual);
+        }
+    }
+
+    let department_count = departments.len();
+    let (mut reported, suppressed): (Vec<_>, Vec<_>) =
+        departments.into_iter().partition(|(_, (count, _))| config.reportable(*count));
+    // Headcount minus the reported departments gives the suppressed ones'
+    // total, so keep suppressing until that total covers `k` people
+    let mut hidden: usize = suppressed.iter().map(|(_, (count, _))| count).sum();
+    if config.reportable(headcount) && hidden > 0 {
+        reported.sort_by_key(|(_, (count, _))| *count);
+        while !config.reportable(hidden) && !reported.is_empty() {
+            let (_, (count, _)) = reported.remove(0);
+            hidden += count;
+        }
+        reported.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+    // Likewise the overall tenure, less the departments' averages, mustn't
+    // expose the tenure of fewer than `k` people
+    let reported_tenures: usize = reported
+        .iter()
+        .map(|(_, (_, tenures))| tenures.len())
+        .filter(|count| config.reportable(*count))
+        .sum();
+    let departments: Vec<DepartmentHeadcount> = reported
+        .into_iter()
+        .map(|(department_id, (headcount, tenures))| DepartmentHeadcount {
+            department_id,
+            headcount,
+            average_tenure_years: average_tenure(&tenures, config),
+        })
+        .collect();
+    let hidden_tenures = tenures.len().saturating_sub(reported_tenures);
+    let average_tenure_years =
+        if hidden_tenures == 0 || config.reportable(hidden_tenures) { average_tenure(&tenures, config) } else { None };
+
+    let currency_count = pay.len();
+    let pay_distributions: Vec<PayDistribution> = pay
+        .into_iter()
+        .filter(|(_, values)| config.reportable(values.len()))
+        .map(|(currency, mut values)| {
+            values.sort();
+            PayDistribution { currency, employees: values.len(), deciles: deciles(&values, config) }
+        })
+        .collect();
+
+    WorkforceAnalytics {
+        as_of,
+        k: config.k,
+        headcount: config.reportable(headcount).then_some(headcount),
+        suppressed_departments: department_count - departments.len(),
+        departments,
+        average_tenure_years,
+        suppressed_currencies: currency_count - pay_distributions.len(),
+        pay_distributions,
+    }
+}
+
+/// Analytics for a tenant's live workforce
+pub fn tenant_analytics(
+    service: &EmployeeService,
+    tenant_id: Uuid,
+    as_of: NaiveDate,
+    config: &AnalyticsConfig,
+) -> WorkforceAnalytics {
+    workforce_analytics(service.tenant_employees(tenant_id), as_of, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{EmployeeId, PayFrequency, PayRate};
+    use rust_decimal_macros::dec;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn employee(seq: u32, department: &str, annual: Decimal, hired: NaiveDate) -> Employee {
+        let email = format!("e{}@example.com", seq);
+        let mut e = Employee::hire(EmployeeId::new(2020, seq), "Test", "Person", &email, "Staff", hired);
+        e.set_compensation(PayRate::salary(annual, "NGN", PayFrequency::Annually), hired);
+        e.transfer(Some(department.into()), None);
+        e
+    }
+
+    fn workforce() -> Vec<Employee> {
+        // Ten engineers paid 1m..10m, two in legal
+        let mut staff: Vec<Employee> = (1..=10)
+            .map(|i| employee(i, "eng", Decimal::from(i) * dec!(1000000), date(2020, 1, 1)))
+            .collect();
+        staff.push(employee(11, "legal", dec!(20000000), date(2022, 1, 1)));
+        staff.push(employee(12, "legal", dec!(25000000), date(2022, 1, 1)));
+        staff
+    }
+
+    #[test]
+    fn test_pay_deciles() {
+        let staff: Vec<Employee> = workforce().into_iter().take(10).collect();
+        let analytics = workforce_analytics(&staff, date(2025, 1, 1), &AnalyticsConfig { k: 1 });
+
+        assert_eq!(analytics.pay_distributions.len(), 1);
+        let ngn = &analytics.pay_distributions[0];
+        assert_eq!(ngn.currency, "NGN");
+        assert_eq!(ngn.employees, 10);
+        let expected: Vec<Option<Decimal>> = (1..=9).map(|i| Some(Decimal::from(i) * dec!(1000000))).collect();
+        assert_eq!(ngn.deciles, expected);
+        assert_eq!(analytics.average_tenure_years, Some(dec!(5.0)));
+
+        // With k = 5 only P50 and P60 have five people on each side; P10
+        // would be the lowest earner's exact pay
+        let analytics = workforce_analytics(&staff, date(2025, 1, 1), &AnalyticsConfig::default());
+        let deciles = &analytics.pay_distributions[0].deciles;
+        assert_eq!(deciles[4..6], [Some(dec!(5000000)), Some(dec!(6000000))]);
+        assert_eq!(deciles.iter().filter(|d| d.is_none()).count(), 7);
+    }
+
+    #[test]
+    fn test_small_department_suppressed() {
+        let mut staff = workforce();
+        let mut gone = employee(13, "eng", dec!(1000000), date(2020, 1, 1));
+        gone.terminate(date(2024, 6, 30), "Resigned").unwrap();
+        staff.push(gone);
+
+        staff.extend((14..=16).map(|i| employee(i, "ops", dec!(3000000), date(2023, 1, 1))));
+
+        let analytics = workforce_analytics(&staff, date(2025, 1, 1), &AnalyticsConfig { k: 3 });
+
+        // Legal's two are suppressed; ops goes too, or 15 - 10 - 3 would
+        // give legal's headcount away
+        assert_eq!(analytics.headcount, Some(15));
+        assert_eq!(analytics.departments.len(), 1);
+        assert_eq!(analytics.departments[0].department_id, "eng");
+        assert_eq!(analytics.departments[0].headcount, 10);
+        assert_eq!(analytics.suppressed_departments, 2);
+        assert!(analytics.departments.iter().all(|d| d.department_id != "legal"));
+        let reported: usize = analytics.departments.iter().map(|d| d.headcount).sum();
+        assert!(analytics.headcount.unwrap() - reported >= 3);
+
+        // Only eng and legal: eng has to go as well
+        let analytics = workforce_analytics(&staff[..13], date(2025, 1, 1), &AnalyticsConfig { k: 3 });
+        assert!(analytics.departments.is_empty());
+        assert_eq!(analytics.suppressed_departments, 2);
+        assert_eq!(analytics.headcount, Some(12));
+
+        // Too few overall: nothing is reported
+        let analytics = workforce_analytics(&staff[10..13], date(2025, 1, 1), &AnalyticsConfig { k: 3 });
+        assert_eq!(analytics.headcount, None);
+        assert_eq!(analytics.average_tenure_years, None);
+        assert!(analytics.pay_distributions.is_empty());
+        assert_eq!(analytics.suppressed_currencies, 1);
+    }
+}
@@ -0,0 +1,304 @@
+//! API Keys
+//!
+//! Service integrations authenticate with `Authorization: ApiKey <key>`
+//! instead of a user JWT. Only a SHA-256 hash of each key is stored; the
+//! key itself is shown once, when issued or rotated. A key belongs to one
+//! tenant and carries an explicit set of scopes, which become the request's
+//! permissions. Revocation takes effect on the next request. Rotation
+//! issues a replacement and retires the old key, after an optional grace
+//! period so integrations can switch over.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::rbac::{AuthContext, Permission, Role};
+
+/// Prefix identifying keys issued by this service
+const KEY_PREFIX: &str = "hrk";
+
+/// Authorization scheme for API keys
+pub const API_KEY_SCHEME: &str = "ApiKey";
+
+/// API key errors
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ApiKeyError {
+    #[error("Invalid API key")]
+    Invalid,
+
+    #[error("API key has been revoked")]
+    Revoked,
+
+    #[error("API key has expired")]
+    Expired,
+
+    #[error("API key not found: {0}")]
+    NotFound(Uuid),
+}
+
+/// API key for integrations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub name: String,
+    /// SHA-256 of the key; never returned by the API
+    #[serde(skip_serializing, default)]
+    pub key_hash: String,
+    pub scopes: HashSet<Permission>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Key issued by rotating this one
+    pub replaced_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    pub fn is_active(&self, at: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires| at < expires)
+    }
+
+    /// Request identity for this key: its scopes and nothing more. The
+    /// least-privileged role keeps role-based record checks closed.
+    pub fn auth_context(&self) -> AuthContext {
+        AuthContext {
+            user_id: self.id,
+            tenant_id: self.tenant_id,
+            employee_id: None,
+            role: Role::Employee,
+            permissions: self.scopes.clone(),
+            department_id: None,
+        }
+    }
+}
+
+/// A newly issued key; `key` is the only copy of the secret
+#[derive(Debug, Clone, Serialize)]
+pub struct IssuedApiKey {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub key: String,
+}
+
+/// Issuance and rotation settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiKeyPolicy {
+    /// Lifetime of keys issued without an explicit expiry; `None` never expires
+    pub default_lifetime: Option<Duration>,
+    /// How long a rotated key keeps working alongside its replacement
+    pub rotation_grace: Duration,
+}
+
+impl Default for ApiKeyPolicy {
+    fn default() -> Self {
+        Self { default_lifetime: Some(Duration::days(365)), rotation_grace: Duration::zero() }
+    }
+}
+
+/// API keys by id
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeyStore {
+    // In real implementation, backed by the api_keys table
+    keys: Arc<DashMap<Uuid, ApiKey>>,
+    policy: ApiKeyPolicy,
+}
+
+impl ApiKeyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_policy(mut self, policy: ApiKeyPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Issue a key for `tenant_id` limited to `scopes`
+    pub fn issue(
+        &self,
+        tenant_id: Uuid,
+        name: &str,
+        scopes: HashSet<Permission>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> IssuedApiKey {
+        let now = Utc::now();
+        let id = Uuid::new_v4();
+        let key = format!("{}_{}_{}{}", KEY_PREFIX, id.simple(), Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let api_key = ApiKey {
+            id,
+            tenant_id,
+            name: name.to_string(),
+            key_hash: hash(&key),
+            scopes,
+            last_used_at: None,
+            expires_at: expires_at.or_else(|| self.policy.default_lifetime.map(|lifetime| now + lifetime)),
+            revoked_at: None,
+            replaced_by: None,
+            created_at: now,
+        };
+        self.keys.insert(id, api_key.clone());
+        IssuedApiKey { api_key, key }
+    }
+
+    pub fn list(&self, tenant_id: Uuid) -> Vec<ApiKey> {
+        let mut keys: Vec<ApiKey> =
+            self.keys.iter().filter(|k| k.tenant_id == tenant_id).map(|k| k.value().clone()).collect();
+        keys.sort_by_key(|k| k.created_at);
+        keys
+    }
+
+    /// Check a presented key and record its use
+    pub fn authenticate(&self, presented: &str) -> Result<ApiKey, ApiKeyError> {
+        let id = parse_id(presented).ok_or(ApiKeyError::Invalid)?;
+        let mut api_key = self.keys.get_mut(&id).ok_or(ApiKeyError::Invalid)?;
+        if api_key.key_hash != hash(presented) {
+            return Err(ApiKeyError::Invalid);
+        }
+        let now = Utc::now();
+        if api_key.revoked_at.is_some() {
+            return Err(ApiKeyError::Revoked);
+        }
+        if !api_key.is_active(now) {
+            return Err(ApiKeyError::Expired);
+        }
+        api_key.last_used_at = Some(now);
+        Ok(api_key.clone())
+    }
+
+    /// Stop a key working from now on
+    pub fn revoke(&self, tenant_id: Uuid, id: Uuid) -> Result<ApiKey, ApiKeyError> {
+        let mut api_key = self.tenant_key(tenant_id, id)?;
+        api_key.revoked_at.get_or_insert_with(Utc::now);
+        Ok(api_key.clone())
+    }
+
+    /// Replace a key with a new one carrying the same name, scopes, and
+    /// lifetime; the old key stops working after the rotation grace period
+    pub fn rotate(&self, tenant_id: Uuid, id: Uuid) -> Result<IssuedApiKey, ApiKeyError> {
+        let old = self.tenant_key(tenant_id, id)?.clone();
+        if old.revoked_at.is_some() {
+            return Err(ApiKeyError::Revoked);
+        }
+        let now = Utc::now();
+        let expires_at = old.expires_at.map(|expires| now + (expires - old.created_at));
+        let issued = self.issue(tenant_id, &old.name, old.scopes.clone(), expires_at);
+
+        let mut old = self.tenant_key(tenant_id, id)?;
+        old.replaced_by = Some(issued.api_key.id);
+        if self.policy.rotation_grace <= Duration::zero() {
+            old.revoked_at = Some(now);
+        } else {
+            let retire_at = now + self.policy.rotation_grace;
+            old.expires_at = Some(old.expires_at.map_or(retire_at, |expires| expires.min(retire_at)));
+        }
+        Ok(issued)
+    }
+
+    fn tenant_key(&self, tenant_id: Uuid, id: Uuid) -> Result<dashmap::mapref::one::RefMut<'_, Uuid, ApiKey>, ApiKeyError> {
+        self.keys.get_mut(&id).filter(|k| k.tenant_id == tenant_id).ok_or(ApiKeyError::NotFound(id))
+    }
+}
+
+fn hash(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Key id embedded in `hrk_<id>_<secret>`
+fn parse_id(presented: &str) -> Option<Uuid> {
+    let mut parts = presented.splitn(3, '_');
+    if parts.next()? != KEY_PREFIX {
+        return None;
+    }
+    let id = Uuid::parse_str(parts.next()?).ok()?;
+    parts.next().filter(|secret| !secret.is_empty())?;
+    Some(id)
+}
+
+/// Authenticate `Authorization: ApiKey <key>` and put the key's
+/// `AuthContext` in request extensions. Requests using another scheme pass
+/// through untouched; a bad, expired, or revoked key is rejected with 401.
+pub async fn authenticate_api_key(State(store): State<ApiKeyStore>, mut request: Request, next: Next) -> Response {
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix(API_KEY_SCHEME))
+        .filter(|rest| rest.starts_with(' '))
+        .map(|rest| rest.trim().to_string());
+    let Some(presented) = presented else {
+        return next.run(request).await;
+    };
+
+    match store.authenticate(&presented) {
+        Ok(api_key) => {
+            request.extensions_mut().insert(api_key.auth_context());
+            next.run(request).await
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "API key rejected");
+            let body = serde_json::json!({ "success": false, "data": null, "error": e.to_string() });
+            (StatusCode::UNAUTHORIZED, Json(body)).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_and_authenticate() {
+        let store = ApiKeyStore::new();
+        let tenant_id = Uuid::new_v4();
+        let issued = store.issue(tenant_id, "Ledger sync", HashSet::from([Permission::PayrollView]), None);
+
+        assert!(issued.key.starts_with("hrk_"));
+        assert_ne!(issued.api_key.key_hash, issued.key);
+        assert!(issued.api_key.expires_at.is_some());
+
+        let key = store.authenticate(&issued.key).unwrap();
+        assert_eq!(key.id, issued.api_key.id);
+        assert!(store.list(tenant_id)[0].last_used_at.is_some());
+        assert!(key.auth_context().has_permission(Permission::PayrollView));
+        assert!(!key.auth_context().has_permission(Permission::PayrollApprove));
+
+        // Right id, wrong secret
+        let forged = format!("{}x", issued.key);
+        assert_eq!(store.authenticate(&forged).unwrap_err(), ApiKeyError::Invalid);
+        assert_eq!(store.authenticate("Bearer abc").unwrap_err(), ApiKeyError::Invalid);
+        // Keys are tenant-scoped for management
+        assert_eq!(store.revoke(Uuid::new_v4(), key.id).unwrap_err(), ApiKeyError::NotFound(key.id));
+    }
+
+    #[test]
+    fn test_rotation_retires_old_key() {
+        let store = ApiKeyStore::new();
+        let tenant_id = Uuid::new_v4();
+        let old = store.issue(tenant_id, "Ledger sync", HashSet::from([Permission::PayrollView]), None);
+
+        let new = store.rotate(tenant_id, old.api_key.id).unwrap();
+        assert_eq!(new.api_key.scopes, old.api_key.scopes);
+        assert_eq!(store.authenticate(&old.key).unwrap_err(), ApiKeyError::Revoked);
+        assert!(store.authenticate(&new.key).is_ok());
+
+        // With a grace period both work until it runs out
+        let store = ApiKeyStore::new().with_policy(ApiKeyPolicy { default_lifetime: None, rotation_grace: Duration::hours(1) });
+        let old = store.issue(tenant_id, "Ledger sync", HashSet::new(), None);
+        let new = store.rotate(tenant_id, old.api_key.id).unwrap();
+        assert!(store.authenticate(&old.key).is_ok());
+        assert!(store.authenticate(&new.key).is_ok());
+        assert_eq!(store.list(tenant_id)[0].replaced_by, Some(new.api_key.id));
+    }
+}
@@ -0,0 +1,247 @@
+//! API Key Handlers
+//!
+//! Endpoints for issuing, listing, rotating, and revoking a tenant's API
+//! keys. A caller can only grant scopes it holds itself.
+
+use std::collections::HashSet;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::api_key::{ApiKeyError, ApiKeyStore};
+use super::rbac::{AuthContext, Permission};
+
+/// API Response wrapper
+#[derive(Debug, Serialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: Option<T>,
+    pub error: Option<String>,
+}
+
+impl<T: Serialize> ApiResponse<T> {
+    pub fn success(data: T) -> Self {
+        Self {
+            success: true,
+            data: Some(data),
+            error: None,
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            data: None,
+            error: Some(message.into()),
+        }
+    }
+}
+
+/// Issue API key request
+#[derive(Debug, Deserialize)]
+pub struct IssueApiKeyRequest {
+    pub name: String,
+    pub scopes: HashSet<Permission>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+fn forbidden() -> Response {
+    (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to manage API keys"))).into_response()
+}
+
+fn key_error(e: ApiKeyError) -> Response {
+    let status = match e {
+        ApiKeyError::NotFound(_) => StatusCode::NOT_FOUND,
+        _ => StatusCode::CONFLICT,
+    };
+    (status, Json(ApiResponse::<()>::error(e.to_string()))).into_response()
+}
+
+/// Issue an API key; the key is only returned here
+///
+/// POST /api/v1/api-keys
+pub async fn issue_api_key(
+    State(store): State<ApiKeyStore>,
+    Extension(auth): Extension<AuthContext>,
+    Json(request): Json<IssueApiKeyRequest>,
+) -> Response {
+    if !auth.has_permission(Permission::ApiKeysManage) {
+        return forbidden();
+    }
+    if request.name.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error("name is required"))).into_response();
+    }
+    if !request.scopes.is_subset(&auth.permissions) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Cannot grant scopes you do not hold")))
+            .into_response();
+    }
+
+    let issued = store.issue(auth.tenant_id, request.name.trim(), request.scopes, request.expires_at);
+    (StatusCode::CREATED, Json(ApiResponse::success(issued))).into_response()
+}
+
+/// List the tenant's API keys, without their secrets
+///
+/// GET /api/v1/api-keys
+pub async fn list_api_keys(State(store): State<ApiKeyStore>, Extension(auth): Extension<AuthContext>) -> Response {
+    if !auth.has_permission(Permission::ApiKeysManage) {
+        return forbidden();
+    }
+    Json(ApiResponse::success(store.list(auth.tenant_id))).into_response()
+}
+
+/// Replace an API key with a new secret
+///
+/// POST /api/v1/api-keys/:id/rotate
+pub async fn rotate_api_key(
+    State(store): State<ApiKeyStore>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if !auth.has_permission(Permission::ApiKeysManage) {
+        return forbidden();
+    }
+    match store.rotate(auth.tenant_id, id) {
+        Ok(issued) => (StatusCode::CREATED, Json(ApiResponse::success(issued))).into_response(),
+        Err(e) => key_error(e),
+    }
+}
+
+/// Revoke an API key with immediate effect
+///
+/// DELETE /api/v1/api-keys/:id
+pub async fn revoke_api_key(
+    State(store): State<ApiKeyStore>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if !auth.has_permission(Permission::ApiKeysManage) {
+        return forbidden();
+    }
+    match store.revoke(auth.tenant_id, id) {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => key_error(e),
+    }
+}
+
+/// Create API key routes
+pub fn api_key_routes() -> axum::Router<ApiKeyStore> {
+    use axum::routing::{delete, get, post};
+
+    axum::Router::new()
+        .route("/api-keys", post(issue_api_key))
+        .route("/api-keys", get(list_api_keys))
+        .route("/api-keys/:id/rotate", post(rotate_api_key))
+        .route("/api-keys/:id", delete(revoke_api_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{authenticate_api_key, Role};
+    use axum::{body::Body, http::Request, middleware};
+    use tower::ServiceExt;
+
+    /// Payroll approval endpoint standing in for any scoped handler
+    async fn approve(Extension(auth): Extension<AuthContext>) -> StatusCode {
+        if auth.has_permission(Permission::PayrollApprove) {
+            StatusCode::OK
+        } else {
+            StatusCode::FORBIDDEN
+        }
+    }
+
+    fn app(store: &ApiKeyStore) -> axum::Router {
+        axum::Router::new()
+            .route("/payroll/approve", axum::routing::post(approve))
+            .layer(middleware::from_fn_with_state(store.clone(), authenticate_api_key))
+    }
+
+    fn call(key: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/payroll/approve")
+            .header("authorization", format!("ApiKey {}", key))
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_api_key_authentication_and_scopes() {
+        let store = ApiKeyStore::new();
+        let tenant_id = Uuid::new_v4();
+        let approver = store.issue(tenant_id, "Approvals bot", HashSet::from([Permission::PayrollApprove]), None);
+        let viewer = store.issue(tenant_id, "Reporting", HashSet::from([Permission::PayrollView]), None);
+
+        assert_eq!(app(&store).oneshot(call(&approver.key)).await.unwrap().status(), StatusCode::OK);
+        assert!(store.list(tenant_id)[0].last_used_at.is_some());
+        // Authenticated, but not scoped for approval
+        assert_eq!(app(&store).oneshot(call(&viewer.key)).await.unwrap().status(), StatusCode::FORBIDDEN);
+        assert_eq!(app(&store).oneshot(call("hrk_nonsense")).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+
+        // Revocation applies to the very next request
+        store.revoke(tenant_id, approver.api_key.id).unwrap();
+        let response = app(&store).oneshot(call(&approver.key)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "API key has been revoked");
+    }
+
+    #[tokio::test]
+    async fn test_manage_api_keys() {
+        let store = ApiKeyStore::new();
+        let tenant_id = Uuid::new_v4();
+        let admin = AuthContext {
+            user_id: Uuid::new_v4(),
+            tenant_id,
+            employee_id: None,
+            role: Role::HrManager,
+            permissions: HashSet::from([Permission::ApiKeysManage, Permission::PayrollView]),
+            department_id: None,
+        };
+        let routes = api_key_routes().layer(Extension(admin)).with_state(store.clone());
+        let issue = |scopes: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/api-keys")
+                .header("content-type", "application/json")
+                .body(Body::from(format!(r#"{{"name": "Ledger sync", "scopes": {}}}"#, scopes)))
+                .unwrap()
+        };
+
+        // Can't grant more than the caller holds
+        let response = routes.clone().oneshot(issue(r#"["payroll_approve"]"#)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = routes.clone().oneshot(issue(r#"["payroll_view"]"#)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let issued: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let old_key = issued["data"]["key"].as_str().unwrap().to_string();
+        assert!(issued["data"].get("key_hash").is_none());
+        let id = issued["data"]["id"].as_str().unwrap().to_string();
+
+        let rotate = Request::builder().method("POST").uri(format!("/api-keys/{}/rotate", id)).body(Body::empty()).unwrap();
+        let response = routes.clone().oneshot(rotate).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let rotated: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let new_key = rotated["data"]["key"].as_str().unwrap();
+        assert_eq!(store.authenticate(&old_key).unwrap_err(), ApiKeyError::Revoked);
+        assert!(store.authenticate(new_key).is_ok());
+
+        let new_id = rotated["data"]["id"].as_str().unwrap();
+        let revoke = |id: &str| Request::builder().method("DELETE").uri(format!("/api-keys/{}", id)).body(Body::empty()).unwrap();
+        assert_eq!(routes.clone().oneshot(revoke(new_id)).await.unwrap().status(), StatusCode::NO_CONTENT);
+        assert_eq!(store.authenticate(new_key).unwrap_err(), ApiKeyError::Revoked);
+        let missing = Uuid::new_v4().to_string();
+        assert_eq!(routes.oneshot(revoke(&missing)).await.unwrap().status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -1,7 +1,9 @@
 //! JWT Authentication
 
 use chrono::{Duration, Utc};
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use uuid::Uuid;
 
 use super::rbac::Role;
@@ -110,8 +112,8 @@ impl JwtService {
         );
 
         // Mock tokens - in production, sign with jsonwebtoken
-        let access_token = format!("mock_access_{}", access_claims.jti);
-        let refresh_token = format!("mock_refresh_{}", refresh_claims.jti);
+        let access_token = self.mock_token("mock_access", &access_claims.jti);
+        let refresh_token = self.mock_token("mock_refresh", &refresh_claims.jti);
 
         TokenPair {
             access_token,
@@ -121,6 +123,14 @@ impl JwtService {
         }
     }
 
+    /// `{prefix}_{jti}.{signature}`, the signature an HMAC-SHA256 of the
+    /// token id under the secret so mock tokens can't be forged
+    fn mock_token(&self, prefix: &str, jti: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes()).expect("HMAC accepts any key length");
+        mac.update(jti.as_bytes());
+        format!("{}_{}.{}", prefix, jti, hex::encode(mac.finalize().into_bytes()))
+    }
+
     /// Validate token (mock - returns None for invalid)
     pub fn validate_token(&self, _token: &str) -> Option<Claims> {
         // In production, decode and validate JWT
@@ -129,19 +139,6 @@ impl JwtService {
     }
 }
 
-/// API Key for integrations
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ApiKey {
-    pub id: Uuid,
-    pub tenant_id: Uuid,
-    pub name: String,
-    pub key_hash: String,  // SHA256 hash of the key
-    pub permissions: Vec<String>,
-    pub last_used_at: Option<chrono::DateTime<Utc>>,
-    pub expires_at: Option<chrono::DateTime<Utc>>,
-    pub created_at: chrono::DateTime<Utc>,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,5 +171,11 @@ mod tests {
         assert!(tokens.access_token.starts_with("mock_access_"));
         assert!(tokens.refresh_token.starts_with("mock_refresh_"));
         assert_eq!(tokens.token_type, "Bearer");
+
+        // Signed under the secret
+        let (id, signature) = tokens.access_token.trim_start_matches("mock_access_").split_once('.').unwrap();
+        assert_eq!(tokens.access_token, service.mock_token("mock_access", id));
+        assert_ne!(JwtService::new("other_secret".to_string()).mock_token("mock_access", id), tokens.access_token);
+        assert_eq!(signature.len(), 64);
     }
 }
@@ -1,9 +1,14 @@
 //! Authentication & Authorization Module
 //!
-//! JWT authentication, RBAC, and multi-tenancy.
+//! JWT authentication, API keys for service integrations, RBAC, and
+//! multi-tenancy.
 
 pub mod jwt;
 pub mod rbac;
+pub mod api_key;
+pub mod handlers;
 
 pub use jwt::*;
 pub use rbac::*;
+pub use api_key::{authenticate_api_key, ApiKey, ApiKeyError, ApiKeyPolicy, ApiKeyStore, IssuedApiKey};
+pub use handlers::api_key_routes;
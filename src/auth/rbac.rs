@@ -134,6 +134,7 @@ pub enum Permission {
     SystemAdmin,
     ReportsView,
     ReportsExport,
+    ApiKeysManage,
 }
 
 impl Permission {
@@ -150,6 +151,7 @@ impl Permission {
             Self::BenefitsEnroll, Self::BenefitsAdmin,
             Self::ComplianceView, Self::ComplianceAdmin,
             Self::SystemAdmin, Self::ReportsView, Self::ReportsExport,
+            Self::ApiKeysManage,
         ]);
         perms
     }
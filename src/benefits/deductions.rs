@@ -0,0 +1,123 @@
+//! Benefit Payroll Deductions
+//!
+//! Turns enrollments into per-period employee and employer contributions.
+//! Plan costs are per full pay period; an enrollment that starts or is
+//! cancelled mid-period pays for the days it was covered, using the same
+//! calendar-day fraction as payroll proration.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::models::{BenefitPlan, EmployeeBenefit};
+use crate::payroll::proration::active_fraction;
+use crate::payroll::rounding::MoneyRounding;
+
+/// One plan's contributions for one pay period
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenefitDeduction {
+    pub benefit_plan_id: Uuid,
+    pub plan_name: String,
+    /// Share of the period the enrollment covered (1 for the full period)
+    pub fraction: Decimal,
+    pub employee_amount: Decimal,
+    pub employer_amount: Decimal,
+}
+
+/// Contributions for `enrollment` over `period_start..=period_end`, or
+/// `None` when it covered none of the period
+pub fn period_deduction(
+    plan: &BenefitPlan,
+    enrollment: &EmployeeBenefit,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    rounding: MoneyRounding,
+) -> Option<BenefitDeduction> {
+    let fraction = active_fraction(period_start, period_end, enrollment.enrolled_date, enrollment.end_date);
+    if fraction.is_zero() {
+        return None;
+    }
+
+    Some(BenefitDeduction {
+        benefit_plan_id: plan.id,
+        plan_name: plan.name.clone(),
+        fraction,
+        employee_amount: rounding.round(plan.cost_employee * fraction),
+        employer_amount: rounding.round(plan.cost_employer * fraction),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+    use crate::benefits::models::{BenefitPlanType, EnrollmentStatus};
+
+    fn d(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    fn hmo_plan() -> BenefitPlan {
+        BenefitPlan {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            name: "Hygeia Gold".to_string(),
+            plan_type: BenefitPlanType::Hmo,
+            provider: Some("Hygeia HMO".to_string()),
+            coverage_details: serde_json::json!({}),
+            cost_employee: dec!(15000),
+            cost_employer: dec!(45000),
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn enrollment(plan: &BenefitPlan, enrolled_date: NaiveDate) -> EmployeeBenefit {
+        EmployeeBenefit {
+            id: Uuid::new_v4(),
+            employee_id: Uuid::new_v4(),
+            benefit_plan_id: plan.id,
+            plan_name: Some(plan.name.clone()),
+            enrolled_date,
+            end_date: None,
+            status: EnrollmentStatus::Active,
+            dependents: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_mid_period_enrollment_is_prorated() {
+        let plan = hmo_plan();
+        // Covered 10–30 June: 21 of 30 days
+        let enrolled = enrollment(&plan, d(6, 10));
+
+        let deduction = period_deduction(&plan, &enrolled, d(6, 1), d(6, 30), MoneyRounding::default()).unwrap();
+        assert_eq!(deduction.fraction, dec!(0.7));
+        assert_eq!(deduction.employee_amount, dec!(10500));
+        assert_eq!(deduction.employer_amount, dec!(31500));
+
+        // Following month is charged in full
+        let july = period_deduction(&plan, &enrolled, d(7, 1), d(7, 31), MoneyRounding::default()).unwrap();
+        assert_eq!(july.employee_amount, dec!(15000));
+    }
+
+    #[test]
+    fn test_mid_period_cancellation_is_prorated() {
+        let plan = hmo_plan();
+        let mut enrolled = enrollment(&plan, d(1, 1));
+        // Covered 1–10 July: 10 of 31 days
+        enrolled.cancel(d(7, 10));
+
+        let deduction = period_deduction(&plan, &enrolled, d(7, 1), d(7, 31), MoneyRounding::default()).unwrap();
+        assert_eq!(enrolled.status, EnrollmentStatus::Cancelled);
+        assert_eq!(deduction.employee_amount, dec!(4838.71));
+        assert_eq!(deduction.employer_amount, dec!(14516.13));
+
+        assert!(period_deduction(&plan, &enrolled, d(8, 1), d(8, 31), MoneyRounding::default()).is_none());
+    }
+}
@@ -0,0 +1,308 @@
+//! Benefit Enrollment
+//!
+//! Elections may only change during open enrollment or within a set number
+//! of days after a recorded qualifying life event (marriage, a birth, loss
+//! of other coverage). Anything else off-cycle is rejected and waits for
+//! the next window.
+
+use std::sync::Arc;
+use chrono::{Days, NaiveDate, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::aggregates::{CoverageLevel, Employee};
+use super::models::{BenefitPlan, EmployeeBenefit, EnrollBenefitRequest, EnrollmentStatus};
+
+/// Benefit enrollment errors
+#[derive(Debug, thiserror::Error)]
+pub enum BenefitsError {
+    #[error("Benefit elections can only change during open enrollment or after a qualifying life event{}",
+        .next_window_opens.map(|d| format!("; next window opens {}", d)).unwrap_or_default())]
+    OutsideEnrollmentWindow { next_window_opens: Option<NaiveDate> },
+
+    #[error("Benefit plan {0} is not active")]
+    PlanInactive(Uuid),
+
+    #[error("Benefit plan not found: {0}")]
+    PlanNotFound(Uuid),
+
+    #[error("Enrollment is for plan {requested} but plan {plan} was given")]
+    PlanMismatch { requested: Uuid, plan: Uuid },
+
+    #[error("Invalid employee id: {0}")]
+    InvalidEmployeeId(String),
+
+    #[error("Enrollment not found: {0}")]
+    EnrollmentNotFound(Uuid),
+}
+
+/// One open enrollment period, inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnrollmentWindow {
+    pub opens: NaiveDate,
+    pub closes: NaiveDate,
+}
+
+impl EnrollmentWindow {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        (self.opens..=self.closes).contains(&date)
+    }
+}
+
+/// When elections may change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnrollmentPolicy {
+    pub windows: Vec<EnrollmentWindow>,
+    /// Days after a qualifying life event that elections stay open
+    pub life_event_days: u64,
+}
+
+impl Default for EnrollmentPolicy {
+    fn default() -> Self {
+        Self { windows: Vec::new(), life_event_days: 30 }
+    }
+}
+
+impl EnrollmentPolicy {
+    pub fn with_window(mut self, opens: NaiveDate, closes: NaiveDate) -> Self {
+        self.windows.push(EnrollmentWindow { opens, closes });
+        self
+    }
+
+    pub fn in_open_enrollment(&self, date: NaiveDate) -> bool {
+        self.windows.iter().any(|w| w.contains(date))
+    }
+
+    pub fn next_window_opens(&self, after: NaiveDate) -> Option<NaiveDate> {
+        self.windows.iter().map(|w| w.opens).filter(|opens| *opens > after).min()
+    }
+}
+
+/// Qualifying life events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LifeEventKind {
+    Marriage,
+    Divorce,
+    BirthOrAdoption,
+    DeathOfDependent,
+    LossOfOtherCoverage,
+    Relocation,
+}
+
+/// A life event on file for an employee
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QualifyingLifeEvent {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub kind: LifeEventKind,
+    pub event_date: NaiveDate,
+}
+
+/// Benefit elections with window enforcement
+#[derive(Debug, Clone, Default)]
+pub struct BenefitsService {
+    policy: EnrollmentPolicy,
+    // In real implementation, backed by the employee_benefits and life_events tables
+    enrollments: Arc<DashMap<Uuid, EmployeeBenefit>>,
+    life_events: Arc<DashMap<Uuid, Vec<QualifyingLifeEvent>>>,
+}
+
+impl BenefitsService {
+    pub fn new(policy: EnrollmentPolicy) -> Self {
+        Self { policy, ..Self::default() }
+    }
+
+    pub fn policy(&self) -> &EnrollmentPolicy {
+        &self.policy
+    }
+
+    pub fn record_life_event(&self, employee_id: Uuid, kind: LifeEventKind, event_date: NaiveDate) -> QualifyingLifeEvent {
+        let event = QualifyingLifeEvent { id: Uuid::new_v4(), employee_id, kind, event_date };
+        self.life_events.entry(employee_id).or_default().push(event.clone());
+        event
+    }
+
+    /// Life event that keeps elections open for the employee on `date`
+    pub fn qualifying_event(&self, employee_id: Uuid, date: NaiveDate) -> Option<QualifyingLifeEvent> {
+        let events = self.life_events.get(&employee_id)?;
+        events
+            .iter()
+            .filter(|e| {
+                let closes = e.event_date.checked_add_days(Days::new(self.policy.life_event_days)).unwrap_or(e.event_date);
+                (e.event_date..=closes).contains(&date)
+            })
+            .max_by_key(|e| e.event_date)
+            .cloned()
+    }
+
+    fn ensure_can_change(&self, employee_id: Uuid, date: NaiveDate) -> Result<(), BenefitsError> {
+        if self.policy.in_open_enrollment(date) || self.qualifying_event(employee_id, date).is_some() {
+            return Ok(());
+        }
+        Err(BenefitsError::OutsideEnrollmentWindow { next_window_opens: self.policy.next_window_opens(date) })
+    }
+
+    /// Enroll `employee_id` of `tenant_id` in `plan` with coverage from
+    /// `date`. The plan must be the tenant's and the one the request names.
+    pub fn enroll(
+        &self,
+        tenant_id: Uuid,
+        employee_id: Uuid,
+        plan: &BenefitPlan,
+        request: EnrollBenefitRequest,
+        date: NaiveDate,
+    ) -> Result<EmployeeBenefit, BenefitsError> {
+        if plan.tenant_id != tenant_id {
+            return Err(BenefitsError::PlanNotFound(plan.id));
+        }
+        if request.benefit_plan_id != plan.id {
+            return Err(BenefitsError::PlanMismatch { requested: request.benefit_plan_id, plan: plan.id });
+        }
+        if !plan.is_active {
+            return Err(BenefitsError::PlanInactive(plan.id));
+        }
+        self.ensure_can_change(employee_id, date)?;
+
+        let now = Utc::now();
+        let enrollment = EmployeeBenefit {
+            id: Uuid::new_v4(),
+            employee_id,
+            benefit_plan_id: plan.id,
+            plan_name: Some(plan.name.clone()),
+            enrolled_date: date,
+            end_date: None,
+            status: EnrollmentStatus::Active,
+            dependents: request.dependents,
+            created_at: now,
+            updated_at: now,
+        };
+        self.enrollments.insert(enrollment.id, enrollment.clone());
+        Ok(enrollment)
+    }
+
+    /// `enroll`, then record the election on the employee's record
+    pub fn enroll_employee(
+        &self,
+        tenant_id: Uuid,
+        employee: &mut Employee,
+        plan: &BenefitPlan,
+        request: EnrollBenefitRequest,
+        coverage: CoverageLevel,
+        date: NaiveDate,
+    ) -> Result<EmployeeBenefit, BenefitsError> {
+        let employee_id = employee.id().parse().map_err(|_| BenefitsError::InvalidEmployeeId(employee.id().to_string()))?;
+        let enrollment = self.enroll(tenant_id, employee_id, plan, request, date)?;
+        let dependents = enrollment.dependents.iter().map(|d| d.name.clone()).collect();
+        employee.enroll_in_benefit(plan.id.to_string(), coverage, dependents);
+        Ok(enrollment)
+    }
+
+    /// Drop coverage after `last_covered_day`, subject to the same window
+    pub fn cancel(&self, enrollment_id: Uuid, last_covered_day: NaiveDate) -> Result<EmployeeBenefit, BenefitsError> {
+        let mut enrollment = self
+            .enrollments
+            .get_mut(&enrollment_id)
+            .ok_or(BenefitsError::EnrollmentNotFound(enrollment_id))?;
+        self.ensure_can_change(enrollment.employee_id, last_covered_day)?;
+        enrollment.cancel(last_covered_day);
+        Ok(enrollment.clone())
+    }
+
+    pub fn enrollments_for(&self, employee_id: Uuid) -> Vec<EmployeeBenefit> {
+        self.enrollments.iter().filter(|e| e.employee_id == employee_id).map(|e| e.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benefits::models::BenefitPlanType;
+    use rust_decimal_macros::dec;
+
+    fn d(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    fn plan() -> BenefitPlan {
+        BenefitPlan {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            name: "Hygeia Gold".to_string(),
+            plan_type: BenefitPlanType::Hmo,
+            provider: Some("Hygeia HMO".to_string()),
+            coverage_details: serde_json::json!({}),
+            cost_employee: dec!(10000),
+            cost_employer: dec!(30000),
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn service() -> BenefitsService {
+        BenefitsService::new(EnrollmentPolicy::default().with_window(d(11, 1), d(11, 30)))
+    }
+
+    fn request(plan: &BenefitPlan) -> EnrollBenefitRequest {
+        EnrollBenefitRequest { benefit_plan_id: plan.id, dependents: vec![] }
+    }
+
+    #[test]
+    fn test_off_window_enrollment_blocked() {
+        let (service, plan) = (service(), plan());
+        let employee_id = Uuid::new_v4();
+
+        let err = service.enroll(plan.tenant_id, employee_id, &plan, request(&plan), d(6, 3)).unwrap_err();
+        assert!(matches!(err, BenefitsError::OutsideEnrollmentWindow { next_window_opens: Some(date) } if date == d(11, 1)));
+        assert!(service.enrollments_for(employee_id).is_empty());
+
+        let enrollment = service.enroll(plan.tenant_id, employee_id, &plan, request(&plan), d(11, 15)).unwrap();
+        assert_eq!(enrollment.status, EnrollmentStatus::Active);
+        assert!(matches!(
+            service.cancel(enrollment.id, d(12, 31)),
+            Err(BenefitsError::OutsideEnrollmentWindow { next_window_opens: None })
+        ));
+    }
+
+    #[test]
+    fn test_qualifying_event_opens_enrollment() {
+        let (service, plan) = (service(), plan());
+        let employee_id = Uuid::new_v4();
+        service.record_life_event(employee_id, LifeEventKind::BirthOrAdoption, d(6, 1));
+
+        let enrollment = service.enroll(plan.tenant_id, employee_id, &plan, request(&plan), d(6, 20)).unwrap();
+        assert_eq!(enrollment.enrolled_date, d(6, 20));
+        assert_eq!(service.qualifying_event(employee_id, d(6, 20)).unwrap().kind, LifeEventKind::BirthOrAdoption);
+
+        // The event's window has passed; another employee never had one
+        assert!(service.enroll(plan.tenant_id, employee_id, &plan, request(&plan), d(7, 15)).is_err());
+        assert!(service.enroll(plan.tenant_id, Uuid::new_v4(), &plan, request(&plan), d(6, 20)).is_err());
+    }
+
+    #[test]
+    fn test_enrollment_checks_plan_and_records_election() {
+        use crate::domain::value_objects::EmployeeId;
+
+        let (service, plan) = (service(), plan());
+        let employee_id = Uuid::new_v4();
+
+        let other_tenant = service.enroll(Uuid::new_v4(), employee_id, &plan, request(&plan), d(11, 15));
+        assert!(matches!(other_tenant, Err(BenefitsError::PlanNotFound(id)) if id == plan.id));
+        let other_plan = EnrollBenefitRequest { benefit_plan_id: Uuid::new_v4(), dependents: vec![] };
+        let mismatch = service.enroll(plan.tenant_id, employee_id, &plan, other_plan, d(11, 15));
+        assert!(matches!(mismatch, Err(BenefitsError::PlanMismatch { plan: id, .. }) if id == plan.id));
+        assert!(service.enrollments_for(employee_id).is_empty());
+
+        let mut employee = Employee::hire(EmployeeId::new(2024, 1), "Ada", "Obi", "ada@company.com", "Analyst", d(1, 8));
+        assert!(service.enroll_employee(plan.tenant_id, &mut employee, &plan, request(&plan), CoverageLevel::Family, d(6, 3)).is_err());
+        assert!(employee.benefits_elections().is_empty());
+
+        let enrollment = service
+            .enroll_employee(plan.tenant_id, &mut employee, &plan, request(&plan), CoverageLevel::Family, d(11, 15))
+            .unwrap();
+        assert_eq!(enrollment.employee_id.to_string(), employee.id());
+        assert_eq!(employee.benefits_elections()[0].benefit_plan_id, plan.id.to_string());
+    }
+}
@@ -1,7 +1,16 @@
 //! Benefits Administration Module
 //!
-//! HMO enrollment, pension AVC, and claims processing.
+//! HMO enrollment, pension AVC, and claims processing. Enrollments feed
+//! payroll as per-period deductions, prorated when coverage starts or ends
+//! mid-period. Elections change only during open enrollment or after a
+//! qualifying life event.
 
 pub mod models;
+pub mod deductions;
+pub mod enrollment;
 
 pub use models::*;
+pub use deductions::{period_deduction, BenefitDeduction};
+pub use enrollment::{
+    BenefitsError, BenefitsService, EnrollmentPolicy, EnrollmentWindow, LifeEventKind, QualifyingLifeEvent,
+};
@@ -48,12 +48,24 @@ pub struct EmployeeBenefit {
     pub benefit_plan_id: Uuid,
     pub plan_name: Option<String>,
     pub enrolled_date: NaiveDate,
+    /// Last day of coverage once cancelled or expired
+    #[serde(default)]
+    pub end_date: Option<NaiveDate>,
     pub status: EnrollmentStatus,
     pub dependents: Vec<Dependent>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl EmployeeBenefit {
+    /// Cancel with coverage running through `last_covered_day`
+    pub fn cancel(&mut self, last_covered_day: NaiveDate) {
+        self.status = EnrollmentStatus::Cancelled;
+        self.end_date = Some(last_covered_day);
+        self.updated_at = Utc::now();
+    }
+}
+
 /// Dependent (for HMO coverage)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dependent {
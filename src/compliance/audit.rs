@@ -0,0 +1,292 @@
+//! Audit Log Queries
+//!
+//! Filtering, keyset pagination, and CSV export over the immutable audit log.
+//! Entries are ordered by `(created_at, id)` so pages stay stable while new
+//! entries are appended.
+
+use std::sync::{Arc, RwLock};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::integrations::csv_field;
+use super::models::{AuditAction, AuditLog};
+
+/// Audit log filter; every field is optional and they combine with AND
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditFilter {
+    pub tenant_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub entity_type: Option<String>,
+    pub action: Option<AuditAction>,
+    /// Inclusive lower bound
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditLog) -> bool {
+        self.tenant_id.is_none_or(|t| entry.tenant_id == t)
+            && self.actor_id.is_none_or(|a| entry.actor_id == Some(a))
+            && self.entity_type.as_ref().is_none_or(|t| entry.entity_type.eq_ignore_ascii_case(t))
+            && self.action.is_none_or(|a| entry.action == a)
+            && self.from.is_none_or(|from| entry.created_at >= from)
+            && self.to.is_none_or(|to| entry.created_at < to)
+    }
+}
+
+/// Position after the last entry of a page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AuditCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl AuditCursor {
+    fn of(entry: &AuditLog) -> Self {
+        Self { created_at: entry.created_at, id: entry.id }
+    }
+
+    /// Opaque form for query strings: `<unix nanos>_<id>`
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.created_at.timestamp_nanos_opt().unwrap_or_default(), self.id)
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        let (nanos, id) = value.split_once('_')?;
+        Some(Self {
+            created_at: DateTime::from_timestamp_nanos(nanos.parse().ok()?),
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+/// One page of audit entries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditPage {
+    pub entries: Vec<AuditLog>,
+    /// Pass back as `cursor` to fetch the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Audit log store
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogStore {
+    // In real implementation, the audit_logs table
+    entries: Arc<RwLock<Vec<AuditLog>>>,
+}
+
+impl AuditLogStore {
+    pub const MAX_PAGE_SIZE: usize = 500;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn append(&self, entry: AuditLog) {
+        let mut entries = self.entries.write().unwrap();
+        let cursor = AuditCursor::of(&entry);
+        let position = entries.partition_point(|e| AuditCursor::of(e) < cursor);
+        entries.insert(position, entry);
+    }
+
+    /// Entries matching `filter` after `cursor`, oldest first
+    pub fn query(&self, filter: &AuditFilter, cursor: Option<AuditCursor>, limit: usize) -> AuditPage {
+        let limit = limit.clamp(1, Self::MAX_PAGE_SIZE);
+        let entries = self.entries.read().unwrap();
+        let start = cursor.map_or(0, |c| entries.partition_point(|e| AuditCursor::of(e) <= c));
+
+        let mut page: Vec<AuditLog> = entries[start..]
+            .iter()
+            .filter(|e| filter.matches(e))
+            .take(limit + 1)
+            .cloned()
+            .collect();
+
+        let next_cursor = if page.len() > limit {
+            page.truncate(limit);
+            page.last().map(|e| AuditCursor::of(e).encode())
+        } else {
+            None
+        };
+
+        AuditPage { entries: page, next_cursor }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CSV EXPORT
+// ═══════════════════════════════════════════════════════════════════════════
+
+pub const AUDIT_CSV_HEADER: &str =
+    "id,created_at,tenant_id,actor_type,actor_id,action,entity_type,entity_id,ip_address,changes\n";
+
+fn snake_case<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+impl AuditLog {
+    /// One CSV line, matching `AUDIT_CSV_HEADER`
+    pub fn to_csv_row(&self) -> String {
+        let changes = self
+            .changes
+            .as_ref()
+            .and_then(|c| serde_json::to_string(c).ok())
+            .unwrap_or_default();
+
+        let fields = [
+            self.id.to_string(),
+            self.created_at.to_rfc3339(),
+            self.tenant_id.to_string(),
+            snake_case(&self.actor_type),
+            self.actor_id.map(|a| a.to_string()).unwrap_or_default(),
+            snake_case(&self.action),
+            self.entity_type.clone(),
+            self.entity_id.to_string(),
+            self.ip_address.map(|ip| ip.to_string()).unwrap_or_default(),
+            changes,
+        ];
+
+        let mut row = fields.iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(",");
+        row.push('\n');
+        row
+    }
+}
+
+/// Page-at-a-time CSV export so large result sets never sit in memory whole
+pub struct AuditCsvExport {
+    store: AuditLogStore,
+    filter: AuditFilter,
+    cursor: Option<AuditCursor>,
+    header_sent: bool,
+    done: bool,
+}
+
+impl AuditCsvExport {
+    pub fn new(store: AuditLogStore, filter: AuditFilter) -> Self {
+        Self { store, filter, cursor: None, header_sent: false, done: false }
+    }
+}
+
+impl Iterator for AuditCsvExport {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if !self.header_sent {
+            self.header_sent = true;
+            return Some(AUDIT_CSV_HEADER.to_string());
+        }
+        if self.done {
+            return None;
+        }
+
+        let page = self.store.query(&self.filter, self.cursor, AuditLogStore::MAX_PAGE_SIZE);
+        self.cursor = page.next_cursor.as_deref().and_then(AuditCursor::decode);
+        self.done = self.cursor.is_none();
+
+        if page.entries.is_empty() {
+            return None;
+        }
+        Some(page.entries.iter().map(AuditLog::to_csv_row).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::ActorType;
+    use chrono::{Duration, TimeZone};
+
+    fn seeded() -> (AuditLogStore, Uuid, Uuid, DateTime<Utc>) {
+        let store = AuditLogStore::new();
+        let tenant = Uuid::new_v4();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let start = Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap();
+
+        for hour in 0..10 {
+            let actor = if hour % 2 == 0 { alice } else { bob };
+            let action = if hour % 3 == 0 { AuditAction::Export } else { AuditAction::Update };
+            let mut entry = AuditLog::new(tenant, "employee", Uuid::new_v4(), action, Some(actor), ActorType::User);
+            entry.created_at = start + Duration::hours(hour);
+            store.append(entry);
+        }
+        (store, alice, bob, start)
+    }
+
+    #[test]
+    fn test_filter_by_actor() {
+        let (store, alice, _, _) = seeded();
+        let filter = AuditFilter { actor_id: Some(alice), ..Default::default() };
+
+        let page = store.query(&filter, None, 100);
+        assert_eq!(page.entries.len(), 5);
+        assert!(page.entries.iter().all(|e| e.actor_id == Some(alice)));
+        assert!(page.entries.windows(2).all(|w| w[0].created_at <= w[1].created_at));
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_filter_by_time_window() {
+        let (store, _, bob, start) = seeded();
+        let filter = AuditFilter {
+            from: Some(start + Duration::hours(2)),
+            to: Some(start + Duration::hours(6)),
+            ..Default::default()
+        };
+
+        let hours: Vec<i64> = store
+            .query(&filter, None, 100)
+            .entries
+            .iter()
+            .map(|e| (e.created_at - start).num_hours())
+            .collect();
+        assert_eq!(hours, [2, 3, 4, 5]);
+
+        let bob_exports = AuditFilter { actor_id: Some(bob), action: Some(AuditAction::Export), ..filter };
+        assert_eq!(store.query(&bob_exports, None, 100).entries.len(), 1);
+    }
+
+    #[test]
+    fn test_pagination_is_stable() {
+        let (store, _, _, _) = seeded();
+        let filter = AuditFilter::default();
+
+        let first = store.query(&filter, None, 4);
+        let cursor = AuditCursor::decode(first.next_cursor.as_deref().unwrap()).unwrap();
+
+        // A late-arriving entry older than the cursor doesn't shift later pages
+        let mut late = first.entries[0].clone();
+        late.id = Uuid::new_v4();
+        late.created_at -= Duration::minutes(30);
+        store.append(late);
+
+        let second = store.query(&filter, Some(cursor), 4);
+        assert_eq!(second.entries[0].created_at, first.entries[3].created_at + Duration::hours(1));
+        let third = store.query(&filter, AuditCursor::decode(second.next_cursor.as_deref().unwrap()), 4);
+        assert_eq!(third.entries.len(), 2);
+        assert!(third.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let (store, alice, _, _) = seeded();
+        let mut entry = AuditLog::new(Uuid::new_v4(), "payroll_run", Uuid::new_v4(), AuditAction::Update, Some(alice), ActorType::User)
+            .with_changes(serde_json::json!({"status": "draft"}), serde_json::json!({"status": "approved"}));
+        entry.created_at = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        store.append(entry);
+
+        let filter = AuditFilter { entity_type: Some("payroll_run".to_string()), ..Default::default() };
+        let csv: String = AuditCsvExport::new(store, filter).collect();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(format!("{}\n", lines[0]), AUDIT_CSV_HEADER);
+        assert!(lines[1].contains(",user,"));
+        assert!(lines[1].contains(",update,payroll_run,"));
+        assert!(lines[1].ends_with(r#""{""before"":{""status"":""draft""},""after"":{""status"":""approved""}}""#));
+    }
+}
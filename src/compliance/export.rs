@@ -0,0 +1,532 @@
+//! Personal Data Exports
+//!
+//! Access and portability requests are answered with a bundle of everything
+//! held on the data subject. Bundles are sealed with AES-256-GCM before they
+//! leave the service, either to the requester's X25519 public key or under a
+//! passphrase; a plaintext bundle has to be asked for explicitly. Bundles
+//! over the inline limit are collected once, with an expiring download
+//! token bound to the tenant and user who asked for the export, instead of
+//! riding on the response.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use dashmap::DashMap;
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+
+use crate::domain::aggregates::{AddressInfo, EmergencyContact, Employee, EmploymentStatus, StatusChangeRecord};
+use super::models::{DataSubjectRequest, DsrType};
+
+/// PBKDF2-HMAC-SHA256 rounds for passphrase-sealed bundles
+pub const PASSPHRASE_ITERATIONS: u32 = 600_000;
+
+const HKDF_INFO: &[u8] = b"sase-hr personal data export v1";
+
+/// Export sealing, storage, and download errors
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ExportError {
+    #[error("Choose an encryption for the export, or opt in to plaintext")]
+    EncryptionRequired,
+
+    #[error("{0:?} requests are not answered with an export")]
+    NotAnExportRequest(DsrType),
+
+    #[error("Invalid X25519 public key")]
+    InvalidPublicKey,
+
+    #[error("Export could not be decrypted with the key given")]
+    DecryptionFailed,
+
+    #[error("Download token not found or already used")]
+    TokenNotFound,
+
+    #[error("Download token expired at {0}")]
+    TokenExpired(DateTime<Utc>),
+}
+
+/// How the requester wants the bundle protected
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ExportEncryption {
+    /// Seal to the requester's X25519 public key, hex encoded
+    PublicKey { public_key: String },
+    Passphrase { passphrase: String },
+    /// Explicit opt-in to an unencrypted bundle
+    Plaintext,
+}
+
+/// Keeps the passphrase out of logs
+impl fmt::Debug for ExportEncryption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PublicKey { public_key } => f.debug_struct("PublicKey").field("public_key", public_key).finish(),
+            Self::Passphrase { .. } => f.debug_struct("Passphrase").field("passphrase", &"[REDACTED]").finish(),
+            Self::Plaintext => f.write_str("Plaintext"),
+        }
+    }
+}
+
+/// Key that opens a sealed bundle
+#[derive(Clone)]
+pub enum ExportKey {
+    SecretKey(StaticSecret),
+    Passphrase(String),
+}
+
+/// Bundle contents as stored and sent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "scheme", rename_all = "snake_case")]
+pub enum SealedPayload {
+    /// X25519 with an ephemeral sender key, HKDF-SHA256, AES-256-GCM
+    X25519 { ephemeral_public_key: [u8; 32], nonce: [u8; 12], ciphertext: Vec<u8> },
+    /// PBKDF2-HMAC-SHA256, AES-256-GCM
+    Passphrase { salt: [u8; 16], iterations: u32, nonce: [u8; 12], ciphertext: Vec<u8> },
+    Plaintext { content: Vec<u8> },
+}
+
+impl SealedPayload {
+    pub fn seal(content: &[u8], encryption: &ExportEncryption) -> Result<Self, ExportError> {
+        Self::seal_with_iterations(content, encryption, PASSPHRASE_ITERATIONS)
+    }
+
+    /// Seal with a chosen PBKDF2 cost for passphrases; the cost travels
+    /// with the bundle
+    pub fn seal_with_iterations(content: &[u8], encryption: &ExportEncryption, iterations: u32) -> Result<Self, ExportError> {
+        match encryption {
+            ExportEncryption::PublicKey { public_key } => {
+                let bytes: [u8; 32] = hex::decode(public_key)
+                    .ok()
+                    .and_then(|b| b.try_into().ok())
+                    .ok_or(ExportError::InvalidPublicKey)?;
+                let recipient = PublicKey::from(bytes);
+                let ephemeral = EphemeralSecret::random_from_rng(OsRng);
+                let ephemeral_public_key = PublicKey::from(&ephemeral).to_bytes();
+                let shared = ephemeral.diffie_hellman(&recipient);
+                let key = hkdf_key(shared.as_bytes(), &ephemeral_public_key, &bytes);
+                let (nonce, ciphertext) = encrypt(&key, content);
+                Ok(Self::X25519 { ephemeral_public_key, nonce, ciphertext })
+            }
+            ExportEncryption::Passphrase { passphrase } => {
+                let mut salt = [0u8; 16];
+                OsRng.fill_bytes(&mut salt);
+                let key = passphrase_key(passphrase, &salt, iterations);
+                let (nonce, ciphertext) = encrypt(&key, content);
+                Ok(Self::Passphrase { salt, iterations, nonce, ciphertext })
+            }
+            ExportEncryption::Plaintext => Ok(Self::Plaintext { content: content.to_vec() }),
+        }
+    }
+
+    /// Decrypt with `key`; plaintext bundles open without one
+    pub fn open(&self, key: Option<&ExportKey>) -> Result<Vec<u8>, ExportError> {
+        match (self, key) {
+            (Self::Plaintext { content }, _) => Ok(content.clone()),
+            (Self::X25519 { ephemeral_public_key, nonce, ciphertext }, Some(ExportKey::SecretKey(secret))) => {
+                let shared = secret.diffie_hellman(&PublicKey::from(*ephemeral_public_key));
+                let recipient = PublicKey::from(secret).to_bytes();
+                decrypt(&hkdf_key(shared.as_bytes(), ephemeral_public_key, &recipient), nonce, ciphertext)
+            }
+            (Self::Passphrase { salt, iterations, nonce, ciphertext }, Some(ExportKey::Passphrase(passphrase))) => {
+                decrypt(&passphrase_key(passphrase, salt, *iterations), nonce, ciphertext)
+            }
+            _ => Err(ExportError::DecryptionFailed),
+        }
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        !matches!(self, Self::Plaintext { .. })
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::X25519 { ciphertext, .. } | Self::Passphrase { ciphertext, .. } => ciphertext.len(),
+            Self::Plaintext { content } => content.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn hkdf_key(shared: &[u8], ephemeral_public_key: &[u8; 32], recipient: &[u8; 32]) -> [u8; 32] {
+    let salt = [ephemeral_public_key.as_slice(), recipient.as_slice()].concat();
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(HKDF_INFO, &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+fn passphrase_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    key
+}
+
+fn encrypt(key: &[u8; 32], content: &[u8]) -> ([u8; 12], Vec<u8>) {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher.encrypt(&nonce, content).expect("AES-GCM encryption of an in-memory buffer");
+    (nonce.into(), ciphertext)
+}
+
+fn decrypt(key: &[u8; 32], nonce: &[u8; 12], ciphertext: &[u8]) -> Result<Vec<u8>, ExportError> {
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| ExportError::DecryptionFailed)
+}
+
+/// Personal data held on one employee record
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubjectRecord {
+    pub employee_id: String,
+    pub employee_number: String,
+    pub first_name: String,
+    pub middle_name: Option<String>,
+    pub last_name: String,
+    pub preferred_name: Option<String>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub gender: Option<String>,
+    pub personal_email: Option<String>,
+    pub phone: Option<String>,
+    pub address: Option<AddressInfo>,
+    pub job_title: String,
+    pub work_email: String,
+    pub work_phone: Option<String>,
+    pub department_id: Option<String>,
+    pub hire_date: Option<NaiveDate>,
+    pub termination_date: Option<NaiveDate>,
+    pub status: EmploymentStatus,
+    pub status_history: Vec<StatusChangeRecord>,
+    pub emergency_contacts: Vec<EmergencyContact>,
+    pub custom_fields: HashMap<String, serde_json::Value>,
+}
+
+impl From<&Employee> for SubjectRecord {
+    fn from(employee: &Employee) -> Self {
+        let personal = employee.personal();
+        let employment = employee.employment();
+        Self {
+            employee_id: employee.id().to_string(),
+            employee_number: employee.employee_id().to_string(),
+            first_name: personal.first_name.clone(),
+            middle_name: personal.middle_name.clone(),
+            last_name: personal.last_name.clone(),
+            preferred_name: personal.preferred_name.clone(),
+            date_of_birth: personal.date_of_birth,
+            gender: personal.gender.clone(),
+            personal_email: personal.personal_email.clone(),
+            phone: personal.phone.clone(),
+            address: personal.address.clone(),
+            job_title: employment.job_title.clone(),
+            work_email: employment.work_email.clone(),
+            work_phone: employment.work_phone.clone(),
+            department_id: employment.department_id.clone(),
+            hire_date: employment.hire_date,
+            termination_date: employment.termination_date,
+            status: *employee.status(),
+            status_history: employee.status_history().to_vec(),
+            emergency_contacts: employee.emergency_contacts().to_vec(),
+            custom_fields: employee.custom_fields().clone(),
+        }
+    }
+}
+
+/// Everything held on a data subject, as serialized into the bundle
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubjectExport {
+    pub request_id: Uuid,
+    pub subject_email: String,
+    pub generated_at: DateTime<Utc>,
+    pub records: Vec<SubjectRecord>,
+}
+
+/// A data subject's export, ready to hand over
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportBundle {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub request_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub payload: SealedPayload,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExportBundle {
+    /// Seal `content` for an access or portability request. `encryption`
+    /// is what the requester chose; `None` is refused rather than sent in
+    /// plaintext.
+    pub fn for_request(
+        request: &DataSubjectRequest,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        content: &[u8],
+        encryption: Option<&ExportEncryption>,
+    ) -> Result<Self, ExportError> {
+        if !matches!(request.request_type, DsrType::Access | DsrType::Portability) {
+            return Err(ExportError::NotAnExportRequest(request.request_type));
+        }
+        let encryption = encryption.ok_or(ExportError::EncryptionRequired)?;
+        Ok(Self {
+            id: Uuid::new_v4(),
+            tenant_id: request.tenant_id,
+            request_id: request.id,
+            file_name: file_name.into(),
+            content_type: content_type.into(),
+            payload: SealedPayload::seal(content, encryption)?,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Seal the subject's employee records as a JSON bundle for `request`
+    pub fn for_subject(
+        request: &DataSubjectRequest,
+        records: &[&Employee],
+        encryption: Option<&ExportEncryption>,
+    ) -> Result<Self, ExportError> {
+        let export = SubjectExport {
+            request_id: request.id,
+            subject_email: request.subject_email.clone(),
+            generated_at: Utc::now(),
+            records: records.iter().map(|e| SubjectRecord::from(*e)).collect(),
+        };
+        let content = serde_json::to_vec_pretty(&export).expect("subject export serializes to JSON");
+        let file_name = format!("personal-data-{}.json", request.id);
+        Self::for_request(request, file_name, "application/json", &content, encryption)
+    }
+}
+
+/// One-time download handed out in place of a large bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadToken {
+    pub token: String,
+    pub bundle_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// How a bundle reaches the requester
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "delivery", rename_all = "snake_case")]
+pub enum ExportDelivery {
+    Inline { bundle: ExportBundle },
+    Download { download: DownloadToken },
+}
+
+#[derive(Debug, Clone)]
+struct PendingDownload {
+    bundle: ExportBundle,
+    /// User the token was issued to; only they can redeem it, and only in
+    /// the bundle's tenant
+    requester_id: Uuid,
+    expires_at: DateTime<Utc>,
+}
+
+/// Bundles awaiting download, keyed by a hash of their token
+#[derive(Debug, Clone)]
+pub struct ExportDownloads {
+    // In real implementation, backed by the export_downloads table and object storage
+    pending: Arc<DashMap<String, PendingDownload>>,
+    /// Largest bundle, in bytes, returned inline
+    pub inline_limit: usize,
+    pub ttl: Duration,
+}
+
+impl Default for ExportDownloads {
+    /// Bundles over 1 MiB are downloaded, within 24 hours
+    fn default() -> Self {
+        Self { pending: Arc::new(DashMap::new()), inline_limit: 1024 * 1024, ttl: Duration::hours(24) }
+    }
+}
+
+impl ExportDownloads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_inline_limit(mut self, inline_limit: usize) -> Self {
+        self.inline_limit = inline_limit;
+        self
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Return small bundles inline and park large ones behind a token
+    /// for `requester_id`
+    pub fn deliver(&self, bundle: ExportBundle, requester_id: Uuid) -> ExportDelivery {
+        if bundle.payload.len() <= self.inline_limit {
+            ExportDelivery::Inline { bundle }
+        } else {
+            ExportDelivery::Download { download: self.issue(bundle, requester_id, Utc::now()) }
+        }
+    }
+
+    /// Park `bundle` behind a fresh token for `requester_id`, valid for the
+    /// TTL from `now`
+    pub fn issue(&self, bundle: ExportBundle, requester_id: Uuid, now: DateTime<Utc>) -> DownloadToken {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let token = hex::encode(bytes);
+        let expires_at = now + self.ttl;
+        let bundle_id = bundle.id;
+        self.pending.insert(token_key(&token), PendingDownload { bundle, requester_id, expires_at });
+        DownloadToken { token, bundle_id, expires_at }
+    }
+
+    /// Hand over the bundle behind `token` to `requester_id` of `tenant_id`
+    /// as of `now`. A token works once, and only for the user it was issued
+    /// to; anyone else is told it doesn't exist and leaves it in place. An
+    /// expired token is discarded along with its bundle.
+    pub fn redeem(
+        &self,
+        token: &str,
+        tenant_id: Uuid,
+        requester_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<ExportBundle, ExportError> {
+        let (_, pending) = self
+            .pending
+            .remove_if(&token_key(token), |_, p| p.bundle.tenant_id == tenant_id && p.requester_id == requester_id)
+            .ok_or(ExportError::TokenNotFound)?;
+        if now >= pending.expires_at {
+            return Err(ExportError::TokenExpired(pending.expires_at));
+        }
+        Ok(pending.bundle)
+    }
+
+    /// Drop bundles whose tokens expired by `now`; returns how many
+    pub fn purge_expired(&self, now: DateTime<Utc>) -> usize {
+        let before = self.pending.len();
+        self.pending.retain(|_, p| now < p.expires_at);
+        before - self.pending.len()
+    }
+}
+
+/// Tokens are stored hashed so the table alone can't be used to download
+fn token_key(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn portability_request() -> DataSubjectRequest {
+        DataSubjectRequest::new(Uuid::new_v4(), DsrType::Portability, "ada@example.com".into(), None)
+    }
+
+    const CONTENT: &[u8] = br#"{"first_name":"Ada","bank_account":"0123456789"}"#;
+
+    #[test]
+    fn test_bundle_round_trips_with_key() {
+        let request = portability_request();
+        let secret = StaticSecret::random_from_rng(OsRng);
+        let public_key = hex::encode(PublicKey::from(&secret).to_bytes());
+
+        let sealed = ExportBundle::for_request(
+            &request,
+            "export.json",
+            "application/json",
+            CONTENT,
+            Some(&ExportEncryption::PublicKey { public_key }),
+        )
+        .unwrap();
+        assert!(sealed.payload.is_encrypted());
+        assert_eq!(sealed.payload.open(Some(&ExportKey::SecretKey(secret))).unwrap(), CONTENT);
+        let stranger = StaticSecret::random_from_rng(OsRng);
+        assert_eq!(sealed.payload.open(Some(&ExportKey::SecretKey(stranger))), Err(ExportError::DecryptionFailed));
+
+        // Full-cost PBKDF2 is too slow for an unoptimized test build
+        let encryption = ExportEncryption::Passphrase { passphrase: "correct horse".into() };
+        let sealed = SealedPayload::seal_with_iterations(CONTENT, &encryption, 1_000).unwrap();
+        assert!(matches!(sealed, SealedPayload::Passphrase { iterations: 1_000, .. }));
+        assert_eq!(sealed.open(Some(&ExportKey::Passphrase("correct horse".into()))).unwrap(), CONTENT);
+        assert_eq!(sealed.open(Some(&ExportKey::Passphrase("wrong".into()))), Err(ExportError::DecryptionFailed));
+        assert_eq!(sealed.open(None), Err(ExportError::DecryptionFailed));
+    }
+
+    #[test]
+    fn test_plaintext_needs_explicit_opt_in() {
+        let request = portability_request();
+        assert_eq!(
+            ExportBundle::for_request(&request, "export.json", "application/json", CONTENT, None),
+            Err(ExportError::EncryptionRequired)
+        );
+        let plain = ExportBundle::for_request(
+            &request,
+            "export.json",
+            "application/json",
+            CONTENT,
+            Some(&ExportEncryption::Plaintext),
+        )
+        .unwrap();
+        assert!(!plain.payload.is_encrypted());
+
+        let erasure = DataSubjectRequest::new(Uuid::new_v4(), DsrType::Erasure, "ada@example.com".into(), None);
+        assert_eq!(
+            ExportBundle::for_request(&erasure, "export.json", "application/json", CONTENT, Some(&ExportEncryption::Plaintext)),
+            Err(ExportError::NotAnExportRequest(DsrType::Erasure))
+        );
+    }
+
+    #[test]
+    fn test_download_token_is_one_time_and_expires() {
+        let request = portability_request();
+        let bundle = ExportBundle::for_request(&request, "export.json", "application/json", CONTENT, Some(&ExportEncryption::Plaintext)).unwrap();
+        let downloads = ExportDownloads::new().with_inline_limit(16).with_ttl(Duration::hours(1));
+        let (tenant_id, requester) = (request.tenant_id, Uuid::new_v4());
+        let now = Utc::now();
+
+        let token = downloads.issue(bundle.clone(), requester, now);
+        assert_eq!(downloads.redeem(&token.token, tenant_id, requester, now + Duration::minutes(59)), Ok(bundle.clone()));
+        assert_eq!(downloads.redeem(&token.token, tenant_id, requester, now), Err(ExportError::TokenNotFound));
+
+        let token = downloads.issue(bundle.clone(), requester, now);
+        assert_eq!(
+            downloads.redeem(&token.token, tenant_id, requester, now + Duration::hours(1)),
+            Err(ExportError::TokenExpired(token.expires_at))
+        );
+        assert_eq!(downloads.redeem(&token.token, tenant_id, requester, now), Err(ExportError::TokenNotFound));
+
+        // Larger than the inline limit, so handed out by token
+        assert!(matches!(downloads.deliver(bundle.clone(), requester), ExportDelivery::Download { .. }));
+        assert_eq!(downloads.purge_expired(Utc::now() + Duration::hours(2)), 1);
+        let roomy = ExportDownloads::new();
+        assert_eq!(roomy.deliver(bundle.clone(), requester), ExportDelivery::Inline { bundle });
+    }
+
+    #[test]
+    fn test_download_token_bound_to_tenant_and_requester() {
+        let request = portability_request();
+        let bundle = ExportBundle::for_request(&request, "export.json", "application/json", CONTENT, Some(&ExportEncryption::Plaintext)).unwrap();
+        let downloads = ExportDownloads::new();
+        let (tenant_id, requester) = (request.tenant_id, Uuid::new_v4());
+        let now = Utc::now();
+
+        let token = downloads.issue(bundle.clone(), requester, now);
+        assert_eq!(downloads.redeem(&token.token, tenant_id, Uuid::new_v4(), now), Err(ExportError::TokenNotFound));
+        assert_eq!(downloads.redeem(&token.token, Uuid::new_v4(), requester, now), Err(ExportError::TokenNotFound));
+        // Failed attempts don't use the token up
+        assert_eq!(downloads.redeem(&token.token, tenant_id, requester, now), Ok(bundle));
+    }
+
+    #[test]
+    fn test_passphrase_redacted_from_debug() {
+        let encryption = ExportEncryption::Passphrase { passphrase: "correct horse".into() };
+        let debug = format!("{:?}", encryption);
+        assert!(!debug.contains("correct horse"));
+        assert!(debug.contains("REDACTED"));
+    }
+}
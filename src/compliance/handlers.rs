@@ -0,0 +1,372 @@
+//! Compliance API Handlers
+//!
+//! Audit log search and CSV export for investigators, and data subject
+//! requests answered with a sealed export of the subject's records.
+
+use std::convert::Infallible;
+use std::sync::{Arc, RwLock};
+
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::auth::{AuthContext, Permission};
+use crate::domain::services::EmployeeService;
+use super::audit::{AuditCsvExport, AuditCursor, AuditFilter, AuditLogStore};
+use super::export::{ExportBundle, ExportDownloads, ExportEncryption, ExportError};
+use super::models::{AuditAction, DataSubjectRequest, DsrStatus, DsrType};
+
+/// API Response wrapper
+#[derive(Debug, Serialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: Option<T>,
+    pub error: Option<String>,
+}
+
+impl<T: Serialize> ApiResponse<T> {
+    pub fn success(data: T) -> Self {
+        Self { success: true, data: Some(data), error: None }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self { success: false, data: None, error: Some(message.into()) }
+    }
+}
+
+/// Shared compliance state
+#[derive(Clone, Default)]
+pub struct ComplianceAppState {
+    pub audit_logs: AuditLogStore,
+    // In real implementation, backed by the data_subject_requests table
+    pub dsr_requests: Arc<DashMap<Uuid, DataSubjectRequest>>,
+    /// Employee records exports are gathered from, shared with the employee API
+    pub employees: Arc<RwLock<EmployeeService>>,
+    pub exports: ExportDownloads,
+}
+
+/// Raise a data subject request
+#[derive(Debug, Deserialize)]
+pub struct CreateDsrRequest {
+    pub request_type: DsrType,
+    pub subject_email: String,
+    pub description: Option<String>,
+}
+
+/// Answer an access or portability request
+#[derive(Debug, Deserialize)]
+pub struct ExportDsrRequest {
+    /// Required; `plaintext` has to be chosen explicitly
+    pub encryption: Option<ExportEncryption>,
+}
+
+/// Collect a bundle too large to return inline
+#[derive(Debug, Deserialize)]
+pub struct DownloadExportRequest {
+    pub token: String,
+}
+
+/// Audit log query parameters
+#[derive(Debug, Deserialize)]
+pub struct AuditLogQuery {
+    pub actor_id: Option<Uuid>,
+    pub entity_type: Option<String>,
+    pub action: Option<AuditAction>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl AuditLogQuery {
+    /// Filter over the caller's own tenant's entries
+    fn filter(&self, tenant_id: Uuid) -> AuditFilter {
+        AuditFilter {
+            tenant_id: Some(tenant_id),
+            actor_id: self.actor_id,
+            entity_type: self.entity_type.clone(),
+            action: self.action,
+            from: self.from,
+            to: self.to,
+        }
+    }
+}
+
+/// Search audit logs
+///
+/// GET /api/v1/audit-logs?actor_id=&entity_type=&action=&from=&to=&cursor=&limit=
+pub async fn list_audit_logs(
+    State(state): State<ComplianceAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Query(query): Query<AuditLogQuery>,
+) -> Response {
+    if !auth.has_permission(Permission::ComplianceView) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to view audit logs"))).into_response();
+    }
+    let cursor = match query.cursor.as_deref().map(AuditCursor::decode) {
+        Some(None) => {
+            return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error("Invalid cursor"))).into_response();
+        }
+        Some(cursor) => cursor,
+        None => None,
+    };
+
+    let page = state.audit_logs.query(&query.filter(auth.tenant_id), cursor, query.limit.unwrap_or(100));
+    Json(ApiResponse::success(page)).into_response()
+}
+
+/// Export matching audit logs as CSV, streamed a page at a time
+///
+/// GET /api/v1/audit-logs/export?actor_id=&entity_type=&action=&from=&to=
+pub async fn export_audit_logs(
+    State(state): State<ComplianceAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Query(query): Query<AuditLogQuery>,
+) -> Response {
+    if !auth.has_permission(Permission::ComplianceView) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to export audit logs"))).into_response();
+    }
+    let export = AuditCsvExport::new(state.audit_logs.clone(), query.filter(auth.tenant_id));
+    let body = Body::from_stream(futures_util::stream::iter(export.map(Ok::<_, Infallible>)));
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "attachment; filename=\"audit-logs.csv\""),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// Record a data subject request for the caller's tenant
+///
+/// POST /api/v1/dsr
+pub async fn create_dsr(
+    State(state): State<ComplianceAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Json(request): Json<CreateDsrRequest>,
+) -> Response {
+    if !auth.has_permission(Permission::ComplianceAdmin) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to raise data subject requests"))).into_response();
+    }
+    let dsr = DataSubjectRequest::new(auth.tenant_id, request.request_type, request.subject_email, request.description);
+    state.dsr_requests.insert(dsr.id, dsr.clone());
+    (StatusCode::CREATED, Json(ApiResponse::success(dsr))).into_response()
+}
+
+/// Answer an access or portability request with a sealed bundle of the
+/// subject's records: inline when small, otherwise behind a download
+/// token only the caller can redeem
+///
+/// POST /api/v1/dsr/:id/export
+pub async fn export_dsr(
+    State(state): State<ComplianceAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<ExportDsrRequest>,
+) -> Response {
+    if !auth.has_permission(Permission::ComplianceAdmin) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to answer data subject requests"))).into_response();
+    }
+    let Some(dsr) = state.dsr_requests.get(&id).map(|r| r.clone()).filter(|r| r.tenant_id == auth.tenant_id) else {
+        return (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Data subject request not found"))).into_response();
+    };
+    if matches!(dsr.status, DsrStatus::Completed | DsrStatus::Rejected) {
+        return (StatusCode::CONFLICT, Json(ApiResponse::<()>::error(format!("Request already {:?}", dsr.status).to_lowercase())))
+            .into_response();
+    }
+
+    let bundle = {
+        let employees = state.employees.read().expect("employee lock poisoned");
+        let records = employees.subject_records(auth.tenant_id, &dsr.subject_email);
+        ExportBundle::for_subject(&dsr, &records, request.encryption.as_ref())
+    };
+    let bundle = match bundle {
+        Ok(bundle) => bundle,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(e.to_string()))).into_response(),
+    };
+    let delivery = state.exports.deliver(bundle, auth.user_id);
+
+    if let Some(mut stored) = state.dsr_requests.get_mut(&id) {
+        let now = Utc::now();
+        stored.status = DsrStatus::Completed;
+        stored.processed_by = Some(auth.user_id);
+        stored.processed_at = Some(now);
+        stored.response = Some("Personal data export delivered".to_string());
+        stored.updated_at = now;
+    }
+    Json(ApiResponse::success(delivery)).into_response()
+}
+
+/// Collect an export bundle by its one-time token. Only the user the token
+/// was issued to can redeem it.
+///
+/// POST /api/v1/exports/download
+pub async fn download_export(
+    State(state): State<ComplianceAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Json(request): Json<DownloadExportRequest>,
+) -> Response {
+    if !auth.has_permission(Permission::ComplianceAdmin) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to download exports"))).into_response();
+    }
+    match state.exports.redeem(&request.token, auth.tenant_id, auth.user_id, Utc::now()) {
+        Ok(bundle) => Json(ApiResponse::success(bundle)).into_response(),
+        Err(e @ ExportError::TokenExpired(_)) => (StatusCode::GONE, Json(ApiResponse::<()>::error(e.to_string()))).into_response(),
+        Err(e) => (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error(e.to_string()))).into_response(),
+    }
+}
+
+/// Compliance routes
+pub fn compliance_routes() -> axum::Router<ComplianceAppState> {
+    use axum::routing::{get, post};
+
+    axum::Router::new()
+        .route("/audit-logs", get(list_audit_logs))
+        .route("/audit-logs/export", get(export_audit_logs))
+        .route("/dsr", post(create_dsr))
+        .route("/dsr/:id/export", post(export_dsr))
+        .route("/exports/download", post(download_export))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    use crate::auth::Role;
+    use crate::compliance::{ActorType, AuditLog};
+
+    fn auth(tenant_id: Uuid, role: Role) -> AuthContext {
+        AuthContext {
+            user_id: Uuid::new_v4(),
+            tenant_id,
+            employee_id: None,
+            role,
+            permissions: role.permissions(),
+            department_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_logs_scoped_to_caller_tenant() {
+        let state = ComplianceAppState::default();
+        let (ours, theirs) = (Uuid::new_v4(), Uuid::new_v4());
+        for tenant_id in [ours, theirs, theirs] {
+            let entry = AuditLog::new(tenant_id, "employee", Uuid::new_v4(), AuditAction::Update, None, ActorType::User);
+            state.audit_logs.append(entry);
+        }
+        let get = |uri: &str, caller: AuthContext| {
+            compliance_routes()
+                .layer(Extension(caller))
+                .with_state(state.clone())
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        };
+
+        let response = get("/audit-logs", auth(ours, Role::HrManager)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let entries = page["data"]["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["tenant_id"], ours.to_string());
+
+        let response = get("/audit-logs/export", auth(ours, Role::HrManager)).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(String::from_utf8(body.to_vec()).unwrap().lines().count(), 2);
+
+        let response = get("/audit-logs", auth(ours, Role::Employee)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = get("/audit-logs/export", auth(ours, Role::Employee)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_portability_request_exported_to_requester_only() {
+        use crate::domain::services::CreateEmployeeRequest;
+        use crate::domain::value_objects::EmployeeId;
+
+        let tenant_id = Uuid::new_v4();
+        let mut employees = EmployeeService::new();
+        let hire = CreateEmployeeRequest {
+            first_name: "Ada".into(),
+            last_name: "Obi".into(),
+            work_email: "ada@company.com".into(),
+            job_title: "Analyst".into(),
+            hire_date: chrono::NaiveDate::from_ymd_opt(2020, 2, 3).unwrap(),
+            tax_id: None,
+        };
+        employees.create_employee(tenant_id, EmployeeId::new(2020, 1), hire, false).unwrap();
+        let state = ComplianceAppState {
+            employees: Arc::new(RwLock::new(employees)),
+            // Every bundle goes by token
+            exports: ExportDownloads::new().with_inline_limit(0),
+            ..ComplianceAppState::default()
+        };
+        let post = |uri: String, caller: AuthContext, body: serde_json::Value| {
+            compliance_routes()
+                .layer(Extension(caller))
+                .with_state(state.clone())
+                .oneshot(
+                    Request::post(uri)
+                        .header(header::CONTENT_TYPE, "application/json")
+                        .body(Body::from(body.to_string()))
+                        .unwrap(),
+                )
+        };
+        let json = |response: Response| async {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+        let officer = auth(tenant_id, Role::HrManager);
+
+        let body = serde_json::json!({ "request_type": "portability", "subject_email": "Ada@Company.com" });
+        let response = post("/dsr".into(), officer.clone(), body.clone()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let dsr_id = json(response).await["data"]["id"].as_str().unwrap().to_string();
+        let response = post("/dsr".into(), auth(tenant_id, Role::Employee), body).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // No encryption chosen, then another tenant's officer
+        let export = format!("/dsr/{}/export", dsr_id);
+        let response = post(export.clone(), officer.clone(), serde_json::json!({})).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let plaintext = serde_json::json!({ "encryption": { "mode": "plaintext" } });
+        let response = post(export.clone(), auth(Uuid::new_v4(), Role::HrManager), plaintext.clone()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = post(export.clone(), officer.clone(), plaintext.clone()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let delivery = json(response).await;
+        assert_eq!(delivery["data"]["delivery"], "download");
+        let token = serde_json::json!({ "token": delivery["data"]["download"]["token"] });
+        let dsr = state.dsr_requests.get(&dsr_id.parse().unwrap()).unwrap().clone();
+        assert_eq!(dsr.status, DsrStatus::Completed);
+        assert_eq!(dsr.processed_by, Some(officer.user_id));
+        let response = post(export, officer.clone(), plaintext).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        // A colleague in the same tenant can't collect it
+        let response = post("/exports/download".into(), auth(tenant_id, Role::HrManager), token.clone()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = post("/exports/download".into(), officer.clone(), token.clone()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bundle: ExportBundle = serde_json::from_value(json(response).await["data"].clone()).unwrap();
+        let content: serde_json::Value = serde_json::from_slice(&bundle.payload.open(None).unwrap()).unwrap();
+        assert_eq!(content["records"][0]["work_email"], "ada@company.com");
+        assert_eq!(content["records"][0]["first_name"], "Ada");
+        let response = post("/exports/download".into(), officer, token).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -5,8 +5,25 @@
 
 pub mod models;
 pub mod global_compliance;
+pub mod audit;
+pub mod export;
+pub mod retention;
+pub mod working_time;
+pub mod overtime;
+pub mod handlers;
 
 pub use models::*;
+pub use audit::{AuditCsvExport, AuditCursor, AuditFilter, AuditLogStore, AuditPage};
+pub use export::{
+    DownloadToken, ExportBundle, ExportDelivery, ExportDownloads, ExportEncryption, ExportError, ExportKey, SealedPayload,
+    SubjectExport, SubjectRecord,
+};
+pub use working_time::{ComplianceCheck, WorkingTimeRules, WorkingTimeViolation};
+pub use overtime::{
+    enforce_overtime_caps, CapEnforcement, OvertimeCapKind, OvertimeCapStatus, OvertimeCaps, OvertimeFinding,
+    OvertimeLedger, OvertimeReview, OvertimeRules,
+};
+pub use retention::{LegalHold, RetainedData, RetentionAction, RetentionJob, RetentionPolicy, RetentionReport, RetentionRule};
 pub use global_compliance::{
     PolicyEngine, GdprEvaluator, DataResidencyEngine, DataClassifier,
     ComplianceFramework, DataCategory, LegalBasis, ResidencyRequirement,
@@ -16,6 +16,8 @@ pub enum AuditAction {
     Export,
     Login,
     Logout,
+    /// Duplicate records folded into one
+    Merge,
 }
 
 /// Actor type
@@ -0,0 +1,437 @@
+//! Overtime Caps
+//!
+//! Statutory limits on overtime hours per day, week, month, and year.
+//! Overtime is time beyond the country's standard day (a fifth of the
+//! standard week) or standard week; the monthly and annual caps count
+//! weekly overtime, carried over from earlier imports through
+//! `OvertimeLedger`. The ledger holds each shift's overtime under the
+//! shift's id, so importing the same shifts again replaces rather than
+//! adds to what was recorded. Depending on the
+//! country's enforcement, shifts that go over a cap are either accepted and
+//! flagged, or rejected from the timesheet. Approaching the annual cap is
+//! flagged so managers can plan before it's reached.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use chrono::{Datelike, Days, NaiveDate};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::WorkingTime;
+use crate::time::{TimeEntry, TimeImport};
+
+/// What happens to a shift that takes overtime over a cap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapEnforcement {
+    /// Keep the shift and report the breach
+    #[default]
+    Flag,
+    /// Drop the shift from the timesheet
+    Reject,
+}
+
+/// Overtime hour limits for one country; `None` is uncapped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct OvertimeCaps {
+    pub daily: Option<Decimal>,
+    pub weekly: Option<Decimal>,
+    pub monthly: Option<Decimal>,
+    pub annual: Option<Decimal>,
+    pub enforcement: CapEnforcement,
+}
+
+/// Which cap a finding is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OvertimeCapKind {
+    Daily,
+    Weekly,
+    Monthly,
+    Annual,
+}
+
+/// How close overtime came to a cap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OvertimeCapStatus {
+    /// Within the annual cap but past the warning threshold
+    Approaching,
+    /// Over the cap; the shifts were kept
+    Exceeded,
+    /// Shifts that would have gone over the cap were rejected
+    Rejected,
+}
+
+/// Overtime against a cap for one employee and period
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OvertimeFinding {
+    pub employee_id: String,
+    pub employee_number: String,
+    pub cap: OvertimeCapKind,
+    /// The day, the Monday of the week, the first of the month, or 1 January
+    pub period_start: NaiveDate,
+    /// Overtime in the period, including any rejected shifts
+    pub overtime_hours: Decimal,
+    pub limit: Decimal,
+    pub status: OvertimeCapStatus,
+}
+
+/// Timesheet entries after applying the caps
+#[derive(Debug, Clone, Default)]
+pub struct OvertimeReview {
+    pub accepted: Vec<TimeEntry>,
+    pub rejected: Vec<TimeEntry>,
+    pub findings: Vec<OvertimeFinding>,
+    /// Weekly overtime attributed to each accepted shift, by shift id;
+    /// shifts that added none are listed with zero hours
+    pub overtime_by_shift: BTreeMap<String, ShiftOvertime>,
+}
+
+/// Weekly overtime attributed to one shift
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShiftOvertime {
+    pub employee_id: String,
+    /// Clock-in date of the shift
+    pub date: NaiveDate,
+    pub hours: Decimal,
+}
+
+/// Overtime hours already accepted per shift, plus hours carried in from
+/// before shifts were tracked
+#[derive(Debug, Clone, Default)]
+pub struct OvertimeLedger {
+    // In real implementation, aggregated from the time_entries table
+    shifts: Arc<DashMap<String, ShiftOvertime>>,
+    carried: Arc<DashMap<(String, i32), Decimal>>,
+}
+
+impl OvertimeLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overtime for an employee in `year`
+    pub fn hours(&self, employee_id: &str, year: i32) -> Decimal {
+        self.carried.get(&(employee_id.to_string(), year)).map(|h| *h).unwrap_or_default()
+            + self.shift_hours(employee_id, &HashSet::new(), |date| date.year() == year)
+    }
+
+    /// Overtime for an employee in one month, from tracked shifts only
+    pub fn month_hours(&self, employee_id: &str, year: i32, month: u32) -> Decimal {
+        self.shift_hours(employee_id, &HashSet::new(), |date| (date.year(), date.month()) == (year, month))
+    }
+
+    /// Carry in overtime worked before shifts were tracked, e.g. a
+    /// year-to-date figure from a previous system
+    pub fn add(&self, employee_id: &str, year: i32, hours: Decimal) {
+        *self.carried.entry((employee_id.to_string(), year)).or_default() += hours;
+    }
+
+    /// Store the overtime a review accepted, replacing what was recorded
+    /// for the same shifts; rejected shifts are dropped from the ledger
+    pub fn record(&self, review: &OvertimeReview) {
+        for (shift_id, overtime) in &review.overtime_by_shift {
+            self.shifts.insert(shift_id.clone(), overtime.clone());
+        }
+        for entry in &review.rejected {
+            self.shifts.remove(&entry.id());
+        }
+    }
+
+    fn shift_hours<F: Fn(NaiveDate) -> bool>(&self, employee_id: &str, excluding: &HashSet<String>, include: F) -> Decimal {
+        self.shifts
+            .iter()
+            .filter(|s| s.employee_id == employee_id && include(s.date) && !excluding.contains(s.key()))
+            .map(|s| s.hours)
+            .sum()
+    }
+}
+
+/// Overtime caps per country, uncapped by default
+#[derive(Debug, Clone)]
+pub struct OvertimeRules {
+    countries: HashMap<String, OvertimeCaps>,
+    default: OvertimeCaps,
+    /// Share of the annual cap at which an `Approaching` finding is raised
+    pub approaching_fraction: Decimal,
+}
+
+impl Default for OvertimeRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(u64::from(date.weekday().num_days_from_monday()))
+}
+
+fn excess(hours: Decimal, standard: Decimal) -> Decimal {
+    (hours - standard).max(Decimal::ZERO)
+}
+
+impl OvertimeRules {
+    /// Built-in statutory caps
+    pub fn new() -> Self {
+        let caps = |daily: Option<Decimal>, weekly: Option<Decimal>, annual: Option<Decimal>| OvertimeCaps {
+            daily,
+            weekly,
+            monthly: None,
+            annual,
+            enforcement: CapEnforcement::Flag,
+        };
+        let mut rules = Self { countries: HashMap::new(), default: OvertimeCaps::default(), approaching_fraction: dec!(0.9) };
+        // Code du travail L3121-20/L3121-33: 48-hour week, 220-hour default quota
+        rules.set("FR", caps(None, Some(dec!(13)), Some(dec!(220))));
+        // ArbZG §3: 10-hour day
+        rules.set("DE", caps(Some(dec!(2)), None, None));
+        // Labour Law art. 41: 3 hours a day, 36 a month
+        rules.set("CN", OvertimeCaps { monthly: Some(dec!(36)), ..caps(Some(dec!(3)), None, None) });
+        // Labor Standards Act art. 36: 360 hours a year under a standard 36 agreement
+        rules.set("JP", caps(None, None, Some(dec!(360))));
+        // Labor Standards Act art. 53: 12 hours a week
+        rules.set("KR", caps(None, Some(dec!(12)), None));
+        // Government Regulation 35/2021: 4 hours a day, 18 a week
+        rules.set("ID", caps(Some(dec!(4)), Some(dec!(18)), None));
+        // Labour Code 2019 art. 107: 200 hours a year outside listed sectors
+        rules.set("VN", caps(None, None, Some(dec!(200))));
+        rules
+    }
+
+    pub fn set(&mut self, country_code: &str, caps: OvertimeCaps) -> &mut Self {
+        self.countries.insert(country_code.to_ascii_uppercase(), caps);
+        self
+    }
+
+    pub fn for_country(&self, country_code: &str) -> OvertimeCaps {
+        self.countries.get(&country_code.to_ascii_uppercase()).copied().unwrap_or(self.default)
+    }
+
+    /// Apply `country_code`'s caps to shifts, in clock-in order per
+    /// employee, on top of overtime already in `ledger`. Overtime the
+    /// ledger holds for these same shifts is left out, so reviewing a
+    /// re-imported timesheet doesn't count it twice.
+    pub fn review(&self, country_code: &str, entries: &[TimeEntry], ledger: &OvertimeLedger) -> OvertimeReview {
+        let caps = self.for_country(country_code);
+        let standard_week = WorkingTime::for_country(country_code).standard_hours_per_week();
+        let standard_day = standard_week / dec!(5);
+
+        let mut ordered: Vec<&TimeEntry> = entries.iter().collect();
+        ordered.sort_by(|a, b| (&a.employee_id, a.clock_in).cmp(&(&b.employee_id, b.clock_in)));
+
+        let reviewed: HashSet<String> = entries.iter().map(TimeEntry::id).collect();
+        let mut review = OvertimeReview::default();
+        let mut month_overtime: HashMap<(&str, i32, u32), Decimal> = HashMap::new();
+        let mut year_overtime: HashMap<(&str, i32), Decimal> = HashMap::new();
+        let mut day_hours: HashMap<(&str, NaiveDate), Decimal> = HashMap::new();
+        let mut week_hours: HashMap<(&str, NaiveDate), Decimal> = HashMap::new();
+        // (employee, cap, period start) -> (overtime including rejected, rejected)
+        let mut tallies: BTreeMap<(&str, &str, OvertimeCapKind, NaiveDate), (Decimal, bool)> = BTreeMap::new();
+
+        for entry in ordered {
+            let employee = entry.employee_id.as_str();
+            let date = entry.clock_in.date();
+            let (week, year, month) = (week_start(date), date.year(), date.month());
+            let month_start = NaiveDate::from_ymd_opt(year, month, 1).expect("valid month");
+            let year_start = NaiveDate::from_ymd_opt(year, 1, 1).expect("valid year");
+
+            let day_before = day_hours.get(&(employee, date)).copied().unwrap_or_default();
+            let week_before = week_hours.get(&(employee, week)).copied().unwrap_or_default();
+            let weekly_added = excess(week_before + entry.hours, standard_week) - excess(week_before, standard_week);
+            let month_before = ledger.shift_hours(employee, &reviewed, |d| (d.year(), d.month()) == (year, month))
+                + month_overtime.get(&(employee, year, month)).copied().unwrap_or_default();
+            let year_before = ledger.carried.get(&(employee.to_string(), year)).map(|h| *h).unwrap_or_default()
+                + ledger.shift_hours(employee, &reviewed, |d| d.year() == year)
+                + year_overtime.get(&(employee, year)).copied().unwrap_or_default();
+
+            let checks = [
+                (OvertimeCapKind::Daily, date, excess(day_before + entry.hours, standard_day), caps.daily),
+                (OvertimeCapKind::Weekly, week, excess(week_before + entry.hours, standard_week), caps.weekly),
+                (OvertimeCapKind::Monthly, month_start, month_before + weekly_added, caps.monthly),
+                (OvertimeCapKind::Annual, year_start, year_before + weekly_added, caps.annual),
+            ];
+            let over_cap = |overtime: Decimal, limit: Option<Decimal>| limit.is_some_and(|l| overtime > l);
+            let reject = caps.enforcement == CapEnforcement::Reject
+                && checks.iter().any(|(_, _, overtime, limit)| over_cap(*overtime, *limit));
+
+            for (kind, period_start, overtime, limit) in checks {
+                if limit.is_none() {
+                    continue;
+                }
+                let tally = tallies.entry((employee, &entry.employee_number, kind, period_start)).or_default();
+                tally.0 = tally.0.max(overtime);
+                tally.1 |= reject && over_cap(overtime, limit);
+            }
+
+            if reject {
+                review.rejected.push(entry.clone());
+                continue;
+            }
+            *day_hours.entry((employee, date)).or_default() += entry.hours;
+            *week_hours.entry((employee, week)).or_default() += entry.hours;
+            *month_overtime.entry((employee, year, month)).or_default() += weekly_added;
+            *year_overtime.entry((employee, year)).or_default() += weekly_added;
+            review.overtime_by_shift.insert(
+                entry.id(),
+                ShiftOvertime { employee_id: entry.employee_id.clone(), date, hours: weekly_added },
+            );
+            review.accepted.push(entry.clone());
+        }
+
+        for ((employee_id, employee_number, kind, period_start), (overtime_hours, rejected)) in tallies {
+            let limit = match kind {
+                OvertimeCapKind::Daily => caps.daily,
+                OvertimeCapKind::Weekly => caps.weekly,
+                OvertimeCapKind::Monthly => caps.monthly,
+                OvertimeCapKind::Annual => caps.annual,
+            }
+            .expect("tallied caps are set");
+            let status = if rejected {
+                OvertimeCapStatus::Rejected
+            } else if overtime_hours > limit {
+                OvertimeCapStatus::Exceeded
+            } else if kind == OvertimeCapKind::Annual && overtime_hours >= limit * self.approaching_fraction {
+                OvertimeCapStatus::Approaching
+            } else {
+                continue;
+            };
+            review.findings.push(OvertimeFinding {
+                employee_id: employee_id.to_string(),
+                employee_number: employee_number.to_string(),
+                cap: kind,
+                period_start,
+                overtime_hours,
+                limit,
+                status,
+            });
+        }
+        review
+    }
+}
+
+/// Apply overtime caps to an imported timesheet: rejected shifts are
+/// removed from `import.entries`, accepted overtime is added to `ledger`,
+/// and the findings are returned
+pub fn enforce_overtime_caps(
+    import: &mut TimeImport,
+    country_code: &str,
+    rules: &OvertimeRules,
+    ledger: &OvertimeLedger,
+) -> Vec<OvertimeFinding> {
+    let review = rules.review(country_code, &import.entries, ledger);
+    ledger.record(&review);
+    import.entries = review.accepted;
+    review.findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shift(date: NaiveDate, start: u32, hours: u32) -> TimeEntry {
+        let clock_in = date.and_hms_opt(start, 0, 0).unwrap();
+        TimeEntry {
+            employee_id: "id-EMP-1".to_string(),
+            employee_number: "EMP-1".to_string(),
+            clock_in,
+            clock_out: clock_in + chrono::Duration::hours(hours.into()),
+            hours: Decimal::from(hours),
+        }
+    }
+
+    /// Monday-to-Friday shifts of `hours` from 1 April 2024
+    fn week(hours: u32) -> Vec<TimeEntry> {
+        let monday = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        (0..5).map(|d| shift(monday + Days::new(d), 8, hours)).collect()
+    }
+
+    #[test]
+    fn test_weekly_cap_breach_flagged_or_rejected() {
+        // Five 11-hour days in Korea: 55 hours, 15 of them overtime against a 12-hour cap
+        let mut rules = OvertimeRules::new();
+        let review = rules.review("KR", &week(11), &OvertimeLedger::new());
+
+        assert_eq!(review.accepted.len(), 5);
+        assert_eq!(review.findings.len(), 1);
+        let finding = &review.findings[0];
+        assert_eq!((finding.cap, finding.status), (OvertimeCapKind::Weekly, OvertimeCapStatus::Exceeded));
+        assert_eq!(finding.period_start, NaiveDate::from_ymd_opt(2024, 4, 1).unwrap());
+        assert_eq!((finding.overtime_hours, finding.limit), (dec!(15), dec!(12)));
+
+        // Rejecting keeps the week within the cap: Friday would take it to 15
+        rules.set("KR", OvertimeCaps { enforcement: CapEnforcement::Reject, ..rules.for_country("KR") });
+        let mut import = TimeImport { entries: week(11), ..Default::default() };
+        let ledger = OvertimeLedger::new();
+        let findings = enforce_overtime_caps(&mut import, "KR", &rules, &ledger);
+
+        assert_eq!(import.entries.len(), 4);
+        assert_eq!(findings[0].status, OvertimeCapStatus::Rejected);
+        assert_eq!(findings[0].overtime_hours, dec!(15));
+        assert_eq!(ledger.hours("id-EMP-1", 2024), dec!(4));
+
+        // Within the cap nothing is reported
+        assert!(OvertimeRules::new().review("KR", &week(10), &OvertimeLedger::new()).findings.is_empty());
+    }
+
+    #[test]
+    fn test_annual_cap_approached_with_ytd_overtime() {
+        // Vietnam: 200 hours a year, 185 already worked before this week
+        let rules = OvertimeRules::new();
+        let ledger = OvertimeLedger::new();
+        ledger.add("id-EMP-1", 2024, dec!(185));
+
+        // Five 9-hour days: 5 hours' overtime brings the year to 190, 95% of the cap
+        let review = rules.review("VN", &week(9), &ledger);
+        assert_eq!(review.findings.len(), 1);
+        let finding = &review.findings[0];
+        assert_eq!((finding.cap, finding.status), (OvertimeCapKind::Annual, OvertimeCapStatus::Approaching));
+        assert_eq!((finding.overtime_hours, finding.limit), (dec!(190), dec!(200)));
+        ledger.record(&review);
+        assert_eq!(ledger.hours("id-EMP-1", 2024), dec!(190));
+
+        // Another long week goes over
+        let next_week: Vec<_> = week(11).into_iter().map(|e| shift(e.clock_in.date() + Days::new(7), 8, 11)).collect();
+        let review = rules.review("VN", &next_week, &ledger);
+        assert_eq!(review.findings[0].status, OvertimeCapStatus::Exceeded);
+        assert_eq!(review.findings[0].overtime_hours, dec!(205));
+
+        // Germany caps the day instead: an 11-hour day is 3 hours over 8
+        let review = rules.review("DE", &[shift(NaiveDate::from_ymd_opt(2024, 4, 1).unwrap(), 7, 11)], &ledger);
+        assert_eq!(review.findings[0].cap, OvertimeCapKind::Daily);
+        assert_eq!(review.findings[0].overtime_hours, dec!(3));
+    }
+
+    #[test]
+    fn test_china_monthly_cap_and_idempotent_ledger() {
+        // Ten-hour days through April: 2 hours a day is within the 3-hour
+        // daily cap, but the fourth week takes the month to 40 hours
+        let rules = OvertimeRules::new();
+        let ledger = OvertimeLedger::new();
+        let monday = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        let month: Vec<TimeEntry> = (0..4)
+            .flat_map(|w| (0..5).map(move |d| shift(monday + Days::new(w * 7 + d), 8, 10)))
+            .collect();
+
+        let review = rules.review("CN", &month, &ledger);
+        assert_eq!(review.findings.len(), 1);
+        let finding = &review.findings[0];
+        assert_eq!((finding.cap, finding.status), (OvertimeCapKind::Monthly, OvertimeCapStatus::Exceeded));
+        assert_eq!((finding.period_start, finding.overtime_hours, finding.limit), (monday, dec!(40), dec!(36)));
+        ledger.record(&review);
+        assert_eq!(ledger.month_hours("id-EMP-1", 2024, 4), dec!(40));
+
+        // Reviewing and recording the same shifts again replaces them
+        let again = rules.review("CN", &month, &ledger);
+        assert_eq!(again.findings[0].overtime_hours, dec!(40));
+        ledger.record(&again);
+        assert_eq!(ledger.month_hours("id-EMP-1", 2024, 4), dec!(40));
+        assert_eq!(ledger.hours("id-EMP-1", 2024), dec!(40));
+
+        // May starts a new month
+        let may = rules.review("CN", &[shift(NaiveDate::from_ymd_opt(2024, 5, 6).unwrap(), 8, 10)], &ledger);
+        assert!(may.findings.is_empty());
+    }
+}
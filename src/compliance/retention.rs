@@ -0,0 +1,318 @@
+//! Retention Enforcement
+//!
+//! Scheduled job applying per-category retention rules to a tenant's
+//! terminated employees. Each rule covers one data category; once its
+//! retention period after termination has passed, only that category's
+//! data is anonymized or deleted, and the record itself is kept so
+//! headcount and payroll aggregates still add up. Every action is written
+//! to the audit log, and records under legal hold are never touched.
+
+use std::sync::Arc;
+use chrono::{DateTime, Months, NaiveDate, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::aggregates::{DocumentType, Employee, EmployeeError, EmploymentStatus};
+use super::audit::AuditLogStore;
+use super::global_compliance::DataCategory;
+use super::models::{ActorType, AuditAction, AuditLog};
+
+/// What happens to a category of data past retention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    /// Replace identifying values with placeholders
+    Anonymize,
+    /// Remove the category's data
+    Delete,
+}
+
+/// How long one category of data is kept after termination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionRule {
+    pub category: DataCategory,
+    pub retain_years: u32,
+    pub action: RetentionAction,
+}
+
+impl RetentionRule {
+    /// First day the data is past retention
+    pub fn expires_on(&self, terminated_on: NaiveDate) -> Option<NaiveDate> {
+        terminated_on.checked_add_months(Months::new(self.retain_years * 12))
+    }
+
+    /// Apply the rule to the category's data on the employee record,
+    /// returning whether there was any left. Categories the record doesn't
+    /// hold (health, biometric, location, ...) are kept in their own stores
+    /// and left to them.
+    fn apply(&self, employee: &mut Employee) -> Result<bool, EmployeeError> {
+        match self.category {
+            DataCategory::PersonalData | DataCategory::SensitivePersonalData => employee.anonymize_personal(),
+            DataCategory::FinancialData => employee.erase_financial(),
+            DataCategory::EmploymentData => employee.erase_documents(&[
+                DocumentType::OfferLetter,
+                DocumentType::Contract,
+                DocumentType::Certification,
+                DocumentType::PerformanceReview,
+            ]),
+            _ => Ok(false),
+        }
+    }
+}
+
+/// Retention rules by data category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    pub rules: Vec<RetentionRule>,
+}
+
+impl Default for RetentionPolicy {
+    /// Personal and financial data kept six years after exit (limitation
+    /// period for employment claims), then anonymized
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                RetentionRule { category: DataCategory::PersonalData, retain_years: 6, action: RetentionAction::Anonymize },
+                RetentionRule { category: DataCategory::FinancialData, retain_years: 6, action: RetentionAction::Anonymize },
+            ],
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// Rules past retention for a record terminated on `terminated_on`,
+    /// one per category, taking the strongest action where a category
+    /// has several
+    pub fn due(&self, terminated_on: NaiveDate, as_of: NaiveDate) -> Vec<RetentionRule> {
+        let mut due: Vec<RetentionRule> = Vec::new();
+        for rule in self.rules.iter().filter(|r| r.expires_on(terminated_on).is_some_and(|expiry| expiry <= as_of)) {
+            match due.iter_mut().find(|r| r.category == rule.category) {
+                Some(existing) if existing.action < rule.action => *existing = *rule,
+                Some(_) => {}
+                None => due.push(*rule),
+            }
+        }
+        due
+    }
+}
+
+/// Legal hold blocking retention for one employee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegalHold {
+    pub employee_id: Uuid,
+    pub reason: String,
+    pub placed_by: Uuid,
+    pub placed_at: DateTime<Utc>,
+}
+
+/// One category of an employee's data acted on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetainedData {
+    pub employee_id: Uuid,
+    pub category: DataCategory,
+}
+
+/// Outcome of one retention run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetentionReport {
+    pub anonymized: Vec<RetainedData>,
+    pub deleted: Vec<RetainedData>,
+    /// Past retention but skipped because of a legal hold
+    pub held: Vec<Uuid>,
+}
+
+/// Retention enforcement job
+#[derive(Clone)]
+pub struct RetentionJob {
+    policy: RetentionPolicy,
+    // In real implementation, employees come from the employee repository
+    employees: Arc<DashMap<(Uuid, Uuid), Employee>>,
+    holds: Arc<DashMap<Uuid, LegalHold>>,
+    audit: AuditLogStore,
+}
+
+impl RetentionJob {
+    pub fn new(policy: RetentionPolicy, audit: AuditLogStore) -> Self {
+        Self { policy, employees: Arc::new(DashMap::new()), holds: Arc::new(DashMap::new()), audit }
+    }
+
+    pub fn add_employee(&self, tenant_id: Uuid, employee_id: Uuid, employee: Employee) {
+        self.employees.insert((tenant_id, employee_id), employee);
+    }
+
+    pub fn employee(&self, tenant_id: Uuid, employee_id: Uuid) -> Option<Employee> {
+        self.employees.get(&(tenant_id, employee_id)).map(|e| e.clone())
+    }
+
+    pub fn place_hold(&self, hold: LegalHold) {
+        self.holds.insert(hold.employee_id, hold);
+    }
+
+    pub fn release_hold(&self, employee_id: Uuid) -> Option<LegalHold> {
+        self.holds.remove(&employee_id).map(|(_, hold)| hold)
+    }
+
+    pub fn is_held(&self, employee_id: Uuid) -> bool {
+        self.holds.contains_key(&employee_id)
+    }
+
+    /// Tenants with employees under retention
+    pub fn tenants(&self) -> Vec<Uuid> {
+        let mut tenants: Vec<Uuid> = self.employees.iter().map(|e| e.key().0).collect();
+        tenants.sort();
+        tenants.dedup();
+        tenants
+    }
+
+    /// Apply retention to `tenant_id`'s employees as of `as_of`
+    pub fn run(&self, tenant_id: Uuid, as_of: NaiveDate) -> RetentionReport {
+        let mut report = RetentionReport::default();
+
+        for mut entry in self.employees.iter_mut().filter(|e| e.key().0 == tenant_id) {
+            let employee_id = entry.key().1;
+            if entry.status() != &EmploymentStatus::Terminated {
+                continue;
+            }
+            let Some(terminated_on) = entry.employment().termination_date else { continue };
+
+            // Work on a copy so a held record is left exactly as it was
+            let mut retained = entry.value().clone();
+            let applied: Vec<RetentionRule> = self
+                .policy
+                .due(terminated_on, as_of)
+                .into_iter()
+                .filter(|rule| rule.apply(&mut retained).unwrap_or(false))
+                .collect();
+            if applied.is_empty() {
+                continue;
+            }
+            if self.is_held(employee_id) {
+                report.held.push(employee_id);
+                continue;
+            }
+
+            *entry.value_mut() = retained;
+            for rule in applied {
+                let (audit_action, outcomes) = match rule.action {
+                    RetentionAction::Anonymize => (AuditAction::Update, &mut report.anonymized),
+                    RetentionAction::Delete => (AuditAction::Delete, &mut report.deleted),
+                };
+                self.record(tenant_id, employee_id, audit_action, &rule, terminated_on);
+                outcomes.push(RetainedData { employee_id, category: rule.category });
+            }
+        }
+
+        report
+    }
+
+    fn record(&self, tenant_id: Uuid, employee_id: Uuid, action: AuditAction, rule: &RetentionRule, terminated_on: NaiveDate) {
+        let mut entry = AuditLog::new(tenant_id, "employee", employee_id, action, None, ActorType::System);
+        entry.metadata = serde_json::json!({
+            "reason": "retention",
+            "category": rule.category,
+            "retention_action": rule.action,
+            "retain_years": rule.retain_years,
+            "terminated_on": terminated_on,
+        });
+        self.audit.append(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::AuditFilter;
+    use crate::domain::aggregates::EmployeeDocument;
+    use crate::domain::value_objects::EmployeeId;
+
+    fn terminated(seq: u32, terminated_on: NaiveDate) -> Employee {
+        let hired = NaiveDate::from_ymd_opt(2010, 3, 1).unwrap();
+        let mut employee = Employee::hire(EmployeeId::new(2010, seq), "Ada", "Obi", "ada@company.com", "Analyst", hired);
+        employee.transfer(Some("finance".into()), None);
+        employee.terminate(terminated_on, "Resigned").unwrap();
+        employee
+    }
+
+    fn with_contract(mut employee: Employee) -> Employee {
+        employee.attach_document(EmployeeDocument {
+            id: "doc-1".into(),
+            doc_type: DocumentType::Contract,
+            name: "contract.pdf".into(),
+            uploaded_at: Utc::now(),
+        });
+        employee
+    }
+
+    #[test]
+    fn test_over_retention_employee_is_anonymized() {
+        let audit = AuditLogStore::new();
+        let job = RetentionJob::new(RetentionPolicy::default(), audit.clone());
+        let tenant_id = Uuid::new_v4();
+        let old = Uuid::new_v4();
+        let recent = Uuid::new_v4();
+        job.add_employee(tenant_id, old, terminated(1, NaiveDate::from_ymd_opt(2017, 6, 30).unwrap()));
+        job.add_employee(tenant_id, recent, terminated(2, NaiveDate::from_ymd_opt(2022, 6, 30).unwrap()));
+
+        // Another tenant's run leaves these records alone
+        assert!(job.run(Uuid::new_v4(), NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()).anonymized.is_empty());
+        assert!(!job.employee(tenant_id, old).unwrap().is_anonymized());
+
+        let report = job.run(tenant_id, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+
+        // Personal data only; financial data had nothing left to erase
+        assert_eq!(report.anonymized, vec![RetainedData { employee_id: old, category: DataCategory::PersonalData }]);
+        let employee = job.employee(tenant_id, old).unwrap();
+        assert!(employee.is_anonymized());
+        assert_eq!(employee.full_name(), "Former Employee");
+        assert!(employee.employment().work_email.is_empty());
+        // Aggregates survive
+        assert_eq!(employee.employee_id(), &EmployeeId::new(2010, 1));
+        assert_eq!(employee.employment().department_id.as_deref(), Some("finance"));
+
+        assert!(!job.employee(tenant_id, recent).unwrap().is_anonymized());
+
+        let logged = audit.query(&AuditFilter::default(), None, 10).entries;
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0].entity_id, old);
+        assert_eq!(logged[0].metadata["reason"], "retention");
+
+        // A second run has nothing left to do
+        assert!(job.run(tenant_id, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()).anonymized.is_empty());
+    }
+
+    #[test]
+    fn test_legal_hold_blocks_purge() {
+        let audit = AuditLogStore::new();
+        let policy = RetentionPolicy {
+            rules: vec![RetentionRule { category: DataCategory::EmploymentData, retain_years: 6, action: RetentionAction::Delete }],
+        };
+        let job = RetentionJob::new(policy, audit.clone());
+        let tenant_id = Uuid::new_v4();
+        let held = Uuid::new_v4();
+        let free = Uuid::new_v4();
+        job.add_employee(tenant_id, held, with_contract(terminated(1, NaiveDate::from_ymd_opt(2015, 1, 31).unwrap())));
+        job.add_employee(tenant_id, free, with_contract(terminated(2, NaiveDate::from_ymd_opt(2015, 1, 31).unwrap())));
+        job.place_hold(LegalHold {
+            employee_id: held,
+            reason: "Pending tribunal claim".into(),
+            placed_by: Uuid::new_v4(),
+            placed_at: Utc::now(),
+        });
+
+        let as_of = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let report = job.run(tenant_id, as_of);
+
+        assert_eq!(report.held, vec![held]);
+        assert_eq!(report.deleted, vec![RetainedData { employee_id: free, category: DataCategory::EmploymentData }]);
+        assert_eq!(job.employee(tenant_id, held).unwrap().documents().len(), 1);
+        // Only the expired category goes: the record and its personal data stay
+        let purged = job.employee(tenant_id, free).unwrap();
+        assert!(purged.documents().is_empty());
+        assert_eq!(purged.full_name(), "Ada Obi");
+        assert_eq!(audit.query(&AuditFilter::default(), None, 10).entries.len(), 1);
+
+        job.release_hold(held);
+        assert_eq!(job.run(tenant_id, as_of).deleted, vec![RetainedData { employee_id: held, category: DataCategory::EmploymentData }]);
+    }
+}
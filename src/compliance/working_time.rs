@@ -0,0 +1,237 @@
+//! Working Time Compliance
+//!
+//! Checks finalized shifts against the EU Working Time Directive: average
+//! weekly hours over a rolling reference period may not exceed 48, and
+//! every working day must be followed by 11 consecutive hours of rest.
+//! Limits are configurable for member states or sectors with different
+//! rules. A week without shifts counts as zero hours.
+
+use std::collections::BTreeMap;
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::time::TimeEntry;
+
+/// Working time limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkingTimeRules {
+    /// Cap on average weekly hours across a reference period
+    pub max_average_weekly_hours: Decimal,
+    /// Minimum consecutive rest between working days
+    pub min_daily_rest_hours: Decimal,
+}
+
+impl Default for WorkingTimeRules {
+    fn default() -> Self {
+        Self { max_average_weekly_hours: dec!(48), min_daily_rest_hours: dec!(11) }
+    }
+}
+
+/// A breach of the working time rules
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WorkingTimeViolation {
+    /// Average over the reference period ending with the week starting `week_of`
+    AverageWeeklyHoursExceeded {
+        employee_id: String,
+        employee_number: String,
+        week_of: NaiveDate,
+        average_hours: Decimal,
+    },
+    /// Rest from the end of one working day to the start of the next
+    InsufficientDailyRest {
+        employee_id: String,
+        employee_number: String,
+        rest_started: NaiveDateTime,
+        rest_hours: Decimal,
+    },
+}
+
+impl WorkingTimeViolation {
+    pub fn employee_id(&self) -> &str {
+        match self {
+            Self::AverageWeeklyHoursExceeded { employee_id, .. }
+            | Self::InsufficientDailyRest { employee_id, .. } => employee_id,
+        }
+    }
+}
+
+/// Compliance checks over recorded working time
+#[derive(Debug, Clone, Default)]
+pub struct ComplianceCheck {
+    pub rules: WorkingTimeRules,
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(u64::from(date.weekday().num_days_from_monday()))
+}
+
+impl ComplianceCheck {
+    pub fn new(rules: WorkingTimeRules) -> Self {
+        Self { rules }
+    }
+
+    /// Violations across every employee in `entries`, averaging weekly
+    /// hours over `reference_weeks` (17 under the Directive). Weeks before
+    /// an employee's first shift count as zero, so a short first stretch of
+    /// long weeks is judged against the whole period.
+    pub fn working_time(&self, entries: &[TimeEntry], reference_weeks: u32) -> Vec<WorkingTimeViolation> {
+        let reference_weeks = reference_weeks.max(1);
+        let mut by_employee: BTreeMap<(&str, &str), Vec<&TimeEntry>> = BTreeMap::new();
+        for entry in entries {
+            by_employee.entry((&entry.employee_id, &entry.employee_number)).or_default().push(entry);
+        }
+
+        let mut violations = Vec::new();
+        for ((employee_id, employee_number), mut shifts) in by_employee {
+            shifts.sort_by_key(|e| e.clock_in);
+            if let Some((week_of, average_hours)) = self.average_hours(&shifts, reference_weeks) {
+                violations.push(WorkingTimeViolation::AverageWeeklyHoursExceeded {
+                    employee_id: employee_id.to_string(),
+                    employee_number: employee_number.to_string(),
+                    week_of,
+                    average_hours,
+                });
+            }
+            for (rest_started, rest_hours) in self.short_rests(&shifts) {
+                violations.push(WorkingTimeViolation::InsufficientDailyRest {
+                    employee_id: employee_id.to_string(),
+                    employee_number: employee_number.to_string(),
+                    rest_started,
+                    rest_hours,
+                });
+            }
+        }
+        violations
+    }
+
+    /// Highest rolling average above the cap, with the week it ends on
+    fn average_hours(&self, shifts: &[&TimeEntry], reference_weeks: u32) -> Option<(NaiveDate, Decimal)> {
+        let mut weekly: BTreeMap<NaiveDate, Decimal> = BTreeMap::new();
+        for shift in shifts {
+            *weekly.entry(week_start(shift.clock_in.date())).or_default() += shift.hours;
+        }
+        let first = *weekly.keys().next()?;
+        let last = *weekly.keys().next_back()?;
+
+        let mut worst: Option<(NaiveDate, Decimal)> = None;
+        let mut week = first;
+        while week <= last {
+            let window_start = week - Days::new(7 * u64::from(reference_weeks - 1));
+            let total: Decimal = weekly.range(window_start..=week).map(|(_, hours)| *hours).sum();
+            let average = (total / Decimal::from(reference_weeks)).round_dp(2);
+            if average > self.rules.max_average_weekly_hours && worst.is_none_or(|(_, w)| average > w) {
+                worst = Some((week, average));
+            }
+            week = week + Days::new(7);
+        }
+        worst
+    }
+
+    /// Rest between the last shift of one working day and the first of the next
+    fn short_rests(&self, shifts: &[&TimeEntry]) -> Vec<(NaiveDateTime, Decimal)> {
+        let mut days: BTreeMap<NaiveDate, (NaiveDateTime, NaiveDateTime)> = BTreeMap::new();
+        for shift in shifts {
+            let day = days.entry(shift.clock_in.date()).or_insert((shift.clock_in, shift.clock_out));
+            day.0 = day.0.min(shift.clock_in);
+            day.1 = day.1.max(shift.clock_out);
+        }
+
+        days.values()
+            .zip(days.values().skip(1))
+            .filter_map(|((_, ended), (started, _))| {
+                let rest_hours = (Decimal::from((*started - *ended).num_minutes()) / dec!(60)).round_dp(2);
+                (rest_hours < self.rules.min_daily_rest_hours).then_some((*ended, rest_hours))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shift(number: &str, date: NaiveDate, start: u32, end: u32) -> TimeEntry {
+        let clock_in = date.and_hms_opt(start, 0, 0).unwrap();
+        let clock_out = if end > start {
+            date.and_hms_opt(end, 0, 0).unwrap()
+        } else {
+            (date + Days::new(1)).and_hms_opt(end, 0, 0).unwrap()
+        };
+        TimeEntry {
+            employee_id: format!("id-{}", number),
+            employee_number: number.to_string(),
+            clock_in,
+            clock_out,
+            hours: Decimal::from((clock_out - clock_in).num_hours()),
+        }
+    }
+
+    /// Monday-to-Friday shifts for `weeks` weeks from 1 April 2024
+    fn weeks_of(number: &str, weeks: u64, start: u32, end: u32) -> Vec<TimeEntry> {
+        let monday = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        (0..weeks * 7)
+            .map(|d| monday + Days::new(d))
+            .filter(|d| d.weekday().num_days_from_monday() < 5)
+            .map(|d| shift(number, d, start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_average_over_48_hours_flagged() {
+        // 07:00-18:00 is 55 hours a week, every week of a 4-week reference period
+        let mut entries = weeks_of("EMP-1", 4, 7, 18);
+        entries.extend(weeks_of("EMP-2", 4, 9, 17));
+
+        let violations = ComplianceCheck::default().working_time(&entries, 4);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0],
+            WorkingTimeViolation::AverageWeeklyHoursExceeded {
+                employee_id: "id-EMP-1".to_string(),
+                employee_number: "EMP-1".to_string(),
+                week_of: NaiveDate::from_ymd_opt(2024, 4, 22).unwrap(),
+                average_hours: dec!(55),
+            }
+        );
+    }
+
+    #[test]
+    fn test_long_weeks_averaged_out_pass() {
+        // Two 55-hour weeks then two 35-hour weeks average 45
+        let mut entries = weeks_of("EMP-3", 2, 7, 18);
+        let later = NaiveDate::from_ymd_opt(2024, 4, 15).unwrap();
+        entries.extend(
+            (0..14)
+                .map(|d| later + Days::new(d))
+                .filter(|d| d.weekday().num_days_from_monday() < 5)
+                .map(|d| shift("EMP-3", d, 9, 16)),
+        );
+
+        // Early weeks alone average 55, but over all four weeks it is 45
+        let check = ComplianceCheck::default();
+        assert!(check.working_time(&entries, 4).is_empty());
+        // A tighter national cap flags the same hours
+        let strict = ComplianceCheck::new(WorkingTimeRules { max_average_weekly_hours: dec!(40), ..Default::default() });
+        assert_eq!(strict.working_time(&entries, 4).len(), 1);
+    }
+
+    #[test]
+    fn test_insufficient_daily_rest() {
+        let monday = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        // Closing at 23:00, opening at 06:00: seven hours' rest
+        let entries = vec![shift("EMP-4", monday, 15, 23), shift("EMP-4", monday + Days::new(1), 6, 14)];
+
+        let violations = ComplianceCheck::default().working_time(&entries, 17);
+
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(
+            &violations[0],
+            WorkingTimeViolation::InsufficientDailyRest { rest_hours, .. } if *rest_hours == dec!(7)
+        ));
+        assert_eq!(violations[0].employee_id(), "id-EMP-4");
+    }
+}
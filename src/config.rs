@@ -0,0 +1,312 @@
+//! Server Configuration
+//!
+//! `AppConfig` is read once at startup from the environment, over an
+//! optional JSON file named by `APP_CONFIG_FILE` whose keys are the
+//! variable names in lower case. Every value is checked before the server
+//! starts, and all problems are reported together instead of stopping at
+//! the first. Production requires the database, NATS, and JWT settings
+//! that development can do without.
+//!
+//! ```json
+//! { "app_env": "production", "port": 8082, "database_url": "postgres://db/hr" }
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::ops::ObservabilityConfig;
+use crate::payroll::tax_config::DEFAULT_TAX_CONFIG_DIR;
+use crate::validation::{FieldError, Validator};
+
+/// Variable naming the JSON config file
+pub const CONFIG_FILE_VAR: &str = "APP_CONFIG_FILE";
+
+/// Settings read from the environment or config file
+pub const CONFIG_KEYS: [&str; 9] = [
+    "APP_ENV",
+    "PORT",
+    "DATABASE_URL",
+    "DATABASE_READ_URL",
+    "NATS_URL",
+    "JWT_SECRET",
+    "TAX_CONFIG_DIR",
+    "TRACE_SAMPLE_RATE",
+    "SLOW_QUERY_THRESHOLD_MS",
+];
+
+/// Signing secret used outside production when `JWT_SECRET` is unset
+pub const DEVELOPMENT_JWT_SECRET: &str = "development-only-jwt-secret";
+
+const DEFAULT_PORT: u16 = 8080;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Environment {
+    #[default]
+    Development,
+    Production,
+}
+
+/// Typed server configuration
+#[derive(Clone, PartialEq)]
+pub struct AppConfig {
+    pub environment: Environment,
+    pub port: u16,
+    pub database_url: Option<Url>,
+    /// Read replica for read-only endpoints; reads use the primary when unset
+    pub database_read_url: Option<Url>,
+    pub nats_url: Option<Url>,
+    pub jwt_secret: String,
+    pub tax_config_dir: PathBuf,
+    pub observability: ObservabilityConfig,
+}
+
+// Written out so logging the config never prints the signing secret
+impl std::fmt::Debug for AppConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppConfig")
+            .field("environment", &self.environment)
+            .field("port", &self.port)
+            .field("database_url", &self.database_url.as_ref().map(redact_password))
+            .field("database_read_url", &self.database_read_url.as_ref().map(redact_password))
+            .field("nats_url", &self.nats_url.as_ref().map(redact_password))
+            .field("jwt_secret", &"<redacted>")
+            .field("tax_config_dir", &self.tax_config_dir)
+            .field("observability", &self.observability)
+            .finish()
+    }
+}
+
+/// The URL as text with any password masked
+fn redact_password(url: &Url) -> String {
+    let mut url = url.clone();
+    if url.password().is_some() {
+        let _ = url.set_password(Some("redacted"));
+    }
+    url.to_string()
+}
+
+/// Every problem found in the configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    pub problems: Vec<FieldError>,
+}
+
+impl ConfigError {
+    pub fn has_problem(&self, key: &str) -> bool {
+        self.problems.iter().any(|p| p.field == key)
+    }
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid configuration, {} problem(s):", self.problems.len())?;
+        for problem in &self.problems {
+            write!(f, "\n  {}: {}", problem.field, problem.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl AppConfig {
+    /// Configuration from the process environment and `APP_CONFIG_FILE`
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let file = std::env::var(CONFIG_FILE_VAR).ok().map(PathBuf::from);
+        Self::load(file.as_deref(), |key| std::env::var(key).ok())
+    }
+
+    /// Configuration from `file`, if any, with values from `env` taking
+    /// precedence
+    pub fn load(file: Option<&Path>, env: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let mut validator = Validator::new();
+        let mut values = match file {
+            Some(path) => read_file(path, &mut validator),
+            None => BTreeMap::new(),
+        };
+        for key in CONFIG_KEYS {
+            if let Some(value) = env(key) {
+                values.insert(key.to_string(), value);
+            }
+        }
+        Self::parse(&values, validator)
+    }
+
+    /// Configuration from raw values keyed by variable name
+    pub fn from_values(values: &BTreeMap<String, String>) -> Result<Self, ConfigError> {
+        Self::parse(values, Validator::new())
+    }
+
+    fn parse(values: &BTreeMap<String, String>, mut validator: Validator) -> Result<Self, ConfigError> {
+        let value = |key: &str| values.get(key).map(|v| v.trim()).filter(|v| !v.is_empty());
+
+        let environment = match value("APP_ENV").map(str::to_ascii_lowercase).as_deref() {
+            None | Some("development") => Environment::Development,
+            Some("production") => Environment::Production,
+            Some(other) => {
+                validator.check(false, "APP_ENV", "invalid", format!("APP_ENV must be development or production, got {}", other));
+                Environment::Development
+            }
+        };
+        let production = environment == Environment::Production;
+
+        let port = match value("PORT") {
+            None => DEFAULT_PORT,
+            Some(raw) => {
+                let port = raw.parse::<u16>().ok().filter(|p| *p != 0);
+                validator.check(port.is_some(), "PORT", "out_of_range", format!("PORT must be between 1 and 65535, got {}", raw));
+                port.unwrap_or(DEFAULT_PORT)
+            }
+        };
+
+        let mut url = |key: &str, schemes: &[&str], required: bool| {
+            let raw = value(key);
+            validator.check(raw.is_some() || !required, key, "required", format!("{} is required in production", key));
+            let raw = raw?;
+            match Url::parse(raw) {
+                Ok(url) if schemes.contains(&url.scheme()) => Some(url),
+                Ok(url) => {
+                    let message = format!("{} must use {}, got {}", key, schemes.join(" or "), url.scheme());
+                    validator.check(false, key, "invalid_scheme", message);
+                    None
+                }
+                Err(e) => {
+                    validator.check(false, key, "invalid_url", format!("{} is not a valid URL: {}", key, e));
+                    None
+                }
+            }
+        };
+        let database_url = url("DATABASE_URL", &["postgres", "postgresql"], production);
+        let database_read_url = url("DATABASE_READ_URL", &["postgres", "postgresql"], false);
+        let nats_url = url("NATS_URL", &["nats", "tls"], production);
+
+        let jwt_secret = value("JWT_SECRET");
+        validator.check(jwt_secret.is_some() || !production, "JWT_SECRET", "required", "JWT_SECRET is required in production");
+
+        let mut observability = ObservabilityConfig::default();
+        if let Some(raw) = value("TRACE_SAMPLE_RATE") {
+            let rate = raw.parse::<f64>().ok().filter(|r| (0.0..=1.0).contains(r));
+            validator.check(
+                rate.is_some(),
+                "TRACE_SAMPLE_RATE",
+                "out_of_range",
+                format!("TRACE_SAMPLE_RATE must be between 0 and 1, got {}", raw),
+            );
+            observability.trace_sample_rate = rate.unwrap_or(observability.trace_sample_rate);
+        }
+        if let Some(raw) = value("SLOW_QUERY_THRESHOLD_MS") {
+            let threshold = raw.parse::<u64>().ok();
+            validator.check(
+                threshold.is_some(),
+                "SLOW_QUERY_THRESHOLD_MS",
+                "invalid",
+                format!("SLOW_QUERY_THRESHOLD_MS must be a whole number of milliseconds, got {}", raw),
+            );
+            observability.slow_query_threshold_ms = threshold.unwrap_or(observability.slow_query_threshold_ms);
+        }
+
+        validator.finish().map_err(|e| ConfigError { problems: e.errors })?;
+        Ok(Self {
+            environment,
+            port,
+            database_url,
+            database_read_url,
+            nats_url,
+            jwt_secret: jwt_secret.unwrap_or(DEVELOPMENT_JWT_SECRET).to_string(),
+            tax_config_dir: value("TAX_CONFIG_DIR").unwrap_or(DEFAULT_TAX_CONFIG_DIR).into(),
+            observability,
+        })
+    }
+}
+
+/// Values from a JSON config file, recording any problem with it
+fn read_file(path: &Path, validator: &mut Validator) -> BTreeMap<String, String> {
+    let parsed = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read config file {}: {}", path.display(), e))
+        .and_then(|text| {
+            serde_json::from_str::<BTreeMap<String, serde_json::Value>>(&text)
+                .map_err(|e| format!("Malformed config file {}: {}", path.display(), e))
+        });
+    let entries = match parsed {
+        Ok(entries) => entries,
+        Err(message) => {
+            validator.check(false, CONFIG_FILE_VAR, "unreadable", message);
+            return BTreeMap::new();
+        }
+    };
+
+    let mut values = BTreeMap::new();
+    for (key, value) in entries {
+        let name = key.to_ascii_uppercase();
+        if !CONFIG_KEYS.contains(&name.as_str()) {
+            validator.check(false, &name, "unknown", format!("{} in {} is not a known setting", key, path.display()));
+            continue;
+        }
+        let value = match value {
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        };
+        values.insert(name, value);
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_reports_missing_and_invalid_settings_together() {
+        let error = AppConfig::from_values(&values(&[
+            ("APP_ENV", "production"),
+            ("PORT", "70000"),
+            ("NATS_URL", "not a url"),
+            ("JWT_SECRET", "s3cret"),
+        ]))
+        .unwrap_err();
+
+        let keys: Vec<&str> = error.problems.iter().map(|p| p.field.as_str()).collect();
+        assert_eq!(keys, ["PORT", "DATABASE_URL", "NATS_URL"]);
+        assert_eq!(error.problems[1].code, "required");
+        let message = error.to_string();
+        assert!(message.starts_with("Invalid configuration, 3 problem(s):"));
+        assert!(message.contains("PORT must be between 1 and 65535, got 70000"));
+        assert!(message.contains("DATABASE_URL is required in production"));
+    }
+
+    #[test]
+    fn test_development_defaults_and_env_over_file() {
+        let config = AppConfig::from_values(&BTreeMap::new()).unwrap();
+        assert_eq!((config.environment, config.port), (Environment::Development, DEFAULT_PORT));
+        assert_eq!(config.database_url, None);
+        assert_eq!(config.jwt_secret, DEVELOPMENT_JWT_SECRET);
+        assert!(!format!("{:?}", config).contains(DEVELOPMENT_JWT_SECRET));
+        let config = AppConfig::from_values(&values(&[
+            ("JWT_SECRET", "s3cret"),
+            ("DATABASE_URL", "postgres://hr:hunter2@db/hr"),
+        ]))
+        .unwrap();
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("s3cret") && !debug.contains("hunter2"));
+
+        let path = std::env::temp_dir().join(format!("sase-hr-config-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, r#"{"port": 8082, "database_url": "postgres://db:5432/hr", "trace_sample_rate": 0.5}"#).unwrap();
+        let env = values(&[("PORT", "9000")]);
+        let config = AppConfig::load(Some(&path), |key| env.get(key).cloned()).unwrap();
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.database_url.unwrap().host_str(), Some("db"));
+        assert_eq!(config.observability.trace_sample_rate, 0.5);
+
+        std::fs::write(&path, r#"{"prot": 8082}"#).unwrap();
+        let error = AppConfig::load(Some(&path), |_| None).unwrap_err();
+        assert!(error.has_problem("PROT"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
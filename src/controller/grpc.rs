@@ -0,0 +1,358 @@
+//! Controller gRPC API
+//!
+//! tonic service over `CentralController` for PoPs and regional tooling:
+//! registration, heartbeats, policy distribution, and a live stream of PoP
+//! status. Definitions live in `proto/controller.proto`.
+
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use tonic::{Request, Response, Status};
+
+use super::{CentralController, ControllerError, DistributionReport, HealthStatus, PolicyType, PopInfo, PopStatus};
+
+pub mod proto {
+    tonic::include_proto!("sase.controller.v1");
+}
+
+use proto::controller_server::{Controller, ControllerServer};
+
+/// Controller shared between the gRPC service and the rest of the process
+pub type SharedController = Arc<RwLock<CentralController>>;
+
+/// Heartbeats buffered per status subscriber before it starts missing updates
+const STATUS_CHANNEL_CAPACITY: usize = 256;
+
+type PopStatusStream = Pin<Box<dyn Stream<Item = Result<proto::PopStatusUpdate, Status>> + Send>>;
+
+/// gRPC service backed by a `CentralController`
+#[derive(Clone)]
+pub struct ControllerGrpcService {
+    controller: SharedController,
+    status_updates: broadcast::Sender<proto::PopStatusUpdate>,
+}
+
+impl ControllerGrpcService {
+    pub fn new(controller: SharedController) -> Self {
+        let (status_updates, _) = broadcast::channel(STATUS_CHANNEL_CAPACITY);
+        Self { controller, status_updates }
+    }
+
+    pub fn into_server(self) -> ControllerServer<Self> {
+        ControllerServer::new(self)
+    }
+}
+
+/// Serve the controller API on `bind_address:grpc_port` from its config
+pub async fn serve(controller: SharedController) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let addr: SocketAddr = {
+        let config = controller.read().await;
+        let config = config.config();
+        format!("{}:{}", config.bind_address, config.grpc_port).parse()?
+    };
+    tracing::info!(%addr, "controller gRPC listening");
+    tonic::transport::Server::builder()
+        .add_service(ControllerGrpcService::new(controller).into_server())
+        .serve(addr)
+        .await?;
+    Ok(())
+}
+
+#[tonic::async_trait]
+impl Controller for ControllerGrpcService {
+    async fn register_pop(
+        &self,
+        request: Request<proto::RegisterPopRequest>,
+    ) -> Result<Response<proto::RegisterPopResponse>, Status> {
+        let request = request.into_inner();
+        if request.pop_id.trim().is_empty() {
+            return Err(Status::invalid_argument("pop_id is required"));
+        }
+        let pop = PopInfo {
+            id: request.pop_id.clone(),
+            location: request.location,
+            region: request.region,
+            health: HealthStatus::Unknown,
+            active_connections: 0,
+            cpu_usage: 0.0,
+            memory_usage: 0.0,
+            bandwidth_mbps: request.bandwidth_mbps,
+            last_heartbeat: String::new(),
+        };
+        self.controller.write().await.register_pop(pop).map_err(status_from)?;
+        Ok(Response::new(proto::RegisterPopResponse { pop_id: request.pop_id }))
+    }
+
+    async fn heartbeat(
+        &self,
+        request: Request<proto::HeartbeatRequest>,
+    ) -> Result<Response<proto::HeartbeatResponse>, Status> {
+        let request = request.into_inner();
+        let status = PopStatus {
+            health: health_from_proto(request.health()),
+            cpu_usage: request.cpu_usage,
+            memory_usage: request.memory_usage,
+            active_connections: request.active_connections,
+        };
+
+        let update = {
+            let mut controller = self.controller.write().await;
+            if controller.get_pop_status(&request.pop_id).is_none() {
+                return Err(Status::not_found(format!("PoP {} is not registered", request.pop_id)));
+            }
+            controller.process_heartbeat(&request.pop_id, status).map_err(status_from)?;
+            controller.get_pop_status(&request.pop_id).map(status_update)
+        };
+
+        let received_at = update.as_ref().map(|u| u.last_heartbeat.clone()).unwrap_or_default();
+        if let Some(update) = update {
+            // No subscribers is fine
+            let _ = self.status_updates.send(update);
+        }
+        Ok(Response::new(proto::HeartbeatResponse { received_at }))
+    }
+
+    async fn distribute_policy(
+        &self,
+        request: Request<proto::DistributePolicyRequest>,
+    ) -> Result<Response<proto::DistributePolicyResponse>, Status> {
+        let policy = request.into_inner().policy.ok_or_else(|| Status::invalid_argument("policy is required"))?;
+        if policy.id.trim().is_empty() {
+            return Err(Status::invalid_argument("policy.id is required"));
+        }
+        let priority = u8::try_from(policy.priority)
+            .map_err(|_| Status::invalid_argument("policy.priority must be 0-255"))?;
+
+        let mut controller = self.controller.write().await;
+        // Keep the original creation time so resubmitting unchanged content
+        // is recognized and not re-versioned
+        let created_at = controller
+            .get_state()
+            .policies
+            .get(&policy.id)
+            .map(|p| p.created_at.clone())
+            .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+        let policy_type = policy_type_from_proto(policy.policy_type());
+        let report = controller
+            .distribute_policy(super::Policy {
+                id: policy.id,
+                name: policy.name,
+                version: 0,
+                policy_type,
+                rules: policy.rules,
+                tenant_id: policy.tenant_id,
+                target_pops: policy.target_pops,
+                priority,
+                created_at,
+                updated_at: String::new(),
+            })
+            .map_err(status_from)?;
+        Ok(Response::new(report_to_proto(report)))
+    }
+
+    type StreamPopStatusStream = PopStatusStream;
+
+    async fn stream_pop_status(
+        &self,
+        request: Request<proto::StreamPopStatusRequest>,
+    ) -> Result<Response<Self::StreamPopStatusStream>, Status> {
+        let pop_ids = request.into_inner().pop_ids;
+        let wanted = move |pop_id: &str| pop_ids.is_empty() || pop_ids.iter().any(|p| p == pop_id);
+
+        // Subscribe before taking the snapshot so no heartbeat falls between them
+        let live = BroadcastStream::new(self.status_updates.subscribe());
+        let mut snapshot: Vec<proto::PopStatusUpdate> = {
+            let controller = self.controller.read().await;
+            controller.get_state().pops.values().filter(|p| wanted(&p.id)).map(status_update).collect()
+        };
+        snapshot.sort_by(|a, b| a.pop_id.cmp(&b.pop_id));
+
+        let live = live.filter_map(move |update| match update {
+            Ok(update) if wanted(&update.pop_id) => Some(Ok(update)),
+            // Lagged subscribers just miss intermediate heartbeats
+            _ => None,
+        });
+        let stream = tokio_stream::iter(snapshot.into_iter().map(Ok)).chain(live);
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+fn status_from(error: ControllerError) -> Status {
+    match error {
+        ControllerError::PopAtCapacity(_) | ControllerError::BandwidthExceeded { .. } => {
+            Status::resource_exhausted(error.to_string())
+        }
+        ControllerError::NoHealthyPops | ControllerError::ConnectionFailed(_) => Status::unavailable(error.to_string()),
+        ControllerError::NoLease => Status::failed_precondition(error.to_string()),
+        ControllerError::PolicyDistributionFailed(_) => Status::internal(error.to_string()),
+    }
+}
+
+fn status_update(pop: &PopInfo) -> proto::PopStatusUpdate {
+    proto::PopStatusUpdate {
+        pop_id: pop.id.clone(),
+        health: health_to_proto(pop.health) as i32,
+        cpu_usage: pop.cpu_usage,
+        memory_usage: pop.memory_usage,
+        active_connections: pop.active_connections,
+        last_heartbeat: pop.last_heartbeat.clone(),
+    }
+}
+
+fn report_to_proto(report: DistributionReport) -> proto::DistributePolicyResponse {
+    proto::DistributePolicyResponse {
+        policy_id: report.policy_id,
+        version: report.version,
+        succeeded: report.succeeded.into_iter().map(|r| r.0).collect(),
+        failed: report
+            .failed
+            .into_iter()
+            .map(|(region, error)| proto::RegionFailure { region: region.0, error })
+            .collect(),
+    }
+}
+
+fn health_from_proto(health: proto::HealthStatus) -> HealthStatus {
+    match health {
+        proto::HealthStatus::Healthy => HealthStatus::Healthy,
+        proto::HealthStatus::Degraded => HealthStatus::Degraded,
+        proto::HealthStatus::Unhealthy => HealthStatus::Unhealthy,
+        proto::HealthStatus::Unknown => HealthStatus::Unknown,
+    }
+}
+
+fn health_to_proto(health: HealthStatus) -> proto::HealthStatus {
+    match health {
+        HealthStatus::Healthy => proto::HealthStatus::Healthy,
+        HealthStatus::Degraded => proto::HealthStatus::Degraded,
+        HealthStatus::Unhealthy => proto::HealthStatus::Unhealthy,
+        HealthStatus::Unknown => proto::HealthStatus::Unknown,
+    }
+}
+
+fn policy_type_from_proto(policy_type: proto::PolicyType) -> PolicyType {
+    match policy_type {
+        proto::PolicyType::Firewall => PolicyType::Firewall,
+        proto::PolicyType::Routing => PolicyType::Routing,
+        proto::PolicyType::Qos => PolicyType::QoS,
+        proto::PolicyType::Security => PolicyType::Security,
+        proto::PolicyType::Compliance => PolicyType::Compliance,
+        proto::PolicyType::Custom => PolicyType::Custom,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::ControllerConfig;
+    use hyper_util::rt::TokioIo;
+    use proto::controller_client::ControllerClient;
+    use tonic::transport::{Channel, Endpoint, Server};
+
+    /// Client connected to a server over an in-memory duplex pipe
+    async fn in_process_client(controller: SharedController) -> ControllerClient<Channel> {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let service = ControllerGrpcService::new(controller).into_server();
+        tokio::spawn(async move {
+            Server::builder()
+                .add_service(service)
+                .serve_with_incoming(tokio_stream::once(Ok::<_, std::io::Error>(server_io)))
+                .await
+        });
+
+        let mut client_io = Some(client_io);
+        let channel = Endpoint::try_from("http://in-process")
+            .unwrap()
+            .connect_with_connector(tower::service_fn(move |_| {
+                let io = client_io.take();
+                async move {
+                    io.map(TokioIo::new)
+                        .ok_or_else(|| std::io::Error::other("already connected"))
+                }
+            }))
+            .await
+            .unwrap();
+        ControllerClient::new(channel)
+    }
+
+    #[tokio::test]
+    async fn test_register_and_heartbeat_update_controller() {
+        let controller: SharedController = Arc::new(RwLock::new(CentralController::new(ControllerConfig::default())));
+        let mut client = in_process_client(controller.clone()).await;
+
+        client
+            .register_pop(proto::RegisterPopRequest {
+                pop_id: "pop-lagos-1".into(),
+                location: "Lagos".into(),
+                region: "af-west".into(),
+                bandwidth_mbps: 1000.0,
+            })
+            .await
+            .unwrap();
+        assert_eq!(controller.read().await.get_pop_status("pop-lagos-1").unwrap().health, HealthStatus::Unknown);
+
+        let mut updates = client
+            .stream_pop_status(proto::StreamPopStatusRequest { pop_ids: vec!["pop-lagos-1".into()] })
+            .await
+            .unwrap()
+            .into_inner();
+        let initial = updates.next().await.unwrap().unwrap();
+        assert_eq!(initial.health(), proto::HealthStatus::Unknown);
+
+        client
+            .heartbeat(proto::HeartbeatRequest {
+                pop_id: "pop-lagos-1".into(),
+                health: proto::HealthStatus::Healthy as i32,
+                cpu_usage: 42.5,
+                memory_usage: 61.0,
+                active_connections: 1200,
+            })
+            .await
+            .unwrap();
+
+        {
+            let controller = controller.read().await;
+            let pop = controller.get_pop_status("pop-lagos-1").unwrap();
+            assert_eq!(pop.health, HealthStatus::Healthy);
+            assert_eq!(pop.active_connections, 1200);
+            assert!(!pop.last_heartbeat.is_empty());
+        }
+        let live = updates.next().await.unwrap().unwrap();
+        assert_eq!(live.health(), proto::HealthStatus::Healthy);
+        assert_eq!(live.cpu_usage, 42.5);
+
+        let unknown = client
+            .heartbeat(proto::HeartbeatRequest { pop_id: "pop-nowhere".into(), ..Default::default() })
+            .await
+            .unwrap_err();
+        assert_eq!(unknown.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_distribute_policy_over_grpc() {
+        let config = ControllerConfig { regions: vec!["af-west".into(), "eu-west".into()], ..Default::default() };
+        let controller: SharedController = Arc::new(RwLock::new(CentralController::new(config)));
+        let mut client = in_process_client(controller.clone()).await;
+
+        let request = proto::DistributePolicyRequest {
+            policy: Some(proto::Policy {
+                id: "firewall-1".into(),
+                name: "Default Firewall".into(),
+                policy_type: proto::PolicyType::Firewall as i32,
+                rules: vec![1, 2, 3],
+                tenant_id: "tenant-1".into(),
+                ..Default::default()
+            }),
+        };
+        let report = client.distribute_policy(request.clone()).await.unwrap().into_inner();
+        assert_eq!(report.version, 1);
+        assert_eq!(report.succeeded, ["af-west", "eu-west"]);
+
+        // Same content again keeps the version
+        let again = client.distribute_policy(request).await.unwrap().into_inner();
+        assert_eq!(again.version, 1);
+        assert_eq!(controller.read().await.get_state().policies["firewall-1"].policy_type, PolicyType::Firewall);
+    }
+}
@@ -5,12 +5,15 @@
 //! - RegionalController: Regional PoP coordination
 //! - FailoverManager: High availability with lease-based failover
 //! - HealthMonitor: Real-time PoP health tracking
+//! - grpc: tonic API for PoP registration, heartbeats, and policy push
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicU8, Ordering};
 
+pub mod grpc;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // CORE TYPES
 // ═══════════════════════════════════════════════════════════════════════════
@@ -77,10 +80,55 @@ pub struct Policy {
     pub rules: Vec<u8>,  // Serialized rules
     pub tenant_id: String,
     pub target_pops: Vec<String>,
+    /// QoS priority applied to the tenant's tunnels (higher wins); other policy types ignore it
+    #[serde(default)]
+    pub priority: u8,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Canonical form of a `Policy`: fields declared in sorted key order and
+/// `target_pops` sorted, so equal content always serializes to equal bytes
+#[derive(Serialize)]
+struct CanonicalPolicy<'a> {
+    created_at: &'a str,
+    id: &'a str,
+    name: &'a str,
+    policy_type: PolicyType,
+    priority: u8,
+    rules: &'a [u8],
+    target_pops: Vec<&'a str>,
+    tenant_id: &'a str,
+    updated_at: &'a str,
+    version: u64,
+}
+
+impl Policy {
+    /// Stable serialization used for hashing: sorted keys, no whitespace
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut target_pops: Vec<&str> = self.target_pops.iter().map(String::as_str).collect();
+        target_pops.sort_unstable();
+        let canonical = CanonicalPolicy {
+            created_at: &self.created_at,
+            id: &self.id,
+            name: &self.name,
+            policy_type: self.policy_type,
+            priority: self.priority,
+            rules: &self.rules,
+            target_pops,
+            tenant_id: &self.tenant_id,
+            updated_at: &self.updated_at,
+            version: self.version,
+        };
+        serde_json::to_vec(&canonical).expect("policy serializes to JSON")
+    }
+    
+    /// Hex SHA-256 of the canonical form
+    pub fn content_hash(&self) -> String {
+        Sha256::digest(self.canonical_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
 /// PoP information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PopInfo {
@@ -112,6 +160,16 @@ pub struct Tunnel {
     pub client_location: String,
     pub status: TunnelStatus,
     pub bandwidth_limit_mbps: Option<u64>,
+    /// Set from the tenant's QoS policy; lower priorities are evicted first
+    #[serde(default)]
+    pub qos_priority: u8,
+}
+
+impl Tunnel {
+    /// Whether the tunnel currently holds bandwidth on its PoP
+    pub fn is_placed(&self) -> bool {
+        matches!(self.status, TunnelStatus::Active | TunnelStatus::Degraded)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -169,7 +227,20 @@ pub struct PolicyConfig {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RoutingConfig {
     pub convergence_timeout_ms: u64,
+    /// Route table entries per PoP (each terminating tunnel uses one); 0 = unlimited
     pub max_routes_per_pop: u32,
+    /// Active connections per PoP; 0 = unlimited
+    #[serde(default)]
+    pub max_connections_per_pop: u64,
+    /// Committed tunnel bandwidth allowed per unit of PoP bandwidth; 0 means 1.0 (no oversubscription)
+    #[serde(default)]
+    pub max_oversubscription_ratio: f64,
+}
+
+impl RoutingConfig {
+    pub fn oversubscription_ratio(&self) -> f64 {
+        if self.max_oversubscription_ratio > 0.0 { self.max_oversubscription_ratio } else { 1.0 }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -194,6 +265,21 @@ pub struct CentralController {
     config: ControllerConfig,
     state: GlobalState,
     regional_controllers: HashMap<Region, RegionalController>,
+    /// Regions still missing the current version of a partially-distributed policy
+    pending_regions: HashMap<String, Vec<Region>>,
+}
+
+/// Per-region outcome of pushing one policy version
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DistributionReport {
+    pub policy_id: String,
+    pub version: u64,
+    pub succeeded: Vec<Region>,
+    pub failed: Vec<(Region, String)>,
+}
+
+impl DistributionReport {
+    pub fn is_complete(&self) -> bool { self.failed.is_empty() }
 }
 
 impl CentralController {
@@ -203,31 +289,84 @@ impl CentralController {
             regional_controllers.insert(region.clone(), RegionalController::new(region.clone()));
         }
         
-        Self { config, state: GlobalState::default(), regional_controllers }
+        Self { config, state: GlobalState::default(), regional_controllers, pending_regions: HashMap::new() }
     }
     
-    /// Distribute policy to all PoPs
-    pub fn distribute_policy(&mut self, policy: Policy) -> Result<(), ControllerError> {
-        // Version the policy
-        let versioned = self.version_policy(policy);
+    /// Distribute policy to all PoPs. Every region is attempted; central state
+    /// keeps the new version and regions that failed are recorded for
+    /// `retry_distribution`.
+    pub fn distribute_policy(&mut self, policy: Policy) -> Result<DistributionReport, ControllerError> {
+        // Re-submitting the current content keeps the current version, so
+        // regional controllers see the same hash and skip it
+        let versioned = match self.state.policies.get(&policy.id) {
+            Some(current) if Self::same_content(current, &policy) => current.clone(),
+            _ => self.version_policy(policy),
+        };
         
         // Update central state
         self.state.policies.insert(versioned.id.clone(), versioned.clone());
         
+        if versioned.policy_type == PolicyType::QoS {
+            self.apply_qos_policy(&versioned);
+        }
+        
         // Distribute to regional controllers
-        for rc in self.regional_controllers.values_mut() {
-            rc.apply_policy(&versioned)?;
+        let regions: Vec<Region> = self.regional_controllers.keys().cloned().collect();
+        Ok(self.push_to_regions(&versioned, regions))
+    }
+    
+    /// Re-push a partially-distributed policy to the regions that failed
+    pub fn retry_distribution(&mut self, policy_id: &str) -> Result<DistributionReport, ControllerError> {
+        let policy = self.state.policies.get(policy_id).cloned()
+            .ok_or_else(|| ControllerError::PolicyDistributionFailed(format!("unknown policy {}", policy_id)))?;
+        let regions = self.pending_regions.get(policy_id).cloned().unwrap_or_default();
+        Ok(self.push_to_regions(&policy, regions))
+    }
+    
+    /// Regions that haven't applied the current version of a policy
+    pub fn pending_regions(&self, policy_id: &str) -> &[Region] {
+        self.pending_regions.get(policy_id).map_or(&[], Vec::as_slice)
+    }
+    
+    pub fn regional_controller_mut(&mut self, region: &Region) -> Option<&mut RegionalController> {
+        self.regional_controllers.get_mut(region)
+    }
+    
+    fn push_to_regions(&mut self, policy: &Policy, mut regions: Vec<Region>) -> DistributionReport {
+        regions.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut report = DistributionReport { policy_id: policy.id.clone(), version: policy.version, ..Default::default() };
+        
+        for region in regions {
+            let Some(rc) = self.regional_controllers.get_mut(&region) else { continue };
+            match rc.apply_policy(policy) {
+                Ok(_) => report.succeeded.push(region),
+                Err(e) => {
+                    tracing::warn!(policy_id = %policy.id, region = %region.0, error = %e, "policy distribution failed");
+                    report.failed.push((region, e.to_string()));
+                }
+            }
         }
         
-        Ok(())
+        if report.is_complete() {
+            self.pending_regions.remove(&policy.id);
+        } else {
+            let failed = report.failed.iter().map(|(r, _)| r.clone()).collect();
+            self.pending_regions.insert(policy.id.clone(), failed);
+        }
+        report
     }
     
     /// Handle PoP failure - reroute traffic
     pub fn handle_pop_failure(&mut self, pop_id: &str) -> Result<(), ControllerError> {
-        // Get affected tunnels
-        let affected_tunnels: Vec<Tunnel> = self.state.tunnels.values()
+        if let Some(pop) = self.state.pops.get_mut(pop_id) {
+            pop.health = HealthStatus::Unhealthy;
+        }
+        
+        // Get affected tunnels, in a stable order so spill-over is predictable
+        let mut affected_tunnels: Vec<Tunnel> = self.state.tunnels.values()
             .filter(|t| t.endpoints.contains(&pop_id.to_string()))
             .cloned().collect();
+        affected_tunnels.sort_by(|a, b| a.id.cmp(&b.id));
         
         // Reroute each tunnel
         for tunnel in affected_tunnels {
@@ -240,10 +379,141 @@ impl CentralController {
     
     /// Register a new PoP
     pub fn register_pop(&mut self, pop: PopInfo) -> Result<(), ControllerError> {
+        let max_connections = self.config.routing.max_connections_per_pop;
+        if max_connections > 0 && pop.active_connections > max_connections {
+            return Err(ControllerError::PopAtCapacity(pop.id));
+        }
         self.state.pops.insert(pop.id.clone(), pop);
         Ok(())
     }
     
+    /// Add a tunnel to global state
+    pub fn add_tunnel(&mut self, tunnel: Tunnel) {
+        self.state.tunnels.insert(tunnel.id.clone(), tunnel);
+    }
+    
+    /// Set the QoS priority of the policy's tenant tunnels (on its target PoPs, if any)
+    fn apply_qos_policy(&mut self, policy: &Policy) {
+        for tunnel in self.state.tunnels.values_mut() {
+            let on_target = policy.target_pops.is_empty()
+                || tunnel.endpoints.iter().any(|e| policy.target_pops.contains(e));
+            if tunnel.tenant_id == policy.tenant_id && on_target {
+                tunnel.qos_priority = policy.priority;
+            }
+        }
+    }
+    
+    /// Bandwidth committed to placed tunnels on a PoP
+    pub fn committed_bandwidth(&self, pop_id: &str) -> u64 {
+        self.tunnels_on(pop_id).iter().filter_map(|t| t.bandwidth_limit_mbps).sum()
+    }
+    
+    /// Bandwidth a PoP may commit after oversubscription
+    pub fn bandwidth_budget(&self, pop_id: &str) -> u64 {
+        self.state.pops.get(pop_id)
+            .map(|p| (p.bandwidth_mbps * self.config.routing.oversubscription_ratio()) as u64)
+            .unwrap_or(0)
+    }
+    
+    fn tunnels_on(&self, pop_id: &str) -> Vec<&Tunnel> {
+        self.state.tunnels.values()
+            .filter(|t| t.is_placed() && t.endpoints.iter().any(|e| e == pop_id))
+            .collect()
+    }
+    
+    fn fits_bandwidth(&self, pop_id: &str, tunnel: &Tunnel) -> bool {
+        let requested = tunnel.bandwidth_limit_mbps.unwrap_or(0);
+        self.committed_bandwidth(pop_id) + requested <= self.bandwidth_budget(pop_id)
+    }
+    
+    /// Lower-priority tunnels to evict from a PoP so `tunnel` fits, lowest
+    /// priority (then largest) first; `None` if eviction can't free enough
+    fn eviction_plan(&self, pop_id: &str, tunnel: &Tunnel) -> Option<Vec<String>> {
+        let requested = tunnel.bandwidth_limit_mbps.unwrap_or(0);
+        let budget = self.bandwidth_budget(pop_id);
+        let mut committed = self.committed_bandwidth(pop_id);
+        
+        let mut victims: Vec<&Tunnel> = self.tunnels_on(pop_id).into_iter()
+            .filter(|t| t.qos_priority < tunnel.qos_priority && t.id != tunnel.id)
+            .collect();
+        victims.sort_by(|a, b| {
+            a.qos_priority.cmp(&b.qos_priority)
+                .then_with(|| b.bandwidth_limit_mbps.cmp(&a.bandwidth_limit_mbps))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        
+        let mut evicted = Vec::new();
+        for victim in victims {
+            if committed + requested <= budget {
+                break;
+            }
+            committed -= victim.bandwidth_limit_mbps.unwrap_or(0);
+            evicted.push(victim.id.clone());
+        }
+        (committed + requested <= budget).then_some(evicted)
+    }
+    
+    /// Place a tunnel on the nearest healthy PoP with route, connection, and
+    /// bandwidth headroom. If none has headroom, lower-priority tunnels are
+    /// evicted from the nearest PoP where that frees enough bandwidth.
+    /// Returns the chosen PoP and the ids of evicted tunnels.
+    pub fn place_tunnel(&mut self, mut tunnel: Tunnel) -> Result<(String, Vec<String>), ControllerError> {
+        let (pop_id, evicted) = match self.find_nearest_healthy_pop(&tunnel) {
+            Ok(pop_id) => (pop_id, Vec::new()),
+            Err(_) => {
+                let mut pops: Vec<&PopInfo> = self.state.pops.values()
+                    .filter(|p| p.health.is_healthy() && self.has_capacity(&p.id))
+                    .collect();
+                if pops.is_empty() {
+                    return Err(ControllerError::NoHealthyPops);
+                }
+                pops.sort_by(|a, b| {
+                    a.latency_to(&tunnel.client_location)
+                        .partial_cmp(&b.latency_to(&tunnel.client_location))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                        .then_with(|| a.id.cmp(&b.id))
+                });
+                let nearest = pops[0].id.clone();
+                let plan = pops.iter().find_map(|p| self.eviction_plan(&p.id, &tunnel).map(|e| (p.id.clone(), e)));
+                plan.ok_or_else(|| ControllerError::BandwidthExceeded {
+                    pop_id: nearest.clone(),
+                    requested_mbps: tunnel.bandwidth_limit_mbps.unwrap_or(0),
+                    available_mbps: self.bandwidth_budget(&nearest).saturating_sub(self.committed_bandwidth(&nearest)),
+                })?
+            }
+        };
+        
+        for id in &evicted {
+            if let Some(victim) = self.state.tunnels.get_mut(id) {
+                tracing::warn!(tunnel_id = %id, pop_id = %pop_id, "evicting lower-priority tunnel");
+                victim.status = TunnelStatus::Inactive;
+                victim.endpoints.clear();
+            }
+        }
+        
+        tunnel.endpoints = vec![pop_id.clone()];
+        tunnel.status = TunnelStatus::Active;
+        self.state.tunnels.insert(tunnel.id.clone(), tunnel);
+        Ok((pop_id, evicted))
+    }
+    
+    /// Route table entries on a PoP: static routes plus one per terminating tunnel
+    pub fn route_count(&self, pop_id: &str) -> u32 {
+        let routes = self.state.routes.values().filter(|r| r.pop_id == pop_id).count();
+        let tunnels = self.state.tunnels.values().filter(|t| t.endpoints.iter().any(|e| e == pop_id)).count();
+        (routes + tunnels) as u32
+    }
+    
+    /// Whether a PoP can take one more tunnel within the route and connection caps
+    pub fn has_capacity(&self, pop_id: &str) -> bool {
+        let Some(pop) = self.state.pops.get(pop_id) else { return false };
+        let routing = &self.config.routing;
+        
+        let routes_ok = routing.max_routes_per_pop == 0 || self.route_count(pop_id) < routing.max_routes_per_pop;
+        let connections_ok = routing.max_connections_per_pop == 0 || pop.active_connections < routing.max_connections_per_pop;
+        routes_ok && connections_ok
+    }
+    
     /// Process PoP heartbeat
     pub fn process_heartbeat(&mut self, pop_id: &str, status: PopStatus) -> Result<(), ControllerError> {
         if let Some(pop) = self.state.pops.get_mut(pop_id) {
@@ -259,11 +529,24 @@ impl CentralController {
     /// Get current global state
     pub fn get_state(&self) -> &GlobalState { &self.state }
     
+    /// Get controller configuration
+    pub fn config(&self) -> &ControllerConfig { &self.config }
+    
     /// Get PoP status
     pub fn get_pop_status(&self, pop_id: &str) -> Option<&PopInfo> {
         self.state.pops.get(pop_id)
     }
     
+    /// Whether `incoming` matches `current` ignoring the fields versioning sets
+    fn same_content(current: &Policy, incoming: &Policy) -> bool {
+        let normalized = Policy {
+            version: current.version,
+            updated_at: current.updated_at.clone(),
+            ..incoming.clone()
+        };
+        normalized.content_hash() == current.content_hash()
+    }
+    
     fn version_policy(&self, mut policy: Policy) -> Policy {
         policy.version = self.state.policies.get(&policy.id)
             .map(|p| p.version + 1)
@@ -272,13 +555,17 @@ impl CentralController {
         policy
     }
     
+    /// Nearest healthy PoP with spare capacity; full PoPs are skipped so
+    /// traffic spills to the next nearest
     fn find_nearest_healthy_pop(&self, tunnel: &Tunnel) -> Result<String, ControllerError> {
         let mut candidates: Vec<_> = self.state.pops.values()
-            .filter(|p| p.health.is_healthy())
+            .filter(|p| p.health.is_healthy() && self.has_capacity(&p.id) && self.fits_bandwidth(&p.id, tunnel))
             .map(|p| (p.id.clone(), p.latency_to(&tunnel.client_location)))
             .collect();
         
-        candidates.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        candidates.sort_by(|a, b| {
+            a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.0.cmp(&b.0))
+        });
         
         candidates.first()
             .map(|(id, _)| id.clone())
@@ -286,6 +573,18 @@ impl CentralController {
     }
     
     fn reroute_tunnel(&mut self, tunnel_id: &str, new_pop: &str) -> Result<(), ControllerError> {
+        if !self.has_capacity(new_pop) {
+            return Err(ControllerError::PopAtCapacity(new_pop.into()));
+        }
+        if let Some(tunnel) = self.state.tunnels.get(tunnel_id) {
+            if !self.fits_bandwidth(new_pop, tunnel) {
+                return Err(ControllerError::BandwidthExceeded {
+                    pop_id: new_pop.into(),
+                    requested_mbps: tunnel.bandwidth_limit_mbps.unwrap_or(0),
+                    available_mbps: self.bandwidth_budget(new_pop).saturating_sub(self.committed_bandwidth(new_pop)),
+                });
+            }
+        }
         if let Some(tunnel) = self.state.tunnels.get_mut(tunnel_id) {
             tunnel.endpoints = vec![new_pop.into()];
             tunnel.status = TunnelStatus::Active;
@@ -312,22 +611,38 @@ pub struct RegionalController {
     region: Region,
     pops: Vec<String>,
     active_policies: HashMap<String, Policy>,
+    reachable: bool,
 }
 
 impl RegionalController {
     pub fn new(region: Region) -> Self {
-        Self { region, pops: Vec::new(), active_policies: HashMap::new() }
+        Self { region, pops: Vec::new(), active_policies: HashMap::new(), reachable: true }
     }
     
-    pub fn apply_policy(&mut self, policy: &Policy) -> Result<(), ControllerError> {
+    /// Mark the region's control channel up or down
+    pub fn set_reachable(&mut self, reachable: bool) { self.reachable = reachable; }
+    
+    /// Apply a policy; returns `false` without pushing when the identical
+    /// version is already active
+    pub fn apply_policy(&mut self, policy: &Policy) -> Result<bool, ControllerError> {
+        if !self.reachable {
+            return Err(ControllerError::PolicyDistributionFailed(format!("region {} unreachable", self.region.0)));
+        }
+        if let Some(active) = self.active_policies.get(&policy.id) {
+            if active.content_hash() == policy.content_hash() {
+                return Ok(false);
+            }
+        }
         self.active_policies.insert(policy.id.clone(), policy.clone());
         // In production: push to all PoPs via gRPC
-        Ok(())
+        Ok(true)
     }
     
     pub fn register_pop(&mut self, pop_id: String) { self.pops.push(pop_id); }
     
     pub fn get_region(&self) -> &Region { &self.region }
+    
+    pub fn active_policy(&self, policy_id: &str) -> Option<&Policy> { self.active_policies.get(policy_id) }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -491,6 +806,8 @@ impl HealthMonitor {
 #[derive(Debug, Clone)]
 pub enum ControllerError {
     NoHealthyPops,
+    PopAtCapacity(String),
+    BandwidthExceeded { pop_id: String, requested_mbps: u64, available_mbps: u64 },
     PolicyDistributionFailed(String),
     NoLease,
     ConnectionFailed(String),
@@ -500,6 +817,10 @@ impl std::fmt::Display for ControllerError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::NoHealthyPops => write!(f, "No healthy PoPs available"),
+            Self::PopAtCapacity(pop) => write!(f, "PoP {} is at capacity", pop),
+            Self::BandwidthExceeded { pop_id, requested_mbps, available_mbps } => write!(
+                f, "PoP {} cannot admit {} Mbps ({} Mbps available)", pop_id, requested_mbps, available_mbps
+            ),
             Self::PolicyDistributionFailed(e) => write!(f, "Policy distribution failed: {}", e),
             Self::NoLease => write!(f, "No active lease"),
             Self::ConnectionFailed(e) => write!(f, "Connection failed: {}", e),
@@ -536,13 +857,105 @@ mod tests {
         let policy = Policy {
             id: "firewall-1".into(), name: "Default Firewall".into(), version: 0,
             policy_type: PolicyType::Firewall, rules: vec![], tenant_id: "tenant-1".into(),
-            target_pops: vec![], created_at: chrono::Utc::now().to_rfc3339(), updated_at: String::new(),
+            target_pops: vec![], priority: 0, created_at: chrono::Utc::now().to_rfc3339(), updated_at: String::new(),
         };
         
         controller.distribute_policy(policy).unwrap();
         assert!(controller.get_state().policies.contains_key("firewall-1"));
     }
     
+    fn firewall_policy() -> Policy {
+        Policy {
+            id: "firewall-1".into(), name: "Default Firewall".into(), version: 3,
+            policy_type: PolicyType::Firewall, rules: vec![1, 2, 3], tenant_id: "tenant-1".into(),
+            target_pops: vec!["pop-b".into(), "pop-a".into()], priority: 0,
+            created_at: "2024-01-01T00:00:00+00:00".into(), updated_at: "2024-02-01T00:00:00+00:00".into(),
+        }
+    }
+    
+    #[test]
+    fn test_policy_content_hash() {
+        let policy = firewall_policy();
+        let identical = firewall_policy();
+        assert_eq!(policy.content_hash(), identical.content_hash());
+        assert_eq!(policy.content_hash().len(), 64);
+        
+        let touched = Policy { updated_at: "2024-03-01T00:00:00+00:00".into(), ..firewall_policy() };
+        assert_ne!(policy.content_hash(), touched.content_hash());
+        
+        // Target PoP order doesn't change the canonical form
+        let reordered = Policy { target_pops: vec!["pop-a".into(), "pop-b".into()], ..firewall_policy() };
+        assert_eq!(policy.canonical_bytes(), reordered.canonical_bytes());
+        
+        // Reserializing is stable, including after a JSON round trip
+        let round_tripped: Policy = serde_json::from_str(&serde_json::to_string(&policy).unwrap()).unwrap();
+        assert_eq!(policy.canonical_bytes(), round_tripped.canonical_bytes());
+        assert_eq!(
+            String::from_utf8(policy.canonical_bytes()).unwrap(),
+            r#"{"created_at":"2024-01-01T00:00:00+00:00","id":"firewall-1","name":"Default Firewall","policy_type":"Firewall","priority":0,"rules":[1,2,3],"target_pops":["pop-a","pop-b"],"tenant_id":"tenant-1","updated_at":"2024-02-01T00:00:00+00:00","version":3}"#
+        );
+    }
+    
+    #[test]
+    fn test_identical_policy_not_redistributed() {
+        let mut controller = CentralController::new(ControllerConfig::default());
+        controller.distribute_policy(firewall_policy()).unwrap();
+        let first = controller.get_state().policies["firewall-1"].clone();
+        
+        // Same content again keeps the version and regional controllers skip it
+        controller.distribute_policy(firewall_policy()).unwrap();
+        let second = controller.get_state().policies["firewall-1"].clone();
+        assert_eq!(second.version, first.version);
+        assert_eq!(second.content_hash(), first.content_hash());
+        
+        let mut rc = RegionalController::new("us-east".into());
+        assert!(rc.apply_policy(&second).unwrap());
+        assert!(!rc.apply_policy(&second).unwrap());
+        
+        // Changed rules bump the version
+        controller.distribute_policy(Policy { rules: vec![9], ..firewall_policy() }).unwrap();
+        assert_eq!(controller.get_state().policies["firewall-1"].version, first.version + 1);
+    }
+    
+    #[test]
+    fn test_distribution_reports_all_regions() {
+        let mut controller = CentralController::new(ControllerConfig::default());
+        let report = controller.distribute_policy(firewall_policy()).unwrap();
+        
+        assert!(report.is_complete());
+        assert_eq!(report.succeeded.len(), 4);
+        assert!(controller.pending_regions("firewall-1").is_empty());
+    }
+    
+    #[test]
+    fn test_partial_distribution_retries_failed_regions() {
+        let mut controller = CentralController::new(ControllerConfig::default());
+        let eu = Region::from("eu-west");
+        controller.regional_controller_mut(&eu).unwrap().set_reachable(false);
+        
+        let report = controller.distribute_policy(firewall_policy()).unwrap();
+        assert!(!report.is_complete());
+        assert_eq!(report.succeeded.len(), 3);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, eu);
+        assert_eq!(controller.pending_regions("firewall-1").to_vec(), vec![eu.clone()]);
+        
+        // Still down: retry only touches the failed region
+        let retry = controller.retry_distribution("firewall-1").unwrap();
+        assert!(retry.succeeded.is_empty());
+        assert_eq!(retry.failed.len(), 1);
+        
+        controller.regional_controller_mut(&eu).unwrap().set_reachable(true);
+        let retry = controller.retry_distribution("firewall-1").unwrap();
+        assert!(retry.is_complete());
+        assert_eq!(retry.succeeded, vec![eu.clone()]);
+        assert!(controller.pending_regions("firewall-1").is_empty());
+        assert_eq!(
+            controller.regional_controller_mut(&eu).unwrap().active_policy("firewall-1").unwrap().version,
+            report.version
+        );
+    }
+    
     #[test]
     fn test_failover_manager() {
         let peers = vec![PeerController { id: "node-2".into(), address: "10.0.0.2:50051".into(), last_heartbeat: None }];
@@ -593,6 +1006,131 @@ mod tests {
         assert_eq!(pop.cpu_usage, 50.0);
     }
     
+    fn pop(id: &str) -> PopInfo {
+        PopInfo {
+            id: id.into(), location: "Frankfurt".into(), region: "eu-west".into(),
+            health: HealthStatus::Healthy, active_connections: 0, cpu_usage: 10.0,
+            memory_usage: 10.0, bandwidth_mbps: 1000.0, last_heartbeat: String::new(),
+        }
+    }
+    
+    fn tunnel(id: &str, pop_id: &str) -> Tunnel {
+        Tunnel {
+            id: id.into(), tenant_id: "tenant-1".into(), name: id.into(), endpoints: vec![pop_id.into()],
+            client_location: "Berlin".into(), status: TunnelStatus::Active, bandwidth_limit_mbps: None,
+            qos_priority: 0,
+        }
+    }
+    
+    fn capped_controller(max_routes_per_pop: u32) -> CentralController {
+        let mut config = ControllerConfig::default();
+        config.routing.max_routes_per_pop = max_routes_per_pop;
+        CentralController::new(config)
+    }
+    
+    #[test]
+    fn test_failover_spills_to_next_pop_at_route_cap() {
+        let mut controller = capped_controller(2);
+        for id in ["pop-a", "pop-b", "pop-c"] {
+            controller.register_pop(pop(id)).unwrap();
+        }
+        for i in 0..3 {
+            controller.add_tunnel(tunnel(&format!("t{}", i), "pop-a"));
+        }
+        
+        controller.handle_pop_failure("pop-a").unwrap();
+        
+        assert_eq!(controller.route_count("pop-b"), 2);
+        assert_eq!(controller.route_count("pop-c"), 1);
+        assert_eq!(controller.get_state().tunnels["t2"].endpoints, vec!["pop-c".to_string()]);
+    }
+    
+    #[test]
+    fn test_full_mesh_reports_no_healthy_pops() {
+        let mut controller = capped_controller(1);
+        for id in ["pop-a", "pop-b"] {
+            controller.register_pop(pop(id)).unwrap();
+        }
+        controller.add_tunnel(tunnel("t0", "pop-a"));
+        controller.add_tunnel(tunnel("t1", "pop-b"));
+        
+        // pop-b's only slot is taken, so pop-a's tunnel has nowhere to go
+        assert!(matches!(controller.handle_pop_failure("pop-a"), Err(ControllerError::NoHealthyPops)));
+        assert!(matches!(controller.reroute_tunnel("t0", "pop-b"), Err(ControllerError::PopAtCapacity(_))));
+    }
+    
+    #[test]
+    fn test_connection_cap() {
+        let mut config = ControllerConfig::default();
+        config.routing.max_connections_per_pop = 1000;
+        let mut controller = CentralController::new(config);
+        
+        let mut busy = pop("pop-busy");
+        busy.active_connections = 1500;
+        assert!(matches!(controller.register_pop(busy.clone()), Err(ControllerError::PopAtCapacity(_))));
+        
+        busy.active_connections = 1000;
+        controller.register_pop(busy).unwrap();
+        controller.register_pop(pop("pop-idle")).unwrap();
+        controller.add_tunnel(tunnel("t0", "pop-old"));
+        controller.handle_pop_failure("pop-old").unwrap();
+        assert_eq!(controller.get_state().tunnels["t0"].endpoints, vec!["pop-idle".to_string()]);
+    }
+    
+    fn bandwidth_controller(ratio: f64) -> CentralController {
+        let mut config = ControllerConfig::default();
+        config.routing.max_oversubscription_ratio = ratio;
+        let mut controller = CentralController::new(config);
+        let mut small = pop("pop-a");
+        small.bandwidth_mbps = 100.0;
+        controller.register_pop(small).unwrap();
+        controller
+    }
+    
+    fn sized(id: &str, mbps: u64) -> Tunnel {
+        Tunnel { bandwidth_limit_mbps: Some(mbps), endpoints: vec![], status: TunnelStatus::Inactive, ..tunnel(id, "") }
+    }
+    
+    #[test]
+    fn test_oversubscription_rejected() {
+        let mut controller = bandwidth_controller(1.5);
+        
+        controller.place_tunnel(sized("t0", 100)).unwrap();
+        controller.place_tunnel(sized("t1", 50)).unwrap();
+        assert_eq!(controller.committed_bandwidth("pop-a"), 150);
+        
+        // 1.5 × 100 Mbps is fully committed
+        let err = controller.place_tunnel(sized("t2", 10)).unwrap_err();
+        assert!(matches!(err, ControllerError::BandwidthExceeded { requested_mbps: 10, available_mbps: 0, .. }));
+    }
+    
+    #[test]
+    fn test_qos_priority_drives_placement() {
+        let mut controller = bandwidth_controller(1.0);
+        controller.place_tunnel(sized("bulk", 80)).unwrap();
+        
+        let mut voice = sized("voice", 50);
+        voice.tenant_id = "tenant-voice".into();
+        controller.add_tunnel(voice.clone());
+        controller.distribute_policy(Policy {
+            id: "qos-voice".into(), name: "Voice first".into(), version: 0, policy_type: PolicyType::QoS,
+            rules: vec![], tenant_id: "tenant-voice".into(), target_pops: vec![], priority: 7,
+            created_at: String::new(), updated_at: String::new(),
+        }).unwrap();
+        let voice = controller.get_state().tunnels["voice"].clone();
+        assert_eq!(voice.qos_priority, 7);
+        
+        // Higher priority evicts the bulk tunnel; equal priority cannot
+        let (pop_id, evicted) = controller.place_tunnel(voice).unwrap();
+        assert_eq!(pop_id, "pop-a");
+        assert_eq!(evicted, vec!["bulk".to_string()]);
+        assert_eq!(controller.get_state().tunnels["bulk"].status, TunnelStatus::Inactive);
+        
+        let mut rival = sized("rival", 60);
+        rival.qos_priority = 7;
+        assert!(matches!(controller.place_tunnel(rival), Err(ControllerError::BandwidthExceeded { .. })));
+    }
+    
     #[test]
     fn test_regional_controller() {
         let mut rc = RegionalController::new(Region::from("eu-west"));
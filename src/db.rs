@@ -0,0 +1,164 @@
+//! Database Pools
+//!
+//! Writes always go to the primary. Read-only endpoints (lists, reports,
+//! analytics) can go to a read replica instead, taking load off the
+//! primary; with no replica configured they read from the primary too.
+//!
+//! A replica trails the primary by its replication lag, so each read
+//! endpoint says whether it tolerates stale results. One that must see the
+//! caller's own latest write, such as fetching a run just created, asks for
+//! `StaleReads::Never` and reads from the primary.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use reqwest::Url;
+
+use crate::config::AppConfig;
+
+/// Whether a read may see data a replication lag behind the primary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaleReads {
+    /// Must reflect every committed write; read from the primary
+    Never,
+    /// Results up to the replica's lag old are acceptable
+    Tolerated,
+}
+
+/// A connection pool to one database
+#[derive(Debug, Clone)]
+pub struct DbPool {
+    name: String,
+    url: Option<Url>,
+    // In real implementation, a sqlx PgPool connected to `url`
+    queries: Arc<AtomicU64>,
+}
+
+impl DbPool {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), url: None, queries: Arc::new(AtomicU64::new(0)) }
+    }
+
+    /// Database the pool connects to
+    pub fn with_url(mut self, url: Url) -> Self {
+        self.url = Some(url);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn url(&self) -> Option<&Url> {
+        self.url.as_ref()
+    }
+
+    /// Host the pool connects to, safe to log
+    pub fn host(&self) -> &str {
+        self.url.as_ref().and_then(|u| u.host_str()).unwrap_or("unconfigured")
+    }
+
+    /// Note a query run through this pool
+    pub fn record(&self, query_name: &str) {
+        self.queries.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(pool = %self.name, query = query_name, "database query");
+    }
+
+    /// Queries run through this pool so far
+    pub fn queries(&self) -> u64 {
+        self.queries.load(Ordering::Relaxed)
+    }
+}
+
+/// Primary pool plus an optional read replica
+#[derive(Debug, Clone)]
+pub struct DbPools {
+    primary: DbPool,
+    read: Option<DbPool>,
+}
+
+impl Default for DbPools {
+    fn default() -> Self {
+        Self::new(DbPool::new("primary"))
+    }
+}
+
+impl DbPools {
+    pub fn new(primary: DbPool) -> Self {
+        Self { primary, read: None }
+    }
+
+    pub fn with_read_replica(mut self, read: DbPool) -> Self {
+        self.read = Some(read);
+        self
+    }
+
+    /// Pools for `DATABASE_URL`, with a replica when `DATABASE_READ_URL`
+    /// is set
+    pub fn from_config(config: &AppConfig) -> Self {
+        let mut primary = DbPool::new("primary");
+        if let Some(url) = &config.database_url {
+            primary = primary.with_url(url.clone());
+        }
+        let pools = Self::new(primary);
+        match &config.database_read_url {
+            Some(url) => pools.with_read_replica(DbPool::new("read_replica").with_url(url.clone())),
+            None => pools,
+        }
+    }
+
+    pub fn has_read_replica(&self) -> bool {
+        self.read.is_some()
+    }
+
+    /// Pool for inserts, updates, and deletes
+    pub fn write(&self) -> &DbPool {
+        &self.primary
+    }
+
+    /// Pool for a read-only query: the replica when the endpoint tolerates
+    /// stale reads and one is configured, the primary otherwise
+    pub fn read(&self, stale_reads: StaleReads) -> &DbPool {
+        match (stale_reads, &self.read) {
+            (StaleReads::Tolerated, Some(read)) => read,
+            _ => &self.primary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_fall_back_to_primary_without_replica() {
+        let pools = DbPools::default();
+        assert!(!pools.has_read_replica());
+        assert_eq!(pools.read(StaleReads::Tolerated).name(), "primary");
+
+        let pools = pools.with_read_replica(DbPool::new("read_replica"));
+        assert_eq!(pools.read(StaleReads::Tolerated).name(), "read_replica");
+        assert_eq!(pools.read(StaleReads::Never).name(), "primary");
+        assert_eq!(pools.write().name(), "primary");
+    }
+
+    #[test]
+    fn test_pools_from_config_urls() {
+        let values = [
+            ("DATABASE_URL", "postgres://primary-db/hr"),
+            ("DATABASE_READ_URL", "postgres://replica-db/hr"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let config = AppConfig::from_values(&values).unwrap();
+
+        let pools = DbPools::from_config(&config);
+        assert_eq!(pools.write().host(), "primary-db");
+        assert_eq!(pools.read(StaleReads::Tolerated).host(), "replica-db");
+        assert_eq!(pools.read(StaleReads::Never).host(), "primary-db");
+
+        let pools = DbPools::from_config(&AppConfig::from_values(&Default::default()).unwrap());
+        assert!(!pools.has_read_replica());
+        assert_eq!(pools.write().host(), "unconfigured");
+    }
+}
@@ -0,0 +1,187 @@
+//! Document API Handlers
+//!
+//! Uploads are raw request bodies; the `Content-Type` header is the
+//! document's content type and the kind/filename come from the query string.
+//! The caller's `AuthContext` is expected in request extensions, inserted by
+//! the authentication layer.
+
+use axum::{
+    body::Bytes,
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::auth::AuthContext;
+use super::models::*;
+use super::service::{DocumentError, DocumentService};
+
+/// API Response wrapper
+#[derive(Debug, Serialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: Option<T>,
+    pub error: Option<String>,
+}
+
+impl<T: Serialize> ApiResponse<T> {
+    pub fn success(data: T) -> Self {
+        Self { success: true, data: Some(data), error: None }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self { success: false, data: None, error: Some(message.into()) }
+    }
+}
+
+/// Shared document state
+#[derive(Clone)]
+pub struct DocumentAppState {
+    pub document_service: DocumentService,
+}
+
+fn error_response(error: DocumentError) -> Response {
+    let status = match &error {
+        DocumentError::NotFound(_) | DocumentError::BlobMissing(_) => StatusCode::NOT_FOUND,
+        DocumentError::TooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+        DocumentError::ContentTypeNotAllowed(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        DocumentError::Forbidden => StatusCode::FORBIDDEN,
+        DocumentError::Validation(_) => StatusCode::BAD_REQUEST,
+        DocumentError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(ApiResponse::<()>::error(error.to_string()))).into_response()
+}
+
+/// Upload a document
+///
+/// POST /api/v1/employees/:employee_id/documents?kind=contract&filename=contract.pdf
+pub async fn upload_document(
+    State(state): State<DocumentAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(employee_id): Path<Uuid>,
+    Query(query): Query<UploadDocumentQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+
+    match state
+        .document_service
+        .upload(&auth, employee_id, query.kind, &query.filename, content_type, &body)
+        .await
+    {
+        Ok(document) => (StatusCode::CREATED, Json(ApiResponse::success(document))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// List an employee's documents
+///
+/// GET /api/v1/employees/:employee_id/documents
+pub async fn list_documents(
+    State(state): State<DocumentAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(employee_id): Path<Uuid>,
+) -> impl IntoResponse {
+    Json(ApiResponse::success(state.document_service.list(&auth, employee_id)))
+}
+
+/// Download document content
+///
+/// GET /api/v1/documents/:id/content
+pub async fn download_document(
+    State(state): State<DocumentAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    match state.document_service.download(&auth, id).await {
+        Ok((document, bytes)) => (
+            [
+                (header::CONTENT_TYPE, document.content_type),
+                (header::CONTENT_DISPOSITION, content_disposition(&document.filename)),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// `attachment` header for a stored filename. Quotes, backslashes, control
+/// and non-ASCII characters can't go in the quoted `filename`, so it gets an
+/// ASCII stand-in and the real name travels percent-encoded in the RFC 5987
+/// `filename*`.
+fn content_disposition(filename: &str) -> String {
+    let fallback: String = filename
+        .chars()
+        .map(|c| if (c.is_ascii_graphic() && c != '"' && c != '\\') || c == ' ' { c } else { '_' })
+        .collect();
+    let encoded: String = filename
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'!' | b'#' | b'$' | b'&' | b'+' | b'-' | b'.' | b'^' | b'_'
+            | b'`' | b'|' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect();
+    format!("attachment; filename=\"{}\"; filename*=UTF-8''{}", fallback, encoded)
+}
+
+/// Delete a document (HR)
+///
+/// DELETE /api/v1/documents/:id
+pub async fn delete_document(
+    State(state): State<DocumentAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    match state.document_service.delete(&auth, id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Create document routes
+pub fn document_routes(state: &DocumentAppState) -> axum::Router<DocumentAppState> {
+    use axum::routing::{delete, get};
+
+    // Let the service report oversized files; only guard against runaway bodies here
+    let body_limit = state.document_service.policy().max_size_bytes as usize + 1;
+
+    axum::Router::new()
+        .route("/employees/:employee_id/documents", get(list_documents).post(upload_document))
+        .route("/documents/:id/content", get(download_document))
+        .route("/documents/:id", delete(delete_document))
+        .layer(DefaultBodyLimit::max(body_limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_disposition_escapes_filename() {
+        assert_eq!(
+            content_disposition("contract.pdf"),
+            "attachment; filename=\"contract.pdf\"; filename*=UTF-8''contract.pdf"
+        );
+        // A quote or line break can't end the parameter or the header
+        let header = content_disposition("a\"; filename=evil.exe\r\nX-Injected: 1.pdf");
+        assert_eq!(
+            header,
+            "attachment; filename=\"a_; filename=evil.exe__X-Injected: 1.pdf\"; \
+             filename*=UTF-8''a%22%3B%20filename%3Devil.exe%0D%0AX-Injected%3A%201.pdf"
+        );
+        assert!(header::HeaderValue::from_str(&header).is_ok());
+        assert_eq!(
+            content_disposition("Ọ̀kọ́ contract.pdf"),
+            "attachment; filename=\"__k__ contract.pdf\"; filename*=UTF-8''%E1%BB%8C%CC%80k%E1%BB%8D%CC%81%20contract.pdf"
+        );
+    }
+}
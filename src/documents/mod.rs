@@ -0,0 +1,13 @@
+//! Employee Documents Module
+//!
+//! Attachment storage for IDs, contracts, tax forms, and photos with
+//! pluggable blob storage, upload validation, and role-based access.
+
+pub mod models;
+pub mod store;
+pub mod service;
+pub mod handlers;
+
+pub use models::*;
+pub use store::{BlobStore, FilesystemBlobStore};
+pub use service::{DocumentError, DocumentPolicy, DocumentService};
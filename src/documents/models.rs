@@ -0,0 +1,51 @@
+//! Document Models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Kind of employee document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentKind {
+    Photo,
+    IdDocument,
+    Contract,
+    OfferLetter,
+    TaxForm,
+    Certificate,
+    MedicalRecord,
+    Other,
+}
+
+impl DocumentKind {
+    /// Sensitive documents are only visible to the employee and HR
+    pub fn is_sensitive(&self) -> bool {
+        matches!(
+            self,
+            Self::IdDocument | Self::Contract | Self::OfferLetter | Self::TaxForm | Self::MedicalRecord
+        )
+    }
+}
+
+/// Stored document metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Document {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub employee_id: Uuid,
+    pub kind: DocumentKind,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub storage_key: String,
+    pub uploaded_by: Uuid,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+/// Query parameters accompanying a raw-body upload
+#[derive(Debug, Clone, Deserialize)]
+pub struct UploadDocumentQuery {
+    pub kind: DocumentKind,
+    pub filename: String,
+}
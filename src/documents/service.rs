@@ -0,0 +1,290 @@
+//! Document Service
+//!
+//! Upload validation, access control, and metadata indexing over a `BlobStore`.
+
+use std::sync::Arc;
+use chrono::Utc;
+use dashmap::DashMap;
+use uuid::Uuid;
+
+use crate::auth::{AuthContext, Permission};
+use super::models::*;
+use super::store::BlobStore;
+
+/// Document service errors
+#[derive(Debug, thiserror::Error)]
+pub enum DocumentError {
+    #[error("Document not found: {0}")]
+    NotFound(Uuid),
+
+    #[error("Document content missing from storage: {0}")]
+    BlobMissing(String),
+
+    #[error("File too large: {size} bytes exceeds limit of {limit}")]
+    TooLarge { size: u64, limit: u64 },
+
+    #[error("Content type not allowed: {0}")]
+    ContentTypeNotAllowed(String),
+
+    #[error("Not authorized to access documents for this employee")]
+    Forbidden,
+
+    #[error("Validation error: {0}")]
+    Validation(String),
+
+    #[error("Storage error: {0}")]
+    Storage(String),
+}
+
+/// Upload limits
+#[derive(Debug, Clone)]
+pub struct DocumentPolicy {
+    pub max_size_bytes: u64,
+    pub allowed_content_types: Vec<String>,
+}
+
+impl Default for DocumentPolicy {
+    fn default() -> Self {
+        Self {
+            max_size_bytes: 10 * 1024 * 1024, // 10 MB
+            allowed_content_types: [
+                "application/pdf",
+                "image/jpeg",
+                "image/png",
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        }
+    }
+}
+
+/// Document Service
+#[derive(Clone)]
+pub struct DocumentService {
+    store: Arc<dyn BlobStore>,
+    policy: DocumentPolicy,
+    // In real implementation, metadata lives in the database
+    index: Arc<DashMap<Uuid, Document>>,
+}
+
+impl DocumentService {
+    pub fn new(store: Arc<dyn BlobStore>) -> Self {
+        Self::with_policy(store, DocumentPolicy::default())
+    }
+
+    pub fn with_policy(store: Arc<dyn BlobStore>, policy: DocumentPolicy) -> Self {
+        Self { store, policy, index: Arc::new(DashMap::new()) }
+    }
+
+    pub fn policy(&self) -> &DocumentPolicy {
+        &self.policy
+    }
+
+    /// Validate and store a document for an employee
+    pub async fn upload(
+        &self,
+        auth: &AuthContext,
+        employee_id: Uuid,
+        kind: DocumentKind,
+        filename: &str,
+        content_type: &str,
+        bytes: &[u8],
+    ) -> Result<Document, DocumentError> {
+        if !auth.can_access_employee(employee_id) {
+            return Err(DocumentError::Forbidden);
+        }
+
+        let size = bytes.len() as u64;
+        if size > self.policy.max_size_bytes {
+            return Err(DocumentError::TooLarge { size, limit: self.policy.max_size_bytes });
+        }
+        if size == 0 {
+            return Err(DocumentError::Validation("File is empty".to_string()));
+        }
+
+        // Ignore parameters such as "; charset=binary"
+        let content_type = content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+        if !self.policy.allowed_content_types.contains(&content_type) {
+            return Err(DocumentError::ContentTypeNotAllowed(content_type));
+        }
+
+        let filename = filename.rsplit(['/', '\\']).next().unwrap_or("").trim();
+        if filename.is_empty() {
+            return Err(DocumentError::Validation("Filename is required".to_string()));
+        }
+
+        let id = Uuid::new_v4();
+        let storage_key = format!("{}/{}/{}", auth.tenant_id, employee_id, id);
+        self.store.put(&storage_key, bytes).await?;
+
+        let document = Document {
+            id,
+            tenant_id: auth.tenant_id,
+            employee_id,
+            kind,
+            filename: filename.to_string(),
+            content_type,
+            size_bytes: size,
+            storage_key,
+            uploaded_by: auth.user_id,
+            uploaded_at: Utc::now(),
+        };
+        self.index.insert(id, document.clone());
+
+        Ok(document)
+    }
+
+    /// Documents for an employee that the caller may see
+    pub fn list(&self, auth: &AuthContext, employee_id: Uuid) -> Vec<Document> {
+        let mut documents: Vec<Document> = self
+            .index
+            .iter()
+            .filter(|d| d.tenant_id == auth.tenant_id && d.employee_id == employee_id)
+            .filter(|d| Self::can_read(auth, d))
+            .map(|d| d.clone())
+            .collect();
+        documents.sort_by_key(|d| d.uploaded_at);
+        documents
+    }
+
+    /// Fetch a document and its content
+    pub async fn download(&self, auth: &AuthContext, document_id: Uuid) -> Result<(Document, Vec<u8>), DocumentError> {
+        let document = self
+            .index
+            .get(&document_id)
+            .map(|d| d.clone())
+            .filter(|d| d.tenant_id == auth.tenant_id)
+            .ok_or(DocumentError::NotFound(document_id))?;
+
+        if !Self::can_read(auth, &document) {
+            return Err(DocumentError::Forbidden);
+        }
+
+        let bytes = self.store.get(&document.storage_key).await?;
+        Ok((document, bytes))
+    }
+
+    /// Delete a document (HR only)
+    pub async fn delete(&self, auth: &AuthContext, document_id: Uuid) -> Result<(), DocumentError> {
+        if !auth.has_permission(Permission::EmployeeUpdate) {
+            return Err(DocumentError::Forbidden);
+        }
+        let (_, document) = self
+            .index
+            .remove(&document_id)
+            .filter(|(_, d)| d.tenant_id == auth.tenant_id)
+            .ok_or(DocumentError::NotFound(document_id))?;
+        self.store.delete(&document.storage_key).await
+    }
+
+    /// Sensitive documents: the employee themself or HR; others: anyone who can view employees
+    fn can_read(auth: &AuthContext, document: &Document) -> bool {
+        let is_owner = auth.employee_id == Some(document.employee_id);
+        if document.kind.is_sensitive() {
+            is_owner || auth.has_permission(Permission::EmployeeUpdate)
+        } else {
+            is_owner || auth.has_permission(Permission::EmployeeView)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+    use crate::documents::FilesystemBlobStore;
+
+    fn auth(role: Role, employee_id: Option<Uuid>, tenant_id: Uuid) -> AuthContext {
+        AuthContext {
+            user_id: Uuid::new_v4(),
+            tenant_id,
+            employee_id,
+            role,
+            permissions: role.permissions(),
+            department_id: None,
+        }
+    }
+
+    fn service() -> DocumentService {
+        let root = std::env::temp_dir().join(format!("sase-hr-docs-{}", Uuid::new_v4()));
+        DocumentService::with_policy(
+            Arc::new(FilesystemBlobStore::new(root)),
+            DocumentPolicy { max_size_bytes: 1024, ..Default::default() },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_upload_download_round_trip() {
+        let service = service();
+        let tenant = Uuid::new_v4();
+        let employee = Uuid::new_v4();
+        let hr = auth(Role::HrManager, None, tenant);
+
+        let doc = service
+            .upload(&hr, employee, DocumentKind::Contract, "contract.pdf", "application/pdf", b"%PDF-1.7 contract")
+            .await
+            .unwrap();
+        assert_eq!(doc.size_bytes, 17);
+
+        let (fetched, bytes) = service.download(&hr, doc.id).await.unwrap();
+        assert_eq!(fetched.filename, "contract.pdf");
+        assert_eq!(bytes, b"%PDF-1.7 contract");
+
+        // The employee can read their own contract
+        let own = auth(Role::Employee, Some(employee), tenant);
+        assert!(service.download(&own, doc.id).await.is_ok());
+        assert_eq!(service.list(&own, employee).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_file_rejected() {
+        let service = service();
+        let hr = auth(Role::HrManager, None, Uuid::new_v4());
+
+        let result = service
+            .upload(&hr, Uuid::new_v4(), DocumentKind::Photo, "me.png", "image/png", &[0u8; 2048])
+            .await;
+        assert!(matches!(result, Err(DocumentError::TooLarge { size: 2048, limit: 1024 })));
+    }
+
+    #[tokio::test]
+    async fn test_content_type_allowlist() {
+        let service = service();
+        let hr = auth(Role::HrManager, None, Uuid::new_v4());
+
+        let result = service
+            .upload(&hr, Uuid::new_v4(), DocumentKind::Other, "run.exe", "application/x-msdownload", b"MZ")
+            .await;
+        assert!(matches!(result, Err(DocumentError::ContentTypeNotAllowed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_sensitive_documents_hidden_from_colleagues() {
+        let service = service();
+        let tenant = Uuid::new_v4();
+        let employee = Uuid::new_v4();
+        let hr = auth(Role::HrManager, None, tenant);
+
+        let id_doc = service
+            .upload(&hr, employee, DocumentKind::IdDocument, "passport.jpg", "image/jpeg", b"jpeg")
+            .await
+            .unwrap();
+        service
+            .upload(&hr, employee, DocumentKind::Photo, "photo.jpg", "image/jpeg", b"jpeg")
+            .await
+            .unwrap();
+
+        let lead = auth(Role::TeamLead, Some(Uuid::new_v4()), tenant);
+        assert!(matches!(service.download(&lead, id_doc.id).await, Err(DocumentError::Forbidden)));
+
+        let visible = service.list(&lead, employee);
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].kind, DocumentKind::Photo);
+
+        // Other tenants see nothing
+        let outsider = auth(Role::HrManager, None, Uuid::new_v4());
+        assert!(matches!(service.download(&outsider, id_doc.id).await, Err(DocumentError::NotFound(_))));
+    }
+}
@@ -0,0 +1,84 @@
+//! Blob Storage
+//!
+//! Storage backends for document content. Metadata lives with the
+//! document service; stores only map keys to bytes.
+
+use std::path::{Path, PathBuf};
+use async_trait::async_trait;
+
+use super::service::DocumentError;
+
+/// Pluggable blob storage backend
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), DocumentError>;
+    async fn get(&self, key: &str) -> Result<Vec<u8>, DocumentError>;
+    async fn delete(&self, key: &str) -> Result<(), DocumentError>;
+}
+
+/// Stores blobs as files under a root directory
+#[derive(Debug, Clone)]
+pub struct FilesystemBlobStore {
+    root: PathBuf,
+}
+
+impl FilesystemBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolve a key to a path, refusing anything that could escape the root
+    fn path_for(&self, key: &str) -> Result<PathBuf, DocumentError> {
+        let valid = !key.is_empty()
+            && !key.starts_with('/')
+            && key.split('/').all(|part| !part.is_empty() && part != "." && part != "..");
+        if !valid {
+            return Err(DocumentError::Storage(format!("invalid storage key: {}", key)));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStore for FilesystemBlobStore {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), DocumentError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| DocumentError::Storage(e.to_string()))?;
+        }
+        tokio::fs::write(&path, bytes).await.map_err(|e| DocumentError::Storage(e.to_string()))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, DocumentError> {
+        let path = self.path_for(key)?;
+        tokio::fs::read(&path).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => DocumentError::BlobMissing(key.to_string()),
+            _ => DocumentError::Storage(e.to_string()),
+        })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), DocumentError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(DocumentError::Storage(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rejects_traversal_keys() {
+        let store = FilesystemBlobStore::new(std::env::temp_dir().join("sase-hr-blob-test"));
+        assert!(matches!(store.put("../escape", b"x").await, Err(DocumentError::Storage(_))));
+        assert!(matches!(store.get("/etc/passwd").await, Err(DocumentError::Storage(_))));
+    }
+}
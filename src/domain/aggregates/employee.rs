@@ -2,13 +2,16 @@
 //!
 //! Rich aggregate root for employee lifecycle management.
 
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::domain::value_objects::{EmployeeId, TaxId, PayRate, PayType, PayFrequency};
+use crate::domain::value_objects::{EmployeeId, PayFrequency, PayRate, PayType, TaxId, WorkingTime};
 use crate::domain::events::{DomainEvent, EmployeeEvent};
+use super::offboarding::OffboardingChecklist;
+use super::onboarding::{OnboardingChecklist, OnboardingTemplate};
 
 /// Employee aggregate root
 #[derive(Clone, Debug)]
@@ -17,12 +20,29 @@ pub struct Employee {
     employee_id: EmployeeId,
     status: EmploymentStatus,
     personal: PersonalInfo,
+    tax_id: Option<TaxId>,
+    /// Earlier employment record for the same person, set on rehire
+    previous_record_id: Option<String>,
+    /// Company within the tenant that employs this person
+    legal_entity_id: Option<Uuid>,
+    /// Whether and when a terminated employee may come back, set at termination
+    rehire_eligibility: RehireEligibility,
     employment: EmploymentInfo,
     compensation: CompensationInfo,
     benefits_elections: Vec<BenefitElection>,
     emergency_contacts: Vec<EmergencyContact>,
     documents: Vec<EmployeeDocument>,
     custom_fields: HashMap<String, serde_json::Value>,
+    pending_changes: Vec<PendingChange>,
+    department_history: Vec<DepartmentTransfer>,
+    /// Status changes in effective-date order, starting with the hire
+    status_history: Vec<StatusChangeRecord>,
+    onboarding: Option<OnboardingChecklist>,
+    offboarding: Option<OffboardingChecklist>,
+    archived_at: Option<DateTime<Utc>>,
+    /// Surviving record this duplicate was merged into
+    merged_into: Option<String>,
+    anonymized_at: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     events: Vec<DomainEvent>,
@@ -42,7 +62,7 @@ pub struct PersonalInfo {
     pub address: Option<AddressInfo>,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AddressInfo {
     pub street1: String,
     pub street2: Option<String>,
@@ -68,12 +88,40 @@ pub struct EmploymentInfo {
 #[derive(Clone, Debug, Default)]
 pub struct CompensationInfo {
     pub pay_rate: Option<PayRate>,
+    /// Standard and contracted weekly hours for hourly↔annual conversion
+    pub working_time: WorkingTime,
     pub effective_date: Option<NaiveDate>,
     pub bonus_eligible: bool,
+    /// Explicit overtime eligibility (non-exempt); `None` derives it from pay and employment type
+    pub overtime_eligible: Option<bool>,
+    pub bank_details: Option<BankDetails>,
     pub equity_grants: Vec<EquityGrant>,
     pub compensation_history: Vec<CompensationChange>,
 }
 
+/// Salary payment account
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BankDetails {
+    pub bank_name: String,
+    pub account_number: String,
+    pub account_name: String,
+}
+
+impl CompensationInfo {
+    /// The stored rate expressed per `target` period, e.g. a monthly salary
+    /// as an hourly rate. `standard_hours` is the weekly hours used for
+    /// hourly conversions. Unrounded; round for display.
+    pub fn as_frequency(&self, target: PayFrequency, standard_hours: Decimal) -> Option<Decimal> {
+        let working_time = WorkingTime::full_time(standard_hours, self.working_time.weeks_per_year());
+        let annual = self.pay_rate.as_ref()?.annual_amount_for(&working_time);
+        let periods = target.periods_per_year(&working_time);
+        if periods.is_zero() {
+            return None;
+        }
+        Some(annual / periods)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct EquityGrant {
     pub grant_date: NaiveDate,
@@ -114,7 +162,7 @@ pub enum CoverageLevel {
     Family,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EmergencyContact {
     pub name: String,
     pub relationship: String,
@@ -130,7 +178,8 @@ pub struct EmployeeDocument {
     pub uploaded_at: DateTime<Utc>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DocumentType {
     Resume,
     OfferLetter,
@@ -141,7 +190,42 @@ pub enum DocumentType {
     PerformanceReview,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Field update that can take effect on a future date
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmployeeChange {
+    JobTitle(String),
+    Address(AddressInfo),
+    Department(Option<String>),
+    Manager(Option<String>),
+    WorkEmail(String),
+    Phone(Option<String>),
+    PersonalEmail(Option<String>),
+    Gender(Option<String>),
+    EmergencyContacts(Vec<EmergencyContact>),
+    BankDetails(BankDetails),
+}
+
+/// Change scheduled for a future effective date
+#[derive(Clone, Debug)]
+pub struct PendingChange {
+    pub id: String,
+    pub change: EmployeeChange,
+    pub effective_date: NaiveDate,
+    pub scheduled_at: DateTime<Utc>,
+}
+
+/// Department move, kept so cost allocation can look back in time
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepartmentTransfer {
+    pub from_department_id: Option<String>,
+    pub to_department_id: String,
+    pub effective_date: NaiveDate,
+    pub reason: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EmploymentStatus {
     #[default]
     Active,
@@ -151,6 +235,188 @@ pub enum EmploymentStatus {
     Retired,
 }
 
+/// Whether a terminated employee may be rehired, and from when
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RehireEligibility {
+    pub rehire_eligible: bool,
+    /// End of a cooling-off period; no rehire before this date
+    pub rehire_not_before: Option<NaiveDate>,
+}
+
+impl Default for RehireEligibility {
+    fn default() -> Self {
+        Self { rehire_eligible: true, rehire_not_before: None }
+    }
+}
+
+impl RehireEligibility {
+    pub fn ineligible() -> Self {
+        Self { rehire_eligible: false, rehire_not_before: None }
+    }
+
+    pub fn not_before(date: NaiveDate) -> Self {
+        Self { rehire_eligible: true, rehire_not_before: Some(date) }
+    }
+
+    /// Whether a rehire effective `rehire_date` is allowed
+    pub fn check(&self, rehire_date: NaiveDate) -> Result<(), EmployeeError> {
+        if !self.rehire_eligible {
+            return Err(EmployeeError::NotRehireEligible);
+        }
+        match self.rehire_not_before {
+            Some(not_before) if rehire_date < not_before => Err(EmployeeError::RehireWaitingPeriod { not_before }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// One allowed employment status move
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StatusTransitionRule {
+    pub from: EmploymentStatus,
+    pub to: EmploymentStatus,
+    /// Only allowed through an explicit rehire
+    pub requires_rehire: bool,
+}
+
+impl StatusTransitionRule {
+    pub const fn new(from: EmploymentStatus, to: EmploymentStatus) -> Self {
+        Self { from, to, requires_rehire: false }
+    }
+
+    pub const fn rehire(from: EmploymentStatus, to: EmploymentStatus) -> Self {
+        Self { from, to, requires_rehire: true }
+    }
+}
+
+/// Allowed employment status moves. `standard()` is the default lifecycle;
+/// tenants with stricter policies start from it and `deny` moves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusTransitionRules {
+    rules: Vec<StatusTransitionRule>,
+}
+
+impl StatusTransitionRules {
+    pub fn standard() -> Self {
+        use EmploymentStatus::*;
+        Self {
+            rules: vec![
+                StatusTransitionRule::new(Active, OnLeave),
+                StatusTransitionRule::new(OnLeave, Active),
+                StatusTransitionRule::new(Active, Suspended),
+                StatusTransitionRule::new(Suspended, Active),
+                StatusTransitionRule::new(Active, Terminated),
+                StatusTransitionRule::new(OnLeave, Terminated),
+                StatusTransitionRule::new(Suspended, Terminated),
+                StatusTransitionRule::new(Active, Retired),
+                StatusTransitionRule::rehire(Terminated, Active),
+            ],
+        }
+    }
+
+    pub fn allow(&mut self, rule: StatusTransitionRule) -> &mut Self {
+        self.deny(rule.from, rule.to);
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn deny(&mut self, from: EmploymentStatus, to: EmploymentStatus) -> &mut Self {
+        self.rules.retain(|r| r.from != from || r.to != to);
+        self
+    }
+
+    pub fn allowed_from(&self, from: EmploymentStatus) -> impl Iterator<Item = &StatusTransitionRule> {
+        self.rules.iter().filter(move |r| r.from == from)
+    }
+
+    /// Whether `from -> to` is allowed, `rehire` marking an explicit rehire
+    pub fn check(&self, from: EmploymentStatus, to: EmploymentStatus, rehire: bool) -> Result<(), EmployeeError> {
+        if from == EmploymentStatus::Terminated && to == EmploymentStatus::Terminated {
+            return Err(EmployeeError::AlreadyTerminated);
+        }
+        match self.allowed_from(from).find(|r| r.to == to) {
+            Some(rule) if rule.requires_rehire && !rehire => Err(EmployeeError::RehireRequired),
+            Some(_) => Ok(()),
+            None => Err(EmployeeError::IllegalStatusTransition { from, to }),
+        }
+    }
+}
+
+impl Default for StatusTransitionRules {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// A status the employee entered, why, and from when
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusChangeRecord {
+    pub status: EmploymentStatus,
+    /// Leave type, suspension or termination reason, or the lifecycle step
+    pub reason: String,
+    pub effective_date: NaiveDate,
+    pub note: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// A requested status move and what it carries into the domain event
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StatusChange {
+    StartLeave { leave_type: String, start_date: NaiveDate },
+    EndLeave { return_date: NaiveDate },
+    Suspend { effective_date: NaiveDate, reason: String },
+    Reinstate { effective_date: NaiveDate },
+    Terminate { termination_date: NaiveDate, reason: String, rehire: RehireEligibility },
+    Retire { retirement_date: NaiveDate },
+    Rehire { rehire_date: NaiveDate },
+}
+
+impl StatusChange {
+    pub fn target(&self) -> EmploymentStatus {
+        match self {
+            Self::StartLeave { .. } => EmploymentStatus::OnLeave,
+            Self::EndLeave { .. } | Self::Reinstate { .. } | Self::Rehire { .. } => EmploymentStatus::Active,
+            Self::Suspend { .. } => EmploymentStatus::Suspended,
+            Self::Terminate { .. } => EmploymentStatus::Terminated,
+            Self::Retire { .. } => EmploymentStatus::Retired,
+        }
+    }
+
+    pub fn effective_date(&self) -> NaiveDate {
+        match self {
+            Self::StartLeave { start_date: date, .. }
+            | Self::EndLeave { return_date: date }
+            | Self::Suspend { effective_date: date, .. }
+            | Self::Reinstate { effective_date: date }
+            | Self::Terminate { termination_date: date, .. }
+            | Self::Retire { retirement_date: date }
+            | Self::Rehire { rehire_date: date } => *date,
+        }
+    }
+
+    /// Why the status changed, as recorded in the history
+    pub fn reason(&self) -> String {
+        match self {
+            Self::StartLeave { leave_type, .. } => leave_type.clone(),
+            Self::Suspend { reason, .. } | Self::Terminate { reason, .. } => reason.clone(),
+            Self::EndLeave { .. } => "Returned from leave".to_string(),
+            Self::Reinstate { .. } => "Reinstated".to_string(),
+            Self::Retire { .. } => "Retired".to_string(),
+            Self::Rehire { .. } => "Rehired".to_string(),
+        }
+    }
+
+    /// Status the change must start from, when it only makes sense from one
+    fn expected_source(&self) -> Option<EmploymentStatus> {
+        match self {
+            Self::EndLeave { .. } => Some(EmploymentStatus::OnLeave),
+            Self::Reinstate { .. } => Some(EmploymentStatus::Suspended),
+            Self::Rehire { .. } => Some(EmploymentStatus::Terminated),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum EmploymentType {
     #[default]
@@ -200,11 +466,29 @@ impl Employee {
                 employment_type: EmploymentType::FullTime,
                 ..Default::default()
             },
+            tax_id: None,
+            previous_record_id: None,
+            legal_entity_id: None,
+            rehire_eligibility: RehireEligibility::default(),
             compensation: CompensationInfo::default(),
             benefits_elections: vec![],
             emergency_contacts: vec![],
             documents: vec![],
             custom_fields: HashMap::new(),
+            pending_changes: vec![],
+            department_history: vec![],
+            status_history: vec![StatusChangeRecord {
+                status: EmploymentStatus::Active,
+                reason: "Hired".to_string(),
+                effective_date: hire_date,
+                note: None,
+                recorded_at: now,
+            }],
+            onboarding: Some(OnboardingChecklist::generate(&OnboardingTemplate::default())),
+            offboarding: None,
+            archived_at: None,
+            merged_into: None,
+            anonymized_at: None,
             created_at: now,
             updated_at: now,
             events: vec![],
@@ -223,8 +507,25 @@ impl Employee {
     pub fn employee_id(&self) -> &EmployeeId { &self.employee_id }
     pub fn status(&self) -> &EmploymentStatus { &self.status }
     pub fn personal(&self) -> &PersonalInfo { &self.personal }
+    pub fn tax_id(&self) -> Option<&TaxId> { self.tax_id.as_ref() }
+    pub fn previous_record_id(&self) -> Option<&str> { self.previous_record_id.as_deref() }
+    pub fn legal_entity_id(&self) -> Option<Uuid> { self.legal_entity_id }
+    pub fn rehire_eligibility(&self) -> &RehireEligibility { &self.rehire_eligibility }
     pub fn employment(&self) -> &EmploymentInfo { &self.employment }
     pub fn compensation(&self) -> &CompensationInfo { &self.compensation }
+    pub fn emergency_contacts(&self) -> &[EmergencyContact] { &self.emergency_contacts }
+    pub fn documents(&self) -> &[EmployeeDocument] { &self.documents }
+    pub fn custom_fields(&self) -> &HashMap<String, serde_json::Value> { &self.custom_fields }
+    pub fn created_at(&self) -> DateTime<Utc> { self.created_at }
+    pub fn pending_changes(&self) -> &[PendingChange] { &self.pending_changes }
+    pub fn department_history(&self) -> &[DepartmentTransfer] { &self.department_history }
+    pub fn status_history(&self) -> &[StatusChangeRecord] { &self.status_history }
+    pub fn onboarding(&self) -> Option<&OnboardingChecklist> { self.onboarding.as_ref() }
+    pub fn offboarding(&self) -> Option<&OffboardingChecklist> { self.offboarding.as_ref() }
+    pub fn is_archived(&self) -> bool { self.archived_at.is_some() }
+    pub fn merged_into(&self) -> Option<&str> { self.merged_into.as_deref() }
+    pub fn benefits_elections(&self) -> &[BenefitElection] { &self.benefits_elections }
+    pub fn is_anonymized(&self) -> bool { self.anonymized_at.is_some() }
     pub fn full_name(&self) -> String { 
         format!("{} {}", self.personal.first_name, self.personal.last_name) 
     }
@@ -252,6 +553,44 @@ impl Employee {
         }));
     }
     
+    /// Set standard and contracted working hours
+    pub fn set_working_time(&mut self, working_time: WorkingTime) {
+        self.compensation.working_time = working_time;
+        self.touch();
+    }
+    
+    /// Actual annual pay at the contracted hours
+    pub fn annual_pay(&self) -> Option<Decimal> {
+        let compensation = &self.compensation;
+        Some(compensation.pay_rate.as_ref()?.annual_amount_for(&compensation.working_time))
+    }
+    
+    /// Whether the employee is non-exempt and earns overtime. Without an
+    /// explicit flag: hourly, part-time, temporary, and intern staff are
+    /// eligible; full-time salaried staff and contractors are not.
+    pub fn overtime_eligible(&self) -> bool {
+        if let Some(eligible) = self.compensation.overtime_eligible {
+            return eligible;
+        }
+        let hourly = self.compensation.pay_rate.as_ref().is_some_and(|r| *r.pay_type() == PayType::Hourly);
+        match self.employment.employment_type {
+            EmploymentType::Contractor => false,
+            EmploymentType::PartTime | EmploymentType::Temporary | EmploymentType::Intern => true,
+            EmploymentType::FullTime => hourly,
+        }
+    }
+    
+    /// Set or clear the explicit overtime exemption flag
+    pub fn set_overtime_eligible(&mut self, eligible: Option<bool>) {
+        self.compensation.overtime_eligible = eligible;
+        self.touch();
+    }
+    
+    /// Annual pay scaled to a full-time week, for comparing against full-time bands
+    pub fn full_time_annual_pay(&self) -> Option<Decimal> {
+        Some(self.compensation.working_time.full_time_equivalent(self.annual_pay()?))
+    }
+    
     /// Promote employee
     pub fn promote(&mut self, new_title: impl Into<String>, new_rate: Option<PayRate>) {
         let old_title = self.employment.job_title.clone();
@@ -277,56 +616,500 @@ impl Employee {
         self.touch();
     }
     
-    /// Put on leave
-    pub fn start_leave(&mut self) -> Result<(), EmployeeError> {
-        if self.status != EmploymentStatus::Active {
+    /// Move to another department on an effective date, keeping history
+    ///
+    /// The department field follows the same rules as `update`: a future
+    /// effective date is scheduled rather than applied. A transfer dated
+    /// before one already recorded slots into the history: the later move
+    /// now leaves the new department, and the current department is left
+    /// to it.
+    pub fn transfer_department(
+        &mut self,
+        to_department_id: impl Into<String>,
+        effective_date: NaiveDate,
+        reason: impl Into<String>,
+    ) -> Result<DepartmentTransfer, EmployeeError> {
+        if self.status == EmploymentStatus::Terminated {
+            return Err(EmployeeError::AlreadyTerminated);
+        }
+        
+        let to_department_id = to_department_id.into();
+        let from_department_id = self.department_on(effective_date).map(str::to_string);
+        if from_department_id.as_deref() == Some(to_department_id.as_str()) {
             return Err(EmployeeError::InvalidStateTransition);
         }
-        self.status = EmploymentStatus::OnLeave;
+        
+        let position = self.department_history.partition_point(|t| t.effective_date <= effective_date);
+        if let Some(successor) = self.department_history.get(position) {
+            // The later move would then go nowhere
+            if successor.to_department_id == to_department_id {
+                return Err(EmployeeError::InvalidStateTransition);
+            }
+        }
+        
+        let transfer = DepartmentTransfer {
+            from_department_id,
+            to_department_id,
+            effective_date,
+            reason: reason.into(),
+            recorded_at: Utc::now(),
+        };
+        self.department_history.insert(position, transfer.clone());
+        match self.department_history.get_mut(position + 1) {
+            Some(successor) => successor.from_department_id = Some(transfer.to_department_id.clone()),
+            None => {
+                self.update(EmployeeChange::Department(Some(transfer.to_department_id.clone())), Some(effective_date));
+            }
+        }
+        
+        self.raise_event(DomainEvent::Employee(EmployeeEvent::Transferred {
+            employee_id: self.employee_id.clone(),
+            from_department_id: transfer.from_department_id.clone(),
+            to_department_id: transfer.to_department_id.clone(),
+            effective_date,
+            reason: transfer.reason.clone(),
+        }));
+        
+        Ok(transfer)
+    }
+    
+    /// Department in effect on a given date
+    pub fn department_on(&self, date: NaiveDate) -> Option<&str> {
+        let Some(first) = self.department_history.first() else {
+            return self.employment.department_id.as_deref();
+        };
+        self.department_history
+            .iter()
+            .take_while(|t| t.effective_date <= date)
+            .last()
+            .map(|t| Some(t.to_department_id.as_str()))
+            .unwrap_or(first.from_department_id.as_deref())
+    }
+    
+    /// Days of a pay period spent in each department, in date order
+    pub fn department_allocation(&self, period_start: NaiveDate, period_end: NaiveDate) -> Vec<(Option<String>, i64)> {
+        let mut allocation: Vec<(Option<String>, i64)> = vec![];
+        for day in period_start.iter_days().take_while(|d| *d <= period_end) {
+            let department = self.department_on(day).map(str::to_string);
+            match allocation.last_mut() {
+                Some((current, days)) if *current == department => *days += 1,
+                _ => allocation.push((department, 1)),
+            }
+        }
+        allocation
+    }
+    
+    /// Update a field, immediately or on a future effective date
+    ///
+    /// Changes dated today or earlier (or undated) apply now; later ones are
+    /// held until `apply_pending_changes` reaches their effective date.
+    /// Returns the pending change id when the update was scheduled.
+    pub fn update(&mut self, change: EmployeeChange, effective_date: Option<NaiveDate>) -> Option<String> {
+        let today = Utc::now().date_naive();
+        match effective_date {
+            Some(date) if date > today => {
+                let id = Uuid::new_v4().to_string();
+                self.pending_changes.push(PendingChange {
+                    id: id.clone(),
+                    change,
+                    effective_date: date,
+                    scheduled_at: Utc::now(),
+                });
+                self.touch();
+                Some(id)
+            }
+            _ => {
+                self.apply_change(change);
+                None
+            }
+        }
+    }
+    
+    /// Drop a scheduled change before it takes effect
+    pub fn cancel_pending_change(&mut self, change_id: &str) -> Result<(), EmployeeError> {
+        let before = self.pending_changes.len();
+        self.pending_changes.retain(|c| c.id != change_id);
+        if self.pending_changes.len() == before {
+            return Err(EmployeeError::NotFound);
+        }
         self.touch();
         Ok(())
     }
     
-    /// Return from leave
-    pub fn end_leave(&mut self) -> Result<(), EmployeeError> {
-        if self.status != EmploymentStatus::OnLeave {
-            return Err(EmployeeError::InvalidStateTransition);
+    /// Apply every pending change effective on or before `as_of`, oldest
+    /// first, and bring the status up to any status change now in force
+    pub fn apply_pending_changes(&mut self, as_of: NaiveDate) -> usize {
+        let (mut due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending_changes)
+            .into_iter()
+            .partition(|c| c.effective_date <= as_of);
+        self.pending_changes = pending;
+        
+        due.sort_by_key(|c| c.effective_date);
+        let mut applied = due.len();
+        for pending in due {
+            self.apply_change(pending.change);
+        }
+        if let Some(status) = self.status_on(as_of).filter(|s| *s != self.status) {
+            self.status = status;
+            self.touch();
+            applied += 1;
+        }
+        applied
+    }
+    
+    fn apply_change(&mut self, change: EmployeeChange) {
+        match change {
+            EmployeeChange::JobTitle(title) => {
+                let old_title = std::mem::replace(&mut self.employment.job_title, title);
+                self.raise_event(DomainEvent::Employee(EmployeeEvent::Promoted {
+                    employee_id: self.employee_id.clone(),
+                    old_title,
+                    new_title: self.employment.job_title.clone(),
+                }));
+            }
+            EmployeeChange::Address(address) => self.personal.address = Some(address),
+            EmployeeChange::Department(department_id) => self.employment.department_id = department_id,
+            EmployeeChange::Manager(manager_id) => self.employment.manager_id = manager_id,
+            EmployeeChange::WorkEmail(email) => self.employment.work_email = email,
+            EmployeeChange::Phone(phone) => self.personal.phone = phone,
+            EmployeeChange::PersonalEmail(email) => self.personal.personal_email = email,
+            EmployeeChange::Gender(gender) => self.personal.gender = gender,
+            EmployeeChange::EmergencyContacts(contacts) => self.emergency_contacts = contacts,
+            EmployeeChange::BankDetails(details) => self.compensation.bank_details = Some(details),
+        }
+        self.touch();
+    }
+    
+    /// Move to a new employment status under `rules`, raising the matching
+    /// event. Illegal moves leave the employee untouched.
+    pub fn change_status(&mut self, change: StatusChange, rules: &StatusTransitionRules) -> Result<(), EmployeeError> {
+        self.change_status_with_note(change, rules, None)
+    }
+
+    /// Move to a new employment status, keeping `note` on the history entry.
+    /// A future-dated change is recorded straight away but only becomes the
+    /// current status once `apply_pending_changes` reaches its date.
+    pub fn change_status_with_note(
+        &mut self,
+        change: StatusChange,
+        rules: &StatusTransitionRules,
+        note: Option<String>,
+    ) -> Result<(), EmployeeError> {
+        let from = self.status_history.last().map_or(self.status, |r| r.status);
+        let to = change.target();
+        if change.expected_source().is_some_and(|source| source != from) {
+            return Err(EmployeeError::IllegalStatusTransition { from, to });
+        }
+        rules.check(from, to, matches!(change, StatusChange::Rehire { .. }))?;
+        if let StatusChange::Rehire { rehire_date } = &change {
+            self.rehire_eligibility.check(*rehire_date)?;
+        }
+
+        let effective_date = change.effective_date();
+        if let Some(latest) = self.status_history.last().filter(|r| r.effective_date > effective_date) {
+            return Err(EmployeeError::StatusChangeBackdated { latest: latest.effective_date });
+        }
+        let record = StatusChangeRecord { status: to, reason: change.reason(), effective_date, note, recorded_at: Utc::now() };
+
+        let employee_id = self.employee_id.clone();
+        let event = match change {
+            StatusChange::StartLeave { leave_type, start_date } => {
+                EmployeeEvent::OnLeaveStarted { employee_id, leave_type, start_date }
+            }
+            StatusChange::EndLeave { return_date } => EmployeeEvent::OnLeaveEnded { employee_id, return_date },
+            StatusChange::Suspend { effective_date, reason } => {
+                EmployeeEvent::Suspended { employee_id, effective_date, reason }
+            }
+            StatusChange::Reinstate { effective_date } => EmployeeEvent::Reinstated { employee_id, effective_date },
+            StatusChange::Terminate { termination_date, reason, rehire } => {
+                self.employment.termination_date = Some(termination_date);
+                self.rehire_eligibility = rehire;
+                let country = self.personal.address.as_ref().map(|a| a.country.as_str());
+                self.offboarding = Some(OffboardingChecklist::generate(country, &self.employment.employment_type));
+                EmployeeEvent::Terminated { employee_id, termination_date, reason }
+            }
+            StatusChange::Retire { retirement_date } => {
+                self.employment.termination_date = Some(retirement_date);
+                EmployeeEvent::Retired { employee_id, retirement_date }
+            }
+            StatusChange::Rehire { rehire_date } => {
+                self.employment.termination_date = None;
+                self.offboarding = None;
+                self.rehire_eligibility = RehireEligibility::default();
+                EmployeeEvent::Rehired { employee_id, rehire_date }
+            }
+        };
+
+        self.status_history.push(record);
+        if effective_date <= Utc::now().date_naive() {
+            self.status = to;
         }
-        self.status = EmploymentStatus::Active;
         self.touch();
+        self.raise_event(DomainEvent::Employee(event));
         Ok(())
     }
+
+    /// The change in force on `date`: the latest one effective on or before it
+    pub fn status_change_on(&self, date: NaiveDate) -> Option<&StatusChangeRecord> {
+        self.status_history.iter().take_while(|r| r.effective_date <= date).last()
+    }
+
+    /// Employment status on `date`; `None` before the hire
+    pub fn status_on(&self, date: NaiveDate) -> Option<EmploymentStatus> {
+        self.status_change_on(date).map(|r| r.status)
+    }
+    
+    /// Put on leave
+    pub fn start_leave(&mut self, leave_type: impl Into<String>, start_date: NaiveDate) -> Result<(), EmployeeError> {
+        self.change_status(
+            StatusChange::StartLeave { leave_type: leave_type.into(), start_date },
+            &StatusTransitionRules::standard(),
+        )
+    }
+    
+    /// Return from leave
+    pub fn end_leave(&mut self, return_date: NaiveDate) -> Result<(), EmployeeError> {
+        self.change_status(StatusChange::EndLeave { return_date }, &StatusTransitionRules::standard())
+    }
     
-    /// Terminate employment
+    /// Suspend pending investigation or disciplinary action
+    pub fn suspend(&mut self, effective_date: NaiveDate, reason: impl Into<String>) -> Result<(), EmployeeError> {
+        self.change_status(
+            StatusChange::Suspend { effective_date, reason: reason.into() },
+            &StatusTransitionRules::standard(),
+        )
+    }
+    
+    /// Lift a suspension
+    pub fn reinstate(&mut self, effective_date: NaiveDate) -> Result<(), EmployeeError> {
+        self.change_status(StatusChange::Reinstate { effective_date }, &StatusTransitionRules::standard())
+    }
+    
+    /// Terminate employment; the employee stays eligible for rehire
     pub fn terminate(&mut self, termination_date: NaiveDate, reason: impl Into<String>) -> Result<(), EmployeeError> {
-        if self.status == EmploymentStatus::Terminated {
-            return Err(EmployeeError::AlreadyTerminated);
+        self.terminate_with_rehire(termination_date, reason, RehireEligibility::default())
+    }
+    
+    /// Terminate for cause (misconduct, gross negligence); not rehireable
+    pub fn terminate_for_cause(&mut self, termination_date: NaiveDate, reason: impl Into<String>) -> Result<(), EmployeeError> {
+        self.terminate_with_rehire(termination_date, reason, RehireEligibility::ineligible())
+    }
+    
+    /// Terminate with explicit rehire terms, e.g. a cooling-off period
+    pub fn terminate_with_rehire(
+        &mut self,
+        termination_date: NaiveDate,
+        reason: impl Into<String>,
+        rehire: RehireEligibility,
+    ) -> Result<(), EmployeeError> {
+        self.change_status(
+            StatusChange::Terminate { termination_date, reason: reason.into(), rehire },
+            &StatusTransitionRules::standard(),
+        )
+    }
+    
+    /// Bring a terminated employee back; the only way out of `Terminated`
+    pub fn rehire(&mut self, rehire_date: NaiveDate) -> Result<(), EmployeeError> {
+        self.change_status(StatusChange::Rehire { rehire_date }, &StatusTransitionRules::standard())
+    }
+    
+    /// Replace the onboarding checklist with a fresh one from the tenant's template
+    pub fn start_onboarding(&mut self, template: &OnboardingTemplate) {
+        self.onboarding = Some(OnboardingChecklist::generate(template));
+        self.touch();
+    }
+    
+    /// Put a document on file
+    pub fn attach_document(&mut self, document: EmployeeDocument) {
+        self.documents.push(document);
+        self.touch();
+    }
+    
+    /// Mark an onboarding item done. Items that need a document can't be
+    /// completed until one of that type is on file.
+    pub fn complete_onboarding_item(&mut self, code: &str, completed_by: impl Into<String>) -> Result<(), EmployeeError> {
+        let checklist = self.onboarding.as_mut().ok_or(EmployeeError::InvalidStateTransition)?;
+        let item = checklist.item(code).ok_or_else(|| EmployeeError::OnboardingItemNotFound(code.to_string()))?;
+        if let Some(document) = item.document {
+            if !self.documents.iter().any(|d| d.doc_type == document) {
+                return Err(EmployeeError::OnboardingDocumentMissing { code: code.to_string(), document });
+            }
         }
-        
-        self.status = EmploymentStatus::Terminated;
-        self.employment.termination_date = Some(termination_date);
+        checklist.complete(code, completed_by);
         self.touch();
-        
-        self.raise_event(DomainEvent::Employee(EmployeeEvent::Terminated {
-            employee_id: self.employee_id.clone(),
-            termination_date,
-            reason: reason.into(),
-        }));
-        
         Ok(())
     }
     
-    /// Enroll in benefits
-    pub fn enroll_in_benefit(&mut self, plan_id: impl Into<String>, coverage: CoverageLevel) {
+    /// Mandatory onboarding items still outstanding
+    pub fn onboarding_outstanding(&self) -> Vec<String> {
+        self.onboarding.as_ref().map(|c| c.outstanding_mandatory()).unwrap_or_default()
+    }
+    
+    /// Whether payroll may pay this employee: every mandatory onboarding
+    /// item is done
+    pub fn payroll_eligible(&self) -> bool {
+        self.onboarding_outstanding().is_empty()
+    }
+    
+    /// Mark an offboarding task done
+    pub fn complete_offboarding_task(&mut self, code: &str, completed_by: impl Into<String>) -> Result<(), EmployeeError> {
+        let checklist = self.offboarding.as_mut().ok_or(EmployeeError::InvalidStateTransition)?;
+        if !checklist.complete(code, completed_by) {
+            return Err(EmployeeError::OffboardingTaskNotFound(code.to_string()));
+        }
+        self.touch();
+        Ok(())
+    }
+    
+    /// Archive a terminated employee once every mandatory offboarding task is done
+    pub fn archive(&mut self) -> Result<(), EmployeeError> {
+        if self.status != EmploymentStatus::Terminated {
+            return Err(EmployeeError::InvalidStateTransition);
+        }
+        let outstanding = self.offboarding.as_ref().map(|c| c.outstanding_mandatory()).unwrap_or_default();
+        if !outstanding.is_empty() {
+            return Err(EmployeeError::OffboardingIncomplete(outstanding));
+        }
+        self.archived_at = Some(Utc::now());
+        self.touch();
+        Ok(())
+    }
+    
+    /// Strip personal data from a terminated employee once retention has
+    /// lapsed. The employee number, job, department history, dates, and pay
+    /// stay so headcount and payroll aggregates still add up. Returns
+    /// whether anything was left to strip.
+    pub fn anonymize_personal(&mut self) -> Result<bool, EmployeeError> {
+        if self.status != EmploymentStatus::Terminated {
+            return Err(EmployeeError::InvalidStateTransition);
+        }
+        if self.anonymized_at.is_some() {
+            return Ok(false);
+        }
+        self.personal = PersonalInfo {
+            first_name: "Former".to_string(),
+            last_name: "Employee".to_string(),
+            ..Default::default()
+        };
+        self.employment.work_email = String::new();
+        self.employment.work_phone = None;
+        self.emergency_contacts.clear();
+        self.custom_fields.clear();
+        self.pending_changes.clear();
+        self.documents.retain(|d| !matches!(d.doc_type, DocumentType::Resume | DocumentType::IdDocument));
+        self.anonymized_at = Some(Utc::now());
+        self.touch();
+        Ok(true)
+    }
+
+    /// Drop a terminated employee's tax id, bank account, pay history, and
+    /// tax forms. The current pay rate stays for payroll aggregates.
+    /// Returns whether there was anything to drop.
+    pub fn erase_financial(&mut self) -> Result<bool, EmployeeError> {
+        if self.status != EmploymentStatus::Terminated {
+            return Err(EmployeeError::InvalidStateTransition);
+        }
+        let held = self.tax_id.is_some()
+            || self.compensation.bank_details.is_some()
+            || !self.compensation.compensation_history.is_empty()
+            || self.documents.iter().any(|d| d.doc_type == DocumentType::TaxForm);
+        if !held {
+            return Ok(false);
+        }
+        self.tax_id = None;
+        self.compensation.bank_details = None;
+        self.compensation.compensation_history.clear();
+        self.documents.retain(|d| d.doc_type != DocumentType::TaxForm);
+        self.touch();
+        Ok(true)
+    }
+
+    /// Drop a terminated employee's documents of the given types, returning
+    /// whether any were held
+    pub fn erase_documents(&mut self, doc_types: &[DocumentType]) -> Result<bool, EmployeeError> {
+        if self.status != EmploymentStatus::Terminated {
+            return Err(EmployeeError::InvalidStateTransition);
+        }
+        let before = self.documents.len();
+        self.documents.retain(|d| !doc_types.contains(&d.doc_type));
+        if self.documents.len() == before {
+            return Ok(false);
+        }
+        self.touch();
+        Ok(true)
+    }
+
+    /// Take over a duplicate record's documents, emergency contacts, and
+    /// benefit elections, skipping contacts and plans already held. This
+    /// record's own custom fields and links win over the duplicate's.
+    pub fn absorb(&mut self, duplicate: &Employee) {
+        self.documents.extend(duplicate.documents.iter().cloned());
+        for contact in &duplicate.emergency_contacts {
+            if !self.emergency_contacts.iter().any(|c| c.phone == contact.phone) {
+                self.emergency_contacts.push(contact.clone());
+            }
+        }
+        for election in &duplicate.benefits_elections {
+            if !self.benefits_elections.iter().any(|e| e.benefit_plan_id == election.benefit_plan_id) {
+                self.benefits_elections.push(election.clone());
+            }
+        }
+        for (key, value) in &duplicate.custom_fields {
+            self.custom_fields.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+        if self.previous_record_id.is_none() {
+            self.previous_record_id = duplicate.previous_record_id.clone();
+        }
+        if self.tax_id.is_none() {
+            self.tax_id = duplicate.tax_id.clone();
+        }
+        self.touch();
+    }
+    
+    /// Retire this record as a duplicate of `survivor_id`; lookups of it
+    /// should redirect there from now on
+    pub fn mark_merged_into(&mut self, survivor_id: impl Into<String>) {
+        let now = Utc::now();
+        self.merged_into = Some(survivor_id.into());
+        self.archived_at.get_or_insert(now);
+        self.updated_at = now;
+    }
+    
+    pub fn set_tax_id(&mut self, tax_id: Option<TaxId>) {
+        self.tax_id = tax_id;
+        self.touch();
+    }
+    
+    /// Move the employee to another company within the tenant
+    pub fn assign_legal_entity(&mut self, legal_entity_id: Uuid) {
+        self.legal_entity_id = Some(legal_entity_id);
+        self.touch();
+    }
+    
+    /// Link a rehire to the person's earlier employment record
+    pub fn link_previous_record(&mut self, previous_record_id: impl Into<String>) {
+        self.previous_record_id = Some(previous_record_id.into());
+        self.touch();
+    }
+    
+    /// Record a benefit election. Only `BenefitsService::enroll_employee`
+    /// calls this, once the plan and enrollment window have been checked.
+    pub(crate) fn enroll_in_benefit(&mut self, plan_id: impl Into<String>, coverage: CoverageLevel, dependents: Vec<String>) {
         self.benefits_elections.push(BenefitElection {
             benefit_plan_id: plan_id.into(),
             coverage_level: coverage,
-            dependents: vec![],
+            dependents,
             enrolled_at: Utc::now(),
         });
         self.touch();
     }
     
+    /// Set a tenant-defined field (skills, certifications, …)
+    pub fn set_custom_field(&mut self, key: impl Into<String>, value: serde_json::Value) {
+        self.custom_fields.insert(key.into(), value);
+        self.touch();
+    }
+    
     /// Add emergency contact
     pub fn add_emergency_contact(&mut self, contact: EmergencyContact) {
         self.emergency_contacts.push(contact);
@@ -335,11 +1118,12 @@ impl Employee {
     
     /// Calculate years of service
     pub fn years_of_service(&self) -> f64 {
+        use rust_decimal::prelude::ToPrimitive;
+
         if let Some(hire_date) = self.employment.hire_date {
             let end_date = self.employment.termination_date
                 .unwrap_or_else(|| chrono::Utc::now().date_naive());
-            let days = (end_date - hire_date).num_days();
-            days as f64 / 365.25
+            tenure_years(hire_date, end_date).to_f64().unwrap_or_default()
         } else {
             0.0
         }
@@ -358,11 +1142,47 @@ impl Employee {
     }
 }
 
+/// Years between `start` and `end`: whole anniversaries plus the days since
+/// the last one over 365. Zero when `end` is not after `start`.
+pub fn tenure_years(start: NaiveDate, end: NaiveDate) -> Decimal {
+    if end <= start {
+        return Decimal::ZERO;
+    }
+
+    let anniversary = |years: i32| start.checked_add_months(chrono::Months::new(12 * years as u32)).unwrap_or(end);
+    let mut years = end.year() - start.year();
+    if anniversary(years) > end {
+        years -= 1;
+    }
+    let days = (end - anniversary(years)).num_days();
+    Decimal::from(years) + Decimal::from(days) / Decimal::from(365)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EmployeeError {
     InvalidStateTransition,
+    IllegalStatusTransition { from: EmploymentStatus, to: EmploymentStatus },
+    /// `Terminated -> Active` attempted outside a rehire
+    RehireRequired,
+    /// Terminated on terms that rule out a rehire
+    NotRehireEligible,
+    /// Rehire attempted during the cooling-off period
+    RehireWaitingPeriod { not_before: NaiveDate },
     AlreadyTerminated,
+    /// Status changes are recorded in effective-date order
+    StatusChangeBackdated { latest: NaiveDate },
     NotFound,
+    DepartmentNotFound(String),
+    LegalEntityNotFound(Uuid),
+    OnboardingItemNotFound(String),
+    OnboardingDocumentMissing { code: String, document: DocumentType },
+    OffboardingTaskNotFound(String),
+    OffboardingIncomplete(Vec<String>),
+    /// A resignation or abandonment dated inside protected leave
+    OnProtectedLeave(NaiveDate),
+    /// The record was merged into another and is no longer used
+    Merged { survivor_id: String },
+    InvalidMerge(String),
 }
 
 impl std::error::Error for EmployeeError {}
@@ -370,8 +1190,32 @@ impl std::fmt::Display for EmployeeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::InvalidStateTransition => write!(f, "Invalid state transition"),
+            Self::IllegalStatusTransition { from, to } => {
+                write!(f, "Illegal status transition: {:?} -> {:?}", from, to)
+            }
+            Self::RehireRequired => write!(f, "Terminated employees can only return through a rehire"),
+            Self::NotRehireEligible => write!(f, "Employee is not eligible for rehire"),
+            Self::RehireWaitingPeriod { not_before } => write!(f, "Employee cannot be rehired before {}", not_before),
             Self::AlreadyTerminated => write!(f, "Employee already terminated"),
+            Self::StatusChangeBackdated { latest } => {
+                write!(f, "Status change cannot take effect before the latest change on {}", latest)
+            }
             Self::NotFound => write!(f, "Employee not found"),
+            Self::DepartmentNotFound(id) => write!(f, "Department not found: {}", id),
+            Self::LegalEntityNotFound(id) => write!(f, "Legal entity not found: {}", id),
+            Self::OnboardingItemNotFound(code) => write!(f, "Onboarding item not found: {}", code),
+            Self::OnboardingDocumentMissing { code, document } => {
+                write!(f, "Onboarding item {} needs a {:?} document on file", code, document)
+            }
+            Self::OffboardingTaskNotFound(code) => write!(f, "Offboarding task not found: {}", code),
+            Self::OffboardingIncomplete(codes) => {
+                write!(f, "Mandatory offboarding tasks outstanding: {}", codes.join(", "))
+            }
+            Self::OnProtectedLeave(date) => {
+                write!(f, "Employee is on protected leave on {}; the absence is not a resignation", date)
+            }
+            Self::Merged { survivor_id } => write!(f, "Employee record was merged into {}", survivor_id),
+            Self::InvalidMerge(reason) => write!(f, "Cannot merge employees: {}", reason),
         }
     }
 }
@@ -379,6 +1223,7 @@ impl std::fmt::Display for EmployeeError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::aggregates::TaskAssignee;
     
     fn create_test_employee() -> Employee {
         Employee::hire(
@@ -391,6 +1236,15 @@ mod tests {
         )
     }
     
+    #[test]
+    fn test_tenure_years() {
+        let d = |y, m, day| NaiveDate::from_ymd_opt(y, m, day).unwrap();
+        assert_eq!(tenure_years(d(2021, 3, 1), d(2024, 3, 1)), Decimal::from(3));
+        assert_eq!(tenure_years(d(2021, 3, 1), d(2024, 2, 28)), Decimal::from(2) + Decimal::from(364) / Decimal::from(365));
+        assert_eq!(tenure_years(d(2020, 2, 29), d(2021, 2, 28)), Decimal::ONE);
+        assert_eq!(tenure_years(d(2024, 3, 1), d(2023, 3, 1)), Decimal::ZERO);
+    }
+    
     #[test]
     fn test_employee_hire() {
         let emp = create_test_employee();
@@ -409,11 +1263,141 @@ mod tests {
     #[test]
     fn test_leave_management() {
         let mut emp = create_test_employee();
-        emp.start_leave().unwrap();
+        emp.start_leave("annual", NaiveDate::from_ymd_opt(2024, 6, 3).unwrap()).unwrap();
         assert_eq!(emp.status(), &EmploymentStatus::OnLeave);
+        emp.take_events();
         
-        emp.end_leave().unwrap();
+        emp.end_leave(NaiveDate::from_ymd_opt(2024, 6, 17).unwrap()).unwrap();
         assert!(emp.is_active());
+        assert!(matches!(
+            emp.take_events().as_slice(),
+            [DomainEvent::Employee(EmployeeEvent::OnLeaveEnded { .. })]
+        ));
+    }
+    
+    #[test]
+    fn test_status_changes_recorded_with_dated_reasons() {
+        let d = |m, day| NaiveDate::from_ymd_opt(2024, m, day).unwrap();
+        let mut emp = create_test_employee();
+        emp.start_leave("Maternity", d(3, 1)).unwrap();
+        emp.end_leave(d(6, 3)).unwrap();
+        emp.change_status_with_note(
+            StatusChange::Suspend { effective_date: d(9, 2), reason: "Pending investigation".into() },
+            &StatusTransitionRules::standard(),
+            Some("Case HR-118".into()),
+        )
+        .unwrap();
+
+        let history: Vec<_> = emp.status_history().iter().map(|r| (r.status, r.reason.as_str(), r.effective_date)).collect();
+        assert_eq!(
+            history,
+            vec![
+                (EmploymentStatus::Active, "Hired", d(1, 15)),
+                (EmploymentStatus::OnLeave, "Maternity", d(3, 1)),
+                (EmploymentStatus::Active, "Returned from leave", d(6, 3)),
+                (EmploymentStatus::Suspended, "Pending investigation", d(9, 2)),
+            ]
+        );
+        assert_eq!(emp.status_history()[3].note.as_deref(), Some("Case HR-118"));
+
+        // On leave since 1 March for maternity
+        let on_leave = emp.status_change_on(d(4, 30)).unwrap();
+        assert_eq!((on_leave.status, on_leave.reason.as_str(), on_leave.effective_date), (EmploymentStatus::OnLeave, "Maternity", d(3, 1)));
+        assert_eq!(emp.status_on(d(1, 1)), None);
+        assert_eq!(emp.status_on(d(6, 3)), Some(EmploymentStatus::Active));
+        assert_eq!(Some(*emp.status()), emp.status_history().last().map(|r| r.status));
+    }
+
+    #[test]
+    fn test_future_status_change_applies_on_its_date() {
+        let today = Utc::now().date_naive();
+        let last_day = today + chrono::Duration::days(30);
+        let mut emp = create_test_employee();
+        emp.terminate(last_day, "Resignation").unwrap();
+
+        assert_eq!(emp.status(), &EmploymentStatus::Active);
+        assert_eq!(emp.status_on(last_day), Some(EmploymentStatus::Terminated));
+        assert_eq!(emp.apply_pending_changes(today), 0);
+        assert!(emp.is_active());
+
+        assert_eq!(emp.apply_pending_changes(last_day), 1);
+        assert_eq!(emp.status(), &EmploymentStatus::Terminated);
+    }
+
+    #[test]
+    fn test_backdated_status_change_rejected() {
+        let d = |m, day| NaiveDate::from_ymd_opt(2024, m, day).unwrap();
+        let mut emp = create_test_employee();
+        emp.start_leave("Annual", d(5, 6)).unwrap();
+
+        assert_eq!(emp.end_leave(d(5, 1)), Err(EmployeeError::StatusChangeBackdated { latest: d(5, 6) }));
+        assert_eq!(emp.status(), &EmploymentStatus::OnLeave);
+        assert_eq!(emp.status_history().len(), 2);
+        assert_eq!(emp.status_on(d(12, 31)), Some(EmploymentStatus::OnLeave));
+    }
+
+    #[test]
+    fn test_illegal_status_transitions() {
+        let mut emp = create_test_employee();
+        let date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        emp.terminate(date, "Resignation").unwrap();
+        emp.take_events();
+        
+        assert_eq!(
+            emp.suspend(date, "Misconduct"),
+            Err(EmployeeError::IllegalStatusTransition {
+                from: EmploymentStatus::Terminated,
+                to: EmploymentStatus::Suspended,
+            })
+        );
+        assert_eq!(
+            emp.change_status(StatusChange::Reinstate { effective_date: date }, &StatusTransitionRules::standard()),
+            Err(EmployeeError::IllegalStatusTransition {
+                from: EmploymentStatus::Terminated,
+                to: EmploymentStatus::Active,
+            })
+        );
+        assert_eq!(emp.status(), &EmploymentStatus::Terminated);
+        assert!(emp.take_events().is_empty());
+        
+        emp.rehire(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()).unwrap();
+        assert!(emp.is_active());
+        assert_eq!(emp.employment().termination_date, None);
+    }
+    
+    #[test]
+    fn test_rehire_only_transition() {
+        let rules = StatusTransitionRules::standard();
+        assert_eq!(
+            rules.check(EmploymentStatus::Terminated, EmploymentStatus::Active, false),
+            Err(EmployeeError::RehireRequired)
+        );
+        assert!(rules.check(EmploymentStatus::Terminated, EmploymentStatus::Active, true).is_ok());
+        
+        let mut strict = StatusTransitionRules::standard();
+        strict.deny(EmploymentStatus::Terminated, EmploymentStatus::Active);
+        assert!(strict.check(EmploymentStatus::Terminated, EmploymentStatus::Active, true).is_err());
+    }
+    
+    #[test]
+    fn test_rehire_eligibility_checked() {
+        let terminated = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        
+        let mut fired = create_test_employee();
+        fired.terminate_for_cause(terminated, "Gross misconduct").unwrap();
+        assert_eq!(fired.rehire(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()), Err(EmployeeError::NotRehireEligible));
+        assert_eq!(fired.status(), &EmploymentStatus::Terminated);
+        
+        let not_before = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let mut laid_off = create_test_employee();
+        laid_off.terminate_with_rehire(terminated, "Redundancy", RehireEligibility::not_before(not_before)).unwrap();
+        assert_eq!(
+            laid_off.rehire(NaiveDate::from_ymd_opt(2024, 12, 1).unwrap()),
+            Err(EmployeeError::RehireWaitingPeriod { not_before })
+        );
+        laid_off.rehire(not_before).unwrap();
+        assert!(laid_off.is_active());
+        assert_eq!(laid_off.rehire_eligibility(), &RehireEligibility::default());
     }
     
     #[test]
@@ -422,4 +1406,177 @@ mod tests {
         emp.terminate(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(), "Resignation").unwrap();
         assert_eq!(emp.status(), &EmploymentStatus::Terminated);
     }
+    
+    #[test]
+    fn test_monthly_salary_to_hourly_and_back() {
+        use rust_decimal_macros::dec;
+        
+        let salaried = CompensationInfo {
+            pay_rate: Some(PayRate::salary(dec!(5000), "USD", PayFrequency::Monthly)),
+            ..Default::default()
+        };
+        let hourly = salaried.as_frequency(PayFrequency::Hourly, dec!(40)).unwrap();
+        // 60,000 / 2,080 hours
+        assert_eq!(hourly.round_dp(2), dec!(28.85));
+        assert_eq!(salaried.as_frequency(PayFrequency::Annually, dec!(40)), Some(dec!(60000)));
+        assert_eq!(salaried.as_frequency(PayFrequency::Weekly, dec!(40)).unwrap().round_dp(2), dec!(1153.85));
+        
+        let hourly_paid = CompensationInfo {
+            pay_rate: Some(PayRate::hourly(hourly, "USD")),
+            ..Default::default()
+        };
+        let monthly = hourly_paid.as_frequency(PayFrequency::Monthly, dec!(40)).unwrap();
+        assert!((monthly - dec!(5000)).abs() < dec!(0.01));
+        
+        // A 35-hour standard week makes each hour worth more
+        assert!(salaried.as_frequency(PayFrequency::Hourly, dec!(35)).unwrap() > hourly);
+        assert_eq!(CompensationInfo::default().as_frequency(PayFrequency::Monthly, dec!(40)), None);
+    }
+    
+    #[test]
+    fn test_termination_creates_offboarding_checklist() {
+        let mut emp = create_test_employee();
+        emp.update(EmployeeChange::Address(AddressInfo { country: "GB".into(), ..Default::default() }), None);
+        assert!(emp.offboarding().is_none());
+        
+        emp.terminate(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(), "Resignation").unwrap();
+        
+        let checklist = emp.offboarding().unwrap();
+        assert_eq!(checklist.outstanding_mandatory(), ["revoke_access", "return_equipment", "final_pay", "issue_p45"]);
+        assert!(!checklist.task("exit_interview").unwrap().mandatory);
+        assert_eq!(checklist.task("revoke_access").unwrap().assignee, TaskAssignee::It);
+    }
+    
+    #[test]
+    fn test_incomplete_mandatory_task_blocks_archival() {
+        let mut emp = create_test_employee();
+        assert_eq!(emp.archive(), Err(EmployeeError::InvalidStateTransition));
+        emp.terminate(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(), "Resignation").unwrap();
+        
+        for code in ["revoke_access", "final_pay"] {
+            emp.complete_offboarding_task(code, "hr-1").unwrap();
+        }
+        assert_eq!(emp.archive(), Err(EmployeeError::OffboardingIncomplete(vec!["return_equipment".to_string()])));
+        assert!(!emp.is_archived());
+        assert_eq!(
+            emp.complete_offboarding_task("shred_files", "hr-1"),
+            Err(EmployeeError::OffboardingTaskNotFound("shred_files".to_string()))
+        );
+        
+        // The optional exit interview can stay open
+        emp.complete_offboarding_task("return_equipment", "it-1").unwrap();
+        emp.archive().unwrap();
+        assert!(emp.is_archived());
+    }
+    
+    #[test]
+    fn test_onboarding_gates_payroll_eligibility() {
+        let mut emp = create_test_employee();
+        assert!(!emp.payroll_eligible());
+        assert_eq!(emp.onboarding_outstanding(), ["contract_signed", "tax_forms", "bank_details"]);
+        
+        // The contract can't be ticked off until it's on file
+        assert_eq!(
+            emp.complete_onboarding_item("contract_signed", "hr-1"),
+            Err(EmployeeError::OnboardingDocumentMissing { code: "contract_signed".to_string(), document: DocumentType::Contract })
+        );
+        for (doc_type, name) in [(DocumentType::Contract, "contract.pdf"), (DocumentType::TaxForm, "tax-form.pdf")] {
+            emp.attach_document(EmployeeDocument { id: Uuid::new_v4().to_string(), doc_type, name: name.into(), uploaded_at: Utc::now() });
+        }
+        for code in ["contract_signed", "tax_forms", "bank_details"] {
+            emp.complete_onboarding_item(code, "hr-1").unwrap();
+        }
+        
+        // Optional items can stay open
+        assert!(!emp.onboarding().unwrap().item("orientation").unwrap().is_complete());
+        assert!(emp.payroll_eligible());
+    }
+    
+    #[test]
+    fn test_future_dated_title_change() {
+        let mut emp = create_test_employee();
+        emp.take_events();
+        let today = Utc::now().date_naive();
+        let next_month = today + chrono::Duration::days(30);
+        
+        let id = emp.update(EmployeeChange::JobTitle("Staff Engineer".to_string()), Some(next_month));
+        assert!(id.is_some());
+        assert_eq!(emp.employment().job_title, "Software Engineer");
+        
+        // Not yet due
+        assert_eq!(emp.apply_pending_changes(today), 0);
+        assert_eq!(emp.employment().job_title, "Software Engineer");
+        assert!(emp.take_events().is_empty());
+        
+        // Effective date reached
+        assert_eq!(emp.apply_pending_changes(next_month), 1);
+        assert_eq!(emp.employment().job_title, "Staff Engineer");
+        assert!(emp.pending_changes().is_empty());
+        
+        let events = emp.take_events();
+        assert!(matches!(
+            events.as_slice(),
+            [DomainEvent::Employee(EmployeeEvent::Promoted { old_title, new_title, .. })]
+                if old_title == "Software Engineer" && new_title == "Staff Engineer"
+        ));
+    }
+    
+    #[test]
+    fn test_undated_change_applies_immediately() {
+        let mut emp = create_test_employee();
+        
+        let id = emp.update(EmployeeChange::Department(Some("eng".to_string())), None);
+        assert!(id.is_none());
+        assert_eq!(emp.employment().department_id.as_deref(), Some("eng"));
+        
+        let scheduled = emp
+            .update(EmployeeChange::WorkEmail("jd@company.com".to_string()), Some(NaiveDate::MAX))
+            .unwrap();
+        emp.cancel_pending_change(&scheduled).unwrap();
+        assert!(emp.pending_changes().is_empty());
+        assert_eq!(emp.cancel_pending_change(&scheduled), Err(EmployeeError::NotFound));
+    }
+    
+    #[test]
+    fn test_department_allocation_follows_transfers() {
+        let mut emp = create_test_employee();
+        emp.update(EmployeeChange::Department(Some("sales".to_string())), None);
+        
+        let mid_month = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        emp.transfer_department("eng", mid_month, "Reorg").unwrap();
+        
+        assert_eq!(emp.department_on(NaiveDate::from_ymd_opt(2024, 3, 10).unwrap()), Some("sales"));
+        assert_eq!(emp.department_on(mid_month), Some("eng"));
+        
+        let allocation = emp.department_allocation(
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(),
+        );
+        assert_eq!(allocation, vec![(Some("sales".to_string()), 10), (Some("eng".to_string()), 21)]);
+        
+        assert_eq!(emp.transfer_department("eng", mid_month, "Again"), Err(EmployeeError::InvalidStateTransition));
+    }
+    
+    #[test]
+    fn test_back_dated_transfer_relinks_later_transfer() {
+        let mut emp = create_test_employee();
+        emp.update(EmployeeChange::Department(Some("sales".to_string())), None);
+        let date = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        emp.transfer_department("eng", date(20), "Reorg").unwrap();
+        
+        // Recorded late: moved to ops on the 10th, before the move to eng
+        let back_dated = emp.transfer_department("ops", date(10), "Cover").unwrap();
+        assert_eq!(back_dated.from_department_id.as_deref(), Some("sales"));
+        
+        let history = emp.department_history();
+        assert_eq!(history.iter().map(|t| t.to_department_id.as_str()).collect::<Vec<_>>(), vec!["ops", "eng"]);
+        assert_eq!(history[1].from_department_id.as_deref(), Some("ops"));
+        assert_eq!(emp.employment().department_id.as_deref(), Some("eng"));
+        assert_eq!(
+            emp.department_allocation(date(1), date(31)),
+            vec![(Some("sales".to_string()), 9), (Some("ops".to_string()), 10), (Some("eng".to_string()), 12)]
+        );
+        
+        assert_eq!(emp.transfer_department("eng", date(15), "Early"), Err(EmployeeError::InvalidStateTransition));
+    }
 }
@@ -0,0 +1,72 @@
+//! Legal Entities
+//!
+//! A tenant may employ people through several companies, each registered
+//! for tax in its own country. Every employee belongs to one entity, and
+//! payroll runs and the filings drawn from them are made per entity.
+
+use std::sync::Arc;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A company within a tenant that employs and pays people
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegalEntity {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub name: String,
+    /// ISO country of registration; selects the payroll calculator
+    pub country: String,
+    /// Employer tax number with the country's revenue authority
+    pub tax_registration: String,
+}
+
+impl LegalEntity {
+    pub fn new(
+        tenant_id: Uuid,
+        name: impl Into<String>,
+        country: &str,
+        tax_registration: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            tenant_id,
+            name: name.into(),
+            country: country.to_uppercase(),
+            tax_registration: tax_registration.into(),
+        }
+    }
+}
+
+/// Legal entities by id, shared by the services that scope by entity
+#[derive(Debug, Clone, Default)]
+pub struct LegalEntities {
+    // In real implementation, backed by the legal_entities table
+    entities: Arc<DashMap<Uuid, LegalEntity>>,
+}
+
+impl LegalEntities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&self, entity: LegalEntity) {
+        self.entities.insert(entity.id, entity);
+    }
+
+    pub fn get(&self, entity_id: Uuid) -> Option<LegalEntity> {
+        self.entities.get(&entity_id).map(|e| e.clone())
+    }
+
+    /// The entity if it exists and belongs to `tenant_id`
+    pub fn for_tenant(&self, tenant_id: Uuid, entity_id: Uuid) -> Option<LegalEntity> {
+        self.get(entity_id).filter(|e| e.tenant_id == tenant_id)
+    }
+
+    /// A tenant's entities, by name
+    pub fn list(&self, tenant_id: Uuid) -> Vec<LegalEntity> {
+        let mut entities: Vec<_> = self.entities.iter().filter(|e| e.tenant_id == tenant_id).map(|e| e.clone()).collect();
+        entities.sort_by(|a, b| a.name.cmp(&b.name));
+        entities
+    }
+}
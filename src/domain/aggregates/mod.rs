@@ -2,6 +2,12 @@
 
 pub mod employee;
 pub mod payroll;
+pub mod offboarding;
+pub mod onboarding;
+pub mod legal_entity;
 
 pub use employee::*;
 pub use payroll::*;
+pub use offboarding::*;
+pub use onboarding::*;
+pub use legal_entity::*;
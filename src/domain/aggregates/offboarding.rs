@@ -0,0 +1,106 @@
+//! Offboarding Checklist
+//!
+//! Tasks generated when an employee is terminated. The employee record
+//! can't be archived while any mandatory task is outstanding.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::employee::EmploymentType;
+
+/// Team responsible for an offboarding task
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskAssignee {
+    Hr,
+    It,
+    Payroll,
+    Manager,
+    Employee,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OffboardingTask {
+    /// Stable task code, e.g. "revoke_access"
+    pub code: String,
+    pub title: String,
+    pub assignee: TaskAssignee,
+    pub mandatory: bool,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub completed_by: Option<String>,
+}
+
+impl OffboardingTask {
+    fn new(code: &str, title: &str, assignee: TaskAssignee, mandatory: bool) -> Self {
+        Self {
+            code: code.to_string(),
+            title: title.to_string(),
+            assignee,
+            mandatory,
+            completed_at: None,
+            completed_by: None,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.completed_at.is_some()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OffboardingChecklist {
+    pub tasks: Vec<OffboardingTask>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OffboardingChecklist {
+    /// Standard tasks plus country- and employment-type-specific ones
+    pub fn generate(country_code: Option<&str>, employment_type: &EmploymentType) -> Self {
+        use TaskAssignee::*;
+        let mut tasks = vec![
+            OffboardingTask::new("revoke_access", "Revoke system and building access", It, true),
+            OffboardingTask::new("return_equipment", "Collect laptop, badge, and other equipment", It, true),
+            OffboardingTask::new("knowledge_transfer", "Hand over open work", Manager, false),
+            OffboardingTask::new("exit_interview", "Conduct exit interview", Hr, false),
+        ];
+
+        if *employment_type == EmploymentType::Contractor {
+            tasks.push(OffboardingTask::new("close_contract", "Close contract and settle final invoice", Hr, true));
+        } else {
+            tasks.push(OffboardingTask::new("final_pay", "Process final pay and leave payout", Payroll, true));
+        }
+
+        match country_code.map(|c| c.to_ascii_uppercase()).as_deref() {
+            Some("NG") => tasks.push(OffboardingTask::new("pfa_exit_notice", "Notify the PFA of the exit", Payroll, true)),
+            Some("GB") => tasks.push(OffboardingTask::new("issue_p45", "Issue P45", Payroll, true)),
+            Some("US") => tasks.push(OffboardingTask::new("cobra_notice", "Send COBRA continuation notice", Hr, true)),
+            Some("ZA") => tasks.push(OffboardingTask::new("uif_ui19", "Submit UI-19 to the UIF", Hr, true)),
+            _ => {}
+        }
+
+        Self { tasks, created_at: Utc::now() }
+    }
+
+    /// Mark a task done; returns false if there's no task with that code
+    pub fn complete(&mut self, code: &str, completed_by: impl Into<String>) -> bool {
+        match self.tasks.iter_mut().find(|t| t.code == code) {
+            Some(task) => {
+                if task.completed_at.is_none() {
+                    task.completed_at = Some(Utc::now());
+                    task.completed_by = Some(completed_by.into());
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Codes of mandatory tasks not yet done
+    pub fn outstanding_mandatory(&self) -> Vec<String> {
+        self.tasks.iter().filter(|t| t.mandatory && !t.is_complete()).map(|t| t.code.clone()).collect()
+    }
+
+    pub fn task(&self, code: &str) -> Option<&OffboardingTask> {
+        self.tasks.iter().find(|t| t.code == code)
+    }
+}
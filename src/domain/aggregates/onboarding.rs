@@ -0,0 +1,125 @@
+//! Onboarding Checklist
+//!
+//! Items generated when an employee is hired, from the tenant's template.
+//! The employee isn't payroll-eligible while any mandatory item is
+//! outstanding. Items that need a document on file can only be completed
+//! once one of that type has been attached.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::employee::DocumentType;
+use super::offboarding::TaskAssignee;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OnboardingItem {
+    /// Stable item code, e.g. "bank_details"
+    pub code: String,
+    pub title: String,
+    pub assignee: TaskAssignee,
+    pub mandatory: bool,
+    /// Document that must be on file before the item can be completed
+    pub document: Option<DocumentType>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub completed_by: Option<String>,
+}
+
+impl OnboardingItem {
+    pub fn new(code: &str, title: &str, assignee: TaskAssignee, mandatory: bool) -> Self {
+        Self {
+            code: code.to_string(),
+            title: title.to_string(),
+            assignee,
+            mandatory,
+            document: None,
+            completed_at: None,
+            completed_by: None,
+        }
+    }
+
+    pub fn requiring(mut self, document: DocumentType) -> Self {
+        self.document = Some(document);
+        self
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.completed_at.is_some()
+    }
+}
+
+/// Items a tenant asks of every new hire
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OnboardingTemplate {
+    items: Vec<OnboardingItem>,
+}
+
+impl Default for OnboardingTemplate {
+    /// Signed contract, tax forms, and bank details before the first pay run
+    fn default() -> Self {
+        use TaskAssignee::*;
+        Self::empty()
+            .with_item(OnboardingItem::new("contract_signed", "Sign employment contract", Employee, true).requiring(DocumentType::Contract))
+            .with_item(OnboardingItem::new("tax_forms", "Submit tax forms", Employee, true).requiring(DocumentType::TaxForm))
+            .with_item(OnboardingItem::new("bank_details", "Provide bank details for salary payment", Payroll, true))
+            .with_item(OnboardingItem::new("it_setup", "Set up laptop and accounts", It, false))
+            .with_item(OnboardingItem::new("orientation", "Attend orientation", Manager, false))
+    }
+}
+
+impl OnboardingTemplate {
+    pub fn empty() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Add an item, replacing any with the same code
+    pub fn with_item(mut self, item: OnboardingItem) -> Self {
+        self.items.retain(|i| i.code != item.code);
+        self.items.push(item);
+        self
+    }
+
+    pub fn without_item(mut self, code: &str) -> Self {
+        self.items.retain(|i| i.code != code);
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OnboardingChecklist {
+    pub items: Vec<OnboardingItem>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OnboardingChecklist {
+    pub fn generate(template: &OnboardingTemplate) -> Self {
+        let items = template
+            .items
+            .iter()
+            .map(|item| OnboardingItem { completed_at: None, completed_by: None, ..item.clone() })
+            .collect();
+        Self { items, created_at: Utc::now() }
+    }
+
+    /// Mark an item done; returns false if there's no item with that code
+    pub fn complete(&mut self, code: &str, completed_by: impl Into<String>) -> bool {
+        match self.items.iter_mut().find(|i| i.code == code) {
+            Some(item) => {
+                if item.completed_at.is_none() {
+                    item.completed_at = Some(Utc::now());
+                    item.completed_by = Some(completed_by.into());
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Codes of mandatory items not yet done
+    pub fn outstanding_mandatory(&self) -> Vec<String> {
+        self.items.iter().filter(|i| i.mandatory && !i.is_complete()).map(|i| i.code.clone()).collect()
+    }
+
+    pub fn item(&self, code: &str) -> Option<&OnboardingItem> {
+        self.items.iter().find(|i| i.code == code)
+    }
+}
@@ -4,7 +4,6 @@
 
 use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
-use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::domain::events::{DomainEvent, PayrollEvent};
@@ -13,13 +12,13 @@ use crate::domain::events::{DomainEvent, PayrollEvent};
 #[derive(Clone, Debug)]
 pub struct PayrollRun {
     id: String,
+    pay_group: String,
     pay_period_start: NaiveDate,
     pay_period_end: NaiveDate,
     check_date: NaiveDate,
     status: PayrollStatus,
     payslips: Vec<Payslip>,
     totals: PayrollTotals,
-    created_at: DateTime<Utc>,
     processed_at: Option<DateTime<Utc>>,
     approved_by: Option<String>,
     events: Vec<DomainEvent>,
@@ -128,31 +127,64 @@ pub enum PayslipStatus {
 }
 
 impl PayrollRun {
-    /// Create a new payroll run
+    /// Default pay group for tenants that run a single payroll
+    pub const DEFAULT_PAY_GROUP: &'static str = "default";
+    
+    /// Create a new payroll run for the default pay group
     pub fn create(
         pay_period_start: NaiveDate,
         pay_period_end: NaiveDate,
         check_date: NaiveDate,
-    ) -> Self {
-        let id = Uuid::new_v4().to_string();
+    ) -> Result<Self, PayrollError> {
+        Self::create_for_group(Self::DEFAULT_PAY_GROUP, pay_period_start, pay_period_end, check_date, &[])
+    }
+    
+    /// Create a payroll run, rejecting inverted periods, check dates before
+    /// the period closes, and periods overlapping another run of the same
+    /// pay group (which would pay the same days twice)
+    pub fn create_for_group(
+        pay_group: impl Into<String>,
+        pay_period_start: NaiveDate,
+        pay_period_end: NaiveDate,
+        check_date: NaiveDate,
+        existing_runs: &[PayrollRun],
+    ) -> Result<Self, PayrollError> {
+        if pay_period_end < pay_period_start {
+            return Err(PayrollError::InvertedPayPeriod { start: pay_period_start, end: pay_period_end });
+        }
+        if check_date < pay_period_end {
+            return Err(PayrollError::CheckDateBeforePeriodEnd { check_date, period_end: pay_period_end });
+        }
+        
+        let pay_group = pay_group.into();
+        let overlapping = existing_runs.iter().find(|run| {
+            run.pay_group == pay_group
+                && run.status != PayrollStatus::Failed
+                && run.pay_period_start <= pay_period_end
+                && pay_period_start <= run.pay_period_end
+        });
+        if let Some(run) = overlapping {
+            return Err(PayrollError::OverlappingRun(run.id.clone()));
+        }
         
-        Self {
-            id,
+        Ok(Self {
+            id: Uuid::new_v4().to_string(),
+            pay_group,
             pay_period_start,
             pay_period_end,
             check_date,
             status: PayrollStatus::Draft,
             payslips: vec![],
             totals: PayrollTotals::default(),
-            created_at: Utc::now(),
             processed_at: None,
             approved_by: None,
             events: vec![],
-        }
+        })
     }
     
     // Getters
     pub fn id(&self) -> &str { &self.id }
+    pub fn pay_group(&self) -> &str { &self.pay_group }
     pub fn status(&self) -> &PayrollStatus { &self.status }
     pub fn pay_period(&self) -> (NaiveDate, NaiveDate) { (self.pay_period_start, self.pay_period_end) }
     pub fn check_date(&self) -> NaiveDate { self.check_date }
@@ -294,6 +326,9 @@ pub enum PayrollError {
     InvalidStatus,
     AlreadyCompleted,
     PayslipNotFound,
+    InvertedPayPeriod { start: NaiveDate, end: NaiveDate },
+    CheckDateBeforePeriodEnd { check_date: NaiveDate, period_end: NaiveDate },
+    OverlappingRun(String),
 }
 
 impl std::error::Error for PayrollError {}
@@ -307,6 +342,13 @@ impl std::fmt::Display for PayrollError {
             Self::InvalidStatus => write!(f, "Invalid payroll status"),
             Self::AlreadyCompleted => write!(f, "Payroll already completed"),
             Self::PayslipNotFound => write!(f, "Payslip not found"),
+            Self::InvertedPayPeriod { start, end } => {
+                write!(f, "Pay period end {} is before start {}", end, start)
+            }
+            Self::CheckDateBeforePeriodEnd { check_date, period_end } => {
+                write!(f, "Check date {} is before pay period end {}", check_date, period_end)
+            }
+            Self::OverlappingRun(id) => write!(f, "Pay period overlaps existing payroll run {}", id),
         }
     }
 }
@@ -344,7 +386,7 @@ mod tests {
             NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
             NaiveDate::from_ymd_opt(2024, 1, 20).unwrap(),
-        );
+        ).unwrap();
         assert_eq!(payroll.status(), &PayrollStatus::Draft);
     }
     
@@ -354,7 +396,7 @@ mod tests {
             NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
             NaiveDate::from_ymd_opt(2024, 1, 20).unwrap(),
-        );
+        ).unwrap();
         
         payroll.add_payslip(create_test_payslip("EMP001")).unwrap();
         payroll.calculate().unwrap();
@@ -367,4 +409,37 @@ mod tests {
         payroll.complete().unwrap();
         assert_eq!(payroll.status(), &PayrollStatus::Completed);
     }
+    
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+    
+    #[test]
+    fn test_inverted_pay_period_rejected() {
+        let result = PayrollRun::create(date(1, 15), date(1, 1), date(1, 20));
+        assert_eq!(result.unwrap_err(), PayrollError::InvertedPayPeriod { start: date(1, 15), end: date(1, 1) });
+    }
+    
+    #[test]
+    fn test_check_date_before_period_end_rejected() {
+        let result = PayrollRun::create(date(1, 1), date(1, 15), date(1, 14));
+        assert_eq!(
+            result.unwrap_err(),
+            PayrollError::CheckDateBeforePeriodEnd { check_date: date(1, 14), period_end: date(1, 15) }
+        );
+        assert!(PayrollRun::create(date(1, 1), date(1, 15), date(1, 15)).is_ok());
+    }
+    
+    #[test]
+    fn test_overlapping_run_rejected() {
+        let first = PayrollRun::create_for_group("hourly", date(1, 1), date(1, 15), date(1, 20), &[]).unwrap();
+        let existing = vec![first.clone()];
+        
+        let overlap = PayrollRun::create_for_group("hourly", date(1, 15), date(1, 31), date(2, 5), &existing);
+        assert_eq!(overlap.unwrap_err(), PayrollError::OverlappingRun(first.id().to_string()));
+        
+        // Adjacent periods and other pay groups are fine
+        assert!(PayrollRun::create_for_group("hourly", date(1, 16), date(1, 31), date(2, 5), &existing).is_ok());
+        assert!(PayrollRun::create_for_group("salaried", date(1, 1), date(1, 31), date(2, 5), &existing).is_ok());
+    }
 }
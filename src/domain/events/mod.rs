@@ -2,17 +2,54 @@
 
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use crate::domain::value_objects::EmployeeId;
 
 /// All domain events
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DomainEvent {
     Employee(EmployeeEvent),
     Payroll(PayrollEvent),
     TimeTracking(TimeTrackingEvent),
 }
 
-#[derive(Clone, Debug)]
+impl DomainEvent {
+    /// Dotted event name used for subjects, webhooks, and the event store,
+    /// e.g. `employee.hired`
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            DomainEvent::Employee(event) => match event {
+                EmployeeEvent::Hired { .. } => "employee.hired",
+                EmployeeEvent::Promoted { .. } => "employee.promoted",
+                EmployeeEvent::CompensationChanged { .. } => "employee.compensation_changed",
+                EmployeeEvent::Terminated { .. } => "employee.terminated",
+                EmployeeEvent::Transferred { .. } => "employee.transferred",
+                EmployeeEvent::OnLeaveStarted { .. } => "employee.on_leave_started",
+                EmployeeEvent::OnLeaveEnded { .. } => "employee.on_leave_ended",
+                EmployeeEvent::Suspended { .. } => "employee.suspended",
+                EmployeeEvent::Reinstated { .. } => "employee.reinstated",
+                EmployeeEvent::Retired { .. } => "employee.retired",
+                EmployeeEvent::Rehired { .. } => "employee.rehired",
+            },
+            DomainEvent::Payroll(event) => match event {
+                PayrollEvent::Created { .. } => "payroll.created",
+                PayrollEvent::Approved { .. } => "payroll.approved",
+                PayrollEvent::Completed { .. } => "payroll.completed",
+                PayrollEvent::Failed { .. } => "payroll.failed",
+            },
+            DomainEvent::TimeTracking(event) => match event {
+                TimeTrackingEvent::ClockedIn { .. } => "time_tracking.clocked_in",
+                TimeTrackingEvent::ClockedOut { .. } => "time_tracking.clocked_out",
+                TimeTrackingEvent::TimeOffRequested { .. } => "time_tracking.time_off_requested",
+                TimeTrackingEvent::TimeOffApproved { .. } => "time_tracking.time_off_approved",
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum EmployeeEvent {
     Hired {
         employee_id: EmployeeId,
@@ -33,6 +70,13 @@ pub enum EmployeeEvent {
         termination_date: NaiveDate,
         reason: String,
     },
+    Transferred {
+        employee_id: EmployeeId,
+        from_department_id: Option<String>,
+        to_department_id: String,
+        effective_date: NaiveDate,
+        reason: String,
+    },
     OnLeaveStarted {
         employee_id: EmployeeId,
         leave_type: String,
@@ -42,9 +86,27 @@ pub enum EmployeeEvent {
         employee_id: EmployeeId,
         return_date: NaiveDate,
     },
+    Suspended {
+        employee_id: EmployeeId,
+        effective_date: NaiveDate,
+        reason: String,
+    },
+    Reinstated {
+        employee_id: EmployeeId,
+        effective_date: NaiveDate,
+    },
+    Retired {
+        employee_id: EmployeeId,
+        retirement_date: NaiveDate,
+    },
+    Rehired {
+        employee_id: EmployeeId,
+        rehire_date: NaiveDate,
+    },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum PayrollEvent {
     Created {
         payroll_id: String,
@@ -67,7 +129,8 @@ pub enum PayrollEvent {
     },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum TimeTrackingEvent {
     ClockedIn {
         employee_id: String,
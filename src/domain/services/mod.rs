@@ -1,5 +1,17 @@
 //! Domain services
 
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::compliance::{ActorType, AuditAction, AuditLog, AuditLogStore};
+use crate::domain::aggregates::{DepartmentTransfer, Employee, EmployeeError, EmploymentStatus, LegalEntities, OnboardingTemplate};
+use crate::domain::value_objects::{EmployeeId, TaxId, WorkingTime};
+use crate::leave::{LeaveAccount, SeparationReason};
+use crate::validation::{Validate, ValidationErrors, Validator};
+
 /// Payroll calculation service
 pub struct PayrollCalculator;
 
@@ -28,18 +40,1121 @@ impl PayrollCalculator {
     }
 }
 
+/// A week's hours split for pay
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeeklyHours {
+    pub worked: Decimal,
+    pub regular: Decimal,
+    pub overtime: Decimal,
+}
+
 /// Time tracking service
 pub struct TimeTrackingCalculator;
 
 impl TimeTrackingCalculator {
     /// Calculate overtime hours (over 40 hours)
     pub fn calculate_overtime(hours_worked: rust_decimal::Decimal) -> rust_decimal::Decimal {
-        use rust_decimal::Decimal;
-        let threshold = Decimal::new(40, 0);
-        if hours_worked > threshold {
-            hours_worked - threshold
+        Self::calculate_overtime_for(hours_worked, &WorkingTime::default())
+    }
+    
+    /// Calculate overtime hours beyond the standard week
+    pub fn calculate_overtime_for(hours_worked: Decimal, working_time: &WorkingTime) -> Decimal {
+        (hours_worked - working_time.standard_hours_per_week()).max(Decimal::ZERO)
+    }
+    
+    /// Split a week's hours; exempt employees record every hour worked but
+    /// never accrue overtime
+    pub fn weekly_hours(hours_worked: Decimal, working_time: &WorkingTime, overtime_eligible: bool) -> WeeklyHours {
+        let overtime = if overtime_eligible {
+            Self::calculate_overtime_for(hours_worked, working_time)
+        } else {
+            Decimal::ZERO
+        };
+        WeeklyHours { worked: hours_worked, regular: hours_worked - overtime, overtime }
+    }
+    
+    /// `weekly_hours` using the employee's working time and exemption status
+    pub fn weekly_hours_for(employee: &Employee, hours_worked: Decimal) -> WeeklyHours {
+        Self::weekly_hours(hours_worked, &employee.compensation().working_time, employee.overtime_eligible())
+    }
+    
+    /// Overtime premium pay for a week
+    pub fn overtime_pay(hours: &WeeklyHours, base_rate: Decimal, multiplier: Decimal) -> Decimal {
+        hours.overtime * base_rate * multiplier
+    }
+    
+    /// Hourly base for overtime pay derived from an annual salary
+    pub fn overtime_base_rate(annual_salary: Decimal, working_time: &WorkingTime) -> Decimal {
+        working_time.annual_to_hourly(annual_salary)
+    }
+}
+
+/// Effective-dated change job
+pub struct EffectiveDatingService;
+
+impl EffectiveDatingService {
+    /// Apply due pending changes across employees; returns the number applied
+    pub fn apply_pending_changes(employees: &mut [Employee], as_of: NaiveDate) -> usize {
+        employees.iter_mut().map(|e| e.apply_pending_changes(as_of)).sum()
+    }
+}
+
+/// Request to hire a new employee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateEmployeeRequest {
+    pub first_name: String,
+    pub last_name: String,
+    pub work_email: String,
+    pub job_title: String,
+    pub hire_date: NaiveDate,
+    #[serde(default)]
+    pub tax_id: Option<String>,
+}
+
+impl Validate for CreateEmployeeRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut v = Validator::new();
+        v.required("first_name", &self.first_name)
+            .max_length("first_name", &self.first_name, 100)
+            .required("last_name", &self.last_name)
+            .max_length("last_name", &self.last_name, 100)
+            .email("work_email", &self.work_email)
+            .required("job_title", &self.job_title);
+        if let Some(tax_id) = &self.tax_id {
+            v.check(TaxId::new_tin(tax_id.as_str()).is_ok(), "tax_id", "invalid_tax_id", "tax_id must contain letters or digits");
+        }
+        v.finish()
+    }
+}
+
+/// Field that identified an existing employee as the same person
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateField {
+    Email,
+    TaxId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CreateEmployeeError {
+    Invalid(ValidationErrors),
+    /// Same person already on file in the tenant; retry with `force` for a rehire
+    Duplicate { existing_employee_id: String, matched_on: DuplicateField },
+    /// Forced rehire of someone whose termination rules it out, or too soon
+    RehireIneligible { existing_employee_id: String, reason: EmployeeError },
+}
+
+impl std::error::Error for CreateEmployeeError {}
+impl std::fmt::Display for CreateEmployeeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(errors) => write!(f, "{}", errors),
+            Self::Duplicate { existing_employee_id, matched_on } => {
+                let field = match matched_on {
+                    DuplicateField::Email => "email",
+                    DuplicateField::TaxId => "tax ID",
+                };
+                write!(f, "An employee with the same {} already exists: {}", field, existing_employee_id)
+            }
+            Self::RehireIneligible { existing_employee_id, reason } => write!(f, "{}: {}", reason, existing_employee_id),
+        }
+    }
+}
+
+impl From<ValidationErrors> for CreateEmployeeError {
+    fn from(errors: ValidationErrors) -> Self {
+        Self::Invalid(errors)
+    }
+}
+
+fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// Rows kept outside the employee aggregate but keyed by its record id,
+/// such as leave history. Registered stores follow the survivor when
+/// duplicate records are merged.
+pub trait EmployeeRecords: std::fmt::Debug + Send + Sync {
+    /// Why the rows held for `from` can't move to `to` right now, if they
+    /// can't. Every store is checked before any of them moves anything.
+    fn check_reparent(&self, _from: Uuid, _to: Uuid) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Move everything held for `from` onto `to`, returning the rows moved
+    fn reparent(&self, from: Uuid, to: Uuid) -> usize;
+}
+
+/// Outcome of merging a duplicate record into its survivor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmployeeMerge {
+    pub survivor_id: String,
+    pub duplicate_id: String,
+    /// Related rows moved from the duplicate, across registered stores
+    pub rows_reparented: usize,
+}
+
+/// Employee lifecycle service over an in-memory store
+#[derive(Debug, Default)]
+pub struct EmployeeService {
+    // In real implementation, these come from repositories
+    employees: HashMap<String, Employee>,
+    departments: HashSet<String>,
+    /// Owning tenant by employee record id
+    tenants: HashMap<String, Uuid>,
+    related: Vec<Arc<dyn EmployeeRecords>>,
+    audit: AuditLogStore,
+    legal_entities: LegalEntities,
+    onboarding_template: OnboardingTemplate,
+}
+
+impl EmployeeService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Reparent `records` onto the survivor when employees are merged
+    pub fn with_related_records(mut self, records: Arc<dyn EmployeeRecords>) -> Self {
+        self.related.push(records);
+        self
+    }
+    
+    /// Companies employees can be assigned to, shared with payroll
+    pub fn with_legal_entities(mut self, legal_entities: LegalEntities) -> Self {
+        self.legal_entities = legal_entities;
+        self
+    }
+    
+    /// Checklist new hires must complete before they're paid
+    pub fn with_onboarding_template(mut self, template: OnboardingTemplate) -> Self {
+        self.onboarding_template = template;
+        self
+    }
+    
+    /// Record merges in `audit` rather than a private log
+    pub fn with_audit_log(mut self, audit: AuditLogStore) -> Self {
+        self.audit = audit;
+        self
+    }
+    
+    pub fn audit_log(&self) -> &AuditLogStore {
+        &self.audit
+    }
+    
+    /// Apply every employee's pending changes due by `as_of`; returns the
+    /// number applied
+    pub fn apply_pending_changes(&mut self, as_of: NaiveDate) -> usize {
+        self.employees.values_mut().map(|e| e.apply_pending_changes(as_of)).sum()
+    }
+    
+    /// End an employee's employment for `reason`. With the employee's leave
+    /// account, a resignation or abandonment dated inside protected leave
+    /// is refused. Dismissals end employment for cause.
+    pub fn separate(
+        &mut self,
+        employee_id: &str,
+        leave_account: Option<&LeaveAccount>,
+        date: NaiveDate,
+        reason: SeparationReason,
+        details: impl Into<String>,
+    ) -> Result<(), EmployeeError> {
+        if let Some(account) = leave_account {
+            account.validate_separation(date, reason).map_err(|_| EmployeeError::OnProtectedLeave(date))?;
+        }
+        let employee = self.employee_mut(employee_id).ok_or(EmployeeError::NotFound)?;
+        match reason {
+            SeparationReason::Dismissal => employee.terminate_for_cause(date, details),
+            _ => employee.terminate(date, details),
+        }
+    }
+    
+    pub fn add_department(&mut self, department_id: impl Into<String>) {
+        self.departments.insert(department_id.into());
+    }
+    
+    /// Validate and hire into a tenant. Every field problem is reported
+    /// together. An existing employee in the tenant with the same email or
+    /// tax ID blocks the hire unless `force` is set and that employee has
+    /// been terminated, in which case the new record is linked to the
+    /// existing one as a rehire, provided the earlier termination left the
+    /// person eligible by the new hire date. Someone still employed is never
+    /// hired a second time.
+    pub fn create_employee(
+        &mut self,
+        tenant_id: Uuid,
+        employee_id: EmployeeId,
+        request: CreateEmployeeRequest,
+        force: bool,
+    ) -> Result<&Employee, CreateEmployeeError> {
+        request.validate()?;
+        let tax_id = request.tax_id.as_deref().map(TaxId::new_tin).transpose().ok().flatten();
+
+        let duplicate = self.find_duplicate(tenant_id, &request.work_email, tax_id.as_ref());
+        if let Some((existing_employee_id, matched_on)) = &duplicate {
+            let previous = self.employees.get(existing_employee_id).filter(|e| e.status() == &EmploymentStatus::Terminated);
+            let Some(previous) = previous.filter(|_| force) else {
+                return Err(CreateEmployeeError::Duplicate {
+                    existing_employee_id: existing_employee_id.clone(),
+                    matched_on: *matched_on,
+                });
+            };
+            previous.rehire_eligibility().check(request.hire_date).map_err(|reason| {
+                CreateEmployeeError::RehireIneligible { existing_employee_id: existing_employee_id.clone(), reason }
+            })?;
+        }
+
+        let mut employee = Employee::hire(
+            employee_id,
+            request.first_name,
+            request.last_name,
+            request.work_email,
+            request.job_title,
+            request.hire_date,
+        );
+        employee.set_tax_id(tax_id);
+        employee.start_onboarding(&self.onboarding_template);
+        if let Some((previous, _)) = duplicate {
+            employee.link_previous_record(previous);
+        }
+        let key = employee.id().to_string();
+        self.tenants.insert(key.clone(), tenant_id);
+        Ok(self.employees.entry(key).or_insert(employee))
+    }
+    
+    /// Most recent employee in the tenant matching the normalized email
+    /// (work or personal) or tax ID
+    pub fn find_duplicate(&self, tenant_id: Uuid, email: &str, tax_id: Option<&TaxId>) -> Option<(String, DuplicateField)> {
+        let email = normalize_email(email);
+        self.tenant_employees(tenant_id)
+            .filter_map(|e| {
+                let email_match = normalize_email(&e.employment().work_email) == email
+                    || e.personal().personal_email.as_deref().is_some_and(|p| normalize_email(p) == email);
+                let tax_match = matches!((tax_id, e.tax_id()), (Some(a), Some(b)) if a.same_number(b));
+                let matched_on = if tax_match {
+                    DuplicateField::TaxId
+                } else if email_match && !email.is_empty() {
+                    DuplicateField::Email
+                } else {
+                    return None;
+                };
+                Some((e, matched_on))
+            })
+            .max_by_key(|(e, _)| e.created_at())
+            .map(|(e, matched_on)| (e.id().to_string(), matched_on))
+    }
+    
+    /// The tenant's records of the person with this work or personal email,
+    /// earlier employments included, for a data subject request
+    pub fn subject_records(&self, tenant_id: Uuid, email: &str) -> Vec<&Employee> {
+        let email = normalize_email(email);
+        if email.is_empty() {
+            return Vec::new();
+        }
+        self.employees
+            .values()
+            .filter(|e| self.tenants.get(e.id()) == Some(&tenant_id))
+            .filter(|e| {
+                normalize_email(&e.employment().work_email) == email
+                    || e.personal().personal_email.as_deref().is_some_and(|p| normalize_email(p) == email)
+            })
+            .collect()
+    }
+    
+    /// The employee's record followed by each earlier record from previous employments
+    pub fn employment_history(&self, employee_id: &str) -> Vec<&Employee> {
+        let mut history = Vec::new();
+        let mut next = self.employees.get(employee_id);
+        while let Some(employee) = next {
+            if history.iter().any(|e: &&Employee| e.id() == employee.id()) {
+                break;
+            }
+            history.push(employee);
+            next = employee.previous_record_id().and_then(|id| self.employees.get(id));
+        }
+        history
+    }
+    
+    /// Next free employee number in the tenant for a hire year. Merged
+    /// records keep their numbers, so those are never reused.
+    pub fn next_employee_id(&self, tenant_id: Uuid, year: u16) -> EmployeeId {
+        let last = self.employees
+            .values()
+            .filter(|e| self.tenants.get(e.id()) == Some(&tenant_id) && e.employee_id().year() == year)
+            .map(|e| e.employee_id().sequence())
+            .max()
+            .unwrap_or(0);
+        EmployeeId::new(year, last + 1)
+    }
+    
+    pub fn add_employee(&mut self, employee: Employee) {
+        self.employees.insert(employee.id().to_string(), employee);
+    }
+    
+    /// Employees belonging to `tenant_id`, in no particular order. Records
+    /// merged into another are left out.
+    pub fn tenant_employees(&self, tenant_id: Uuid) -> impl Iterator<Item = &Employee> + '_ {
+        self.employees
+            .values()
+            .filter(move |e| self.tenants.get(e.id()) == Some(&tenant_id) && e.merged_into().is_none())
+    }
+    
+    pub fn tenant_of(&self, employee_id: &str) -> Option<Uuid> {
+        self.tenants.get(employee_id).copied()
+    }
+    
+    /// Mandatory onboarding items the tenant's employee hasn't finished, or
+    /// `None` when there is no such employee
+    pub fn onboarding_outstanding(&self, tenant_id: Uuid, employee_id: &str) -> Option<Vec<String>> {
+        if self.tenant_of(employee_id) != Some(tenant_id) {
+            return None;
+        }
+        self.employee(employee_id).map(Employee::onboarding_outstanding)
+    }
+    
+    pub fn employee(&self, employee_id: &str) -> Option<&Employee> {
+        self.employees.get(employee_id)
+    }
+    
+    pub fn employee_mut(&mut self, employee_id: &str) -> Option<&mut Employee> {
+        self.employees.get_mut(employee_id)
+    }
+    
+    /// Record id that `employee_id` now lives under, following merges.
+    /// `None` when the id was never on file.
+    pub fn resolve<'a>(&'a self, employee_id: &'a str) -> Option<&'a str> {
+        let mut id = employee_id;
+        for _ in 0..self.employees.len() {
+            match self.employees.get(id)?.merged_into() {
+                Some(survivor) => id = survivor,
+                None => return Some(id),
+            }
+        }
+        None
+    }
+    
+    /// Fold a duplicate record into `survivor_id`. Every registered store
+    /// moves its rows across (leave history, payroll history), then the
+    /// survivor takes over the duplicate's documents, contacts, and
+    /// benefits. The duplicate is archived and redirects to the survivor
+    /// from then on. Both records and every store are checked before
+    /// anything moves, and nothing after the checks can fail, so a rejected
+    /// merge leaves everything untouched. In real implementation, the moves
+    /// run in one database transaction.
+    pub fn merge(
+        &mut self,
+        survivor_id: &str,
+        duplicate_id: &str,
+        actor_id: Option<Uuid>,
+    ) -> Result<EmployeeMerge, EmployeeError> {
+        if survivor_id == duplicate_id {
+            return Err(EmployeeError::InvalidMerge("a record cannot be merged into itself".to_string()));
+        }
+        for id in [survivor_id, duplicate_id] {
+            let employee = self.employees.get(id).ok_or(EmployeeError::NotFound)?;
+            if let Some(survivor_id) = employee.merged_into() {
+                return Err(EmployeeError::Merged { survivor_id: survivor_id.to_string() });
+            }
+        }
+        let tenant_id = self
+            .tenants
+            .get(survivor_id)
+            .copied()
+            .ok_or_else(|| EmployeeError::InvalidMerge("survivor has no tenant".to_string()))?;
+        if self.tenants.get(duplicate_id) != Some(&tenant_id) {
+            return Err(EmployeeError::InvalidMerge("records belong to different tenants".to_string()));
+        }
+        let (Ok(survivor_uuid), Ok(duplicate_uuid)) = (survivor_id.parse::<Uuid>(), duplicate_id.parse::<Uuid>()) else {
+            return Err(EmployeeError::InvalidMerge("record ids are not UUIDs".to_string()));
+        };
+        for records in &self.related {
+            records.check_reparent(duplicate_uuid, survivor_uuid).map_err(EmployeeError::InvalidMerge)?;
+        }
+
+        let rows_reparented = self.related.iter().map(|r| r.reparent(duplicate_uuid, survivor_uuid)).sum();
+        let duplicate = self.employees.get(duplicate_id).cloned().ok_or(EmployeeError::NotFound)?;
+        if let Some(survivor) = self.employees.get_mut(survivor_id) {
+            survivor.absorb(&duplicate);
+        }
+        if let Some(duplicate) = self.employees.get_mut(duplicate_id) {
+            duplicate.mark_merged_into(survivor_id);
+        }
+
+        let merge = EmployeeMerge {
+            survivor_id: survivor_id.to_string(),
+            duplicate_id: duplicate_id.to_string(),
+            rows_reparented,
+        };
+        let actor_type = if actor_id.is_some() { ActorType::User } else { ActorType::System };
+        self.audit.append(
+            AuditLog::new(tenant_id, "employee", duplicate_uuid, AuditAction::Merge, actor_id, actor_type)
+                .with_changes(
+                    serde_json::json!({ "id": duplicate_id, "employee_number": duplicate.employee_id().to_string() }),
+                    serde_json::to_value(&merge).unwrap_or_default(),
+                ),
+        );
+        Ok(merge)
+    }
+    
+    /// Employ someone through one of their tenant's legal entities
+    pub fn assign_legal_entity(&mut self, employee_id: &str, legal_entity_id: Uuid) -> Result<(), EmployeeError> {
+        let tenant_id = self.tenants.get(employee_id).copied().ok_or(EmployeeError::NotFound)?;
+        if self.legal_entities.for_tenant(tenant_id, legal_entity_id).is_none() {
+            return Err(EmployeeError::LegalEntityNotFound(legal_entity_id));
+        }
+        let employee = self.employees.get_mut(employee_id).ok_or(EmployeeError::NotFound)?;
+        employee.assign_legal_entity(legal_entity_id);
+        Ok(())
+    }
+    
+    /// Employees of one legal entity, in no particular order
+    pub fn entity_employees(&self, legal_entity_id: Uuid) -> impl Iterator<Item = &Employee> + '_ {
+        self.employees
+            .values()
+            .filter(move |e| e.legal_entity_id() == Some(legal_entity_id) && e.merged_into().is_none())
+    }
+    
+    /// Transfer an employee to another department, recording history and
+    /// raising `EmployeeEvent::Transferred` on the aggregate
+    pub fn transfer(
+        &mut self,
+        employee_id: &str,
+        new_department_id: &str,
+        effective_date: NaiveDate,
+        reason: impl Into<String>,
+    ) -> Result<DepartmentTransfer, EmployeeError> {
+        if !self.departments.contains(new_department_id) {
+            return Err(EmployeeError::DepartmentNotFound(new_department_id.to_string()));
+        }
+        let employee = self.employees.get_mut(employee_id).ok_or(EmployeeError::NotFound)?;
+        employee.transfer_department(new_department_id, effective_date, reason)
+    }
+    
+    /// Cost-center split for a pay period: days per department in effect
+    pub fn cost_center_allocation(
+        &self,
+        employee_id: &str,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> Result<Vec<(Option<String>, i64)>, EmployeeError> {
+        let employee = self.employees.get(employee_id).ok_or(EmployeeError::NotFound)?;
+        Ok(employee.department_allocation(period_start, period_end))
+    }
+}
+
+/// Salary band for a role (annual amounts)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SalaryBand {
+    pub job_title: String,
+    pub min: Decimal,
+    pub mid: Decimal,
+    pub max: Decimal,
+}
+
+/// Where an employee's pay sits relative to their band
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BandStatus {
+    BelowMin,
+    WithinBand,
+    AboveMax,
+}
+
+/// An employee's position in their band
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BandPosition {
+    pub employee_id: String,
+    pub job_title: String,
+    pub annual_salary: Decimal,
+    pub status: BandStatus,
+    /// Salary as a percentage of band midpoint
+    pub compa_ratio: Decimal,
+}
+
+/// Pay statistics for one group within a band
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupPayStats {
+    pub group: String,
+    pub count: usize,
+    pub mean: Decimal,
+    pub median: Decimal,
+}
+
+/// Pay gap within a band between groups of an attribute
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PayGapReport {
+    pub job_title: String,
+    pub groups: Vec<GroupPayStats>,
+    /// (highest mean − lowest mean) / highest mean, as a percentage
+    pub mean_gap_percent: Decimal,
+    pub median_gap_percent: Decimal,
+}
+
+/// Salary band and pay-equity analysis
+///
+/// Employees without a pay rate or whose job title has no band are left
+/// out of band checks and reported by `unbanded`.
+#[derive(Clone, Debug, Default)]
+pub struct CompensationAnalysis {
+    bands: HashMap<String, SalaryBand>,
+}
+
+impl CompensationAnalysis {
+    pub fn new(bands: Vec<SalaryBand>) -> Self {
+        Self {
+            bands: bands.into_iter().map(|b| (b.job_title.to_lowercase(), b)).collect(),
+        }
+    }
+    
+    pub fn band_for(&self, job_title: &str) -> Option<&SalaryBand> {
+        self.bands.get(&job_title.to_lowercase())
+    }
+    
+    /// Band position for every employee that has a band and a pay rate
+    pub fn positions(&self, employees: &[Employee]) -> Vec<BandPosition> {
+        employees.iter().filter_map(|e| self.position(e)).collect()
+    }
+    
+    /// Employees paid below their band minimum or above its maximum
+    pub fn out_of_band(&self, employees: &[Employee]) -> Vec<BandPosition> {
+        self.positions(employees)
+            .into_iter()
+            .filter(|p| p.status != BandStatus::WithinBand)
+            .collect()
+    }
+    
+    /// Employee ids that could not be checked (no band or no pay rate)
+    pub fn unbanded<'a>(&self, employees: &'a [Employee]) -> Vec<&'a str> {
+        employees
+            .iter()
+            .filter(|e| self.position(e).is_none())
+            .map(|e| e.id())
+            .collect()
+    }
+    
+    /// Pay gap by gender within one band
+    pub fn gender_pay_gap(&self, employees: &[Employee], job_title: &str) -> Option<PayGapReport> {
+        self.pay_gap_by(employees, job_title, |e| e.personal().gender.clone())
+    }
+    
+    /// Pay gap within one band grouped by any employee attribute
+    ///
+    /// Employees with no value for the attribute are excluded. Returns `None`
+    /// when the band is undefined or fewer than two groups are present.
+    pub fn pay_gap_by<F>(&self, employees: &[Employee], job_title: &str, group_of: F) -> Option<PayGapReport>
+    where
+        F: Fn(&Employee) -> Option<String>,
+    {
+        let band = self.band_for(job_title)?;
+        
+        let mut salaries: BTreeMap<String, Vec<Decimal>> = BTreeMap::new();
+        for employee in employees.iter().filter(|e| e.employment().job_title.eq_ignore_ascii_case(&band.job_title)) {
+            let (Some(group), Some(annual)) = (group_of(employee), employee.full_time_annual_pay()) else {
+                continue;
+            };
+            salaries.entry(group).or_default().push(annual);
+        }
+        if salaries.len() < 2 {
+            return None;
+        }
+        
+        let groups: Vec<GroupPayStats> = salaries
+            .into_iter()
+            .map(|(group, mut values)| {
+                values.sort();
+                let count = values.len();
+                let mean = values.iter().sum::<Decimal>() / Decimal::from(count);
+                let median = if count % 2 == 0 {
+                    (values[count / 2 - 1] + values[count / 2]) / Decimal::TWO
+                } else {
+                    values[count / 2]
+                };
+                GroupPayStats { group, count, mean, median }
+            })
+            .collect();
+        
+        let gap = |values: Vec<Decimal>| {
+            let high = values.iter().copied().max().unwrap_or_default();
+            let low = values.iter().copied().min().unwrap_or_default();
+            if high.is_zero() { Decimal::ZERO } else { ((high - low) / high * Decimal::ONE_HUNDRED).round_dp(2) }
+        };
+        
+        Some(PayGapReport {
+            job_title: band.job_title.clone(),
+            mean_gap_percent: gap(groups.iter().map(|g| g.mean).collect()),
+            median_gap_percent: gap(groups.iter().map(|g| g.median).collect()),
+            groups,
+        })
+    }
+    
+    fn position(&self, employee: &Employee) -> Option<BandPosition> {
+        let band = self.band_for(&employee.employment().job_title)?;
+        let annual_salary = employee.full_time_annual_pay()?;
+        
+        let status = if annual_salary < band.min {
+            BandStatus::BelowMin
+        } else if annual_salary > band.max {
+            BandStatus::AboveMax
         } else {
+            BandStatus::WithinBand
+        };
+        let compa_ratio = if band.mid.is_zero() {
             Decimal::ZERO
+        } else {
+            (annual_salary / band.mid * Decimal::ONE_HUNDRED).round_dp(1)
+        };
+        
+        Some(BandPosition {
+            employee_id: employee.id().to_string(),
+            job_title: band.job_title.clone(),
+            annual_salary,
+            status,
+            compa_ratio,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::events::{DomainEvent, EmployeeEvent};
+    use crate::domain::aggregates::EmployeeChange;
+    use crate::domain::value_objects::{EmployeeId, PayFrequency, PayRate};
+    use rust_decimal_macros::dec;
+    
+    fn service_with_employee() -> (EmployeeService, String) {
+        let mut service = EmployeeService::new();
+        service.add_department("sales");
+        service.add_department("eng");
+        
+        let mut employee = Employee::hire(
+            EmployeeId::new(2024, 7),
+            "Ada",
+            "Obi",
+            "ada@company.com",
+            "Analyst",
+            NaiveDate::from_ymd_opt(2024, 1, 8).unwrap(),
+        );
+        employee.take_events();
+        let id = employee.id().to_string();
+        service.add_employee(employee);
+        service.transfer(&id, "sales", NaiveDate::from_ymd_opt(2024, 1, 8).unwrap(), "Initial placement").unwrap();
+        (service, id)
+    }
+    
+    #[test]
+    fn test_exempt_employee_gets_no_overtime() {
+        let hired = NaiveDate::from_ymd_opt(2024, 1, 8).unwrap();
+        let mut salaried = Employee::hire(EmployeeId::new(2024, 20), "Ada", "Obi", "ada@company.com", "Manager", hired);
+        salaried.set_compensation(PayRate::salary(dec!(104000), "USD", PayFrequency::Annually), hired);
+        let mut hourly = Employee::hire(EmployeeId::new(2024, 21), "Tunde", "Bello", "tunde@company.com", "Technician", hired);
+        hourly.set_compensation(PayRate::hourly(dec!(50), "USD"), hired);
+        
+        assert!(!salaried.overtime_eligible());
+        let exempt = TimeTrackingCalculator::weekly_hours_for(&salaried, dec!(50));
+        assert_eq!(exempt, WeeklyHours { worked: dec!(50), regular: dec!(50), overtime: Decimal::ZERO });
+        assert_eq!(TimeTrackingCalculator::overtime_pay(&exempt, dec!(50), dec!(1.5)), Decimal::ZERO);
+        
+        assert!(hourly.overtime_eligible());
+        let non_exempt = TimeTrackingCalculator::weekly_hours_for(&hourly, dec!(50));
+        assert_eq!(non_exempt.overtime, dec!(10));
+        assert_eq!(non_exempt.regular, dec!(40));
+        assert_eq!(TimeTrackingCalculator::overtime_pay(&non_exempt, dec!(50), dec!(1.5)), dec!(750));
+        
+        // A salaried non-exempt employee flagged explicitly
+        salaried.set_overtime_eligible(Some(true));
+        assert_eq!(TimeTrackingCalculator::weekly_hours_for(&salaried, dec!(50)).overtime, dec!(10));
+    }
+    
+    #[test]
+    fn test_transfer_records_history() {
+        let (mut service, id) = service_with_employee();
+        let date = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        
+        let transfer = service.transfer(&id, "eng", date, "Moved to platform team").unwrap();
+        assert_eq!(transfer.from_department_id.as_deref(), Some("sales"));
+        
+        let employee = service.employee_mut(&id).unwrap();
+        assert_eq!(employee.department_history().len(), 2);
+        assert_eq!(employee.department_history()[1].reason, "Moved to platform team");
+        assert_eq!(employee.employment().department_id.as_deref(), Some("eng"));
+        
+        let events = employee.take_events();
+        assert!(matches!(
+            events.last(),
+            Some(DomainEvent::Employee(EmployeeEvent::Transferred { to_department_id, .. })) if to_department_id == "eng"
+        ));
+        
+        let allocation = service
+            .cost_center_allocation(&id, NaiveDate::from_ymd_opt(2024, 5, 20).unwrap(), NaiveDate::from_ymd_opt(2024, 6, 9).unwrap())
+            .unwrap();
+        assert_eq!(allocation, vec![(Some("sales".to_string()), 12), (Some("eng".to_string()), 9)]);
+    }
+    
+    #[test]
+    fn test_transfer_to_unknown_department_rejected() {
+        let (mut service, id) = service_with_employee();
+        
+        let result = service.transfer(&id, "legal", NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(), "Reorg");
+        assert_eq!(result, Err(EmployeeError::DepartmentNotFound("legal".to_string())));
+        assert_eq!(service.employee(&id).unwrap().department_history().len(), 1);
+    }
+    
+    fn engineer(seq: u32, salary: Decimal, gender: Option<&str>) -> Employee {
+        let hire_date = NaiveDate::from_ymd_opt(2023, 1, 2).unwrap();
+        let mut employee = Employee::hire(EmployeeId::new(2023, seq), "Test", "Person", "t@company.com", "Engineer", hire_date);
+        employee.set_compensation(PayRate::salary(salary, "USD", PayFrequency::Annually), hire_date);
+        employee.update(EmployeeChange::Gender(gender.map(str::to_string)), None);
+        employee
+    }
+    
+    fn bands() -> CompensationAnalysis {
+        CompensationAnalysis::new(vec![SalaryBand {
+            job_title: "Engineer".to_string(),
+            min: dec!(80000),
+            mid: dec!(100000),
+            max: dec!(120000),
+        }])
+    }
+    
+    #[test]
+    fn test_out_of_band_employee_flagged() {
+        let low = engineer(1, dec!(70000), None);
+        let ok = engineer(2, dec!(100000), None);
+        let mut analyst = engineer(3, dec!(50000), None);
+        analyst.promote("Analyst", None);
+        let employees = vec![low.clone(), ok, analyst.clone()];
+        
+        let flagged = bands().out_of_band(&employees);
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].employee_id, low.id());
+        assert_eq!(flagged[0].status, BandStatus::BelowMin);
+        assert_eq!(flagged[0].compa_ratio, dec!(70.0));
+        
+        // No band for "Analyst": reported, not flagged
+        assert_eq!(bands().unbanded(&employees), vec![analyst.id()]);
+    }
+    
+    #[test]
+    fn test_two_group_gender_gap() {
+        let employees = vec![
+            engineer(1, dec!(100000), Some("male")),
+            engineer(2, dec!(110000), Some("male")),
+            engineer(3, dec!(90000), Some("female")),
+            engineer(4, dec!(94000), Some("female")),
+            engineer(5, dec!(200000), None),
+        ];
+        
+        let report = bands().gender_pay_gap(&employees, "engineer").unwrap();
+        assert_eq!(report.groups.len(), 2);
+        assert_eq!(report.groups[0].group, "female");
+        assert_eq!(report.groups[0].mean, dec!(92000));
+        assert_eq!(report.groups[1].mean, dec!(105000));
+        // (105000 − 92000) / 105000
+        assert_eq!(report.mean_gap_percent, dec!(12.38));
+        
+        assert!(bands().gender_pay_gap(&employees, "Designer").is_none());
+        assert!(bands().gender_pay_gap(&employees[..2], "Engineer").is_none());
+    }
+    
+    fn hire_request(email: &str, tax_id: Option<&str>) -> CreateEmployeeRequest {
+        CreateEmployeeRequest {
+            first_name: "Ada".into(),
+            last_name: "Obi".into(),
+            work_email: email.into(),
+            job_title: "Analyst".into(),
+            hire_date: NaiveDate::from_ymd_opt(2020, 2, 3).unwrap(),
+            tax_id: tax_id.map(str::to_string),
+        }
+    }
+    
+    #[test]
+    fn test_duplicate_tax_id_within_tenant() {
+        let mut service = EmployeeService::new();
+        let tenant = Uuid::new_v4();
+        let first = service.create_employee(tenant, EmployeeId::new(2020, 1), hire_request("ada@company.com", Some("2345-6789-01")), false)
+            .unwrap().id().to_string();
+        
+        // Different email, same tax number in another format
+        let err = service.create_employee(tenant, EmployeeId::new(2020, 2), hire_request("ada.obi@company.com", Some("23456789 01")), false)
+            .unwrap_err();
+        assert_eq!(err, CreateEmployeeError::Duplicate { existing_employee_id: first, matched_on: DuplicateField::TaxId });
+        
+        // Another tenant may employ the same person
+        assert!(service.create_employee(Uuid::new_v4(), EmployeeId::new(2020, 3), hire_request("ada@company.com", Some("2345678901")), false).is_ok());
+    }
+    
+    #[test]
+    fn test_forced_rehire_links_history() {
+        let mut service = EmployeeService::new();
+        service.add_department("finance");
+        let tenant = Uuid::new_v4();
+        let first = service.create_employee(tenant, EmployeeId::new(2020, 1), hire_request("ada@company.com", None), false)
+            .unwrap().id().to_string();
+        // Forcing can't hire someone a second time while they're employed
+        assert!(matches!(
+            service.create_employee(tenant, EmployeeId::new(2020, 2), hire_request("ada@company.com", None), true),
+            Err(CreateEmployeeError::Duplicate { matched_on: DuplicateField::Email, .. })
+        ));
+        let left_on = NaiveDate::from_ymd_opt(2022, 6, 30).unwrap();
+        service.transfer(&first, "finance", NaiveDate::from_ymd_opt(2021, 1, 4).unwrap(), "Reorg").unwrap();
+        service.employee_mut(&first).unwrap().terminate(left_on, "Resigned").unwrap();
+        
+        let rehire = CreateEmployeeRequest { hire_date: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), ..hire_request("Ada@Company.com", None) };
+        assert!(matches!(
+            service.create_employee(tenant, EmployeeId::new(2024, 1), rehire.clone(), false),
+            Err(CreateEmployeeError::Duplicate { matched_on: DuplicateField::Email, .. })
+        ));
+        
+        let second = service.create_employee(tenant, EmployeeId::new(2024, 1), rehire, true).unwrap();
+        assert_eq!(second.previous_record_id(), Some(first.as_str()));
+        let second = second.id().to_string();
+        
+        let history = service.employment_history(&second);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].id(), first);
+        assert_eq!(history[1].employment().termination_date, Some(left_on));
+        assert_eq!(history[1].department_history().len(), 1);
+        assert_eq!(service.next_employee_id(tenant, 2024), EmployeeId::new(2024, 2));
+        // Numbering is per tenant
+        assert_eq!(service.next_employee_id(Uuid::new_v4(), 2024), EmployeeId::new(2024, 1));
+    }
+    
+    #[test]
+    fn test_forced_rehire_respects_eligibility() {
+        use crate::domain::aggregates::RehireEligibility;
+        
+        let mut service = EmployeeService::new();
+        let tenant = Uuid::new_v4();
+        let left_on = NaiveDate::from_ymd_opt(2022, 6, 30).unwrap();
+        let fired = service.create_employee(tenant, EmployeeId::new(2020, 1), hire_request("ada@company.com", None), false)
+            .unwrap().id().to_string();
+        service.employee_mut(&fired).unwrap().terminate_for_cause(left_on, "Fraud").unwrap();
+        let waiting = service.create_employee(tenant, EmployeeId::new(2020, 2), hire_request("tunde@company.com", None), false)
+            .unwrap().id().to_string();
+        let not_before = NaiveDate::from_ymd_opt(2023, 7, 1).unwrap();
+        service.employee_mut(&waiting).unwrap()
+            .terminate_with_rehire(left_on, "Redundancy", RehireEligibility::not_before(not_before)).unwrap();
+        
+        let rehire = |email: &str, hire_date| CreateEmployeeRequest { hire_date, ..hire_request(email, None) };
+        assert_eq!(
+            service.create_employee(tenant, EmployeeId::new(2024, 1), rehire("ada@company.com", NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()), true)
+                .unwrap_err(),
+            CreateEmployeeError::RehireIneligible { existing_employee_id: fired, reason: EmployeeError::NotRehireEligible }
+        );
+        assert!(matches!(
+            service.create_employee(tenant, EmployeeId::new(2024, 1), rehire("tunde@company.com", NaiveDate::from_ymd_opt(2023, 1, 9).unwrap()), true),
+            Err(CreateEmployeeError::RehireIneligible { reason: EmployeeError::RehireWaitingPeriod { .. }, .. })
+        ));
+        
+        // After the waiting period the rehire goes through
+        let back = service.create_employee(tenant, EmployeeId::new(2024, 1), rehire("tunde@company.com", not_before), true).unwrap();
+        assert_eq!(back.previous_record_id(), Some(waiting.as_str()));
+    }
+    
+    #[test]
+    fn test_employee_belongs_to_one_entity_of_their_tenant() {
+        use crate::domain::aggregates::LegalEntity;
+        
+        let tenant = Uuid::new_v4();
+        let lagos = LegalEntity::new(tenant, "Acme Nigeria Ltd", "NG", "TIN-10293847");
+        let joburg = LegalEntity::new(tenant, "Acme South Africa (Pty) Ltd", "ZA", "7001234567");
+        let elsewhere = LegalEntity::new(Uuid::new_v4(), "Other Co", "NG", "TIN-555");
+        let entities = LegalEntities::new();
+        for entity in [&lagos, &joburg, &elsewhere] {
+            entities.add(entity.clone());
+        }
+        let mut service = EmployeeService::new().with_legal_entities(entities);
+        let ada = service.create_employee(tenant, EmployeeId::new(2024, 1), hire_request("ada@company.com", None), false)
+            .unwrap().id().to_string();
+        
+        service.assign_legal_entity(&ada, lagos.id).unwrap();
+        service.assign_legal_entity(&ada, joburg.id).unwrap();
+        assert_eq!(service.employee(&ada).unwrap().legal_entity_id(), Some(joburg.id));
+        assert_eq!(service.entity_employees(lagos.id).count(), 0);
+        assert_eq!(service.entity_employees(joburg.id).count(), 1);
+        
+        assert!(matches!(
+            service.assign_legal_entity(&ada, elsewhere.id),
+            Err(EmployeeError::LegalEntityNotFound(id)) if id == elsewhere.id
+        ));
+    }
+    
+    #[test]
+    fn test_merge_moves_leave_history_to_survivor() {
+        use crate::compliance::AuditFilter;
+        use crate::leave::{LeaveBalance, LeaveHistory, LeaveRequest, LeaveRequestStatus};
+        
+        let history = LeaveHistory::new();
+        let mut service = EmployeeService::new().with_related_records(Arc::new(history.clone()));
+        let tenant = Uuid::new_v4();
+        let survivor = service.create_employee(tenant, EmployeeId::new(2020, 1), hire_request("ada@company.com", None), false)
+            .unwrap().id().to_string();
+        let duplicate = service.create_employee(tenant, EmployeeId::new(2020, 2), hire_request("ada.obi@company.com", None), false)
+            .unwrap().id().to_string();
+        service.employee_mut(&duplicate).unwrap().set_custom_field("skills", serde_json::json!(["sql"]));
+        
+        let annual = Uuid::new_v4();
+        let now = chrono::Utc::now();
+        let d = |m, day| NaiveDate::from_ymd_opt(2024, m, day).unwrap();
+        let request = |employee_id: &str, start, end, days| LeaveRequest {
+            id: Uuid::new_v4(),
+            employee_id: employee_id.parse().unwrap(),
+            employee_name: None,
+            leave_type_id: annual,
+            leave_type_name: Some("Annual".into()),
+            start_date: start,
+            end_date: end,
+            days_requested: days,
+            half_day: false,
+            reason: None,
+            document_url: None,
+            relief_officer_id: None,
+            relief_officer_name: None,
+            handover_notes: None,
+            status: LeaveRequestStatus::Approved,
+            approved_by: None,
+            approver_name: None,
+            approved_at: None,
+            rejection_reason: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let balance = |employee_id: &str, used| LeaveBalance {
+            id: Uuid::new_v4(),
+            employee_id: employee_id.parse().unwrap(),
+            leave_type_id: annual,
+            leave_type_name: "Annual".into(),
+            year: 2024,
+            entitled_days: dec!(20),
+            used_days: used,
+            pending_days: Decimal::ZERO,
+            carried_over: Decimal::ZERO,
+            encashed_days: Decimal::ZERO,
+            created_at: now,
+            updated_at: now,
+        };
+        history.record_request(request(&survivor, d(3, 4), d(3, 8), dec!(5)));
+        history.record_request(request(&duplicate, d(6, 10), d(6, 12), dec!(3)));
+        history.record_balance(balance(&survivor, dec!(5)));
+        history.record_balance(balance(&duplicate, dec!(3)));
+        
+        let merge = service.merge(&survivor, &duplicate, None).unwrap();
+        assert_eq!(merge.rows_reparented, 2);
+        
+        let survivor_uuid: Uuid = survivor.parse().unwrap();
+        let requests = history.requests(survivor_uuid);
+        assert_eq!(requests.iter().map(|r| r.start_date).collect::<Vec<_>>(), vec![d(3, 4), d(6, 10)]);
+        assert!(history.requests(duplicate.parse().unwrap()).is_empty());
+        let balances = history.balances(survivor_uuid, 2024);
+        assert_eq!(balances.len(), 1);
+        // One person's entitlement, recorded twice, is not doubled
+        assert_eq!((balances[0].entitled_days, balances[0].used_days), (dec!(20), dec!(8)));
+        assert_eq!(service.employee(&survivor).unwrap().custom_fields()["skills"], serde_json::json!(["sql"]));
+        
+        // The duplicate id now points at the survivor and drops out of listings
+        assert_eq!(service.resolve(&duplicate), Some(survivor.as_str()));
+        assert!(service.employee(&duplicate).unwrap().is_archived());
+        assert_eq!(service.tenant_employees(tenant).count(), 1);
+        assert_eq!(
+            service.merge(&duplicate, &survivor, None).unwrap_err(),
+            EmployeeError::Merged { survivor_id: survivor.clone() }
+        );
+        
+        let entries = service.audit_log().query(&AuditFilter { action: Some(AuditAction::Merge), ..Default::default() }, None, 10);
+        assert_eq!(entries.entries.len(), 1);
+        assert_eq!(entries.entries[0].entity_id.to_string(), duplicate);
+        assert_eq!(entries.entries[0].tenant_id, tenant);
+    }
+    
+    #[test]
+    fn test_merge_refused_by_a_store_moves_nothing() {
+        use crate::leave::{LeaveBalance, LeaveHistory};
+        
+        #[derive(Debug)]
+        struct Locked;
+        impl EmployeeRecords for Locked {
+            fn check_reparent(&self, _from: Uuid, _to: Uuid) -> Result<(), String> {
+                Err("records are locked".to_string())
+            }
+            fn reparent(&self, _from: Uuid, _to: Uuid) -> usize {
+                unreachable!("refused in check_reparent")
+            }
         }
+        
+        let history = LeaveHistory::new();
+        let mut service = EmployeeService::new().with_related_records(Arc::new(history.clone())).with_related_records(Arc::new(Locked));
+        let tenant = Uuid::new_v4();
+        let survivor = service.create_employee(tenant, EmployeeId::new(2020, 1), hire_request("ada@company.com", None), false)
+            .unwrap().id().to_string();
+        let duplicate = service.create_employee(tenant, EmployeeId::new(2020, 2), hire_request("ada.obi@company.com", None), false)
+            .unwrap().id().to_string();
+        let now = chrono::Utc::now();
+        history.record_balance(LeaveBalance {
+            id: Uuid::new_v4(),
+            employee_id: duplicate.parse().unwrap(),
+            leave_type_id: Uuid::new_v4(),
+            leave_type_name: "Annual".into(),
+            year: 2024,
+            entitled_days: dec!(20),
+            used_days: dec!(3),
+            pending_days: Decimal::ZERO,
+            carried_over: Decimal::ZERO,
+            encashed_days: Decimal::ZERO,
+            created_at: now,
+            updated_at: now,
+        });
+        
+        assert_eq!(service.merge(&survivor, &duplicate, None).unwrap_err(), EmployeeError::InvalidMerge("records are locked".to_string()));
+        assert_eq!(history.balances(duplicate.parse().unwrap(), 2024).len(), 1);
+        assert!(service.employee(&duplicate).unwrap().merged_into().is_none());
+        assert!(service.audit_log().query(&Default::default(), None, 10).entries.is_empty());
+    }
+    
+    #[test]
+    fn test_merge_moves_payroll_history_to_survivor() {
+        use crate::payroll::{CreatePayrollRunRequest, EmployeeSalary, PayrollService};
+        
+        let payroll = PayrollService::new();
+        let mut service = EmployeeService::new().with_related_records(Arc::new(payroll.clone()));
+        let tenant = Uuid::new_v4();
+        let survivor = service.create_employee(tenant, EmployeeId::new(2020, 1), hire_request("ada@company.com", None), false)
+            .unwrap().id().to_string();
+        let duplicate = service.create_employee(tenant, EmployeeId::new(2020, 2), hire_request("ada.obi@company.com", None), false)
+            .unwrap().id().to_string();
+        let (survivor_uuid, duplicate_uuid): (Uuid, Uuid) = (survivor.parse().unwrap(), duplicate.parse().unwrap());
+        
+        let request = CreatePayrollRunRequest {
+            name: "June 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let salary: EmployeeSalary = serde_json::from_value(serde_json::json!({
+            "employee_id": duplicate_uuid,
+            "employee_name": "Ada Obi",
+            "employee_code": "EMP002",
+            "basic_salary": "300000",
+            "housing_allowance": "0",
+            "transport_allowance": "0",
+            "meal_allowance": "0",
+            "utility_allowance": "0",
+            "other_allowances": {},
+            "loan_balance": "0",
+            "loan_monthly_repayment": "0",
+            "onboarding_outstanding": [],
+        }))
+        .unwrap();
+        let mut run = payroll.create_payroll_run(tenant, request).unwrap();
+        payroll.process_payroll(&mut run, vec![salary], Uuid::new_v4()).unwrap();
+        payroll.approve_payroll(&mut run, Uuid::new_v4()).unwrap();
+        payroll.mark_as_paid(&mut run).unwrap();
+        let gross = payroll.ytd_summary(duplicate_uuid, 2024).gross;
+        assert!(gross > Decimal::ZERO);
+        
+        // Payslip, salary input, and YTD line
+        let merge = service.merge(&survivor, &duplicate, None).unwrap();
+        assert_eq!(merge.rows_reparented, 3);
+        assert_eq!(payroll.ytd_summary(survivor_uuid, 2024).gross, gross);
+        assert_eq!(payroll.ytd_summary(duplicate_uuid, 2024).periods, 0);
     }
 }
@@ -0,0 +1,186 @@
+//! Locale formatting for outputs
+//!
+//! Amounts and dates in SMS messages and payslips follow the reader's
+//! conventions: 1,234.56 in Lagos, 1 234,56 in Abidjan, 1'234.56 in Zurich;
+//! day-first dates in most places, month-first in the US. Conventions are
+//! looked up by locale tag ("fr-CI"), falling back to the language ("fr")
+//! and then to English.
+
+use std::collections::HashMap;
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+
+use super::money::NumberFormat;
+
+/// Order of day, month, and year in a written date
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateOrder {
+    DayMonthYear,
+    MonthDayYear,
+    YearMonthDay,
+}
+
+/// Output conventions for one locale
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LocaleFormat {
+    pub number: NumberFormat,
+    pub date_order: DateOrder,
+    pub date_separator: char,
+    /// Currency written after the amount ("1 234 FCFA") rather than before
+    pub currency_after: bool,
+}
+
+impl LocaleFormat {
+    /// 1,234.56 and 31/01/2024, currency first
+    pub const EN: Self = Self {
+        number: NumberFormat::EN,
+        date_order: DateOrder::DayMonthYear,
+        date_separator: '/',
+        currency_after: false,
+    };
+}
+
+/// Formatting conventions by locale tag
+#[derive(Debug, Clone)]
+pub struct LocaleFormatter {
+    locales: HashMap<String, LocaleFormat>,
+    default: LocaleFormat,
+}
+
+impl Default for LocaleFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocaleFormatter {
+    /// Built-in conventions. Separators come from `NumberFormat::for_locale`
+    /// so that amounts are formatted the way `parse_money` reads them.
+    pub fn new() -> Self {
+        use DateOrder::*;
+
+        let format = |locale: &str, date_order, date_separator, currency_after| LocaleFormat {
+            number: NumberFormat::for_locale(locale),
+            date_order,
+            date_separator,
+            currency_after,
+        };
+        let mut formatter = Self { locales: HashMap::new(), default: LocaleFormat::EN };
+        // Day-first, as in Nigeria, Ghana, Kenya, South Africa, and the UK
+        formatter.set("en", LocaleFormat::EN);
+        formatter.set("en-US", format("en-US", MonthDayYear, '/', false));
+        formatter.set("fr", format("fr", DayMonthYear, '/', true));
+        formatter.set("de", format("de", DayMonthYear, '.', true));
+        formatter.set("de-CH", format("de-CH", DayMonthYear, '.', false));
+        formatter.set("it", format("it", DayMonthYear, '/', true));
+        formatter.set("it-CH", format("it-CH", DayMonthYear, '.', false));
+        formatter.set("nl", format("nl", DayMonthYear, '-', false));
+        formatter.set("pt", format("pt", DayMonthYear, '/', false));
+        formatter.set("es", format("es", DayMonthYear, '/', true));
+        formatter.set("sw", LocaleFormat::EN);
+        formatter
+    }
+
+    pub fn set(&mut self, locale: &str, format: LocaleFormat) -> &mut Self {
+        self.locales.insert(normalize(locale), format);
+        self
+    }
+
+    /// Conventions for `locale`, then its language, then English dates
+    /// with the locale's separators
+    pub fn for_locale(&self, locale: &str) -> LocaleFormat {
+        let locale = normalize(locale);
+        let language = locale.split('-').next().unwrap_or_default();
+        self.locales
+            .get(&locale)
+            .or_else(|| self.locales.get(language))
+            .copied()
+            .unwrap_or(LocaleFormat { number: NumberFormat::for_locale(&locale), ..self.default })
+    }
+
+    /// Grouped amount without a currency
+    pub fn format_amount(&self, locale: &str, amount: Decimal, decimal_places: u32) -> String {
+        self.for_locale(locale).number.format(amount, decimal_places)
+    }
+
+    /// Grouped amount with the currency on the locale's side. Symbols sit
+    /// against the amount ("₦500,000"); codes are spaced ("CHF 1'234.50").
+    pub fn format_money(&self, locale: &str, amount: Decimal, currency: &str, decimal_places: u32) -> String {
+        let format = self.for_locale(locale);
+        let amount = format.number.format(amount, decimal_places);
+        if currency.is_empty() {
+            amount
+        } else if format.currency_after {
+            format!("{} {}", amount, currency)
+        } else if currency.chars().last().is_some_and(char::is_alphabetic) {
+            format!("{} {}", currency, amount)
+        } else {
+            format!("{}{}", currency, amount)
+        }
+    }
+
+    pub fn format_date(&self, locale: &str, date: NaiveDate) -> String {
+        let format = self.for_locale(locale);
+        let sep = format.date_separator;
+        let (day, month, year) = (date.day(), date.month(), date.year());
+        match format.date_order {
+            DateOrder::DayMonthYear => format!("{:02}{sep}{:02}{sep}{}", day, month, year),
+            DateOrder::MonthDayYear => format!("{:02}{sep}{:02}{sep}{}", month, day, year),
+            DateOrder::YearMonthDay => format!("{}{sep}{:02}{sep}{:02}", year, month, day),
+        }
+    }
+}
+
+/// "fr_ci" -> "fr-CI"
+fn normalize(locale: &str) -> String {
+    let mut parts = locale.split(['-', '_']);
+    let language = parts.next().unwrap_or_default().to_ascii_lowercase();
+    match parts.next() {
+        Some(region) if !region.is_empty() => format!("{}-{}", language, region.to_ascii_uppercase()),
+        _ => language,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_same_amount_and_date_across_locales() {
+        let formatter = LocaleFormatter::new();
+        let amount = dec!(1234567.891);
+        let date = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+
+        assert_eq!(formatter.format_amount("en-NG", amount, 2), "1,234,567.89");
+        assert_eq!(formatter.format_money("en-NG", amount, "₦", 2), "₦1,234,567.89");
+        assert_eq!(formatter.format_date("en-NG", date), "05/03/2024");
+
+        // French: space grouping, comma decimals, currency last
+        assert_eq!(formatter.format_amount("fr-CI", amount, 2), "1\u{202f}234\u{202f}567,89");
+        assert_eq!(formatter.format_money("fr_ci", amount, "FCFA", 0), "1\u{202f}234\u{202f}568 FCFA");
+        assert_eq!(formatter.format_date("fr-CI", date), "05/03/2024");
+
+        assert_eq!(formatter.format_amount("de-CH", amount, 2), "1'234'567.89");
+        assert_eq!(formatter.format_money("de-CH", amount, "CHF", 2), "CHF 1'234'567.89");
+        assert_eq!(formatter.format_date("de-CH", date), "05.03.2024");
+
+        assert_eq!(formatter.format_date("en-US", date), "03/05/2024");
+        assert_eq!(formatter.format_money("de-AT", dec!(-1234.5), "€", 2), "-1.234,50 €");
+        assert_eq!(formatter.for_locale("ja"), LocaleFormat::EN);
+    }
+
+    #[test]
+    fn test_formatting_round_trips_through_parse_money() {
+        use crate::domain::value_objects::money::parse_money;
+
+        let formatter = LocaleFormatter::new();
+        for locale in ["en-NG", "en-US", "fr-CI", "de", "de-CH", "it", "it-CH", "nl", "pt-BR", "es", "id", "tr", "sw"] {
+            assert_eq!(formatter.for_locale(locale).number, NumberFormat::for_locale(locale), "{}", locale);
+            let written = formatter.format_amount(locale, dec!(1234567.89), 2);
+            assert_eq!(parse_money(&written, locale), Ok(dec!(1234567.89)), "{}", locale);
+        }
+        assert_eq!(formatter.format_amount("it-CH", dec!(1234.5), 2), "1'234.50");
+        assert_eq!(formatter.format_amount("nl", dec!(1234.5), 2), "1.234,50");
+    }
+}
@@ -3,8 +3,14 @@
 pub mod employee_id;
 pub mod tax_id;
 pub mod pay_rate;
+pub mod working_time;
+pub mod money;
+pub mod locale;
 
 pub use employee_id::EmployeeId;
 pub use tax_id::{TaxId, TaxIdType, TaxIdError};
-pub use pay_rate::{PayRate, PayType, PayFrequency};
+pub use pay_rate::{PayRate, PayType, PayFrequency, UnknownPayFrequency};
+pub use working_time::WorkingTime;
+pub use money::{format_money, parse_money, MoneyParseError, NumberFormat};
+pub use locale::{DateOrder, LocaleFormat, LocaleFormatter};
 
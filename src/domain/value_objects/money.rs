@@ -0,0 +1,175 @@
+//! Locale-aware money parsing and formatting
+//!
+//! Amounts arrive as text from SMS replies and imports ("₦500,000",
+//! "1.234,56"). `parse_money` strips currency symbols and applies the
+//! locale's grouping and decimal separators; `format_money` produces the
+//! grouped form used in SMS templates, so the two round-trip.
+
+use rust_decimal::Decimal;
+use std::fmt;
+use std::str::FromStr;
+
+/// Separators used when writing numbers in a locale
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub decimal_separator: char,
+    pub group_separator: char,
+}
+
+impl NumberFormat {
+    /// 1,234.56
+    pub const EN: Self = Self { decimal_separator: '.', group_separator: ',' };
+    /// 1.234,56
+    pub const DE: Self = Self { decimal_separator: ',', group_separator: '.' };
+    /// 1 234,56 (narrow no-break space when formatting; any space accepted when parsing)
+    pub const FR: Self = Self { decimal_separator: ',', group_separator: '\u{202f}' };
+    /// 1'234.56
+    pub const CH: Self = Self { decimal_separator: '.', group_separator: '\'' };
+
+    /// Separators for a language or locale code ("de", "fr-CI", "en_NG").
+    /// Unknown locales use English separators.
+    pub fn for_locale(locale: &str) -> Self {
+        let mut parts = locale.split(['-', '_']);
+        let language = parts.next().unwrap_or_default().to_ascii_lowercase();
+        let region = parts.next().unwrap_or_default().to_ascii_uppercase();
+        match language.as_str() {
+            "de" | "it" if region == "CH" => Self::CH,
+            "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" => Self::DE,
+            "fr" => Self::FR,
+            _ => Self::EN,
+        }
+    }
+
+    /// Group an amount with these separators, keeping `decimal_places`
+    pub fn format(&self, amount: Decimal, decimal_places: u32) -> String {
+        let rounded = amount.round_dp(decimal_places).abs();
+        let text = format!("{:.*}", decimal_places as usize, rounded);
+        let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+
+        let mut out = String::new();
+        if amount.is_sign_negative() && !rounded.is_zero() {
+            out.push('-');
+        }
+        for (i, c) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                out.push(self.group_separator);
+            }
+            out.push(c);
+        }
+        if !fraction.is_empty() {
+            out.push(self.decimal_separator);
+            out.push_str(fraction);
+        }
+        out
+    }
+
+    fn is_group_separator(&self, c: char) -> bool {
+        c == self.group_separator || (self.group_separator.is_whitespace() && c.is_whitespace())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneyParseError {
+    /// No digits in the input
+    Empty,
+    /// Digits present but separators don't fit the locale
+    Malformed(String),
+}
+
+impl std::error::Error for MoneyParseError {}
+impl fmt::Display for MoneyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "No amount found"),
+            Self::Malformed(input) => write!(f, "Not a valid amount: {}", input),
+        }
+    }
+}
+
+/// Parse an amount written for `locale`, ignoring currency symbols and
+/// codes around the number. A leading minus or surrounding parentheses
+/// make it negative. Grouping must be in threes so that a value written
+/// for another locale ("1.234,56" read as English) is rejected rather than
+/// misread.
+pub fn parse_money(input: &str, locale: &str) -> Result<Decimal, MoneyParseError> {
+    let format = NumberFormat::for_locale(locale);
+    let malformed = || MoneyParseError::Malformed(input.to_string());
+
+    let first = input.find(|c: char| c.is_ascii_digit()).ok_or(MoneyParseError::Empty)?;
+    let last = input.rfind(|c: char| c.is_ascii_digit()).ok_or(MoneyParseError::Empty)?;
+    let (prefix, core, suffix) = (&input[..first], &input[first..=last], &input[last + 1..]);
+
+    let negative = prefix.contains('-') || (prefix.contains('(') && suffix.contains(')'));
+
+    let (integer, fraction) = match core.split_once(format.decimal_separator) {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (core, None),
+    };
+
+    let groups: Vec<&str> = integer.split(|c| format.is_group_separator(c)).collect();
+    let grouped_ok = groups.iter().all(|g| g.chars().all(|c| c.is_ascii_digit()))
+        && (groups.len() == 1 || (
+            (1..=3).contains(&groups[0].len()) && groups[1..].iter().all(|g| g.len() == 3)
+        ));
+    if !grouped_ok {
+        return Err(malformed());
+    }
+    if let Some(fraction) = fraction {
+        if fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(malformed());
+        }
+    }
+
+    let mut normalized = String::with_capacity(core.len() + 1);
+    if negative {
+        normalized.push('-');
+    }
+    normalized.push_str(&groups.concat());
+    if let Some(fraction) = fraction {
+        normalized.push('.');
+        normalized.push_str(fraction);
+    }
+    Decimal::from_str(&normalized).map_err(|_| malformed())
+}
+
+/// Group an amount for display in `locale`, keeping `decimal_places`
+pub fn format_money(amount: Decimal, locale: &str, decimal_places: u32) -> String {
+    NumberFormat::for_locale(locale).format(amount, decimal_places)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_locale_separators() {
+        assert_eq!(parse_money("1.234,56", "de"), Ok(dec!(1234.56)));
+        assert_eq!(parse_money("1,234.56", "en"), Ok(dec!(1234.56)));
+        assert_eq!(parse_money("1 234,56", "fr-CI"), Ok(dec!(1234.56)));
+
+        // Read with the wrong locale, the grouping doesn't fit
+        assert!(matches!(parse_money("1.234,56", "en"), Err(MoneyParseError::Malformed(_))));
+        assert!(matches!(parse_money("12,34", "en"), Err(MoneyParseError::Malformed(_))));
+    }
+
+    #[test]
+    fn test_symbols_are_stripped() {
+        assert_eq!(parse_money("₦500,000", "en-NG"), Ok(dec!(500000)));
+        assert_eq!(parse_money("KSh 1,250.50", "sw"), Ok(dec!(1250.50)));
+        assert_eq!(parse_money("1.250,00 €", "de"), Ok(dec!(1250.00)));
+        assert_eq!(parse_money("-₦2,000", "en"), Ok(dec!(-2000)));
+        assert_eq!(parse_money("(₦2,000)", "en"), Ok(dec!(-2000)));
+        assert_eq!(parse_money("₦", "en"), Err(MoneyParseError::Empty));
+    }
+
+    #[test]
+    fn test_round_trip_with_sms_formatting() {
+        for (amount, locale) in [(dec!(500000), "en"), (dec!(1234567.89), "de"), (dec!(987654.3), "fr")] {
+            let text = format_money(amount, locale, 2);
+            assert_eq!(parse_money(&format!("₦{}", text), locale), Ok(amount));
+        }
+        assert_eq!(format_money(dec!(500000), "en", 0), "500,000");
+        assert_eq!(format_money(dec!(-1234.5), "de", 2), "-1.234,50");
+    }
+}
@@ -4,6 +4,8 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use super::working_time::WorkingTime;
+
 /// Pay rate with type and frequency
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PayRate {
@@ -23,8 +25,12 @@ pub enum PayType {
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PayFrequency {
+    /// Rate unit only; hourly workers are still paid on a pay cycle
+    Hourly,
     Weekly,
     BiWeekly,
+    /// Every four weeks, 13 periods a year
+    FourWeekly,
     SemiMonthly,
     Monthly,
     Annually,
@@ -54,15 +60,17 @@ impl PayRate {
     pub fn pay_type(&self) -> &PayType { &self.pay_type }
     pub fn frequency(&self) -> &PayFrequency { &self.frequency }
     
-    /// Calculate annual salary
+    /// Calculate annual salary, assuming a standard 40-hour week for hourly rates
     pub fn annual_amount(&self) -> Decimal {
-        match self.frequency {
-            PayFrequency::Annually => self.amount,
-            PayFrequency::Monthly => self.amount * Decimal::from(12),
-            PayFrequency::SemiMonthly => self.amount * Decimal::from(24),
-            PayFrequency::BiWeekly => self.amount * Decimal::from(26),
-            PayFrequency::Weekly => self.amount * Decimal::from(52),
+        self.annual_amount_for(&WorkingTime::default())
+    }
+    
+    /// Calculate annual salary; hourly rates are annualized over `working_time`
+    pub fn annual_amount_for(&self, working_time: &WorkingTime) -> Decimal {
+        if self.pay_type == PayType::Hourly {
+            return working_time.hourly_to_annual(self.amount);
         }
+        self.amount * self.frequency.periods_per_year(working_time)
     }
     
     /// Calculate per-period amount from annual
@@ -71,6 +79,49 @@ impl PayRate {
     }
 }
 
+impl PayFrequency {
+    /// Periods in a year; for `Hourly`, the contracted hours in a year
+    pub fn periods_per_year(&self, working_time: &WorkingTime) -> Decimal {
+        match self {
+            Self::Hourly => working_time.annual_hours(),
+            Self::Weekly => Decimal::from(52),
+            Self::BiWeekly => Decimal::from(26),
+            Self::FourWeekly => Decimal::from(13),
+            Self::SemiMonthly => Decimal::from(24),
+            Self::Monthly => Decimal::from(12),
+            Self::Annually => Decimal::ONE,
+        }
+    }
+}
+
+impl std::str::FromStr for PayFrequency {
+    type Err = UnknownPayFrequency;
+
+    /// Lowercase names as used in query strings and exports, e.g. "biweekly"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "hourly" => Ok(Self::Hourly),
+            "weekly" => Ok(Self::Weekly),
+            "biweekly" => Ok(Self::BiWeekly),
+            "fourweekly" => Ok(Self::FourWeekly),
+            "semimonthly" => Ok(Self::SemiMonthly),
+            "monthly" => Ok(Self::Monthly),
+            "annually" => Ok(Self::Annually),
+            _ => Err(UnknownPayFrequency(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownPayFrequency(pub String);
+
+impl std::error::Error for UnknownPayFrequency {}
+impl fmt::Display for UnknownPayFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown pay frequency: {}", self.0)
+    }
+}
+
 impl fmt::Display for PayRate {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} {:.2}/{:?}", self.currency, self.amount, self.frequency)
@@ -90,4 +141,11 @@ mod tests {
         );
         assert_eq!(rate.annual_amount(), Decimal::new(60000, 0));
     }
+    
+    #[test]
+    fn test_hourly_annualized_over_working_time() {
+        let rate = PayRate::hourly(Decimal::new(20, 0), "USD");
+        assert_eq!(rate.annual_amount(), Decimal::new(41600, 0));
+        assert_eq!(rate.annual_amount_for(&WorkingTime::for_country("FR")), Decimal::new(36400, 0));
+    }
 }
@@ -33,8 +33,22 @@ impl TaxId {
         Ok(Self { id_type: TaxIdType::EIN, value })
     }
     
+    /// Generic tax number; letters are kept (upper-cased) for schemes like the NIN
+    pub fn new_tin(value: impl Into<String>) -> Result<Self, TaxIdError> {
+        let value: String = value.into().chars().filter(|c| c.is_ascii_alphanumeric()).map(|c| c.to_ascii_uppercase()).collect();
+        if value.is_empty() {
+            return Err(TaxIdError::Invalid);
+        }
+        Ok(Self { id_type: TaxIdType::TIN, value })
+    }
+    
     pub fn id_type(&self) -> &TaxIdType { &self.id_type }
     
+    /// Same number regardless of the recorded type
+    pub fn same_number(&self, other: &TaxId) -> bool {
+        self.value == other.value
+    }
+    
     /// Get masked value (last 4 digits only)
     pub fn masked(&self) -> String {
         let len = self.value.len();
@@ -0,0 +1,128 @@
+//! Working Time value object
+//!
+//! Standard and contracted weekly hours used for every hourly↔annual
+//! conversion (proration, overtime base, salary band comparison).
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+/// Standard full-time week, weeks paid per year, and the employee's contracted hours
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkingTime {
+    standard_hours_per_week: Decimal,
+    weeks_per_year: Decimal,
+    contracted_hours_per_week: Decimal,
+}
+
+impl Default for WorkingTime {
+    /// 40-hour week, 52 weeks
+    fn default() -> Self {
+        Self::full_time(dec!(40), dec!(52))
+    }
+}
+
+impl WorkingTime {
+    pub fn full_time(standard_hours_per_week: Decimal, weeks_per_year: Decimal) -> Self {
+        Self {
+            standard_hours_per_week,
+            weeks_per_year,
+            contracted_hours_per_week: standard_hours_per_week,
+        }
+    }
+
+    /// Statutory or customary full-time week for a country
+    pub fn for_country(country_code: &str) -> Self {
+        let hours = match country_code.to_ascii_uppercase().as_str() {
+            "FR" => dec!(35),
+            "GB" | "IE" | "NL" => dec!(37.5),
+            "DK" | "NO" => dec!(37),
+            "BE" => dec!(38),
+            "AU" | "NZ" => dec!(38),
+            "CH" => dec!(42),
+            "MX" | "IN" | "PH" => dec!(48),
+            _ => dec!(40),
+        };
+        Self::full_time(hours, dec!(52))
+    }
+
+    /// Same standard week with fewer contracted hours
+    pub fn with_contracted_hours(mut self, contracted_hours_per_week: Decimal) -> Self {
+        self.contracted_hours_per_week = contracted_hours_per_week;
+        self
+    }
+
+    pub fn standard_hours_per_week(&self) -> Decimal { self.standard_hours_per_week }
+    pub fn weeks_per_year(&self) -> Decimal { self.weeks_per_year }
+    pub fn contracted_hours_per_week(&self) -> Decimal { self.contracted_hours_per_week }
+
+    /// Full-time equivalent fraction, e.g. 0.5 for 20h of a 40h week
+    pub fn fte(&self) -> Decimal {
+        if self.standard_hours_per_week.is_zero() {
+            return Decimal::ZERO;
+        }
+        self.contracted_hours_per_week / self.standard_hours_per_week
+    }
+
+    pub fn is_part_time(&self) -> bool {
+        self.fte() < Decimal::ONE
+    }
+
+    /// Contracted hours over a year
+    pub fn annual_hours(&self) -> Decimal {
+        self.contracted_hours_per_week * self.weeks_per_year
+    }
+
+    /// Annual pay for an hourly rate at the contracted hours
+    pub fn hourly_to_annual(&self, hourly_rate: Decimal) -> Decimal {
+        hourly_rate * self.annual_hours()
+    }
+
+    /// Hourly rate for an annual salary at the contracted hours
+    pub fn annual_to_hourly(&self, annual: Decimal) -> Decimal {
+        let hours = self.annual_hours();
+        if hours.is_zero() {
+            return Decimal::ZERO;
+        }
+        annual / hours
+    }
+
+    /// Full-time annual equivalent of an actual (possibly part-time) salary,
+    /// for comparing part-timers against full-time bands
+    pub fn full_time_equivalent(&self, annual: Decimal) -> Decimal {
+        let fte = self.fte();
+        if fte.is_zero() {
+            return Decimal::ZERO;
+        }
+        annual / fte
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_35_and_40_hour_weeks_annualize_differently() {
+        let france = WorkingTime::for_country("FR");
+        let us = WorkingTime::for_country("US");
+        let hourly = dec!(25);
+
+        assert_eq!(france.hourly_to_annual(hourly), dec!(45_500));
+        assert_eq!(us.hourly_to_annual(hourly), dec!(52_000));
+        assert_eq!(france.annual_to_hourly(dec!(45_500)), hourly);
+        assert_eq!(us.annual_to_hourly(dec!(52_000)), hourly);
+    }
+
+    #[test]
+    fn test_part_time_fte() {
+        let part_time = WorkingTime::for_country("US").with_contracted_hours(dec!(20));
+        assert_eq!(part_time.fte(), dec!(0.5));
+        assert!(part_time.is_part_time());
+        assert_eq!(part_time.hourly_to_annual(dec!(25)), dec!(26_000));
+        assert_eq!(part_time.full_time_equivalent(dec!(26_000)), dec!(52_000));
+
+        let france = WorkingTime::for_country("FR").with_contracted_hours(dec!(28));
+        assert_eq!(france.fte(), dec!(0.8));
+    }
+}
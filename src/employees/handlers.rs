@@ -0,0 +1,669 @@
+//! Employee API Handlers
+//!
+//! The caller's `AuthContext` is expected in request extensions, inserted by
+//! the authentication layer; its tenant scopes duplicate detection.
+
+use std::sync::{Arc, RwLock};
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::{AuthContext, Permission};
+use crate::features::{Feature, FeatureFlags};
+use crate::domain::aggregates::EmployeeError;
+use crate::domain::services::{CreateEmployeeError, CreateEmployeeRequest, DuplicateField, EmployeeService};
+use crate::integrations::import::{import_employees, ColumnMapping};
+use crate::leave::{LeaveService, SeparationReason};
+use crate::validation::ValidJson;
+use super::import::ImportJobs;
+use super::search::{search_employees, EmployeeSearchRequest, EmployeeSummary};
+
+/// API Response wrapper
+#[derive(Debug, Serialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: Option<T>,
+    pub error: Option<String>,
+}
+
+impl<T: Serialize> ApiResponse<T> {
+    pub fn success(data: T) -> Self {
+        Self { success: true, data: Some(data), error: None }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self { success: false, data: None, error: Some(message.into()) }
+    }
+}
+
+/// 409 body naming the record the new hire collides with
+#[derive(Debug, Serialize)]
+pub struct DuplicateEmployeeResponse {
+    pub success: bool,
+    pub error: String,
+    pub existing_employee_id: String,
+    pub matched_on: DuplicateField,
+}
+
+/// Shared employee state
+#[derive(Clone, Default)]
+pub struct EmployeeAppState {
+    pub employees: Arc<RwLock<EmployeeService>>,
+    pub features: FeatureFlags,
+    pub imports: ImportJobs,
+    /// Leave accounts, checked before a separation
+    pub leave: LeaveService,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CreateEmployeeQuery {
+    /// Hire even if the person is already on file (rehire)
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Newly created employee record
+#[derive(Debug, Serialize)]
+pub struct EmployeeCreated {
+    pub id: String,
+    pub employee_number: String,
+    pub previous_record_id: Option<String>,
+}
+
+/// Merge a duplicate record into the one in the path
+#[derive(Debug, Deserialize)]
+pub struct MergeEmployeeRequest {
+    pub duplicate_id: String,
+}
+
+/// End an employee's employment
+#[derive(Debug, Deserialize)]
+pub struct TerminateEmployeeRequest {
+    pub termination_date: chrono::NaiveDate,
+    pub reason: SeparationReason,
+    #[serde(default)]
+    pub details: Option<String>,
+}
+
+/// 410 body for a record that was merged away
+#[derive(Debug, Serialize)]
+pub struct MergedEmployeeResponse {
+    pub success: bool,
+    pub error: String,
+    pub merged_into: String,
+}
+
+fn merged_response(survivor_id: String) -> Response {
+    let body = MergedEmployeeResponse {
+        success: false,
+        error: format!("Employee record was merged into {}", survivor_id),
+        merged_into: survivor_id,
+    };
+    (StatusCode::GONE, Json(body)).into_response()
+}
+
+fn error_response(error: CreateEmployeeError) -> Response {
+    match error {
+        CreateEmployeeError::Invalid(errors) => errors.into_response(),
+        CreateEmployeeError::Duplicate { ref existing_employee_id, matched_on } => {
+            let body = DuplicateEmployeeResponse {
+                success: false,
+                error: error.to_string(),
+                existing_employee_id: existing_employee_id.clone(),
+                matched_on,
+            };
+            (StatusCode::CONFLICT, Json(body)).into_response()
+        }
+        CreateEmployeeError::RehireIneligible { .. } => {
+            (StatusCode::CONFLICT, Json(ApiResponse::<()>::error(error.to_string()))).into_response()
+        }
+    }
+}
+
+/// Hire an employee
+///
+/// POST /api/v1/employees?force=true
+pub async fn create_employee(
+    State(state): State<EmployeeAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Query(query): Query<CreateEmployeeQuery>,
+    ValidJson(request): ValidJson<CreateEmployeeRequest>,
+) -> Response {
+    if !auth.has_permission(Permission::EmployeeCreate) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to create employees"))).into_response();
+    }
+
+    let mut employees = state.employees.write().unwrap();
+    let year = u16::try_from(request.hire_date.year()).unwrap_or_default();
+    let employee_id = employees.next_employee_id(auth.tenant_id, year);
+    match employees.create_employee(auth.tenant_id, employee_id, request, query.force) {
+        Ok(employee) => {
+            let created = EmployeeCreated {
+                id: employee.id().to_string(),
+                employee_number: employee.employee_id().to_string(),
+                previous_record_id: employee.previous_record_id().map(str::to_string),
+            };
+            (StatusCode::CREATED, Json(ApiResponse::success(created))).into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+/// Fetch an employee. A record merged into another answers with a
+/// permanent redirect to the survivor.
+///
+/// GET /api/v1/employees/:id
+pub async fn get_employee(
+    State(state): State<EmployeeAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<String>,
+) -> Response {
+    if !auth.has_permission(Permission::EmployeeView) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to view employees"))).into_response();
+    }
+
+    let employees = state.employees.read().unwrap();
+    let Some(resolved) = employees.resolve(&id).filter(|_| employees.tenant_of(&id) == Some(auth.tenant_id)) else {
+        return (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Employee not found"))).into_response();
+    };
+    if resolved != id {
+        let location = format!("/api/v1/employees/{}", resolved);
+        return (StatusCode::PERMANENT_REDIRECT, [(header::LOCATION, location)]).into_response();
+    }
+    match employees.employee(resolved) {
+        Some(employee) => Json(ApiResponse::success(EmployeeSummary::from(employee))).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Employee not found"))).into_response(),
+    }
+}
+
+/// Fold a duplicate record into this one. Either record having already
+/// been merged away is 410 Gone.
+///
+/// POST /api/v1/employees/:id/merge
+pub async fn merge_employee(
+    State(state): State<EmployeeAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(survivor_id): Path<String>,
+    Json(request): Json<MergeEmployeeRequest>,
+) -> Response {
+    if !auth.has_permission(Permission::EmployeeDelete) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to merge employees"))).into_response();
+    }
+
+    let mut employees = state.employees.write().unwrap();
+    let in_tenant = |id: &str| employees.tenant_of(id) == Some(auth.tenant_id);
+    if !in_tenant(&survivor_id) || !in_tenant(&request.duplicate_id) {
+        return (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Employee not found"))).into_response();
+    }
+    match employees.merge(&survivor_id, &request.duplicate_id, Some(auth.user_id)) {
+        Ok(merge) => Json(ApiResponse::success(merge)).into_response(),
+        Err(EmployeeError::Merged { survivor_id }) => merged_response(survivor_id),
+        Err(EmployeeError::NotFound) => {
+            (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Employee not found"))).into_response()
+        }
+        Err(e) => (StatusCode::CONFLICT, Json(ApiResponse::<()>::error(e.to_string()))).into_response(),
+    }
+}
+
+/// Search employees by custom fields
+///
+/// POST /api/v1/employees/search
+pub async fn search(
+    State(state): State<EmployeeAppState>,
+    Extension(auth): Extension<AuthContext>,
+    ValidJson(request): ValidJson<EmployeeSearchRequest>,
+) -> Response {
+    if let Some(disabled) = state.features.disabled_response(auth.tenant_id, Feature::EmployeeSearch) {
+        return disabled;
+    }
+    if !auth.has_permission(Permission::EmployeeView) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to view employees"))).into_response();
+    }
+
+    let employees = state.employees.read().unwrap();
+    let page = search_employees(&employees, auth.tenant_id, &request);
+    Json(ApiResponse::success(page)).into_response()
+}
+
+/// Start importing a CSV file of hires. The header is checked straight
+/// away; the rows are hired on a worker and the response is the job to poll.
+///
+/// POST /api/v1/employees/import
+pub async fn start_import(
+    State(state): State<EmployeeAppState>,
+    Extension(auth): Extension<AuthContext>,
+    csv: String,
+) -> Response {
+    if !auth.has_permission(Permission::EmployeeCreate) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to create employees"))).into_response();
+    }
+    let import = match import_employees(&csv, &ColumnMapping::default()) {
+        Ok(import) => import,
+        Err(e) => {
+            return (StatusCode::UNPROCESSABLE_ENTITY, Json(ApiResponse::<()>::error(e.to_string()))).into_response();
+        }
+    };
+
+    let job = state.imports.start(auth.tenant_id, import.rows.len());
+    let (imports, employees, job_id) = (state.imports.clone(), state.employees.clone(), job.id);
+    tokio::task::spawn_blocking(move || {
+        // Lock per batch so other requests get in between batches
+        for batch in import.rows.chunks(imports.batch_size()) {
+            let mut employees = employees.write().unwrap_or_else(|e| e.into_inner());
+            imports.import_batch(job_id, &mut employees, batch);
+        }
+        imports.complete(job_id);
+    });
+    (StatusCode::ACCEPTED, Json(ApiResponse::success(job))).into_response()
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportJobQuery {
+    /// Leave out the errors before this position, already seen by an
+    /// earlier poll
+    #[serde(default)]
+    pub errors_from: usize,
+}
+
+/// Progress of an import job, with row errors so far
+///
+/// GET /api/v1/employees/import/:job_id?errors_from=N
+pub async fn get_import_job(
+    State(state): State<EmployeeAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(job_id): Path<uuid::Uuid>,
+    Query(query): Query<ImportJobQuery>,
+) -> Response {
+    if !auth.has_permission(Permission::EmployeeView) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to view employees"))).into_response();
+    }
+    match state.imports.get(job_id).filter(|job| job.tenant_id == auth.tenant_id) {
+        Some(job) => Json(ApiResponse::success(job.errors_from(query.errors_from))).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error(format!("Import job {} not found", job_id))))
+            .into_response(),
+    }
+}
+
+/// End employment. A resignation or job abandonment dated inside the
+/// employee's protected leave is refused with 409.
+///
+/// POST /api/v1/employees/:id/terminate
+pub async fn terminate_employee(
+    State(state): State<EmployeeAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<String>,
+    Json(request): Json<TerminateEmployeeRequest>,
+) -> Response {
+    if !auth.has_permission(Permission::EmployeeUpdate) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to terminate employees"))).into_response();
+    }
+
+    let mut employees = state.employees.write().unwrap();
+    if employees.tenant_of(&id) != Some(auth.tenant_id) {
+        return (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Employee not found"))).into_response();
+    }
+    let account = id.parse().ok().and_then(|employee_id| state.leave.account(employee_id));
+    let details = request.details.unwrap_or_else(|| format!("{:?}", request.reason));
+    match employees.separate(&id, account.as_ref(), request.termination_date, request.reason, details) {
+        Ok(()) => match employees.employee(&id) {
+            Some(employee) => Json(ApiResponse::success(EmployeeSummary::from(employee))).into_response(),
+            None => (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Employee not found"))).into_response(),
+        },
+        Err(EmployeeError::NotFound) => {
+            (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Employee not found"))).into_response()
+        }
+        Err(e) => (StatusCode::CONFLICT, Json(ApiResponse::<()>::error(e.to_string()))).into_response(),
+    }
+}
+
+/// Employee routes
+pub fn employee_routes() -> axum::Router<EmployeeAppState> {
+    use axum::routing::{get, post};
+
+    axum::Router::new()
+        .route("/employees", post(create_employee))
+        .route("/employees/search", post(search))
+        .route("/employees/import", post(start_import))
+        .route("/employees/import/:job_id", get(get_import_job))
+        .route("/employees/:id", get(get_employee))
+        .route("/employees/:id/merge", post(merge_employee))
+        .route("/employees/:id/terminate", post(terminate_employee))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_duplicate_email_is_conflict() {
+        let state = EmployeeAppState::default();
+        let auth = AuthContext {
+            user_id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            employee_id: None,
+            role: Role::HrManager,
+            permissions: Role::HrManager.permissions(),
+            department_id: None,
+        };
+        let app = employee_routes().layer(Extension(auth)).with_state(state.clone());
+        let post = |uri: &str, email: &str| {
+            let body = format!(
+                r#"{{"first_name":"Ada","last_name":"Obi","work_email":"{}","job_title":"Analyst","hire_date":"2024-01-08"}}"#,
+                email
+            );
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(post("/employees", "ada@company.com")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let first_id = created["data"]["id"].as_str().unwrap().to_string();
+
+        let response = app.clone().oneshot(post("/employees", "ADA@Company.com")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let conflict: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(conflict["existing_employee_id"], first_id.as_str());
+        assert_eq!(conflict["matched_on"], "email");
+
+        // Forcing only rehires someone who has left
+        let response = app.clone().oneshot(post("/employees?force=true", "ada@company.com")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let left_on = chrono::NaiveDate::from_ymd_opt(2024, 6, 28).unwrap();
+        state.employees.write().unwrap().employee_mut(&first_id).unwrap().terminate(left_on, "Resigned").unwrap();
+        let response = app.oneshot(post("/employees?force=true", "ada@company.com")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    fn search_state(tenant_id: Uuid) -> EmployeeAppState {
+        let state = EmployeeAppState::default();
+        {
+            let mut employees = state.employees.write().unwrap();
+            let people = [
+                ("Ada", serde_json::json!(["rust", "sql"]), serde_json::json!({"aws": "professional"})),
+                ("Bola", serde_json::json!(["rust", "go"]), serde_json::json!({"cka": true})),
+                ("Chidi", serde_json::json!(["java"]), serde_json::json!({"aws": "associate"})),
+            ];
+            for (name, skills, certifications) in people {
+                let request = CreateEmployeeRequest {
+                    first_name: name.to_string(),
+                    last_name: "Okafor".to_string(),
+                    work_email: format!("{}@company.com", name.to_lowercase()),
+                    job_title: "Engineer".to_string(),
+                    hire_date: chrono::NaiveDate::from_ymd_opt(2024, 1, 8).unwrap(),
+                    tax_id: None,
+                };
+                let employee_id = employees.next_employee_id(tenant_id, 2024);
+                let id = employees.create_employee(tenant_id, employee_id, request, false).unwrap().id().to_string();
+                let employee = employees.employee_mut(&id).unwrap();
+                employee.set_custom_field("skills", skills);
+                employee.set_custom_field("certifications", certifications);
+            }
+        }
+        state
+    }
+
+    #[tokio::test]
+    async fn test_resignation_during_protected_leave_refused() {
+        use crate::leave::{LeaveAccount, ProtectedLeave};
+        use rust_decimal_macros::dec;
+
+        let tenant_id = Uuid::new_v4();
+        let state = search_state(tenant_id);
+        let ids: Vec<String> = state.employees.read().unwrap().tenant_employees(tenant_id).map(|e| e.id().to_string()).collect();
+        let d = |m, day| chrono::NaiveDate::from_ymd_opt(2024, m, day).unwrap();
+        let mut account = LeaveAccount::new(ids[0].parse().unwrap(), 2024, ProtectedLeave::new(24, dec!(1800)));
+        account.protected.take(d(3, 4), d(4, 26), dec!(40)).unwrap();
+        state.leave.open_account(account);
+        let app = search_app_with_state(tenant_id, state.clone());
+
+        let terminate = |id: &str, body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/employees/{}/terminate", id))
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let abandoned = serde_json::json!({"termination_date": "2024-04-01", "reason": "job_abandonment"});
+        let response = app.clone().oneshot(terminate(&ids[0], abandoned.clone())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert!(state.employees.read().unwrap().employee(&ids[0]).unwrap().is_active());
+
+        // Without protected leave on file the same separation goes through
+        let response = app.clone().oneshot(terminate(&ids[1], abandoned)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let dismissed = serde_json::json!({"termination_date": "2024-04-01", "reason": "dismissal", "details": "Gross misconduct"});
+        let response = app.clone().oneshot(terminate(&ids[0], dismissed)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let status = *state.employees.read().unwrap().employee(&ids[0]).unwrap().status();
+        assert_eq!(status, crate::domain::aggregates::EmploymentStatus::Terminated);
+
+        let stranger = search_app_with_state(Uuid::new_v4(), state);
+        let body = serde_json::json!({"termination_date": "2024-04-01", "reason": "redundancy"});
+        assert_eq!(stranger.oneshot(terminate(&ids[2], body)).await.unwrap().status(), StatusCode::NOT_FOUND);
+    }
+
+    async fn search_names(app: axum::Router, body: serde_json::Value) -> (usize, Vec<String>) {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/employees/search")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let names = page["data"]["employees"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["name"].as_str().unwrap().to_string())
+            .collect();
+        (page["data"]["total"].as_u64().unwrap() as usize, names)
+    }
+
+    fn search_app(tenant_id: Uuid) -> axum::Router {
+        search_app_with_state(tenant_id, search_state(tenant_id))
+    }
+
+    fn search_app_with_state(tenant_id: Uuid, state: EmployeeAppState) -> axum::Router {
+        let auth = AuthContext {
+            user_id: Uuid::new_v4(),
+            tenant_id,
+            employee_id: None,
+            role: Role::HrManager,
+            permissions: Role::HrManager.permissions(),
+            department_id: None,
+        };
+        employee_routes().layer(Extension(auth)).with_state(state)
+    }
+
+    #[tokio::test]
+    async fn test_merged_id_redirects_and_is_gone() {
+        let tenant_id = Uuid::new_v4();
+        let state = search_state(tenant_id);
+        let (survivor, duplicate) = {
+            let employees = state.employees.read().unwrap();
+            let mut ids: Vec<_> = employees.tenant_employees(tenant_id).map(|e| e.id().to_string()).collect();
+            ids.sort();
+            (ids[0].clone(), ids[1].clone())
+        };
+        let app = search_app_with_state(tenant_id, state);
+        let merge = |survivor: &str, duplicate: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/employees/{}/merge", survivor))
+                .header("content-type", "application/json")
+                .body(Body::from(format!(r#"{{"duplicate_id":"{}"}}"#, duplicate)))
+                .unwrap()
+        };
+        let get = |id: &str| Request::builder().uri(format!("/employees/{}", id)).body(Body::empty()).unwrap();
+
+        assert_eq!(app.clone().oneshot(merge(&survivor, &duplicate)).await.unwrap().status(), StatusCode::OK);
+
+        let response = app.clone().oneshot(get(&duplicate)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[header::LOCATION], format!("/api/v1/employees/{}", survivor).as_str());
+        assert_eq!(app.clone().oneshot(get(&survivor)).await.unwrap().status(), StatusCode::OK);
+
+        // The merged id can't take part in another merge
+        let response = app.clone().oneshot(merge(&duplicate, &survivor)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::GONE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let gone: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(gone["merged_into"], survivor.as_str());
+
+        // Search no longer lists the duplicate
+        let (total, _) = search_names(app, serde_json::json!({})).await;
+        assert_eq!(total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_by_skill() {
+        let tenant_id = Uuid::new_v4();
+        let app = search_app(tenant_id);
+
+        let filter = serde_json::json!({"custom_fields": [{"field": "skills", "contains": "rust"}]});
+        let (total, names) = search_names(app.clone(), filter).await;
+        assert_eq!(total, 2);
+        assert_eq!(names, ["Ada Okafor", "Bola Okafor"]);
+
+        // Pagination keeps the total
+        let filter = serde_json::json!({"custom_fields": [{"field": "skills", "contains": "rust"}], "limit": 1, "offset": 1});
+        assert_eq!(search_names(app.clone(), filter).await, (2, vec!["Bola Okafor".to_string()]));
+
+        // Unknown field is an empty result, not an error
+        let filter = serde_json::json!({"custom_fields": [{"field": "languages", "contains": "yoruba"}]});
+        assert_eq!(search_names(app, filter).await, (0, vec![]));
+    }
+
+    #[tokio::test]
+    async fn test_search_by_two_predicates() {
+        let tenant_id = Uuid::new_v4();
+        let app = search_app(tenant_id);
+
+        let filter = serde_json::json!({"custom_fields": [
+            {"field": "skills", "contains": ["rust"]},
+            {"field": "certifications", "contains": {"aws": "professional"}},
+        ]});
+        let (total, names) = search_names(app, filter).await;
+        assert_eq!(total, 1);
+        assert_eq!(names, ["Ada Okafor"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_hidden_when_feature_disabled() {
+        let tenant_id = Uuid::new_v4();
+        let state = search_state(tenant_id);
+        state.features.set(tenant_id, Feature::EmployeeSearch, false);
+        let app = search_app_with_state(tenant_id, state.clone());
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/employees/search")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"custom_fields": []}"#))
+            .unwrap();
+        assert_eq!(app.clone().oneshot(request).await.unwrap().status(), StatusCode::NOT_FOUND);
+
+        // Switched back on at runtime, no restart
+        state.features.set(tenant_id, Feature::EmployeeSearch, true);
+        assert_eq!(search_names(app, serde_json::json!({"custom_fields": []})).await.0, 3);
+    }
+
+    #[tokio::test]
+    async fn test_import_job_reports_progress_and_summary() {
+        use crate::integrations::import::import_employees;
+        use super::super::import::{ImportJob, ImportJobStatus, ImportJobs};
+
+        let tenant_id = Uuid::new_v4();
+        let auth = AuthContext {
+            user_id: Uuid::new_v4(),
+            tenant_id,
+            employee_id: None,
+            role: Role::HrManager,
+            permissions: Role::HrManager.permissions(),
+            department_id: None,
+        };
+        let state = EmployeeAppState { imports: ImportJobs::new().with_batch_size(2), ..EmployeeAppState::default() };
+        let app = employee_routes().layer(Extension(auth)).with_state(state.clone());
+        let poll = |job_id: Uuid, errors_from: usize| {
+            let app = app.clone();
+            async move {
+                let uri = format!("/employees/import/{}?errors_from={}", job_id, errors_from);
+                let response = app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                serde_json::from_value::<ImportJob>(json["data"].clone()).unwrap()
+            }
+        };
+
+        let csv = "first_name,last_name,work_email,job_title,hire_date\n\
+            Ada,Obi,ada@company.com,Analyst,2024-01-08\n\
+            Bola,Ade,bola@company.com,Analyst,08/01/2024\n\
+            Chidi,Eze,chidi@company.com,Engineer,2024-02-01\n\
+            Dayo,Ola,ADA@company.com,Engineer,2024-02-01\n\
+            Efe,Uche,efe@company.com,Designer,2024-03-04\n";
+
+        // Each batch shows up in the job before the next one runs
+        let rows = import_employees(csv, &ColumnMapping::default()).unwrap().rows;
+        let job = state.imports.start(tenant_id, rows.len());
+        state.imports.import_batch(job.id, &mut state.employees.write().unwrap(), &rows[..2]);
+        let progress = poll(job.id, 0).await;
+        assert_eq!((progress.status, progress.processed, progress.created), (ImportJobStatus::Processing, 2, 1));
+        assert_eq!(progress.errors.iter().map(|e| e.line).collect::<Vec<_>>(), [3]);
+        state.imports.import_batch(job.id, &mut state.employees.write().unwrap(), &rows[2..4]);
+        let progress = poll(job.id, 1).await;
+        assert_eq!(progress.processed, 4);
+        // Only the error not seen by the earlier poll
+        assert_eq!(progress.errors.iter().map(|e| e.line).collect::<Vec<_>>(), [5]);
+
+        // Through the API, into a fresh tenant's worth of people
+        let csv = csv.replace("@company.com", "@example.com").replace("ADA@", "ada@");
+        let request = Request::builder()
+            .method("POST")
+            .uri("/employees/import")
+            .header("content-type", "text/csv")
+            .body(Body::from(csv))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let started: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let job_id: Uuid = started["data"]["id"].as_str().unwrap().parse().unwrap();
+        assert_eq!(started["data"]["total"], 5);
+
+        let summary = loop {
+            let job = poll(job_id, 0).await;
+            if job.status == ImportJobStatus::Completed {
+                break job;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        };
+        assert_eq!((summary.processed, summary.created, summary.errors.len()), (5, 3, 2));
+        assert!(summary.errors[1].error.contains("already"), "{:?}", summary.errors);
+
+        let bad_header = Request::builder().method("POST").uri("/employees/import").body(Body::from("name,email\n")).unwrap();
+        assert_eq!(app.oneshot(bad_header).await.unwrap().status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,150 @@
+//! Employee Import Jobs
+//!
+//! A large CSV import takes longer than an HTTP request should, so the API
+//! checks the header, queues the rows, and answers with a job to poll, like
+//! payroll processing. A worker hires the rows in batches and records
+//! progress after each; row errors are added to the job as they happen, so
+//! a poll can ask for only the errors it hasn't seen yet.
+
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::services::EmployeeService;
+use crate::integrations::import::ImportRow;
+
+/// Rows hired between progress updates
+pub const IMPORT_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportJobStatus {
+    Processing,
+    Completed,
+}
+
+/// A row that was not hired
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRowError {
+    /// 1-based line in the file, the header being line 1
+    pub line: usize,
+    pub error: String,
+}
+
+/// Background import of one CSV file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportJob {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub status: ImportJobStatus,
+    /// Rows handled so far, hired or not
+    pub processed: usize,
+    pub total: usize,
+    pub created: usize,
+    /// Errors in the order they occurred
+    pub errors: Vec<ImportRowError>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ImportJob {
+    /// The job with only the errors from position `from` on
+    pub fn errors_from(mut self, from: usize) -> Self {
+        self.errors = self.errors.split_off(from.min(self.errors.len()));
+        self
+    }
+}
+
+/// Import jobs by id
+#[derive(Debug, Clone)]
+pub struct ImportJobs {
+    // In real implementation, backed by the employee_import_jobs table
+    jobs: Arc<DashMap<Uuid, ImportJob>>,
+    batch_size: usize,
+}
+
+impl Default for ImportJobs {
+    fn default() -> Self {
+        Self { jobs: Arc::new(DashMap::new()), batch_size: IMPORT_BATCH_SIZE }
+    }
+}
+
+impl ImportJobs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rows hired between progress updates
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Record a job for `total` rows
+    pub fn start(&self, tenant_id: Uuid, total: usize) -> ImportJob {
+        let job = ImportJob {
+            id: Uuid::new_v4(),
+            tenant_id,
+            status: ImportJobStatus::Processing,
+            processed: 0,
+            total,
+            created: 0,
+            errors: Vec::new(),
+            created_at: Utc::now(),
+            completed_at: None,
+        };
+        self.jobs.insert(job.id, job.clone());
+        job
+    }
+
+    pub fn get(&self, job_id: Uuid) -> Option<ImportJob> {
+        self.jobs.get(&job_id).map(|j| j.clone())
+    }
+
+    /// Hire one batch of rows for the job's tenant, recording each row's
+    /// outcome on the job as it goes
+    pub fn import_batch(&self, job_id: Uuid, employees: &mut EmployeeService, rows: &[ImportRow]) {
+        let Some(tenant_id) = self.jobs.get(&job_id).map(|j| j.tenant_id) else {
+            return;
+        };
+        for row in rows {
+            let outcome = match &row.result {
+                Ok(imported) => {
+                    let year = u16::try_from(chrono::Datelike::year(&imported.request.hire_date)).unwrap_or_default();
+                    let employee_id = employees.next_employee_id(tenant_id, year);
+                    match employees.create_employee(tenant_id, employee_id, imported.request.clone(), false) {
+                        Ok(employee) => {
+                            let id = employee.id().to_string();
+                            if let (Some(department_id), Some(employee)) = (&imported.department_id, employees.employee_mut(&id)) {
+                                employee.transfer(Some(department_id.clone()), None);
+                            }
+                            Ok(())
+                        }
+                        Err(e) => Err(e.to_string()),
+                    }
+                }
+                Err(e) => Err(e.to_string()),
+            };
+            if let Some(mut job) = self.jobs.get_mut(&job_id) {
+                job.processed += 1;
+                match outcome {
+                    Ok(()) => job.created += 1,
+                    Err(error) => job.errors.push(ImportRowError { line: row.line, error }),
+                }
+            }
+        }
+    }
+
+    pub fn complete(&self, job_id: Uuid) {
+        if let Some(mut job) = self.jobs.get_mut(&job_id) {
+            job.status = ImportJobStatus::Completed;
+            job.completed_at = Some(Utc::now());
+        }
+    }
+}
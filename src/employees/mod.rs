@@ -0,0 +1,14 @@
+//! Employee Records API
+//!
+//! HTTP surface over `EmployeeService`: hiring with duplicate detection
+//! and rehire linking, search by custom fields, merging duplicate
+//! records, after which the merged id redirects to the survivor, and CSV
+//! imports run as jobs that report progress while they go.
+
+pub mod handlers;
+pub mod import;
+pub mod search;
+
+pub use handlers::{employee_routes, EmployeeAppState};
+pub use import::{ImportJob, ImportJobStatus, ImportJobs, ImportRowError};
+pub use search::{CustomFieldFilter, EmployeePage, EmployeeSearchRequest};
@@ -0,0 +1,162 @@
+//! Custom-Field Search
+//!
+//! Finds employees by tenant-defined custom fields (skills, certifications)
+//! using JSONB containment: a filter `{"field": "skills", "contains": "rust"}`
+//! matches when `custom_fields -> 'skills'` contains `"rust"`. Each filter
+//! is bound as one element of a `jsonb[]` parameter, so field names and
+//! values never reach the SQL text. A field no employee has simply matches
+//! nothing.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::aggregates::Employee;
+use crate::domain::services::EmployeeService;
+use crate::validation::{Validate, ValidationErrors, Validator};
+
+/// Search used by the Postgres implementation. `$2` is one containment
+/// document per filter, e.g. `ARRAY['{"skills": "rust"}']::jsonb[]`.
+pub const SEARCH_CUSTOM_FIELDS_SQL: &str = "\
+SELECT *, COUNT(*) OVER () AS total FROM employees \
+WHERE tenant_id = $1 AND custom_fields @> ALL($2::jsonb[]) \
+ORDER BY employee_number \
+LIMIT $3 OFFSET $4";
+
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+pub const MAX_PAGE_SIZE: usize = 200;
+
+/// One containment predicate on a custom field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomFieldFilter {
+    pub field: String,
+    /// Scalar, array (all elements present), or object (subset) to look for
+    pub contains: serde_json::Value,
+}
+
+impl CustomFieldFilter {
+    /// The `{field: contains}` document bound into `$2`
+    pub fn containment_document(&self) -> serde_json::Value {
+        serde_json::json!({ self.field.clone(): self.contains.clone() })
+    }
+}
+
+/// Employee search request; all filters must match
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmployeeSearchRequest {
+    #[serde(default)]
+    pub custom_fields: Vec<CustomFieldFilter>,
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: usize,
+}
+
+impl Validate for EmployeeSearchRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut v = Validator::new();
+        for filter in &self.custom_fields {
+            v.required("custom_fields.field", &filter.field)
+                .max_length("custom_fields.field", &filter.field, 100);
+        }
+        v.check(
+            self.limit.is_none_or(|limit| (1..=MAX_PAGE_SIZE).contains(&limit)),
+            "limit",
+            "out_of_range",
+            format!("limit must be between 1 and {}", MAX_PAGE_SIZE),
+        );
+        v.finish()
+    }
+}
+
+/// Matching employee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmployeeSummary {
+    pub id: String,
+    pub employee_number: String,
+    pub name: String,
+    pub job_title: String,
+    pub department_id: Option<String>,
+    pub custom_fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl From<&Employee> for EmployeeSummary {
+    fn from(employee: &Employee) -> Self {
+        Self {
+            id: employee.id().to_string(),
+            employee_number: employee.employee_id().to_string(),
+            name: employee.full_name(),
+            job_title: employee.employment().job_title.clone(),
+            department_id: employee.employment().department_id.clone(),
+            custom_fields: employee.custom_fields().iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        }
+    }
+}
+
+/// One page of search results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmployeePage {
+    pub employees: Vec<EmployeeSummary>,
+    /// Matches across all pages
+    pub total: usize,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+/// Postgres `@>` on JSON values: objects match on a subset of keys, arrays
+/// when every needle element is contained by some haystack element (a bare
+/// scalar is found in an array), scalars on equality
+pub fn json_contains(haystack: &serde_json::Value, needle: &serde_json::Value) -> bool {
+    use serde_json::Value;
+
+    match (haystack, needle) {
+        (Value::Object(h), Value::Object(n)) => {
+            n.iter().all(|(key, value)| h.get(key).is_some_and(|h| json_contains(h, value)))
+        }
+        (Value::Array(h), Value::Array(n)) => n.iter().all(|value| h.iter().any(|h| json_contains(h, value))),
+        (Value::Array(h), scalar) if !scalar.is_object() => h.iter().any(|h| h == scalar),
+        (h, n) => h == n,
+    }
+}
+
+/// Employees in the tenant matching every filter, ordered by employee number
+pub fn search_employees(service: &EmployeeService, tenant_id: Uuid, request: &EmployeeSearchRequest) -> EmployeePage {
+    let limit = request.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let mut matches: Vec<&Employee> = service
+        .tenant_employees(tenant_id)
+        .filter(|employee| {
+            request.custom_fields.iter().all(|filter| {
+                employee
+                    .custom_fields()
+                    .get(&filter.field)
+                    .is_some_and(|value| json_contains(value, &filter.contains))
+            })
+        })
+        .collect();
+    matches.sort_by_key(|e| e.employee_id().to_string());
+
+    EmployeePage {
+        total: matches.len(),
+        employees: matches.into_iter().skip(request.offset).take(limit).map(EmployeeSummary::from).collect(),
+        limit,
+        offset: request.offset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_contains() {
+        let skills = json!(["rust", "go", "sql"]);
+        assert!(json_contains(&skills, &json!("rust")));
+        assert!(json_contains(&skills, &json!(["sql", "rust"])));
+        assert!(!json_contains(&skills, &json!(["rust", "java"])));
+
+        let certs = json!({"aws": {"level": "professional", "year": 2023}, "cka": true});
+        assert!(json_contains(&certs, &json!({"aws": {"level": "professional"}})));
+        assert!(!json_contains(&certs, &json!({"gcp": true})));
+        assert!(json_contains(&json!("B2"), &json!("B2")));
+    }
+}
@@ -0,0 +1,125 @@
+//! Feature Flags
+//!
+//! Per-tenant switches for optional features. Every feature has a platform
+//! default; a tenant override set at runtime wins until it is cleared.
+//! Handlers check their feature before doing anything else. A feature that
+//! hides a whole endpoint answers 404, as if the route did not exist; one
+//! that gates an action the tenant can otherwise see answers 403.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Optional features a tenant can have switched off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    /// Net pay sent to mobile money wallets
+    MobileMoney,
+    /// Employees editing their own profiles
+    SelfService,
+    /// Custom-field employee search
+    EmployeeSearch,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 3] = [Feature::MobileMoney, Feature::SelfService, Feature::EmployeeSearch];
+
+    /// Status a request to a disabled feature gets
+    pub fn disabled_status(&self) -> StatusCode {
+        match self {
+            Self::EmployeeSearch => StatusCode::NOT_FOUND,
+            Self::MobileMoney | Self::SelfService => StatusCode::FORBIDDEN,
+        }
+    }
+}
+
+/// Feature switches per tenant over platform defaults
+#[derive(Debug, Clone, Default)]
+pub struct FeatureFlags {
+    /// Features off unless a tenant turns them on; everything else defaults on
+    defaults: HashMap<Feature, bool>,
+    // In real implementation, backed by the tenant_features table
+    overrides: Arc<DashMap<Uuid, HashMap<Feature, bool>>>,
+}
+
+impl FeatureFlags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Platform-wide default for `feature`
+    pub fn with_default(mut self, feature: Feature, enabled: bool) -> Self {
+        self.defaults.insert(feature, enabled);
+        self
+    }
+
+    pub fn is_enabled(&self, tenant_id: Uuid, feature: Feature) -> bool {
+        self.overrides
+            .get(&tenant_id)
+            .and_then(|flags| flags.get(&feature).copied())
+            .unwrap_or_else(|| self.defaults.get(&feature).copied().unwrap_or(true))
+    }
+
+    /// Override `feature` for one tenant; takes effect on the next request
+    pub fn set(&self, tenant_id: Uuid, feature: Feature, enabled: bool) {
+        self.overrides.entry(tenant_id).or_default().insert(feature, enabled);
+    }
+
+    /// Drop a tenant override, returning the feature to the platform default
+    pub fn clear(&self, tenant_id: Uuid, feature: Feature) {
+        if let Some(mut flags) = self.overrides.get_mut(&tenant_id) {
+            flags.remove(&feature);
+        }
+    }
+
+    /// Every feature and whether it is on for the tenant
+    pub fn for_tenant(&self, tenant_id: Uuid) -> HashMap<Feature, bool> {
+        Feature::ALL.iter().map(|&feature| (feature, self.is_enabled(tenant_id, feature))).collect()
+    }
+
+    /// Response to send instead of handling the request when `feature` is off
+    pub fn disabled_response(&self, tenant_id: Uuid, feature: Feature) -> Option<Response> {
+        if self.is_enabled(tenant_id, feature) {
+            return None;
+        }
+        let status = feature.disabled_status();
+        let message = match status {
+            StatusCode::NOT_FOUND => "Not found".to_string(),
+            _ => format!("Feature {:?} is not enabled for this tenant", feature),
+        };
+        let body = serde_json::json!({ "success": false, "data": null, "error": message });
+        Some((status, Json(body)).into_response())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenant_override_over_default() {
+        let flags = FeatureFlags::new().with_default(Feature::MobileMoney, false);
+        let (tenant, other) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(!flags.is_enabled(tenant, Feature::MobileMoney));
+        assert!(flags.is_enabled(tenant, Feature::EmployeeSearch));
+
+        flags.set(tenant, Feature::MobileMoney, true);
+        flags.set(tenant, Feature::EmployeeSearch, false);
+        assert!(flags.is_enabled(tenant, Feature::MobileMoney));
+        assert!(!flags.is_enabled(tenant, Feature::EmployeeSearch));
+        assert!(!flags.is_enabled(other, Feature::MobileMoney));
+
+        flags.clear(tenant, Feature::MobileMoney);
+        assert!(!flags.is_enabled(tenant, Feature::MobileMoney));
+        assert_eq!(flags.for_tenant(tenant).get(&Feature::EmployeeSearch), Some(&false));
+    }
+}
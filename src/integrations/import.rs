@@ -0,0 +1,275 @@
+//! Employee Import
+//!
+//! Bulk hires from client CSV files. Client layouts vary, so a
+//! `ColumnMapping` says which source header feeds which employee field; the
+//! default mapping understands the canonical headers of the roster export.
+//! The header is checked against the mapping before any row is read, and a
+//! file that leaves a required field unmapped is rejected as a whole.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::domain::services::CreateEmployeeRequest;
+use crate::validation::{Validate, ValidationErrors};
+
+/// Employee field a CSV column can feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportField {
+    FirstName,
+    LastName,
+    WorkEmail,
+    JobTitle,
+    HireDate,
+    TaxId,
+    DepartmentId,
+}
+
+impl ImportField {
+    pub const REQUIRED: [ImportField; 5] =
+        [Self::FirstName, Self::LastName, Self::WorkEmail, Self::JobTitle, Self::HireDate];
+
+    /// Header used by the roster export
+    pub fn canonical_header(&self) -> &'static str {
+        match self {
+            Self::FirstName => "first_name",
+            Self::LastName => "last_name",
+            Self::WorkEmail => "work_email",
+            Self::JobTitle => "job_title",
+            Self::HireDate => "hire_date",
+            Self::TaxId => "tax_id",
+            Self::DepartmentId => "department_id",
+        }
+    }
+
+    pub fn is_required(&self) -> bool {
+        Self::REQUIRED.contains(self)
+    }
+}
+
+/// Import errors
+#[derive(Debug, Clone, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EmployeeImportError {
+    #[error("File has no header row")]
+    MissingHeader,
+
+    #[error("Required fields not mapped to any column: {0:?}")]
+    UnmappedRequiredFields(Vec<ImportField>),
+
+    #[error("Malformed row: {0}")]
+    MalformedRow(String),
+
+    #[error("Malformed date for {field:?}: {value}")]
+    MalformedDate { field: ImportField, value: String },
+
+    #[error("{0}")]
+    Invalid(ValidationErrors),
+}
+
+/// Source header → employee field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnMapping {
+    /// Keyed by header, trimmed and lowercased
+    columns: HashMap<String, ImportField>,
+    /// chrono format for date columns
+    pub date_format: String,
+}
+
+impl Default for ColumnMapping {
+    /// Canonical export headers, ISO dates
+    fn default() -> Self {
+        let fields = [
+            ImportField::FirstName,
+            ImportField::LastName,
+            ImportField::WorkEmail,
+            ImportField::JobTitle,
+            ImportField::HireDate,
+            ImportField::TaxId,
+            ImportField::DepartmentId,
+        ];
+        let mut mapping = Self::empty();
+        for field in fields {
+            mapping = mapping.map(field.canonical_header(), field);
+        }
+        mapping
+    }
+}
+
+fn normalize_header(header: &str) -> String {
+    header.trim().trim_start_matches('\u{feff}').to_lowercase()
+}
+
+impl ColumnMapping {
+    /// Mapping with no columns, for fully custom layouts
+    pub fn empty() -> Self {
+        Self { columns: HashMap::new(), date_format: "%Y-%m-%d".to_string() }
+    }
+
+    /// Feed `field` from the column headed `header` (case-insensitive)
+    pub fn map(mut self, header: &str, field: ImportField) -> Self {
+        self.columns.insert(normalize_header(header), field);
+        self
+    }
+
+    pub fn with_date_format(mut self, format: &str) -> Self {
+        self.date_format = format.to_string();
+        self
+    }
+
+    /// Resolve the file's header to column positions, failing with every
+    /// required field no column maps to. Unmapped columns are ignored.
+    pub fn validate(&self, headers: &[String]) -> Result<HashMap<ImportField, usize>, EmployeeImportError> {
+        let mut positions = HashMap::new();
+        for (index, header) in headers.iter().enumerate() {
+            if let Some(field) = self.columns.get(&normalize_header(header)) {
+                positions.entry(*field).or_insert(index);
+            }
+        }
+        let unmapped: Vec<ImportField> =
+            ImportField::REQUIRED.into_iter().filter(|f| !positions.contains_key(f)).collect();
+        if !unmapped.is_empty() {
+            return Err(EmployeeImportError::UnmappedRequiredFields(unmapped));
+        }
+        Ok(positions)
+    }
+}
+
+/// A row ready to hire
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedEmployee {
+    pub request: CreateEmployeeRequest,
+    pub department_id: Option<String>,
+}
+
+/// Outcome for one CSV data row (line numbers are 1-based, header is line 1)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRow {
+    pub line: usize,
+    pub result: Result<ImportedEmployee, EmployeeImportError>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmployeeImport {
+    pub rows: Vec<ImportRow>,
+}
+
+impl EmployeeImport {
+    pub fn employees(&self) -> impl Iterator<Item = &ImportedEmployee> {
+        self.rows.iter().filter_map(|r| r.result.as_ref().ok())
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.rows.iter().filter(|r| r.result.is_err()).count()
+    }
+}
+
+/// Split one CSV line, honouring double-quoted fields and `""` escapes
+fn split_csv_line(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    fields.push(field);
+    Some(fields)
+}
+
+/// Parse a client file through `mapping` into hire requests, each row
+/// validated on its own. Nothing is read if the header fails validation.
+pub fn import_employees(csv: &str, mapping: &ColumnMapping) -> Result<EmployeeImport, EmployeeImportError> {
+    let mut lines = csv.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+    let (_, header) = lines.next().ok_or(EmployeeImportError::MissingHeader)?;
+    let headers = split_csv_line(header).ok_or_else(|| EmployeeImportError::MalformedRow(header.to_string()))?;
+    let positions = mapping.validate(&headers)?;
+
+    let mut import = EmployeeImport::default();
+    for (index, raw) in lines {
+        let result = split_csv_line(raw)
+            .ok_or_else(|| EmployeeImportError::MalformedRow(raw.to_string()))
+            .and_then(|fields| {
+                let value = |field: ImportField| {
+                    positions.get(&field).and_then(|&i| fields.get(i)).map(|v| v.trim().to_string()).unwrap_or_default()
+                };
+                let optional = |field: ImportField| Some(value(field)).filter(|v| !v.is_empty());
+
+                let hire_date = value(ImportField::HireDate);
+                let hire_date = NaiveDate::parse_from_str(&hire_date, &mapping.date_format)
+                    .map_err(|_| EmployeeImportError::MalformedDate { field: ImportField::HireDate, value: hire_date })?;
+                let request = CreateEmployeeRequest {
+                    first_name: value(ImportField::FirstName),
+                    last_name: value(ImportField::LastName),
+                    work_email: value(ImportField::WorkEmail),
+                    job_title: value(ImportField::JobTitle),
+                    hire_date,
+                    tax_id: optional(ImportField::TaxId),
+                };
+                request.validate().map_err(EmployeeImportError::Invalid)?;
+                Ok(ImportedEmployee { request, department_id: optional(ImportField::DepartmentId) })
+            });
+        import.rows.push(ImportRow { line: index + 1, result });
+    }
+    Ok(import)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_with_custom_mapping() {
+        let csv = "Given Name,Surname,E-mail Address,Position,Start Date,Cost Centre,Notes\n\
+                   Ada,Obi,ada@example.com,Engineer,15/01/2024,eng,\"likes \"\"tea\"\", not coffee\"\n\
+                   Tunde,Bello,not-an-email,\"Sales, West\",01/03/2024,,\n";
+        let mapping = ColumnMapping::empty()
+            .map("given name", ImportField::FirstName)
+            .map("SURNAME", ImportField::LastName)
+            .map("E-mail Address", ImportField::WorkEmail)
+            .map("Position", ImportField::JobTitle)
+            .map("Start Date", ImportField::HireDate)
+            .map("Cost Centre", ImportField::DepartmentId)
+            .with_date_format("%d/%m/%Y");
+
+        let import = import_employees(csv, &mapping).unwrap();
+
+        assert_eq!(import.rows.len(), 2);
+        let ada = import.employees().next().unwrap();
+        assert_eq!(ada.request.first_name, "Ada");
+        assert_eq!(ada.request.hire_date, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+        assert_eq!(ada.department_id.as_deref(), Some("eng"));
+        assert_eq!(import.error_count(), 1);
+        assert!(matches!(import.rows[1].result, Err(EmployeeImportError::Invalid(_))));
+
+        // The canonical layout needs no mapping at all
+        let canonical = "first_name,last_name,work_email,job_title,hire_date\nAda,Obi,ada@example.com,Engineer,2024-01-15\n";
+        assert_eq!(import_employees(canonical, &ColumnMapping::default()).unwrap().employees().count(), 1);
+    }
+
+    #[test]
+    fn test_unmapped_required_field_rejected() {
+        let csv = "Given Name,Surname,Email,Position\nAda,Obi,ada@example.com,Engineer\n";
+        let mapping = ColumnMapping::default()
+            .map("Given Name", ImportField::FirstName)
+            .map("Surname", ImportField::LastName)
+            .map("Email", ImportField::WorkEmail)
+            .map("Position", ImportField::JobTitle);
+
+        let err = import_employees(csv, &mapping).unwrap_err();
+        assert_eq!(err, EmployeeImportError::UnmappedRequiredFields(vec![ImportField::HireDate]));
+    }
+}
@@ -0,0 +1,323 @@
+//! Integrations Module
+//!
+//! Roster exports for payroll bureaus and benefits providers. Field names
+//! and their order are part of the contract with those integrations, so new
+//! fields are only ever appended. Dates are ISO-8601 and money is always
+//! paired with its currency code.
+//!
+//! Imports go the other way: client CSV files become hire requests through
+//! a configurable column mapping. Outbound calls to providers are
+//! throttled per integration by `OutboundLimiter`.
+
+pub mod import;
+pub mod throttle;
+
+pub use import::{import_employees, ColumnMapping, EmployeeImport, EmployeeImportError, ImportField};
+pub use throttle::{
+    provider_key, OutboundLimit, OutboundLimiter, OutboundPermit, ThrottledMobileMoneyGateway, ThrottledSmsSender,
+    ThrottledWebhookSender,
+};
+
+use crate::domain::aggregates::{Employee, EmploymentStatus, EmploymentType};
+use crate::domain::value_objects::PayFrequency;
+
+/// Export file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    /// HR-Open / HR-XML style roster document
+    HrXml,
+}
+
+/// Which employees to export
+#[derive(Debug, Clone, Default)]
+pub struct ExportFilter {
+    pub department_id: Option<String>,
+    /// Exact status; `None` exports the active roster (everyone not terminated or retired)
+    pub status: Option<EmploymentStatus>,
+}
+
+impl ExportFilter {
+    pub fn matches(&self, employee: &Employee) -> bool {
+        let status_ok = match &self.status {
+            Some(status) => employee.status() == status,
+            None => !matches!(employee.status(), EmploymentStatus::Terminated | EmploymentStatus::Retired),
+        };
+        let department_ok = self.department_id.as_deref()
+            .is_none_or(|d| employee.employment().department_id.as_deref() == Some(d));
+        status_ok && department_ok
+    }
+}
+
+pub const EMPLOYEE_CSV_HEADER: &str = "employee_number,first_name,last_name,work_email,job_title,department_id,\
+employment_type,status,hire_date,termination_date,pay_amount,pay_currency,pay_frequency\n";
+
+/// Export matching employees, ordered by employee number
+pub fn export_employees(employees: &[Employee], format: ExportFormat, filter: &ExportFilter) -> String {
+    let mut roster: Vec<ExportRecord> = employees.iter()
+        .filter(|e| filter.matches(e))
+        .map(ExportRecord::from)
+        .collect();
+    roster.sort_by(|a, b| a.employee_number.cmp(&b.employee_number));
+
+    match format {
+        ExportFormat::Csv => to_csv(&roster),
+        ExportFormat::HrXml => to_hr_xml(&roster),
+    }
+}
+
+/// Flattened, stringly-typed view of an employee with the stable field mappings
+struct ExportRecord {
+    employee_number: String,
+    first_name: String,
+    last_name: String,
+    work_email: String,
+    job_title: String,
+    department_id: String,
+    employment_type: &'static str,
+    status: &'static str,
+    hire_date: String,
+    termination_date: String,
+    pay: Option<(String, String, &'static str)>,
+}
+
+impl From<&Employee> for ExportRecord {
+    fn from(employee: &Employee) -> Self {
+        let employment = employee.employment();
+        Self {
+            employee_number: employee.employee_id().to_string(),
+            first_name: employee.personal().first_name.clone(),
+            last_name: employee.personal().last_name.clone(),
+            work_email: employment.work_email.clone(),
+            job_title: employment.job_title.clone(),
+            department_id: employment.department_id.clone().unwrap_or_default(),
+            employment_type: employment_type_code(&employment.employment_type),
+            status: status_code(employee.status()),
+            hire_date: employment.hire_date.map(|d| d.to_string()).unwrap_or_default(),
+            termination_date: employment.termination_date.map(|d| d.to_string()).unwrap_or_default(),
+            pay: employee.compensation().pay_rate.as_ref().map(|rate| {
+                (rate.amount().to_string(), rate.currency().to_string(), frequency_code(rate.frequency()))
+            }),
+        }
+    }
+}
+
+fn employment_type_code(employment_type: &EmploymentType) -> &'static str {
+    match employment_type {
+        EmploymentType::FullTime => "full_time",
+        EmploymentType::PartTime => "part_time",
+        EmploymentType::Contractor => "contractor",
+        EmploymentType::Intern => "intern",
+        EmploymentType::Temporary => "temporary",
+    }
+}
+
+fn status_code(status: &EmploymentStatus) -> &'static str {
+    match status {
+        EmploymentStatus::Active => "active",
+        EmploymentStatus::OnLeave => "on_leave",
+        EmploymentStatus::Suspended => "suspended",
+        EmploymentStatus::Terminated => "terminated",
+        EmploymentStatus::Retired => "retired",
+    }
+}
+
+fn frequency_code(frequency: &PayFrequency) -> &'static str {
+    match frequency {
+        PayFrequency::Hourly => "hourly",
+        PayFrequency::Weekly => "weekly",
+        PayFrequency::BiWeekly => "biweekly",
+        PayFrequency::FourWeekly => "fourweekly",
+        PayFrequency::SemiMonthly => "semimonthly",
+        PayFrequency::Monthly => "monthly",
+        PayFrequency::Annually => "annually",
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CSV
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Quote a CSV field when it contains a delimiter, quote, or newline.
+/// Shared by every CSV this crate writes.
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn to_csv(roster: &[ExportRecord]) -> String {
+    let mut out = String::from(EMPLOYEE_CSV_HEADER);
+    for record in roster {
+        let (amount, currency, frequency) = record.pay.clone().unwrap_or_default();
+        let fields = [
+            record.employee_number.as_str(),
+            &record.first_name,
+            &record.last_name,
+            &record.work_email,
+            &record.job_title,
+            &record.department_id,
+            record.employment_type,
+            record.status,
+            &record.hire_date,
+            &record.termination_date,
+            &amount,
+            &currency,
+            frequency,
+        ];
+        out.push_str(&fields.iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(","));
+        out.push('\n');
+    }
+    out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// HR-XML
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn element(out: &mut String, indent: &str, name: &str, value: &str) {
+    out.push_str(&format!("{indent}<{name}>{}</{name}>\n", xml_escape(value)));
+}
+
+fn to_hr_xml(roster: &[ExportRecord]) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!("<EmployeeRoster count=\"{}\">\n", roster.len()));
+
+    for record in roster {
+        out.push_str("  <Employee>\n");
+        element(&mut out, "    ", "EmployeeId", &record.employee_number);
+        out.push_str("    <PersonName>\n");
+        element(&mut out, "      ", "GivenName", &record.first_name);
+        element(&mut out, "      ", "FamilyName", &record.last_name);
+        out.push_str("    </PersonName>\n");
+        element(&mut out, "    ", "Email", &record.work_email);
+        element(&mut out, "    ", "PositionTitle", &record.job_title);
+        if !record.department_id.is_empty() {
+            element(&mut out, "    ", "DepartmentId", &record.department_id);
+        }
+        element(&mut out, "    ", "EmploymentType", record.employment_type);
+        element(&mut out, "    ", "Status", record.status);
+        if !record.hire_date.is_empty() {
+            element(&mut out, "    ", "HireDate", &record.hire_date);
+        }
+        if !record.termination_date.is_empty() {
+            element(&mut out, "    ", "TerminationDate", &record.termination_date);
+        }
+        if let Some((amount, currency, frequency)) = &record.pay {
+            out.push_str(&format!(
+                "    <Remuneration currencyCode=\"{}\" frequency=\"{}\">{}</Remuneration>\n",
+                xml_escape(currency), frequency, xml_escape(amount)
+            ));
+        }
+        out.push_str("  </Employee>\n");
+    }
+
+    out.push_str("</EmployeeRoster>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{EmployeeId, PayRate};
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn roster() -> Vec<Employee> {
+        let mut ada = Employee::hire(EmployeeId::new(2024, 1), "Ada", "Obi", "ada@example.com", "Engineer", date(2024, 1, 15));
+        ada.set_compensation(PayRate::salary(dec!(850000), "NGN", PayFrequency::Monthly), date(2024, 1, 15));
+        ada.transfer(Some("eng".into()), None);
+
+        // Comma and ampersand exercise CSV quoting and XML escaping
+        let mut tunde = Employee::hire(EmployeeId::new(2024, 2), "Tunde", "Bello", "tunde@example.com", "Sales, West & Central", date(2024, 3, 1));
+        tunde.transfer(Some("sales".into()), None);
+
+        let mut gone = Employee::hire(EmployeeId::new(2023, 9), "Chidi", "Eze", "chidi@example.com", "Analyst", date(2023, 5, 1));
+        gone.terminate(date(2024, 2, 29), "Resigned").unwrap();
+
+        vec![tunde, ada, gone]
+    }
+
+    /// Minimal well-formedness check: declaration, single root, balanced tags
+    fn assert_well_formed(xml: &str) {
+        let body = xml.strip_prefix("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n").expect("XML declaration");
+        let mut stack: Vec<&str> = Vec::new();
+        let mut roots = 0;
+        let mut rest = body;
+        while let Some(start) = rest.find('<') {
+            assert!(!rest[..start].contains(['<', '>']), "stray markup");
+            let end = start + rest[start..].find('>').expect("unterminated tag");
+            let tag = &rest[start + 1..end];
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(stack.pop(), Some(name), "mismatched closing tag");
+            } else {
+                if stack.is_empty() {
+                    roots += 1;
+                }
+                stack.push(tag.split_whitespace().next().unwrap());
+            }
+            rest = &rest[end + 1..];
+        }
+        assert!(stack.is_empty(), "unclosed tags: {:?}", stack);
+        assert_eq!(roots, 1);
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let csv = export_employees(&roster(), ExportFormat::Csv, &ExportFilter::default());
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(format!("{}\n", lines[0]), EMPLOYEE_CSV_HEADER);
+        // Terminated employee is not on the active roster
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], format!(
+            "{},Ada,Obi,ada@example.com,Engineer,eng,full_time,active,2024-01-15,,850000,NGN,monthly",
+            EmployeeId::new(2024, 1)
+        ));
+        assert!(lines[2].contains("\"Sales, West & Central\""));
+
+        let sales = ExportFilter { department_id: Some("sales".into()), ..Default::default() };
+        assert_eq!(export_employees(&roster(), ExportFormat::Csv, &sales).lines().count(), 2);
+
+        let terminated = ExportFilter { status: Some(EmploymentStatus::Terminated), ..Default::default() };
+        let csv = export_employees(&roster(), ExportFormat::Csv, &terminated);
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.contains(",terminated,2023-05-01,2024-02-29,"));
+    }
+
+    #[test]
+    fn test_hr_xml_export() {
+        let xml = export_employees(&roster(), ExportFormat::HrXml, &ExportFilter::default());
+        assert_well_formed(&xml);
+
+        assert!(xml.contains("<EmployeeRoster count=\"2\">"));
+        assert_eq!(xml.matches("<Employee>").count(), 2);
+        for required in ["<EmployeeId>", "<GivenName>", "<FamilyName>", "<Email>", "<PositionTitle>", "<Status>", "<HireDate>"] {
+            assert_eq!(xml.matches(required).count(), 2, "missing {}", required);
+        }
+        assert!(xml.contains("<HireDate>2024-01-15</HireDate>"));
+        assert!(xml.contains("<Remuneration currencyCode=\"NGN\" frequency=\"monthly\">850000</Remuneration>"));
+        assert!(xml.contains("<PositionTitle>Sales, West &amp; Central</PositionTitle>"));
+    }
+}
@@ -0,0 +1,418 @@
+//! Outbound Throttling
+//!
+//! Webhook, SMS, and mobile money providers throttle or reject clients that
+//! burst. Every outbound call goes through an `OutboundLimiter`, which caps
+//! calls in flight per provider with a semaphore and spaces them out with a
+//! token bucket. Callers over either limit wait their turn in arrival order;
+//! nothing is dropped.
+//!
+//! Limits are keyed `integration:provider`, e.g. `sms:africastalking`, so
+//! one provider's quota never holds up another's. A provider without its
+//! own limit takes its integration's, then the limiter's default.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+use crate::messaging::event_store::{WebhookDelivery, WebhookSender};
+use crate::messaging::MessagingError;
+use crate::payroll::africa_mobile_gateway::{GatewayError, MobileMoneyGateway, PaymentRequest, PaymentResponse, ProviderRouter};
+use crate::sms::{SmsError, SmsSender};
+
+/// Integration names used as limiter keys
+pub const WEBHOOK_INTEGRATION: &str = "webhook";
+pub const SMS_INTEGRATION: &str = "sms";
+pub const MOBILE_MONEY_INTEGRATION: &str = "mobile_money";
+
+/// Limits for one integration
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OutboundLimit {
+    /// Calls in flight at once
+    pub max_concurrent: usize,
+    /// Sustained call rate; 0 disables rate limiting
+    pub requests_per_second: u32,
+    /// Calls allowed back to back before the rate applies
+    pub burst: u32,
+}
+
+impl Default for OutboundLimit {
+    fn default() -> Self {
+        Self { max_concurrent: 10, requests_per_second: 20, burst: 20 }
+    }
+}
+
+/// Token bucket that hands out reservations: a caller takes a token even
+/// when none is left and waits until the bucket would have refilled it, so
+/// waiters are served in the order they arrived
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Debug)]
+struct Gate {
+    limit: OutboundLimit,
+    permits: Arc<Semaphore>,
+    bucket: Mutex<TokenBucket>,
+}
+
+impl Gate {
+    fn new(limit: OutboundLimit) -> Self {
+        Self {
+            limit,
+            permits: Arc::new(Semaphore::new(limit.max_concurrent.max(1))),
+            bucket: Mutex::new(TokenBucket { tokens: f64::from(limit.burst.max(1)), updated: Instant::now() }),
+        }
+    }
+
+    /// Concurrency slot for a caller that can't await, polled until free
+    fn acquire_blocking(&self) -> OwnedSemaphorePermit {
+        loop {
+            if let Ok(permit) = self.permits.clone().try_acquire_owned() {
+                return permit;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    /// How long the caller must wait for its token
+    fn reserve(&self) -> Duration {
+        if self.limit.requests_per_second == 0 {
+            return Duration::ZERO;
+        }
+        let rate = f64::from(self.limit.requests_per_second);
+        let mut bucket = self.bucket.lock().expect("token bucket lock poisoned");
+        let now = Instant::now();
+        let refilled = now.duration_since(bucket.updated).as_secs_f64() * rate;
+        bucket.tokens = (bucket.tokens + refilled).min(f64::from(self.limit.burst.max(1)));
+        bucket.updated = now;
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+}
+
+/// Held for the duration of an outbound call
+#[derive(Debug)]
+pub struct OutboundPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+/// Limiter key for one provider of an integration
+pub fn provider_key(integration: &str, provider: &str) -> String {
+    format!("{}:{}", integration, provider)
+}
+
+/// Concurrency and rate limits per integration and provider
+#[derive(Debug, Clone, Default)]
+pub struct OutboundLimiter {
+    default_limit: OutboundLimit,
+    limits: Arc<Mutex<HashMap<String, OutboundLimit>>>,
+    gates: Arc<Mutex<HashMap<String, Arc<Gate>>>>,
+}
+
+impl OutboundLimiter {
+    pub fn new(default_limit: OutboundLimit) -> Self {
+        Self { default_limit, limits: Arc::default(), gates: Arc::default() }
+    }
+
+    /// Set the limits for an integration's providers, or for one provider
+    /// when `key` is a `provider_key`. Calls already waiting keep the old ones.
+    pub fn with_limit(self, key: &str, limit: OutboundLimit) -> Self {
+        self.limits.lock().expect("limiter lock poisoned").insert(key.to_string(), limit);
+        let prefix = format!("{}:", key);
+        self.gates.lock().expect("limiter lock poisoned").retain(|gate, _| gate != key && !gate.starts_with(&prefix));
+        self
+    }
+
+    pub fn limit(&self, key: &str) -> OutboundLimit {
+        self.gate(key).limit
+    }
+
+    fn configured_limit(&self, key: &str) -> OutboundLimit {
+        let limits = self.limits.lock().expect("limiter lock poisoned");
+        let integration = key.split_once(':').map_or(key, |(integration, _)| integration);
+        limits.get(key).or_else(|| limits.get(integration)).copied().unwrap_or(self.default_limit)
+    }
+
+    fn gate(&self, key: &str) -> Arc<Gate> {
+        let limit = self.configured_limit(key);
+        let mut gates = self.gates.lock().expect("limiter lock poisoned");
+        gates.entry(key.to_string()).or_insert_with(|| Arc::new(Gate::new(limit))).clone()
+    }
+
+    /// Wait for a concurrency slot and then for a rate token
+    pub async fn acquire(&self, key: &str) -> OutboundPermit {
+        let gate = self.gate(key);
+        let permit = gate.permits.clone().acquire_owned().await.expect("limiter semaphore is never closed");
+        let wait = gate.reserve();
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        OutboundPermit { _permit: permit }
+    }
+
+    /// `acquire` for synchronous callers, blocking the thread while it waits
+    pub fn acquire_blocking(&self, key: &str) -> OutboundPermit {
+        let gate = self.gate(key);
+        let permit = gate.acquire_blocking();
+        let wait = gate.reserve();
+        if !wait.is_zero() {
+            std::thread::sleep(wait);
+        }
+        OutboundPermit { _permit: permit }
+    }
+
+    /// Run `call` once the limits for `key` allow it
+    pub async fn run<F, T>(&self, key: &str, call: F) -> T
+    where
+        F: Future<Output = T>,
+    {
+        let _permit = self.acquire(key).await;
+        call.await
+    }
+}
+
+/// Webhook sender behind the `webhook` limits
+pub struct ThrottledWebhookSender {
+    inner: Arc<dyn WebhookSender>,
+    limiter: OutboundLimiter,
+}
+
+impl ThrottledWebhookSender {
+    pub fn new(inner: Arc<dyn WebhookSender>, limiter: OutboundLimiter) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+#[async_trait]
+impl WebhookSender for ThrottledWebhookSender {
+    async fn send(&self, url: &str, delivery: &WebhookDelivery) -> Result<(), MessagingError> {
+        self.limiter.run(WEBHOOK_INTEGRATION, self.inner.send(url, delivery)).await
+    }
+}
+
+/// SMS gateway behind the `sms` limits for its provider
+pub struct ThrottledSmsSender {
+    inner: Arc<dyn SmsSender>,
+    limiter: OutboundLimiter,
+    key: String,
+}
+
+impl ThrottledSmsSender {
+    pub fn new(inner: Arc<dyn SmsSender>, limiter: OutboundLimiter, provider: &str) -> Self {
+        Self { inner, limiter, key: provider_key(SMS_INTEGRATION, provider) }
+    }
+}
+
+impl SmsSender for ThrottledSmsSender {
+    fn send(&self, phone: &str, message: &str) -> Result<(), SmsError> {
+        let _permit = self.limiter.acquire_blocking(&self.key);
+        self.inner.send(phone, message)
+    }
+}
+
+/// Mobile money gateway behind the `mobile_money` limits of the provider
+/// each payment is routed to
+pub struct ThrottledMobileMoneyGateway {
+    inner: Arc<dyn MobileMoneyGateway>,
+    limiter: OutboundLimiter,
+    router: ProviderRouter,
+}
+
+impl ThrottledMobileMoneyGateway {
+    pub fn new(inner: Arc<dyn MobileMoneyGateway>, limiter: OutboundLimiter) -> Self {
+        Self { inner, limiter, router: ProviderRouter::new() }
+    }
+
+    fn key(&self, request: &PaymentRequest) -> String {
+        match self.router.route(&request.country, &request.phone_number, request.provider.as_deref()) {
+            Ok(provider) => provider_key(MOBILE_MONEY_INTEGRATION, &provider),
+            Err(_) => MOBILE_MONEY_INTEGRATION.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl MobileMoneyGateway for ThrottledMobileMoneyGateway {
+    async fn send(&self, request: &PaymentRequest) -> Result<PaymentResponse, GatewayError> {
+        self.limiter.run(&self.key(request), self.inner.send(request)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_concurrent_dispatches_capped_and_queued() {
+        let limiter = OutboundLimiter::new(OutboundLimit::default()).with_limit(
+            SMS_INTEGRATION,
+            OutboundLimit { max_concurrent: 2, requests_per_second: 0, burst: 1 },
+        );
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let calls = (0..6).map(|i| {
+            let (limiter, in_flight, peak) = (limiter.clone(), in_flight.clone(), peak.clone());
+            tokio::spawn(async move {
+                limiter
+                    .run(SMS_INTEGRATION, async {
+                        let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        in_flight.fetch_sub(1, Ordering::SeqCst);
+                        i
+                    })
+                    .await
+            })
+        });
+        let mut done = Vec::new();
+        for call in calls.collect::<Vec<_>>() {
+            done.push(call.await.unwrap());
+        }
+
+        // Every call ran, never more than two at once
+        assert_eq!(done, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_burst_smoothed_to_rate() {
+        let limiter = OutboundLimiter::new(OutboundLimit { max_concurrent: 10, requests_per_second: 50, burst: 2 });
+        let started = Instant::now();
+
+        // Two go straight away, the other three wait 20ms apiece
+        for _ in 0..5 {
+            limiter.run(MOBILE_MONEY_INTEGRATION, async {}).await;
+        }
+
+        assert!(started.elapsed() >= Duration::from_millis(55));
+        assert_eq!(limiter.limit(WEBHOOK_INTEGRATION), limiter.limit(MOBILE_MONEY_INTEGRATION));
+    }
+
+    #[test]
+    fn test_limits_keyed_by_provider() {
+        let sms = OutboundLimit { max_concurrent: 1, requests_per_second: 0, burst: 1 };
+        let twilio = OutboundLimit { max_concurrent: 3, requests_per_second: 5, burst: 5 };
+        let limiter = OutboundLimiter::new(OutboundLimit::default())
+            .with_limit(SMS_INTEGRATION, sms)
+            .with_limit(&provider_key(SMS_INTEGRATION, "twilio"), twilio);
+
+        assert_eq!(limiter.limit(&provider_key(SMS_INTEGRATION, "twilio")), twilio);
+        assert_eq!(limiter.limit(&provider_key(SMS_INTEGRATION, "africastalking")), sms);
+        assert_eq!(limiter.limit(&provider_key(MOBILE_MONEY_INTEGRATION, "MPESA")), OutboundLimit::default());
+
+        // Each provider has its own slot: holding one doesn't block the other
+        let _held = limiter.acquire_blocking(&provider_key(SMS_INTEGRATION, "africastalking"));
+        let _other = limiter.acquire_blocking(&provider_key(SMS_INTEGRATION, "termii"));
+    }
+
+    #[derive(Default)]
+    struct SlowSms {
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl SmsSender for SlowSms {
+        fn send(&self, _phone: &str, _message: &str) -> Result<(), SmsError> {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(20));
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sms_sender_throttled() {
+        let limiter = OutboundLimiter::new(OutboundLimit::default())
+            .with_limit(SMS_INTEGRATION, OutboundLimit { max_concurrent: 2, requests_per_second: 0, burst: 1 });
+        let inner = Arc::new(SlowSms::default());
+        let sender = Arc::new(ThrottledSmsSender::new(inner.clone(), limiter, "africastalking"));
+
+        let threads: Vec<_> = (0..6)
+            .map(|_| {
+                let sender = sender.clone();
+                std::thread::spawn(move || sender.send("+254700000000", "Leave approved").unwrap())
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(inner.peak.load(Ordering::SeqCst), 2);
+    }
+
+    struct RecordingGateway {
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl MobileMoneyGateway for RecordingGateway {
+        async fn send(&self, request: &PaymentRequest) -> Result<PaymentResponse, GatewayError> {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(PaymentResponse {
+                transaction_id: request.id.clone(),
+                provider_ref: request.reference.clone(),
+                status: crate::payroll::africa_mobile_gateway::TransactionState::Pending,
+                amount: request.amount,
+                currency: request.currency.clone(),
+                fees: rust_decimal::Decimal::ZERO,
+                provider_message: String::new(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mobile_money_throttled_per_routed_provider() {
+        let one_at_a_time = OutboundLimit { max_concurrent: 1, requests_per_second: 0, burst: 1 };
+        let limiter = OutboundLimiter::new(OutboundLimit::default()).with_limit(MOBILE_MONEY_INTEGRATION, one_at_a_time);
+        let inner = Arc::new(RecordingGateway { in_flight: AtomicUsize::new(0), peak: AtomicUsize::new(0) });
+        let gateway = Arc::new(ThrottledMobileMoneyGateway::new(inner.clone(), limiter));
+        let request = |provider: &str| PaymentRequest {
+            id: Uuid::new_v4().to_string(),
+            external_id: String::new(),
+            amount: rust_decimal::Decimal::ONE,
+            currency: "KES".to_string(),
+            phone_number: "+254700000000".to_string(),
+            recipient_name: "Wanjiru".to_string(),
+            country: "KE".to_string(),
+            provider: Some(provider.to_string()),
+            description: String::new(),
+            reference: "PAY-1".to_string(),
+            callback_url: None,
+            metadata: Default::default(),
+        };
+
+        // Two providers run side by side; each is held to one call at a time
+        let calls: Vec<_> = ["MPESA", "MPESA", "AIRTEL_KE", "AIRTEL_KE"]
+            .into_iter()
+            .map(|provider| {
+                let (gateway, request) = (gateway.clone(), request(provider));
+                tokio::spawn(async move { gateway.send(&request).await.unwrap() })
+            })
+            .collect();
+        for call in calls {
+            call.await.unwrap();
+        }
+
+        assert_eq!(inner.peak.load(Ordering::SeqCst), 2);
+    }
+}
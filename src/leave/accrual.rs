@@ -0,0 +1,296 @@
+//! Leave Accrual & Protected Leave
+//!
+//! Separate accrual, balance, and carryover rules for PTO and sick leave,
+//! plus an FMLA-style protected-leave counter (12 workweeks per 12 months)
+//! that keeps unpaid protected absence from being treated as a resignation.
+
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::service::LeaveError;
+
+/// Accruing leave category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaveCategory {
+    Pto,
+    Sick,
+}
+
+/// What happens to an unused balance at year end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CarryoverRule {
+    Forfeit,
+    Capped(Decimal),
+    Unlimited,
+}
+
+/// Accrual policy for one category (days)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccrualPolicy {
+    pub category: LeaveCategory,
+    pub days_per_month: Decimal,
+    /// Accrual stops while the balance is at this ceiling
+    pub max_balance: Option<Decimal>,
+    pub carryover: CarryoverRule,
+}
+
+impl AccrualPolicy {
+    /// 20 days a year, up to 5 days carried over
+    pub fn default_pto() -> Self {
+        Self {
+            category: LeaveCategory::Pto,
+            days_per_month: dec!(1.6667),
+            max_balance: Some(dec!(30)),
+            carryover: CarryoverRule::Capped(dec!(5)),
+        }
+    }
+
+    /// 12 days a year, carried over in full up to a 60-day bank
+    pub fn default_sick() -> Self {
+        Self {
+            category: LeaveCategory::Sick,
+            days_per_month: dec!(1),
+            max_balance: Some(dec!(60)),
+            carryover: CarryoverRule::Unlimited,
+        }
+    }
+}
+
+/// Balance for one category
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CategoryBalance {
+    pub carried_over: Decimal,
+    pub accrued: Decimal,
+    pub used: Decimal,
+}
+
+impl CategoryBalance {
+    pub fn available(&self) -> Decimal {
+        self.carried_over + self.accrued - self.used
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// PROTECTED LEAVE (FMLA-STYLE)
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Protected-leave entitlement and usage for a 12-month leave year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtectedLeave {
+    pub entitlement_weeks: Decimal,
+    pub workdays_per_week: Decimal,
+    pub used_days: Decimal,
+    pub eligible: bool,
+    /// Protected absences (inclusive), used to guard separations
+    pub periods: Vec<(NaiveDate, NaiveDate)>,
+}
+
+impl ProtectedLeave {
+    /// FMLA minimums: 12 months of service and 1,250 hours in the last 12 months
+    pub const MIN_SERVICE_MONTHS: u32 = 12;
+    pub const MIN_HOURS_WORKED: Decimal = dec!(1250);
+
+    pub fn new(months_of_service: u32, hours_last_12_months: Decimal) -> Self {
+        Self {
+            entitlement_weeks: dec!(12),
+            workdays_per_week: dec!(5),
+            used_days: Decimal::ZERO,
+            eligible: months_of_service >= Self::MIN_SERVICE_MONTHS
+                && hours_last_12_months >= Self::MIN_HOURS_WORKED,
+            periods: vec![],
+        }
+    }
+
+    pub fn entitlement_days(&self) -> Decimal {
+        self.entitlement_weeks * self.workdays_per_week
+    }
+
+    pub fn remaining_days(&self) -> Decimal {
+        (self.entitlement_days() - self.used_days).max(Decimal::ZERO)
+    }
+
+    pub fn remaining_weeks(&self) -> Decimal {
+        self.remaining_days() / self.workdays_per_week
+    }
+
+    /// Record protected leave; fails if ineligible or the entitlement is exhausted
+    pub fn take(&mut self, start: NaiveDate, end: NaiveDate, workdays: Decimal) -> Result<Decimal, LeaveError> {
+        if !self.eligible {
+            return Err(LeaveError::NotEligibleForProtectedLeave);
+        }
+        if end < start {
+            return Err(LeaveError::InvalidDateRange);
+        }
+        if workdays > self.remaining_days() {
+            return Err(LeaveError::ProtectedLeaveExhausted {
+                remaining_days: self.remaining_days(),
+                requested: workdays,
+            });
+        }
+        self.used_days += workdays;
+        self.periods.push((start, end));
+        Ok(self.remaining_days())
+    }
+
+    pub fn is_protected_on(&self, date: NaiveDate) -> bool {
+        self.periods.iter().any(|(start, end)| *start <= date && date <= *end)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// LEAVE ACCOUNT
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Why employment would end, as inferred by payroll or HR
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeparationReason {
+    Resignation,
+    JobAbandonment,
+    Dismissal,
+    Redundancy,
+}
+
+/// Per-employee PTO, sick, and protected-leave balances for a year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaveAccount {
+    pub employee_id: Uuid,
+    pub year: i32,
+    pub balances: HashMap<LeaveCategory, CategoryBalance>,
+    pub protected: ProtectedLeave,
+}
+
+impl LeaveAccount {
+    pub fn new(employee_id: Uuid, year: i32, protected: ProtectedLeave) -> Self {
+        let balances = [LeaveCategory::Pto, LeaveCategory::Sick]
+            .into_iter()
+            .map(|c| (c, CategoryBalance::default()))
+            .collect();
+        Self { employee_id, year, balances, protected }
+    }
+
+    pub fn balance(&self, category: LeaveCategory) -> &CategoryBalance {
+        &self.balances[&category]
+    }
+
+    pub fn available(&self, category: LeaveCategory) -> Decimal {
+        self.balance(category).available()
+    }
+
+    /// Accrue one month under the given policy, respecting its ceiling
+    pub fn accrue_month(&mut self, policy: &AccrualPolicy) -> Decimal {
+        let balance = self.balances.entry(policy.category).or_default();
+        let mut amount = policy.days_per_month;
+        if let Some(max) = policy.max_balance {
+            amount = amount.min((max - balance.available()).max(Decimal::ZERO));
+        }
+        balance.accrued += amount;
+        amount
+    }
+
+    /// Use leave from one category only; sick and PTO never borrow from each other
+    pub fn take(&mut self, category: LeaveCategory, days: Decimal) -> Result<Decimal, LeaveError> {
+        let balance = self.balances.entry(category).or_default();
+        if days > balance.available() {
+            return Err(LeaveError::InsufficientBalance { available: balance.available(), requested: days });
+        }
+        balance.used += days;
+        Ok(balance.available())
+    }
+
+    /// Open next year's account, applying each category's carryover rule.
+    /// The protected-leave counter resets with the new leave year.
+    pub fn roll_over(&self, policies: &[AccrualPolicy], protected: ProtectedLeave) -> LeaveAccount {
+        let mut next = LeaveAccount::new(self.employee_id, self.year + 1, protected);
+        for policy in policies {
+            let unused = self.balances.get(&policy.category).map(|b| b.available()).unwrap_or_default();
+            let carried = match policy.carryover {
+                CarryoverRule::Forfeit => Decimal::ZERO,
+                CarryoverRule::Capped(cap) => unused.min(cap),
+                CarryoverRule::Unlimited => unused,
+            };
+            next.balances.entry(policy.category).or_default().carried_over = carried.max(Decimal::ZERO);
+        }
+        next
+    }
+
+    /// Guard used before ending employment: an absence covered by protected
+    /// leave cannot be read as a resignation or job abandonment
+    pub fn validate_separation(&self, date: NaiveDate, reason: SeparationReason) -> Result<(), LeaveError> {
+        let voluntary = matches!(reason, SeparationReason::Resignation | SeparationReason::JobAbandonment);
+        if voluntary && self.protected.is_protected_on(date) {
+            return Err(LeaveError::OnProtectedLeave(date));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account() -> LeaveAccount {
+        LeaveAccount::new(Uuid::new_v4(), 2024, ProtectedLeave::new(24, dec!(1800)))
+    }
+
+    #[test]
+    fn test_sick_and_pto_balances_are_separate() {
+        let (pto, sick) = (AccrualPolicy::default_pto(), AccrualPolicy::default_sick());
+        let mut account = account();
+        for _ in 0..12 {
+            account.accrue_month(&pto);
+            account.accrue_month(&sick);
+        }
+        assert_eq!(account.available(LeaveCategory::Sick), dec!(12));
+        assert_eq!(account.available(LeaveCategory::Pto).round_dp(2), dec!(20.00));
+
+        account.take(LeaveCategory::Sick, dec!(10)).unwrap();
+        assert_eq!(account.available(LeaveCategory::Sick), dec!(2));
+        assert_eq!(account.available(LeaveCategory::Pto).round_dp(2), dec!(20.00));
+
+        // Sick leave cannot dip into PTO
+        assert!(matches!(account.take(LeaveCategory::Sick, dec!(3)), Err(LeaveError::InsufficientBalance { .. })));
+
+        // PTO carryover is capped; sick carries over in full
+        account.take(LeaveCategory::Pto, dec!(8)).unwrap();
+        let next = account.roll_over(&[pto, sick], ProtectedLeave::new(36, dec!(1800)));
+        assert_eq!(next.balance(LeaveCategory::Pto).carried_over, dec!(5));
+        assert_eq!(next.balance(LeaveCategory::Sick).carried_over, dec!(2));
+    }
+
+    #[test]
+    fn test_protected_leave_exhaustion() {
+        let mut account = account();
+        let d = |m, day| NaiveDate::from_ymd_opt(2024, m, day).unwrap();
+
+        assert_eq!(account.protected.take(d(3, 4), d(4, 26), dec!(40)).unwrap(), dec!(20));
+        assert_eq!(account.protected.remaining_weeks(), dec!(4));
+
+        let err = account.protected.take(d(6, 3), d(7, 12), dec!(30)).unwrap_err();
+        assert!(matches!(err, LeaveError::ProtectedLeaveExhausted { remaining_days, .. } if remaining_days == dec!(20)));
+
+        account.protected.take(d(6, 3), d(6, 28), dec!(20)).unwrap();
+        assert_eq!(account.protected.remaining_days(), Decimal::ZERO);
+
+        // Unpaid protected absence is not a resignation
+        assert!(matches!(
+            account.validate_separation(d(4, 1), SeparationReason::JobAbandonment),
+            Err(LeaveError::OnProtectedLeave(_))
+        ));
+        assert!(account.validate_separation(d(5, 1), SeparationReason::Resignation).is_ok());
+    }
+
+    #[test]
+    fn test_protected_leave_eligibility() {
+        let mut new_hire = ProtectedLeave::new(6, dec!(900));
+        assert!(!new_hire.eligible);
+        let d = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        assert!(matches!(new_hire.take(d, d, dec!(1)), Err(LeaveError::NotEligibleForProtectedLeave)));
+    }
+}
@@ -0,0 +1,111 @@
+//! Leave Encashment
+//!
+//! Year-end payout of unused leave for policies that pay days out instead
+//! of carrying them over. Each country (or tenant policy) caps how many
+//! days a year may be encashed; the payout is the days at the employee's
+//! daily rate, annual pay over the policy's working days per year.
+
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::payroll::MoneyRounding;
+
+/// Payroll line code for encashed leave
+pub const LEAVE_ENCASHMENT_LINE: &str = "leave_encashment";
+
+/// Encashment rules for one country or policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncashmentPolicy {
+    /// Days that may be encashed per leave year; zero disables encashment
+    pub max_days_per_year: Decimal,
+    /// Divisor turning annual pay into a daily rate
+    pub working_days_per_year: Decimal,
+}
+
+impl Default for EncashmentPolicy {
+    fn default() -> Self {
+        Self { max_days_per_year: dec!(10), working_days_per_year: dec!(260) }
+    }
+}
+
+impl EncashmentPolicy {
+    pub fn daily_rate(&self, annual_pay: Decimal) -> Decimal {
+        if self.working_days_per_year.is_zero() {
+            return Decimal::ZERO;
+        }
+        annual_pay / self.working_days_per_year
+    }
+}
+
+/// Encashment policies by country, falling back to a default
+#[derive(Debug, Clone, Default)]
+pub struct EncashmentPolicies {
+    countries: HashMap<String, EncashmentPolicy>,
+    default: EncashmentPolicy,
+}
+
+impl EncashmentPolicies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, country_code: &str, policy: EncashmentPolicy) -> &mut Self {
+        self.countries.insert(country_code.to_ascii_uppercase(), policy);
+        self
+    }
+
+    pub fn for_country(&self, country_code: &str) -> EncashmentPolicy {
+        self.countries
+            .get(&country_code.to_ascii_uppercase())
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// Encashed leave and the payroll line paying it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaveEncashment {
+    pub employee_id: Uuid,
+    pub leave_type_id: Uuid,
+    pub leave_year: i32,
+    pub days: Decimal,
+    pub daily_rate: Decimal,
+    pub amount: Decimal,
+    pub as_of: NaiveDate,
+}
+
+impl LeaveEncashment {
+    pub(super) fn new(
+        employee_id: Uuid,
+        leave_type_id: Uuid,
+        leave_year: i32,
+        days: Decimal,
+        daily_rate: Decimal,
+        as_of: NaiveDate,
+    ) -> Self {
+        Self {
+            employee_id,
+            leave_type_id,
+            leave_year,
+            days,
+            daily_rate,
+            amount: MoneyRounding::default().round(days * daily_rate),
+            as_of,
+        }
+    }
+
+    /// `other_allowances` entry for the payroll run that pays it
+    pub fn payroll_line(&self) -> serde_json::Value {
+        serde_json::json!({
+            LEAVE_ENCASHMENT_LINE: {
+                "days": self.days,
+                "daily_rate": MoneyRounding::default().round(self.daily_rate),
+                "amount": self.amount,
+            }
+        })
+    }
+}
@@ -11,6 +11,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::validation::ValidJson;
 use super::models::*;
 use super::service::LeaveService;
 
@@ -126,14 +127,15 @@ pub async fn get_employee_balances(
 /// POST /api/v1/leave/requests
 pub async fn create_leave_request(
     State(_state): State<LeaveAppState>,
-    Json(_request): Json<CreateLeaveRequest>,
+    ValidJson(_request): ValidJson<CreateLeaveRequest>,
 ) -> impl IntoResponse {
     // In real implementation:
     // 1. Get employee_id from auth
     // 2. Fetch leave type
     // 3. Fetch current balance
     // 4. Fetch public holidays
-    // 5. Create request
+    // 5. Create request for the employee's country (leave type checked against
+    //    its statutory types; blackout periods apply unless the caller has LeaveAdmin)
     // 6. Update pending balance
     // 7. Send notification to manager
     (StatusCode::CREATED, Json(ApiResponse::<LeaveRequest>::error("Stub")))
@@ -0,0 +1,97 @@
+//! Leave History
+//!
+//! Requests and yearly balances on file for each employee record. When a
+//! duplicate record is merged away its requests move to the survivor. Its
+//! used and pending days are added to the survivor's balance for the same
+//! leave type and year; the survivor's entitlement stands, since it is one
+//! person's entitlement recorded twice.
+
+use std::sync::Arc;
+use chrono::Utc;
+use dashmap::DashMap;
+use uuid::Uuid;
+
+use crate::domain::services::EmployeeRecords;
+use super::models::{LeaveBalance, LeaveRequest};
+
+/// Leave requests and balances by id
+#[derive(Debug, Clone, Default)]
+pub struct LeaveHistory {
+    // In real implementation, backed by the leave_requests and leave_balances tables
+    requests: Arc<DashMap<Uuid, LeaveRequest>>,
+    balances: Arc<DashMap<Uuid, LeaveBalance>>,
+}
+
+impl LeaveHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_request(&self, request: LeaveRequest) {
+        self.requests.insert(request.id, request);
+    }
+
+    pub fn record_balance(&self, balance: LeaveBalance) {
+        self.balances.insert(balance.id, balance);
+    }
+
+    /// An employee's requests, oldest first
+    pub fn requests(&self, employee_id: Uuid) -> Vec<LeaveRequest> {
+        let mut requests: Vec<_> = self.requests
+            .iter()
+            .filter(|r| r.employee_id == employee_id)
+            .map(|r| r.clone())
+            .collect();
+        requests.sort_by_key(|r| (r.start_date, r.created_at));
+        requests
+    }
+
+    /// An employee's balances for `year`, by leave type name
+    pub fn balances(&self, employee_id: Uuid, year: i32) -> Vec<LeaveBalance> {
+        let mut balances: Vec<_> = self.balances
+            .iter()
+            .filter(|b| b.employee_id == employee_id && b.year == year)
+            .map(|b| b.clone())
+            .collect();
+        balances.sort_by(|a, b| a.leave_type_name.cmp(&b.leave_type_name));
+        balances
+    }
+}
+
+impl EmployeeRecords for LeaveHistory {
+    fn reparent(&self, from: Uuid, to: Uuid) -> usize {
+        let mut moved = 0;
+        for mut request in self.requests.iter_mut().filter(|r| r.employee_id == from) {
+            request.employee_id = to;
+            moved += 1;
+        }
+
+        let duplicates: Vec<LeaveBalance> = self.balances
+            .iter()
+            .filter(|b| b.employee_id == from)
+            .map(|b| b.clone())
+            .collect();
+        for duplicate in duplicates {
+            let existing = self.balances
+                .iter()
+                .find(|b| b.employee_id == to && b.leave_type_id == duplicate.leave_type_id && b.year == duplicate.year)
+                .map(|b| b.id);
+            match existing.and_then(|id| self.balances.get_mut(&id)) {
+                Some(mut balance) => {
+                    balance.used_days += duplicate.used_days;
+                    balance.pending_days += duplicate.pending_days;
+                    balance.updated_at = Utc::now();
+                    drop(balance);
+                    self.balances.remove(&duplicate.id);
+                }
+                None => {
+                    if let Some(mut balance) = self.balances.get_mut(&duplicate.id) {
+                        balance.employee_id = to;
+                    }
+                }
+            }
+            moved += 1;
+        }
+        moved
+    }
+}
@@ -1,10 +1,25 @@
 //! Leave Management Module
 //!
 //! Nigerian leave management with standard leave types, balances, and request workflow.
+//! Approvals and rejections are texted to the employee via `notifications`;
+//! unused days can be paid out at year-end via `encashment`. Departments
+//! can black out critical periods; only an admin override books leave
+//! through one. `history` keeps each employee's requests and balances and
+//! folds them together when duplicate records are merged.
 
 pub mod models;
 pub mod service;
 pub mod handlers;
+pub mod registry;
+pub mod accrual;
+pub mod notifications;
+pub mod encashment;
+pub mod history;
 
 pub use models::*;
 pub use service::LeaveService;
+pub use accrual::{AccrualPolicy, CarryoverRule, LeaveAccount, LeaveCategory, ProtectedLeave, SeparationReason};
+pub use encashment::{EncashmentPolicies, EncashmentPolicy, LeaveEncashment};
+pub use history::LeaveHistory;
+pub use notifications::{LeaveNotifier, SmsContact};
+pub use registry::{AccrualRule, LeaveTypeRegistry, StatutoryLeave};
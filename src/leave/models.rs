@@ -7,6 +7,8 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::validation::{Validate, ValidationErrors, Validator};
+
 /// Leave Type
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaveType {
@@ -81,32 +83,30 @@ pub struct LeaveBalance {
     pub used_days: Decimal,
     pub pending_days: Decimal,
     pub carried_over: Decimal,
+    /// Days paid out instead of taken
+    #[serde(default)]
+    pub encashed_days: Decimal,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 impl LeaveBalance {
     pub fn available_days(&self) -> Decimal {
-        self.entitled_days + self.carried_over - self.used_days - self.pending_days
+        self.entitled_days + self.carried_over - self.used_days - self.pending_days - self.encashed_days
     }
 }
 
 /// Leave Request Status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LeaveRequestStatus {
+    #[default]
     Pending,
     Approved,
     Rejected,
     Cancelled,
 }
 
-impl Default for LeaveRequestStatus {
-    fn default() -> Self {
-        Self::Pending
-    }
-}
-
 /// Leave Request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaveRequest {
@@ -161,6 +161,59 @@ pub struct CreateLeaveRequest {
     pub handover_notes: Option<String>,
 }
 
+impl Validate for CreateLeaveRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut v = Validator::new();
+        v.date_order("end_date", self.start_date, self.end_date);
+        v.check(
+            !self.half_day || self.start_date == self.end_date,
+            "half_day",
+            "multi_day_half_day",
+            "half_day requests must start and end on the same date",
+        );
+        if let Some(reason) = &self.reason {
+            v.max_length("reason", reason, 500);
+        }
+        if let Some(notes) = &self.handover_notes {
+            v.max_length("handover_notes", notes, 2000);
+        }
+        v.finish()
+    }
+}
+
+/// Dates a department may not take leave, e.g. year-end close
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlackoutPeriod {
+    pub id: Uuid,
+    pub department_id: Uuid,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub reason: String,
+}
+
+impl BlackoutPeriod {
+    pub fn new(department_id: Uuid, start: NaiveDate, end: NaiveDate, reason: impl Into<String>) -> Self {
+        Self { id: Uuid::new_v4(), department_id, start, end, reason: reason.into() }
+    }
+
+    /// Days of `start..=end` that fall inside the blackout
+    pub fn overlap(&self, start: NaiveDate, end: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let (from, to) = (start.max(self.start), end.min(self.end));
+        (from <= to).then_some((from, to))
+    }
+}
+
+/// Who a leave request is being created for
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LeaveApplicant<'a> {
+    /// Country whose statutory leave types the request is checked against
+    pub country_code: &'a str,
+    pub gender: Option<&'a str>,
+    pub department_id: Option<Uuid>,
+    /// Set when an admin (`LeaveAdmin`) books leave through a blackout
+    pub blackout_override: bool,
+}
+
 /// Request to approve/reject leave
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaveDecisionRequest {
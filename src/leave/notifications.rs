@@ -0,0 +1,84 @@
+//! Leave Decision Notifications
+//!
+//! Texts the employee when a request is approved or rejected, in their
+//! language, through the configured `SmsSender`. Sending is best effort:
+//! a gateway failure is logged and never undoes the decision.
+
+use std::sync::Arc;
+use dashmap::DashMap;
+use uuid::Uuid;
+
+use super::models::LeaveRequest;
+use crate::sms::{NoopSmsSender, SmsSender, SmsTemplateRegistry};
+
+/// Where and in which language to text an employee
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmsContact {
+    pub phone: String,
+    /// SMS template language, e.g. "en", "fr", "ha"
+    pub language: String,
+}
+
+/// Sends leave decision SMS
+#[derive(Clone)]
+pub struct LeaveNotifier {
+    sender: Arc<dyn SmsSender>,
+    templates: Arc<SmsTemplateRegistry>,
+    // In real implementation, read from the employee profile
+    contacts: Arc<DashMap<Uuid, SmsContact>>,
+}
+
+impl std::fmt::Debug for LeaveNotifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LeaveNotifier").field("contacts", &self.contacts.len()).finish_non_exhaustive()
+    }
+}
+
+impl Default for LeaveNotifier {
+    fn default() -> Self {
+        Self::new(Arc::new(NoopSmsSender))
+    }
+}
+
+impl LeaveNotifier {
+    pub fn new(sender: Arc<dyn SmsSender>) -> Self {
+        Self {
+            sender,
+            templates: Arc::new(SmsTemplateRegistry::new()),
+            contacts: Arc::new(DashMap::new()),
+        }
+    }
+
+    pub fn set_contact(&self, employee_id: Uuid, contact: SmsContact) {
+        self.contacts.insert(employee_id, contact);
+    }
+
+    pub fn leave_approved(&self, request: &LeaveRequest) {
+        self.send(request, |templates, language, leave_type| {
+            templates.format_leave_approved_sms(
+                language,
+                leave_type,
+                &request.start_date.format("%Y-%m-%d").to_string(),
+                &request.end_date.format("%Y-%m-%d").to_string(),
+            )
+        });
+    }
+
+    pub fn leave_rejected(&self, request: &LeaveRequest) {
+        self.send(request, |templates, language, leave_type| {
+            templates.format_leave_rejected_sms(language, leave_type, request.rejection_reason.as_deref().unwrap_or("-"))
+        });
+    }
+
+    fn send(&self, request: &LeaveRequest, render: impl FnOnce(&SmsTemplateRegistry, &str, &str) -> String) {
+        let Some(contact) = self.contacts.get(&request.employee_id).map(|c| c.clone()) else {
+            return;
+        };
+        let leave_type = request.leave_type_name.as_deref().unwrap_or_default();
+        let message = render(&self.templates, &contact.language, leave_type);
+
+        if let Err(e) = self.sender.send(&contact.phone, &message) {
+            tracing::warn!(leave_request_id = %request.id, error = %e, "leave decision SMS not sent");
+        }
+    }
+}
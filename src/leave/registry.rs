@@ -0,0 +1,257 @@
+//! Statutory Leave Registry
+//!
+//! Per-country statutory leave entitlements: minimum days, paid/unpaid,
+//! and the accrual rule applied when balances are initialised.
+
+use std::collections::HashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::models::{LeaveType, StandardLeaveType};
+use super::service::LeaveError;
+
+/// How an entitlement is earned over the leave year
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccrualRule {
+    /// Full entitlement available from the start of the year
+    UpFront,
+    /// Earned pro-rata per completed month of service
+    Monthly,
+    /// Nothing until the qualifying service is completed, then full entitlement
+    AfterQualifyingService { months: u32 },
+    /// Granted per qualifying event (birth, bereavement) rather than per year
+    PerEvent,
+}
+
+/// Statutory definition of one leave type in one country
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatutoryLeave {
+    pub leave_type: StandardLeaveType,
+    pub statutory_min_days: i32,
+    pub is_paid: bool,
+    pub accrual: AccrualRule,
+    pub legal_reference: &'static str,
+}
+
+impl StatutoryLeave {
+    fn new(
+        leave_type: StandardLeaveType,
+        statutory_min_days: i32,
+        is_paid: bool,
+        accrual: AccrualRule,
+        legal_reference: &'static str,
+    ) -> Self {
+        Self { leave_type, statutory_min_days, is_paid, accrual, legal_reference }
+    }
+}
+
+/// Leave types permitted per country with their statutory minimums
+#[derive(Debug, Clone)]
+pub struct LeaveTypeRegistry {
+    countries: HashMap<String, Vec<StatutoryLeave>>,
+}
+
+impl LeaveTypeRegistry {
+    pub fn new() -> Self {
+        use AccrualRule::*;
+        use StandardLeaveType::*;
+
+        let mut countries = HashMap::new();
+
+        // Nigeria - Labour Act Cap L1 LFN 2004
+        countries.insert("NG".to_string(), vec![
+            StatutoryLeave::new(Annual, 6, true, AfterQualifyingService { months: 12 }, "Labour Act s.18"),
+            StatutoryLeave::new(Sick, 12, true, UpFront, "Labour Act s.16"),
+            StatutoryLeave::new(Maternity, 84, true, PerEvent, "Labour Act s.54"),
+            // No Labour Act provision; employer policy (public service grants 14 days)
+            StatutoryLeave::new(Paternity, 0, true, PerEvent, "Employer policy"),
+            StatutoryLeave::new(Compassionate, 0, true, PerEvent, "Employer policy"),
+            StatutoryLeave::new(Study, 0, false, UpFront, "Employer policy"),
+            StatutoryLeave::new(LeaveWithoutPay, 0, false, UpFront, "Employer policy"),
+        ]);
+
+        // Ghana - Labour Act 2003 (Act 651)
+        countries.insert("GH".to_string(), vec![
+            StatutoryLeave::new(Annual, 15, true, AfterQualifyingService { months: 12 }, "Act 651 s.20"),
+            StatutoryLeave::new(Sick, 0, true, UpFront, "Employer policy"),
+            StatutoryLeave::new(Maternity, 84, true, PerEvent, "Act 651 s.57"),
+            StatutoryLeave::new(LeaveWithoutPay, 0, false, UpFront, "Employer policy"),
+        ]);
+
+        // Kenya - Employment Act 2007
+        countries.insert("KE".to_string(), vec![
+            StatutoryLeave::new(Annual, 21, true, Monthly, "Employment Act s.28"),
+            StatutoryLeave::new(Sick, 14, true, AfterQualifyingService { months: 2 }, "Employment Act s.30"),
+            StatutoryLeave::new(Maternity, 90, true, PerEvent, "Employment Act s.29(1)"),
+            StatutoryLeave::new(Paternity, 14, true, PerEvent, "Employment Act s.29(8)"),
+            StatutoryLeave::new(LeaveWithoutPay, 0, false, UpFront, "Employer policy"),
+        ]);
+
+        // South Africa - Basic Conditions of Employment Act 1997
+        countries.insert("ZA".to_string(), vec![
+            StatutoryLeave::new(Annual, 15, true, Monthly, "BCEA s.20"),
+            // 30 days per 36-month cycle, 1 day per 26 worked in the first 6 months
+            StatutoryLeave::new(Sick, 30, true, UpFront, "BCEA s.22"),
+            // Employer need not pay; claimed from UIF
+            StatutoryLeave::new(Maternity, 120, false, PerEvent, "BCEA s.25"),
+            StatutoryLeave::new(Paternity, 10, false, PerEvent, "BCEA s.25A"),
+            StatutoryLeave::new(Compassionate, 3, true, PerEvent, "BCEA s.27"),
+            StatutoryLeave::new(LeaveWithoutPay, 0, false, UpFront, "Employer policy"),
+        ]);
+
+        Self { countries }
+    }
+
+    /// Statutory leave types defined for a country
+    pub fn entitlements(&self, country_code: &str) -> Option<&[StatutoryLeave]> {
+        self.countries.get(country_code).map(|v| v.as_slice())
+    }
+
+    /// Look up a leave type by its code (e.g. "annual") for a country
+    pub fn get(&self, country_code: &str, code: &str) -> Result<&StatutoryLeave, LeaveError> {
+        self.entitlements(country_code)
+            .ok_or_else(|| LeaveError::UnsupportedCountry(country_code.to_string()))?
+            .iter()
+            .find(|s| s.leave_type.code() == code)
+            .ok_or_else(|| LeaveError::UnknownLeaveType(code.to_string()))
+    }
+
+    /// Check a tenant-configured leave type against the country's statutory rules
+    pub fn validate_leave_type(
+        &self,
+        country_code: &str,
+        leave_type: &LeaveType,
+    ) -> Result<&StatutoryLeave, LeaveError> {
+        let statutory = self.get(country_code, &leave_type.code)?;
+
+        if leave_type.default_days < statutory.statutory_min_days {
+            return Err(LeaveError::BelowStatutoryMinimum {
+                code: leave_type.code.clone(),
+                configured: leave_type.default_days,
+                minimum: statutory.statutory_min_days,
+            });
+        }
+        if statutory.is_paid && statutory.statutory_min_days > 0 && !leave_type.is_paid {
+            return Err(LeaveError::Validation(format!(
+                "{} leave must be paid ({})",
+                leave_type.code, statutory.legal_reference
+            )));
+        }
+
+        Ok(statutory)
+    }
+
+    /// Days accrued for the year under the leave type's accrual rule
+    ///
+    /// `months_of_service` is completed months of continuous service at the
+    /// accrual date; for `Monthly` it is capped at 12 within the leave year.
+    pub fn accrued_days(
+        &self,
+        country_code: &str,
+        leave_type: &LeaveType,
+        months_of_service: u32,
+    ) -> Result<Decimal, LeaveError> {
+        let statutory = self.validate_leave_type(country_code, leave_type)?;
+        let entitlement = Decimal::from(leave_type.default_days);
+
+        Ok(match statutory.accrual {
+            AccrualRule::UpFront | AccrualRule::PerEvent => entitlement,
+            AccrualRule::Monthly => {
+                (entitlement * Decimal::from(months_of_service.min(12)) / Decimal::from(12)).round_dp(2)
+            }
+            AccrualRule::AfterQualifyingService { months } => {
+                if months_of_service >= months { entitlement } else { Decimal::ZERO }
+            }
+        })
+    }
+
+    /// Countries with statutory leave definitions
+    pub fn supported_countries(&self) -> Vec<&str> {
+        let mut codes: Vec<&str> = self.countries.keys().map(|c| c.as_str()).collect();
+        codes.sort();
+        codes
+    }
+}
+
+impl Default for LeaveTypeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    fn leave_type(code: &str, default_days: i32) -> LeaveType {
+        LeaveType {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            name: code.to_string(),
+            code: code.to_string(),
+            default_days,
+            is_paid: true,
+            requires_approval: true,
+            requires_document: false,
+            document_threshold_days: 0,
+            max_carry_over: 5,
+            gender_restriction: None,
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_nigeria_annual_minimum_enforced() {
+        let registry = LeaveTypeRegistry::new();
+
+        let result = registry.validate_leave_type("NG", &leave_type("annual", 5));
+        assert!(matches!(
+            result,
+            Err(LeaveError::BelowStatutoryMinimum { minimum: 6, configured: 5, .. })
+        ));
+
+        let statutory = registry.validate_leave_type("NG", &leave_type("annual", 21)).unwrap();
+        assert_eq!(statutory.statutory_min_days, 6);
+        assert_eq!(statutory.accrual, AccrualRule::AfterQualifyingService { months: 12 });
+    }
+
+    #[test]
+    fn test_unknown_leave_type_rejected() {
+        let registry = LeaveTypeRegistry::new();
+
+        let result = registry.validate_leave_type("NG", &leave_type("sabbatical", 30));
+        assert!(matches!(result, Err(LeaveError::UnknownLeaveType(code)) if code == "sabbatical"));
+
+        let result = registry.get("XX", "annual");
+        assert!(matches!(result, Err(LeaveError::UnsupportedCountry(_))));
+    }
+
+    #[test]
+    fn test_accrual_rules() {
+        let registry = LeaveTypeRegistry::new();
+
+        // Nigeria: nothing before 12 months of service
+        assert_eq!(registry.accrued_days("NG", &leave_type("annual", 21), 6).unwrap(), Decimal::ZERO);
+        assert_eq!(registry.accrued_days("NG", &leave_type("annual", 21), 12).unwrap(), dec!(21));
+
+        // Kenya: pro-rata by month
+        assert_eq!(registry.accrued_days("KE", &leave_type("annual", 21), 6).unwrap(), dec!(10.5));
+
+        // Sick leave is available up front
+        assert_eq!(registry.accrued_days("NG", &leave_type("sick", 12), 0).unwrap(), dec!(12));
+    }
+
+    #[test]
+    fn test_unpaid_statutory_maternity_in_south_africa() {
+        let registry = LeaveTypeRegistry::new();
+        let maternity = registry.get("ZA", "maternity").unwrap();
+        assert!(!maternity.is_paid);
+        assert_eq!(maternity.statutory_min_days, 120);
+    }
+}
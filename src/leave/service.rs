@@ -3,12 +3,18 @@
 //! Business logic for leave requests, balances, and approvals.
 
 use std::collections::HashSet;
+use std::sync::Arc;
 use chrono::{Datelike, NaiveDate, Utc};
+use dashmap::DashMap;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use uuid::Uuid;
 
+use super::accrual::{AccrualPolicy, LeaveAccount};
+use super::encashment::{EncashmentPolicies, LeaveEncashment};
 use super::models::*;
+use super::notifications::LeaveNotifier;
+use super::registry::LeaveTypeRegistry;
 
 /// Leave service errors
 #[derive(Debug, thiserror::Error)]
@@ -19,6 +25,15 @@ pub enum LeaveError {
     #[error("Leave type not found: {0}")]
     LeaveTypeNotFound(Uuid),
     
+    #[error("Unknown leave type for country: {0}")]
+    UnknownLeaveType(String),
+    
+    #[error("No statutory leave rules for country: {0}")]
+    UnsupportedCountry(String),
+    
+    #[error("{code} leave of {configured} days is below the statutory minimum of {minimum}")]
+    BelowStatutoryMinimum { code: String, configured: i32, minimum: i32 },
+    
     #[error("Insufficient leave balance: available {available}, requested {requested}")]
     InsufficientBalance { available: Decimal, requested: Decimal },
     
@@ -40,6 +55,21 @@ pub enum LeaveError {
     #[error("This leave type is restricted to {0} employees")]
     GenderRestricted(String),
     
+    #[error("Employee is not eligible for protected leave")]
+    NotEligibleForProtectedLeave,
+    
+    #[error("Protected leave exhausted: {remaining_days} days remaining, {requested} requested")]
+    ProtectedLeaveExhausted { remaining_days: Decimal, requested: Decimal },
+    
+    #[error("Employee is on protected leave on {0}")]
+    OnProtectedLeave(NaiveDate),
+    
+    #[error("Encashment cap exceeded: {already_encashed} of {cap} days encashed this year, {requested} requested")]
+    EncashmentCapExceeded { cap: Decimal, already_encashed: Decimal, requested: Decimal },
+    
+    #[error("Leave from {from} to {to} falls in a blackout period: {reason}")]
+    InBlackoutPeriod { from: NaiveDate, to: NaiveDate, reason: String },
+    
     #[error("Validation error: {0}")]
     Validation(String),
 }
@@ -48,11 +78,131 @@ pub enum LeaveError {
 #[derive(Debug, Clone, Default)]
 pub struct LeaveService {
     // In real implementation, would have database pool
+    notifier: LeaveNotifier,
+    encashment: EncashmentPolicies,
+    // In real implementation, backed by the leave_blackout_periods table
+    blackouts: Arc<DashMap<Uuid, BlackoutPeriod>>,
+    registry: LeaveTypeRegistry,
+    // In real implementation, backed by the leave_accounts table
+    accounts: Arc<DashMap<Uuid, LeaveAccount>>,
 }
 
 impl LeaveService {
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Text employees approval and rejection decisions through `notifier`
+    pub fn with_notifier(mut self, notifier: LeaveNotifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Encashment caps and daily-rate basis per country
+    pub fn with_encashment_policies(mut self, policies: EncashmentPolicies) -> Self {
+        self.encashment = policies;
+        self
+    }
+
+    /// Statutory leave types requests are checked against
+    pub fn with_leave_type_registry(mut self, registry: LeaveTypeRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Open or replace an employee's accrual account
+    pub fn open_account(&self, account: LeaveAccount) {
+        self.accounts.insert(account.employee_id, account);
+    }
+
+    pub fn account(&self, employee_id: Uuid) -> Option<LeaveAccount> {
+        self.accounts.get(&employee_id).map(|a| a.clone())
+    }
+
+    /// Credit one month of accrual under each policy to every open
+    /// account; returns the number of accounts credited
+    pub fn accrue_month(&self, policies: &[AccrualPolicy]) -> usize {
+        let mut credited = 0;
+        for mut account in self.accounts.iter_mut() {
+            for policy in policies {
+                account.accrue_month(policy);
+            }
+            credited += 1;
+        }
+        credited
+    }
+
+    pub fn add_blackout(&self, period: BlackoutPeriod) {
+        self.blackouts.insert(period.id, period);
+    }
+
+    pub fn remove_blackout(&self, id: Uuid) -> Option<BlackoutPeriod> {
+        self.blackouts.remove(&id).map(|(_, period)| period)
+    }
+
+    /// A department's blackout periods, earliest first
+    pub fn blackouts_for(&self, department_id: Uuid) -> Vec<BlackoutPeriod> {
+        let mut periods: Vec<BlackoutPeriod> =
+            self.blackouts.iter().filter(|p| p.department_id == department_id).map(|p| p.clone()).collect();
+        periods.sort_by_key(|p| p.start);
+        periods
+    }
+
+    /// Reject leave whose dates reach into one of the department's
+    /// blackout periods, naming the days that overlap
+    pub fn check_blackouts(
+        &self,
+        department_id: Option<Uuid>,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<(), LeaveError> {
+        let Some(department_id) = department_id else { return Ok(()) };
+        match self.blackouts_for(department_id).into_iter().find_map(|p| p.overlap(start, end).map(|o| (o, p.reason))) {
+            Some(((from, to), reason)) => Err(LeaveError::InBlackoutPeriod { from, to, reason }),
+            None => Ok(()),
+        }
+    }
+
+    /// Pay out `days` of unused leave from `balance` at the employee's
+    /// daily rate, within the country's yearly encashment cap
+    pub fn encash(
+        &self,
+        employee_id: Uuid,
+        days: Decimal,
+        as_of: NaiveDate,
+        country_code: &str,
+        annual_pay: Decimal,
+        balance: &mut LeaveBalance,
+    ) -> Result<LeaveEncashment, LeaveError> {
+        if balance.employee_id != employee_id {
+            return Err(LeaveError::Validation("Leave balance belongs to another employee".to_string()));
+        }
+        if days <= Decimal::ZERO {
+            return Err(LeaveError::Validation("Days to encash must be positive".to_string()));
+        }
+        if days > balance.available_days() {
+            return Err(LeaveError::InsufficientBalance { available: balance.available_days(), requested: days });
+        }
+        let policy = self.encashment.for_country(country_code);
+        if balance.encashed_days + days > policy.max_days_per_year {
+            return Err(LeaveError::EncashmentCapExceeded {
+                cap: policy.max_days_per_year,
+                already_encashed: balance.encashed_days,
+                requested: days,
+            });
+        }
+
+        balance.encashed_days += days;
+        balance.updated_at = Utc::now();
+
+        Ok(LeaveEncashment::new(
+            employee_id,
+            balance.leave_type_id,
+            balance.year,
+            days,
+            policy.daily_rate(annual_pay),
+            as_of,
+        ))
     }
 
     /// Calculate working days between two dates, excluding weekends and public holidays
@@ -133,10 +283,11 @@ impl LeaveService {
         }
 
         // Check document requirement
-        if leave_type.requires_document && days > Decimal::from(leave_type.document_threshold_days) {
-            if request.reason.is_none() {
-                return Err(LeaveError::DocumentRequired(leave_type.document_threshold_days));
-            }
+        if leave_type.requires_document
+            && days > Decimal::from(leave_type.document_threshold_days)
+            && request.reason.is_none()
+        {
+            return Err(LeaveError::DocumentRequired(leave_type.document_threshold_days));
         }
 
         // Check relief officer requirement (for leave > 3 days)
@@ -147,24 +298,34 @@ impl LeaveService {
         Ok(days)
     }
 
-    /// Create a leave request
+    /// Create a leave request. Where the registry has a table for the
+    /// applicant's country, the leave type must be one the country
+    /// recognises, configured to its statutory minimum. Dates in a
+    /// blackout period for the applicant's department are refused unless an
+    /// admin overrides.
     pub fn create_leave_request(
         &self,
         employee_id: Uuid,
         request: CreateLeaveRequest,
         leave_type: &LeaveType,
         balance: &LeaveBalance,
-        employee_gender: Option<&str>,
+        applicant: LeaveApplicant<'_>,
         public_holidays: &[PublicHoliday],
     ) -> Result<LeaveRequest, LeaveError> {
+        if self.registry.entitlements(applicant.country_code).is_some() {
+            self.registry.validate_leave_type(applicant.country_code, leave_type)?;
+        }
         // Validate and calculate days
         let days = self.validate_leave_request(
             &request,
             leave_type,
             balance,
-            employee_gender,
+            applicant.gender,
             public_holidays,
         )?;
+        if !applicant.blackout_override {
+            self.check_blackouts(applicant.department_id, request.start_date, request.end_date)?;
+        }
 
         let now = Utc::now();
         
@@ -220,6 +381,7 @@ impl LeaveService {
         request.approved_at = Some(Utc::now());
         request.updated_at = Utc::now();
 
+        self.notifier.leave_approved(request);
         Ok(())
     }
 
@@ -246,6 +408,7 @@ impl LeaveService {
         request.rejection_reason = reason;
         request.updated_at = Utc::now();
 
+        self.notifier.leave_rejected(request);
         Ok(())
     }
 
@@ -317,12 +480,33 @@ impl LeaveService {
                 used_days: Decimal::ZERO,
                 pending_days: Decimal::ZERO,
                 carried_over,
+                encashed_days: Decimal::ZERO,
                 created_at: now,
                 updated_at: now,
             }
         }).collect()
     }
 
+    /// Initialize balances for a new year using statutory accrual rules
+    ///
+    /// Every leave type is validated against the country's registry first, so
+    /// an unknown or sub-statutory type fails the whole batch.
+    pub fn accrue_statutory_balances(
+        &self,
+        employee_id: Uuid,
+        year: i32,
+        country_code: &str,
+        months_of_service: u32,
+        leave_types: &[LeaveType],
+        registry: &LeaveTypeRegistry,
+    ) -> Result<Vec<LeaveBalance>, LeaveError> {
+        let mut balances = self.initialize_annual_balances(employee_id, year, leave_types, None);
+        for (balance, lt) in balances.iter_mut().zip(leave_types) {
+            balance.entitled_days = registry.accrued_days(country_code, lt, months_of_service)?;
+        }
+        Ok(balances)
+    }
+
     /// Get leave balance summary
     pub fn get_balance_summary(
         &self,
@@ -370,6 +554,10 @@ mod tests {
         }
     }
 
+    fn nigerian() -> LeaveApplicant<'static> {
+        LeaveApplicant { country_code: "NG", ..Default::default() }
+    }
+
     fn create_test_balance(leave_type_id: Uuid, employee_id: Uuid) -> LeaveBalance {
         LeaveBalance {
             id: Uuid::new_v4(),
@@ -381,6 +569,7 @@ mod tests {
             used_days: dec!(5),
             pending_days: dec!(0),
             carried_over: dec!(3),
+            encashed_days: Decimal::ZERO,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -443,7 +632,7 @@ mod tests {
             request,
             &leave_type,
             &balance,
-            None,
+            nigerian(),
             &[],
         );
 
@@ -453,6 +642,59 @@ mod tests {
         assert_eq!(leave_request.days_requested, dec!(3));
     }
 
+    #[test]
+    fn test_leave_type_checked_against_country() {
+        let service = LeaveService::new();
+        let employee_id = Uuid::new_v4();
+        let request = |leave_type: &LeaveType| CreateLeaveRequest {
+            leave_type_id: leave_type.id,
+            start_date: NaiveDate::from_ymd_opt(2024, 6, 3).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2024, 6, 4).unwrap(),
+            half_day: false,
+            reason: None,
+            relief_officer_id: None,
+            handover_notes: None,
+        };
+        let create = |leave_type: &LeaveType, applicant| {
+            let balance = create_test_balance(leave_type.id, employee_id);
+            service.create_leave_request(employee_id, request(leave_type), leave_type, &balance, applicant, &[])
+        };
+
+        let sabbatical = LeaveType { code: "sabbatical".to_string(), ..create_test_leave_type() };
+        assert!(matches!(create(&sabbatical, nigerian()), Err(LeaveError::UnknownLeaveType(code)) if code == "sabbatical"));
+        // Kenya has no statutory study leave
+        let study = LeaveType { code: "study".to_string(), is_paid: false, ..create_test_leave_type() };
+        assert!(create(&study, nigerian()).is_ok());
+        let kenyan = LeaveApplicant { country_code: "KE", ..Default::default() };
+        assert!(matches!(create(&study, kenyan), Err(LeaveError::UnknownLeaveType(_))));
+        let short = LeaveType { default_days: 10, ..create_test_leave_type() };
+        assert!(matches!(create(&short, kenyan), Err(LeaveError::BelowStatutoryMinimum { minimum: 21, .. })));
+    }
+
+    #[test]
+    fn test_country_without_statutory_table_not_checked() {
+        let service = LeaveService::new();
+        let employee_id = Uuid::new_v4();
+        let sabbatical = LeaveType { code: "sabbatical".to_string(), default_days: 5, ..create_test_leave_type() };
+        let balance = create_test_balance(sabbatical.id, employee_id);
+        let request = CreateLeaveRequest {
+            leave_type_id: sabbatical.id,
+            start_date: NaiveDate::from_ymd_opt(2024, 6, 3).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2024, 6, 4).unwrap(),
+            half_day: false,
+            reason: None,
+            relief_officer_id: None,
+            handover_notes: None,
+        };
+
+        for applicant in [LeaveApplicant { country_code: "US", ..Default::default() }, LeaveApplicant::default()] {
+            let created = service
+                .create_leave_request(employee_id, request.clone(), &sabbatical, &balance, applicant, &[])
+                .unwrap();
+            assert_eq!(created.days_requested, dec!(2));
+        }
+    }
+
     #[test]
     fn test_insufficient_balance() {
         let service = LeaveService::new();
@@ -476,7 +718,7 @@ mod tests {
             request,
             &leave_type,
             &balance,
-            None,
+            nigerian(),
             &[],
         );
 
@@ -484,14 +726,53 @@ mod tests {
     }
 
     #[test]
-    fn test_approve_leave() {
+    fn test_blackout_rejects_overlapping_days() {
         let service = LeaveService::new();
         let leave_type = create_test_leave_type();
         let employee_id = Uuid::new_v4();
-        let mut balance = create_test_balance(leave_type.id, employee_id);
-        balance.pending_days = dec!(3); // Simulate pending request
+        let balance = create_test_balance(leave_type.id, employee_id);
+        let finance = Uuid::new_v4();
+        service.add_blackout(BlackoutPeriod::new(
+            finance,
+            NaiveDate::from_ymd_opt(2024, 12, 23).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+            "Year-end close",
+        ));
+
+        // Thursday 19th to Tuesday 24th: only the 23rd and 24th are blacked out
+        let request = CreateLeaveRequest {
+            leave_type_id: leave_type.id,
+            start_date: NaiveDate::from_ymd_opt(2024, 12, 19).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2024, 12, 24).unwrap(),
+            half_day: false,
+            reason: None,
+            relief_officer_id: Some(Uuid::new_v4()),
+            handover_notes: None,
+        };
+        let applicant = LeaveApplicant { department_id: Some(finance), ..nigerian() };
+
+        let result = service.create_leave_request(employee_id, request.clone(), &leave_type, &balance, applicant, &[]);
+        match result {
+            Err(LeaveError::InBlackoutPeriod { from, to, reason }) => {
+                assert_eq!(from, NaiveDate::from_ymd_opt(2024, 12, 23).unwrap());
+                assert_eq!(to, NaiveDate::from_ymd_opt(2024, 12, 24).unwrap());
+                assert_eq!(reason, "Year-end close");
+            }
+            other => panic!("expected blackout rejection, got {:?}", other),
+        }
+
+        // Another department is unaffected
+        let sales = LeaveApplicant { department_id: Some(Uuid::new_v4()), ..nigerian() };
+        assert!(service.create_leave_request(employee_id, request.clone(), &leave_type, &balance, sales, &[]).is_ok());
 
-        let mut request = LeaveRequest {
+        // An admin override books it anyway
+        let overridden = LeaveApplicant { blackout_override: true, ..applicant };
+        let leave = service.create_leave_request(employee_id, request, &leave_type, &balance, overridden, &[]).unwrap();
+        assert_eq!(leave.days_requested, dec!(4));
+    }
+
+    fn create_pending_request(leave_type: &LeaveType, employee_id: Uuid) -> LeaveRequest {
+        LeaveRequest {
             id: Uuid::new_v4(),
             employee_id,
             employee_name: None,
@@ -513,7 +794,18 @@ mod tests {
             rejection_reason: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
-        };
+        }
+    }
+
+    #[test]
+    fn test_approve_leave() {
+        let service = LeaveService::new();
+        let leave_type = create_test_leave_type();
+        let employee_id = Uuid::new_v4();
+        let mut balance = create_test_balance(leave_type.id, employee_id);
+        balance.pending_days = dec!(3); // Simulate pending request
+
+        let mut request = create_pending_request(&leave_type, employee_id);
 
         let approver_id = Uuid::new_v4();
         let result = service.approve_leave(&mut request, &mut balance, approver_id);
@@ -523,4 +815,143 @@ mod tests {
         assert_eq!(balance.pending_days, dec!(0));
         assert_eq!(balance.used_days, dec!(8)); // 5 + 3
     }
+
+    #[derive(Default)]
+    struct RecordingSender {
+        fail: bool,
+        sent: std::sync::Mutex<Vec<(String, String)>>,
+    }
+
+    impl crate::sms::SmsSender for RecordingSender {
+        fn send(&self, phone: &str, message: &str) -> Result<(), crate::sms::SmsError> {
+            self.sent.lock().unwrap().push((phone.to_string(), message.to_string()));
+            if self.fail {
+                return Err(crate::sms::SmsError::Gateway("503 from gateway".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    fn notifying_service(sender: std::sync::Arc<RecordingSender>, employee_id: Uuid, language: &str) -> LeaveService {
+        let notifier = LeaveNotifier::new(sender);
+        notifier.set_contact(employee_id, crate::leave::SmsContact {
+            phone: "+2348012345678".to_string(),
+            language: language.to_string(),
+        });
+        LeaveService::new().with_notifier(notifier)
+    }
+
+    #[test]
+    fn test_approval_sends_localized_sms() {
+        let leave_type = create_test_leave_type();
+        let employee_id = Uuid::new_v4();
+        let sender = std::sync::Arc::new(RecordingSender::default());
+        let service = notifying_service(sender.clone(), employee_id, "fr");
+        let mut balance = create_test_balance(leave_type.id, employee_id);
+        balance.pending_days = dec!(3);
+        let mut request = create_pending_request(&leave_type, employee_id);
+
+        service.approve_leave(&mut request, &mut balance, Uuid::new_v4()).unwrap();
+
+        let sent = sender.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, "+2348012345678");
+        assert_eq!(
+            sent[0].1,
+            "OpenSASE: Votre congé Annual Leave du 2024-06-03 au 2024-06-05 a été APPROUVÉ."
+        );
+    }
+
+    #[test]
+    fn test_rejection_sends_sms_and_survives_gateway_failure() {
+        let leave_type = create_test_leave_type();
+        let employee_id = Uuid::new_v4();
+        let sender = std::sync::Arc::new(RecordingSender { fail: true, ..Default::default() });
+        let service = notifying_service(sender.clone(), employee_id, "sw");
+        let mut balance = create_test_balance(leave_type.id, employee_id);
+        balance.pending_days = dec!(3);
+        let mut request = create_pending_request(&leave_type, employee_id);
+
+        let result = service.reject_leave(&mut request, &mut balance, Uuid::new_v4(), Some("Peak season".to_string()));
+
+        assert!(result.is_ok());
+        assert_eq!(request.status, LeaveRequestStatus::Rejected);
+        assert_eq!(balance.pending_days, dec!(0));
+        assert_eq!(
+            sender.sent.lock().unwrap()[0].1,
+            "OpenSASE: Ombi la likizo LIMEKATALIWA. Sababu: Peak season"
+        );
+    }
+
+    #[test]
+    fn test_accrue_statutory_balances() {
+        let service = LeaveService::new();
+        let registry = LeaveTypeRegistry::new();
+        let annual = create_test_leave_type();
+
+        let balances = service
+            .accrue_statutory_balances(Uuid::new_v4(), 2024, "NG", 18, std::slice::from_ref(&annual), &registry)
+            .unwrap();
+        assert_eq!(balances[0].entitled_days, dec!(21));
+
+        let mut short = annual;
+        short.default_days = 4;
+        let result = service.accrue_statutory_balances(Uuid::new_v4(), 2024, "NG", 18, &[short], &registry);
+        assert!(matches!(result, Err(LeaveError::BelowStatutoryMinimum { .. })));
+    }
+
+    #[test]
+    fn test_accrue_month_credits_open_accounts() {
+        use crate::leave::{AccrualPolicy, LeaveAccount, LeaveCategory, ProtectedLeave};
+
+        let service = LeaveService::new();
+        let employee_id = Uuid::new_v4();
+        service.open_account(LeaveAccount::new(employee_id, 2026, ProtectedLeave::new(24, dec!(1800))));
+
+        let policies = [AccrualPolicy::default_pto(), AccrualPolicy::default_sick()];
+        assert_eq!(service.accrue_month(&policies), 1);
+        assert_eq!(service.accrue_month(&policies), 1);
+
+        let account = service.account(employee_id).unwrap();
+        assert_eq!(account.available(LeaveCategory::Pto), dec!(3.3334));
+        assert_eq!(account.available(LeaveCategory::Sick), dec!(2));
+    }
+
+    #[test]
+    fn test_encash_reduces_balance_and_pays_daily_rate() {
+        let service = LeaveService::new();
+        let employee_id = Uuid::new_v4();
+        let mut balance = create_test_balance(Uuid::new_v4(), employee_id);
+        let year_end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+
+        // ₦5.2m a year over 260 working days is ₦20,000 a day
+        let payout = service.encash(employee_id, dec!(8), year_end, "NG", dec!(5_200_000), &mut balance).unwrap();
+
+        assert_eq!(payout.daily_rate, dec!(20_000));
+        assert_eq!(payout.amount, dec!(160_000));
+        assert_eq!(balance.available_days(), dec!(11));
+        assert_eq!(payout.payroll_line()["leave_encashment"]["amount"], serde_json::json!(dec!(160_000)));
+    }
+
+    #[test]
+    fn test_encash_rejects_cap_and_balance_overruns() {
+        let employee_id = Uuid::new_v4();
+        let mut balance = create_test_balance(Uuid::new_v4(), employee_id);
+        let year_end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+
+        let service = LeaveService::new();
+        service.encash(employee_id, dec!(8), year_end, "NG", dec!(5_200_000), &mut balance).unwrap();
+        let result = service.encash(employee_id, dec!(3), year_end, "NG", dec!(5_200_000), &mut balance);
+        assert!(matches!(
+            result,
+            Err(LeaveError::EncashmentCapExceeded { cap, already_encashed, .. }) if cap == dec!(10) && already_encashed == dec!(8)
+        ));
+        assert_eq!(balance.available_days(), dec!(11));
+
+        let mut policies = EncashmentPolicies::new();
+        policies.set("NG", crate::leave::EncashmentPolicy { max_days_per_year: dec!(30), ..Default::default() });
+        let generous = LeaveService::new().with_encashment_policies(policies);
+        let result = generous.encash(employee_id, dec!(12), year_end, "NG", dec!(5_200_000), &mut balance);
+        assert!(matches!(result, Err(LeaveError::InsufficientBalance { .. })));
+    }
 }
@@ -14,6 +14,17 @@
 //! - **compliance**: NDPR compliance and audit logging
 //! - **auth**: JWT authentication and RBAC
 //! - **sms**: SMS/USSD fallback channels for emerging markets
+//! - **documents**: Employee document and photo attachments
+//! - **messaging**: NATS event publishing with JetStream acks
+//! - **self_service**: Employee profile self-service with HR approval
+//! - **time**: Clock punch import from time terminals
+//! - **integrations**: Roster exports and imports, and throttled outbound provider calls
+//! - **validation**: Request validation that reports every field error at once
+//! - **employees**: Employee hiring API with duplicate detection and rehires
+//! - **analytics**: k-anonymous workforce analytics for export
+//! - **features**: Per-tenant feature flags consulted by handlers
+//! - **config**: Server configuration validated at startup
+//! - **db**: Primary and read-replica pool routing
 //!
 //! ## Nigerian Compliance Features
 //!
@@ -44,6 +55,17 @@ pub mod auth;
 pub mod sms;
 pub mod ops;
 pub mod controller;
+pub mod documents;
+pub mod messaging;
+pub mod integrations;
+pub mod time;
+pub mod self_service;
+pub mod validation;
+pub mod employees;
+pub mod analytics;
+pub mod features;
+pub mod config;
+pub mod db;
 
 // Re-exports from domain
 pub use domain::aggregates::{Employee, EmployeeError, PayrollRun, PayrollError};
@@ -10,21 +10,30 @@
 //! - NDPR compliance
 
 use axum::{
+    middleware,
     routing::{get, post},
     Router,
     Json,
 };
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tower_http::cors::CorsLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 // Import modules from library
 use sase_hr::{
-    payroll::{self, PayrollService},
-    leave::{self, LeaveService},
-    auth::{Role, JwtService},
+    config::AppConfig,
+    compliance::{retention::{RetentionJob, RetentionPolicy}, AuditLogStore},
+    db::{DbPools, StaleReads},
+    payroll::{handlers, PayrollService, TaxConfigLoader},
+    leave::{AccrualPolicy, LeaveService},
+    auth::JwtService,
+    ops::{self, scheduler, JobScheduler, SchedulerConfig, SharedMetrics, TraceSampler},
 };
 
+/// A payroll run still processing after this long has lost its worker
+const STUCK_RUN_AFTER_HOURS: i64 = 2;
+
 /// Health check response
 #[derive(serde::Serialize)]
 struct HealthResponse {
@@ -97,25 +106,70 @@ async fn main() {
 
     tracing::info!("Starting OpenSASE HR API Server v{}", env!("CARGO_PKG_VERSION"));
 
+    // Refuse to start on bad configuration, listing every problem
+    let config = match AppConfig::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Primary pool, plus a replica for read-only endpoints when configured
+    let db = DbPools::from_config(&config);
+    match db.has_read_replica() {
+        true => tracing::info!(
+            "Database primary {}, read replica {}",
+            db.write().host(),
+            db.read(StaleReads::Tolerated).host()
+        ),
+        false => tracing::info!("Database primary {}, no read replica", db.write().host()),
+    }
+
     // Initialize services
-    let _payroll_service = PayrollService::new();
-    let _leave_service = LeaveService::new();
-    let _jwt_service = JwtService::new("your-secret-key".to_string());
+    let payroll_service = PayrollService::new();
+    let payroll_state = handlers::AppState {
+        payroll_service: payroll_service.clone(),
+        db,
+        ..Default::default()
+    };
+    let leave_service = LeaveService::new();
+    let retention = RetentionJob::new(RetentionPolicy::default(), AuditLogStore::new());
+    let _jwt_service = JwtService::new(config.jwt_secret.clone());
+    let metrics = SharedMetrics::default();
+    let sampler = Arc::new(TraceSampler::new(config.observability.trace_sample_rate));
+
+    // Tax tables from config files over the compiled defaults, reloaded on SIGHUP
+    let tax_config = TaxConfigLoader::new(&config.tax_config_dir);
+    reload_tax_config(&tax_config, &payroll_service);
+    #[cfg(unix)]
+    tokio::spawn(reload_tax_config_on_hangup(tax_config, payroll_service.clone()));
+
+    // Background jobs on the business timezone's calendar
+    let scheduler = JobScheduler::new(SchedulerConfig::default());
+    register_jobs(&scheduler, &payroll_state, &leave_service, &retention);
+    tokio::spawn(Arc::new(scheduler).run());
 
     // Build router
     let app = Router::new()
         // Health & Info
         .route("/health", get(health_check))
         .route("/api/info", get(api_info))
+        .route("/metrics", get(ops::metrics_handler))
+        .route("/api/v1/version", get(ops::version_handler))
         
         // API v1 routes (stubs - implement with database later)
         .route("/api/v1/payroll/tax/calculate", post(calculate_tax_preview))
+        .route("/api/v1/payroll/countries", get(handlers::list_supported_countries))
         
-        // CORS
-        .layer(CorsLayer::permissive());
+        // Request metrics, sampled tracing & CORS
+        .layer(middleware::from_fn_with_state(metrics.clone(), ops::track_metrics))
+        .layer(middleware::from_fn_with_state(sampler, ops::trace_requests))
+        .layer(CorsLayer::permissive())
+        .with_state(metrics);
 
     // Bind to address
-    let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
+    let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
     tracing::info!("Listening on http://{}", addr);
 
     // Start server
@@ -123,6 +177,91 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
+/// Register the recurring jobs with their real work
+fn register_jobs(
+    scheduler: &JobScheduler,
+    payroll: &handlers::AppState,
+    leave: &LeaveService,
+    retention: &RetentionJob,
+) {
+    let now = chrono::Utc::now();
+
+    let leave = leave.clone();
+    scheduler.register(scheduler::ACCRUAL_JOB, now, move |_| {
+        let leave = leave.clone();
+        async move {
+            let credited = leave.accrue_month(&[AccrualPolicy::default_pto(), AccrualPolicy::default_sick()]);
+            tracing::info!("Leave accrued for {} account(s)", credited);
+        }
+    });
+
+    let employees = payroll.employees.clone();
+    scheduler.register(scheduler::PENDING_CHANGES_JOB, now, move |at| {
+        let employees = employees.clone();
+        async move {
+            let applied = employees.write().expect("employee lock poisoned").apply_pending_changes(at.date_naive());
+            tracing::info!("Applied {} pending employee change(s)", applied);
+        }
+    });
+
+    let payroll_service = payroll.payroll_service.clone();
+    scheduler.register(scheduler::STUCK_RUN_RECOVERY_JOB, now, move |_| {
+        let payroll_service = payroll_service.clone();
+        async move {
+            let started_before = chrono::Utc::now() - chrono::Duration::hours(STUCK_RUN_AFTER_HOURS);
+            for run_id in payroll_service.recover_stuck_runs(started_before) {
+                tracing::warn!("Payroll run {} was stuck processing and has been failed", run_id);
+            }
+        }
+    });
+
+    let retention = retention.clone();
+    scheduler.register(scheduler::RETENTION_JOB, now, move |at| {
+        let retention = retention.clone();
+        async move {
+            for tenant_id in retention.tenants() {
+                let report = retention.run(tenant_id, at.date_naive());
+                tracing::info!(
+                    "Retention for tenant {}: {} anonymized, {} deleted, {} held",
+                    tenant_id,
+                    report.anonymized.len(),
+                    report.deleted.len(),
+                    report.held.len()
+                );
+            }
+        }
+    });
+}
+
+/// Apply the tax config directory, keeping the current tables on error
+fn reload_tax_config(loader: &TaxConfigLoader, payroll: &PayrollService) {
+    if !loader.dir().is_dir() {
+        tracing::info!("No tax config at {}; using compiled tax tables", loader.dir().display());
+        return;
+    }
+    match loader.reload_into(payroll.tax_tables()) {
+        Ok(count) => tracing::info!("Loaded {} tax config file(s) from {}", count, loader.dir().display()),
+        Err(e) => tracing::error!("Tax config not applied: {}", e),
+    }
+}
+
+#[cfg(unix)]
+async fn reload_tax_config_on_hangup(loader: TaxConfigLoader, payroll: PayrollService) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            tracing::warn!("SIGHUP tax config reload unavailable: {}", e);
+            return;
+        }
+    };
+    while hangup.recv().await.is_some() {
+        tracing::info!("SIGHUP received, reloading tax config");
+        reload_tax_config(&loader, &payroll);
+    }
+}
+
 /// Tax calculation preview endpoint
 async fn calculate_tax_preview(
     Json(request): Json<TaxCalculateRequest>,
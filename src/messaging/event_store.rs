@@ -0,0 +1,264 @@
+//! Event Store & Webhook Replay
+//!
+//! Every `DomainEvent` is appended to a durable log with a monotonic
+//! sequence number. Integrators that missed deliveries can page through the
+//! log (`GET /api/v1/events?since=`) or ask for a replay, which re-sends the
+//! events through the webhook pipeline flagged as replays. The delivery id
+//! is the same for the original send and any replay, so consumers can dedupe.
+
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::MessagingError;
+use crate::domain::events::DomainEvent;
+
+/// Event as stored in the log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredEvent {
+    pub sequence: u64,
+    pub tenant_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Append-only event log
+#[derive(Debug, Clone, Default)]
+pub struct EventStore {
+    // In real implementation, the event_store table with a BIGSERIAL sequence
+    events: Arc<RwLock<Vec<StoredEvent>>>,
+}
+
+impl EventStore {
+    pub const MAX_PAGE_SIZE: usize = 1000;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an event; sequences start at 1 and never repeat
+    pub fn append(&self, tenant_id: Uuid, event: &DomainEvent) -> Result<StoredEvent, MessagingError> {
+        let payload = serde_json::to_value(event).map_err(|e| MessagingError::Serialization(e.to_string()))?;
+
+        let mut events = self.events.write().unwrap();
+        let stored = StoredEvent {
+            sequence: events.len() as u64 + 1,
+            tenant_id,
+            event_type: event.event_type().to_string(),
+            payload,
+            recorded_at: Utc::now(),
+        };
+        events.push(stored.clone());
+        Ok(stored)
+    }
+
+    /// A tenant's events with a sequence greater than `since`, oldest first
+    pub fn since(&self, tenant_id: Uuid, since: u64, limit: usize) -> Vec<StoredEvent> {
+        let events = self.events.read().unwrap();
+        // Sequence n lives at index n - 1
+        let start = (since as usize).min(events.len());
+        events[start..]
+            .iter()
+            .filter(|e| e.tenant_id == tenant_id)
+            .take(limit.clamp(1, Self::MAX_PAGE_SIZE))
+            .cloned()
+            .collect()
+    }
+
+    /// Highest sequence among a tenant's events, or 0 before its first
+    pub fn latest_sequence(&self, tenant_id: Uuid) -> u64 {
+        let events = self.events.read().unwrap();
+        events.iter().rev().find(|e| e.tenant_id == tenant_id).map_or(0, |e| e.sequence)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// WEBHOOKS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Integrator webhook subscription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub url: String,
+    /// Event types to deliver; empty means all
+    pub event_types: Vec<String>,
+}
+
+impl WebhookSubscription {
+    pub fn wants(&self, event: &StoredEvent) -> bool {
+        event.tenant_id == self.tenant_id
+            && (self.event_types.is_empty() || self.event_types.iter().any(|t| t == &event.event_type))
+    }
+}
+
+/// Webhook request body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    /// Stable across the original delivery and any replays
+    pub delivery_id: String,
+    pub sequence: u64,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    /// Set when re-sent by `replay`
+    pub replay: bool,
+}
+
+/// HTTP side of the webhook pipeline
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    async fn send(&self, url: &str, delivery: &WebhookDelivery) -> Result<(), MessagingError>;
+}
+
+/// Outcome of a replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaySummary {
+    pub subscription_id: Uuid,
+    pub delivered: usize,
+    /// Highest sequence re-sent; pass as `since` to resume
+    pub last_sequence: Option<u64>,
+}
+
+/// Delivers stored events to webhook subscriptions
+pub struct WebhookDispatcher {
+    store: EventStore,
+    sender: Arc<dyn WebhookSender>,
+}
+
+impl WebhookDispatcher {
+    pub fn new(store: EventStore, sender: Arc<dyn WebhookSender>) -> Self {
+        Self { store, sender }
+    }
+
+    fn delivery(subscription: &WebhookSubscription, event: &StoredEvent, replay: bool) -> WebhookDelivery {
+        WebhookDelivery {
+            delivery_id: format!("{}:{}", subscription.id, event.sequence),
+            sequence: event.sequence,
+            event_type: event.event_type.clone(),
+            payload: event.payload.clone(),
+            replay,
+        }
+    }
+
+    /// Live delivery of a newly stored event
+    pub async fn dispatch(&self, subscription: &WebhookSubscription, event: &StoredEvent) -> Result<bool, MessagingError> {
+        if !subscription.wants(event) {
+            return Ok(false);
+        }
+        self.sender.send(&subscription.url, &Self::delivery(subscription, event, false)).await?;
+        Ok(true)
+    }
+
+    /// Re-deliver every event after `since`, in sequence order. Stops at the
+    /// first failed send; the summary's `last_sequence` says where to resume.
+    pub async fn replay(&self, subscription: &WebhookSubscription, since: u64) -> Result<ReplaySummary, MessagingError> {
+        let mut summary = ReplaySummary { subscription_id: subscription.id, delivered: 0, last_sequence: None };
+        let mut cursor = since;
+
+        loop {
+            let page = self.store.since(subscription.tenant_id, cursor, EventStore::MAX_PAGE_SIZE);
+            let Some(last) = page.last() else { break };
+            cursor = last.sequence;
+
+            for event in page.iter().filter(|e| subscription.wants(e)) {
+                self.sender.send(&subscription.url, &Self::delivery(subscription, event, true)).await?;
+                summary.delivered += 1;
+                summary.last_sequence = Some(event.sequence);
+            }
+        }
+
+        tracing::info!(subscription_id = %subscription.id, since, delivered = summary.delivered, "webhook replay finished");
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::events::{EmployeeEvent, PayrollEvent};
+    use crate::domain::value_objects::EmployeeId;
+    use chrono::NaiveDate;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingSender {
+        sent: Mutex<Vec<WebhookDelivery>>,
+    }
+
+    #[async_trait]
+    impl WebhookSender for RecordingSender {
+        async fn send(&self, _url: &str, delivery: &WebhookDelivery) -> Result<(), MessagingError> {
+            self.sent.lock().unwrap().push(delivery.clone());
+            Ok(())
+        }
+    }
+
+    fn hired(seq: u32) -> DomainEvent {
+        DomainEvent::Employee(EmployeeEvent::Hired {
+            employee_id: EmployeeId::new(2024, seq),
+            hire_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        })
+    }
+
+    fn payroll_failed() -> DomainEvent {
+        DomainEvent::Payroll(PayrollEvent::Failed { payroll_id: "run-1".to_string(), reason: "bank timeout".to_string() })
+    }
+
+    #[test]
+    fn test_events_stored_in_order() {
+        let store = EventStore::new();
+        let (tenant, other) = (Uuid::new_v4(), Uuid::new_v4());
+
+        store.append(tenant, &hired(1)).unwrap();
+        store.append(other, &hired(2)).unwrap();
+        store.append(tenant, &payroll_failed()).unwrap();
+
+        let events = store.since(tenant, 0, 100);
+        assert_eq!(events.iter().map(|e| e.sequence).collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(events[0].event_type, "employee.hired");
+        assert_eq!(events[1].event_type, "payroll.failed");
+        assert_eq!(events[1].payload["payroll"]["reason"], "bank timeout");
+        assert_eq!(store.latest_sequence(tenant), 3);
+        assert_eq!(store.latest_sequence(other), 2);
+        assert_eq!(store.latest_sequence(Uuid::new_v4()), 0);
+    }
+
+    #[tokio::test]
+    async fn test_replay_from_sequence_sends_only_later_events() {
+        let store = EventStore::new();
+        let tenant = Uuid::new_v4();
+        for seq in 1..=5 {
+            store.append(tenant, &hired(seq)).unwrap();
+        }
+        store.append(tenant, &payroll_failed()).unwrap();
+
+        let sender = Arc::new(RecordingSender::default());
+        let dispatcher = WebhookDispatcher::new(store.clone(), sender.clone());
+        let subscription = WebhookSubscription {
+            id: Uuid::new_v4(),
+            tenant_id: tenant,
+            url: "https://integrator.example/hooks".to_string(),
+            event_types: vec!["employee.hired".to_string()],
+        };
+
+        // Live delivery of event 4, then a replay from 3 re-sends 4 and 5
+        let fourth = store.since(tenant, 3, 1).remove(0);
+        dispatcher.dispatch(&subscription, &fourth).await.unwrap();
+        let summary = dispatcher.replay(&subscription, 3).await.unwrap();
+
+        assert_eq!(summary.delivered, 2);
+        assert_eq!(summary.last_sequence, Some(5));
+
+        let sent = sender.sent.lock().unwrap();
+        assert_eq!(sent.iter().map(|d| d.sequence).collect::<Vec<_>>(), [4, 4, 5]);
+        assert!(!sent[0].replay);
+        assert!(sent[1].replay && sent[2].replay);
+        // Same delivery id for the original and its replay
+        assert_eq!(sent[0].delivery_id, sent[1].delivery_id);
+    }
+}
@@ -0,0 +1,54 @@
+//! Event API Handlers
+//!
+//! Read access to the event log for integrators catching up after an outage.
+//! The caller's `AuthContext` is expected in request extensions.
+
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthContext;
+use super::event_store::{EventStore, StoredEvent};
+
+/// Shared event state
+#[derive(Clone, Default)]
+pub struct EventAppState {
+    pub event_store: EventStore,
+}
+
+/// Event log query parameters
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    /// Return events with a sequence greater than this
+    #[serde(default)]
+    pub since: u64,
+    pub limit: Option<usize>,
+}
+
+/// Page of stored events
+#[derive(Debug, Serialize)]
+pub struct EventsPage {
+    pub events: Vec<StoredEvent>,
+    /// Pass as `since` to fetch the next page
+    pub next_since: u64,
+}
+
+/// List events after a sequence number
+///
+/// GET /api/v1/events?since=seq&limit=
+pub async fn list_events(
+    State(state): State<EventAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Query(query): Query<EventsQuery>,
+) -> Json<EventsPage> {
+    let events = state.event_store.since(auth.tenant_id, query.since, query.limit.unwrap_or(100));
+    let next_since = events.last().map_or(query.since, |e| e.sequence);
+    Json(EventsPage { events, next_since })
+}
+
+/// Event routes
+pub fn event_routes() -> axum::Router<EventAppState> {
+    axum::Router::new().route("/events", axum::routing::get(list_events))
+}
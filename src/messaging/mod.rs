@@ -0,0 +1,425 @@
+//! Event Messaging Module
+//!
+//! Publishes domain events to NATS under a per-tenant subject prefix.
+//! In JetStream mode each publish waits for the stream's ack so critical
+//! events (payroll completed, disbursement issued) survive broker hiccups;
+//! when JetStream is unavailable the publisher falls back to core NATS.
+//!
+//! The broker connection sits behind `NatsTransport` so the client library
+//! can be swapped and tests can run without a server.
+//!
+//! `event_store` keeps a durable, sequenced log of every domain event for
+//! integrator catch-up and webhook replay. The publisher appends each domain
+//! event to it before sending, so the log holds everything subscribers saw.
+//!
+//! With `sign_payroll_events` on, payroll approval and completion events
+//! are published as `signing::SignedEnvelope`s under the tenant's key.
+
+pub mod event_store;
+pub mod handlers;
+pub mod signing;
+
+pub use event_store::{EventStore, StoredEvent, WebhookDispatcher, WebhookSubscription};
+pub use signing::{SignatureError, SignedEnvelope, SigningKeys};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::events::{DomainEvent, PayrollEvent};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CONFIGURATION
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// NATS publishing configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatsConfig {
+    pub url: String,
+    /// Subject prefix; `{tenant_id}` is replaced with the event's tenant
+    pub subject_prefix: String,
+    /// Publish through JetStream and wait for the ack
+    pub jetstream: bool,
+    pub ack_timeout_ms: u64,
+    /// Sign payroll approved/completed events with the tenant's key
+    #[serde(default)]
+    pub sign_payroll_events: bool,
+}
+
+impl Default for NatsConfig {
+    fn default() -> Self {
+        Self {
+            url: "nats://localhost:4222".to_string(),
+            subject_prefix: "tenant.{tenant_id}.hr.".to_string(),
+            jetstream: false,
+            ack_timeout_ms: 5000,
+            sign_payroll_events: false,
+        }
+    }
+}
+
+impl NatsConfig {
+    /// Full subject for an event, e.g. `tenant.<id>.hr.payroll.completed`
+    pub fn subject(&self, tenant_id: Uuid, event: &str) -> String {
+        let prefix = self.subject_prefix.replace("{tenant_id}", &tenant_id.to_string());
+        format!("{}{}", prefix, event)
+    }
+
+    pub fn ack_timeout(&self) -> Duration {
+        Duration::from_millis(self.ack_timeout_ms)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TRANSPORT
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Messaging errors
+#[derive(Debug, thiserror::Error)]
+pub enum MessagingError {
+    #[error("Publish failed: {0}")]
+    Publish(String),
+
+    #[error("Timed out waiting for JetStream ack on {0}")]
+    AckTimeout(String),
+
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+
+    #[error("Signing failed: {0}")]
+    Signing(#[from] SignatureError),
+}
+
+/// Acknowledgement from a JetStream stream
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublishAck {
+    pub stream: String,
+    pub sequence: u64,
+}
+
+/// Connection to a NATS server
+#[async_trait]
+pub trait NatsTransport: Send + Sync {
+    /// Core NATS publish (fire-and-forget)
+    async fn publish(&self, subject: &str, payload: Vec<u8>) -> Result<(), MessagingError>;
+
+    /// JetStream publish, resolving once the stream acks the message
+    async fn jetstream_publish(&self, subject: &str, payload: Vec<u8>) -> Result<PublishAck, MessagingError>;
+
+    /// Whether the server has JetStream enabled and a stream bound to our subjects
+    async fn jetstream_available(&self) -> bool;
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// PUBLISHER
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// How an event was delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeliveryMode {
+    Core,
+    JetStream,
+}
+
+/// Result of publishing one event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishReceipt {
+    pub subject: String,
+    pub mode: DeliveryMode,
+    pub ack: Option<PublishAck>,
+}
+
+/// Domain event publisher
+pub struct EventPublisher {
+    config: NatsConfig,
+    transport: Arc<dyn NatsTransport>,
+    signing_keys: SigningKeys,
+    event_store: EventStore,
+    downgrade_logged: AtomicBool,
+}
+
+impl EventPublisher {
+    pub fn new(config: NatsConfig, transport: Arc<dyn NatsTransport>) -> Self {
+        Self {
+            config,
+            transport,
+            signing_keys: SigningKeys::new(),
+            event_store: EventStore::new(),
+            downgrade_logged: AtomicBool::new(false),
+        }
+    }
+
+    pub fn with_signing_keys(mut self, signing_keys: SigningKeys) -> Self {
+        self.signing_keys = signing_keys;
+        self
+    }
+
+    /// Record published domain events in `event_store`, e.g. the one the
+    /// event API and webhook dispatcher read from
+    pub fn with_event_store(mut self, event_store: EventStore) -> Self {
+        self.event_store = event_store;
+        self
+    }
+
+    pub fn event_store(&self) -> &EventStore {
+        &self.event_store
+    }
+
+    pub fn config(&self) -> &NatsConfig {
+        &self.config
+    }
+
+    /// Publish an event as JSON under the tenant's subject prefix
+    pub async fn publish<T: Serialize>(
+        &self,
+        tenant_id: Uuid,
+        event: &str,
+        payload: &T,
+    ) -> Result<PublishReceipt, MessagingError> {
+        let subject = self.config.subject(tenant_id, event);
+        let bytes = serde_json::to_vec(payload).map_err(|e| MessagingError::Serialization(e.to_string()))?;
+
+        if self.config.jetstream {
+            if self.transport.jetstream_available().await {
+                let ack = tokio::time::timeout(
+                    self.config.ack_timeout(),
+                    self.transport.jetstream_publish(&subject, bytes),
+                )
+                .await
+                .map_err(|_| MessagingError::AckTimeout(subject.clone()))??;
+
+                return Ok(PublishReceipt { subject, mode: DeliveryMode::JetStream, ack: Some(ack) });
+            }
+
+            if !self.downgrade_logged.swap(true, Ordering::Relaxed) {
+                tracing::warn!(url = %self.config.url, "JetStream unavailable, downgrading to core NATS publishing");
+            }
+        }
+
+        self.transport.publish(&subject, bytes).await?;
+        Ok(PublishReceipt { subject, mode: DeliveryMode::Core, ack: None })
+    }
+
+    /// Append a domain event to the event store, then publish it under its
+    /// event type. Payroll events go through `publish_payroll_event`.
+    pub async fn publish_event(&self, tenant_id: Uuid, event: &DomainEvent) -> Result<PublishReceipt, MessagingError> {
+        match event {
+            DomainEvent::Payroll(payroll) => self.publish_payroll_event(tenant_id, payroll).await,
+            _ => {
+                self.event_store.append(tenant_id, event)?;
+                self.publish(tenant_id, event.event_type(), event).await
+            }
+        }
+    }
+
+    /// Append a payroll event to the event store and publish it, signed
+    /// when `sign_payroll_events` is on and the event is one finance systems
+    /// act on. Refuses to fall back to an unsigned message when the tenant
+    /// has no key, and then records nothing.
+    pub async fn publish_payroll_event(
+        &self,
+        tenant_id: Uuid,
+        event: &PayrollEvent,
+    ) -> Result<PublishReceipt, MessagingError> {
+        let domain_event = DomainEvent::Payroll(event.clone());
+        let event_type = domain_event.event_type();
+        if !(self.config.sign_payroll_events && signing::requires_signature(event)) {
+            self.event_store.append(tenant_id, &domain_event)?;
+            return self.publish(tenant_id, event_type, event).await;
+        }
+
+        let key = self.signing_keys.key_for(tenant_id).ok_or(SignatureError::MissingKey(tenant_id))?;
+        self.event_store.append(tenant_id, &domain_event)?;
+        let payload = serde_json::to_value(event).map_err(|e| MessagingError::Serialization(e.to_string()))?;
+        let envelope = SignedEnvelope::sign(tenant_id, event_type, payload, &key);
+        self.publish(tenant_id, event_type, &envelope).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockTransport {
+        jetstream: bool,
+        ack_delay_ms: u64,
+        core: Mutex<Vec<String>>,
+        acked: Mutex<Vec<String>>,
+        payloads: Mutex<Vec<Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl NatsTransport for MockTransport {
+        async fn publish(&self, subject: &str, payload: Vec<u8>) -> Result<(), MessagingError> {
+            self.core.lock().unwrap().push(subject.to_string());
+            self.payloads.lock().unwrap().push(payload);
+            Ok(())
+        }
+
+        async fn jetstream_publish(&self, subject: &str, payload: Vec<u8>) -> Result<PublishAck, MessagingError> {
+            tokio::time::sleep(Duration::from_millis(self.ack_delay_ms)).await;
+            self.payloads.lock().unwrap().push(payload);
+            let mut acked = self.acked.lock().unwrap();
+            acked.push(subject.to_string());
+            Ok(PublishAck { stream: "HR_EVENTS".to_string(), sequence: acked.len() as u64 })
+        }
+
+        async fn jetstream_available(&self) -> bool {
+            self.jetstream
+        }
+    }
+
+    fn publisher(jetstream_mode: bool, transport: Arc<MockTransport>) -> EventPublisher {
+        EventPublisher::new(NatsConfig { jetstream: jetstream_mode, ack_timeout_ms: 200, ..Default::default() }, transport)
+    }
+
+    #[test]
+    fn test_subject_prefix() {
+        let tenant = Uuid::nil();
+        let subject = NatsConfig::default().subject(tenant, "payroll.completed");
+        assert_eq!(subject, format!("tenant.{}.hr.payroll.completed", tenant));
+    }
+
+    #[tokio::test]
+    async fn test_jetstream_mode_awaits_ack() {
+        let transport = Arc::new(MockTransport { jetstream: true, ack_delay_ms: 20, ..Default::default() });
+        let publisher = publisher(true, transport.clone());
+
+        let receipt = publisher.publish(Uuid::new_v4(), "payroll.completed", &"run-1").await.unwrap();
+
+        assert_eq!(receipt.mode, DeliveryMode::JetStream);
+        assert_eq!(receipt.ack, Some(PublishAck { stream: "HR_EVENTS".to_string(), sequence: 1 }));
+        assert_eq!(transport.acked.lock().unwrap().as_slice(), [receipt.subject]);
+        assert!(transport.core.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_core_mode_does_not_wait_for_ack() {
+        let transport = Arc::new(MockTransport { jetstream: true, ack_delay_ms: 1000, ..Default::default() });
+        let publisher = publisher(false, transport.clone());
+
+        let receipt = publisher.publish(Uuid::new_v4(), "leave.approved", &"req-1").await.unwrap();
+
+        assert_eq!(receipt.mode, DeliveryMode::Core);
+        assert!(receipt.ack.is_none());
+        assert!(transport.acked.lock().unwrap().is_empty());
+        assert_eq!(transport.core.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_core_without_jetstream() {
+        let transport = Arc::new(MockTransport { jetstream: false, ..Default::default() });
+        let publisher = publisher(true, transport.clone());
+
+        let receipt = publisher.publish(Uuid::new_v4(), "disbursement.issued", &"batch-1").await.unwrap();
+        assert_eq!(receipt.mode, DeliveryMode::Core);
+        assert_eq!(transport.core.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_missing_ack_times_out() {
+        let transport = Arc::new(MockTransport { jetstream: true, ack_delay_ms: 1000, ..Default::default() });
+        let publisher = publisher(true, transport);
+
+        let result = publisher.publish(Uuid::new_v4(), "payroll.completed", &"run-2").await;
+        assert!(matches!(result, Err(MessagingError::AckTimeout(_))));
+    }
+
+    fn signing_publisher(transport: Arc<MockTransport>, keys: SigningKeys) -> EventPublisher {
+        let config = NatsConfig { sign_payroll_events: true, ..Default::default() };
+        EventPublisher::new(config, transport).with_signing_keys(keys)
+    }
+
+    fn completed() -> PayrollEvent {
+        PayrollEvent::Completed {
+            payroll_id: "PR-2024-03".to_string(),
+            check_date: chrono::NaiveDate::from_ymd_opt(2024, 3, 29).unwrap(),
+            total_disbursed: rust_decimal_macros::dec!(184250.75),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signed_payroll_event_verifies() {
+        let tenant = Uuid::new_v4();
+        let keys = SigningKeys::new();
+        keys.set_key(tenant, b"tenant-secret".to_vec());
+        let transport = Arc::new(MockTransport::default());
+        let publisher = signing_publisher(transport.clone(), keys);
+
+        let receipt = publisher.publish_payroll_event(tenant, &completed()).await.unwrap();
+        assert!(receipt.subject.ends_with("payroll.completed"));
+
+        let published = transport.payloads.lock().unwrap().pop().unwrap();
+        let envelope: SignedEnvelope = serde_json::from_slice(&published).unwrap();
+        assert_eq!(envelope.event_type, "payroll.completed");
+        assert_eq!(envelope.payload["payroll_id"], "PR-2024-03");
+        assert_eq!(envelope.verify(b"tenant-secret"), Ok(()));
+        assert_eq!(envelope.verify(b"other-tenant-secret"), Err(SignatureError::BadSignature));
+    }
+
+    #[tokio::test]
+    async fn test_altered_payload_fails_verification() {
+        let tenant = Uuid::new_v4();
+        let keys = SigningKeys::new();
+        keys.set_key(tenant, b"tenant-secret".to_vec());
+        let transport = Arc::new(MockTransport::default());
+        let publisher = signing_publisher(transport.clone(), keys);
+
+        publisher.publish_payroll_event(tenant, &completed()).await.unwrap();
+        let published = transport.payloads.lock().unwrap().pop().unwrap();
+        let mut envelope: SignedEnvelope = serde_json::from_slice(&published).unwrap();
+
+        envelope.payload["total_disbursed"] = serde_json::json!("284250.75");
+        assert_eq!(envelope.verify(b"tenant-secret"), Err(SignatureError::HashMismatch));
+
+        // Recomputing the hash doesn't help without the key
+        let forged = SignedEnvelope::sign(tenant, "payroll.completed", envelope.payload.clone(), b"guess");
+        envelope.payload_hash = forged.payload_hash;
+        assert_eq!(envelope.verify(b"tenant-secret"), Err(SignatureError::BadSignature));
+    }
+
+    #[tokio::test]
+    async fn test_signing_requires_tenant_key() {
+        let transport = Arc::new(MockTransport::default());
+        let publisher = signing_publisher(transport.clone(), SigningKeys::new());
+
+        let tenant = Uuid::new_v4();
+        let result = publisher.publish_payroll_event(tenant, &completed()).await;
+        assert!(matches!(result, Err(MessagingError::Signing(SignatureError::MissingKey(_)))));
+        assert!(transport.core.lock().unwrap().is_empty());
+        assert_eq!(publisher.event_store().latest_sequence(tenant), 0);
+
+        // Events outside the signed set still go out as plain JSON
+        let failed = PayrollEvent::Failed { payroll_id: "PR-2024-03".to_string(), reason: "bank rejected".to_string() };
+        publisher.publish_payroll_event(Uuid::new_v4(), &failed).await.unwrap();
+        let published = transport.payloads.lock().unwrap().pop().unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&published).unwrap();
+        assert_eq!(body["type"], "failed");
+    }
+
+    #[tokio::test]
+    async fn test_published_events_are_stored() {
+        let store = EventStore::new();
+        let transport = Arc::new(MockTransport::default());
+        let publisher = publisher(false, transport.clone()).with_event_store(store.clone());
+        let tenant = Uuid::new_v4();
+
+        let hired = DomainEvent::Employee(crate::domain::events::EmployeeEvent::Hired {
+            employee_id: crate::domain::value_objects::EmployeeId::new(2024, 1),
+            hire_date: chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        });
+        publisher.publish_event(tenant, &hired).await.unwrap();
+        publisher.publish_event(tenant, &DomainEvent::Payroll(completed())).await.unwrap();
+
+        let stored = store.since(tenant, 0, 10);
+        assert_eq!(stored.iter().map(|e| e.event_type.as_str()).collect::<Vec<_>>(), ["employee.hired", "payroll.completed"]);
+        assert_eq!(store.latest_sequence(tenant), 2);
+        assert_eq!(transport.core.lock().unwrap().len(), 2);
+    }
+}
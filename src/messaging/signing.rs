@@ -0,0 +1,139 @@
+//! Payroll Event Signing
+//!
+//! Payroll approval and completion events go to downstream finance
+//! systems, so they're published inside a `SignedEnvelope`: the payload's
+//! canonical JSON, its SHA-256, and an HMAC-SHA256 over both under a
+//! per-tenant key. Consumers holding the key call `verify` before acting.
+
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::domain::events::PayrollEvent;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const SIGNATURE_ALGORITHM: &str = "HMAC-SHA256";
+
+/// Signing errors
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SignatureError {
+    #[error("No signing key configured for tenant {0}")]
+    MissingKey(Uuid),
+
+    #[error("Unsupported signature algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("Payload hash does not match payload")]
+    HashMismatch,
+
+    #[error("Signature does not match")]
+    BadSignature,
+}
+
+/// Per-tenant HMAC keys
+#[derive(Debug, Clone, Default)]
+pub struct SigningKeys {
+    // In real implementation, fetched from the secrets store
+    keys: Arc<DashMap<Uuid, Vec<u8>>>,
+}
+
+impl SigningKeys {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_key(&self, tenant_id: Uuid, key: impl Into<Vec<u8>>) {
+        self.keys.insert(tenant_id, key.into());
+    }
+
+    pub fn key_for(&self, tenant_id: Uuid) -> Option<Vec<u8>> {
+        self.keys.get(&tenant_id).map(|k| k.clone())
+    }
+}
+
+/// Signed payroll event as published
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedEnvelope {
+    pub tenant_id: Uuid,
+    /// Dotted event name, e.g. `payroll.completed`
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    /// Hex SHA-256 of the canonical payload
+    pub payload_hash: String,
+    pub algorithm: String,
+    /// Hex HMAC over `tenant_id`, `event_type`, and `payload_hash`
+    pub signature: String,
+}
+
+/// Whether a payroll event goes out signed
+pub fn requires_signature(event: &PayrollEvent) -> bool {
+    matches!(event, PayrollEvent::Approved { .. } | PayrollEvent::Completed { .. })
+}
+
+/// Compact JSON with object keys sorted, so equal content hashes equally
+/// whatever order a consumer re-serializes it in
+pub fn canonical_json(value: &serde_json::Value) -> Vec<u8> {
+    // serde_json's map is ordered by key unless `preserve_order` is enabled
+    serde_json::to_vec(value).expect("JSON value serializes")
+}
+
+fn payload_hash(payload: &serde_json::Value) -> String {
+    hex::encode(Sha256::digest(canonical_json(payload)))
+}
+
+fn mac(key: &[u8], tenant_id: Uuid, event_type: &str, payload_hash: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(tenant_id.as_bytes());
+    mac.update(b"\n");
+    mac.update(event_type.as_bytes());
+    mac.update(b"\n");
+    mac.update(payload_hash.as_bytes());
+    mac
+}
+
+impl SignedEnvelope {
+    pub fn sign(tenant_id: Uuid, event_type: &str, payload: serde_json::Value, key: &[u8]) -> Self {
+        let payload_hash = payload_hash(&payload);
+        let signature = hex::encode(mac(key, tenant_id, event_type, &payload_hash).finalize().into_bytes());
+        Self {
+            tenant_id,
+            event_type: event_type.to_string(),
+            payload,
+            payload_hash,
+            algorithm: SIGNATURE_ALGORITHM.to_string(),
+            signature,
+        }
+    }
+
+    /// Check the payload against its hash and the hash against the signature
+    pub fn verify(&self, key: &[u8]) -> Result<(), SignatureError> {
+        if self.algorithm != SIGNATURE_ALGORITHM {
+            return Err(SignatureError::UnsupportedAlgorithm(self.algorithm.clone()));
+        }
+        if payload_hash(&self.payload) != self.payload_hash {
+            return Err(SignatureError::HashMismatch);
+        }
+        let signature = hex::decode(&self.signature).map_err(|_| SignatureError::BadSignature)?;
+        mac(key, self.tenant_id, &self.event_type, &self.payload_hash)
+            .verify_slice(&signature)
+            .map_err(|_| SignatureError::BadSignature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_hash_ignores_key_order() {
+        let a: serde_json::Value = serde_json::from_str(r#"{"b":1,"a":{"y":2,"x":3}}"#).unwrap();
+        let b: serde_json::Value = serde_json::from_str(r#"{"a":{"x":3,"y":2},"b":1}"#).unwrap();
+        assert_eq!(canonical_json(&a), br#"{"a":{"x":3,"y":2},"b":1}"#);
+        assert_eq!(payload_hash(&a), payload_hash(&b));
+    }
+}
@@ -3,8 +3,22 @@
 //! Health checks, metrics, and deployment configuration for the HR platform.
 //! Supports multi-region Kubernetes deployments with observability.
 
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub mod scheduler;
+
+pub use scheduler::{Cadence, JobScheduler, JobTrigger, Schedule, SchedulerConfig, TriggerOutcome};
 
 // ═══════════════════════════════════════════════════════════════════════════
 // HEALTH CHECKS
@@ -72,12 +86,40 @@ impl SystemHealth {
 // METRICS
 // ═══════════════════════════════════════════════════════════════════════════
 
+/// Default histogram bucket upper bounds (seconds), matching the Prometheus client defaults
+pub const DEFAULT_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Observations bucketed as they arrive, so memory stays fixed however
+/// many are recorded
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    /// Observations at or below each `DEFAULT_BUCKETS` bound, cumulative
+    buckets: [u64; DEFAULT_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        for (bound, bucket) in DEFAULT_BUCKETS.iter().zip(self.buckets.iter_mut()) {
+            if value <= *bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
 /// Metrics registry
+///
+/// Series are keyed by their full Prometheus name including labels,
+/// e.g. `http_requests_total{status="200"}`.
 #[derive(Debug, Default)]
 pub struct MetricsRegistry {
     counters: HashMap<String, u64>,
     gauges: HashMap<String, f64>,
-    histograms: HashMap<String, Vec<f64>>,
+    histograms: HashMap<String, Histogram>,
 }
 
 impl MetricsRegistry {
@@ -89,38 +131,66 @@ impl MetricsRegistry {
         *self.counters.entry(name.to_string()).or_insert(0) += value;
     }
     
+    /// Increment a labelled counter series
+    pub fn increment_with_labels(&mut self, name: &str, labels: &[(&str, &str)], value: u64) {
+        self.increment(&series_key(name, labels), value);
+    }
+    
     pub fn set_gauge(&mut self, name: &str, value: f64) {
         self.gauges.insert(name.to_string(), value);
     }
     
+    /// Set a labelled gauge series, e.g. `up{job="tax-engine"}`
+    pub fn set_gauge_with_labels(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.set_gauge(&series_key(name, labels), value);
+    }
+    
     pub fn record_histogram(&mut self, name: &str, value: f64) {
         self.histograms
             .entry(name.to_string())
-            .or_insert_with(Vec::new)
-            .push(value);
+            .or_default()
+            .observe(value);
+    }
+    
+    /// Current value of a counter series (0 if never incremented)
+    pub fn counter(&self, series: &str) -> u64 {
+        self.counters.get(series).copied().unwrap_or(0)
     }
     
     /// Export metrics in Prometheus format
     pub fn export_prometheus(&self) -> String {
         let mut output = String::new();
         
-        for (name, value) in &self.counters {
-            output.push_str(&format!("# TYPE {} counter\n", name));
-            output.push_str(&format!("{} {}\n", name, value));
+        let mut counters: Vec<_> = self.counters.iter().collect();
+        counters.sort();
+        let mut last_family = "";
+        for (series, value) in counters {
+            let family = metric_family(series);
+            if family != last_family {
+                output.push_str(&format!("# TYPE {} counter\n", family));
+                last_family = family;
+            }
+            output.push_str(&format!("{} {}\n", series, value));
         }
         
-        for (name, value) in &self.gauges {
+        let mut gauges: Vec<_> = self.gauges.iter().collect();
+        gauges.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, value) in gauges {
             output.push_str(&format!("# TYPE {} gauge\n", name));
             output.push_str(&format!("{} {}\n", name, value));
         }
         
-        for (name, values) in &self.histograms {
-            if !values.is_empty() {
+        let mut histograms: Vec<_> = self.histograms.iter().collect();
+        histograms.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, histogram) in histograms {
+            if histogram.count > 0 {
                 output.push_str(&format!("# TYPE {} histogram\n", name));
-                let sum: f64 = values.iter().sum();
-                let count = values.len();
-                output.push_str(&format!("{}_count {}\n", name, count));
-                output.push_str(&format!("{}_sum {}\n", name, sum));
+                for (bound, in_bucket) in DEFAULT_BUCKETS.iter().zip(histogram.buckets) {
+                    output.push_str(&format!("{}_bucket{{le=\"{}\"}} {}\n", name, bound, in_bucket));
+                }
+                output.push_str(&format!("{}_bucket{{le=\"+Inf\"}} {}\n", name, histogram.count));
+                output.push_str(&format!("{}_count {}\n", name, histogram.count));
+                output.push_str(&format!("{}_sum {}\n", name, histogram.sum));
             }
         }
         
@@ -128,6 +198,213 @@ impl MetricsRegistry {
     }
 }
 
+/// Build a series key such as `name{a="1",b="2"}`
+fn series_key(name: &str, labels: &[(&str, &str)]) -> String {
+    if labels.is_empty() {
+        return name.to_string();
+    }
+    let labels: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, v))
+        .collect();
+    format!("{}{{{}}}", name, labels.join(","))
+}
+
+/// Metric family name of a series (the part before any label set)
+fn metric_family(series: &str) -> &str {
+    series.split('{').next().unwrap_or(series)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// HTTP METRICS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Registry shared between the request middleware and the `/metrics` handler
+pub type SharedMetrics = Arc<Mutex<MetricsRegistry>>;
+
+/// Middleware recording `http_requests_total{status}` and
+/// `http_request_duration_seconds` for every request
+pub async fn track_metrics(
+    State(metrics): State<SharedMetrics>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let start = Instant::now();
+    let response = next.run(request).await;
+    let elapsed = start.elapsed().as_secs_f64();
+    let status = response.status().as_u16().to_string();
+    
+    let mut registry = metrics.lock().unwrap_or_else(|e| e.into_inner());
+    registry.increment_with_labels("http_requests_total", &[("status", &status)], 1);
+    registry.record_histogram("http_request_duration_seconds", elapsed);
+    
+    response
+}
+
+/// Prometheus scrape endpoint
+///
+/// GET /metrics
+pub async fn metrics_handler(State(metrics): State<SharedMetrics>) -> impl IntoResponse {
+    let body = metrics
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .export_prometheus();
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        body,
+    )
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// REQUEST TRACING & QUERY TIMING
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Tracing and database timing settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObservabilityConfig {
+    /// Fraction of requests traced, 0.0 to 1.0; server errors are always traced
+    pub trace_sample_rate: f64,
+    /// Queries at or above this are logged and counted as slow
+    pub slow_query_threshold_ms: u64,
+}
+
+impl Default for ObservabilityConfig {
+    fn default() -> Self {
+        Self { trace_sample_rate: 0.1, slow_query_threshold_ms: 500 }
+    }
+}
+
+/// Deterministic request sampler: at rate 0.25 every fourth request is
+/// traced, so the sampled share is exact rather than probabilistic
+#[derive(Debug, Default)]
+pub struct TraceSampler {
+    rate: f64,
+    seen: AtomicU64,
+    sampled: AtomicU64,
+}
+
+impl TraceSampler {
+    pub fn new(rate: f64) -> Self {
+        Self { rate: rate.clamp(0.0, 1.0), ..Default::default() }
+    }
+
+    pub fn should_sample(&self) -> bool {
+        let n = self.seen.fetch_add(1, Ordering::Relaxed) as f64;
+        let sample = ((n + 1.0) * self.rate).floor() > (n * self.rate).floor();
+        if sample {
+            self.sampled.fetch_add(1, Ordering::Relaxed);
+        }
+        sample
+    }
+
+    /// Requests traced so far
+    pub fn sampled(&self) -> u64 {
+        self.sampled.load(Ordering::Relaxed)
+    }
+}
+
+/// Middleware tracing sampled requests, and every 5xx regardless of sampling
+pub async fn trace_requests(
+    State(sampler): State<Arc<TraceSampler>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let sampled = sampler.should_sample();
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let start = Instant::now();
+    let response = next.run(request).await;
+    let latency_ms = start.elapsed().as_millis() as u64;
+    let status = response.status().as_u16();
+
+    if response.status().is_server_error() {
+        tracing::warn!(%method, %path, status, latency_ms, "request failed");
+    } else if sampled {
+        tracing::info!(%method, %path, status, latency_ms, "request");
+    }
+    response
+}
+
+/// Times database queries into `db_query_duration_seconds` and logs and
+/// counts (`db_slow_queries_total{query}`) those over the threshold.
+/// Repository calls run their sqlx future through `observe`.
+#[derive(Debug, Clone)]
+pub struct QueryObserver {
+    metrics: SharedMetrics,
+    slow_threshold: Duration,
+}
+
+impl QueryObserver {
+    pub fn new(metrics: SharedMetrics, config: &ObservabilityConfig) -> Self {
+        Self { metrics, slow_threshold: Duration::from_millis(config.slow_query_threshold_ms) }
+    }
+
+    /// Run `query`, recording how long it took under the name `query_name`
+    pub async fn observe<T>(&self, query_name: &str, query: impl Future<Output = T>) -> T {
+        let start = Instant::now();
+        let output = query.await;
+        self.record(query_name, start.elapsed());
+        output
+    }
+
+    /// Record one query's duration; true when it was slow
+    pub fn record(&self, query_name: &str, elapsed: Duration) -> bool {
+        let slow = elapsed >= self.slow_threshold;
+        let mut registry = self.metrics.lock().unwrap_or_else(|e| e.into_inner());
+        registry.record_histogram("db_query_duration_seconds", elapsed.as_secs_f64());
+        if slow {
+            registry.increment_with_labels("db_slow_queries_total", &[("query", query_name)], 1);
+            tracing::warn!(
+                query = query_name,
+                elapsed_ms = elapsed.as_millis() as u64,
+                threshold_ms = self.slow_threshold.as_millis() as u64,
+                "slow query"
+            );
+        }
+        slow
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// BUILD INFO
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Build metadata for ops and support
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub version: String,
+    pub git_commit: String,
+    pub build_timestamp: String,
+    pub supported_countries: usize,
+}
+
+impl BuildInfo {
+    /// Metadata embedded at compile time by `build.rs`
+    pub fn current() -> Self {
+        let build_timestamp = env!("HR_BUILD_UNIX_TIME")
+            .parse::<i64>()
+            .ok()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("HR_GIT_SHA").to_string(),
+            build_timestamp,
+            supported_countries: crate::payroll::PayrollRegistry::country_count(),
+        }
+    }
+}
+
+/// Version/build-info endpoint
+///
+/// GET /api/v1/version
+pub async fn version_handler() -> axum::Json<BuildInfo> {
+    axum::Json(BuildInfo::current())
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // DEPLOYMENT CONFIGURATION
 // ═══════════════════════════════════════════════════════════════════════════
@@ -324,6 +601,261 @@ pub fn standard_alerts() -> Vec<AlertDefinition> {
     ]
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+// ALERT EVALUATION
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// An alert whose expression currently holds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FiringAlert {
+    pub name: String,
+    pub severity: AlertSeverity,
+    pub summary: String,
+    pub value: f64,
+}
+
+/// Comparison operator in an alert expression
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl Comparison {
+    fn holds(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Comparison::Gt => lhs > rhs,
+            Comparison::Ge => lhs >= rhs,
+            Comparison::Lt => lhs < rhs,
+            Comparison::Le => lhs <= rhs,
+            Comparison::Eq => lhs == rhs,
+            Comparison::Ne => lhs != rhs,
+        }
+    }
+}
+
+/// Label matcher: `label="value"` or `label=~"pattern"`
+#[derive(Debug, Clone)]
+struct LabelMatcher {
+    label: String,
+    value: String,
+    is_pattern: bool,
+}
+
+impl LabelMatcher {
+    fn matches(&self, labels: &[(String, String)]) -> bool {
+        let actual = labels
+            .iter()
+            .find(|(k, _)| *k == self.label)
+            .map(|(_, v)| v.as_str())
+            .unwrap_or("");
+        if self.is_pattern {
+            wildcard_match(&self.value, actual)
+        } else {
+            actual == self.value
+        }
+    }
+}
+
+/// Metric selector: `name{matchers...}`
+#[derive(Debug, Clone)]
+struct Selector {
+    name: String,
+    matchers: Vec<LabelMatcher>,
+}
+
+/// Left-hand side of a supported expression
+#[derive(Debug, Clone)]
+enum Operand {
+    /// Bare selector, compared series by series (`up{job="x"} == 0`)
+    Instant(Selector),
+    /// `sum(rate(selector[window]))`, evaluated as the summed counter value
+    SumRate(Selector),
+}
+
+/// Parsed form of the expression subset evaluated in-process
+#[derive(Debug, Clone)]
+enum AlertExpr {
+    Threshold(Operand, Comparison, f64),
+    Ratio(Operand, Operand, Comparison, f64),
+}
+
+impl AlertExpr {
+    fn parse(expr: &str) -> Option<Self> {
+        let expr = expr.trim();
+        let (lhs, op, rhs) = [
+            (" >= ", Comparison::Ge),
+            (" <= ", Comparison::Le),
+            (" == ", Comparison::Eq),
+            (" != ", Comparison::Ne),
+            (" > ", Comparison::Gt),
+            (" < ", Comparison::Lt),
+        ]
+        .iter()
+        .find_map(|(token, op)| {
+            expr.rfind(token)
+                .map(|i| (&expr[..i], *op, &expr[i + token.len()..]))
+        })?;
+        let threshold: f64 = rhs.trim().parse().ok()?;
+        
+        match lhs.split_once(" / ") {
+            Some((num, den)) => Some(AlertExpr::Ratio(
+                parse_operand(num)?,
+                parse_operand(den)?,
+                op,
+                threshold,
+            )),
+            None => Some(AlertExpr::Threshold(parse_operand(lhs)?, op, threshold)),
+        }
+    }
+}
+
+fn parse_operand(text: &str) -> Option<Operand> {
+    let text = text.trim();
+    if let Some(inner) = text.strip_prefix("sum(rate(").and_then(|t| t.strip_suffix("))")) {
+        // Drop the range window; counters are compared over the same window on both sides
+        let selector = inner.rsplit_once('[').map(|(sel, _)| sel).unwrap_or(inner);
+        return parse_selector(selector).map(Operand::SumRate);
+    }
+    parse_selector(text).map(Operand::Instant)
+}
+
+fn parse_selector(text: &str) -> Option<Selector> {
+    let text = text.trim();
+    let (name, body) = match text.split_once('{') {
+        Some((name, rest)) => (name, Some(rest.strip_suffix('}')?)),
+        None => (text, None),
+    };
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':') {
+        return None;
+    }
+    
+    let mut matchers = vec![];
+    for part in body.unwrap_or("").split(',').filter(|p| !p.trim().is_empty()) {
+        let (label, value, is_pattern) = match part.split_once("=~") {
+            Some((label, value)) => (label, value, true),
+            None => {
+                let (label, value) = part.split_once('=')?;
+                (label, value, false)
+            }
+        };
+        let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+        // Only `.` wildcards are understood; anything richer is unsupported
+        if is_pattern && value.chars().any(|c| "*+?[](){}|^$\\".contains(c)) {
+            return None;
+        }
+        matchers.push(LabelMatcher {
+            label: label.trim().to_string(),
+            value: value.to_string(),
+            is_pattern,
+        });
+    }
+    
+    Some(Selector { name: name.to_string(), matchers })
+}
+
+/// Anchored match where `.` stands for any single character
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    pattern.chars().count() == value.chars().count()
+        && pattern.chars().zip(value.chars()).all(|(p, v)| p == '.' || p == v)
+}
+
+/// Split a series key into its family name and label pairs
+fn parse_series(series: &str) -> (&str, Vec<(String, String)>) {
+    let Some((name, rest)) = series.split_once('{') else {
+        return (series, vec![]);
+    };
+    let labels = rest
+        .trim_end_matches('}')
+        .split(',')
+        .filter_map(|pair| {
+            let (k, v) = pair.split_once('=')?;
+            Some((k.to_string(), v.trim_matches('"').to_string()))
+        })
+        .collect();
+    (name, labels)
+}
+
+impl MetricsRegistry {
+    /// Values of every counter and gauge series matching a selector
+    fn select(&self, selector: &Selector) -> Vec<f64> {
+        let counters = self.counters.iter().map(|(k, v)| (k, *v as f64));
+        let gauges = self.gauges.iter().map(|(k, v)| (k, *v));
+        counters
+            .chain(gauges)
+            .filter(|(series, _)| {
+                let (name, labels) = parse_series(series);
+                name == selector.name && selector.matchers.iter().all(|m| m.matches(&labels))
+            })
+            .map(|(_, value)| value)
+            .collect()
+    }
+    
+    fn operand_values(&self, operand: &Operand) -> Vec<f64> {
+        match operand {
+            Operand::Instant(selector) => self.select(selector),
+            Operand::SumRate(selector) => {
+                let values = self.select(selector);
+                if values.is_empty() {
+                    vec![]
+                } else {
+                    vec![values.iter().sum()]
+                }
+            }
+        }
+    }
+}
+
+/// Evaluate alert definitions against the in-process registry
+///
+/// Supports the forms used by [`standard_alerts`]: thresholds on a series
+/// (`up{job="tax-engine"} == 0`) and ratios of `sum(rate(...))` counters.
+/// Counters are cumulative, so a ratio is taken over process lifetime rather
+/// than the range window, and `duration` is not tracked. Series with no data
+/// never fire. Unsupported expressions are skipped and logged.
+pub fn evaluate_alerts(alerts: &[AlertDefinition], registry: &MetricsRegistry) -> Vec<FiringAlert> {
+    let mut firing = vec![];
+    
+    for alert in alerts {
+        let Some(expr) = AlertExpr::parse(&alert.expr) else {
+            tracing::debug!(alert = %alert.name, expr = %alert.expr, "skipping unsupported alert expression");
+            continue;
+        };
+        
+        let fired_value = match &expr {
+            AlertExpr::Threshold(operand, op, threshold) => registry
+                .operand_values(operand)
+                .into_iter()
+                .find(|v| op.holds(*v, *threshold)),
+            AlertExpr::Ratio(num, den, op, threshold) => {
+                let num = registry.operand_values(num).first().copied().unwrap_or(0.0);
+                match registry.operand_values(den).first() {
+                    Some(den) if *den > 0.0 => {
+                        let ratio = num / den;
+                        op.holds(ratio, *threshold).then_some(ratio)
+                    }
+                    _ => None,
+                }
+            }
+        };
+        
+        if let Some(value) = fired_value {
+            firing.push(FiringAlert {
+                name: alert.name.clone(),
+                severity: alert.severity,
+                summary: alert.summary.clone(),
+                value,
+            });
+        }
+    }
+    
+    firing
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -372,6 +904,105 @@ mod tests {
         let output = registry.export_prometheus();
         assert!(output.contains("http_requests_total 6"));
         assert!(output.contains("active_connections 42"));
+        assert!(output.contains("request_duration_seconds_bucket{le=\"0.1\"} 0"));
+        assert!(output.contains("request_duration_seconds_bucket{le=\"0.25\"} 2"));
+        assert!(output.contains("request_duration_seconds_count 2"));
+        assert!(output.contains("request_duration_seconds_sum 0.4"));
+    }
+    
+    #[test]
+    fn test_labelled_counters_share_type_line() {
+        let mut registry = MetricsRegistry::new();
+        
+        registry.increment_with_labels("http_requests_total", &[("status", "200")], 3);
+        registry.increment_with_labels("http_requests_total", &[("status", "500")], 1);
+        
+        let output = registry.export_prometheus();
+        assert_eq!(output.matches("# TYPE http_requests_total counter").count(), 1);
+        assert!(output.contains("http_requests_total{status=\"200\"} 3"));
+        assert!(output.contains("http_requests_total{status=\"500\"} 1"));
+        assert_eq!(registry.counter("http_requests_total{status=\"200\"}"), 3);
+    }
+    
+    #[tokio::test]
+    async fn test_metrics_endpoint_reports_live_requests() {
+        use axum::{body::Body, http::Request, middleware, routing::get, Router};
+        use tower::ServiceExt;
+        
+        let metrics = SharedMetrics::default();
+        let app = Router::new()
+            .route("/ok", get(|| async { "ok" }))
+            .route("/fail", get(|| async { StatusCode::INTERNAL_SERVER_ERROR }))
+            .route("/metrics", get(metrics_handler))
+            .layer(middleware::from_fn_with_state(metrics.clone(), track_metrics))
+            .with_state(metrics);
+        
+        for uri in ["/ok", "/ok", "/fail"] {
+            let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            app.clone().oneshot(request).await.unwrap();
+        }
+        
+        let request = Request::builder().uri("/metrics").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let output = String::from_utf8(body.to_vec()).unwrap();
+        assert!(output.contains("http_requests_total{status=\"200\"} 2"));
+        assert!(output.contains("http_requests_total{status=\"500\"} 1"));
+        assert!(output.contains("http_request_duration_seconds_count 3"));
+        assert!(output.contains("http_request_duration_seconds_bucket{le=\"+Inf\"} 3"));
+    }
+    
+    #[test]
+    fn test_trace_sampler_rate() {
+        let quarter = TraceSampler::new(0.25);
+        let picks: Vec<bool> = (0..8).map(|_| quarter.should_sample()).collect();
+        assert_eq!(picks, [false, false, false, true, false, false, false, true]);
+        assert_eq!(quarter.sampled(), 2);
+        
+        let none = TraceSampler::new(0.0);
+        assert!((0..100).all(|_| !none.should_sample()));
+        let all = TraceSampler::new(1.5);
+        assert!((0..100).all(|_| all.should_sample()));
+    }
+    
+    #[tokio::test]
+    async fn test_slow_query_logged_and_counted() {
+        let metrics = SharedMetrics::default();
+        let config = ObservabilityConfig { slow_query_threshold_ms: 20, ..Default::default() };
+        let observer = QueryObserver::new(metrics.clone(), &config);
+        
+        let fast = observer.observe("employees.find_by_id", async { 1 }).await;
+        let slow = observer
+            .observe("payroll.list_items", async {
+                tokio::time::sleep(Duration::from_millis(30)).await;
+                2
+            })
+            .await;
+        assert_eq!((fast, slow), (1, 2));
+        
+        let registry = metrics.lock().unwrap();
+        assert_eq!(registry.counter("db_slow_queries_total{query=\"payroll.list_items\"}"), 1);
+        assert_eq!(registry.counter("db_slow_queries_total{query=\"employees.find_by_id\"}"), 0);
+        assert!(registry.export_prometheus().contains("db_query_duration_seconds_count 2"));
+    }
+    
+    #[tokio::test]
+    async fn test_version_endpoint() {
+        use axum::{body::Body, http::Request, routing::get, Router};
+        use tower::ServiceExt;
+        
+        let app: Router = Router::new().route("/api/v1/version", get(version_handler));
+        let request = Request::builder().uri("/api/v1/version").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let info: BuildInfo = serde_json::from_slice(&body).unwrap();
+        assert!(!info.version.is_empty());
+        assert!(!info.git_commit.is_empty());
+        assert!(info.supported_countries > 0);
     }
     
     #[test]
@@ -396,4 +1027,64 @@ mod tests {
             .count();
         assert!(critical_count >= 3);
     }
+    
+    fn healthy_registry() -> MetricsRegistry {
+        let mut registry = MetricsRegistry::new();
+        registry.increment_with_labels("http_requests_total", &[("status", "200")], 1000);
+        registry.set_gauge("payroll_pending_calculations", 12.0);
+        registry.set_gauge_with_labels("up", &[("job", "tax-engine")], 1.0);
+        registry.set_gauge_with_labels("up", &[("job", "payment-processor")], 1.0);
+        registry
+    }
+    
+    #[test]
+    fn test_high_error_rate_fires() {
+        let mut registry = healthy_registry();
+        registry.increment_with_labels("http_requests_total", &[("status", "500")], 50);
+        registry.increment_with_labels("http_requests_total", &[("status", "503")], 10);
+        
+        let firing = evaluate_alerts(&standard_alerts(), &registry);
+        
+        assert_eq!(firing.len(), 1);
+        assert_eq!(firing[0].name, "HighErrorRate");
+        assert_eq!(firing[0].severity, AlertSeverity::Critical);
+        assert!((firing[0].value - 60.0 / 1060.0).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_healthy_state_fires_nothing() {
+        let firing = evaluate_alerts(&standard_alerts(), &healthy_registry());
+        assert!(firing.is_empty());
+        
+        // No data at all is not an outage either
+        let firing = evaluate_alerts(&standard_alerts(), &MetricsRegistry::new());
+        assert!(firing.is_empty());
+    }
+    
+    #[test]
+    fn test_service_down_and_backlog_fire() {
+        let mut registry = healthy_registry();
+        registry.set_gauge_with_labels("up", &[("job", "tax-engine")], 0.0);
+        registry.set_gauge("payroll_pending_calculations", 25_000.0);
+        
+        let names: Vec<String> = evaluate_alerts(&standard_alerts(), &registry)
+            .into_iter()
+            .map(|a| a.name)
+            .collect();
+        assert_eq!(names, vec!["PayrollCalculationBacklog", "TaxEngineDown"]);
+    }
+    
+    #[test]
+    fn test_unsupported_expression_is_skipped() {
+        let alerts = vec![AlertDefinition {
+            name: "Quantile".to_string(),
+            severity: AlertSeverity::Warning,
+            expr: "histogram_quantile(0.99, sum(rate(x_bucket[5m])) by (le)) > 2".to_string(),
+            duration: "5m".to_string(),
+            summary: String::new(),
+            description: String::new(),
+        }];
+        
+        assert!(evaluate_alerts(&alerts, &healthy_registry()).is_empty());
+    }
 }
@@ -0,0 +1,400 @@
+//! Scheduled Jobs
+//!
+//! Leave accrual, effective-dated changes, stuck payroll run recovery, and
+//! data retention run on timers. "Month-end" and "midnight" depend on the
+//! business timezone, so schedules are evaluated in a configured IANA
+//! timezone, daylight saving included, and converted back to UTC instants.
+//! A wall-clock time skipped by a spring-forward fires at the first instant
+//! after the gap; one repeated by a fall-back fires once, at the earlier.
+//! A job never overlaps itself: a trigger that arrives while the previous
+//! run is still active is skipped.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+
+/// Well-known job names used as schedule keys
+pub const ACCRUAL_JOB: &str = "leave_accrual";
+pub const PENDING_CHANGES_JOB: &str = "apply_pending_changes";
+pub const STUCK_RUN_RECOVERY_JOB: &str = "recover_stuck_runs";
+pub const RETENTION_JOB: &str = "retention";
+
+/// How often a job repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cadence {
+    /// Every hour at the minute and second of `at`
+    Hourly,
+    Daily,
+    Weekly(Weekday),
+    /// Day of month, clamped to the last day in shorter months
+    Monthly(u32),
+    MonthEnd,
+}
+
+/// When a job fires, in local time of the scheduler's timezone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schedule {
+    pub every: Cadence,
+    pub at: NaiveTime,
+}
+
+impl Schedule {
+    pub fn new(every: Cadence, at: NaiveTime) -> Self {
+        Self { every, at }
+    }
+
+    /// First firing instant strictly after `after`
+    pub fn next_after(&self, after: DateTime<Utc>, tz: Tz) -> DateTime<Utc> {
+        let mut local = after.with_timezone(&tz).naive_local();
+        loop {
+            let next = self.next_local_after(local);
+            let instant = resolve_local(tz, next).with_timezone(&Utc);
+            // In a repeated hour the next wall-clock time can map to an
+            // instant already past
+            if instant > after {
+                return instant;
+            }
+            local = next;
+        }
+    }
+
+    fn next_local_after(&self, local: NaiveDateTime) -> NaiveDateTime {
+        match self.every {
+            Cadence::Hourly => {
+                let candidate = local
+                    .date()
+                    .and_hms_opt(local.hour(), self.at.minute(), self.at.second())
+                    .expect("valid wall-clock time");
+                if candidate > local { candidate } else { candidate + Duration::hours(1) }
+            }
+            Cadence::Daily => first_day_after(local, self.at, |_| true),
+            Cadence::Weekly(weekday) => first_day_after(local, self.at, |d| d.weekday() == weekday),
+            Cadence::Monthly(day) => first_month_day_after(local, self.at, |y, m| day.clamp(1, days_in_month(y, m))),
+            Cadence::MonthEnd => first_month_day_after(local, self.at, days_in_month),
+        }
+    }
+}
+
+/// The instant a wall-clock time names in `tz`: the earlier of a repeated
+/// time, or the first instant after a skipped one
+fn resolve_local(tz: Tz, local: NaiveDateTime) -> DateTime<Tz> {
+    let mut probe = local;
+    loop {
+        match tz.from_local_datetime(&probe) {
+            LocalResult::Single(instant) => return instant,
+            LocalResult::Ambiguous(earlier, _) => return earlier,
+            LocalResult::None => probe += Duration::minutes(1),
+        }
+    }
+}
+
+fn first_day_after(local: NaiveDateTime, at: NaiveTime, matches: impl Fn(NaiveDate) -> bool) -> NaiveDateTime {
+    let mut date = local.date();
+    loop {
+        let candidate = date.and_time(at);
+        if candidate > local && matches(date) {
+            return candidate;
+        }
+        date = date.succ_opt().expect("date within range");
+    }
+}
+
+fn first_month_day_after(
+    local: NaiveDateTime,
+    at: NaiveTime,
+    day_of: impl Fn(i32, u32) -> u32,
+) -> NaiveDateTime {
+    let (mut year, mut month) = (local.year(), local.month());
+    loop {
+        let date = NaiveDate::from_ymd_opt(year, month, day_of(year, month)).expect("clamped day is valid");
+        let candidate = date.and_time(at);
+        if candidate > local {
+            return candidate;
+        }
+        (year, month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .map(|d| d.day())
+        .unwrap_or(28)
+}
+
+/// Scheduler settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulerConfig {
+    /// Business timezone, e.g. "Africa/Lagos"
+    pub timezone: Tz,
+    pub jobs: HashMap<String, Schedule>,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        let at = |h, m| NaiveTime::from_hms_opt(h, m, 0).expect("valid time");
+        let jobs = HashMap::from([
+            (ACCRUAL_JOB.to_string(), Schedule::new(Cadence::MonthEnd, at(23, 0))),
+            (PENDING_CHANGES_JOB.to_string(), Schedule::new(Cadence::Daily, at(0, 5))),
+            (STUCK_RUN_RECOVERY_JOB.to_string(), Schedule::new(Cadence::Hourly, at(0, 15))),
+            (RETENTION_JOB.to_string(), Schedule::new(Cadence::Daily, at(2, 0))),
+        ]);
+        Self { timezone: chrono_tz::Africa::Lagos, jobs }
+    }
+}
+
+/// What a trigger did with a due job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerOutcome {
+    Started,
+    /// The previous run was still active
+    SkippedOverlap,
+}
+
+/// One due job seen by `tick`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobTrigger {
+    pub job: String,
+    pub scheduled_for: DateTime<Utc>,
+    pub outcome: TriggerOutcome,
+}
+
+/// Job body; receives the scheduled instant in the business timezone
+type JobTask = Arc<dyn Fn(DateTime<Tz>) -> BoxFuture<'static, ()> + Send + Sync>;
+
+struct ScheduledJob {
+    schedule: Schedule,
+    task: JobTask,
+    running: Arc<AtomicBool>,
+    next_run: DateTime<Utc>,
+}
+
+/// Clears the running flag when a run finishes, including by panic
+struct RunningGuard(Arc<AtomicBool>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// Cron-like scheduler for background jobs
+pub struct JobScheduler {
+    config: SchedulerConfig,
+    jobs: Mutex<HashMap<String, ScheduledJob>>,
+}
+
+impl JobScheduler {
+    pub fn new(config: SchedulerConfig) -> Self {
+        Self { config, jobs: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn timezone(&self) -> Tz {
+        self.config.timezone
+    }
+
+    /// Attach a job body to its configured schedule; first run is the next
+    /// scheduled instant after `from`. Returns false when the config has no
+    /// schedule for `name`.
+    pub fn register<F, Fut>(&self, name: &str, from: DateTime<Utc>, task: F) -> bool
+    where
+        F: Fn(DateTime<Tz>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let Some(schedule) = self.config.jobs.get(name).copied() else { return false };
+        let task: JobTask = Arc::new(move |at| Box::pin(task(at)));
+        let job = ScheduledJob {
+            schedule,
+            task,
+            running: Arc::new(AtomicBool::new(false)),
+            next_run: schedule.next_after(from, self.config.timezone),
+        };
+        self.jobs.lock().expect("scheduler lock poisoned").insert(name.to_string(), job);
+        true
+    }
+
+    pub fn next_run(&self, name: &str) -> Option<DateTime<Utc>> {
+        self.jobs.lock().expect("scheduler lock poisoned").get(name).map(|j| j.next_run)
+    }
+
+    pub fn is_running(&self, name: &str) -> bool {
+        self.jobs
+            .lock()
+            .expect("scheduler lock poisoned")
+            .get(name)
+            .is_some_and(|j| j.running.load(Ordering::Acquire))
+    }
+
+    /// Start every job due at `now`. Missed instants collapse into one
+    /// trigger; the next run is the first scheduled instant after `now`.
+    pub fn tick(&self, now: DateTime<Utc>) -> Vec<JobTrigger> {
+        let mut jobs = self.jobs.lock().expect("scheduler lock poisoned");
+        let mut triggers = Vec::new();
+        for (name, job) in jobs.iter_mut().filter(|(_, j)| j.next_run <= now) {
+            let scheduled_for = job.next_run;
+            job.next_run = job.schedule.next_after(now, self.config.timezone);
+
+            let outcome = if job.running.swap(true, Ordering::AcqRel) {
+                tracing::warn!(job = %name, %scheduled_for, "Previous run still active; skipping");
+                TriggerOutcome::SkippedOverlap
+            } else {
+                let guard = RunningGuard(job.running.clone());
+                let run = (job.task)(scheduled_for.with_timezone(&self.config.timezone));
+                tokio::spawn(async move {
+                    let _guard = guard;
+                    run.await;
+                });
+                TriggerOutcome::Started
+            };
+            triggers.push(JobTrigger { job: name.clone(), scheduled_for, outcome });
+        }
+        triggers.sort_by(|a, b| a.job.cmp(&b.job));
+        triggers
+    }
+
+    /// Sleep until the next due job and trigger it, forever
+    pub async fn run(self: Arc<Self>) {
+        loop {
+            let next = self.jobs.lock().expect("scheduler lock poisoned").values().map(|j| j.next_run).min();
+            let Some(next) = next else { return };
+            if let Ok(wait) = (next - Utc::now()).to_std() {
+                tokio::time::sleep(wait).await;
+            }
+            self.tick(Utc::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use tokio::sync::Notify;
+
+    use crate::compliance::{AuditLogStore, RetentionJob, RetentionPolicy};
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn scheduler(name: &str, schedule: Schedule) -> JobScheduler {
+        JobScheduler::new(SchedulerConfig {
+            timezone: chrono_tz::Africa::Lagos,
+            jobs: HashMap::from([(name.to_string(), schedule)]),
+        })
+    }
+
+    #[test]
+    fn test_month_end_resolved_in_business_timezone() {
+        let tz = chrono_tz::Africa::Lagos;
+        let schedule = Schedule::new(Cadence::MonthEnd, NaiveTime::from_hms_opt(23, 30, 0).unwrap());
+
+        // 23:30 on 29 Feb in Lagos is 22:30 UTC
+        assert_eq!(schedule.next_after(utc(2028, 2, 10, 0, 0), tz), utc(2028, 2, 29, 22, 30));
+        // Already past this month's run: roll to 31 March
+        assert_eq!(schedule.next_after(utc(2028, 2, 29, 22, 30), tz), utc(2028, 3, 31, 22, 30));
+
+        let monthly = Schedule::new(Cadence::Monthly(31), NaiveTime::from_hms_opt(0, 30, 0).unwrap());
+        // 00:30 local on 30 April is still 29 April in UTC
+        assert_eq!(monthly.next_after(utc(2028, 4, 2, 0, 0), tz), utc(2028, 4, 29, 23, 30));
+    }
+
+    #[test]
+    fn test_schedules_follow_daylight_saving() {
+        let tz = chrono_tz::Europe::London;
+        let daily = |h, m| Schedule::new(Cadence::Daily, NaiveTime::from_hms_opt(h, m, 0).unwrap());
+
+        // 02:00 on the night of 1 July is 01:00 UTC in summer, 02:00 UTC in winter
+        assert_eq!(daily(2, 0).next_after(utc(2026, 7, 1, 0, 0), tz), utc(2026, 7, 1, 1, 0));
+        assert_eq!(daily(2, 0).next_after(utc(2026, 12, 1, 0, 0), tz), utc(2026, 12, 1, 2, 0));
+
+        // 01:30 doesn't exist on 29 March 2026: fire as the clocks go forward
+        assert_eq!(daily(1, 30).next_after(utc(2026, 3, 28, 12, 0), tz), utc(2026, 3, 29, 1, 0));
+        // 01:30 happens twice on 25 October 2026: fire once, at the first
+        let first = daily(1, 30).next_after(utc(2026, 10, 24, 12, 0), tz);
+        assert_eq!(first, utc(2026, 10, 25, 0, 30));
+        assert_eq!(daily(1, 30).next_after(first, tz), utc(2026, 10, 26, 1, 30));
+    }
+
+    #[tokio::test]
+    async fn test_job_fires_at_scheduled_instant() {
+        let scheduler = scheduler(RETENTION_JOB, Schedule::new(Cadence::Daily, NaiveTime::from_hms_opt(2, 0, 0).unwrap()));
+        let audit = AuditLogStore::new();
+        let retention = RetentionJob::new(RetentionPolicy::default(), audit);
+        let tenant_id = uuid::Uuid::new_v4();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let seen = runs.clone();
+        assert!(scheduler.register(RETENTION_JOB, utc(2026, 5, 1, 12, 0), move |at| {
+            let (retention, seen) = (retention.clone(), seen.clone());
+            async move {
+                retention.run(tenant_id, at.date_naive());
+                seen.fetch_add(1, Ordering::SeqCst);
+            }
+        }));
+        assert!(!scheduler.register(ACCRUAL_JOB, utc(2026, 5, 1, 12, 0), |_| async {}));
+
+        // 02:00 in UTC+1 is 01:00 UTC
+        let due = utc(2026, 5, 2, 1, 0);
+        assert_eq!(scheduler.next_run(RETENTION_JOB), Some(due));
+        assert!(scheduler.tick(due - Duration::seconds(1)).is_empty());
+
+        let triggers = scheduler.tick(due);
+        assert_eq!(triggers, vec![JobTrigger {
+            job: RETENTION_JOB.to_string(),
+            scheduled_for: due,
+            outcome: TriggerOutcome::Started,
+        }]);
+        assert_eq!(scheduler.next_run(RETENTION_JOB), Some(due + Duration::days(1)));
+
+        while scheduler.is_running(RETENTION_JOB) {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_trigger_is_skipped() {
+        let scheduler = scheduler(STUCK_RUN_RECOVERY_JOB, Schedule::new(Cadence::Hourly, NaiveTime::from_hms_opt(0, 15, 0).unwrap()));
+        let release = Arc::new(Notify::new());
+        let runs = Arc::new(AtomicUsize::new(0));
+        let (gate, seen) = (release.clone(), runs.clone());
+        scheduler.register(STUCK_RUN_RECOVERY_JOB, utc(2026, 5, 1, 9, 0), move |_| {
+            let (gate, seen) = (gate.clone(), seen.clone());
+            async move {
+                seen.fetch_add(1, Ordering::SeqCst);
+                gate.notified().await;
+            }
+        });
+
+        let first = scheduler.tick(utc(2026, 5, 1, 9, 15));
+        assert_eq!(first[0].outcome, TriggerOutcome::Started);
+        assert!(scheduler.is_running(STUCK_RUN_RECOVERY_JOB));
+
+        // Still stuck an hour later: the next trigger is skipped, not queued
+        let second = scheduler.tick(utc(2026, 5, 1, 10, 15));
+        assert_eq!(second[0].outcome, TriggerOutcome::SkippedOverlap);
+        assert_eq!(scheduler.next_run(STUCK_RUN_RECOVERY_JOB), Some(utc(2026, 5, 1, 11, 15)));
+
+        release.notify_one();
+        while scheduler.is_running(STUCK_RUN_RECOVERY_JOB) {
+            tokio::task::yield_now().await;
+        }
+        let third = scheduler.tick(utc(2026, 5, 1, 11, 15));
+        assert_eq!(third[0].outcome, TriggerOutcome::Started);
+        while runs.load(Ordering::SeqCst) < 2 {
+            tokio::task::yield_now().await;
+        }
+        release.notify_one();
+    }
+}
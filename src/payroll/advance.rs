@@ -0,0 +1,151 @@
+//! Salary Advances
+//!
+//! An advance is paid off-cycle, between regular runs, and recovered from
+//! net pay over the following runs through a repayment schedule. The
+//! payment goes through the disbursement ledger like any net pay, and
+//! `gl::advance_journal` books it against an advances receivable account
+//! that the recoveries later clear.
+//!
+//! Tax follows the employee's country. Most countries don't tax an advance
+//! when paid: the wages it anticipates are taxed in full by the regular
+//! run, and recovery is an after-tax deduction, so the same income is never
+//! taxed twice. Where the authority treats an advance of wages as wages
+//! when paid, tax is withheld from it at a flat rate; the regular runs
+//! still tax the wages, so that withholding is a prepayment credited
+//! against the year's tax.
+
+use std::sync::Arc;
+use chrono::{DateTime, Datelike, Utc};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Payslip line for advance recovery in `other_deductions`
+pub const SALARY_ADVANCE_LINE: &str = "salary_advance";
+
+/// How a country taxes a salary advance when it is paid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdvanceTaxTreatment {
+    /// Nothing withheld; the regular runs tax the wages it anticipates
+    Deferred,
+    /// Withheld at a flat rate when paid, credited against the year's tax
+    WithheldWhenPaid { rate: Decimal },
+}
+
+impl AdvanceTaxTreatment {
+    pub fn for_country(country_code: &str) -> Self {
+        match country_code.to_ascii_uppercase().as_str() {
+            // IRS: advances of wages are wages when paid, withheld at the
+            // supplemental rate
+            "US" => Self::WithheldWhenPaid { rate: dec!(0.22) },
+            _ => Self::Deferred,
+        }
+    }
+
+    /// Tax withheld from an advance of `amount`, unrounded
+    pub fn tax_on(&self, amount: Decimal) -> Decimal {
+        match self {
+            Self::Deferred => Decimal::ZERO,
+            Self::WithheldWhenPaid { rate } => amount * rate,
+        }
+    }
+}
+
+/// An off-cycle advance of net pay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SalaryAdvance {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    /// Advanced before tax; this is what later runs recover
+    pub amount: Decimal,
+    pub currency: String,
+    pub tax_treatment: AdvanceTaxTreatment,
+    pub tax_withheld: Decimal,
+    /// Paid to the employee: the amount less any tax withheld
+    pub net_paid: Decimal,
+    /// Payment in the disbursement ledger, keyed by this advance's id in
+    /// place of a payroll run
+    pub disbursement_id: Uuid,
+    pub recover_over_periods: u32,
+    /// Schedule that recovers the advance from later runs
+    pub repayment_schedule_id: Uuid,
+    pub paid_at: DateTime<Utc>,
+}
+
+/// Advances paid per employee
+#[derive(Debug, Clone, Default)]
+pub struct SalaryAdvances {
+    // In real implementation, backed by the off_cycle_payments table
+    advances: Arc<DashMap<Uuid, Vec<SalaryAdvance>>>,
+}
+
+impl SalaryAdvances {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, advance: SalaryAdvance) {
+        self.advances.entry(advance.employee_id).or_default().push(advance);
+    }
+
+    pub fn for_employee(&self, employee_id: Uuid) -> Vec<SalaryAdvance> {
+        self.advances.get(&employee_id).map(|a| a.clone()).unwrap_or_default()
+    }
+
+    pub fn get(&self, advance_id: Uuid) -> Option<SalaryAdvance> {
+        self.advances.iter().find_map(|advances| advances.iter().find(|a| a.id == advance_id).cloned())
+    }
+
+    /// Tax withheld from an employee's advances paid in `year`, to credit
+    /// against the year's tax
+    pub fn tax_withheld(&self, employee_id: Uuid, year: i32) -> Decimal {
+        self.for_employee(employee_id)
+            .iter()
+            .filter(|a| a.paid_at.year() == year)
+            .map(|a| a.tax_withheld)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payroll::gl::{advance_journal, GlAccountMap, JournalLine};
+
+    #[test]
+    fn test_advance_taxed_when_paid_where_required() {
+        let treatment = AdvanceTaxTreatment::for_country("us");
+        assert_eq!(treatment, AdvanceTaxTreatment::WithheldWhenPaid { rate: dec!(0.22) });
+        assert_eq!(AdvanceTaxTreatment::for_country("NG").tax_on(dec!(1_000)), Decimal::ZERO);
+
+        let advance = SalaryAdvance {
+            id: Uuid::new_v4(),
+            employee_id: Uuid::new_v4(),
+            amount: dec!(1_000),
+            currency: "USD".to_string(),
+            tax_treatment: treatment,
+            tax_withheld: treatment.tax_on(dec!(1_000)),
+            net_paid: dec!(780),
+            disbursement_id: Uuid::new_v4(),
+            recover_over_periods: 2,
+            repayment_schedule_id: Uuid::new_v4(),
+            paid_at: Utc::now(),
+        };
+        let advances = SalaryAdvances::new();
+        advances.record(advance.clone());
+        assert_eq!(advances.tax_withheld(advance.employee_id, advance.paid_at.year()), dec!(220));
+        assert_eq!(advances.tax_withheld(advance.employee_id, advance.paid_at.year() - 1), Decimal::ZERO);
+
+        // The full advance is receivable; the withholding is owed to the authority
+        let accounts = GlAccountMap::default();
+        let journal = advance_journal(&advance, &accounts);
+        assert!(journal.is_balanced());
+        let line = |account: &str| journal.lines.iter().find(|l| l.account == account).map(JournalLine::amount);
+        assert_eq!(line(&accounts.advances_receivable), Some(dec!(1_000)));
+        assert_eq!(line(&accounts.income_tax_payable), Some(dec!(-220)));
+        assert_eq!(line(&accounts.net_pay_clearing), Some(dec!(-780)));
+    }
+}
@@ -5,8 +5,8 @@
 //! - Tier 2: EcoCash, Telebirr, Flutterwave, Paystack
 //! - Smart routing by country and phone prefix
 
+use async_trait::async_trait;
 use rust_decimal::Decimal;
-use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -50,6 +50,19 @@ pub struct PaymentResponse {
     pub provider_message: String,
 }
 
+/// Mobile money gateway errors
+#[derive(Debug, thiserror::Error)]
+pub enum GatewayError {
+    #[error("Mobile money provider error: {0}")]
+    Provider(String),
+}
+
+/// Provider API that executes payment requests
+#[async_trait]
+pub trait MobileMoneyGateway: Send + Sync {
+    async fn send(&self, request: &PaymentRequest) -> Result<PaymentResponse, GatewayError>;
+}
+
 /// Provider routing table by country
 pub struct ProviderRouter {
     country_providers: HashMap<String, Vec<String>>,
@@ -160,8 +173,8 @@ impl ProviderRouter {
         ].into_iter().collect();
         
         if let Some(prefix) = prefixes.get(country) {
-            if phone.starts_with(prefix) {
-                return format!("0{}", &phone[prefix.len()..]);
+            if let Some(local) = phone.strip_prefix(prefix) {
+                return format!("0{}", local);
             }
         }
         phone.to_string()
@@ -0,0 +1,220 @@
+//! Annualization
+//!
+//! Engines tax a pay period by annualizing its pay and spreading the annual
+//! tax back over the year's periods. Weekly cycles don't divide a calendar
+//! year evenly: a biweekly year has 27 pay dates when the first falls early
+//! enough, and a weekly year can have 53. Dividing by the nominal 26 in such
+//! a year understates annual pay and withholds short of the liability.
+
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::{PayFrequency, WorkingTime};
+use super::calendar::PayrollCalendar;
+
+const MONTHS_PER_YEAR: Decimal = Decimal::from_parts(12, 0, 0, false, 0);
+
+/// How many periods a year is annualized over
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtraPeriodPolicy {
+    /// The frequency's nominal count (52 weekly, 26 biweekly, 13 four-weekly)
+    Nominal,
+    /// The number of pay dates actually falling in the calendar year
+    #[default]
+    Actual,
+    /// The actual count, with each period's tax rounded to the currency and
+    /// the year's last period taking the remainder, so the instalments sum
+    /// exactly to the annual liability
+    Smoothed,
+}
+
+/// Periods one pay period's figures are annualized over
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annualization {
+    pub periods: Decimal,
+    /// 1-based position of the period being taxed, when known
+    pub period_number: Option<u32>,
+    pub smoothed: bool,
+    /// Places instalments are rounded to when smoothed
+    pub decimal_places: u32,
+}
+
+impl Annualization {
+    /// The frequency's nominal periods per year
+    pub fn nominal(frequency: &PayFrequency) -> Self {
+        Self {
+            periods: frequency.periods_per_year(&WorkingTime::default()),
+            period_number: None,
+            smoothed: false,
+            decimal_places: 2,
+        }
+    }
+
+    /// Annualization for the period paid on `pay_date`, counting the pay
+    /// dates `calendar` schedules in that calendar year. Frequencies the
+    /// calendar can't schedule keep their nominal count.
+    pub fn for_pay_date(
+        calendar: &PayrollCalendar,
+        frequency: &PayFrequency,
+        pay_date: NaiveDate,
+        policy: ExtraPeriodPolicy,
+    ) -> Self {
+        let nominal = Self::nominal(frequency);
+        if policy == ExtraPeriodPolicy::Nominal {
+            return nominal;
+        }
+        match calendar.pay_periods(frequency, pay_date, pay_date.year()) {
+            Ok(periods) if !periods.is_empty() => Self {
+                periods: Decimal::from(periods.len()),
+                period_number: periods
+                    .iter()
+                    .find(|p| p.pay_date == pay_date || p.period_end == pay_date)
+                    .map(|p| p.number),
+                smoothed: policy == ExtraPeriodPolicy::Smoothed,
+                ..nominal
+            },
+            _ => nominal,
+        }
+    }
+
+    pub fn with_decimal_places(mut self, decimal_places: u32) -> Self {
+        self.decimal_places = decimal_places;
+        self
+    }
+
+    /// A period's amount as a year's
+    pub fn annualize(&self, amount: Decimal) -> Decimal {
+        amount * self.periods
+    }
+
+    /// A period's amount as a month's, for engines whose tables are monthly
+    pub fn monthly_equivalent(&self, amount: Decimal) -> Decimal {
+        amount * self.periods / MONTHS_PER_YEAR
+    }
+
+    /// A monthly amount, such as a contribution cap, scaled to one period
+    pub fn monthly_to_period(&self, monthly: Decimal) -> Decimal {
+        monthly * MONTHS_PER_YEAR / self.periods
+    }
+
+    /// This period's share of an annual amount
+    pub fn period_share(&self, annual: Decimal) -> Decimal {
+        match self.period_number {
+            Some(number) if self.smoothed => self.share_of_period(annual, number),
+            _ => annual / self.periods,
+        }
+    }
+
+    /// Every period's share of an annual amount, in order
+    pub fn instalments(&self, annual: Decimal) -> Vec<Decimal> {
+        let count = self.periods.to_u32().unwrap_or(0);
+        (1..=count).map(|number| self.share_of_period(annual, number)).collect()
+    }
+
+    fn share_of_period(&self, annual: Decimal, number: u32) -> Decimal {
+        if !self.smoothed {
+            return annual / self.periods;
+        }
+        let even = (annual / self.periods).round_dp_with_strategy(self.decimal_places, RoundingStrategy::MidpointAwayFromZero);
+        if Decimal::from(number) == self.periods {
+            annual - even * (self.periods - Decimal::ONE)
+        } else {
+            even
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use crate::payroll::tax_calculator::NigerianTaxCalculator;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_detects_27_period_biweekly_year() {
+        let calendar = PayrollCalendar::for_country("US");
+        // Fortnightly Fridays from 1 January 2021 give 27 pay dates; from 8 January, 26
+        let long = Annualization::for_pay_date(&calendar, &PayFrequency::BiWeekly, date(2021, 12, 31), ExtraPeriodPolicy::Actual);
+        assert_eq!((long.periods, long.period_number), (dec!(27), Some(27)));
+
+        let short = Annualization::for_pay_date(&calendar, &PayFrequency::BiWeekly, date(2021, 1, 8), ExtraPeriodPolicy::Actual);
+        assert_eq!((short.periods, short.period_number), (dec!(26), Some(1)));
+
+        let nominal = Annualization::for_pay_date(&calendar, &PayFrequency::BiWeekly, date(2021, 12, 31), ExtraPeriodPolicy::Nominal);
+        assert_eq!((nominal.periods, nominal.period_number), (dec!(26), None));
+        let monthly = Annualization::for_pay_date(&calendar, &PayFrequency::Monthly, date(2024, 6, 30), ExtraPeriodPolicy::Actual);
+        assert_eq!(monthly.periods, dec!(12));
+    }
+
+    #[test]
+    fn test_27_period_year_withholds_annual_liability() {
+        let calendar = PayrollCalendar::for_country("NG");
+        let calculator = NigerianTaxCalculator::new();
+        let per_period = dec!(230_000);
+        let pay_date = date(2021, 1, 1);
+        let liability = calculator.calculate_annual_paye(per_period * dec!(27), Decimal::ZERO, Decimal::ZERO).annual_tax;
+
+        let actual = Annualization::for_pay_date(&calendar, &PayFrequency::BiWeekly, pay_date, ExtraPeriodPolicy::Actual);
+        let tax = calculator.calculate_annualized_paye(per_period, Decimal::ZERO, Decimal::ZERO, &actual).period_tax;
+        assert_eq!((tax * dec!(27)).round_dp(6), liability);
+
+        // Dividing by 26 falls short of what 27 paydays owe
+        let nominal = Annualization::nominal(&PayFrequency::BiWeekly);
+        let short = calculator.calculate_annualized_paye(per_period, Decimal::ZERO, Decimal::ZERO, &nominal).period_tax;
+        assert!(short * dec!(27) < liability);
+
+        let smoothed = Annualization::for_pay_date(&calendar, &PayFrequency::BiWeekly, pay_date, ExtraPeriodPolicy::Smoothed);
+        let instalments = smoothed.instalments(liability);
+        assert_eq!(instalments.len(), 27);
+        assert_eq!(instalments.iter().sum::<Decimal>(), liability);
+        assert!(instalments[..26].iter().all(|i| i.round_dp(2) == *i));
+        assert_eq!(
+            calculator.calculate_annualized_paye(per_period, Decimal::ZERO, Decimal::ZERO, &smoothed).period_tax,
+            instalments[0]
+        );
+    }
+
+    #[test]
+    fn test_engines_tax_a_year_the_same_whatever_the_frequency() {
+        use crate::payroll::{
+            ArgentinaTaxCalculator, AustrianTaxCalculator, Bundesland, PeruTaxCalculator, PhilippinesTaxCalculator,
+            SingaporeTaxCalculator, TurkeyTaxCalculator,
+        };
+
+        let monthly = Annualization::nominal(&PayFrequency::Monthly);
+        let weekly = Annualization::nominal(&PayFrequency::Weekly);
+        // The same annual pay, a month's worth and a week's worth at a time
+        let (month, week) = (dec!(52_000), dec!(12_000));
+        let close = |a: Decimal, b: Decimal| (a - b).abs() < dec!(0.01);
+
+        let tr = |pay, a: &Annualization| TurkeyTaxCalculator::calculate_annualized(pay, a).gelir_vergisi * a.periods;
+        assert!(close(tr(month, &monthly), tr(week, &weekly)));
+        let ph = |pay, a: &Annualization| PhilippinesTaxCalculator::calculate_annualized(pay, a).income_tax * a.periods;
+        assert!(close(ph(month, &monthly), ph(week, &weekly)));
+
+        let sg = SingaporeTaxCalculator::new();
+        let sg_tax = |pay, a: &Annualization| {
+            sg.calculate_annualized(pay, Decimal::ZERO, a.annualize(pay), Decimal::ZERO, a).estimated_tax * a.periods
+        };
+        // Each period's tax is rounded to the cent
+        assert!((sg_tax(month, &monthly) - sg_tax(week, &weekly)).abs() < dec!(0.60));
+
+        // Statutory extra salaries stay a month's pay each
+        let ar = ArgentinaTaxCalculator::new();
+        let ar_tax = |pay, a: &Annualization| ar.calculate_annualized(pay * dec!(100), false, 0, a).income_tax * a.periods;
+        assert!(ar_tax(month, &monthly) > Decimal::ZERO && close(ar_tax(month, &monthly), ar_tax(week, &weekly)));
+        let pe = PeruTaxCalculator::new();
+        let pe_tax = |pay, a: &Annualization| pe.calculate_annualized(pay, true, a).income_tax * a.periods;
+        assert!(pe_tax(month, &monthly) > Decimal::ZERO && close(pe_tax(month, &monthly), pe_tax(week, &weekly)));
+        let at = AustrianTaxCalculator::new(Bundesland::Wien);
+        let at_net = |pay, a: &Annualization| at.calculate_annualized(pay / dec!(10), a).net_monthly * a.periods;
+        assert!(close(at_net(month, &monthly), at_net(week, &weekly)));
+    }
+}
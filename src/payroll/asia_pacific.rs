@@ -15,6 +15,9 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::value_objects::PayFrequency;
+use super::annualization::Annualization;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // INDIA (IN) - New Tax Regime + PF + Professional Tax
 // ═══════════════════════════════════════════════════════════════════════════
@@ -116,6 +119,16 @@ pub enum IndonesiaMaritalStatus { Single, Married, MarriedSpouseWorking }
 
 impl IndonesiaTaxCalculator {
     pub fn calculate_monthly(gross_monthly: Decimal, status: IndonesiaMaritalStatus) -> IndonesiaTaxResult {
+        Self::calculate_annualized(gross_monthly, status, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// PPh 21 and BPJS on one pay period's pay, annualized over the periods
+    /// in `annualization`; the result holds per-period amounts
+    pub fn calculate_annualized(
+        gross_monthly: Decimal,
+        status: IndonesiaMaritalStatus,
+        annualization: &Annualization,
+    ) -> IndonesiaTaxResult {
         // PTKP (Non-Taxable Income) annual values
         let ptkp_annual = match status {
             IndonesiaMaritalStatus::Single => dec!(54000000),
@@ -123,9 +136,9 @@ impl IndonesiaTaxCalculator {
             IndonesiaMaritalStatus::MarriedSpouseWorking => dec!(54000000),
         };
         
-        let ptkp_monthly = ptkp_annual / dec!(12);
+        let ptkp_monthly = ptkp_annual / annualization.periods;
         let taxable = (gross_monthly - ptkp_monthly).max(Decimal::ZERO);
-        let tax = Self::apply_ter(taxable * dec!(12)) / dec!(12);
+        let tax = annualization.period_share(Self::apply_ter(annualization.annualize(taxable)));
         
         // BPJS Ketenagakerjaan (JHT)
         let jht_ee = gross_monthly * dec!(0.02);
@@ -258,15 +271,20 @@ pub struct PhilippinesTaxCalculator;
 
 impl PhilippinesTaxCalculator {
     pub fn calculate_monthly(gross_monthly: Decimal) -> PhilippinesTaxResult {
-        let annual = gross_monthly * dec!(12);
-        let tax = Self::calculate_annual(annual) / dec!(12);
+        Self::calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// Withholding tax and contributions on one pay period's pay, with
+    /// contribution caps scaled to the period
+    pub fn calculate_annualized(gross_monthly: Decimal, annualization: &Annualization) -> PhilippinesTaxResult {
+        let tax = annualization.period_share(Self::calculate_annual(annualization.annualize(gross_monthly)));
         
         // SSS (Social Security) - simplified
-        let sss = (gross_monthly * dec!(0.045)).min(dec!(1350));
+        let sss = (gross_monthly * dec!(0.045)).min(annualization.monthly_to_period(dec!(1350)));
         // PhilHealth
-        let philhealth = (gross_monthly * dec!(0.025)).min(dec!(1800));
+        let philhealth = (gross_monthly * dec!(0.025)).min(annualization.monthly_to_period(dec!(1800)));
         // Pag-IBIG
-        let pagibig = dec!(100);
+        let pagibig = annualization.monthly_to_period(dec!(100));
         
         PhilippinesTaxResult {
             sahod: gross_monthly,
@@ -312,11 +330,16 @@ impl ThailandTaxCalculator {
     const SSF_CAP: Decimal = dec!(750);  // Monthly cap
     
     pub fn calculate_monthly(gross_monthly: Decimal) -> ThailandTaxResult {
-        let annual = gross_monthly * dec!(12);
-        let tax = Self::calculate_annual(annual) / dec!(12);
+        Self::calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// Tax and SSF on one pay period's pay; the SSF cap is scaled to the period
+    pub fn calculate_annualized(gross_monthly: Decimal, annualization: &Annualization) -> ThailandTaxResult {
+        let tax = annualization.period_share(Self::calculate_annual(annualization.annualize(gross_monthly)));
         
-        let ssf_ee = (gross_monthly * Self::SSF_EE).min(Self::SSF_CAP);
-        let ssf_er = (gross_monthly * Self::SSF_ER).min(Self::SSF_CAP);
+        let ssf_cap = annualization.monthly_to_period(Self::SSF_CAP);
+        let ssf_ee = (gross_monthly * Self::SSF_EE).min(ssf_cap);
+        let ssf_er = (gross_monthly * Self::SSF_ER).min(ssf_cap);
         
         ThailandTaxResult {
             ngoen_duan: gross_monthly,
@@ -367,8 +390,12 @@ impl MalaysiaTaxCalculator {
     const EIS_EE: Decimal = dec!(0.002);   // 0.2% EIS
     
     pub fn calculate_monthly(gross_monthly: Decimal) -> MalaysiaTaxResult {
-        let annual = gross_monthly * dec!(12);
-        let tax = Self::calculate_annual(annual) / dec!(12);
+        Self::calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// PCB, EPF, SOCSO, and EIS on one pay period's pay
+    pub fn calculate_annualized(gross_monthly: Decimal, annualization: &Annualization) -> MalaysiaTaxResult {
+        let tax = annualization.period_share(Self::calculate_annual(annualization.annualize(gross_monthly)));
         
         let epf_ee = gross_monthly * Self::EPF_EE;
         let epf_er = gross_monthly * Self::EPF_ER;
@@ -428,8 +455,12 @@ impl PakistanTaxCalculator {
     const EOBI_ER: Decimal = dec!(0.05);  // 5% EOBI (employer)
     
     pub fn calculate_monthly(gross_monthly: Decimal) -> PakistanTaxResult {
-        let annual = gross_monthly * dec!(12);
-        let tax = Self::calculate_annual(annual) / dec!(12);
+        Self::calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// Income tax and EOBI on one pay period's pay
+    pub fn calculate_annualized(gross_monthly: Decimal, annualization: &Annualization) -> PakistanTaxResult {
+        let tax = annualization.period_share(Self::calculate_annual(annualization.annualize(gross_monthly)));
         let eobi_ee = gross_monthly * Self::EOBI_EE;
         let eobi_er = gross_monthly * Self::EOBI_ER;
         
@@ -472,8 +503,12 @@ pub struct BangladeshTaxCalculator;
 
 impl BangladeshTaxCalculator {
     pub fn calculate_monthly(gross_monthly: Decimal) -> BangladeshTaxResult {
-        let annual = gross_monthly * dec!(12);
-        let tax = Self::calculate_annual(annual) / dec!(12);
+        Self::calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// Income tax and provident fund on one pay period's pay
+    pub fn calculate_annualized(gross_monthly: Decimal, annualization: &Annualization) -> BangladeshTaxResult {
+        let tax = annualization.period_share(Self::calculate_annual(annualization.annualize(gross_monthly)));
         
         // Provident fund (if applicable)
         let pf = gross_monthly * dec!(0.10);
@@ -0,0 +1,111 @@
+//! Department Budgets
+//!
+//! Departments carry a payroll budget per pay period. `budget_variance`
+//! compares it with a run's actual employer cost (gross plus employer
+//! contributions). Departments form a tree: a department's actual cost
+//! includes every department beneath it, and one without a budget of its
+//! own is budgeted at the sum of its children's.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::models::PayrollItem;
+
+/// A department and its payroll budget
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Department {
+    pub id: Uuid,
+    pub name: String,
+    pub parent_id: Option<Uuid>,
+    /// Employer cost budgeted per pay period, in payroll currency
+    pub budget: Option<Decimal>,
+}
+
+impl Department {
+    pub fn new(name: impl Into<String>, parent_id: Option<Uuid>, budget: Option<Decimal>) -> Self {
+        Self { id: Uuid::new_v4(), name: name.into(), parent_id, budget }
+    }
+}
+
+/// Budgeted vs actual employer cost for a department and its subtree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetVariance {
+    pub department_id: Uuid,
+    pub name: String,
+    pub budgeted: Decimal,
+    pub actual: Decimal,
+    /// Budget left over; negative when over budget
+    pub variance: Decimal,
+    /// Variance as a percentage of budget; `None` without a budget
+    pub variance_percent: Option<Decimal>,
+    pub over_budget: bool,
+    pub children: Vec<BudgetVariance>,
+}
+
+/// Department tree
+#[derive(Debug, Clone, Default)]
+pub struct Departments {
+    // In real implementation, backed by the departments table
+    departments: Arc<DashMap<Uuid, Department>>,
+}
+
+impl Departments {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn upsert(&self, department: Department) {
+        self.departments.insert(department.id, department);
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<Department> {
+        self.departments.get(&id).map(|d| d.clone())
+    }
+
+    fn children(&self, id: Uuid) -> Vec<Department> {
+        let mut children: Vec<Department> =
+            self.departments.iter().filter(|d| d.parent_id == Some(id)).map(|d| d.clone()).collect();
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+        children
+    }
+
+    /// Variance for `department` over `items`, with its children's; a
+    /// department is never visited twice, so a parent cycle can't recurse
+    pub fn variance(&self, department: &Department, items: &[PayrollItem]) -> BudgetVariance {
+        self.variance_inner(department, items, &mut HashSet::new())
+    }
+
+    fn variance_inner(&self, department: &Department, items: &[PayrollItem], visited: &mut HashSet<Uuid>) -> BudgetVariance {
+        visited.insert(department.id);
+        let mut children = Vec::new();
+        for child in self.children(department.id) {
+            if !visited.contains(&child.id) {
+                children.push(self.variance_inner(&child, items, visited));
+            }
+        }
+
+        let own: Decimal = items
+            .iter()
+            .filter(|item| item.department_id == Some(department.id))
+            .map(PayrollItem::employer_cost)
+            .sum();
+        let actual = own + children.iter().map(|c| c.actual).sum::<Decimal>();
+        let budgeted = department.budget.unwrap_or_else(|| children.iter().map(|c| c.budgeted).sum());
+        let variance = budgeted - actual;
+
+        BudgetVariance {
+            department_id: department.id,
+            name: department.name.clone(),
+            budgeted,
+            actual,
+            variance,
+            variance_percent: (!budgeted.is_zero()).then(|| (variance / budgeted * Decimal::ONE_HUNDRED).round_dp(2)),
+            over_budget: actual > budgeted,
+            children,
+        }
+    }
+}
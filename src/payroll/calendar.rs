@@ -0,0 +1,410 @@
+//! Payroll Calendar
+//!
+//! Resolves nominal pay dates to actual check dates per region: weekend
+//! rules, public holidays, business-day roll policy, and the bank's
+//! direct-deposit cutoff before the check date. Also generates a year's
+//! pay-period schedule for a pay frequency.
+
+use std::collections::HashSet;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::PayFrequency;
+use crate::leave::PublicHoliday;
+
+/// Pay schedule errors
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PayScheduleError {
+    #[error("{0:?} is not a pay cycle")]
+    UnsupportedFrequency(PayFrequency),
+}
+
+/// One pay period in a schedule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayPeriod {
+    /// 1-based position in the year
+    pub number: u32,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    /// Scheduled pay date before business-day adjustment
+    pub pay_date: NaiveDate,
+    pub check_date: NaiveDate,
+    pub funding_deadline: NaiveDate,
+}
+
+/// How a nominal pay date that falls on a non-business day is moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BusinessDayPolicy {
+    /// Pay on the preceding business day (employees are never paid late)
+    Previous,
+    /// Pay on the following business day
+    Next,
+    /// Following business day, unless that crosses into the next month
+    ModifiedFollowing,
+}
+
+/// Furthest a check date is moved looking for a business day
+const BUSINESS_DAY_SEARCH_DAYS: usize = 366;
+
+/// Regional payroll calendar
+#[derive(Debug, Clone)]
+pub struct PayrollCalendar {
+    pub country_code: String,
+    /// Business timezone, with its daylight saving rules, used to decide
+    /// which calendar day "today" is
+    pub timezone: Tz,
+    pub weekend: Vec<Weekday>,
+    pub policy: BusinessDayPolicy,
+    /// Business days before the check date that funds must reach the bank
+    pub direct_deposit_cutoff_days: u32,
+    holidays: HashSet<NaiveDate>,
+}
+
+impl PayrollCalendar {
+    pub fn new(country_code: &str, weekend: Vec<Weekday>, policy: BusinessDayPolicy, cutoff_days: u32) -> Self {
+        Self {
+            country_code: country_code.to_string(),
+            timezone: Tz::UTC,
+            weekend,
+            policy,
+            direct_deposit_cutoff_days: cutoff_days,
+            holidays: HashSet::new(),
+        }
+    }
+
+    /// Default calendar for a country
+    pub fn for_country(country_code: &str) -> Self {
+        use chrono_tz::{America, Asia, Europe};
+        use Weekday::*;
+        let (calendar, timezone) = match country_code {
+            // NIBSS same-day credit, but banks want the file a day ahead
+            "NG" => (Self::new("NG", vec![Sat, Sun], BusinessDayPolicy::Previous, 1), chrono_tz::Africa::Lagos),
+            // ACH needs two banking days
+            "US" => (Self::new("US", vec![Sat, Sun], BusinessDayPolicy::Previous, 2), America::New_York),
+            // BACS three-day cycle
+            "GB" => (Self::new("GB", vec![Sat, Sun], BusinessDayPolicy::Previous, 3), Europe::London),
+            // Friday-Saturday weekend
+            "SA" | "KW" | "QA" | "BH" | "OM" => {
+                let timezone = match country_code {
+                    "SA" => Asia::Riyadh,
+                    "KW" => Asia::Kuwait,
+                    "QA" => Asia::Qatar,
+                    "BH" => Asia::Bahrain,
+                    _ => Asia::Muscat,
+                };
+                (Self::new(country_code, vec![Fri, Sat], BusinessDayPolicy::Previous, 1), timezone)
+            }
+            // SEPA credit transfer D-1
+            "DE" | "FR" | "ES" | "IT" | "NL" => {
+                let timezone = match country_code {
+                    "DE" => Europe::Berlin,
+                    "FR" => Europe::Paris,
+                    "ES" => Europe::Madrid,
+                    "IT" => Europe::Rome,
+                    _ => Europe::Amsterdam,
+                };
+                (Self::new(country_code, vec![Sat, Sun], BusinessDayPolicy::ModifiedFollowing, 1), timezone)
+            }
+            _ => (Self::new(country_code, vec![Sat, Sun], BusinessDayPolicy::Previous, 2), Tz::UTC),
+        };
+        calendar.with_timezone(timezone)
+    }
+
+    /// Set the business timezone
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Calendar date in the business timezone at a given instant
+    pub fn local_date(&self, at: DateTime<Utc>) -> NaiveDate {
+        at.with_timezone(&self.timezone).date_naive()
+    }
+
+    pub fn with_holidays(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays.extend(dates);
+        self
+    }
+
+    /// Add holidays from the leave module's public holiday list
+    pub fn with_public_holidays(self, holidays: &[PublicHoliday]) -> Self {
+        self.with_holidays(holidays.iter().map(|h| h.date))
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date)
+    }
+
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.weekend.contains(&date.weekday()) && !self.holidays.contains(&date)
+    }
+
+    /// Actual check date for a nominal pay date
+    pub fn check_date(&self, nominal: NaiveDate) -> NaiveDate {
+        if self.is_business_day(nominal) {
+            return nominal;
+        }
+        match self.policy {
+            BusinessDayPolicy::Previous => self.previous_business_day(nominal),
+            BusinessDayPolicy::Next => self.next_business_day(nominal),
+            BusinessDayPolicy::ModifiedFollowing => {
+                let next = self.next_business_day(nominal);
+                if next.month() == nominal.month() {
+                    next
+                } else {
+                    self.previous_business_day(nominal)
+                }
+            }
+        }
+    }
+
+    /// Check date for "last business day of the month" schedules; `None`
+    /// when the calendar has no business days
+    pub fn last_business_day(&self, year: i32, month: u32) -> Option<NaiveDate> {
+        let first_of_next = if month == 12 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+        } else {
+            NaiveDate::from_ymd_opt(year, month + 1, 1)?
+        };
+        Some(self.previous_business_day(first_of_next)).filter(|date| self.is_business_day(*date))
+    }
+
+    /// Latest date funds must be submitted for a check date
+    pub fn funding_deadline(&self, check_date: NaiveDate) -> NaiveDate {
+        (0..self.direct_deposit_cutoff_days).fold(check_date, |date, _| self.previous_business_day(date))
+    }
+
+    /// Pay periods whose scheduled pay date falls in `year`
+    ///
+    /// Weekly, biweekly, and four-weekly cycles repeat from `anchor`, a known pay date;
+    /// each period runs up to and including its pay date, so a biweekly
+    /// year has 27 periods when the first pay date is early enough.
+    /// Semi-monthly pays on the 15th and the last day of the month.
+    /// Monthly pays on the anchor's day of month (clamped to month end)
+    /// for the calendar month.
+    pub fn pay_periods(
+        &self,
+        frequency: &PayFrequency,
+        anchor: NaiveDate,
+        year: i32,
+    ) -> Result<Vec<PayPeriod>, PayScheduleError> {
+        let dated: Vec<(NaiveDate, NaiveDate, NaiveDate)> = match frequency {
+            PayFrequency::Weekly | PayFrequency::BiWeekly | PayFrequency::FourWeekly => {
+                let step = match frequency {
+                    PayFrequency::Weekly => 7,
+                    PayFrequency::BiWeekly => 14,
+                    _ => 28,
+                };
+                let Some(jan_1) = NaiveDate::from_ymd_opt(year, 1, 1) else {
+                    return Ok(vec![]);
+                };
+                let cycles_to_year = (jan_1 - anchor).num_days().div_euclid(step);
+                let mut pay_date = anchor + Duration::days(cycles_to_year * step);
+                if pay_date < jan_1 {
+                    pay_date += Duration::days(step);
+                }
+
+                let mut dates = Vec::new();
+                while pay_date.year() == year {
+                    dates.push((pay_date - Duration::days(step - 1), pay_date, pay_date));
+                    pay_date += Duration::days(step);
+                }
+                dates
+            }
+            PayFrequency::SemiMonthly => (1..=12)
+                .filter_map(|month| {
+                    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                    let fifteenth = NaiveDate::from_ymd_opt(year, month, 15)?;
+                    let last = last_day_of_month(year, month)?;
+                    Some([(first, fifteenth, fifteenth), (fifteenth + Duration::days(1), last, last)])
+                })
+                .flatten()
+                .collect(),
+            PayFrequency::Monthly => (1..=12)
+                .filter_map(|month| {
+                    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                    let last = last_day_of_month(year, month)?;
+                    let pay_date = NaiveDate::from_ymd_opt(year, month, anchor.day()).unwrap_or(last);
+                    Some((first, last, pay_date))
+                })
+                .collect(),
+            PayFrequency::Hourly | PayFrequency::Annually => {
+                return Err(PayScheduleError::UnsupportedFrequency(frequency.clone()));
+            }
+        };
+
+        Ok(dated
+            .into_iter()
+            .zip(1..)
+            .map(|((period_start, period_end, pay_date), number)| {
+                let check_date = self.check_date(pay_date);
+                PayPeriod {
+                    number,
+                    period_start,
+                    period_end,
+                    pay_date,
+                    check_date,
+                    funding_deadline: self.funding_deadline(check_date),
+                }
+            })
+            .collect())
+    }
+
+    /// Nearest business day before `date`; `date` itself when there is none
+    /// within `BUSINESS_DAY_SEARCH_DAYS` (a calendar with every day off)
+    fn previous_business_day(&self, date: NaiveDate) -> NaiveDate {
+        date.iter_days()
+            .rev()
+            .skip(1)
+            .take(BUSINESS_DAY_SEARCH_DAYS)
+            .find(|d| self.is_business_day(*d))
+            .unwrap_or(date)
+    }
+
+    /// Nearest business day after `date`, bounded like `previous_business_day`
+    fn next_business_day(&self, date: NaiveDate) -> NaiveDate {
+        date.iter_days()
+            .skip(1)
+            .take(BUSINESS_DAY_SEARCH_DAYS)
+            .find(|d| self.is_business_day(*d))
+            .unwrap_or(date)
+    }
+}
+
+fn last_day_of_month(year: i32, month: u32) -> Option<NaiveDate> {
+    let first_of_next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    first_of_next.pred_opt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_sunday_rolls_to_friday() {
+        let calendar = PayrollCalendar::for_country("NG");
+        // 2024-03-31 is a Sunday
+        assert_eq!(calendar.check_date(date(2024, 3, 31)), date(2024, 3, 29));
+        // Business days are unchanged
+        assert_eq!(calendar.check_date(date(2024, 3, 28)), date(2024, 3, 28));
+    }
+
+    #[test]
+    fn test_holiday_before_weekend() {
+        // Good Friday 2024-03-29 pushes a Sunday pay date back to Thursday
+        let calendar = PayrollCalendar::for_country("NG").with_holidays([date(2024, 3, 29)]);
+        assert_eq!(calendar.check_date(date(2024, 3, 31)), date(2024, 3, 28));
+        assert_eq!(calendar.last_business_day(2024, 3), Some(date(2024, 3, 28)));
+    }
+
+    #[test]
+    fn test_next_and_modified_following() {
+        let next = PayrollCalendar::new("XX", vec![Weekday::Sat, Weekday::Sun], BusinessDayPolicy::Next, 0);
+        assert_eq!(next.check_date(date(2024, 3, 31)), date(2024, 4, 1));
+
+        // Modified following stays in March
+        let sepa = PayrollCalendar::for_country("DE");
+        assert_eq!(sepa.check_date(date(2024, 3, 31)), date(2024, 3, 29));
+        // ...but rolls forward mid-month (2024-06-15 is a Saturday)
+        assert_eq!(sepa.check_date(date(2024, 6, 15)), date(2024, 6, 17));
+    }
+
+    #[test]
+    fn test_gulf_weekend() {
+        let calendar = PayrollCalendar::for_country("SA");
+        // 2024-05-31 is a Friday
+        assert_eq!(calendar.check_date(date(2024, 5, 31)), date(2024, 5, 30));
+        assert!(calendar.is_business_day(date(2024, 6, 2))); // Sunday
+    }
+
+    #[test]
+    fn test_calendar_without_business_days_terminates() {
+        use Weekday::*;
+        let every_day = vec![Mon, Tue, Wed, Thu, Fri, Sat, Sun];
+        for policy in [BusinessDayPolicy::Previous, BusinessDayPolicy::Next, BusinessDayPolicy::ModifiedFollowing] {
+            let calendar = PayrollCalendar::new("XX", every_day.clone(), policy, 2);
+            assert_eq!(calendar.check_date(date(2024, 5, 31)), date(2024, 5, 31));
+            assert_eq!(calendar.funding_deadline(date(2024, 5, 31)), date(2024, 5, 31));
+            assert_eq!(calendar.last_business_day(2024, 5), None);
+        }
+        let periods = PayrollCalendar::new("XX", every_day, BusinessDayPolicy::Previous, 1)
+            .pay_periods(&PayFrequency::Monthly, date(2024, 1, 31), 2024)
+            .unwrap();
+        assert_eq!(periods.len(), 12);
+    }
+
+    #[test]
+    fn test_local_date_uses_business_timezone() {
+        let at = DateTime::parse_from_rfc3339("2024-03-29T23:30:00Z").unwrap().with_timezone(&Utc);
+        // Already Saturday in Lagos, still Friday in New York
+        assert_eq!(PayrollCalendar::for_country("NG").local_date(at), date(2024, 3, 30));
+        assert_eq!(PayrollCalendar::for_country("US").local_date(at), date(2024, 3, 29));
+    }
+
+    #[test]
+    fn test_local_date_in_summer_time() {
+        // 00:30 on Saturday 6 July in Berlin (UTC+2) and London (UTC+1)
+        let at = DateTime::parse_from_rfc3339("2024-07-05T22:30:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(PayrollCalendar::for_country("DE").local_date(at), date(2024, 7, 6));
+        let at = DateTime::parse_from_rfc3339("2024-07-05T23:30:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(PayrollCalendar::for_country("GB").local_date(at), date(2024, 7, 6));
+        // 00:30 in New York (UTC-4); 23:30 the day before in winter time
+        let at = DateTime::parse_from_rfc3339("2024-07-06T04:30:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(PayrollCalendar::for_country("US").local_date(at), date(2024, 7, 6));
+        let at = DateTime::parse_from_rfc3339("2024-01-06T04:30:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(PayrollCalendar::for_country("US").local_date(at), date(2024, 1, 5));
+    }
+
+    #[test]
+    fn test_funding_deadline() {
+        let calendar = PayrollCalendar::for_country("US");
+        // Check date Monday 2024-04-01 -> funds due Thursday 2024-03-28
+        assert_eq!(calendar.funding_deadline(date(2024, 4, 1)), date(2024, 3, 28));
+    }
+
+    #[test]
+    fn test_biweekly_periods() {
+        let calendar = PayrollCalendar::for_country("US");
+        let periods = calendar.pay_periods(&PayFrequency::BiWeekly, date(2024, 1, 5), 2024).unwrap();
+
+        assert_eq!(periods.len(), 26);
+        assert_eq!(periods[0].period_start, date(2023, 12, 23));
+        assert_eq!(periods[0].period_end, date(2024, 1, 5));
+        assert_eq!(periods[25].pay_date, date(2024, 12, 20));
+
+        // An anchor from a prior year lands on the same cycle
+        let from_old_anchor = calendar.pay_periods(&PayFrequency::BiWeekly, date(2022, 12, 23), 2024).unwrap();
+        assert_eq!(from_old_anchor, periods);
+
+        // Friday 2021-01-01 starts a 27-period year
+        let periods = calendar.pay_periods(&PayFrequency::BiWeekly, date(2021, 1, 15), 2021).unwrap();
+        assert_eq!(periods.len(), 27);
+        assert_eq!(periods[26].pay_date, date(2021, 12, 31));
+    }
+
+    #[test]
+    fn test_monthly_and_semi_monthly_periods() {
+        let calendar = PayrollCalendar::for_country("NG");
+        let monthly = calendar.pay_periods(&PayFrequency::Monthly, date(2024, 1, 31), 2024).unwrap();
+        assert_eq!(monthly.len(), 12);
+        assert_eq!(monthly[1].pay_date, date(2024, 2, 29));
+        // 2024-03-31 is a Sunday
+        assert_eq!(monthly[2].check_date, date(2024, 3, 29));
+
+        let semi = calendar.pay_periods(&PayFrequency::SemiMonthly, date(2024, 1, 15), 2024).unwrap();
+        assert_eq!(semi.len(), 24);
+        assert_eq!((semi[1].period_start, semi[1].period_end), (date(2024, 1, 16), date(2024, 1, 31)));
+
+        assert!(calendar.pay_periods(&PayFrequency::Hourly, date(2024, 1, 1), 2024).is_err());
+    }
+}
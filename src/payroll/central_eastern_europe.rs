@@ -14,6 +14,9 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::value_objects::PayFrequency;
+use super::annualization::Annualization;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // POLAND (PL) - POLSKI ŁAD
 // ═══════════════════════════════════════════════════════════════════════════
@@ -237,7 +240,7 @@ impl HungarianTaxCalculator {
         }
     }
     
-    fn family_benefit(&self, gross: Decimal) -> Decimal {
+    fn family_benefit(&self, _gross: Decimal) -> Decimal {
         if self.num_children == 0 { return Decimal::ZERO; }
         
         // Tax base reduction per child, saving = reduction * 15%
@@ -345,13 +348,20 @@ impl EstonianTaxCalculator {
     pub fn new() -> Self { Self { has_pillar2_pension: true } }
     
     pub fn calculate(&self, gross_monthly: Decimal) -> EstonianTaxResult {
-        let annual = gross_monthly * dec!(12);
+        self.calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// Tulumaks and contributions on one pay period's pay, with the annual
+    /// basic exemption spread over the periods in `annualization`
+    pub fn calculate_annualized(&self, gross_monthly: Decimal, annualization: &Annualization) -> EstonianTaxResult {
+        let annual = annualization.annualize(gross_monthly);
         
         // Basic exemption (€7,848/year, reduced above €14,400)
-        let exemption = if annual <= dec!(14400) { dec!(654) } // 7848/12
+        let annual_exemption = if annual <= dec!(14400) { dec!(7848) }
         else if annual <= dec!(25200) { 
-            dec!(654) * (dec!(25200) - annual) / (dec!(25200) - dec!(14400))
+            dec!(7848) * (dec!(25200) - annual) / (dec!(25200) - dec!(14400))
         } else { Decimal::ZERO };
+        let exemption = annual_exemption / annualization.periods;
         
         // Employee contributions
         let unemployment = gross_monthly * dec!(0.016); // 1.6%
@@ -522,7 +532,7 @@ impl CentralEasternEuropeRegistry {
     }
     
     pub fn is_eurozone(code: &str) -> bool { matches!(code, "SK" | "SI" | "HR" | "EE" | "LV" | "LT") }
-    pub fn is_eu_member(code: &str) -> bool { true } // All are EU
+    pub fn is_eu_member(_code: &str) -> bool { true } // All are EU
     pub fn has_flat_tax(code: &str) -> bool { matches!(code, "HU" | "RO" | "BG" | "EE") }
     pub fn uses_sepa(_code: &str) -> bool { true }
 }
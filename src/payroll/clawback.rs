@@ -0,0 +1,135 @@
+//! Clawbacks
+//!
+//! Recovery of an overpayment from a later run, entered as negative
+//! earnings on that run's payslip. A clawback lowers the period's gross,
+//! and so the employee's YTD gross, and gives back the tax withheld on the
+//! overpaid amount at the year's effective rate, never more than the year
+//! has withheld. When the clawback exceeds what the period pays, net pay
+//! stops at zero and the rest moves to a repayment schedule recovered from
+//! the following runs.
+
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Negative earnings line in `other_allowances`
+pub const CLAWBACK_LINE: &str = "clawback";
+
+/// Line in `other_deductions` taking out the part of a clawback the period
+/// can't absorb; it is negative, moving that amount to a recovery schedule
+pub const CLAWBACK_CARRIED_FORWARD_LINE: &str = "clawback_carried_forward";
+
+/// Line in `other_deductions` for clawback recovered by later runs
+pub const CLAWBACK_RECOVERY_LINE: &str = "clawback_recovery";
+
+/// How clawbacks are settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClawbackPolicy {
+    /// Give back withholding on clawed-back pay at the year's effective rate
+    pub reverse_withholding: bool,
+    /// Runs an unabsorbed clawback is recovered over
+    pub recovery_periods: u32,
+}
+
+impl Default for ClawbackPolicy {
+    fn default() -> Self {
+        Self { reverse_withholding: true, recovery_periods: 1 }
+    }
+}
+
+/// An overpayment recovered on one run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Clawback {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub run_id: Uuid,
+    /// Gross amount overpaid, positive
+    pub amount: Decimal,
+    pub reason: String,
+    /// Schedule recovering what the run couldn't absorb
+    pub recovery_schedule_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Clawback {
+    pub fn new(employee_id: Uuid, run_id: Uuid, amount: Decimal, reason: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            employee_id,
+            run_id,
+            amount,
+            reason: reason.into(),
+            recovery_schedule_id: None,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Tax to give back on `clawed_back` pay, at the effective rate of what the
+/// year has paid and withheld so far
+pub fn withholding_reversal(clawed_back: Decimal, ytd_gross: Decimal, ytd_tax: Decimal) -> Decimal {
+    if ytd_gross <= Decimal::ZERO || ytd_tax <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    (clawed_back.min(ytd_gross) * ytd_tax / ytd_gross).min(ytd_tax)
+}
+
+/// Clawbacks by id
+#[derive(Debug, Clone, Default)]
+pub struct Clawbacks {
+    // In real implementation, backed by the payroll_clawbacks table
+    clawbacks: Arc<DashMap<Uuid, Clawback>>,
+}
+
+impl Clawbacks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, clawback: Clawback) {
+        self.clawbacks.insert(clawback.id, clawback);
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<Clawback> {
+        self.clawbacks.get(&id).map(|c| c.clone())
+    }
+
+    /// Clawbacks against an employee on a run, oldest first
+    pub fn for_run(&self, run_id: Uuid, employee_id: Uuid) -> Vec<Clawback> {
+        let mut clawbacks: Vec<_> = self
+            .clawbacks
+            .iter()
+            .filter(|c| c.run_id == run_id && c.employee_id == employee_id)
+            .map(|c| c.clone())
+            .collect();
+        clawbacks.sort_by_key(|c| c.created_at);
+        clawbacks
+    }
+
+    /// Point each of `ids` at the schedule recovering their remainder
+    pub fn set_recovery_schedule(&self, ids: &[Uuid], schedule_id: Option<Uuid>) {
+        for id in ids {
+            if let Some(mut entry) = self.clawbacks.get_mut(id) {
+                entry.recovery_schedule_id = schedule_id;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_reversal_at_ytd_effective_rate_capped_by_withholding() {
+        // 20% effective so far this year
+        assert_eq!(withholding_reversal(dec!(50_000), dec!(1_000_000), dec!(200_000)), dec!(10_000));
+        // Never more than the year withheld
+        assert_eq!(withholding_reversal(dec!(2_000_000), dec!(1_000_000), dec!(200_000)), dec!(200_000));
+        assert_eq!(withholding_reversal(dec!(50_000), Decimal::ZERO, Decimal::ZERO), Decimal::ZERO);
+    }
+}
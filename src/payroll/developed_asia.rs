@@ -4,13 +4,22 @@
 //! - Japan: 7 brackets (5%-45%), residence tax, bonus taxation
 //! - South Korea: 8 brackets (6%-45%), 4 insurances
 //! - Taiwan: 6 brackets (5%-40%), labor insurance
-//! - Hong Kong: Progressive vs Standard rate (15%), MPF
+//! - Hong Kong: Progressive vs two-tier Standard rate (15%/16%), MPF
 //! - Singapore: 13 brackets (0%-24%), CPF by age
 
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::value_objects::PayFrequency;
+use super::annualization::Annualization;
+use super::residency::ResidencyStatus;
+use super::rounding::{progressive_tax, TaxRounding};
+use super::tax_parameters::{
+    TaxParameters, JP_BASIC_DEDUCTION, JP_DEPENDENT_DEDUCTION, TW_PERSONAL_EXEMPTION, TW_STANDARD_DEDUCTION,
+};
+use super::trace::{percent, CalcStep, CalcTrace};
+
 // ═══════════════════════════════════════════════════════════════════════════
 // JAPAN (JP) - 所得税 SHOTOKU-ZEI
 // ═══════════════════════════════════════════════════════════════════════════
@@ -41,20 +50,72 @@ pub struct JapanTaxCalculator {
     pub si: JapanSocialInsurance,
     pub num_dependents: u8,
     pub age: u8,
+    /// Basic and dependent deductions for the year
+    pub parameters: TaxParameters,
+    /// Tax residency for the year; non-residents pay a flat 20% and no residence tax
+    pub residency: ResidencyStatus,
+    pub rounding: TaxRounding,
 }
 
 impl JapanTaxCalculator {
     pub fn new() -> Self {
-        Self { si: JapanSocialInsurance::default(), num_dependents: 0, age: 35 }
+        Self {
+            si: JapanSocialInsurance::default(),
+            num_dependents: 0,
+            age: 35,
+            parameters: TaxParameters::builtin("JP", 2024),
+            residency: ResidencyStatus::Resident,
+            rounding: TaxRounding::for_country("JP"),
+        }
+    }
+    
+    pub fn with_parameters(mut self, parameters: TaxParameters) -> Self {
+        self.parameters = parameters;
+        self
+    }
+    
+    /// Select resident or non-resident rates from a residency determination
+    pub fn with_residency(mut self, residency: ResidencyStatus) -> Self {
+        self.residency = residency;
+        self
     }
     
     /// Calculate monthly payroll (源泉徴収)
     pub fn calculate_monthly(&self, monthly_salary: Decimal, prev_year_income: Decimal) -> JapanPayrollResult {
+        self.calculate_monthly_with_trace(monthly_salary, prev_year_income, false)
+    }
+    
+    /// Calculate monthly payroll, optionally tracing every deduction that makes up `total_deductions`
+    pub fn calculate_monthly_with_trace(
+        &self,
+        monthly_salary: Decimal,
+        prev_year_income: Decimal,
+        trace: bool,
+    ) -> JapanPayrollResult {
+        self.calculate_annualized_with_trace(
+            monthly_salary,
+            prev_year_income,
+            &Annualization::nominal(&PayFrequency::Monthly),
+            trace,
+        )
+    }
+
+    /// Payroll for one pay period's salary: social insurance on the standard
+    /// monthly remuneration of its monthly equivalent scaled back to the
+    /// period, and income tax annualized over the periods in `annualization`
+    pub fn calculate_annualized_with_trace(
+        &self,
+        monthly_salary: Decimal,
+        prev_year_income: Decimal,
+        annualization: &Annualization,
+        trace: bool,
+    ) -> JapanPayrollResult {
         let si = &self.si;
+        let mut steps = CalcTrace::new(trace);
         
         // Standard monthly remuneration (標準報酬月額)
-        let standard = (monthly_salary / dec!(10000)).round() * dec!(10000);
-        let capped = standard.min(si.max_standard_monthly);
+        let standard = (annualization.monthly_equivalent(monthly_salary) / dec!(10000)).round() * dec!(10000);
+        let capped = annualization.monthly_to_period(standard.min(si.max_standard_monthly));
         
         // Social insurance (employee portion = 50%)
         let health = capped * si.health_rate / dec!(2);
@@ -62,27 +123,56 @@ impl JapanTaxCalculator {
         let pension = capped * si.pension_rate / dec!(2);
         let employment = monthly_salary * si.employment_ee;
         let si_employee = health + nursing + pension + employment;
+        steps.charge(format!("Health insurance {} of ¥{} standard, employee half", percent(si.health_rate), capped),
+            capped, si.health_rate / dec!(2), health);
+        if nursing > Decimal::ZERO {
+            steps.charge(format!("Nursing care {} of ¥{} standard, employee half", percent(si.nursing_rate), capped),
+                capped, si.nursing_rate / dec!(2), nursing);
+        }
+        steps.charge(format!("Pension {} of ¥{} standard, employee half", percent(si.pension_rate), capped),
+            capped, si.pension_rate / dec!(2), pension);
+        steps.charge(format!("Employment insurance {} of ¥{}", percent(si.employment_ee), monthly_salary),
+            monthly_salary, si.employment_ee, employment);
         
         // Employer contributions
         let si_employer = health + nursing + pension + monthly_salary * si.employment_er;
         
         // Taxable income
-        let annual_projection = (monthly_salary - si_employee) * dec!(12);
+        let annual_projection = annualization.annualize(monthly_salary - si_employee);
         let employment_deduction = self.employment_income_deduction(annual_projection);
-        let basic_deduction = dec!(480000);
-        let dependent_deduction = dec!(380000) * Decimal::from(self.num_dependents);
+        let basic_deduction = self.parameters.amount(JP_BASIC_DEDUCTION);
+        let dependent_deduction = self.parameters.amount(JP_DEPENDENT_DEDUCTION) * Decimal::from(self.num_dependents);
         let taxable = (annual_projection - employment_deduction - basic_deduction - dependent_deduction).max(Decimal::ZERO);
         
-        // Income tax (7 brackets)
-        let annual_tax = self.calculate_income_tax(taxable);
-        let income_tax = annual_tax / dec!(12);
+        // Income tax (7 brackets); non-residents 20% of the gross salary
+        let income_tax = match self.residency {
+            ResidencyStatus::Resident => {
+                let annual_tax = self.rounding.step(self.calculate_income_tax(taxable));
+                self.trace_income_tax(taxable, annualization, &mut steps);
+                self.rounding.step(annualization.period_share(annual_tax))
+            }
+            ResidencyStatus::NonResident => {
+                let tax = self.rounding.step(monthly_salary * dec!(0.20));
+                steps.charge(format!("Non-resident income tax 20% of ¥{}", monthly_salary), monthly_salary, dec!(0.20), tax);
+                tax
+            }
+        };
         
         // Reconstruction surtax (2.1%)
         let reconstruction = income_tax * dec!(0.021);
+        steps.charge(format!("Reconstruction surtax 2.1% of ¥{} income tax", self.rounding.finish(income_tax)),
+            income_tax, dec!(0.021), reconstruction);
         
-        // Residence tax (住民税 - based on previous year, 10%)
+        // Residence tax (住民税 - based on previous year, 10%), only for residents
         let prev_taxable = (prev_year_income - basic_deduction).max(Decimal::ZERO);
-        let residence_tax = (prev_taxable * dec!(0.10) + dec!(5000)) / dec!(12);
+        let residence_tax = match self.residency {
+            ResidencyStatus::Resident => (prev_taxable * dec!(0.10) + dec!(5000)) / annualization.periods,
+            ResidencyStatus::NonResident => Decimal::ZERO,
+        };
+        if residence_tax > Decimal::ZERO {
+            steps.charge(format!("Residence tax 10% of ¥{} prior-year taxable + ¥5000 per capita, per period", prev_taxable),
+                prev_taxable, dec!(0.10), residence_tax);
+        }
         
         let total_deductions = si_employee + income_tax + reconstruction + residence_tax;
         
@@ -91,12 +181,13 @@ impl JapanTaxCalculator {
             standard_monthly: standard,
             health_pension_employee: health + nursing + pension,
             employment_insurance: employment,
-            income_tax: income_tax.round_dp(0),
-            reconstruction_tax: reconstruction.round_dp(0),
-            residence_tax: residence_tax.round_dp(0),
-            total_deductions: total_deductions.round_dp(0),
-            net_pay: (monthly_salary - total_deductions).round_dp(0),
-            employer_cost: (monthly_salary + si_employer).round_dp(0),
+            income_tax: self.rounding.finish(income_tax),
+            reconstruction_tax: self.rounding.finish(reconstruction),
+            residence_tax: self.rounding.finish(residence_tax),
+            total_deductions: self.rounding.finish(total_deductions),
+            net_pay: self.rounding.finish(monthly_salary - total_deductions),
+            employer_cost: self.rounding.finish(monthly_salary + si_employer),
+            trace: steps.into_steps(),
         }
     }
     
@@ -108,7 +199,18 @@ impl JapanTaxCalculator {
         let capped = bonus.min(si.max_standard_monthly * dec!(3));
         let si_employee = capped * (si.health_rate + si.pension_rate) / dec!(2) + bonus * si.employment_ee;
         
-        // Bonus tax rate (simplified - based on previous month)
+        // Bonus tax rate (simplified - based on previous month); non-residents 20% of the gross
+        if self.residency == ResidencyStatus::NonResident {
+            let income_tax = bonus * dec!(0.20);
+            let reconstruction = income_tax * dec!(0.021);
+            return JapanBonusResult {
+                gross_bonus: bonus,
+                social_insurance: self.rounding.finish(si_employee),
+                income_tax: self.rounding.finish(income_tax),
+                reconstruction_tax: self.rounding.finish(reconstruction),
+                net_bonus: self.rounding.finish(bonus - si_employee - income_tax - reconstruction),
+            };
+        }
         let rate = if prev_month_salary < dec!(79000) { Decimal::ZERO }
         else if prev_month_salary < dec!(252000) { dec!(0.02042) }
         else if prev_month_salary < dec!(300000) { dec!(0.04084) }
@@ -120,10 +222,10 @@ impl JapanTaxCalculator {
         
         JapanBonusResult {
             gross_bonus: bonus,
-            social_insurance: si_employee.round_dp(0),
-            income_tax: income_tax.round_dp(0),
-            reconstruction_tax: reconstruction.round_dp(0),
-            net_bonus: (bonus - si_employee - income_tax - reconstruction).round_dp(0),
+            social_insurance: self.rounding.finish(si_employee),
+            income_tax: self.rounding.finish(income_tax),
+            reconstruction_tax: self.rounding.finish(reconstruction),
+            net_bonus: self.rounding.finish(bonus - si_employee - income_tax - reconstruction),
         }
     }
     
@@ -136,9 +238,23 @@ impl JapanTaxCalculator {
         else { dec!(1950000) }
     }
     
-    fn calculate_income_tax(&self, taxable: Decimal) -> Decimal {
-        // 7 brackets with deduction method
-        let brackets: [(Decimal, Decimal, Decimal); 7] = [
+    /// Trace income tax bracket by bracket (marginal form of the deduction method), period share
+    fn trace_income_tax(&self, taxable: Decimal, annualization: &Annualization, steps: &mut CalcTrace) {
+        let mut prev = Decimal::ZERO;
+        for (max, rate, _) in Self::income_tax_brackets() {
+            if taxable <= prev { break; }
+            let slice = taxable.min(max) - prev;
+            steps.charge(
+                format!("Income tax {} on ¥{} (¥{} – ¥{}), period share", percent(rate), slice, prev, taxable.min(max)),
+                slice, rate, slice * rate / annualization.periods,
+            );
+            prev = max;
+        }
+    }
+    
+    /// 7 brackets as (upper bound, rate, quick deduction) for the deduction method
+    fn income_tax_brackets() -> [(Decimal, Decimal, Decimal); 7] {
+        [
             (dec!(1950000), dec!(0.05), Decimal::ZERO),
             (dec!(3300000), dec!(0.10), dec!(97500)),
             (dec!(6950000), dec!(0.20), dec!(427500)),
@@ -146,9 +262,11 @@ impl JapanTaxCalculator {
             (dec!(18000000), dec!(0.33), dec!(1536000)),
             (dec!(40000000), dec!(0.40), dec!(2796000)),
             (dec!(999999999999), dec!(0.45), dec!(4796000)),
-        ];
-        
-        for (max, rate, deduction) in brackets {
+        ]
+    }
+    
+    fn calculate_income_tax(&self, taxable: Decimal) -> Decimal {
+        for (max, rate, deduction) in Self::income_tax_brackets() {
             if taxable <= max {
                 return (taxable * rate - deduction).max(Decimal::ZERO);
             }
@@ -173,6 +291,8 @@ pub struct JapanPayrollResult {
     pub total_deductions: Decimal,
     pub net_pay: Decimal,
     pub employer_cost: Decimal,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trace: Vec<CalcStep>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -216,59 +336,57 @@ impl Default for KoreanFourInsurances {
 /// Korean Tax Calculator
 pub struct KoreanTaxCalculator {
     pub insurances: KoreanFourInsurances,
+    pub rounding: TaxRounding,
 }
 
 impl KoreanTaxCalculator {
-    pub fn new() -> Self { Self { insurances: KoreanFourInsurances::default() } }
+    pub fn new() -> Self {
+        Self { insurances: KoreanFourInsurances::default(), rounding: TaxRounding::for_country("KR") }
+    }
     
     pub fn calculate(&self, gross_annual: Decimal) -> KoreanTaxResult {
         let ins = &self.insurances;
+        let round = &self.rounding;
         
-        // 4 Insurances (employee portions)
-        let pension = gross_annual * ins.national_pension_ee;
-        let health = gross_annual * ins.health_insurance_ee;
-        let long_term = health * ins.long_term_care_ee;
-        let employment = gross_annual * ins.employment_insurance_ee;
+        // 4 Insurances (employee portions); long-term care is levied on the rounded health premium
+        let pension = round.step(gross_annual * ins.national_pension_ee);
+        let health = round.step(gross_annual * ins.health_insurance_ee);
+        let long_term = round.step(health * ins.long_term_care_ee);
+        let employment = round.step(gross_annual * ins.employment_insurance_ee);
         let social_total = pension + health + long_term + employment;
         
         // Income tax (8 brackets: 6%-45%)
         let taxable = (gross_annual - social_total).max(Decimal::ZERO);
-        let income_tax = self.calculate_income_tax(taxable);
+        let income_tax = round.step(self.calculate_income_tax(taxable));
         
         // Local income tax (10% of income tax)
-        let local_tax = income_tax * dec!(0.10);
+        let local_tax = round.step(income_tax * dec!(0.10));
         
         KoreanTaxResult {
             geup_yeo: gross_annual,
-            gukmin_yeonkeum: pension.round_dp(0),
-            geongang_boheom: health.round_dp(0),
-            janggi_yoyang: long_term.round_dp(0),
-            goyong_boheom: employment.round_dp(0),
-            sodeuk_se: income_tax.round_dp(0),
-            jibangsodeuk_se: local_tax.round_dp(0),
-            silsu_ryeong: (gross_annual - social_total - income_tax - local_tax).round_dp(0),
+            gukmin_yeonkeum: round.finish(pension),
+            geongang_boheom: round.finish(health),
+            janggi_yoyang: round.finish(long_term),
+            goyong_boheom: round.finish(employment),
+            sodeuk_se: round.finish(income_tax),
+            jibangsodeuk_se: round.finish(local_tax),
+            silsu_ryeong: round.finish(gross_annual - social_total - income_tax - local_tax),
         }
     }
     
     fn calculate_income_tax(&self, taxable: Decimal) -> Decimal {
-        // 8 brackets
-        let brackets: [(Decimal, Decimal, Decimal); 8] = [
-            (dec!(14000000), dec!(0.06), Decimal::ZERO),
-            (dec!(50000000), dec!(0.15), dec!(1260000)),
-            (dec!(88000000), dec!(0.24), dec!(5760000)),
-            (dec!(150000000), dec!(0.35), dec!(15440000)),
-            (dec!(300000000), dec!(0.38), dec!(19940000)),
-            (dec!(500000000), dec!(0.40), dec!(25940000)),
-            (dec!(1000000000), dec!(0.42), dec!(35940000)),
-            (dec!(999999999999), dec!(0.45), dec!(65940000)),
+        // 8 brackets, marginal form so each bracket's tax can be rounded
+        let brackets: [(Decimal, Decimal); 8] = [
+            (dec!(14000000), dec!(0.06)),
+            (dec!(50000000), dec!(0.15)),
+            (dec!(88000000), dec!(0.24)),
+            (dec!(150000000), dec!(0.35)),
+            (dec!(300000000), dec!(0.38)),
+            (dec!(500000000), dec!(0.40)),
+            (dec!(1000000000), dec!(0.42)),
+            (dec!(999999999999), dec!(0.45)),
         ];
-        
-        for (max, rate, deduction) in brackets {
-            if taxable <= max {
-                return (taxable * rate - deduction).max(Decimal::ZERO);
-            }
-        }
-        Decimal::ZERO
+        progressive_tax(&brackets, taxable, &self.rounding)
     }
 }
 
@@ -295,10 +413,33 @@ pub struct KoreanTaxResult {
 /// Taiwan Tax Calculator
 pub struct TaiwanTaxCalculator {
     pub num_dependents: u8,
+    /// Standard deduction and personal exemption for the year
+    pub parameters: TaxParameters,
+    /// Tax residency for the year; non-residents pay a flat 18% without deductions
+    pub residency: ResidencyStatus,
+    pub rounding: TaxRounding,
 }
 
 impl TaiwanTaxCalculator {
-    pub fn new() -> Self { Self { num_dependents: 0 } }
+    pub fn new() -> Self {
+        Self {
+            num_dependents: 0,
+            parameters: TaxParameters::builtin("TW", 2024),
+            residency: ResidencyStatus::Resident,
+            rounding: TaxRounding::for_country("TW"),
+        }
+    }
+    
+    pub fn with_parameters(mut self, parameters: TaxParameters) -> Self {
+        self.parameters = parameters;
+        self
+    }
+    
+    /// Select resident or non-resident rates from a residency determination
+    pub fn with_residency(mut self, residency: ResidencyStatus) -> Self {
+        self.residency = residency;
+        self
+    }
     
     pub fn calculate(&self, gross_annual: Decimal) -> TaiwanTaxResult {
         // Labor insurance (勞保) 11.5% (employee 20% = 2.3%)
@@ -307,21 +448,24 @@ impl TaiwanTaxCalculator {
         // Health insurance (健保) 5.17% (employee 30% = 1.55%)
         let health_insurance = gross_annual * dec!(0.0155);
         
-        // Standard deduction NT$124,000 single / NT$248,000 married
-        let standard_deduction = dec!(124000);
-        let personal_exemption = dec!(92000) * (Decimal::ONE + Decimal::from(self.num_dependents));
+        // Standard deduction (single filer) and a personal exemption for the taxpayer and each dependent
+        let standard_deduction = self.parameters.amount(TW_STANDARD_DEDUCTION);
+        let personal_exemption = self.parameters.amount(TW_PERSONAL_EXEMPTION) * (Decimal::ONE + Decimal::from(self.num_dependents));
         
         let taxable = (gross_annual - labor_insurance - health_insurance - standard_deduction - personal_exemption).max(Decimal::ZERO);
         
-        // 6 brackets (5%-40%)
-        let income_tax = self.calculate_income_tax(taxable);
+        // 6 brackets (5%-40%); non-resident salaries are withheld at 18% of the gross
+        let income_tax = match self.residency {
+            ResidencyStatus::Resident => self.calculate_income_tax(taxable),
+            ResidencyStatus::NonResident => gross_annual * dec!(0.18),
+        };
         
         TaiwanTaxResult {
             nian_shou_ru: gross_annual,
-            lao_bao: labor_insurance.round_dp(0),
-            jian_bao: health_insurance.round_dp(0),
-            suo_de_shui: income_tax.round_dp(0),
-            shi_ling: (gross_annual - labor_insurance - health_insurance - income_tax).round_dp(0),
+            lao_bao: self.rounding.finish(labor_insurance),
+            jian_bao: self.rounding.finish(health_insurance),
+            suo_de_shui: self.rounding.finish(income_tax),
+            shi_ling: self.rounding.finish(gross_annual - labor_insurance - health_insurance - income_tax),
         }
     }
     
@@ -367,42 +511,93 @@ pub struct TaiwanTaxResult {
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum HkMaritalStatus { Single, Married }
 
+/// Hong Kong standard rate, two-tiered from 2024/25: 15% on the first
+/// HK$5,000,000 of net income, 16% on the rest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HkStandardRate {
+    pub lower_rate: Decimal,
+    pub upper_rate: Decimal,
+    /// Net income taxed at `lower_rate`
+    pub threshold: Decimal,
+}
+
+impl Default for HkStandardRate {
+    fn default() -> Self {
+        Self { lower_rate: dec!(0.15), upper_rate: dec!(0.16), threshold: dec!(5000000) }
+    }
+}
+
+impl HkStandardRate {
+    pub fn tax(&self, net_income: Decimal) -> Decimal {
+        let net_income = net_income.max(Decimal::ZERO);
+        let lower = net_income.min(self.threshold);
+        lower * self.lower_rate + (net_income - lower) * self.upper_rate
+    }
+}
+
 /// Hong Kong Tax Calculator
 pub struct HongKongTaxCalculator {
     pub marital_status: HkMaritalStatus,
     pub num_children: u8,
+    pub standard_rate: HkStandardRate,
+    pub rounding: TaxRounding,
 }
 
 impl HongKongTaxCalculator {
-    pub fn new() -> Self { Self { marital_status: HkMaritalStatus::Single, num_children: 0 } }
+    pub fn new() -> Self {
+        Self {
+            marital_status: HkMaritalStatus::Single,
+            num_children: 0,
+            standard_rate: HkStandardRate::default(),
+            rounding: TaxRounding::for_country("HK"),
+        }
+    }
     
     pub fn calculate(&self, gross_annual: Decimal, mpf_contributions: Decimal) -> HongKongTaxResult {
+        self.calculate_with_deductions(gross_annual, mpf_contributions, Decimal::ZERO)
+    }
+    
+    /// IRD method: net income is assessable income less deductions (MPF
+    /// relief, self-education, donations and other allowable deductions);
+    /// progressive rates apply to net income less allowances, and tax is
+    /// capped at the standard rate on net income
+    pub fn calculate_with_deductions(
+        &self,
+        gross_annual: Decimal,
+        mpf_contributions: Decimal,
+        other_deductions: Decimal,
+    ) -> HongKongTaxResult {
+        // Deductions
+        let mpf_relief = mpf_contributions.min(dec!(18000));
+        let total_deductions = mpf_relief + other_deductions.max(Decimal::ZERO);
+        let net_income = (gross_annual - total_deductions).max(Decimal::ZERO);
+        
         // Allowances
         let personal = match self.marital_status {
             HkMaritalStatus::Single => dec!(132000),
             HkMaritalStatus::Married => dec!(264000),
         };
         let child = dec!(130000) * Decimal::from(self.num_children);
-        let mpf_relief = mpf_contributions.min(dec!(18000));
-        let total_allowances = personal + child + mpf_relief;
-        
-        // Progressive tax (5 bands)
-        let net_chargeable = (gross_annual - total_allowances).max(Decimal::ZERO);
-        let progressive = self.calculate_progressive(net_chargeable);
+        let total_allowances = personal + child;
         
-        // Standard rate (15% on net income)
-        let standard = (gross_annual - mpf_relief) * dec!(0.15);
+        // Progressive tax (5 bands) on net chargeable income
+        let net_chargeable = (net_income - total_allowances).max(Decimal::ZERO);
+        let progressive = self.rounding.finish(self.calculate_progressive(net_chargeable));
         
-        // Pay the lower
+        // Standard rate on net income caps the tax
+        let standard = self.rounding.finish(self.standard_rate.tax(net_income));
         let final_tax = progressive.min(standard);
         
         HongKongTaxResult {
             annual_income: gross_annual,
+            total_deductions,
+            net_income,
             total_allowances,
             net_chargeable_income: net_chargeable,
-            progressive_tax: progressive.round_dp(0),
-            standard_tax: standard.round_dp(0),
-            final_tax: final_tax.round_dp(0),
+            progressive_tax: progressive,
+            standard_tax: standard,
+            final_tax,
+            standard_rate_applied: standard < progressive,
             effective_rate: if gross_annual > Decimal::ZERO { final_tax / gross_annual * dec!(100) } else { Decimal::ZERO },
         }
     }
@@ -449,11 +644,14 @@ impl Default for HongKongTaxCalculator {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HongKongTaxResult {
     pub annual_income: Decimal,
+    pub total_deductions: Decimal,
+    pub net_income: Decimal,
     pub total_allowances: Decimal,
     pub net_chargeable_income: Decimal,
     pub progressive_tax: Decimal,
     pub standard_tax: Decimal,
     pub final_tax: Decimal,
+    pub standard_rate_applied: bool,
     pub effective_rate: Decimal,
 }
 
@@ -487,42 +685,121 @@ impl CpfRatesByAge {
     }
 }
 
+/// CPF wage ceilings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpfWageCeilings {
+    /// Ordinary wages subject to CPF per month
+    pub ordinary_monthly: Decimal,
+    /// Ordinary and additional wages subject to CPF per year
+    pub annual: Decimal,
+}
+
+impl Default for CpfWageCeilings {
+    fn default() -> Self {
+        Self { ordinary_monthly: dec!(6800), annual: dec!(102000) }
+    }
+}
+
+impl CpfWageCeilings {
+    /// Additional wages (bonuses) still subject to CPF this year: the annual
+    /// ceiling less the year's ordinary wages subject to CPF, less additional
+    /// wages already paid
+    pub fn additional_wage_ceiling(&self, year_ordinary_wages: Decimal, additional_wages_to_date: Decimal) -> Decimal {
+        let ordinary_subject = year_ordinary_wages.min(self.ordinary_monthly * dec!(12));
+        (self.annual - ordinary_subject - additional_wages_to_date).max(Decimal::ZERO)
+    }
+}
+
 /// Singapore Tax Calculator
 pub struct SingaporeTaxCalculator {
     pub age: u8,
     pub is_pr_or_citizen: bool,
+    /// Tax residency for the year; non-residents pay the higher of 15% or resident rates
+    pub residency: ResidencyStatus,
+    pub cpf_ceilings: CpfWageCeilings,
+    pub rounding: TaxRounding,
 }
 
 impl SingaporeTaxCalculator {
-    pub fn new() -> Self { Self { age: 35, is_pr_or_citizen: true } }
+    pub fn new() -> Self {
+        Self {
+            age: 35,
+            is_pr_or_citizen: true,
+            residency: ResidencyStatus::Resident,
+            cpf_ceilings: CpfWageCeilings::default(),
+            rounding: TaxRounding::for_country("SG"),
+        }
+    }
     
+    /// Select resident or non-resident rates from a residency determination
+    pub fn with_residency(mut self, residency: ResidencyStatus) -> Self {
+        self.residency = residency;
+        self
+    }
+    
+    /// Monthly payroll assuming the same ordinary wages all year and no
+    /// earlier bonus
     pub fn calculate_monthly(&self, gross_monthly: Decimal, bonus: Decimal) -> SingaporePayrollResult {
-        // CPF ceiling: $6,800/month OW
-        let ow_ceiling = dec!(6800);
-        let ordinary_wages = gross_monthly.min(ow_ceiling);
+        let monthly = Annualization::nominal(&PayFrequency::Monthly);
+        self.calculate_annualized(gross_monthly, bonus, monthly.annualize(gross_monthly), Decimal::ZERO, &monthly)
+    }
+    
+    /// Monthly payroll with the bonus capped by the Additional Wage ceiling,
+    /// given the year's total ordinary wages and bonuses already paid
+    pub fn calculate_monthly_for_year(
+        &self,
+        gross_monthly: Decimal,
+        bonus: Decimal,
+        year_ordinary_wages: Decimal,
+        additional_wages_to_date: Decimal,
+    ) -> SingaporePayrollResult {
+        let monthly = Annualization::nominal(&PayFrequency::Monthly);
+        self.calculate_annualized(gross_monthly, bonus, year_ordinary_wages, additional_wages_to_date, &monthly)
+    }
+
+    /// Payroll for one pay period's ordinary wages plus any bonus, with the
+    /// Ordinary Wage ceiling scaled to the period and tax annualized over
+    /// the periods in `annualization`
+    pub fn calculate_annualized(
+        &self,
+        gross_monthly: Decimal,
+        bonus: Decimal,
+        year_ordinary_wages: Decimal,
+        additional_wages_to_date: Decimal,
+        annualization: &Annualization,
+    ) -> SingaporePayrollResult {
+        let ordinary_wages = gross_monthly.min(annualization.monthly_to_period(self.cpf_ceilings.ordinary_monthly));
+        let additional_wages =
+            bonus.min(self.cpf_ceilings.additional_wage_ceiling(year_ordinary_wages, additional_wages_to_date));
         
         let cpf_rates = CpfRatesByAge::for_age(self.age);
         
         // CPF contributions (only for PR/Citizens)
-        let (cpf_ee, cpf_er) = if self.is_pr_or_citizen {
-            ((ordinary_wages + bonus) * cpf_rates.employee_rate,
-             (ordinary_wages + bonus) * cpf_rates.employer_rate)
-        } else { (Decimal::ZERO, Decimal::ZERO) };
+        let cpf = |rate: Decimal| {
+            if self.is_pr_or_citizen { (ordinary_wages * rate, additional_wages * rate) } else { (Decimal::ZERO, Decimal::ZERO) }
+        };
+        let (ow_cpf_ee, aw_cpf_ee) = cpf(cpf_rates.employee_rate);
+        let (ow_cpf_er, aw_cpf_er) = cpf(cpf_rates.employer_rate);
+        let (cpf_ee, cpf_er) = (ow_cpf_ee + aw_cpf_ee, ow_cpf_er + aw_cpf_er);
         
         // Estimate annual tax
-        let annual_gross = gross_monthly * dec!(12) + bonus;
-        let annual_cpf = cpf_ee * dec!(12);
+        let annual_gross = annualization.annualize(gross_monthly) + bonus;
+        let annual_cpf = annualization.annualize(ow_cpf_ee) + aw_cpf_ee;
         let taxable = annual_gross - annual_cpf; // CPF relief
-        let annual_tax = self.calculate_income_tax(taxable);
-        let monthly_tax = annual_tax / dec!(12);
+        let annual_tax = match self.residency {
+            ResidencyStatus::Resident => self.calculate_income_tax(taxable),
+            // Employment income: flat 15% or resident rates without reliefs, whichever is more
+            ResidencyStatus::NonResident => (annual_gross * dec!(0.15)).max(self.calculate_income_tax(annual_gross)),
+        };
+        let monthly_tax = annualization.period_share(annual_tax);
         
         SingaporePayrollResult {
             gross_salary: gross_monthly,
             bonus,
-            cpf_employee: cpf_ee.round_dp(2),
-            cpf_employer: cpf_er.round_dp(2),
-            estimated_tax: monthly_tax.round_dp(2),
-            net_pay: (gross_monthly + bonus - cpf_ee - monthly_tax).round_dp(2),
+            cpf_employee: self.rounding.finish(cpf_ee),
+            cpf_employer: self.rounding.finish(cpf_er),
+            estimated_tax: self.rounding.finish(monthly_tax),
+            net_pay: self.rounding.finish(gross_monthly + bonus - cpf_ee - monthly_tax),
             employer_cost: gross_monthly + bonus + cpf_er,
         }
     }
@@ -614,6 +891,43 @@ mod tests {
         assert!(result.health_pension_employee > Decimal::ZERO);
     }
     
+    #[test]
+    fn test_japan_trace_reconciles() {
+        let mut calc = JapanTaxCalculator::new();
+        calc.age = 45;
+        
+        for salary in [dec!(250000), dec!(400000), dec!(1500000)] {
+            let result = calc.calculate_monthly_with_trace(salary, dec!(5000000), true);
+            let sum: Decimal = result.trace.iter().map(|s| s.amount).sum();
+            assert_eq!(sum.round_dp(0), result.total_deductions);
+            assert_eq!(result.trace.last().unwrap().running_total, sum);
+            
+            // Marginal bracket steps agree with the deduction-method figure
+            let income_tax: Decimal = result.trace.iter()
+                .filter(|s| s.description.starts_with("Income tax"))
+                .map(|s| s.amount)
+                .sum();
+            assert_eq!(income_tax.round_dp(0), result.income_tax);
+        }
+        
+        assert!(calc.calculate_monthly(dec!(400000), dec!(5000000)).trace.is_empty());
+        
+        // Each quick deduction is what the marginal slices below its bracket imply
+        let brackets = JapanTaxCalculator::income_tax_brackets();
+        for pair in brackets.windows(2) {
+            let ((lower, lower_rate, lower_deduction), (_, rate, deduction)) = (pair[0], pair[1]);
+            assert_eq!(deduction, lower_deduction + lower * (rate - lower_rate));
+        }
+    }
+    
+    #[test]
+    fn test_japan_nets_whole_yen() {
+        let result = JapanTaxCalculator::new().calculate_monthly(dec!(412345.67), dec!(5000000));
+        for amount in [result.income_tax, result.total_deductions, result.net_pay, result.employer_cost] {
+            assert_eq!(amount.scale(), 0, "{} is not whole yen", amount);
+        }
+    }
+    
     #[test]
     fn test_japan_bonus() {
         let calc = JapanTaxCalculator::new();
@@ -622,6 +936,29 @@ mod tests {
         assert!(result.net_bonus < result.gross_bonus);
     }
     
+    #[test]
+    fn test_korea_rounds_each_step() {
+        let step_rounded = KoreanTaxCalculator::new();
+        let final_only = KoreanTaxCalculator {
+            rounding: TaxRounding::final_only(step_rounded.rounding.final_rounding),
+            ..KoreanTaxCalculator::new()
+        };
+        let gross = dec!(45678901);
+        let stepped = step_rounded.calculate(gross);
+        let unstepped = final_only.calculate(gross);
+
+        let lines = |r: &KoreanTaxResult| {
+            r.gukmin_yeonkeum + r.geongang_boheom + r.janggi_yoyang + r.goyong_boheom + r.sodeuk_se + r.jibangsodeuk_se
+        };
+
+        // Step rounding: net pay is gross less the payslip lines to the won
+        assert_eq!(stepped.silsu_ryeong, gross - lines(&stepped));
+        assert_eq!(stepped.silsu_ryeong, dec!(35940992));
+        // Final-only truncation drops the fractional won of every line at once
+        assert_eq!(unstepped.silsu_ryeong, dec!(35940990));
+        assert_ne!(unstepped.silsu_ryeong, gross - lines(&unstepped));
+    }
+    
     #[test]
     fn test_korea() {
         let calc = KoreanTaxCalculator::new();
@@ -637,6 +974,30 @@ mod tests {
         assert!(result.suo_de_shui > Decimal::ZERO);
     }
     
+    #[test]
+    fn test_taiwan_allowances_from_parameters() {
+        // 1,000,000 - 23,000 labor - 15,500 health - 124,000 standard - 92,000 exemption = 745,500 taxable
+        let result = TaiwanTaxCalculator::new().calculate(dec!(1000000));
+        assert_eq!(result.suo_de_shui, dec!(50260));
+        
+        let raised = TaxParameters::builtin("TW", 2025).with(TW_STANDARD_DEDUCTION, dec!(131000));
+        let result = TaiwanTaxCalculator::new().with_parameters(raised).calculate(dec!(1000000));
+        assert_eq!(result.suo_de_shui, dec!(49420));
+    }
+    
+    #[test]
+    fn test_japan_basic_deduction_from_parameters() {
+        let defaults = JapanTaxCalculator::new();
+        assert_eq!(defaults.parameters.amount(JP_BASIC_DEDUCTION), dec!(480000));
+        let baseline = defaults.calculate_monthly(dec!(400000), dec!(5000000));
+        
+        let raised = TaxParameters::builtin("JP", 2025).with(JP_BASIC_DEDUCTION, dec!(580000));
+        let result = JapanTaxCalculator::new().with_parameters(raised).calculate_monthly(dec!(400000), dec!(5000000));
+        assert!(result.income_tax < baseline.income_tax);
+        // Residence tax uses the same deduction on prior-year income: ¥100,000 × 10% / 12
+        assert_eq!(baseline.residence_tax - result.residence_tax, dec!(833));
+    }
+    
     #[test]
     fn test_hong_kong_progressive_vs_standard() {
         let calc = HongKongTaxCalculator::new();
@@ -646,6 +1007,45 @@ mod tests {
         assert!(result.final_tax <= result.standard_tax);
     }
     
+    #[test]
+    fn test_hong_kong_ird_examples() {
+        let calc = HongKongTaxCalculator::new();
+        
+        // Single, $300,000 salary, $15,000 MPF: NCI $153,000 at progressive rates
+        let result = calc.calculate(dec!(300000), dec!(15000));
+        assert_eq!(result.net_income, dec!(285000));
+        assert_eq!(result.net_chargeable_income, dec!(153000));
+        assert_eq!(result.final_tax, dec!(9420));
+        assert!(!result.standard_rate_applied);
+        
+        // Crossover for a single taxpayer is $2,022,000 net income
+        let below = calc.calculate(dec!(2018000), dec!(18000));
+        assert_eq!((below.progressive_tax, below.standard_tax), (dec!(299560), dec!(300000)));
+        assert_eq!(below.final_tax, dec!(299560));
+        let at = calc.calculate(dec!(2040000), dec!(18000));
+        assert_eq!(at.progressive_tax, at.standard_tax);
+        assert_eq!(at.final_tax, dec!(303300));
+        let above = calc.calculate(dec!(3000000), dec!(18000));
+        assert_eq!(above.progressive_tax, dec!(466500));
+        assert_eq!(above.final_tax, dec!(447300));
+        assert!(above.standard_rate_applied);
+    }
+    
+    #[test]
+    fn test_hong_kong_standard_rate_on_net_income() {
+        let calc = HongKongTaxCalculator::new();
+        
+        // A $100,000 donation lowers the standard-rate base, not just NCI
+        let result = calc.calculate_with_deductions(dec!(3000000), dec!(18000), dec!(100000));
+        assert_eq!(result.net_income, dec!(2882000));
+        assert_eq!(result.final_tax, dec!(432300));
+        
+        // Net income over $5M: 15% on the first $5M, 16% on the rest
+        let result = calc.calculate(dec!(6018000), dec!(18000));
+        assert_eq!(result.standard_tax, dec!(910000));
+        assert_eq!(result.final_tax, dec!(910000));
+    }
+    
     #[test]
     fn test_hong_kong_mpf() {
         let calc = HongKongTaxCalculator::new();
@@ -669,6 +1069,68 @@ mod tests {
         assert_eq!(result.cpf_employee, Decimal::ZERO); // No CPF for foreigners
     }
     
+    #[test]
+    fn test_singapore_bonus_below_aw_ceiling() {
+        let calc = SingaporeTaxCalculator::new();
+        // AW ceiling: 102,000 - 72,000 = 30,000
+        let result = calc.calculate_monthly(dec!(6000), dec!(12000));
+        assert_eq!(result.cpf_employee, dec!(3600.00));
+        assert_eq!(result.cpf_employer, dec!(3060.00));
+    }
+    
+    #[test]
+    fn test_singapore_bonus_capped_at_aw_ceiling() {
+        let calc = SingaporeTaxCalculator::new();
+        // Only 30,000 of the 60,000 bonus attracts CPF
+        let result = calc.calculate_monthly(dec!(6000), dec!(60000));
+        assert_eq!(result.cpf_employee, dec!(7200.00));
+        
+        // OW above the monthly ceiling counts at the ceiling: 102,000 - 81,600
+        let high = calc.calculate_monthly(dec!(10000), dec!(50000));
+        assert_eq!(high.cpf_employee, (dec!(6800) + dec!(20400)) * dec!(0.20));
+        
+        // A second bonus only gets what is left of the year's ceiling
+        let second = calc.calculate_monthly_for_year(dec!(6000), dec!(20000), dec!(72000), dec!(25000));
+        assert_eq!(second.cpf_employee, (dec!(6000) + dec!(5000)) * dec!(0.20));
+        let exhausted = calc.calculate_monthly_for_year(dec!(6000), dec!(20000), dec!(72000), dec!(30000));
+        assert_eq!(exhausted.cpf_employee, dec!(1200.00));
+    }
+    
+    #[test]
+    fn test_singapore_non_resident_rates() {
+        use crate::payroll::residency::{Presence, ResidencyDeterminer};
+        
+        let arrived = chrono::NaiveDate::from_ymd_opt(2024, 9, 1).unwrap();
+        let residency = ResidencyDeterminer::new().determine("SG", 2024, &[Presence::new(arrived, None)]);
+        assert_eq!(residency.status, ResidencyStatus::NonResident);
+        
+        let mut calc = SingaporeTaxCalculator::new().with_residency(residency.status);
+        calc.is_pr_or_citizen = false;
+        let non_resident = calc.calculate_monthly(dec!(6000), Decimal::ZERO);
+        let resident = SingaporeTaxCalculator { residency: ResidencyStatus::Resident, ..calc }
+            .calculate_monthly(dec!(6000), Decimal::ZERO);
+        assert_eq!(non_resident.estimated_tax, dec!(900.00)); // 15% flat
+        assert!(resident.estimated_tax < non_resident.estimated_tax);
+    }
+    
+    #[test]
+    fn test_japan_and_taiwan_non_resident_rates() {
+        let jp = JapanTaxCalculator::new().with_residency(ResidencyStatus::NonResident);
+        let result = jp.calculate_monthly_with_trace(dec!(400000), dec!(4800000), true);
+        assert_eq!(result.income_tax, dec!(80000));
+        assert_eq!(result.reconstruction_tax, dec!(1680));
+        assert_eq!(result.residence_tax, Decimal::ZERO);
+        assert!(!result.trace.iter().any(|s| s.description.starts_with("Residence tax")));
+        assert_eq!(jp.calculate_bonus(dec!(1000000), dec!(400000)).income_tax, dec!(200000));
+        
+        let resident = JapanTaxCalculator::new().calculate_monthly(dec!(400000), dec!(4800000));
+        assert!(resident.residence_tax > Decimal::ZERO);
+        
+        let tw = TaiwanTaxCalculator::new().with_residency(ResidencyStatus::NonResident);
+        assert_eq!(tw.calculate(dec!(1000000)).suo_de_shui, dec!(180000));
+        assert!(TaiwanTaxCalculator::new().calculate(dec!(1000000)).suo_de_shui < dec!(180000));
+    }
+    
     #[test]
     fn test_registry() {
         let countries = DevelopedAsiaRegistry::supported_countries();
@@ -0,0 +1,295 @@
+//! Disbursement Ledger
+//!
+//! Record of every net-pay payment sent out, keyed by payroll run and
+//! employee. Runs are already idempotent, but a retried bank upload or
+//! mobile money call could still pay someone twice; the ledger is the last
+//! check before money leaves. `disburse_once` records a payment the first
+//! time and hands back that same record on every replay. A record stays
+//! pending until the bank or provider confirms it, and only pending
+//! records are sent: regenerating a payment file after a failed upload
+//! repeats them under the same reference, which the bank rejects if the
+//! first upload did go through, while confirmed payments are never sent
+//! again.
+
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::integrations::csv_field;
+use super::africa_mobile_gateway::PaymentRequest;
+
+/// Insert used by the Postgres implementation, backed by
+/// `UNIQUE (payroll_run_id, employee_id)` on `disbursements`. No row
+/// returned means the payment already exists and is read back instead.
+pub const INSERT_DISBURSEMENT_SQL: &str = "\
+INSERT INTO disbursements (id, payroll_run_id, employee_id, amount, currency, channel, reference, status, created_at) \
+VALUES ($1, $2, $3, $4, $5, $6, $7, 'pending', $8) \
+ON CONFLICT (payroll_run_id, employee_id) DO NOTHING \
+RETURNING *";
+
+/// Mark a payment confirmed once the bank or provider reports it settled
+pub const CONFIRM_DISBURSEMENT_SQL: &str = "\
+UPDATE disbursements SET status = 'confirmed', confirmed_at = COALESCE(confirmed_at, $3) \
+WHERE payroll_run_id = $1 AND employee_id = $2 \
+RETURNING *";
+
+/// How net pay reaches the employee
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisbursementChannel {
+    /// Bank payment file uploaded to the tenant's bank
+    BankTransfer,
+    MobileMoney,
+}
+
+/// Whether the bank or provider has confirmed a payment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisbursementStatus {
+    /// Recorded and sent, or about to be; may be sent again
+    #[default]
+    Pending,
+    /// Settled; must never be sent again
+    Confirmed,
+}
+
+/// One net-pay payment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Disbursement {
+    pub id: Uuid,
+    pub payroll_run_id: Uuid,
+    pub employee_id: Uuid,
+    pub amount: Decimal,
+    pub currency: String,
+    pub channel: DisbursementChannel,
+    /// Sent to the bank or provider so they can reject duplicates too
+    pub reference: String,
+    pub status: DisbursementStatus,
+    pub created_at: DateTime<Utc>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+}
+
+impl Disbursement {
+    /// Mobile money request paying this disbursement
+    pub fn payment_request(
+        &self,
+        phone_number: &str,
+        recipient_name: &str,
+        country: &str,
+        provider: Option<String>,
+    ) -> PaymentRequest {
+        PaymentRequest {
+            id: self.id.to_string(),
+            external_id: self.reference.clone(),
+            amount: self.amount,
+            currency: self.currency.clone(),
+            phone_number: phone_number.to_string(),
+            recipient_name: recipient_name.to_string(),
+            country: country.to_ascii_uppercase(),
+            provider,
+            description: "Salary payment".to_string(),
+            reference: self.reference.clone(),
+            callback_url: None,
+            metadata: HashMap::from([
+                ("payroll_run_id".to_string(), self.payroll_run_id.to_string()),
+                ("employee_id".to_string(), self.employee_id.to_string()),
+            ]),
+        }
+    }
+}
+
+/// Result of `disburse_once`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Disbursed {
+    /// First payment for this run and employee; send it
+    New(Disbursement),
+    /// Recorded earlier; resend only while it is still pending
+    Existing(Disbursement),
+}
+
+impl Disbursed {
+    pub fn record(&self) -> &Disbursement {
+        match self {
+            Self::New(d) | Self::Existing(d) => d,
+        }
+    }
+
+    pub fn is_new(&self) -> bool {
+        matches!(self, Self::New(_))
+    }
+
+    /// Whether the payment should go (again) to the bank or provider
+    pub fn needs_sending(&self) -> bool {
+        self.record().status == DisbursementStatus::Pending
+    }
+}
+
+/// Disbursements by (run, employee)
+#[derive(Debug, Clone, Default)]
+pub struct DisbursementLedger {
+    // In real implementation, backed by the disbursements table
+    entries: Arc<DashMap<(Uuid, Uuid), Disbursement>>,
+}
+
+impl DisbursementLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a payment of `amount` unless one exists for this run and
+    /// employee, in which case the earlier record comes back unchanged
+    pub fn disburse_once(
+        &self,
+        payroll_run_id: Uuid,
+        employee_id: Uuid,
+        amount: Decimal,
+        currency: &str,
+        channel: DisbursementChannel,
+    ) -> Disbursed {
+        // The shard write lock makes check-and-insert one step, like the
+        // unique constraint behind INSERT_DISBURSEMENT_SQL
+        match self.entries.entry((payroll_run_id, employee_id)) {
+            Entry::Occupied(existing) => Disbursed::Existing(existing.get().clone()),
+            Entry::Vacant(slot) => {
+                let id = Uuid::new_v4();
+                let disbursement = Disbursement {
+                    id,
+                    payroll_run_id,
+                    employee_id,
+                    amount,
+                    currency: currency.to_string(),
+                    channel,
+                    reference: format!("PAY-{}", id.simple()),
+                    status: DisbursementStatus::Pending,
+                    created_at: Utc::now(),
+                    confirmed_at: None,
+                };
+                slot.insert(disbursement.clone());
+                Disbursed::New(disbursement)
+            }
+        }
+    }
+
+    /// Mark a payment confirmed; confirming it again keeps the first time
+    pub fn confirm(&self, payroll_run_id: Uuid, employee_id: Uuid) -> Option<Disbursement> {
+        let mut entry = self.entries.get_mut(&(payroll_run_id, employee_id))?;
+        if entry.status == DisbursementStatus::Pending {
+            entry.status = DisbursementStatus::Confirmed;
+            entry.confirmed_at = Some(Utc::now());
+        }
+        Some(entry.clone())
+    }
+
+    pub fn get(&self, payroll_run_id: Uuid, employee_id: Uuid) -> Option<Disbursement> {
+        self.entries.get(&(payroll_run_id, employee_id)).map(|d| d.clone())
+    }
+
+    pub fn for_run(&self, payroll_run_id: Uuid) -> Vec<Disbursement> {
+        let mut disbursements: Vec<Disbursement> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.key().0 == payroll_run_id)
+            .map(|entry| entry.value().clone())
+            .collect();
+        disbursements.sort_by_key(|d| d.created_at);
+        disbursements
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// A payment-file line: disbursement plus the bank details it pays into
+#[derive(Debug, Clone)]
+pub struct PaymentFileLine<'a> {
+    pub disbursement: &'a Disbursement,
+    pub bank_name: &'a str,
+    pub account_number: &'a str,
+    pub account_name: &'a str,
+}
+
+/// Bank payment file as CSV, one row per disbursement
+pub fn render_payment_file(lines: &[PaymentFileLine<'_>]) -> String {
+    let mut out = String::from("reference,bank_name,account_number,account_name,amount,currency\n");
+    for line in lines {
+        let d = line.disbursement;
+        out.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            csv_field(&d.reference),
+            csv_field(line.bank_name),
+            csv_field(line.account_number),
+            csv_field(line.account_name),
+            d.amount,
+            csv_field(&d.currency),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_repeat_disbursement_returns_prior_record() {
+        let ledger = DisbursementLedger::new();
+        let (run_id, employee_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let first = ledger.disburse_once(run_id, employee_id, dec!(350000), "NGN", DisbursementChannel::BankTransfer);
+        assert!(first.is_new());
+
+        // A retry, even with a different amount or channel, changes nothing
+        let replay = ledger.disburse_once(run_id, employee_id, dec!(999999), "NGN", DisbursementChannel::MobileMoney);
+        assert!(!replay.is_new());
+        assert_eq!(replay.record(), first.record());
+        assert_eq!(replay.record().amount, dec!(350000));
+        assert_eq!(ledger.len(), 1);
+
+        // Same employee in another run is a separate payment
+        assert!(ledger.disburse_once(Uuid::new_v4(), employee_id, dec!(350000), "NGN", DisbursementChannel::BankTransfer).is_new());
+        assert_eq!(ledger.len(), 2);
+        assert_eq!(ledger.for_run(run_id).len(), 1);
+    }
+
+    #[test]
+    fn test_pending_until_confirmed() {
+        let ledger = DisbursementLedger::new();
+        let (run_id, employee_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let first = ledger.disburse_once(run_id, employee_id, dec!(350000), "NGN", DisbursementChannel::BankTransfer);
+        assert!(first.needs_sending());
+        // The upload failed: the replay is still pending, under the same reference
+        let replay = ledger.disburse_once(run_id, employee_id, dec!(350000), "NGN", DisbursementChannel::BankTransfer);
+        assert!(replay.needs_sending());
+        assert_eq!(replay.record().reference, first.record().reference);
+
+        let confirmed = ledger.confirm(run_id, employee_id).unwrap();
+        assert_eq!(confirmed.status, DisbursementStatus::Confirmed);
+        assert_eq!(ledger.confirm(run_id, employee_id).unwrap().confirmed_at, confirmed.confirmed_at);
+        assert!(!ledger.disburse_once(run_id, employee_id, dec!(350000), "NGN", DisbursementChannel::BankTransfer).needs_sending());
+        assert!(ledger.confirm(Uuid::new_v4(), employee_id).is_none());
+    }
+
+    #[test]
+    fn test_payment_request_carries_reference() {
+        let ledger = DisbursementLedger::new();
+        let paid = ledger.disburse_once(Uuid::new_v4(), Uuid::new_v4(), dec!(120000), "GHS", DisbursementChannel::MobileMoney);
+        let request = paid.record().payment_request("+233241234567", "Kofi Mensah", "gh", Some("mtn_momo".to_string()));
+
+        assert_eq!(request.reference, paid.record().reference);
+        assert_eq!(request.external_id, paid.record().reference);
+        assert_eq!(request.amount, dec!(120000));
+        assert_eq!(request.country, "GH");
+    }
+}
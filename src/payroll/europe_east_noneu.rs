@@ -16,6 +16,9 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::value_objects::PayFrequency;
+use super::annualization::Annualization;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // UKRAINE (UA) - PDFO + Military Levy + ESV
 // ═══════════════════════════════════════════════════════════════════════════
@@ -227,15 +230,16 @@ impl AzerbaijanTaxCalculator {
     const UNEMP_ER: Decimal = dec!(0.005);  // 0.5% unemployment (employer)
     
     pub fn calculate(gross_monthly: Decimal) -> AzerbaijanTaxResult {
+        Self::calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// Income tax and DSMF on one pay period's pay, annualized over the
+    /// periods in `annualization`
+    pub fn calculate_annualized(gross_monthly: Decimal, annualization: &Annualization) -> AzerbaijanTaxResult {
         // Progressive: 14% up to AZN 8,000, 25% above
-        let annual = gross_monthly * dec!(12);
-        let income_tax = if annual <= dec!(8000) {
-            gross_monthly * dec!(0.14)
-        } else {
-            let base = dec!(8000) / dec!(12) * dec!(0.14);
-            let excess = (gross_monthly - dec!(8000) / dec!(12)) * dec!(0.25);
-            base + excess.max(Decimal::ZERO)
-        };
+        let annual = annualization.annualize(gross_monthly);
+        let annual_tax = annual.min(dec!(8000)) * dec!(0.14) + (annual - dec!(8000)).max(Decimal::ZERO) * dec!(0.25);
+        let income_tax = annualization.period_share(annual_tax);
         
         let dsmf_ee = gross_monthly * Self::DSMF_EE;
         let dsmf_er = gross_monthly * Self::DSMF_ER;
@@ -281,15 +285,14 @@ impl RussiaTaxCalculator {
     const FSS_ER: Decimal = dec!(0.029);         // 2.9% social (employer)
     const FOMS_ER: Decimal = dec!(0.051);        // 5.1% medical (employer)
     
+    const HIGH_RATE_THRESHOLD: Decimal = dec!(5000000);
+
+    /// NDFL on this period's pay given `ytd_income` already paid this
+    /// calendar year: 15% only on the part taking the year past 5M RUB
     pub fn calculate(gross_monthly: Decimal, ytd_income: Decimal) -> RussiaTaxResult {
-        // 15% kicks in above 5M RUB annually
-        let rate = if ytd_income + gross_monthly > dec!(5000000) {
-            Self::RATE_HIGH
-        } else {
-            Self::RATE_STANDARD
-        };
-        
-        let ndfl = gross_monthly * rate;
+        let standard_room = (Self::HIGH_RATE_THRESHOLD - ytd_income).max(Decimal::ZERO);
+        let at_standard = gross_monthly.min(standard_room);
+        let ndfl = at_standard * Self::RATE_STANDARD + (gross_monthly - at_standard) * Self::RATE_HIGH;
         let pfr = gross_monthly * Self::PFR_ER;
         let fss = gross_monthly * Self::FSS_ER;
         let foms = gross_monthly * Self::FOMS_ER;
@@ -331,10 +334,15 @@ impl TurkeyTaxCalculator {
     const UNEMP_ER: Decimal = dec!(0.02);    // 2% unemployment (employer)
     
     pub fn calculate(gross_monthly: Decimal) -> TurkeyTaxResult {
-        let annual = gross_monthly * dec!(12);
+        Self::calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// Income tax, SGK, and unemployment insurance on one pay period's pay
+    pub fn calculate_annualized(gross_monthly: Decimal, annualization: &Annualization) -> TurkeyTaxResult {
+        let annual = annualization.annualize(gross_monthly);
         
         // 2024 brackets (simplified to TRY)
-        let income_tax = Self::calculate_progressive(annual) / dec!(12);
+        let income_tax = annualization.period_share(Self::calculate_progressive(annual));
         
         let sgk_ee = gross_monthly * Self::SGK_EE;
         let sgk_er = gross_monthly * Self::SGK_ER;
@@ -398,10 +406,15 @@ impl KosovoTaxCalculator {
     const TRUST_ER: Decimal = dec!(0.05);    // 5% pension (employer)
     
     pub fn calculate(gross_monthly: Decimal) -> KosovoTaxResult {
-        let annual = gross_monthly * dec!(12);
+        Self::calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// Income tax and Trust pension on one pay period's pay
+    pub fn calculate_annualized(gross_monthly: Decimal, annualization: &Annualization) -> KosovoTaxResult {
+        let annual = annualization.annualize(gross_monthly);
         
         // Progressive: 0% up to €960, 4% €960-3000, 8% €3000-5400, 10% above
-        let income_tax = Self::calculate_progressive(annual) / dec!(12);
+        let income_tax = annualization.period_share(Self::calculate_progressive(annual));
         
         let trust_ee = gross_monthly * Self::TRUST_EE;
         let trust_er = gross_monthly * Self::TRUST_ER;
@@ -557,7 +570,8 @@ mod tests {
     #[test]
     fn test_russia_high_income() {
         let result = RussiaTaxCalculator::calculate(dec!(500000), dec!(4900000));
-        assert_eq!(result.ndfl, dec!(75000)); // 15%
+        // 100k to reach 5M at 13%, the other 400k at 15%
+        assert_eq!(result.ndfl, dec!(73000));
     }
     
     #[test]
@@ -0,0 +1,133 @@
+//! Exchange Rates
+//!
+//! Dated rates per currency pair, as the tenant's treasury publishes them.
+//! A lookup takes the most recent rate on or before the date asked for, so
+//! a June run converts at June's rate however long after it's reported.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use chrono::NaiveDate;
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Exchange rate lookup errors
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FxError {
+    #[error("No {base}/{quote} exchange rate on file")]
+    NoRate { base: String, quote: String },
+
+    #[error("No {base}/{quote} exchange rate on or before {date}; history starts {earliest}")]
+    BeforeHistory { base: String, quote: String, date: NaiveDate, earliest: NaiveDate },
+
+    #[error("Exchange rate must be positive, got {0}")]
+    InvalidRate(Decimal),
+}
+
+/// Units of `quote` per unit of `base`, in effect from `effective_date`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FxRate {
+    pub base: String,
+    pub quote: String,
+    pub effective_date: NaiveDate,
+    pub rate: Decimal,
+}
+
+/// Rate history by (base, quote)
+#[derive(Debug, Clone, Default)]
+pub struct FxRates {
+    // In real implementation, backed by the fx_rates table
+    rates: Arc<DashMap<(String, String), BTreeMap<NaiveDate, Decimal>>>,
+}
+
+impl FxRates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the `base`/`quote` rate from `effective_date`, replacing any
+    /// recorded for the same day
+    pub fn record(&self, base: &str, quote: &str, effective_date: NaiveDate, rate: Decimal) -> Result<(), FxError> {
+        if rate <= Decimal::ZERO {
+            return Err(FxError::InvalidRate(rate));
+        }
+        self.rates.entry(pair(base, quote)).or_default().insert(effective_date, rate);
+        Ok(())
+    }
+
+    /// Most recent `base`/`quote` rate on or before `date`. A currency
+    /// converts to itself at 1.
+    pub fn rate_as_of(&self, base: &str, quote: &str, date: NaiveDate) -> Result<FxRate, FxError> {
+        let (base, quote) = pair(base, quote);
+        if base == quote {
+            return Ok(FxRate { base, quote, effective_date: date, rate: Decimal::ONE });
+        }
+        let history = self.rates.get(&(base.clone(), quote.clone()));
+        let Some(history) = history.filter(|h| !h.is_empty()) else {
+            return Err(FxError::NoRate { base, quote });
+        };
+        match history.range(..=date).next_back() {
+            Some((effective_date, rate)) => Ok(FxRate {
+                effective_date: *effective_date,
+                rate: *rate,
+                base: base.clone(),
+                quote: quote.clone(),
+            }),
+            None => {
+                let earliest = *history.keys().next().expect("history is not empty");
+                Err(FxError::BeforeHistory { base: base.clone(), quote: quote.clone(), date, earliest })
+            }
+        }
+    }
+
+    /// `amount` in `base` converted to `quote` at the rate on `date`
+    pub fn convert(&self, amount: Decimal, base: &str, quote: &str, date: NaiveDate) -> Result<Decimal, FxError> {
+        Ok(amount * self.rate_as_of(base, quote, date)?.rate)
+    }
+}
+
+fn pair(base: &str, quote: &str) -> (String, String) {
+    (base.to_ascii_uppercase(), quote.to_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_as_of_picks_latest_rate_on_or_before_date() {
+        let rates = FxRates::new();
+        rates.record("NGN", "USD", date(5, 31), dec!(0.00068)).unwrap();
+        rates.record("NGN", "USD", date(6, 14), dec!(0.00067)).unwrap();
+        rates.record("ngn", "usd", date(7, 1), dec!(0.00066)).unwrap();
+
+        let june = rates.rate_as_of("NGN", "USD", date(6, 30)).unwrap();
+        assert_eq!((june.effective_date, june.rate), (date(6, 14), dec!(0.00067)));
+        assert_eq!(rates.rate_as_of("NGN", "USD", date(6, 14)).unwrap().rate, dec!(0.00067));
+        assert_eq!(rates.rate_as_of("NGN", "USD", date(6, 13)).unwrap().rate, dec!(0.00068));
+        assert_eq!(rates.rate_as_of("NGN", "USD", date(12, 31)).unwrap().rate, dec!(0.00066));
+        assert_eq!(rates.convert(dec!(1_000_000), "NGN", "USD", date(6, 30)).unwrap(), dec!(670));
+        assert_eq!(rates.convert(dec!(5), "KES", "kes", date(1, 1)).unwrap(), dec!(5));
+    }
+
+    #[test]
+    fn test_lookup_before_history_errors() {
+        let rates = FxRates::new();
+        rates.record("ZAR", "USD", date(3, 1), dec!(0.053)).unwrap();
+
+        assert_eq!(
+            rates.rate_as_of("ZAR", "USD", date(2, 29)),
+            Err(FxError::BeforeHistory { base: "ZAR".into(), quote: "USD".into(), date: date(2, 29), earliest: date(3, 1) })
+        );
+        assert_eq!(
+            rates.rate_as_of("USD", "ZAR", date(6, 1)),
+            Err(FxError::NoRate { base: "USD".into(), quote: "ZAR".into() })
+        );
+        assert_eq!(rates.record("ZAR", "USD", date(4, 1), dec!(0)), Err(FxError::InvalidRate(dec!(0))));
+    }
+}
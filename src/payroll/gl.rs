@@ -0,0 +1,202 @@
+//! General Ledger Journal
+//!
+//! Turns a processed run into the journal finance posts: wage and employer
+//! contribution expense debits against liability credits for every
+//! deduction and a net-pay clearing credit. Accounts come from a tenant's
+//! `GlAccountMap`; lines are grouped by currency and every currency's set
+//! balances on its own, since a ledger never nets naira against rand.
+
+use std::collections::BTreeMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::advance::{SalaryAdvance, SALARY_ADVANCE_LINE};
+use super::models::PayrollItem;
+
+/// Ledger account codes for each payroll line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlAccountMap {
+    pub wage_expense: String,
+    pub employer_contribution_expense: String,
+    pub income_tax_payable: String,
+    pub pension_payable: String,
+    pub nhf_payable: String,
+    pub loan_receivable: String,
+    /// Salary advances paid and not yet recovered
+    #[serde(default = "default_advances_receivable")]
+    pub advances_receivable: String,
+    /// Other-deduction key (e.g. "uif", "benefits") to account
+    #[serde(default)]
+    pub other_deductions: BTreeMap<String, String>,
+    /// Other deductions without their own account
+    pub other_deductions_payable: String,
+    pub net_pay_clearing: String,
+}
+
+impl Default for GlAccountMap {
+    fn default() -> Self {
+        Self {
+            wage_expense: "6000".to_string(),
+            employer_contribution_expense: "6100".to_string(),
+            income_tax_payable: "2100".to_string(),
+            pension_payable: "2110".to_string(),
+            nhf_payable: "2120".to_string(),
+            loan_receivable: "1300".to_string(),
+            advances_receivable: default_advances_receivable(),
+            other_deductions: BTreeMap::new(),
+            other_deductions_payable: "2190".to_string(),
+            net_pay_clearing: "2200".to_string(),
+        }
+    }
+}
+
+fn default_advances_receivable() -> String {
+    "1310".to_string()
+}
+
+impl GlAccountMap {
+    /// Advance recoveries clear the receivable unless mapped elsewhere
+    fn other_deduction_account(&self, key: &str) -> &str {
+        match self.other_deductions.get(key) {
+            Some(account) => account,
+            None if key == SALARY_ADVANCE_LINE => &self.advances_receivable,
+            None => &self.other_deductions_payable,
+        }
+    }
+}
+
+/// One debit or credit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalLine {
+    pub currency: String,
+    pub account: String,
+    pub description: String,
+    pub debit: Decimal,
+    pub credit: Decimal,
+}
+
+impl JournalLine {
+    /// Debit positive, credit negative
+    pub fn amount(&self) -> Decimal {
+        self.debit - self.credit
+    }
+}
+
+/// Journal entries for one payroll run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlJournal {
+    pub payroll_run_id: Uuid,
+    pub lines: Vec<JournalLine>,
+}
+
+impl GlJournal {
+    /// Debits less credits per currency; zero for a balanced journal
+    pub fn balances(&self) -> BTreeMap<String, Decimal> {
+        let mut balances: BTreeMap<String, Decimal> = BTreeMap::new();
+        for line in &self.lines {
+            *balances.entry(line.currency.clone()).or_default() += line.amount();
+        }
+        balances
+    }
+
+    pub fn is_balanced(&self) -> bool {
+        self.balances().values().all(|balance| balance.is_zero())
+    }
+
+    pub fn lines_for<'a>(&'a self, currency: &'a str) -> impl Iterator<Item = &'a JournalLine> {
+        self.lines.iter().filter(move |line| line.currency == currency)
+    }
+}
+
+/// Per-account totals for one currency, sorted by account
+#[derive(Default)]
+struct CurrencyTotals {
+    debits: BTreeMap<(String, String), Decimal>,
+    credits: BTreeMap<(String, String), Decimal>,
+}
+
+impl CurrencyTotals {
+    fn debit(&mut self, account: &str, description: &str, amount: Decimal) {
+        *self.debits.entry((account.to_string(), description.to_string())).or_default() += amount;
+    }
+
+    fn credit(&mut self, account: &str, description: &str, amount: Decimal) {
+        *self.credits.entry((account.to_string(), description.to_string())).or_default() += amount;
+    }
+}
+
+/// Build a run's journal from `(currency, item)` pairs
+pub fn build_journal<'a>(
+    payroll_run_id: Uuid,
+    items: impl IntoIterator<Item = (&'a str, &'a PayrollItem)>,
+    accounts: &GlAccountMap,
+) -> GlJournal {
+    let mut by_currency: BTreeMap<String, CurrencyTotals> = BTreeMap::new();
+
+    for (currency, item) in items {
+        let totals = by_currency.entry(currency.to_string()).or_default();
+
+        totals.debit(&accounts.wage_expense, "Gross wages", item.gross_pay);
+        totals.debit(&accounts.employer_contribution_expense, "Employer pension", item.pension_employer);
+        for (code, amount) in item.employer_contributions.iter().filter(|(code, _)| *code != "pension") {
+            totals.debit(&accounts.employer_contribution_expense, &format!("Employer {}", code), *amount);
+            totals.credit(accounts.other_deduction_account(code), code, *amount);
+        }
+
+        totals.credit(&accounts.income_tax_payable, "PAYE", item.paye_tax);
+        totals.credit(&accounts.pension_payable, "Pension", item.pension_employee + item.pension_employer);
+        totals.credit(&accounts.nhf_payable, "NHF", item.nhf_deduction);
+        totals.credit(&accounts.loan_receivable, "Loan repayment", item.loan_repayment);
+
+        let mut other_total = Decimal::ZERO;
+        for (key, amount) in item.other_deduction_amounts() {
+            totals.credit(accounts.other_deduction_account(&key), &key, amount);
+            other_total += amount;
+        }
+        // Anything in total deductions not broken out above
+        let unallocated = item.total_deductions
+            - item.paye_tax
+            - item.pension_employee
+            - item.nhf_deduction
+            - item.loan_repayment
+            - other_total;
+        totals.credit(&accounts.other_deductions_payable, "Other deductions", unallocated);
+
+        totals.credit(&accounts.net_pay_clearing, "Net pay", item.net_pay);
+    }
+
+    GlJournal { payroll_run_id, lines: into_lines(by_currency) }
+}
+
+/// Journal for an off-cycle salary advance, under the advance's id: the
+/// receivable against tax withheld and the net paid
+pub fn advance_journal(advance: &SalaryAdvance, accounts: &GlAccountMap) -> GlJournal {
+    let mut totals = CurrencyTotals::default();
+    totals.debit(&accounts.advances_receivable, "Salary advance", advance.amount);
+    totals.credit(&accounts.income_tax_payable, "PAYE", advance.tax_withheld);
+    totals.credit(&accounts.net_pay_clearing, "Net pay", advance.net_paid);
+    GlJournal { payroll_run_id: advance.id, lines: into_lines(BTreeMap::from([(advance.currency.clone(), totals)])) }
+}
+
+/// Non-zero lines, debits then credits, per currency
+fn into_lines(by_currency: BTreeMap<String, CurrencyTotals>) -> Vec<JournalLine> {
+    let mut lines = Vec::new();
+    for (currency, totals) in by_currency {
+        let debits = totals.debits.into_iter().map(|(key, amount)| (key, amount, Decimal::ZERO));
+        let credits = totals.credits.into_iter().map(|(key, amount)| (key, Decimal::ZERO, amount));
+        lines.extend(
+            debits
+                .chain(credits)
+                .filter(|(_, debit, credit)| !debit.is_zero() || !credit.is_zero())
+                .map(|((account, description), debit, credit)| JournalLine {
+                    currency: currency.clone(),
+                    account,
+                    description,
+                    debit,
+                    credit,
+                }),
+        );
+    }
+    lines
+}
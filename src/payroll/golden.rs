@@ -0,0 +1,279 @@
+//! Golden Tax Fixtures
+//!
+//! Worked examples that each tax engine must keep reproducing, kept as one
+//! JSON file per country under `fixtures/tax`. A case gives the gross pay,
+//! any engine options, and the expected result fields; each expected
+//! amount is compared at the number of decimals it is written with (rounding
+//! half to even), so an official example quoted in whole rand checks to the rand.
+//!
+//! ```json
+//! {
+//!   "country_code": "ZA",
+//!   "source": "SARS 2024/25 monthly tax deduction tables",
+//!   "cases": [
+//!     { "name": "R30,000 a month, under 65", "gross": "30000",
+//!       "options": { "age": 40 }, "expected": { "monthly_paye": "4783.08" } }
+//!   ]
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::PayFrequency;
+use super::developed_asia::JapanTaxCalculator;
+use super::south_africa::SouthAfricaTaxCalculator;
+use super::south_america::BrazilTaxCalculator;
+use super::tax_calculator::NigerianTaxCalculator;
+use super::western_europe::{BundessteuerTarif, KantonaleSteuer, SwissSocialInsurance, SwissTaxCalculator};
+
+/// Directory the fixture test reads, relative to the crate root
+pub const GOLDEN_FIXTURE_DIR: &str = "fixtures/tax";
+
+/// Fixture loading and evaluation errors
+#[derive(Debug, thiserror::Error)]
+pub enum GoldenError {
+    #[error("Cannot read golden fixtures {path}: {source}")]
+    Io { path: String, source: std::io::Error },
+
+    #[error("Malformed golden fixture {path}: {message}")]
+    Malformed { path: String, message: String },
+
+    #[error("No golden fixture runner for country: {0}")]
+    UnsupportedCountry(String),
+
+    #[error("Invalid option for {country}: {message}")]
+    InvalidOption { country: String, message: String },
+}
+
+/// One country's worked examples
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GoldenFixtureFile {
+    pub country_code: String,
+    /// Where the examples come from, e.g. the authority's published tables
+    #[serde(default)]
+    pub source: String,
+    pub cases: Vec<GoldenCase>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GoldenCase {
+    pub name: String,
+    /// Pay in the period the engine works in: monthly, or annual for
+    /// engines that calculate a year (CH, NG)
+    pub gross: Decimal,
+    #[serde(default)]
+    pub options: GoldenOptions,
+    /// Result field to expected amount
+    pub expected: BTreeMap<String, Decimal>,
+}
+
+/// Engine inputs beyond gross pay; each engine reads the ones it takes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GoldenOptions {
+    pub age: Option<u8>,
+    pub dependants: Option<u8>,
+    /// Pay frequency name as accepted by `PayFrequency::from_str`
+    pub pay_frequency: Option<String>,
+    /// Previous year's income, for Japanese residence tax
+    pub prev_year_income: Option<Decimal>,
+    /// "zurich", "zug", or "geneva"
+    pub municipality: Option<String>,
+    /// Annual pension and NHF contributions, for Nigerian relief
+    pub pension: Option<Decimal>,
+    pub nhf: Option<Decimal>,
+}
+
+/// An expected field the engine no longer reproduces
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoldenMismatch {
+    pub case: String,
+    pub field: String,
+    pub expected: Decimal,
+    /// `None` when the result has no such field
+    pub actual: Option<Decimal>,
+}
+
+/// Outcome of one country's fixtures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoldenReport {
+    pub country_code: String,
+    pub cases: usize,
+    pub mismatches: Vec<GoldenMismatch>,
+}
+
+impl GoldenReport {
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl std::fmt::Display for GoldenReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = if self.passed() { "PASS" } else { "FAIL" };
+        write!(f, "{} {}: {} cases", status, self.country_code, self.cases)?;
+        for m in &self.mismatches {
+            let actual = m.actual.map_or("missing".to_string(), |a| a.to_string());
+            write!(f, "\n  {} / {}: expected {}, got {}", m.case, m.field, m.expected, actual)?;
+        }
+        Ok(())
+    }
+}
+
+/// Run the engine for `country_code` and return its result as JSON
+pub fn evaluate(country_code: &str, gross: Decimal, options: &GoldenOptions) -> Result<serde_json::Value, GoldenError> {
+    let country = country_code.to_ascii_uppercase();
+    let invalid = |message: String| GoldenError::InvalidOption { country: country.clone(), message };
+    let frequency = match &options.pay_frequency {
+        Some(name) => name.parse::<PayFrequency>().map_err(|e| invalid(e.to_string()))?,
+        None => PayFrequency::Monthly,
+    };
+
+    let result = match country.as_str() {
+        "ZA" => serde_json::to_value(SouthAfricaTaxCalculator::new().calculate_for_period(
+            gross,
+            options.age.unwrap_or(35),
+            &frequency,
+        )),
+        "BR" => serde_json::to_value(BrazilTaxCalculator::new().calculate(gross, options.dependants.unwrap_or(0))),
+        "JP" => {
+            let calculator = JapanTaxCalculator {
+                age: options.age.unwrap_or(35),
+                num_dependents: options.dependants.unwrap_or(0),
+                ..JapanTaxCalculator::new()
+            };
+            serde_json::to_value(calculator.calculate_monthly(gross, options.prev_year_income.unwrap_or_default()))
+        }
+        "CH" => {
+            let kantonale_steuer = match options.municipality.as_deref().unwrap_or("zurich") {
+                "zurich" => KantonaleSteuer::zurich_city(),
+                "zug" => KantonaleSteuer::zug_city(),
+                "geneva" => KantonaleSteuer::geneva_city(),
+                other => return Err(invalid(format!("unknown municipality {}", other))),
+            };
+            let calculator = SwissTaxCalculator {
+                bundessteuer_tarif: BundessteuerTarif::single_tarif(),
+                kantonale_steuer,
+                social_insurance: SwissSocialInsurance::default(),
+                age: options.age.unwrap_or(35),
+            };
+            serde_json::to_value(calculator.calculate(gross))
+        }
+        "NG" => serde_json::to_value(NigerianTaxCalculator::new().calculate_annual_paye(
+            gross,
+            options.pension.unwrap_or_default(),
+            options.nhf.unwrap_or_default(),
+        )),
+        _ => return Err(GoldenError::UnsupportedCountry(country)),
+    };
+    result.map_err(|e| invalid(e.to_string()))
+}
+
+/// Check every case in `fixture` against its engine
+pub fn run_fixture(fixture: &GoldenFixtureFile) -> Result<GoldenReport, GoldenError> {
+    let mut mismatches = Vec::new();
+    for case in &fixture.cases {
+        let result = evaluate(&fixture.country_code, case.gross, &case.options)?;
+        for (field, expected) in &case.expected {
+            let actual = result
+                .get(field)
+                .and_then(|v| serde_json::from_value::<Decimal>(v.clone()).ok())
+                .map(|a| a.round_dp(expected.scale()));
+            if actual != Some(*expected) {
+                mismatches.push(GoldenMismatch {
+                    case: case.name.clone(),
+                    field: field.clone(),
+                    expected: *expected,
+                    actual,
+                });
+            }
+        }
+    }
+    Ok(GoldenReport { country_code: fixture.country_code.to_ascii_uppercase(), cases: fixture.cases.len(), mismatches })
+}
+
+/// Load and check every `*.json` fixture in `dir`, by file name
+pub fn run_fixture_dir(dir: &Path) -> Result<Vec<GoldenReport>, GoldenError> {
+    let io = |path: &Path| {
+        let path = path.display().to_string();
+        move |source| GoldenError::Io { path, source }
+    };
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .map_err(io(dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let text = std::fs::read_to_string(path).map_err(io(path))?;
+            let fixture: GoldenFixtureFile = serde_json::from_str(&text)
+                .map_err(|e| GoldenError::Malformed { path: path.display().to_string(), message: e.to_string() })?;
+            run_fixture(&fixture)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_golden_fixtures() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(GOLDEN_FIXTURE_DIR);
+        let reports = run_fixture_dir(&dir).unwrap();
+
+        let countries: Vec<_> = reports.iter().map(|r| r.country_code.as_str()).collect();
+        assert_eq!(countries, ["BR", "CH", "JP", "NG", "ZA"]);
+        let failed: Vec<String> = reports.iter().filter(|r| !r.passed()).map(|r| r.to_string()).collect();
+        assert!(failed.is_empty(), "golden fixtures failed:\n{}", failed.join("\n"));
+    }
+
+    #[test]
+    fn test_mismatch_reported_per_field() {
+        let fixture = GoldenFixtureFile {
+            country_code: "za".into(),
+            source: String::new(),
+            cases: vec![GoldenCase {
+                name: "R15,000".into(),
+                gross: dec!(15000),
+                options: GoldenOptions::default(),
+                expected: BTreeMap::from([
+                    ("monthly_paye".to_string(), dec!(1263.75)),
+                    ("sdl".to_string(), dec!(151)),
+                    ("pension".to_string(), dec!(0)),
+                ]),
+            }],
+        };
+
+        let report = run_fixture(&fixture).unwrap();
+        assert!(!report.passed());
+        assert_eq!(report.country_code, "ZA");
+        assert_eq!(
+            report.mismatches,
+            vec![
+                GoldenMismatch { case: "R15,000".into(), field: "pension".into(), expected: dec!(0), actual: None },
+                GoldenMismatch { case: "R15,000".into(), field: "sdl".into(), expected: dec!(151), actual: Some(dec!(150)) },
+            ]
+        );
+        assert!(report.to_string().starts_with("FAIL ZA: 1 cases"));
+    }
+
+    #[test]
+    fn test_unknown_country_and_option_rejected() {
+        assert!(matches!(
+            evaluate("XX", dec!(1000), &GoldenOptions::default()),
+            Err(GoldenError::UnsupportedCountry(c)) if c == "XX"
+        ));
+        let options = GoldenOptions { municipality: Some("basel".into()), ..GoldenOptions::default() };
+        assert!(matches!(evaluate("CH", dec!(100000), &options), Err(GoldenError::InvalidOption { .. })));
+    }
+}
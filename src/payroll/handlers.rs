@@ -5,31 +5,41 @@
 use axum::{
     extract::{Path, State, Query},
     http::StatusCode,
-    response::IntoResponse,
-    Json,
+    response::{IntoResponse, Response},
+    Extension, Json,
 };
+use std::sync::{Arc, RwLock};
 use serde::{Deserialize, Serialize};
+use chrono::Datelike;
 use uuid::Uuid;
 use rust_decimal::Decimal;
 
+use crate::auth::{AuthContext, Permission};
+use crate::db::{DbPools, StaleReads};
+use crate::domain::services::EmployeeService;
+use crate::domain::value_objects::PayFrequency;
+use crate::features::{Feature, FeatureFlags};
+use crate::validation::ValidJson;
 use super::{
     models::*,
-    service::{PayrollService, TaxPreviewResponse},
+    africa_mobile_gateway::PaymentRequest,
+    disbursement::Disbursement,
+    calendar::PayPeriod,
+    service::{PayrollError, PayrollService},
+    registry::PayrollRegistry,
 };
 
 /// Shared application state
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct AppState {
     pub payroll_service: PayrollService,
-    // In real app: database pool, auth service, etc.
-}
-
-impl Default for AppState {
-    fn default() -> Self {
-        Self {
-            payroll_service: PayrollService::new(),
-        }
-    }
+    /// Employee records, shared with the employee API; onboarding status
+    /// for each run is read from here
+    pub employees: Arc<RwLock<EmployeeService>>,
+    pub features: FeatureFlags,
+    /// Primary for writes, replica for read-only endpoints
+    pub db: DbPools,
+    // In real app: auth service, etc.
 }
 
 /// API Response wrapper
@@ -72,91 +82,224 @@ pub struct ListPayrollRunsQuery {
 /// POST /api/v1/payroll/runs
 pub async fn create_payroll_run(
     State(state): State<AppState>,
-    Json(request): Json<CreatePayrollRunRequest>,
+    ValidJson(request): ValidJson<CreatePayrollRunRequest>,
 ) -> impl IntoResponse {
     // In real implementation, get tenant_id from auth context
     let tenant_id = Uuid::new_v4();
     
     match state.payroll_service.create_payroll_run(tenant_id, request) {
-        Ok(run) => (StatusCode::CREATED, Json(ApiResponse::success(run))),
+        Ok(run) => {
+            state.db.write().record("insert_payroll_run");
+            (StatusCode::CREATED, Json(ApiResponse::success(run)))
+        }
+        Err(e @ PayrollError::OverlappingRun(_)) => (StatusCode::CONFLICT, Json(ApiResponse::<PayrollRun>::error(e.to_string()))),
         Err(e) => (StatusCode::BAD_REQUEST, Json(ApiResponse::<PayrollRun>::error(e.to_string()))),
     }
 }
 
-/// Get payroll run by ID
+/// Get payroll run by ID. Reads the primary: clients fetch a run right
+/// after creating or processing it and must see that change.
 /// 
 /// GET /api/v1/payroll/runs/:id
 pub async fn get_payroll_run(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
+    state.db.read(StaleReads::Never).record("select_payroll_run");
     // In real implementation, fetch from database
     Json(ApiResponse::<PayrollRun>::error(format!("Payroll run {} not found (stub)", id)))
 }
 
-/// List payroll runs
+/// List payroll runs. Reads the replica; a run created within the
+/// replication lag may not be listed yet.
 /// 
 /// GET /api/v1/payroll/runs
 pub async fn list_payroll_runs(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Query(_query): Query<ListPayrollRunsQuery>,
 ) -> impl IntoResponse {
+    state.db.read(StaleReads::Tolerated).record("list_payroll_runs");
     // In real implementation, fetch from database with filters
     let runs: Vec<PayrollRun> = vec![];
     Json(ApiResponse::success(runs))
 }
 
-/// Process payroll handler
-/// 
+/// Start processing a payroll run. Processing happens on a worker; the
+/// response is the job to poll.
+///
 /// POST /api/v1/payroll/runs/:id/process
 pub async fn process_payroll_run(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
     Path(id): Path<Uuid>,
-    Json(_request): Json<ProcessPayrollRequest>,
-) -> impl IntoResponse {
-    // In real implementation:
-    // 1. Fetch payroll run from DB
-    // 2. Fetch active employees with salary configs
-    // 3. Process payroll
-    // 4. Save payroll items to DB
-    // 5. Update payroll run status
-    
-    Json(ApiResponse::<PayrollRun>::error(format!("Processing payroll {} (stub)", id)))
+    Json(request): Json<ProcessPayrollRequest>,
+) -> Response {
+    if !auth.has_permission(Permission::PayrollProcess) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to process payroll"))).into_response();
+    }
+    let not_found = || {
+        (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error(format!("Payroll run {} not found", id)))).into_response()
+    };
+    if state.payroll_service.payroll_run(id).is_none_or(|run| run.tenant_id != auth.tenant_id) {
+        return not_found();
+    }
+
+    let employee_ids = request.employee_ids.filter(|ids| !ids.is_empty());
+    let mut employees = match state.payroll_service.salaries().load(auth.tenant_id, employee_ids.as_deref()) {
+        Ok(employees) => employees,
+        Err(missing) => {
+            let missing: Vec<String> = missing.iter().map(Uuid::to_string).collect();
+            let message = format!("No salary record for employees: {}", missing.join(", "));
+            return (StatusCode::UNPROCESSABLE_ENTITY, Json(ApiResponse::<()>::error(message))).into_response();
+        }
+    };
+    // Onboarding status comes from the employee record; anyone without one
+    // is left unknown, and so out of the run
+    {
+        let records = state.employees.read().unwrap();
+        for employee in &mut employees {
+            employee.onboarding_outstanding =
+                records.onboarding_outstanding(auth.tenant_id, &employee.employee_id.to_string());
+        }
+    }
+
+    match state.payroll_service.start_processing(id, employees, auth.user_id) {
+        Ok(job) => (StatusCode::ACCEPTED, Json(ApiResponse::success(job))).into_response(),
+        Err(PayrollError::NotFound(_)) => not_found(),
+        Err(e @ (PayrollError::StatusConflict { .. } | PayrollError::InvalidTransition { .. })) => {
+            (StatusCode::CONFLICT, Json(ApiResponse::<()>::error(e.to_string()))).into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(e.to_string()))).into_response(),
+    }
+}
+
+/// Progress of a payroll processing job
+///
+/// GET /api/v1/payroll/jobs/:job_id
+pub async fn get_payroll_job(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(job_id): Path<Uuid>,
+) -> Response {
+    if !auth.has_permission(Permission::PayrollView) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to view payroll"))).into_response();
+    }
+    match state.payroll_service.jobs().get(job_id).filter(|job| job.tenant_id == auth.tenant_id) {
+        Some(job) => Json(ApiResponse::success(job)).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error(format!("Payroll job {} not found", job_id))))
+            .into_response(),
+    }
 }
 
 /// Approve payroll handler
 /// 
 /// POST /api/v1/payroll/runs/:id/approve
 pub async fn approve_payroll_run(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
+    state.db.write().record("update_payroll_run_status");
     // In real implementation, get approver from auth context
     Json(ApiResponse::<PayrollRun>::error(format!("Approving payroll {} (stub)", id)))
 }
 
-/// Get payroll items (payslips) for a run
+/// Pay one employee's net pay to their mobile money wallet. Until the
+/// payment is confirmed a repeat returns the same request, with the same
+/// reference; afterwards it returns 200 with no request.
+///
+/// POST /api/v1/payroll/runs/:id/employees/:employee_id/mobile-money
+pub async fn disburse_mobile_money(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path((id, employee_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<MobileMoneyDisbursementRequest>,
+) -> Response {
+    if let Some(disabled) = state.features.disabled_response(auth.tenant_id, Feature::MobileMoney) {
+        return disabled;
+    }
+    if !auth.has_permission(Permission::PayrollApprove) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to disburse payroll"))).into_response();
+    }
+
+    match state.payroll_service.mobile_money_payment(auth.tenant_id, id, employee_id, &request) {
+        Ok(Some(payment)) => (StatusCode::CREATED, Json(ApiResponse::success(Some(payment)))).into_response(),
+        Ok(None) => Json(ApiResponse::<Option<PaymentRequest>>::success(None)).into_response(),
+        Err(PayrollError::NotFound(_)) => {
+            (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error(format!("Payroll run {} not found", id)))).into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(e.to_string()))).into_response(),
+    }
+}
+
+/// Mark payments from a run settled, once the bank statement or provider
+/// callback shows them. Confirmed payments are left out of regenerated
+/// payment files and never requested again.
+///
+/// POST /api/v1/payroll/runs/:id/disbursements/confirm
+pub async fn confirm_disbursements(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<ConfirmDisbursementsRequest>,
+) -> Response {
+    if !auth.has_permission(Permission::PayrollApprove) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::<()>::error("Not allowed to disburse payroll"))).into_response();
+    }
+
+    match state.payroll_service.confirm_disbursements(auth.tenant_id, id, &request.employee_ids) {
+        Ok(confirmed) => Json(ApiResponse::<Vec<Disbursement>>::success(confirmed)).into_response(),
+        Err(PayrollError::NotFound(_)) => {
+            (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error(format!("Payroll run {} not found", id)))).into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(e.to_string()))).into_response(),
+    }
+}
+
+/// Get payroll items (payslips) for a run. Reads the replica; payslips
+/// only change while a run is processed, which clients follow through the
+/// job rather than the items.
 /// 
 /// GET /api/v1/payroll/runs/:id/items
 pub async fn get_payroll_items(
-    State(_state): State<AppState>,
-    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    Path(_id): Path<Uuid>,
 ) -> impl IntoResponse {
+    state.db.read(StaleReads::Tolerated).record("list_payroll_items");
     let items: Vec<PayrollItem> = vec![];
     Json(ApiResponse::success(items))
 }
 
-/// Get employee payroll history
+/// Get employee payroll history. Reads the replica; history is of paid
+/// runs, which no longer change.
 /// 
 /// GET /api/v1/payroll/employees/:employee_id/history
 pub async fn get_employee_payroll_history(
-    State(_state): State<AppState>,
-    Path(employee_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Path(_employee_id): Path<Uuid>,
 ) -> impl IntoResponse {
+    state.db.read(StaleReads::Tolerated).record("list_employee_payroll_items");
     let items: Vec<PayrollItem> = vec![];
     Json(ApiResponse::success(items))
 }
 
+/// YTD query parameters
+#[derive(Debug, Deserialize)]
+pub struct YtdQuery {
+    pub year: Option<i32>,
+}
+
+/// Get employee year-to-date totals
+/// 
+/// GET /api/v1/employees/:employee_id/ytd?year=YYYY
+pub async fn get_employee_ytd(
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Query(query): Query<YtdQuery>,
+) -> impl IntoResponse {
+    let year = query.year.unwrap_or_else(|| chrono::Utc::now().year());
+    Json(ApiResponse::success(state.payroll_service.ytd_summary(employee_id, year)))
+}
+
 /// Tax calculation preview request
 #[derive(Debug, Deserialize)]
 pub struct TaxCalculateRequest {
@@ -174,13 +317,15 @@ pub async fn calculate_tax_preview(
     Json(ApiResponse::success(preview))
 }
 
-/// Generate P9A tax return
+/// Generate P9A tax return. Reads the replica; the return covers a
+/// closed tax year.
 /// 
 /// GET /api/v1/payroll/reports/p9/:year/:employee_id
 pub async fn generate_p9a(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path((year, employee_id)): Path<(i32, Uuid)>,
 ) -> impl IntoResponse {
+    state.db.read(StaleReads::Tolerated).record("p9a_payroll_items");
     // In real implementation, aggregate all payroll items for the year
     let p9a = P9AReturn {
         year,
@@ -196,17 +341,71 @@ pub async fn generate_p9a(
     Json(ApiResponse::success(p9a))
 }
 
-/// Generate pension schedule
+/// Generate pension schedule. Reads the replica; schedules are filed for
+/// approved runs, whose items no longer change.
 /// 
 /// GET /api/v1/payroll/reports/pension/:payroll_run_id
 pub async fn generate_pension_schedule(
-    State(_state): State<AppState>,
-    Path(payroll_run_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Path(_payroll_run_id): Path<Uuid>,
 ) -> impl IntoResponse {
+    state.db.read(StaleReads::Tolerated).record("pension_schedule_items");
     let schedules: Vec<PensionSchedule> = vec![];
     Json(ApiResponse::success(schedules))
 }
 
+/// List supported payroll countries
+/// 
+/// GET /api/v1/payroll/countries
+pub async fn list_supported_countries() -> impl IntoResponse {
+    Json(ApiResponse::success(PayrollRegistry::all_countries()))
+}
+
+/// Pay calendar query parameters
+#[derive(Debug, Deserialize)]
+pub struct PayCalendarQuery {
+    /// weekly, biweekly, semimonthly, or monthly
+    pub frequency: String,
+    /// A known pay date on the cycle
+    pub anchor: chrono::NaiveDate,
+    pub year: i32,
+    /// Country whose weekends and holidays adjust check dates (default NG)
+    pub country: Option<String>,
+}
+
+/// Pay-period schedule for a year
+#[derive(Debug, Serialize)]
+pub struct PayCalendarResponse {
+    pub frequency: String,
+    pub year: i32,
+    pub country_code: String,
+    pub periods: Vec<PayPeriod>,
+}
+
+/// Generate a year's pay periods with business-day-adjusted check dates
+///
+/// GET /api/v1/payroll/calendar?frequency=biweekly&anchor=2024-01-05&year=2024
+pub async fn get_pay_calendar(
+    State(state): State<AppState>,
+    Query(query): Query<PayCalendarQuery>,
+) -> impl IntoResponse {
+    let frequency: PayFrequency = match query.frequency.parse() {
+        Ok(frequency) => frequency,
+        Err(e) => {
+            return (StatusCode::BAD_REQUEST, Json(ApiResponse::<PayCalendarResponse>::error(format!("{}", e))));
+        }
+    };
+    let country_code = query.country.as_deref().unwrap_or("NG").to_ascii_uppercase();
+
+    match state.payroll_service.calendar(&country_code).pay_periods(&frequency, query.anchor, query.year) {
+        Ok(periods) => {
+            let response = PayCalendarResponse { frequency: query.frequency, year: query.year, country_code, periods };
+            (StatusCode::OK, Json(ApiResponse::success(response)))
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
 /// Create payroll routes
 pub fn payroll_routes() -> axum::Router<AppState> {
     use axum::routing::{get, post};
@@ -219,6 +418,9 @@ pub fn payroll_routes() -> axum::Router<AppState> {
         .route("/runs/:id/process", post(process_payroll_run))
         .route("/runs/:id/approve", post(approve_payroll_run))
         .route("/runs/:id/items", get(get_payroll_items))
+        .route("/runs/:id/employees/:employee_id/mobile-money", post(disburse_mobile_money))
+        .route("/runs/:id/disbursements/confirm", post(confirm_disbursements))
+        .route("/jobs/:job_id", get(get_payroll_job))
         
         // Employee History
         .route("/employees/:employee_id/history", get(get_employee_payroll_history))
@@ -226,7 +428,373 @@ pub fn payroll_routes() -> axum::Router<AppState> {
         // Tax Preview
         .route("/tax/calculate", post(calculate_tax_preview))
         
+        // Coverage
+        .route("/countries", get(list_supported_countries))
+        
+        // Pay calendar
+        .route("/calendar", get(get_pay_calendar))
+        
         // Reports
         .route("/reports/p9/:year/:employee_id", get(generate_p9a))
         .route("/reports/pension/:payroll_run_id", get(generate_pension_schedule))
 }
+
+/// Employee-scoped payroll routes (mounted under /api/v1)
+pub fn employee_payroll_routes() -> axum::Router<AppState> {
+    use axum::routing::get;
+    
+    axum::Router::new()
+        .route("/employees/:employee_id/ytd", get(get_employee_ytd))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use chrono::NaiveDate;
+    use tower::ServiceExt;
+
+    fn salary(employee_id: Uuid) -> EmployeeSalary {
+        EmployeeSalary {
+            employee_id,
+            employee_name: "Ama Mensah".to_string(),
+            employee_code: "EMP001".to_string(),
+            department_id: None,
+            legal_entity_id: None,
+            country_code: "NG".to_string(),
+            basic_salary: rust_decimal_macros::dec!(300_000),
+            housing_allowance: Decimal::ZERO,
+            transport_allowance: Decimal::ZERO,
+            meal_allowance: Decimal::ZERO,
+            utility_allowance: Decimal::ZERO,
+            other_allowances: serde_json::json!({}),
+            bank_name: None,
+            account_number: None,
+            account_name: None,
+            tin: None,
+            pension_pin: None,
+            nhf_number: None,
+            loan_balance: Decimal::ZERO,
+            loan_monthly_repayment: Decimal::ZERO,
+            benefit_deductions: vec![],
+            start_date: None,
+            pay_frequency: PayFrequency::Monthly,
+            onboarding_outstanding: Some(vec![]),
+        }
+    }
+
+    /// An employee of the tenant with onboarding done, by id
+    fn hired(state: &AppState, tenant_id: Uuid) -> Uuid {
+        let mut employees = state.employees.write().unwrap();
+        let employee_id = employees.next_employee_id(tenant_id, 2024);
+        let request = crate::domain::services::CreateEmployeeRequest {
+            first_name: "Ama".to_string(),
+            last_name: "Mensah".to_string(),
+            work_email: format!("{}@company.com", employee_id),
+            job_title: "Analyst".to_string(),
+            hire_date: NaiveDate::from_ymd_opt(2024, 1, 8).unwrap(),
+            tax_id: None,
+        };
+        employees.create_employee(tenant_id, employee_id, request, false).unwrap().id().parse().unwrap()
+    }
+
+    fn state_without_onboarding() -> AppState {
+        let employees = EmployeeService::new().with_onboarding_template(crate::domain::aggregates::OnboardingTemplate::empty());
+        AppState { employees: Arc::new(RwLock::new(employees)), ..AppState::default() }
+    }
+
+    fn hr_manager(tenant_id: Uuid) -> AuthContext {
+        AuthContext {
+            user_id: Uuid::new_v4(),
+            tenant_id,
+            employee_id: None,
+            role: crate::auth::Role::HrManager,
+            permissions: crate::auth::Role::HrManager.permissions(),
+            department_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_biweekly_calendar_with_holidays() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2021, m, d).unwrap();
+        let state = AppState::default();
+        // New Year's Day, Juneteenth (observed), New Year's Day 2022 (observed)
+        state.payroll_service.add_holidays("US", [date(1, 1), date(6, 18), date(12, 31)]);
+        let app = payroll_routes().with_state(state);
+
+        let request = Request::builder()
+            .uri("/calendar?frequency=biweekly&anchor=2021-01-01&year=2021&country=us")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let calendar: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let periods = calendar["data"]["periods"].as_array().unwrap();
+
+        assert_eq!(periods.len(), 27);
+        assert_eq!(periods[0]["pay_date"], "2021-01-01");
+        assert_eq!(periods[0]["check_date"], "2020-12-31");
+        assert_eq!(periods[12]["pay_date"], "2021-06-18");
+        assert_eq!(periods[12]["check_date"], "2021-06-17");
+        assert_eq!(periods[1]["check_date"], "2021-01-15");
+        assert_eq!(periods[26]["period_start"], "2021-12-18");
+        assert_eq!(periods[26]["check_date"], "2021-12-30");
+
+        let request = Request::builder()
+            .uri("/calendar?frequency=fortnightly&anchor=2021-01-01&year=2021")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(app.oneshot(request).await.unwrap().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_list_reads_replica_and_create_writes_primary() {
+        use crate::db::DbPool;
+
+        let (primary, replica) = (DbPool::new("primary"), DbPool::new("read_replica"));
+        let state = AppState { db: DbPools::new(primary.clone()).with_read_replica(replica.clone()), ..AppState::default() };
+        let app = payroll_routes().with_state(state);
+
+        let list = Request::builder().uri("/runs").body(Body::empty()).unwrap();
+        assert_eq!(app.clone().oneshot(list).await.unwrap().status(), StatusCode::OK);
+        assert_eq!((primary.queries(), replica.queries()), (0, 1));
+
+        let create = Request::builder()
+            .method("POST")
+            .uri("/runs")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"name":"June 2024","period_start":"2024-06-01","period_end":"2024-06-30","notes":null}"#))
+            .unwrap();
+        assert_eq!(app.clone().oneshot(create).await.unwrap().status(), StatusCode::CREATED);
+        assert_eq!((primary.queries(), replica.queries()), (1, 1));
+
+        // Fetching one run must see the write just made
+        let get = Request::builder().uri(format!("/runs/{}", Uuid::new_v4())).body(Body::empty()).unwrap();
+        app.oneshot(get).await.unwrap();
+        assert_eq!((primary.queries(), replica.queries()), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_mobile_money_disbursement_feature_flag() {
+        let state = AppState::default();
+        let service = &state.payroll_service;
+        let tenant_id = Uuid::new_v4();
+        let request = CreatePayrollRunRequest {
+            name: "June 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut run = service.create_payroll_run(tenant_id, request).unwrap();
+        let employee_id = Uuid::new_v4();
+        let salary = salary(employee_id);
+        service.process_payroll(&mut run, vec![salary], Uuid::new_v4()).unwrap();
+        service.approve_payroll(&mut run, Uuid::new_v4()).unwrap();
+
+        let app = payroll_routes().layer(Extension(hr_manager(tenant_id))).with_state(state.clone());
+        let disburse = || {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/runs/{}/employees/{}/mobile-money", run.id, employee_id))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"phone_number": "+2348031234567", "recipient_name": "Ama Mensah", "country": "NG"}"#))
+                .unwrap()
+        };
+
+        state.features.set(tenant_id, Feature::MobileMoney, false);
+        assert_eq!(app.clone().oneshot(disburse()).await.unwrap().status(), StatusCode::FORBIDDEN);
+        assert!(state.payroll_service.disbursements().is_empty());
+
+        state.features.set(tenant_id, Feature::MobileMoney, true);
+        let response = app.clone().oneshot(disburse()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(created["data"]["phone_number"], "+2348031234567");
+
+        // A retry before the provider confirms repeats the same payment
+        let response = app.clone().oneshot(disburse()).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let retried: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(retried["data"]["reference"], created["data"]["reference"]);
+        assert_eq!(state.payroll_service.disbursements().len(), 1);
+
+        let confirm = Request::builder()
+            .method("POST")
+            .uri(format!("/runs/{}/disbursements/confirm", run.id))
+            .header("content-type", "application/json")
+            .body(Body::from(format!(r#"{{"employee_ids": ["{}"]}}"#, employee_id)))
+            .unwrap();
+        let response = app.clone().oneshot(confirm).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"][0]["status"], "confirmed");
+
+        // Once confirmed, a retry is acknowledged without a second payment
+        let response = app.oneshot(disburse()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"].is_null());
+        assert_eq!(state.payroll_service.disbursements().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mobile_money_disbursement_rejects_other_tenant_run() {
+        let state = AppState::default();
+        let service = &state.payroll_service;
+        let tenant_id = Uuid::new_v4();
+        let request = CreatePayrollRunRequest {
+            name: "June 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut run = service.create_payroll_run(tenant_id, request).unwrap();
+        let employee_id = Uuid::new_v4();
+        service.process_payroll(&mut run, vec![salary(employee_id)], Uuid::new_v4()).unwrap();
+        service.approve_payroll(&mut run, Uuid::new_v4()).unwrap();
+
+        let other_tenant = Uuid::new_v4();
+        state.features.set(other_tenant, Feature::MobileMoney, true);
+        let app = payroll_routes().layer(Extension(hr_manager(other_tenant))).with_state(state.clone());
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/runs/{}/employees/{}/mobile-money", run.id, employee_id))
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"phone_number": "+2348031234567", "recipient_name": "Ama Mensah", "country": "NG"}"#))
+            .unwrap();
+
+        assert_eq!(app.oneshot(request).await.unwrap().status(), StatusCode::NOT_FOUND);
+        assert!(state.payroll_service.disbursements().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_async_processing_and_job_polling() {
+        let state = state_without_onboarding();
+        let tenant_id = Uuid::new_v4();
+        let request = CreatePayrollRunRequest {
+            name: "June 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let run = state.payroll_service.create_payroll_run(tenant_id, request).unwrap();
+        for _ in 0..120 {
+            state.payroll_service.salaries().upsert(tenant_id, salary(hired(&state, tenant_id)));
+        }
+        let body = serde_json::json!({}).to_string();
+        let app = payroll_routes().layer(Extension(hr_manager(tenant_id))).with_state(state.clone());
+        let process = || {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/runs/{}/process", run.id))
+                .header("content-type", "application/json")
+                .body(Body::from(body.clone()))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(process()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let job: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(job["data"]["status"], "processing");
+        assert_eq!(job["data"]["total"], 120);
+        let job_id = job["data"]["id"].as_str().unwrap().to_string();
+
+        // The run is claimed, so processing it again conflicts
+        assert_eq!(app.clone().oneshot(process()).await.unwrap().status(), StatusCode::CONFLICT);
+
+        let mut processed = 0;
+        let completed = loop {
+            let request = Request::builder().uri(format!("/jobs/{}", job_id)).body(Body::empty()).unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let job: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            let progress = job["data"]["processed"].as_u64().unwrap();
+            assert!(progress >= processed);
+            processed = progress;
+            match job["data"]["status"].as_str().unwrap() {
+                "processing" => {
+                    // The worker moves the run on just before completing the job
+                    let status = state.payroll_service.payroll_run(run.id).unwrap().status;
+                    assert!(matches!(status, PayrollRunStatus::Processing | PayrollRunStatus::PendingApproval));
+                    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                }
+                _ => break job,
+            }
+        };
+        assert_eq!(completed["data"]["status"], "completed");
+        assert_eq!(completed["data"]["processed"], 120);
+        let run = state.payroll_service.payroll_run(run.id).unwrap();
+        assert_eq!(run.status, PayrollRunStatus::PendingApproval);
+        assert_eq!(run.total_employees, 120);
+
+        let unknown = Request::builder().uri(format!("/jobs/{}", Uuid::new_v4())).body(Body::empty()).unwrap();
+        assert_eq!(app.oneshot(unknown).await.unwrap().status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_processing_pays_from_stored_salary_records() {
+        let state = state_without_onboarding();
+        let tenant_id = Uuid::new_v4();
+        let request = CreatePayrollRunRequest {
+            name: "June 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let run = state.payroll_service.create_payroll_run(tenant_id, request).unwrap();
+        let employee_id = hired(&state, tenant_id);
+        let stored = EmployeeSalary { account_number: Some("0123456789".to_string()), ..salary(employee_id) };
+        state.payroll_service.salaries().upsert(tenant_id, stored);
+        // A salary record with no employee record has no known onboarding status
+        let unknown = Uuid::new_v4();
+        state.payroll_service.salaries().upsert(tenant_id, salary(unknown));
+        // Another tenant's record can't be paid through this tenant's run
+        let outsider = Uuid::new_v4();
+        state.payroll_service.salaries().upsert(Uuid::new_v4(), salary(outsider));
+
+        let app = payroll_routes().layer(Extension(hr_manager(tenant_id))).with_state(state.clone());
+        let process = |body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/runs/{}/process", run.id))
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(process(serde_json::json!({ "employee_ids": [employee_id, outsider] }))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains(&outsider.to_string()));
+
+        // Salary and bank details in the body are not trusted
+        let tampered = EmployeeSalary {
+            basic_salary: rust_decimal_macros::dec!(9_000_000),
+            account_number: Some("9999999999".to_string()),
+            ..salary(employee_id)
+        };
+        let body = serde_json::json!({ "employee_ids": [employee_id, unknown], "employees": [tampered] });
+        assert_eq!(app.oneshot(process(body)).await.unwrap().status(), StatusCode::ACCEPTED);
+
+        while state.payroll_service.payroll_run(run.id).unwrap().status == PayrollRunStatus::Processing {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        let run = state.payroll_service.payroll_run(run.id).unwrap();
+        assert_eq!(run.total_employees, 1);
+        assert_eq!(run.total_gross, rust_decimal_macros::dec!(300_000));
+        let file = {
+            let mut run = run.clone();
+            state.payroll_service.approve_payroll(&mut run, Uuid::new_v4()).unwrap();
+            state.payroll_service.payment_file(tenant_id, run.id).unwrap()
+        };
+        assert!(file.contains("0123456789") && !file.contains("9999999999"));
+    }
+}
@@ -0,0 +1,230 @@
+//! Hourly Pay
+//!
+//! Turns finalized time entries into earning lines for hourly workers:
+//! regular hours, weekly overtime beyond the standard week, and hours
+//! worked on a public holiday from the payroll calendar, which pay the
+//! country's holiday premium.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, IsoWeek, NaiveDate};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use super::calendar::PayrollCalendar;
+use super::rounding::MoneyRounding;
+use crate::domain::aggregates::{EarningLine, EarningType};
+use crate::domain::value_objects::WorkingTime;
+use crate::time::TimeEntry;
+
+/// How the holiday premium combines with overtime for the same hour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PremiumOverlap {
+    /// Multiply: 2× holiday on a 1.5× overtime hour pays 3×
+    Stack,
+    /// Pay whichever multiplier is higher
+    TakeHigher,
+}
+
+/// Holiday pay premium for a country
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HolidayPremiumRule {
+    /// Multiple of the base rate for hours worked on a public holiday
+    pub multiplier: Decimal,
+    pub overlap: PremiumOverlap,
+}
+
+impl HolidayPremiumRule {
+    /// No premium: holiday hours pay the normal rate
+    pub const NONE: Self = Self { multiplier: Decimal::ONE, overlap: PremiumOverlap::TakeHigher };
+
+    pub fn for_country(country_code: &str) -> Self {
+        match country_code.to_ascii_uppercase().as_str() {
+            // Regular holiday: 200%, with the overtime premium applied on top
+            "PH" => Self { multiplier: dec!(2), overlap: PremiumOverlap::Stack },
+            // LFT art. 75: double pay on top of the day's wage
+            "MX" => Self { multiplier: dec!(3), overlap: PremiumOverlap::TakeHigher },
+            // Modern award public holiday penalty rate
+            "AU" => Self { multiplier: dec!(2.5), overlap: PremiumOverlap::TakeHigher },
+            "NG" | "GH" | "KE" | "ZA" => Self { multiplier: dec!(2), overlap: PremiumOverlap::TakeHigher },
+            _ => Self::NONE,
+        }
+    }
+
+    /// Multiplier for an hour, given whether it's a holiday and/or overtime
+    pub fn resolve(&self, holiday: bool, overtime_multiplier: Option<Decimal>) -> Decimal {
+        match (holiday, overtime_multiplier) {
+            (false, None) => Decimal::ONE,
+            (false, Some(ot)) => ot,
+            (true, None) => self.multiplier,
+            (true, Some(ot)) => match self.overlap {
+                PremiumOverlap::Stack => self.multiplier * ot,
+                PremiumOverlap::TakeHigher => self.multiplier.max(ot),
+            },
+        }
+    }
+}
+
+/// Earning lines for an hourly worker's time entries in one pay period
+#[derive(Debug, Clone)]
+pub struct HourlyPayCalculator {
+    pub hourly_rate: Decimal,
+    pub working_time: WorkingTime,
+    pub overtime_multiplier: Decimal,
+    pub holiday_rule: HolidayPremiumRule,
+    /// Exempt workers log every hour as regular
+    pub overtime_eligible: bool,
+    /// Applied to each line's amount
+    pub rounding: MoneyRounding,
+}
+
+impl HourlyPayCalculator {
+    pub fn new(hourly_rate: Decimal, country_code: &str) -> Self {
+        Self {
+            hourly_rate,
+            working_time: WorkingTime::for_country(country_code),
+            overtime_multiplier: dec!(1.5),
+            holiday_rule: HolidayPremiumRule::for_country(country_code),
+            overtime_eligible: true,
+            rounding: MoneyRounding::default().in_country(country_code),
+        }
+    }
+
+    /// Round lines the way the tenant's payroll does, e.g. with
+    /// `PayrollService::rounding_for`
+    pub fn with_rounding(mut self, rounding: MoneyRounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn with_overtime_eligible(mut self, eligible: bool) -> Self {
+        self.overtime_eligible = eligible;
+        self
+    }
+
+    pub fn with_holiday_rule(mut self, rule: HolidayPremiumRule) -> Self {
+        self.holiday_rule = rule;
+        self
+    }
+
+    /// Split entries into regular/overtime hours per ISO week (in clock-in
+    /// order), flag holiday hours from the calendar, and price each bucket.
+    /// Lines come out as regular, overtime, then holiday.
+    pub fn earnings(&self, entries: &[TimeEntry], calendar: &PayrollCalendar) -> Vec<EarningLine> {
+        let mut ordered: Vec<&TimeEntry> = entries.iter().collect();
+        ordered.sort_by_key(|e| e.clock_in);
+
+        let standard = self.working_time.standard_hours_per_week();
+        let mut week_hours: BTreeMap<IsoWeek, Decimal> = BTreeMap::new();
+        // (holiday, overtime) -> hours
+        let mut buckets: BTreeMap<(bool, bool), Decimal> = BTreeMap::new();
+
+        for entry in ordered {
+            let date: NaiveDate = entry.clock_in.date();
+            let worked = week_hours.entry(date.iso_week()).or_default();
+            let regular = if self.overtime_eligible {
+                entry.hours.min((standard - *worked).max(Decimal::ZERO))
+            } else {
+                entry.hours
+            };
+            let overtime = entry.hours - regular;
+            *worked += entry.hours;
+
+            let holiday = calendar.is_holiday(date);
+            *buckets.entry((holiday, false)).or_default() += regular;
+            *buckets.entry((holiday, true)).or_default() += overtime;
+        }
+
+        let mut lines = Vec::new();
+        for (holiday, overtime) in [(false, false), (false, true), (true, false), (true, true)] {
+            let hours = buckets.get(&(holiday, overtime)).copied().unwrap_or_default();
+            if hours.is_zero() {
+                continue;
+            }
+            let multiplier = self.holiday_rule.resolve(holiday, overtime.then_some(self.overtime_multiplier));
+            let earning_type = match (holiday, overtime) {
+                (false, false) => EarningType::Regular,
+                (false, true) => EarningType::Overtime,
+                // Holiday overtime paid at the plain overtime rate is just overtime
+                (true, true) if multiplier == self.overtime_multiplier => EarningType::Overtime,
+                (true, _) => EarningType::Holiday,
+            };
+            let rate = self.hourly_rate * multiplier;
+            lines.push(EarningLine { earning_type, hours: Some(hours), rate: Some(rate), amount: self.rounding.round(rate * hours) });
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shift(day: u32, start: u32, hours: u32) -> TimeEntry {
+        let clock_in = NaiveDate::from_ymd_opt(2024, 12, day).unwrap().and_hms_opt(start, 0, 0).unwrap();
+        TimeEntry {
+            employee_id: "emp-1".to_string(),
+            employee_number: "EMP-2024-00001".to_string(),
+            clock_in,
+            clock_out: clock_in + chrono::Duration::hours(hours.into()),
+            hours: Decimal::from(hours),
+        }
+    }
+
+    fn christmas_calendar(country: &str) -> PayrollCalendar {
+        PayrollCalendar::for_country(country).with_holidays([NaiveDate::from_ymd_opt(2024, 12, 25).unwrap()])
+    }
+
+    fn line(lines: &[EarningLine], wanted: fn(&EarningType) -> bool) -> &EarningLine {
+        lines.iter().find(|l| wanted(&l.earning_type)).expect("earning line")
+    }
+
+    #[test]
+    fn test_holiday_shift_pays_double() {
+        let calculator = HourlyPayCalculator::new(dec!(2000), "NG");
+        // Mon 23rd and Wed 25th (Christmas)
+        let lines = calculator.earnings(&[shift(23, 9, 8), shift(25, 9, 8)], &christmas_calendar("NG"));
+
+        assert_eq!(lines.len(), 2);
+        let regular = line(&lines, |t| matches!(t, EarningType::Regular));
+        assert_eq!(regular.amount, dec!(16000));
+        let holiday = line(&lines, |t| matches!(t, EarningType::Holiday));
+        assert_eq!(holiday.hours, Some(dec!(8)));
+        assert_eq!(holiday.rate, Some(dec!(4000)));
+        assert_eq!(holiday.amount, dec!(32000));
+    }
+
+    #[test]
+    fn test_holiday_overtime_stack_vs_take_higher() {
+        // 40 hours on Mon 23rd–Tue 24th, so the whole Christmas shift is weekly overtime
+        let entries = vec![shift(23, 6, 10), shift(23, 16, 10), shift(24, 6, 10), shift(24, 16, 10), shift(25, 8, 10)];
+
+        let stack = HourlyPayCalculator::new(dec!(100), "US")
+            .with_holiday_rule(HolidayPremiumRule { multiplier: dec!(2), overlap: PremiumOverlap::Stack });
+        let lines = stack.earnings(&entries, &christmas_calendar("US"));
+        let holiday_ot = line(&lines, |t| matches!(t, EarningType::Holiday));
+        assert_eq!(holiday_ot.hours, Some(dec!(10)));
+        assert_eq!(holiday_ot.rate, Some(dec!(300)));
+
+        let higher = stack.clone()
+            .with_holiday_rule(HolidayPremiumRule { multiplier: dec!(2), overlap: PremiumOverlap::TakeHigher });
+        let lines = higher.earnings(&entries, &christmas_calendar("US"));
+        let holiday_ot = line(&lines, |t| matches!(t, EarningType::Holiday));
+        assert_eq!(holiday_ot.rate, Some(dec!(200)));
+        assert_eq!(holiday_ot.amount, dec!(2000));
+    }
+
+    #[test]
+    fn test_lines_rounded_in_the_country_currency() {
+        use crate::payroll::rounding::RoundingMode;
+
+        let calendar = PayrollCalendar::for_country("JP");
+        let lines = HourlyPayCalculator::new(dec!(1234.5), "JP").earnings(&[shift(23, 9, 1)], &calendar);
+        assert_eq!(lines[0].amount, dec!(1235));
+
+        let banker = HourlyPayCalculator::new(dec!(1234.5), "JP").with_rounding(MoneyRounding::new(RoundingMode::HalfEven, 2).in_country("JP"));
+        assert_eq!(banker.earnings(&[shift(23, 9, 1)], &calendar)[0].amount, dec!(1234));
+    }
+}
@@ -0,0 +1,105 @@
+//! Payroll Processing Jobs
+//!
+//! Processing a large run takes longer than an HTTP request should, so the
+//! API queues it and answers straight away with a job to poll. The run sits
+//! in `Processing` while a worker calculates payslips in batches, recording
+//! progress on the job after each batch, and moves on to `PendingApproval`
+//! or `Failed` when done.
+
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Employees calculated between progress updates
+pub const PROCESSING_BATCH_SIZE: usize = 50;
+
+/// Where a processing job is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayrollJobStatus {
+    Processing,
+    Completed,
+    Failed,
+}
+
+/// Background processing of one payroll run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayrollJob {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub payroll_run_id: Uuid,
+    pub status: PayrollJobStatus,
+    /// Employees calculated so far, including any skipped
+    pub processed: usize,
+    pub total: usize,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Processing jobs by id
+#[derive(Debug, Clone, Default)]
+pub struct PayrollJobs {
+    // In real implementation, backed by the payroll_jobs table
+    jobs: Arc<DashMap<Uuid, PayrollJob>>,
+}
+
+impl PayrollJobs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a job for `total` employees
+    pub fn start(&self, tenant_id: Uuid, payroll_run_id: Uuid, total: usize) -> PayrollJob {
+        let job = PayrollJob {
+            id: Uuid::new_v4(),
+            tenant_id,
+            payroll_run_id,
+            status: PayrollJobStatus::Processing,
+            processed: 0,
+            total,
+            error: None,
+            created_at: Utc::now(),
+            completed_at: None,
+        };
+        self.jobs.insert(job.id, job.clone());
+        job
+    }
+
+    pub fn get(&self, job_id: Uuid) -> Option<PayrollJob> {
+        self.jobs.get(&job_id).map(|j| j.clone())
+    }
+
+    /// The latest job started for a run
+    pub fn for_run(&self, payroll_run_id: Uuid) -> Option<PayrollJob> {
+        self.jobs
+            .iter()
+            .filter(|j| j.payroll_run_id == payroll_run_id)
+            .max_by_key(|j| j.created_at)
+            .map(|j| j.clone())
+    }
+
+    pub fn advance(&self, job_id: Uuid, processed: usize) {
+        if let Some(mut job) = self.jobs.get_mut(&job_id) {
+            job.processed = (job.processed + processed).min(job.total);
+        }
+    }
+
+    pub fn complete(&self, job_id: Uuid) {
+        if let Some(mut job) = self.jobs.get_mut(&job_id) {
+            job.status = PayrollJobStatus::Completed;
+            job.processed = job.total;
+            job.completed_at = Some(Utc::now());
+        }
+    }
+
+    pub fn fail(&self, job_id: Uuid, error: String) {
+        if let Some(mut job) = self.jobs.get_mut(&job_id) {
+            job.status = PayrollJobStatus::Failed;
+            job.error = Some(error);
+            job.completed_at = Some(Utc::now());
+        }
+    }
+}
@@ -4,11 +4,16 @@
 //! - GCC: Zero income tax, GPSSA/GOSI (nationals), End of Service Benefits
 //! - Levant: Israel (complex Bituach Leumi), Jordan, Lebanon
 //! - WPS (Wage Protection System) compliance
+//!
+//! Results are rounded at the currency's minor unit: fils for dirhams and
+//! riyals, thousandths for the Kuwaiti and Bahraini dinar.
 
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use super::rounding::{MoneyRounding, RoundingMode};
+
 // ═══════════════════════════════════════════════════════════════════════════
 // UAE TAX CALCULATOR
 // ═══════════════════════════════════════════════════════════════════════════
@@ -92,6 +97,7 @@ impl UAETaxCalculator {
                 "Federal Law No. 7 of 1999 (GPSSA)".to_string(),
             ],
         }
+        .rounded()
     }
     
     /// Calculate end of service gratuity for termination
@@ -110,7 +116,7 @@ impl UAETaxCalculator {
         
         // Resignation adjustment
         if is_resignation && years < dec!(5) {
-            total = total * dec!(0.5);
+            total *= dec!(0.5);
         }
         if is_resignation && years < dec!(1) {
             total = Decimal::ZERO;
@@ -210,6 +216,7 @@ impl SaudiTaxCalculator {
                 "GOSI Law (Royal Decree M/33)".to_string(),
             ],
         }
+        .rounded()
     }
 }
 
@@ -319,6 +326,7 @@ impl IsraelTaxCalculator {
                 "Mandatory Pension Law 2008".to_string(),
             ],
         }
+        .rounded()
     }
     
     fn calculate_brackets(&self, taxable: Decimal) -> Decimal {
@@ -352,6 +360,170 @@ impl Default for IsraelTaxCalculator {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+// KUWAIT TAX CALCULATOR
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Kuwait PIFSS config (nationals only)
+#[derive(Debug, Clone)]
+pub struct KuwaitConfig {
+    pub tax_year: i32,
+    pub pifss_employee_rate: Decimal,  // 10.5% basic + supplementary
+    pub pifss_employer_rate: Decimal,  // 11.5%
+    pub pifss_ceiling: Decimal,        // KWD 2,750
+}
+
+impl Default for KuwaitConfig {
+    fn default() -> Self {
+        Self {
+            tax_year: 2024,
+            pifss_employee_rate: dec!(0.105),
+            pifss_employer_rate: dec!(0.115),
+            pifss_ceiling: dec!(2_750),
+        }
+    }
+}
+
+/// Kuwait tax calculator
+pub struct KuwaitTaxCalculator {
+    config: KuwaitConfig,
+}
+
+impl KuwaitTaxCalculator {
+    pub fn new() -> Self {
+        Self { config: KuwaitConfig::default() }
+    }
+
+    pub fn calculate(&self, gross_monthly: Decimal, is_kuwaiti: bool) -> TaxResult {
+        let (pifss_employee, pifss_employer) = if is_kuwaiti {
+            let base = gross_monthly.min(self.config.pifss_ceiling);
+            (base * self.config.pifss_employee_rate, base * self.config.pifss_employer_rate)
+        } else {
+            (Decimal::ZERO, Decimal::ZERO)
+        };
+
+        TaxResult {
+            country_code: "KW".to_string(),
+            currency: "KWD".to_string(),
+            gross_monthly,
+            income_tax: Decimal::ZERO, // No income tax
+            social_security_employee: pifss_employee,
+            social_security_employer: pifss_employer,
+            pension_employee: Decimal::ZERO,
+            pension_employer: Decimal::ZERO,
+            other_employee: Decimal::ZERO,
+            other_employer: Decimal::ZERO,
+            total_employee_deductions: pifss_employee,
+            total_employer_contributions: pifss_employer,
+            net_monthly: gross_monthly - pifss_employee,
+            effective_rate: if gross_monthly > Decimal::ZERO {
+                pifss_employee / gross_monthly * dec!(100)
+            } else {
+                Decimal::ZERO
+            },
+            legal_references: vec![
+                "Social Security Law No. 61 of 1976 (PIFSS)".to_string(),
+            ],
+        }
+        .rounded()
+    }
+}
+
+impl Default for KuwaitTaxCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// BAHRAIN TAX CALCULATOR
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Bahrain SIO config
+#[derive(Debug, Clone)]
+pub struct BahrainConfig {
+    pub tax_year: i32,
+    pub pension_employee_rate: Decimal,       // 7% (nationals)
+    pub pension_employer_rate: Decimal,       // 12% (nationals)
+    pub unemployment_employee_rate: Decimal,  // 1% (all)
+    pub unemployment_employer_rate: Decimal,  // 1% (all)
+    pub work_injury_employer_rate: Decimal,   // 3% (expats)
+    pub sio_ceiling: Decimal,                 // BHD 4,000
+}
+
+impl Default for BahrainConfig {
+    fn default() -> Self {
+        Self {
+            tax_year: 2024,
+            pension_employee_rate: dec!(0.07),
+            pension_employer_rate: dec!(0.12),
+            unemployment_employee_rate: dec!(0.01),
+            unemployment_employer_rate: dec!(0.01),
+            work_injury_employer_rate: dec!(0.03),
+            sio_ceiling: dec!(4_000),
+        }
+    }
+}
+
+/// Bahrain tax calculator
+pub struct BahrainTaxCalculator {
+    config: BahrainConfig,
+}
+
+impl BahrainTaxCalculator {
+    pub fn new() -> Self {
+        Self { config: BahrainConfig::default() }
+    }
+
+    pub fn calculate(&self, gross_monthly: Decimal, is_bahraini: bool) -> TaxResult {
+        let base = gross_monthly.min(self.config.sio_ceiling);
+        let unemployment_employee = base * self.config.unemployment_employee_rate;
+        let unemployment_employer = base * self.config.unemployment_employer_rate;
+
+        // Nationals pay into the pension fund; expats are covered for work injury
+        let (pension_employee, pension_employer, work_injury) = if is_bahraini {
+            (base * self.config.pension_employee_rate, base * self.config.pension_employer_rate, Decimal::ZERO)
+        } else {
+            (Decimal::ZERO, Decimal::ZERO, base * self.config.work_injury_employer_rate)
+        };
+
+        let total_employee = pension_employee + unemployment_employee;
+        let total_employer = pension_employer + unemployment_employer + work_injury;
+
+        TaxResult {
+            country_code: "BH".to_string(),
+            currency: "BHD".to_string(),
+            gross_monthly,
+            income_tax: Decimal::ZERO, // No income tax
+            social_security_employee: unemployment_employee,
+            social_security_employer: unemployment_employer,
+            pension_employee,
+            pension_employer,
+            other_employee: Decimal::ZERO,
+            other_employer: work_injury,
+            total_employee_deductions: total_employee,
+            total_employer_contributions: total_employer,
+            net_monthly: gross_monthly - total_employee,
+            effective_rate: if gross_monthly > Decimal::ZERO {
+                total_employee / gross_monthly * dec!(100)
+            } else {
+                Decimal::ZERO
+            },
+            legal_references: vec![
+                "Social Insurance Law (Decree-Law No. 24 of 1976)".to_string(),
+                "Unemployment Insurance Law (Decree-Law No. 78 of 2006)".to_string(),
+            ],
+        }
+        .rounded()
+    }
+}
+
+impl Default for BahrainTaxCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // COMMON TYPES
 // ═══════════════════════════════════════════════════════════════════════════
@@ -376,6 +548,31 @@ pub struct TaxResult {
     pub legal_references: Vec<String>,
 }
 
+impl TaxResult {
+    /// Each amount rounded half-up at the currency's minor unit, with totals
+    /// and net pay summed from the rounded lines
+    fn rounded(mut self) -> Self {
+        let rounding = MoneyRounding::for_currency(RoundingMode::HalfUp, &self.currency);
+        for amount in [
+            &mut self.gross_monthly,
+            &mut self.income_tax,
+            &mut self.social_security_employee,
+            &mut self.social_security_employer,
+            &mut self.pension_employee,
+            &mut self.pension_employer,
+            &mut self.other_employee,
+            &mut self.other_employer,
+        ] {
+            *amount = rounding.round(*amount);
+        }
+        self.total_employee_deductions =
+            self.income_tax + self.social_security_employee + self.pension_employee + self.other_employee;
+        self.total_employer_contributions = self.social_security_employer + self.pension_employer + self.other_employer;
+        self.net_monthly = self.gross_monthly - self.total_employee_deductions;
+        self
+    }
+}
+
 /// Middle East country registry
 pub struct MiddleEastRegistry;
 
@@ -389,12 +586,11 @@ impl MiddleEastRegistry {
             ("KW", "Kuwait", "KWD"),
             ("BH", "Bahrain", "BHD"),
             ("OM", "Oman", "OMR"),
-            // Levant & Other
+            // Levant & Other (Turkey is served by the Eastern Europe engine)
             ("IL", "Israel", "ILS"),
             ("JO", "Jordan", "JOD"),
             ("LB", "Lebanon", "LBP"),
             ("IQ", "Iraq", "IQD"),
-            ("TR", "Turkey", "TRY"),
             ("IR", "Iran", "IRR"),
         ]
     }
@@ -480,10 +676,36 @@ mod tests {
         assert!(result.pension_employee > Decimal::ZERO);
     }
     
+    #[test]
+    fn test_gulf_dinars_round_to_fils() {
+        // 10.5% of KWD 1,234.5678 is 129.629619
+        let kuwaiti = KuwaitTaxCalculator::new().calculate(dec!(1234.5678), true);
+        assert_eq!(kuwaiti.gross_monthly, dec!(1234.568));
+        assert_eq!(kuwaiti.social_security_employee, dec!(129.630));
+        assert_eq!(kuwaiti.social_security_employer, dec!(141.975));
+        assert_eq!(kuwaiti.net_monthly, dec!(1104.938));
+        assert_eq!(KuwaitTaxCalculator::new().calculate(dec!(1234.5678), false).net_monthly, dec!(1234.568));
+        
+        // Expat in Bahrain: 1% unemployment insurance, employer 1% + 3% work injury
+        let expat = BahrainTaxCalculator::new().calculate(dec!(987.6545), false);
+        assert_eq!(expat.currency, "BHD");
+        assert_eq!(expat.gross_monthly, dec!(987.655));
+        assert_eq!(expat.total_employee_deductions, dec!(9.877));
+        assert_eq!(expat.total_employer_contributions, dec!(39.507));
+        assert_eq!(expat.net_monthly, dec!(977.778));
+        
+        let bahraini = BahrainTaxCalculator::new().calculate(dec!(1500), true);
+        assert_eq!(bahraini.total_employee_deductions, dec!(120.000));
+        assert_eq!(bahraini.net_monthly, dec!(1380.000));
+        
+        // Dirhams keep two places
+        assert_eq!(UAETaxCalculator::new().calculate(dec!(20_000), true, 3).social_security_employee, dec!(1000.00));
+    }
+    
     #[test]
     fn test_middle_east_registry() {
         let countries = MiddleEastRegistry::supported_countries();
-        assert!(countries.len() >= 12);
+        assert_eq!(countries.len(), 11);
         
         assert!(MiddleEastRegistry::is_gcc("AE"));
         assert!(MiddleEastRegistry::is_gcc("SA"));
@@ -21,11 +21,83 @@ pub mod central_eastern_europe;
 pub mod developed_asia;
 pub mod europe_east_noneu;
 pub mod asia_pacific;
+pub mod north_america;
+pub mod registry;
+pub mod ytd;
+pub mod trace;
+pub mod calendar;
+pub mod repository;
+pub mod tax_tables;
+pub mod tax_parameters;
+pub mod tax_config;
+pub mod rounding;
+pub mod proration;
+pub mod severance;
+pub mod notice;
+pub mod payslip;
+pub mod payslip_spec;
+pub mod hourly;
+pub mod gl;
+pub mod disbursement;
+pub mod repayment;
+pub mod work_location;
+pub mod budget;
+pub mod advance;
+pub mod social_security;
+pub mod residency;
+pub mod preflight;
+pub mod recurring;
+pub mod reconcile;
+pub mod jobs;
+pub mod net_pay_floor;
+pub mod statutory_report;
+pub mod fx;
+#[cfg(test)]
+mod golden;
+pub mod annualization;
+pub mod tax_override;
+pub mod clawback;
+pub mod salary_records;
 
 pub use models::*;
 pub use service::PayrollService;
-pub use tax_calculator::NigerianTaxCalculator;
+pub use tax_calculator::{CraBasis, NigerianTaxCalculator};
 pub use pension::PensionCalculator;
+pub use trace::{ensure_traced, CalcStep, CalcTrace, TraceError, TRACED_COUNTRIES};
+pub use ytd::{YtdLine, YtdStore, YtdSummary};
+pub use repository::PayrollRunRepository;
+pub use tax_tables::TaxTables;
+pub use tax_parameters::TaxParameters;
+pub use tax_config::{TaxConfigError, TaxConfigFile, TaxConfigLoader};
+pub use rounding::{MoneyRounding, RoundingMode, TaxRounding};
+pub use payslip::{render_localized_payslip_csv, render_payslip_csv, PayslipLabel, PayslipLabels};
+pub use payslip_spec::{PayslipSpec, PayslipSpecError, PayslipSpecs, StatutoryLine, StatutoryLines};
+pub use notice::{NoticeLength, NoticePeriod, NoticePeriods, NoticeTier, NoticeUnit};
+pub use severance::{SeveranceCalculator, SeveranceCalculators, SeveranceInput, SeveranceResult, TerminationType};
+pub use gl::{advance_journal, GlAccountMap, GlJournal, JournalLine};
+pub use work_location::{
+    AllocationBasis, JurisdictionWithholding, ReciprocityAgreements, WorkLocationAllocation, WorkLocationError,
+};
+pub use advance::{AdvanceTaxTreatment, SalaryAdvance, SalaryAdvances};
+pub use clawback::{Clawback, ClawbackPolicy, Clawbacks};
+pub use salary_records::SalaryRecords;
+pub use jobs::{PayrollJob, PayrollJobStatus, PayrollJobs};
+pub use reconcile::{ReconciliationDiscrepancy, ReconciliationReport};
+pub use recurring::{DeductionAmount, RecurringDeduction, RecurringDeductions};
+pub use net_pay_floor::{DeferredDeduction, NetPayFloorLedger, NetPayFloorOutcome, NetPayFloors};
+pub use tax_override::{TaxOverride, TaxOverrideKind, TaxOverrideStatus, TaxOverrides};
+pub use annualization::{Annualization, ExtraPeriodPolicy};
+pub use fx::{FxError, FxRate, FxRates};
+pub use statutory_report::{StatutoryReport, StatutoryReportFormat, StatutoryReportFormats, StatutoryReportLine, StatutoryScheduleRow};
+pub use preflight::{PreflightCheck, PreflightFinding, PreflightRules, PreflightSeverity};
+pub use residency::{Presence, ResidencyDetermination, ResidencyDeterminer, ResidencyRule, ResidencyStatus};
+pub use social_security::{SocialSecurityProration, SocialSecurityProrations};
+pub use budget::{BudgetVariance, Department, Departments};
+pub use repayment::{ProtectedEarnings, RepaymentDeduction, RepaymentKind, RepaymentSchedule, RepaymentSchedules};
+pub use disbursement::{Disbursed, Disbursement, DisbursementChannel, DisbursementLedger, DisbursementStatus};
+pub use hourly::{HolidayPremiumRule, HourlyPayCalculator, PremiumOverlap};
+pub use calendar::{BusinessDayPolicy, PayPeriod, PayrollCalendar};
+pub use registry::{CountryInfo, CountryCapabilities, TaxStructure, PayrollRegistry};
 pub use west_africa::{GhanaTaxCalculator, UemoaTaxCalculator, WestAfricaTaxRegistry};
 pub use west_africa_enhanced::{CFAZoneConfig, GhanaEnhancedConfig, LaborLawSummary};
 pub use mobile_money::WestAfricaMobileMoneyRegistry;
@@ -33,14 +105,14 @@ pub use south_africa::{
     SouthAfricaTaxCalculator, ZimbabweTaxCalculator, 
     ZambiaTaxCalculator, AngolaTaxCalculator, SouthernAfricaRegistry
 };
-pub use africa_mobile_gateway::{ProviderRouter, AfricaMobileMoneyRegistry};
+pub use africa_mobile_gateway::{ProviderRouter, AfricaMobileMoneyRegistry, GatewayError, MobileMoneyGateway};
 pub use south_america::{
     BrazilTaxCalculator, ArgentinaTaxCalculator,
     ColombiaTaxCalculator, PeruTaxCalculator, SouthAmericaRegistry
 };
 pub use middle_east::{
-    UAETaxCalculator, SaudiTaxCalculator, 
-    IsraelTaxCalculator, MiddleEastRegistry
+    UAETaxCalculator, SaudiTaxCalculator, KuwaitTaxCalculator,
+    BahrainTaxCalculator, IsraelTaxCalculator, MiddleEastRegistry
 };
 pub use western_europe::{
     SwissTaxCalculator, AustrianTaxCalculator,
@@ -64,8 +136,8 @@ pub use central_eastern_europe::{
 };
 pub use developed_asia::{
     JapanTaxCalculator, KoreanTaxCalculator,
-    TaiwanTaxCalculator, HongKongTaxCalculator,
-    SingaporeTaxCalculator, HkMaritalStatus,
+    TaiwanTaxCalculator, HongKongTaxCalculator, HkStandardRate,
+    SingaporeTaxCalculator, CpfWageCeilings, HkMaritalStatus,
     DevelopedAsiaRegistry,
 };
 pub use europe_east_noneu::{
@@ -83,3 +155,7 @@ pub use asia_pacific::{
     PakistanTaxCalculator, BangladeshTaxCalculator,
     IndonesiaMaritalStatus, AsiaPacificRegistry,
 };
+pub use north_america::{
+    UsTaxCalculator, CanadaTaxCalculator,
+    UsState, Province, NorthAmericaRegistry,
+};
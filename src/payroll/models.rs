@@ -7,21 +7,54 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::benefits::BenefitDeduction;
+use crate::domain::value_objects::PayFrequency;
+use crate::validation::{Validate, ValidationErrors, Validator};
+
 /// Payroll Run Status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum PayrollRunStatus {
+    #[default]
     Draft,
     Processing,
     PendingApproval,
     Approved,
     Paid,
+    Failed,
     Cancelled,
 }
 
-impl Default for PayrollRunStatus {
-    fn default() -> Self {
-        Self::Draft
+impl PayrollRunStatus {
+    /// Statuses reachable from this one. Every status change, in memory or
+    /// in the database, must be checked against this table.
+    pub fn allowed_transitions(self) -> &'static [PayrollRunStatus] {
+        use PayrollRunStatus::*;
+        match self {
+            // Draft -> PendingApproval is the synchronous in-process path
+            Draft => &[Processing, PendingApproval, Cancelled],
+            Processing => &[PendingApproval, Failed],
+            PendingApproval => &[Approved, Draft, Cancelled],
+            Approved => &[Paid],
+            Failed => &[Draft, Cancelled],
+            Paid | Cancelled => &[],
+        }
+    }
+
+    pub fn can_transition_to(self, next: PayrollRunStatus) -> bool {
+        self.allowed_transitions().contains(&next)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PayrollRunStatus::Draft => "draft",
+            PayrollRunStatus::Processing => "processing",
+            PayrollRunStatus::PendingApproval => "pending_approval",
+            PayrollRunStatus::Approved => "approved",
+            PayrollRunStatus::Paid => "paid",
+            PayrollRunStatus::Failed => "failed",
+            PayrollRunStatus::Cancelled => "cancelled",
+        }
     }
 }
 
@@ -35,6 +68,10 @@ pub struct PayrollRun {
     pub period_end: NaiveDate,
     pub run_date: Option<DateTime<Utc>>,
     pub status: PayrollRunStatus,
+    /// Company the run pays; filings from it are made under that company's
+    /// tax registration. None covers the whole tenant.
+    #[serde(default)]
+    pub legal_entity_id: Option<Uuid>,
     
     // Totals
     pub total_employees: i32,
@@ -65,6 +102,7 @@ impl PayrollRun {
             period_end,
             run_date: None,
             status: PayrollRunStatus::Draft,
+            legal_entity_id: None,
             total_employees: 0,
             total_gross: Decimal::ZERO,
             total_deductions: Decimal::ZERO,
@@ -128,10 +166,32 @@ pub struct PayrollItem {
     pub account_number: Option<String>,
     pub account_name: Option<String>,
     
+    /// Department at time of payroll, for cost-center reporting
+    #[serde(default)]
+    pub department_id: Option<Uuid>,
+    
+    /// Employer-side contributions by type, e.g. "pension", "uif", "sdl"
+    #[serde(default)]
+    pub employer_contributions: std::collections::BTreeMap<String, Decimal>,
+    
     pub created_at: DateTime<Utc>,
 }
 
 impl PayrollItem {
+    /// Gross pay plus employer-side contributions
+    pub fn employer_cost(&self) -> Decimal {
+        self.gross_pay + self.employer_contribution_total()
+    }
+
+    /// Sum of employer contributions; items stored before they were
+    /// itemized only carry the employer pension
+    pub fn employer_contribution_total(&self) -> Decimal {
+        if self.employer_contributions.is_empty() {
+            return self.pension_employer;
+        }
+        self.employer_contributions.values().sum()
+    }
+
     pub fn calculate_gross(&self) -> Decimal {
         self.basic_salary 
             + self.housing_allowance 
@@ -146,6 +206,39 @@ impl PayrollItem {
             + self.nhf_deduction 
             + self.loan_repayment
     }
+
+    /// Amounts in `other_deductions`: numeric entries by key, the employee
+    /// share of listed benefit contributions under `benefits`, and the sum
+    /// of named lines such as `recurring`
+    pub fn other_deduction_amounts(&self) -> Vec<(String, Decimal)> {
+        let Some(lines) = self.other_deductions.as_object() else {
+            return Vec::new();
+        };
+
+        lines
+            .iter()
+            .filter_map(|(key, value)| {
+                let amount = match value {
+                    serde_json::Value::Array(benefits) => benefits
+                        .iter()
+                        .filter_map(|b| serde_json::from_value::<Decimal>(b["employee_amount"].clone()).ok())
+                        .sum(),
+                    serde_json::Value::Object(named) => named
+                        .values()
+                        .filter_map(|amount| serde_json::from_value::<Decimal>(amount.clone()).ok())
+                        .sum(),
+                    value => serde_json::from_value::<Decimal>(value.clone()).ok()?,
+                };
+                Some((key.clone(), amount))
+            })
+            .collect()
+    }
+
+    /// Every deduction line on the payslip added up, independently of
+    /// `total_deductions`
+    pub fn itemised_deductions(&self) -> Decimal {
+        self.calculate_total_deductions() + self.other_deduction_amounts().iter().map(|(_, amount)| amount).sum::<Decimal>()
+    }
 }
 
 /// Employee salary details for payroll calculation
@@ -154,6 +247,14 @@ pub struct EmployeeSalary {
     pub employee_id: Uuid,
     pub employee_name: String,
     pub employee_code: String,
+    #[serde(default)]
+    pub department_id: Option<Uuid>,
+    /// Company that employs them
+    #[serde(default)]
+    pub legal_entity_id: Option<Uuid>,
+    /// ISO country of employment; selects the tax calculator
+    #[serde(default = "default_country_code")]
+    pub country_code: String,
     
     // Salary components
     pub basic_salary: Decimal,
@@ -176,6 +277,82 @@ pub struct EmployeeSalary {
     // Deductions
     pub loan_balance: Decimal,
     pub loan_monthly_repayment: Decimal,
+    /// Benefit contributions for the run's period, already prorated
+    #[serde(default)]
+    pub benefit_deductions: Vec<BenefitDeduction>,
+    /// First day of employment when it falls inside the run's period
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
+    /// How often the employee is paid; salary amounts are per period
+    #[serde(default = "default_pay_frequency")]
+    pub pay_frequency: PayFrequency,
+    /// Mandatory onboarding items not yet done; the employee is left out
+    /// of runs until there are none. `None` means the status is unknown,
+    /// which also leaves them out.
+    #[serde(default)]
+    pub onboarding_outstanding: Option<Vec<String>>,
+}
+
+impl EmployeeSalary {
+    /// Employee share of benefit contributions for the period
+    pub fn benefit_employee_total(&self) -> Decimal {
+        self.benefit_deductions.iter().map(|b| b.employee_amount).sum()
+    }
+
+    /// This salary with each pay component scaled by `fraction`, passed
+    /// through `round`
+    pub fn prorated(&self, fraction: Decimal, round: impl Fn(Decimal) -> Decimal) -> Self {
+        if fraction == Decimal::ONE {
+            return self.clone();
+        }
+        Self {
+            basic_salary: round(self.basic_salary * fraction),
+            housing_allowance: round(self.housing_allowance * fraction),
+            transport_allowance: round(self.transport_allowance * fraction),
+            meal_allowance: round(self.meal_allowance * fraction),
+            utility_allowance: round(self.utility_allowance * fraction),
+            ..self.clone()
+        }
+    }
+}
+
+fn default_country_code() -> String {
+    "NG".to_string()
+}
+
+fn default_pay_frequency() -> PayFrequency {
+    PayFrequency::Monthly
+}
+
+/// Employee left out of a payroll run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedEmployee {
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub country_code: String,
+    pub reason: String,
+}
+
+/// Outcome of processing a payroll run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayrollProcessingResult {
+    pub items: Vec<PayrollItem>,
+    pub skipped: Vec<SkippedEmployee>,
+}
+
+/// Mobile money disbursement request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MobileMoneyDisbursementRequest {
+    pub phone_number: String,
+    pub recipient_name: String,
+    pub country: String,
+    pub provider: Option<String>,
+}
+
+/// Payments the bank or provider reports settled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfirmDisbursementsRequest {
+    pub employee_ids: Vec<Uuid>,
 }
 
 /// Request to create a payroll run
@@ -185,14 +362,32 @@ pub struct CreatePayrollRunRequest {
     pub period_start: NaiveDate,
     pub period_end: NaiveDate,
     pub notes: Option<String>,
+    /// Pay only this company's employees
+    #[serde(default)]
+    pub legal_entity_id: Option<Uuid>,
+}
+
+impl Validate for CreatePayrollRunRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut v = Validator::new();
+        v.required("name", &self.name)
+            .max_length("name", &self.name, 200)
+            .date_order("period_end", self.period_start, self.period_end);
+        if let Some(notes) = &self.notes {
+            v.max_length("notes", notes, 2000);
+        }
+        v.finish()
+    }
 }
 
 /// Request to process payroll
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessPayrollRequest {
-    /// Optional list of employee IDs to include (if empty, all active employees)
+    /// Employees to pay, by id; everyone with a salary record when omitted
+    /// or empty. Salary and bank details come from their salary records.
     pub employee_ids: Option<Vec<Uuid>>,
     /// Whether to recalculate if already processed
+    #[serde(default)]
     pub force_recalculate: bool,
 }
 
@@ -222,6 +417,43 @@ pub struct DepartmentPayrollSummary {
     pub total_net: Decimal,
 }
 
+/// Employer cost per department for one run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepartmentCostReport {
+    pub payroll_run_id: Uuid,
+    pub currency: String,
+    /// Report-currency units per unit of payroll currency
+    pub exchange_rate: Decimal,
+    /// Department id (or "unassigned") to total employer cost
+    pub by_department: std::collections::BTreeMap<String, Decimal>,
+    pub total: Decimal,
+}
+
+impl DepartmentCostReport {
+    pub const UNASSIGNED: &'static str = "unassigned";
+}
+
+/// One employee's employer-side contributions in a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmployerContributionLine {
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub country_code: String,
+    /// Every contribution type in the run, zero where this country has none
+    pub contributions: std::collections::BTreeMap<String, Decimal>,
+    pub total: Decimal,
+}
+
+/// Employer contributions per employee and in aggregate for one run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmployerContributionsReport {
+    pub payroll_run_id: Uuid,
+    pub contribution_types: Vec<String>,
+    pub employees: Vec<EmployerContributionLine>,
+    pub totals: std::collections::BTreeMap<String, Decimal>,
+    pub total: Decimal,
+}
+
 /// P9A Tax Return (Annual)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct P9AReturn {
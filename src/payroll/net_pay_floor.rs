@@ -0,0 +1,123 @@
+//! Net Pay Floor
+//!
+//! Take-home a run must leave each employee once every deduction is in:
+//! the higher of the tenant's protected earnings and any legal minimum for
+//! the employee's country. Repayments and voluntary deductions are cut back
+//! to respect it, and whatever a voluntary deduction could not take is
+//! recorded as deferred against the run. Tax and statutory contributions
+//! can't be cut, so net pay still under the floor after them is a shortfall
+//! that pre-flight raises with approvers.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Minimum take-home per pay period by country, as the tenant's counsel
+/// reads local wage-protection law; none unless set
+#[derive(Debug, Clone, Default)]
+pub struct NetPayFloors {
+    countries: HashMap<String, Decimal>,
+}
+
+impl NetPayFloors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, country_code: &str, minimum: Decimal) -> &mut Self {
+        self.countries.insert(country_code.to_uppercase(), minimum);
+        self
+    }
+
+    pub fn for_country(&self, country_code: &str) -> Option<Decimal> {
+        self.countries.get(&country_code.to_uppercase()).copied()
+    }
+}
+
+/// Part of a voluntary deduction a run left untaken to respect the floor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeferredDeduction {
+    pub name: String,
+    pub due: Decimal,
+    pub taken: Decimal,
+    pub deferred: Decimal,
+}
+
+/// How the floor bore on one employee's payslip in a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetPayFloorOutcome {
+    pub payroll_run_id: Uuid,
+    pub employee_id: Uuid,
+    pub floor: Decimal,
+    pub net_pay: Decimal,
+    pub deferred: Vec<DeferredDeduction>,
+    /// How far net pay sits under the floor after deductions that can't be cut
+    pub shortfall: Decimal,
+}
+
+/// Deferrals and shortfalls per run and employee
+#[derive(Debug, Clone, Default)]
+pub struct NetPayFloorLedger {
+    // In real implementation, backed by the payroll_deduction_deferrals table
+    outcomes: Arc<DashMap<(Uuid, Uuid), NetPayFloorOutcome>>,
+}
+
+impl NetPayFloorLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a payslip's outcome, replacing any from an earlier calculation
+    /// of the same run. Payslips the floor did not touch leave no entry.
+    pub fn record(&self, outcome: NetPayFloorOutcome) {
+        let key = (outcome.payroll_run_id, outcome.employee_id);
+        if outcome.deferred.is_empty() && outcome.shortfall.is_zero() {
+            self.outcomes.remove(&key);
+        } else {
+            self.outcomes.insert(key, outcome);
+        }
+    }
+
+    pub fn get(&self, payroll_run_id: Uuid, employee_id: Uuid) -> Option<NetPayFloorOutcome> {
+        self.outcomes.get(&(payroll_run_id, employee_id)).map(|o| o.clone())
+    }
+
+    pub fn for_run(&self, payroll_run_id: Uuid) -> Vec<NetPayFloorOutcome> {
+        self.outcomes.iter().filter(|o| o.payroll_run_id == payroll_run_id).map(|o| o.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_untouched_payslips_leave_no_entry() {
+        let ledger = NetPayFloorLedger::new();
+        let (run_id, employee_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let outcome = |deferred: Vec<DeferredDeduction>| NetPayFloorOutcome {
+            payroll_run_id: run_id,
+            employee_id,
+            floor: dec!(50000),
+            net_pay: dec!(50000),
+            deferred,
+            shortfall: Decimal::ZERO,
+        };
+
+        ledger.record(outcome(vec![DeferredDeduction {
+            name: "Union dues".into(),
+            due: dec!(2000),
+            taken: dec!(500),
+            deferred: dec!(1500),
+        }]));
+        assert_eq!(ledger.for_run(run_id).len(), 1);
+
+        // Recalculating with room for the deduction clears the deferral
+        ledger.record(outcome(vec![]));
+        assert!(ledger.get(run_id, employee_id).is_none());
+    }
+}
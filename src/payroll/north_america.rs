@@ -0,0 +1,728 @@
+//! North America Tax Engines
+//!
+//! Tax calculators for the United States and Canada:
+//! - United States: federal brackets, FICA with Social Security wage base,
+//!   Additional Medicare, per-state income tax, SDI, SUI and FUTA
+//! - Canada: federal + provincial brackets, CPP/CPP2 (QPP in Québec), EI
+//!
+//! Figures are 2024 single-filer values. Local taxes (NYC, Yonkers, Ontario
+//! Health Premium) are out of scope.
+//!
+//! Employees working in several states or provinces carry a
+//! `WorkLocationAllocation`; each jurisdiction then withholds its own tax
+//! on its share of wages. Resident-state credits for tax paid elsewhere
+//! are left to the annual return.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use super::rounding::{progressive_tax, TaxRounding};
+use super::work_location::{JurisdictionWithholding, ReciprocityAgreements, WorkLocationAllocation, WorkLocationError};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// UNITED STATES (US) - FEDERAL + STATES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// US States with payroll support
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UsState {
+    California, NewYork, Texas, Florida, Washington, Nevada,
+    Illinois, Pennsylvania, Massachusetts, Colorado, NorthCarolina, Georgia,
+}
+
+impl UsState {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::California => "CA", Self::NewYork => "NY", Self::Texas => "TX",
+            Self::Florida => "FL", Self::Washington => "WA", Self::Nevada => "NV",
+            Self::Illinois => "IL", Self::Pennsylvania => "PA", Self::Massachusetts => "MA",
+            Self::Colorado => "CO", Self::NorthCarolina => "NC", Self::Georgia => "GA",
+        }
+    }
+
+    pub fn has_income_tax(&self) -> bool {
+        !matches!(self, Self::Texas | Self::Florida | Self::Washington | Self::Nevada)
+    }
+
+    /// State standard deduction (or personal exemption for flat-rate states)
+    pub fn standard_deduction(&self) -> Decimal {
+        match self {
+            Self::California => dec!(5540),
+            Self::NewYork => dec!(8000),
+            Self::Illinois => dec!(2775),
+            Self::Colorado => dec!(14600),      // Follows federal taxable income
+            Self::NorthCarolina => dec!(12750),
+            Self::Georgia => dec!(12000),
+            _ => Decimal::ZERO,
+        }
+    }
+
+    /// State income tax brackets (upper bound, rate)
+    pub fn brackets(&self) -> Vec<(Decimal, Decimal)> {
+        match self {
+            Self::California => vec![
+                (dec!(10756), dec!(0.01)), (dec!(25499), dec!(0.02)), (dec!(40245), dec!(0.04)),
+                (dec!(55866), dec!(0.06)), (dec!(70606), dec!(0.08)), (dec!(360659), dec!(0.093)),
+                (dec!(432787), dec!(0.103)), (dec!(721314), dec!(0.113)), (dec!(999999999), dec!(0.123)),
+            ],
+            Self::NewYork => vec![
+                (dec!(8500), dec!(0.04)), (dec!(11700), dec!(0.045)), (dec!(13900), dec!(0.0525)),
+                (dec!(80650), dec!(0.055)), (dec!(215400), dec!(0.06)), (dec!(1077550), dec!(0.0685)),
+                (dec!(5000000), dec!(0.0965)), (dec!(25000000), dec!(0.103)), (dec!(999999999), dec!(0.109)),
+            ],
+            Self::Illinois => vec![(dec!(999999999), dec!(0.0495))],
+            Self::Pennsylvania => vec![(dec!(999999999), dec!(0.0307))],
+            Self::Massachusetts => vec![(dec!(1053750), dec!(0.05)), (dec!(999999999), dec!(0.09))], // 4% millionaires' surtax
+            Self::Colorado => vec![(dec!(999999999), dec!(0.044))],
+            Self::NorthCarolina => vec![(dec!(999999999), dec!(0.045))],
+            Self::Georgia => vec![(dec!(999999999), dec!(0.0539))],
+            Self::Texas | Self::Florida | Self::Washington | Self::Nevada => vec![],
+        }
+    }
+
+    /// Nonrefundable personal exemption credit
+    pub fn personal_credit(&self) -> Decimal {
+        match self {
+            Self::California => dec!(149),
+            _ => Decimal::ZERO,
+        }
+    }
+
+    /// Employee state disability / paid family leave (rate, wage cap)
+    pub fn disability_insurance(&self) -> Option<(Decimal, Option<Decimal>)> {
+        match self {
+            Self::California => Some((dec!(0.011), None)),                  // SDI, uncapped since 2024
+            Self::NewYork => Some((dec!(0.00373), Some(dec!(89343.80)))),   // PFL (DBL is nominal)
+            Self::Washington => Some((dec!(0.006272), Some(dec!(168600)))), // PFML employee share
+            Self::Massachusetts => Some((dec!(0.0046), Some(dec!(168600)))),
+            _ => None,
+        }
+    }
+
+    /// Employer state unemployment insurance (new-employer rate, wage base)
+    pub fn sui(&self) -> (Decimal, Decimal) {
+        match self {
+            Self::California => (dec!(0.034), dec!(7000)),
+            Self::NewYork => (dec!(0.041), dec!(12500)),
+            Self::Texas => (dec!(0.027), dec!(9000)),
+            Self::Florida => (dec!(0.027), dec!(7000)),
+            Self::Washington => (dec!(0.0121), dec!(68500)),
+            Self::Nevada => (dec!(0.0295), dec!(40100)),
+            Self::Illinois => (dec!(0.0395), dec!(13590)),
+            Self::Pennsylvania => (dec!(0.03822), dec!(10000)),
+            Self::Massachusetts => (dec!(0.0242), dec!(15000)),
+            Self::Colorado => (dec!(0.017), dec!(23800)),
+            Self::NorthCarolina => (dec!(0.01), dec!(31400)),
+            Self::Georgia => (dec!(0.0264), dec!(9500)),
+        }
+    }
+}
+
+/// US FICA (Social Security + Medicare)
+#[derive(Debug, Clone)]
+pub struct UsFica {
+    pub social_security_rate: Decimal,            // 6.2% each side
+    pub social_security_wage_base: Decimal,       // $168,600
+    pub medicare_rate: Decimal,                   // 1.45% each side
+    pub additional_medicare_rate: Decimal,        // 0.9% employee only
+    pub additional_medicare_threshold: Decimal,   // $200,000
+}
+
+impl Default for UsFica {
+    fn default() -> Self {
+        Self {
+            social_security_rate: dec!(0.062),
+            social_security_wage_base: dec!(168600),
+            medicare_rate: dec!(0.0145),
+            additional_medicare_rate: dec!(0.009),
+            additional_medicare_threshold: dec!(200000),
+        }
+    }
+}
+
+/// US Tax Calculator
+pub struct UsTaxCalculator {
+    pub state: UsState,
+    pub fica: UsFica,
+    pub federal_standard_deduction: Decimal,
+    pub pretax_deductions: Decimal,  // 401(k), Section 125
+    /// Work split across states; `state` is the state of residence
+    pub work_locations: Option<WorkLocationAllocation>,
+    pub reciprocity: ReciprocityAgreements,
+}
+
+impl UsTaxCalculator {
+    pub fn new(state: UsState) -> Self {
+        Self {
+            state,
+            fica: UsFica::default(),
+            federal_standard_deduction: dec!(14600),
+            pretax_deductions: Decimal::ZERO,
+            work_locations: None,
+            reciprocity: ReciprocityAgreements::new(),
+        }
+    }
+
+    /// Apportion state income tax across the states worked in
+    pub fn with_work_locations(mut self, allocation: WorkLocationAllocation, reciprocity: ReciprocityAgreements) -> Self {
+        self.work_locations = Some(allocation);
+        self.reciprocity = reciprocity;
+        self
+    }
+
+    /// Full-year income tax `state` would levy on `wages`
+    fn state_tax(state: UsState, wages: Decimal, rounding: &TaxRounding) -> Decimal {
+        let taxable = (wages - state.standard_deduction()).max(Decimal::ZERO);
+        (progressive_tax(&state.brackets(), taxable, rounding) - state.personal_credit()).max(Decimal::ZERO)
+    }
+
+    /// Each state's share of wages and of its own tax on the full wages
+    fn state_withholding(
+        &self,
+        wages: Decimal,
+        rounding: &TaxRounding,
+    ) -> Result<Vec<JurisdictionWithholding>, WorkLocationError> {
+        let residence = self.state.code();
+        let shares = match &self.work_locations {
+            Some(allocation) => allocation.apportion(wages, residence, &self.reciprocity)?,
+            None => vec![(residence.to_string(), wages)],
+        };
+
+        shares
+            .into_iter()
+            .map(|(code, share)| {
+                let state = NorthAmericaRegistry::us_state(&code)
+                    .ok_or(WorkLocationError::UnsupportedJurisdiction(code))?;
+                let tax = Self::state_tax(state, wages, rounding);
+                let income_tax = if share == wages { tax } else { tax * share / wages };
+                Ok(JurisdictionWithholding { jurisdiction: state.code().to_string(), wages: share, income_tax })
+            })
+            .collect()
+    }
+
+    /// Fails when the work location allocation is invalid or names a state without tax tables
+    pub fn calculate(&self, gross_annual: Decimal) -> Result<UsTaxResult, WorkLocationError> {
+        let wages = (gross_annual - self.pretax_deductions).max(Decimal::ZERO);
+
+        // Federal income tax
+        let federal_taxable = (wages - self.federal_standard_deduction).max(Decimal::ZERO);
+        let rounding = TaxRounding::for_country("US");
+        let federal_income_tax = progressive_tax(&Self::federal_brackets(), federal_taxable, &rounding);
+
+        // State income tax, apportioned when work spans states
+        let state_taxable = (wages - self.state.standard_deduction()).max(Decimal::ZERO);
+        let state_withholding = self.state_withholding(wages, &rounding)?;
+        let state_income_tax = state_withholding.iter().map(|w| w.income_tax).sum();
+
+        // FICA is levied on gross wages (401(k) deferrals do not reduce it)
+        let ss_wages = gross_annual.min(self.fica.social_security_wage_base);
+        let social_security = ss_wages * self.fica.social_security_rate;
+        let medicare = gross_annual * self.fica.medicare_rate;
+        let additional_medicare = (gross_annual - self.fica.additional_medicare_threshold).max(Decimal::ZERO)
+            * self.fica.additional_medicare_rate;
+
+        let state_disability = match self.state.disability_insurance() {
+            Some((rate, Some(cap))) => gross_annual.min(cap) * rate,
+            Some((rate, None)) => gross_annual * rate,
+            None => Decimal::ZERO,
+        };
+
+        // Employer side
+        let (sui_rate, sui_base) = self.state.sui();
+        let state_sui = gross_annual.min(sui_base) * sui_rate;
+        let futa = gross_annual.min(dec!(7000)) * dec!(0.006);  // Net of full state credit
+
+        let total_employee = federal_income_tax + state_income_tax + social_security
+            + medicare + additional_medicare + state_disability;
+        let total_employer = social_security + medicare + state_sui + futa;
+
+        Ok(UsTaxResult {
+            gross_annual,
+            federal_taxable_income: federal_taxable,
+            federal_income_tax,
+            state_taxable_income: state_taxable,
+            state_income_tax,
+            state_withholding,
+            social_security,
+            medicare,
+            additional_medicare,
+            state_disability,
+            total_employee_deductions: total_employee,
+            net_annual: gross_annual - self.pretax_deductions - total_employee,
+            employer_social_security: social_security,
+            employer_medicare: medicare,
+            state_sui,
+            futa,
+            total_employer_cost: gross_annual + total_employer,
+            effective_rate: if gross_annual > Decimal::ZERO { total_employee / gross_annual * dec!(100) } else { Decimal::ZERO },
+        })
+    }
+
+    fn federal_brackets() -> [(Decimal, Decimal); 7] {
+        [
+            (dec!(11600), dec!(0.10)), (dec!(47150), dec!(0.12)), (dec!(100525), dec!(0.22)),
+            (dec!(191950), dec!(0.24)), (dec!(243725), dec!(0.32)), (dec!(609350), dec!(0.35)),
+            (dec!(999999999), dec!(0.37)),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsTaxResult {
+    pub gross_annual: Decimal,
+    pub federal_taxable_income: Decimal,
+    pub federal_income_tax: Decimal,
+    pub state_taxable_income: Decimal,
+    pub state_income_tax: Decimal,
+    /// State income tax by state worked in
+    pub state_withholding: Vec<JurisdictionWithholding>,
+    pub social_security: Decimal,
+    pub medicare: Decimal,
+    pub additional_medicare: Decimal,
+    pub state_disability: Decimal,
+    pub total_employee_deductions: Decimal,
+    pub net_annual: Decimal,
+    pub employer_social_security: Decimal,
+    pub employer_medicare: Decimal,
+    pub state_sui: Decimal,
+    pub futa: Decimal,
+    pub total_employer_cost: Decimal,
+    pub effective_rate: Decimal,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CANADA (CA) - FEDERAL + PROVINCES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Canadian Provinces with payroll support
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Province {
+    Ontario, Quebec, BritishColumbia, Alberta, Manitoba, Saskatchewan, NovaScotia,
+}
+
+impl Province {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Ontario => "ON", Self::Quebec => "QC", Self::BritishColumbia => "BC",
+            Self::Alberta => "AB", Self::Manitoba => "MB", Self::Saskatchewan => "SK",
+            Self::NovaScotia => "NS",
+        }
+    }
+
+    /// Provincial basic personal amount
+    pub fn basic_personal_amount(&self) -> Decimal {
+        match self {
+            Self::Ontario => dec!(12399),
+            Self::Quebec => dec!(18056),
+            Self::BritishColumbia => dec!(12580),
+            Self::Alberta => dec!(21885),
+            Self::Manitoba => dec!(15780),
+            Self::Saskatchewan => dec!(18491),
+            Self::NovaScotia => dec!(8744),
+        }
+    }
+
+    /// Provincial income tax brackets (upper bound, rate)
+    pub fn brackets(&self) -> Vec<(Decimal, Decimal)> {
+        match self {
+            Self::Ontario => vec![
+                (dec!(51446), dec!(0.0505)), (dec!(102894), dec!(0.0915)), (dec!(150000), dec!(0.1116)),
+                (dec!(220000), dec!(0.1216)), (dec!(999999999), dec!(0.1316)),
+            ],
+            Self::Quebec => vec![
+                (dec!(51780), dec!(0.14)), (dec!(103545), dec!(0.19)), (dec!(126000), dec!(0.24)),
+                (dec!(999999999), dec!(0.2575)),
+            ],
+            Self::BritishColumbia => vec![
+                (dec!(47937), dec!(0.0506)), (dec!(95875), dec!(0.077)), (dec!(110076), dec!(0.105)),
+                (dec!(133664), dec!(0.1229)), (dec!(181232), dec!(0.147)), (dec!(252752), dec!(0.168)),
+                (dec!(999999999), dec!(0.205)),
+            ],
+            Self::Alberta => vec![
+                (dec!(148269), dec!(0.10)), (dec!(177922), dec!(0.12)), (dec!(237230), dec!(0.13)),
+                (dec!(355845), dec!(0.14)), (dec!(999999999), dec!(0.15)),
+            ],
+            Self::Manitoba => vec![
+                (dec!(47000), dec!(0.108)), (dec!(100000), dec!(0.1275)), (dec!(999999999), dec!(0.174)),
+            ],
+            Self::Saskatchewan => vec![
+                (dec!(52057), dec!(0.105)), (dec!(148734), dec!(0.125)), (dec!(999999999), dec!(0.145)),
+            ],
+            Self::NovaScotia => vec![
+                (dec!(29590), dec!(0.0879)), (dec!(59180), dec!(0.1495)), (dec!(93000), dec!(0.1667)),
+                (dec!(150000), dec!(0.175)), (dec!(999999999), dec!(0.21)),
+            ],
+        }
+    }
+
+    /// Québec runs its own pension plan (QPP) and parental insurance (QPIP)
+    pub fn is_quebec(&self) -> bool { matches!(self, Self::Quebec) }
+}
+
+/// Canada Pension Plan (CPP / QPP) and Employment Insurance parameters
+#[derive(Debug, Clone)]
+pub struct CanadaContributions {
+    pub basic_exemption: Decimal,        // $3,500
+    pub ympe: Decimal,                   // Year's Maximum Pensionable Earnings $68,500
+    pub yampe: Decimal,                  // Year's Additional Maximum $73,200
+    pub cpp_rate: Decimal,               // 5.95%
+    pub qpp_rate: Decimal,               // 6.40%
+    pub cpp2_rate: Decimal,              // 4% between YMPE and YAMPE
+    pub ei_max_insurable: Decimal,       // $63,200
+    pub ei_rate: Decimal,                // 1.66%
+    pub ei_rate_quebec: Decimal,         // 1.32%
+    pub ei_employer_multiplier: Decimal, // 1.4x
+    pub qpip_rate: Decimal,              // 0.494%
+    pub qpip_max_insurable: Decimal,     // $94,000
+}
+
+impl Default for CanadaContributions {
+    fn default() -> Self {
+        Self {
+            basic_exemption: dec!(3500),
+            ympe: dec!(68500),
+            yampe: dec!(73200),
+            cpp_rate: dec!(0.0595),
+            qpp_rate: dec!(0.064),
+            cpp2_rate: dec!(0.04),
+            ei_max_insurable: dec!(63200),
+            ei_rate: dec!(0.0166),
+            ei_rate_quebec: dec!(0.0132),
+            ei_employer_multiplier: dec!(1.4),
+            qpip_rate: dec!(0.00494),
+            qpip_max_insurable: dec!(94000),
+        }
+    }
+}
+
+/// Canada Tax Calculator
+pub struct CanadaTaxCalculator {
+    pub province: Province,
+    pub contributions: CanadaContributions,
+    pub federal_basic_personal_amount: Decimal,
+    /// Work split across provinces; `province` is the province of residence
+    pub work_locations: Option<WorkLocationAllocation>,
+    pub reciprocity: ReciprocityAgreements,
+}
+
+impl CanadaTaxCalculator {
+    pub fn new(province: Province) -> Self {
+        Self {
+            province,
+            contributions: CanadaContributions::default(),
+            federal_basic_personal_amount: dec!(15705),
+            work_locations: None,
+            reciprocity: ReciprocityAgreements::new(),
+        }
+    }
+
+    /// Apportion provincial tax across the provinces worked in
+    pub fn with_work_locations(mut self, allocation: WorkLocationAllocation, reciprocity: ReciprocityAgreements) -> Self {
+        self.work_locations = Some(allocation);
+        self.reciprocity = reciprocity;
+        self
+    }
+
+    /// Each province's share of taxable income and of its own tax on the whole
+    fn provincial_withholding(
+        &self,
+        taxable_income: Decimal,
+        credit_base: Decimal,
+        rounding: &TaxRounding,
+    ) -> Result<Vec<JurisdictionWithholding>, WorkLocationError> {
+        let residence = self.province.code();
+        let shares = match &self.work_locations {
+            Some(allocation) => allocation.apportion(taxable_income, residence, &self.reciprocity)?,
+            None => vec![(residence.to_string(), taxable_income)],
+        };
+
+        shares
+            .into_iter()
+            .map(|(code, share)| {
+                let province = NorthAmericaRegistry::province(&code)
+                    .ok_or(WorkLocationError::UnsupportedJurisdiction(code))?;
+                let brackets = province.brackets();
+                let credits = (province.basic_personal_amount() + credit_base) * brackets[0].1;
+                let tax = (progressive_tax(&brackets, taxable_income, rounding) - credits).max(Decimal::ZERO);
+                let income_tax = if share == taxable_income { tax } else { tax * share / taxable_income };
+                Ok(JurisdictionWithholding { jurisdiction: province.code().to_string(), wages: share, income_tax })
+            })
+            .collect()
+    }
+
+    /// Fails when the work location allocation is invalid or names a province without tax tables
+    pub fn calculate(&self, gross_annual: Decimal) -> Result<CanadaTaxResult, WorkLocationError> {
+        let c = &self.contributions;
+
+        // CPP/QPP base + CPP2 (enhanced tier)
+        let pension_rate = if self.province.is_quebec() { c.qpp_rate } else { c.cpp_rate };
+        let pensionable = (gross_annual.min(c.ympe) - c.basic_exemption).max(Decimal::ZERO);
+        let cpp = pensionable * pension_rate;
+        let cpp2 = (gross_annual.min(c.yampe) - c.ympe).max(Decimal::ZERO) * c.cpp2_rate;
+
+        // EI (reduced in Québec, which funds QPIP instead)
+        let ei_rate = if self.province.is_quebec() { c.ei_rate_quebec } else { c.ei_rate };
+        let ei = gross_annual.min(c.ei_max_insurable) * ei_rate;
+        let qpip = if self.province.is_quebec() {
+            gross_annual.min(c.qpip_max_insurable) * c.qpip_rate
+        } else {
+            Decimal::ZERO
+        };
+
+        // CPP2 is deductible from income; base CPP and EI earn credits at the lowest rate
+        let taxable_income = (gross_annual - cpp2).max(Decimal::ZERO);
+
+        let rounding = TaxRounding::for_country("CA");
+        let federal_credits = (self.federal_basic_personal_amount + cpp + ei + qpip) * dec!(0.15);
+        let mut federal_tax = (progressive_tax(&Self::federal_brackets(), taxable_income, &rounding) - federal_credits)
+            .max(Decimal::ZERO);
+
+        let provincial_withholding = self.provincial_withholding(taxable_income, cpp + ei + qpip, &rounding)?;
+        let provincial_tax = provincial_withholding.iter().map(|w| w.income_tax).sum();
+
+        // Québec abatement of 16.5% on the income earned in Québec
+        let quebec_income: Decimal = provincial_withholding
+            .iter()
+            .filter(|w| w.jurisdiction == Province::Quebec.code())
+            .map(|w| w.wages)
+            .sum();
+        if !quebec_income.is_zero() {
+            federal_tax *= Decimal::ONE - dec!(0.165) * quebec_income / taxable_income;
+        }
+
+        let total_employee = federal_tax + provincial_tax + cpp + cpp2 + ei + qpip;
+        let employer_ei = ei * c.ei_employer_multiplier;
+        let employer_qpip = if self.province.is_quebec() {
+            gross_annual.min(c.qpip_max_insurable) * dec!(0.00692)
+        } else {
+            Decimal::ZERO
+        };
+
+        Ok(CanadaTaxResult {
+            gross_annual,
+            taxable_income,
+            federal_tax,
+            provincial_tax,
+            provincial_withholding,
+            cpp,
+            cpp2,
+            ei,
+            qpip,
+            total_employee_deductions: total_employee,
+            net_annual: gross_annual - total_employee,
+            employer_cpp: cpp + cpp2,
+            employer_ei,
+            total_employer_cost: gross_annual + cpp + cpp2 + employer_ei + employer_qpip,
+            effective_rate: if gross_annual > Decimal::ZERO { total_employee / gross_annual * dec!(100) } else { Decimal::ZERO },
+        })
+    }
+
+    fn federal_brackets() -> [(Decimal, Decimal); 5] {
+        [
+            (dec!(55867), dec!(0.15)), (dec!(111733), dec!(0.205)), (dec!(173205), dec!(0.26)),
+            (dec!(246752), dec!(0.29)), (dec!(999999999), dec!(0.33)),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanadaTaxResult {
+    pub gross_annual: Decimal,
+    pub taxable_income: Decimal,
+    pub federal_tax: Decimal,
+    pub provincial_tax: Decimal,
+    /// Provincial tax by province worked in
+    pub provincial_withholding: Vec<JurisdictionWithholding>,
+    pub cpp: Decimal,
+    pub cpp2: Decimal,
+    pub ei: Decimal,
+    pub qpip: Decimal,
+    pub total_employee_deductions: Decimal,
+    pub net_annual: Decimal,
+    pub employer_cpp: Decimal,
+    pub employer_ei: Decimal,
+    pub total_employer_cost: Decimal,
+    pub effective_rate: Decimal,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// REGISTRY
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// North America Registry
+pub struct NorthAmericaRegistry;
+
+impl NorthAmericaRegistry {
+    pub fn supported_countries() -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![("US", "United States", "USD"), ("CA", "Canada", "CAD")]
+    }
+
+    pub fn us_states() -> Vec<UsState> {
+        use UsState::*;
+        vec![California, NewYork, Texas, Florida, Washington, Nevada,
+             Illinois, Pennsylvania, Massachusetts, Colorado, NorthCarolina, Georgia]
+    }
+
+    pub fn provinces() -> Vec<Province> {
+        use Province::*;
+        vec![Ontario, Quebec, BritishColumbia, Alberta, Manitoba, Saskatchewan, NovaScotia]
+    }
+
+    pub fn us_state(code: &str) -> Option<UsState> {
+        Self::us_states().into_iter().find(|s| s.code().eq_ignore_ascii_case(code))
+    }
+
+    pub fn province(code: &str) -> Option<Province> {
+        Self::provinces().into_iter().find(|p| p.code().eq_ignore_ascii_case(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_us_california() {
+        let result = UsTaxCalculator::new(UsState::California).calculate(dec!(100000)).unwrap();
+
+        // Federal: 85,400 taxable → 1,160 + 4,266 + 8,415
+        assert_eq!(result.federal_income_tax, dec!(13841));
+        // CA: 94,460 taxable, less $149 exemption credit
+        assert_eq!(result.state_income_tax.round_dp(2), dec!(5178.14));
+        assert_eq!(result.social_security, dec!(6200));
+        assert_eq!(result.medicare, dec!(1450));
+        assert_eq!(result.state_disability, dec!(1100));
+        assert_eq!(result.state_sui, dec!(238));
+    }
+
+    #[test]
+    fn test_us_new_york() {
+        let result = UsTaxCalculator::new(UsState::NewYork).calculate(dec!(100000)).unwrap();
+
+        // NY: 92,000 taxable
+        assert_eq!(result.state_income_tax, dec!(4951.75));
+        assert_eq!(result.federal_income_tax, dec!(13841));
+        assert!(result.net_annual < dec!(100000) - result.federal_income_tax - result.state_income_tax);
+    }
+
+    #[test]
+    fn test_us_fica_wage_base() {
+        let result = UsTaxCalculator::new(UsState::Texas).calculate(dec!(250000)).unwrap();
+        assert_eq!(result.social_security, dec!(10453.20));
+        assert_eq!(result.additional_medicare, dec!(450));
+        assert_eq!(result.state_income_tax, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_ontario_cpp_ei_caps() {
+        let calc = CanadaTaxCalculator::new(Province::Ontario);
+
+        let mid = calc.calculate(dec!(60000)).unwrap();
+        assert_eq!(mid.cpp, dec!(3361.75));
+        assert_eq!(mid.cpp2, Decimal::ZERO);
+        assert_eq!(mid.ei, dec!(996.00));
+
+        for gross in [dec!(100000), dec!(250000)] {
+            let high = calc.calculate(gross).unwrap();
+            assert_eq!(high.cpp, dec!(3867.50));
+            assert_eq!(high.cpp2, dec!(188));
+            assert_eq!(high.ei, dec!(1049.12));
+            assert_eq!(high.employer_ei.round_dp(2), dec!(1468.77));
+        }
+
+        assert!(calc.calculate(dec!(100000)).unwrap().provincial_tax > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_quebec_uses_qpp() {
+        let result = CanadaTaxCalculator::new(Province::Quebec).calculate(dec!(100000)).unwrap();
+        assert_eq!(result.cpp, dec!(4160));
+        assert!(result.qpip > Decimal::ZERO);
+        assert!(result.ei < dec!(1049.12));
+    }
+
+    #[test]
+    fn test_two_states_split_by_workdays() {
+        // NY resident spending 3 of 5 days a week in New York, 2 in California
+        let allocation = WorkLocationAllocation::by_days(&[("NY", dec!(138)), ("CA", dec!(92))]);
+        let result = UsTaxCalculator::new(UsState::NewYork)
+            .with_work_locations(allocation, ReciprocityAgreements::new())
+            .calculate(dec!(100000)).unwrap();
+
+        let ny = UsTaxCalculator::new(UsState::NewYork).calculate(dec!(100000)).unwrap().state_income_tax;
+        let ca = UsTaxCalculator::new(UsState::California).calculate(dec!(100000)).unwrap().state_income_tax;
+        let by_state: Vec<(&str, Decimal, Decimal)> = result
+            .state_withholding
+            .iter()
+            .map(|w| (w.jurisdiction.as_str(), w.wages, w.income_tax.round_dp(2)))
+            .collect();
+        assert_eq!(by_state, [("CA", dec!(40000), (ca * dec!(0.4)).round_dp(2)), ("NY", dec!(60000), (ny * dec!(0.6)).round_dp(2))]);
+        assert_eq!(result.state_withholding.iter().map(|w| w.wages).sum::<Decimal>(), dec!(100000));
+        assert_eq!(result.state_income_tax, result.state_withholding.iter().map(|w| w.income_tax).sum::<Decimal>());
+        // Federal and FICA are unaffected by where the work is done
+        assert_eq!(result.federal_income_tax, dec!(13841));
+    }
+
+    #[test]
+    fn test_reciprocity_taxes_at_residence() {
+        let allocation = WorkLocationAllocation::by_percent(&[("PA", dec!(50)), ("NY", dec!(50))]);
+        let reciprocal = ReciprocityAgreements::new().add_mutual("PA", "NY");
+
+        let result = UsTaxCalculator::new(UsState::Pennsylvania)
+            .with_work_locations(allocation.clone(), reciprocal)
+            .calculate(dec!(100000)).unwrap();
+        assert_eq!(result.state_withholding.len(), 1);
+        assert_eq!(result.state_withholding[0].jurisdiction, "PA");
+        assert_eq!(result.state_income_tax, UsTaxCalculator::new(UsState::Pennsylvania).calculate(dec!(100000)).unwrap().state_income_tax);
+
+        // Without the agreement New York withholds on its half
+        let result = UsTaxCalculator::new(UsState::Pennsylvania)
+            .with_work_locations(allocation, ReciprocityAgreements::new())
+            .calculate(dec!(100000)).unwrap();
+        assert_eq!(result.state_withholding.len(), 2);
+    }
+
+    #[test]
+    fn test_unusable_allocation_is_refused() {
+        let unsupported = WorkLocationAllocation::by_days(&[("NY", dec!(100)), ("WY", dec!(100))]);
+        let result = UsTaxCalculator::new(UsState::NewYork)
+            .with_work_locations(unsupported, ReciprocityAgreements::new())
+            .calculate(dec!(100000));
+        assert_eq!(result.unwrap_err(), WorkLocationError::UnsupportedJurisdiction("WY".to_string()));
+
+        let short = WorkLocationAllocation::by_percent(&[("NY", dec!(60)), ("CA", dec!(30))]);
+        let result = UsTaxCalculator::new(UsState::NewYork)
+            .with_work_locations(short.clone(), ReciprocityAgreements::new())
+            .calculate(dec!(100000));
+        assert!(matches!(result, Err(WorkLocationError::InvalidAllocation(_))));
+
+        let result = CanadaTaxCalculator::new(Province::Ontario)
+            .with_work_locations(short, ReciprocityAgreements::new())
+            .calculate(dec!(100000));
+        assert!(matches!(result, Err(WorkLocationError::InvalidAllocation(_))));
+    }
+
+    #[test]
+    fn test_provinces_split_with_quebec_abatement() {
+        let allocation = WorkLocationAllocation::by_percent(&[("ON", dec!(75)), ("QC", dec!(25))]);
+        let ontario = CanadaTaxCalculator::new(Province::Ontario).calculate(dec!(100000)).unwrap();
+        let split = CanadaTaxCalculator::new(Province::Ontario)
+            .with_work_locations(allocation, ReciprocityAgreements::new())
+            .calculate(dec!(100000)).unwrap();
+
+        assert_eq!(split.provincial_withholding.len(), 2);
+        assert_eq!(split.provincial_withholding.iter().map(|w| w.wages).sum::<Decimal>(), split.taxable_income);
+        assert!(split.provincial_tax > ontario.provincial_tax);
+        // A quarter of the Québec abatement
+        assert_eq!(split.federal_tax.round_dp(2), (ontario.federal_tax * dec!(0.95875)).round_dp(2));
+    }
+
+    #[test]
+    fn test_registry() {
+        assert_eq!(NorthAmericaRegistry::supported_countries().len(), 2);
+        assert_eq!(NorthAmericaRegistry::us_state("ny"), Some(UsState::NewYork));
+        assert_eq!(NorthAmericaRegistry::province("ON"), Some(Province::Ontario));
+        assert!(!UsState::Florida.has_income_tax());
+    }
+}
@@ -0,0 +1,149 @@
+//! Notice Periods
+//!
+//! Notice owed by an employer on termination, by country and length of
+//! service, with a shorter period during probation. When the employer ends
+//! employment with immediate effect, the notice is paid instead (pay in
+//! lieu of notice) as part of final pay; see `SeveranceCalculators`.
+
+use std::collections::HashMap;
+use chrono::{Months, NaiveDate};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+/// Unit a notice length is expressed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoticeUnit {
+    /// Calendar days, paid at a thirtieth of monthly pay
+    Days,
+    Weeks,
+    Months,
+}
+
+/// A length of notice, e.g. two weeks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoticeLength {
+    pub amount: Decimal,
+    pub unit: NoticeUnit,
+}
+
+impl NoticeLength {
+    pub fn days(amount: Decimal) -> Self {
+        Self { amount, unit: NoticeUnit::Days }
+    }
+
+    pub fn weeks(amount: Decimal) -> Self {
+        Self { amount, unit: NoticeUnit::Weeks }
+    }
+
+    pub fn months(amount: Decimal) -> Self {
+        Self { amount, unit: NoticeUnit::Months }
+    }
+
+    /// Pay for this much notice at `monthly_salary`
+    pub fn pay(&self, monthly_salary: Decimal) -> Decimal {
+        match self.unit {
+            NoticeUnit::Days => monthly_salary / dec!(30) * self.amount,
+            NoticeUnit::Weeks => monthly_salary * dec!(12) / dec!(52) * self.amount,
+            NoticeUnit::Months => monthly_salary * self.amount,
+        }
+    }
+}
+
+/// Notice owed from `min_years` of service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoticeTier {
+    pub min_years: Decimal,
+    pub length: NoticeLength,
+}
+
+/// Tenure-scaled notice for one country or policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoticePeriod {
+    pub probation_months: u32,
+    pub during_probation: NoticeLength,
+    /// Ascending by `min_years`; the last tier reached applies
+    pub tiers: Vec<NoticeTier>,
+}
+
+impl Default for NoticePeriod {
+    /// One week under two years' service, a month after
+    fn default() -> Self {
+        Self {
+            probation_months: 3,
+            during_probation: NoticeLength::days(dec!(1)),
+            tiers: vec![
+                NoticeTier { min_years: Decimal::ZERO, length: NoticeLength::weeks(dec!(1)) },
+                NoticeTier { min_years: dec!(2), length: NoticeLength::months(dec!(1)) },
+            ],
+        }
+    }
+}
+
+impl NoticePeriod {
+    /// Notice owed to someone hired on `hire_date` and leaving on `termination_date`
+    pub fn notice_for(&self, hire_date: NaiveDate, termination_date: NaiveDate, service_years: Decimal) -> NoticeLength {
+        let probation_ends = hire_date.checked_add_months(Months::new(self.probation_months));
+        if probation_ends.is_some_and(|end| termination_date < end) {
+            return self.during_probation;
+        }
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| service_years >= tier.min_years)
+            .map(|tier| tier.length)
+            .unwrap_or(self.during_probation)
+    }
+}
+
+/// Notice rules per country, falling back to `NoticePeriod::default`
+#[derive(Debug, Clone)]
+pub struct NoticePeriods {
+    countries: HashMap<String, NoticePeriod>,
+    default: NoticePeriod,
+}
+
+impl Default for NoticePeriods {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoticePeriods {
+    /// Built-in statutory minimums
+    pub fn new() -> Self {
+        let mut periods = Self { countries: HashMap::new(), default: NoticePeriod::default() };
+        // Labour Act s.11: 1 day within 3 months, 1 week to 2 years, 2 weeks to 5 years, 1 month after
+        periods.set("NG", NoticePeriod {
+            probation_months: 3,
+            during_probation: NoticeLength::days(dec!(1)),
+            tiers: vec![
+                NoticeTier { min_years: Decimal::ZERO, length: NoticeLength::weeks(dec!(1)) },
+                NoticeTier { min_years: dec!(2), length: NoticeLength::weeks(dec!(2)) },
+                NoticeTier { min_years: dec!(5), length: NoticeLength::months(dec!(1)) },
+            ],
+        });
+        // ERA 1996 s.86: 1 week after a month, then a week per full year up to 12
+        periods.set("GB", NoticePeriod {
+            probation_months: 1,
+            during_probation: NoticeLength::days(Decimal::ZERO),
+            tiers: (1..=12)
+                .map(|weeks| NoticeTier {
+                    min_years: if weeks == 1 { Decimal::ZERO } else { Decimal::from(weeks) },
+                    length: NoticeLength::weeks(Decimal::from(weeks)),
+                })
+                .collect(),
+        });
+        periods
+    }
+
+    pub fn set(&mut self, country_code: &str, period: NoticePeriod) -> &mut Self {
+        self.countries.insert(country_code.to_ascii_uppercase(), period);
+        self
+    }
+
+    pub fn for_country(&self, country_code: &str) -> &NoticePeriod {
+        self.countries.get(&country_code.to_ascii_uppercase()).unwrap_or(&self.default)
+    }
+}
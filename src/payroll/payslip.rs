@@ -0,0 +1,464 @@
+//! Payslip Rendering
+//!
+//! Localized field labels for payslip documents and the CSV payslip. The
+//! SMS templates only carry a one-line summary; a payslip needs a label
+//! for every earning and deduction line. Lookups fall back from the
+//! requested language (`pt-BR`) to its base language (`pt`) and then to
+//! English, one label at a time, so a partial translation never leaves a
+//! line unlabelled. The localized CSV also writes amounts and dates the
+//! way the reader's locale does.
+
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::LocaleFormatter;
+use crate::integrations::csv_field;
+use super::models::PayrollItem;
+
+/// Fallback language; every label must exist here
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Labelled payslip fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayslipLabel {
+    Payslip,
+    Period,
+    Description,
+    Amount,
+    BasicSalary,
+    HousingAllowance,
+    TransportAllowance,
+    MealAllowance,
+    UtilityAllowance,
+    GrossPay,
+    IncomeTax,
+    PensionEmployee,
+    PensionEmployer,
+    HousingFund,
+    LoanRepayment,
+    TotalDeductions,
+    NetPay,
+}
+
+impl PayslipLabel {
+    pub const ALL: [PayslipLabel; 17] = [
+        Self::Payslip,
+        Self::Period,
+        Self::Description,
+        Self::Amount,
+        Self::BasicSalary,
+        Self::HousingAllowance,
+        Self::TransportAllowance,
+        Self::MealAllowance,
+        Self::UtilityAllowance,
+        Self::GrossPay,
+        Self::IncomeTax,
+        Self::PensionEmployee,
+        Self::PensionEmployer,
+        Self::HousingFund,
+        Self::LoanRepayment,
+        Self::TotalDeductions,
+        Self::NetPay,
+    ];
+}
+
+/// Payslip labels by language
+#[derive(Debug, Clone)]
+pub struct PayslipLabels {
+    labels: HashMap<String, HashMap<PayslipLabel, String>>,
+    /// Texts for `other_deductions` lines by key ("uif"), by language
+    lines: HashMap<String, HashMap<String, String>>,
+}
+
+impl PayslipLabels {
+    pub fn new() -> Self {
+        use PayslipLabel::*;
+
+        let mut registry = Self { labels: HashMap::new(), lines: HashMap::new() };
+
+        registry.insert_language("en", &[
+            (Payslip, "Payslip"),
+            (Period, "Period"),
+            (Description, "Description"),
+            (Amount, "Amount"),
+            (BasicSalary, "Basic Salary"),
+            (HousingAllowance, "Housing Allowance"),
+            (TransportAllowance, "Transport Allowance"),
+            (MealAllowance, "Meal Allowance"),
+            (UtilityAllowance, "Utility Allowance"),
+            (GrossPay, "Gross Pay"),
+            (IncomeTax, "Income Tax"),
+            (PensionEmployee, "Pension (Employee)"),
+            (PensionEmployer, "Pension (Employer)"),
+            (HousingFund, "Housing Fund"),
+            (LoanRepayment, "Loan Repayment"),
+            (TotalDeductions, "Total Deductions"),
+            (NetPay, "Net Pay"),
+        ]);
+
+        registry.insert_language("fr", &[
+            (Payslip, "Bulletin de paie"),
+            (Period, "Période"),
+            (Description, "Libellé"),
+            (Amount, "Montant"),
+            (BasicSalary, "Salaire de base"),
+            (HousingAllowance, "Indemnité de logement"),
+            (TransportAllowance, "Indemnité de transport"),
+            (MealAllowance, "Indemnité de repas"),
+            (UtilityAllowance, "Indemnité de charges"),
+            (GrossPay, "Salaire brut"),
+            (IncomeTax, "Impôt sur le revenu"),
+            (PensionEmployee, "Retraite (salarié)"),
+            (PensionEmployer, "Retraite (employeur)"),
+            (HousingFund, "Fonds de logement"),
+            (LoanRepayment, "Remboursement de prêt"),
+            (TotalDeductions, "Total des retenues"),
+            (NetPay, "Net à payer"),
+        ]);
+
+        registry.insert_language("pt", &[
+            (Payslip, "Recibo de vencimento"),
+            (Period, "Período"),
+            (Description, "Descrição"),
+            (Amount, "Valor"),
+            (BasicSalary, "Salário base"),
+            (HousingAllowance, "Subsídio de habitação"),
+            (TransportAllowance, "Subsídio de transporte"),
+            (MealAllowance, "Subsídio de alimentação"),
+            (UtilityAllowance, "Subsídio de despesas"),
+            (GrossPay, "Salário bruto"),
+            (IncomeTax, "Imposto sobre o rendimento"),
+            (PensionEmployee, "Pensão (trabalhador)"),
+            (PensionEmployer, "Pensão (empregador)"),
+            (HousingFund, "Fundo de habitação"),
+            (LoanRepayment, "Reembolso de empréstimo"),
+            (TotalDeductions, "Total de descontos"),
+            (NetPay, "Salário líquido"),
+        ]);
+
+        // Brazilian usage where it differs from European Portuguese
+        registry.insert_language("pt-BR", &[
+            (Payslip, "Holerite"),
+            (MealAllowance, "Vale-refeição"),
+        ]);
+
+        registry.insert_language("es", &[
+            (Payslip, "Nómina"),
+            (Period, "Periodo"),
+            (Description, "Concepto"),
+            (Amount, "Importe"),
+            (BasicSalary, "Salario base"),
+            (HousingAllowance, "Subsidio de vivienda"),
+            (TransportAllowance, "Subsidio de transporte"),
+            (MealAllowance, "Subsidio de comida"),
+            (UtilityAllowance, "Subsidio de suministros"),
+            (GrossPay, "Salario bruto"),
+            (IncomeTax, "Impuesto sobre la renta"),
+            (PensionEmployee, "Pensión (trabajador)"),
+            (PensionEmployer, "Pensión (empresa)"),
+            (HousingFund, "Fondo de vivienda"),
+            (LoanRepayment, "Amortización de préstamo"),
+            (TotalDeductions, "Total deducciones"),
+            (NetPay, "Líquido a percibir"),
+        ]);
+
+        registry.insert_language("sw", &[
+            (Payslip, "Hati ya mshahara"),
+            (Period, "Kipindi"),
+            (Description, "Maelezo"),
+            (Amount, "Kiasi"),
+            (BasicSalary, "Mshahara wa msingi"),
+            (GrossPay, "Mshahara ghafi"),
+            (IncomeTax, "Kodi ya mapato"),
+            (TotalDeductions, "Jumla ya makato"),
+            (NetPay, "Mshahara halisi"),
+        ]);
+
+        registry.insert_lines("en", &[
+            ("uif", "UIF"),
+            ("benefits", "Benefits"),
+            ("recurring", "Recurring Deductions"),
+            ("garnishment", "Garnishment"),
+            ("salary_advance", "Salary Advance"),
+            ("clawback", "Overpayment Recovery"),
+            ("clawback_recovery", "Overpayment Recovery"),
+            ("clawback_carried_forward", "Overpayment Carried Forward"),
+            ("leave_encashment", "Leave Encashment"),
+        ]);
+
+        registry.insert_lines("fr", &[
+            ("benefits", "Avantages sociaux"),
+            ("recurring", "Retenues récurrentes"),
+            ("garnishment", "Saisie sur salaire"),
+            ("salary_advance", "Avance sur salaire"),
+            ("leave_encashment", "Indemnité de congés"),
+        ]);
+
+        registry
+    }
+
+    /// Add or replace texts for `other_deductions` lines in a language
+    pub fn insert_lines(&mut self, language: &str, lines: &[(&str, &str)]) {
+        let entry = self.lines.entry(language.to_string()).or_default();
+        for (key, text) in lines {
+            entry.insert(key.to_string(), text.to_string());
+        }
+    }
+
+    /// Text for the `other_deductions` line `key`, falling back like
+    /// `label` and then to the key itself
+    pub fn line_label<'a>(&'a self, language: &str, key: &'a str) -> &'a str {
+        Self::fallback_chain(language)
+            .into_iter()
+            .find_map(|lang| self.lines.get(lang).and_then(|lines| lines.get(key)))
+            .map_or(key, String::as_str)
+    }
+
+    /// Add or replace labels for a language; labels not given keep falling back
+    pub fn insert_language(&mut self, language: &str, labels: &[(PayslipLabel, &str)]) {
+        let entry = self.labels.entry(language.to_string()).or_default();
+        for (label, text) in labels {
+            entry.insert(*label, text.to_string());
+        }
+    }
+
+    pub fn supports(&self, language: &str) -> bool {
+        self.labels.contains_key(language)
+    }
+
+    /// Languages tried for `language`, most specific first
+    fn fallback_chain(language: &str) -> Vec<&str> {
+        let mut chain = vec![language];
+        if let Some((base, _)) = language.split_once(['-', '_']) {
+            chain.push(base);
+        }
+        chain.push(DEFAULT_LANGUAGE);
+        chain
+    }
+
+    pub fn label(&self, language: &str, label: PayslipLabel) -> &str {
+        Self::fallback_chain(language)
+            .into_iter()
+            .find_map(|lang| self.labels.get(lang).and_then(|labels| labels.get(&label)))
+            .map(String::as_str)
+            .expect("every payslip label has an English text")
+    }
+}
+
+impl Default for PayslipLabels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Payslip as two-column CSV (label, amount) in `language`
+///
+/// Deductions include the `other_deductions` lines (UIF, garnishments,
+/// benefits, ...) after the standard ones.
+/// Zero earnings and deductions are left out; gross, total deductions,
+/// and net pay are always shown. Amounts are plain decimals for machine
+/// reading; see `render_localized_payslip_csv` for display.
+pub fn render_payslip_csv(item: &PayrollItem, period: &str, labels: &PayslipLabels, language: &str) -> String {
+    render_csv(item, period, labels, language, |amount| amount.to_string())
+}
+
+/// Payslip CSV for people in `locale` ("fr-CI"): labels in its language,
+/// the period as local dates, and amounts grouped the local way
+pub fn render_localized_payslip_csv(
+    item: &PayrollItem,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    labels: &PayslipLabels,
+    formatter: &LocaleFormatter,
+    locale: &str,
+    decimal_places: u32,
+) -> String {
+    let period = format!("{} - {}", formatter.format_date(locale, period_start), formatter.format_date(locale, period_end));
+    let language = locale.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+    render_csv(item, &period, labels, &language, |amount| formatter.format_amount(locale, amount, decimal_places))
+}
+
+fn render_csv(
+    item: &PayrollItem,
+    period: &str,
+    labels: &PayslipLabels,
+    language: &str,
+    format_amount: impl Fn(Decimal) -> String,
+) -> String {
+    use PayslipLabel::*;
+
+    let optional: [(PayslipLabel, Decimal); 10] = [
+        (BasicSalary, item.basic_salary),
+        (HousingAllowance, item.housing_allowance),
+        (TransportAllowance, item.transport_allowance),
+        (MealAllowance, item.meal_allowance),
+        (UtilityAllowance, item.utility_allowance),
+        (IncomeTax, item.paye_tax),
+        (PensionEmployee, item.pension_employee),
+        (HousingFund, item.nhf_deduction),
+        (LoanRepayment, item.loan_repayment),
+        (PensionEmployer, item.pension_employer),
+    ];
+    let (earnings, deductions) = optional.split_at(5);
+    let (deductions, employer) = deductions.split_at(4);
+
+    let other = item.other_deduction_amounts();
+    let label = |l| labels.label(language, l);
+    let labelled = |rows: &[(PayslipLabel, Decimal)]| -> Vec<(&str, Decimal)> {
+        rows.iter().filter(|(_, amount)| !amount.is_zero()).map(|(l, amount)| (label(*l), *amount)).collect()
+    };
+
+    let mut rows: Vec<(&str, Decimal)> = labelled(earnings);
+    rows.push((label(GrossPay), item.gross_pay));
+    rows.extend(labelled(deductions));
+    rows.extend(
+        other
+            .iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(key, amount)| (labels.line_label(language, key), *amount)),
+    );
+    rows.push((label(TotalDeductions), item.total_deductions));
+    rows.push((label(NetPay), item.net_pay));
+    rows.extend(labelled(employer));
+
+    let mut out = format!("{},{}\n", csv_field(label(Payslip)), csv_field(period));
+    out.push_str(&format!("{},{}\n", csv_field(label(Description)), csv_field(label(Amount))));
+    for (text, amount) in rows {
+        out.push_str(&format!("{},{}\n", csv_field(text), csv_field(&format_amount(amount))));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    fn item() -> PayrollItem {
+        PayrollItem {
+            id: Uuid::new_v4(),
+            payroll_run_id: Uuid::new_v4(),
+            employee_id: Uuid::new_v4(),
+            basic_salary: dec!(250000),
+            housing_allowance: dec!(100000),
+            transport_allowance: Decimal::ZERO,
+            meal_allowance: Decimal::ZERO,
+            utility_allowance: Decimal::ZERO,
+            other_allowances: serde_json::json!({}),
+            gross_pay: dec!(350000),
+            paye_tax: dec!(40000),
+            pension_employee: dec!(28000),
+            pension_employer: dec!(35000),
+            nhf_deduction: Decimal::ZERO,
+            loan_repayment: Decimal::ZERO,
+            other_deductions: serde_json::json!({}),
+            total_deductions: dec!(68000),
+            net_pay: dec!(282000),
+            bank_name: None,
+            account_number: None,
+            account_name: None,
+            department_id: None,
+            employer_contributions: Default::default(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_every_label_has_english() {
+        let labels = PayslipLabels::new();
+        for label in PayslipLabel::ALL {
+            assert!(!labels.label("xx", label).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_french_payslip() {
+        let csv = render_payslip_csv(&item(), "Janvier 2024", &PayslipLabels::new(), "fr");
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "Bulletin de paie,Janvier 2024");
+        assert_eq!(lines[1], "Libellé,Montant");
+        assert_eq!(lines[2], "Salaire de base,250000");
+        assert_eq!(lines[4], "Salaire brut,350000");
+        assert!(lines.contains(&"Impôt sur le revenu,40000"));
+        assert!(lines.contains(&"Net à payer,282000"));
+        assert_eq!(lines.last(), Some(&"Retraite (employeur),35000"));
+    }
+
+    #[test]
+    fn test_portuguese_payslip_with_regional_fallback() {
+        let labels = PayslipLabels::new();
+        let csv = render_payslip_csv(&item(), "Janeiro 2024", &labels, "pt-BR");
+
+        // Regional label, then base-language labels for the rest
+        assert!(csv.starts_with("Holerite,Janeiro 2024\nDescrição,Valor\n"));
+        assert!(csv.contains("Salário bruto,350000\n"));
+        assert!(csv.contains("Salário líquido,282000\n"));
+
+        assert_eq!(labels.label("pt", PayslipLabel::Payslip), "Recibo de vencimento");
+    }
+
+    #[test]
+    fn test_unsupported_language_falls_back_to_english() {
+        let labels = PayslipLabels::new();
+        assert!(!labels.supports("ja"));
+        let csv = render_payslip_csv(&item(), "Jan 2024", &labels, "ja");
+        assert!(csv.starts_with("Payslip,Jan 2024\nDescription,Amount\nBasic Salary,250000\n"));
+
+        // Partial translations fall back per label rather than blanking
+        assert_eq!(labels.label("sw", PayslipLabel::NetPay), "Mshahara halisi");
+        assert_eq!(labels.label("sw", PayslipLabel::HousingFund), "Housing Fund");
+    }
+
+    #[test]
+    fn test_other_deductions_listed_before_total() {
+        let item = PayrollItem {
+            other_deductions: serde_json::json!({
+                "uif": "177.12",
+                "garnishment": "5000",
+                "recurring": { "Union dues": "1500", "Cooperative": "2500" },
+                "benefits": [{ "employee_amount": "3000", "employer_amount": "6000" }],
+                "salary_advance": "0",
+            }),
+            total_deductions: dec!(80177.12),
+            net_pay: dec!(269822.88),
+            ..item()
+        };
+        let csv = render_payslip_csv(&item, "June 2024", &PayslipLabels::new(), "en");
+        let lines: Vec<&str> = csv.lines().collect();
+        let at = |line| lines.iter().position(|l| *l == line).unwrap_or_else(|| panic!("no line {:?} in\n{}", line, csv));
+
+        for line in ["UIF,177.12", "Garnishment,5000", "Recurring Deductions,4000", "Benefits,3000"] {
+            assert!(at(line) > at("Pension (Employee),28000") && at(line) < at("Total Deductions,80177.12"));
+        }
+        assert!(!csv.contains("Salary Advance"));
+
+        let labels = PayslipLabels::new();
+        assert_eq!(labels.line_label("fr-CI", "garnishment"), "Saisie sur salaire");
+        assert_eq!(labels.line_label("fr", "uif"), "UIF");
+        assert_eq!(labels.line_label("en", "medical_aid"), "medical_aid");
+    }
+
+    #[test]
+    fn test_localized_payslip_amounts_and_period() {
+        let (labels, formatter) = (PayslipLabels::new(), LocaleFormatter::new());
+        let (start, end) = (NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2024, 1, 31).unwrap());
+
+        let csv = render_localized_payslip_csv(&item(), start, end, &labels, &formatter, "en-NG", 2);
+        assert!(csv.starts_with("Payslip,01/01/2024 - 31/01/2024\n"));
+        assert!(csv.contains("Net Pay,\"282,000.00\"\n"));
+
+        let csv = render_localized_payslip_csv(&item(), start, end, &labels, &formatter, "fr-CI", 0);
+        assert!(csv.starts_with("Bulletin de paie,01/01/2024 - 31/01/2024\n"));
+        assert!(csv.contains("Net à payer,282\u{202f}000\n"));
+
+        let csv = render_localized_payslip_csv(&item(), start, end, &labels, &formatter, "de-CH", 2);
+        assert!(csv.starts_with("Payslip,01.01.2024 - 31.01.2024\n"));
+        assert!(csv.contains("Net Pay,282'000.00\n"));
+    }
+}
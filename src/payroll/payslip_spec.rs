@@ -0,0 +1,213 @@
+//! Statutory Payslip Lines
+//!
+//! Some countries prescribe items a payslip must show whatever their
+//! amount: South Africa's UIF and SDL, Brazil's INSS and FGTS, Japan's
+//! residence tax. The generic payslip drops zero lines and only knows the
+//! `PayrollItem` fields, so each country's result struct lists its own
+//! statutory lines and a `PayslipSpec` names the ones the law requires.
+//! Rendering refuses a payslip that is missing any of them.
+
+use std::collections::HashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::developed_asia::JapanPayrollResult;
+use super::{south_africa, south_america};
+
+/// A statutory amount on a payslip
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatutoryLine {
+    /// Stable code the spec refers to, e.g. "UIF_EMPLOYEE"
+    pub code: String,
+    /// Name the authority uses
+    pub label: String,
+    pub amount: Decimal,
+    /// Paid by the employer rather than withheld
+    pub employer: bool,
+}
+
+impl StatutoryLine {
+    fn employee(code: &str, label: &str, amount: Decimal) -> Self {
+        Self { code: code.to_string(), label: label.to_string(), amount, employer: false }
+    }
+
+    fn employer(code: &str, label: &str, amount: Decimal) -> Self {
+        Self { employer: true, ..Self::employee(code, label, amount) }
+    }
+}
+
+/// Result structs that know their statutory payslip lines
+pub trait StatutoryLines {
+    fn statutory_lines(&self) -> Vec<StatutoryLine>;
+}
+
+impl StatutoryLines for south_africa::TaxResult {
+    fn statutory_lines(&self) -> Vec<StatutoryLine> {
+        if self.country_code != "ZA" {
+            return vec![StatutoryLine::employee("PAYE", "PAYE", self.monthly_paye)];
+        }
+        vec![
+            StatutoryLine::employee("PAYE", "PAYE", self.monthly_paye),
+            StatutoryLine::employee("UIF_EMPLOYEE", "UIF (Employee)", self.uif_employee),
+            StatutoryLine::employer("UIF_EMPLOYER", "UIF (Employer)", self.uif_employer),
+            StatutoryLine::employer("SDL", "Skills Development Levy", self.sdl),
+        ]
+    }
+}
+
+impl StatutoryLines for south_america::TaxResult {
+    fn statutory_lines(&self) -> Vec<StatutoryLine> {
+        if self.country_code == "BR" {
+            return vec![
+                StatutoryLine::employee("INSS", "INSS", self.inss),
+                StatutoryLine::employee("IRRF", "IRRF", self.income_tax),
+                StatutoryLine::employer("FGTS", "FGTS", self.pension_employer),
+            ];
+        }
+        vec![
+            StatutoryLine::employee("INCOME_TAX", "Income Tax", self.income_tax),
+            StatutoryLine::employee("PENSION_EMPLOYEE", "Pension (Employee)", self.pension_employee),
+            StatutoryLine::employer("PENSION_EMPLOYER", "Pension (Employer)", self.pension_employer),
+        ]
+    }
+}
+
+impl StatutoryLines for JapanPayrollResult {
+    fn statutory_lines(&self) -> Vec<StatutoryLine> {
+        vec![
+            StatutoryLine::employee("SOCIAL_INSURANCE", "健康保険・厚生年金", self.health_pension_employee),
+            StatutoryLine::employee("EMPLOYMENT_INSURANCE", "雇用保険", self.employment_insurance),
+            StatutoryLine::employee("INCOME_TAX", "所得税", self.income_tax),
+            StatutoryLine::employee("RECONSTRUCTION_TAX", "復興特別所得税", self.reconstruction_tax),
+            StatutoryLine::employee("RESIDENCE_TAX", "住民税", self.residence_tax),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PayslipSpecError {
+    #[error("{country_code} payslip is missing required statutory lines: {}", .missing.join(", "))]
+    MissingStatutoryLines { country_code: String, missing: Vec<String> },
+}
+
+/// Lines a country's payslips must show
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayslipSpec {
+    pub country_code: String,
+    /// Statutory line codes, in payslip order
+    pub required: Vec<String>,
+}
+
+impl PayslipSpec {
+    pub fn new(country_code: &str, required: &[&str]) -> Self {
+        Self {
+            country_code: country_code.to_ascii_uppercase(),
+            required: required.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    pub fn validate(&self, lines: &[StatutoryLine]) -> Result<(), PayslipSpecError> {
+        let missing: Vec<String> =
+            self.required.iter().filter(|code| !lines.iter().any(|l| &l.code == *code)).cloned().collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(PayslipSpecError::MissingStatutoryLines { country_code: self.country_code.clone(), missing })
+        }
+    }
+
+    /// Statutory section of the payslip as CSV (label, amount). Required
+    /// lines come first in spec order and are shown even when zero; other
+    /// non-zero lines follow.
+    pub fn render_csv(&self, lines: &[StatutoryLine]) -> Result<String, PayslipSpecError> {
+        self.validate(lines)?;
+        let mut out = String::new();
+        let required = self.required.iter().filter_map(|code| lines.iter().find(|l| &l.code == code));
+        let extra = lines.iter().filter(|l| !self.required.contains(&l.code) && !l.amount.is_zero());
+        for line in required.chain(extra) {
+            out.push_str(&format!("{},{}\n", line.label, line.amount.round_dp(2)));
+        }
+        Ok(out)
+    }
+}
+
+/// Payslip specs by country
+#[derive(Debug, Clone)]
+pub struct PayslipSpecs {
+    specs: HashMap<String, PayslipSpec>,
+}
+
+impl PayslipSpecs {
+    pub fn new() -> Self {
+        let mut registry = Self { specs: HashMap::new() };
+        registry.set(PayslipSpec::new("ZA", &["PAYE", "UIF_EMPLOYEE", "UIF_EMPLOYER", "SDL"]));
+        registry.set(PayslipSpec::new("BR", &["INSS", "IRRF", "FGTS"]));
+        registry.set(PayslipSpec::new("JP", &["SOCIAL_INSURANCE", "EMPLOYMENT_INSURANCE", "INCOME_TAX", "RESIDENCE_TAX"]));
+        registry
+    }
+
+    pub fn set(&mut self, spec: PayslipSpec) {
+        self.specs.insert(spec.country_code.clone(), spec);
+    }
+
+    /// Countries without a spec require no statutory lines
+    pub fn for_country(&self, country_code: &str) -> PayslipSpec {
+        self.specs
+            .get(&country_code.to_ascii_uppercase())
+            .cloned()
+            .unwrap_or_else(|| PayslipSpec::new(country_code, &[]))
+    }
+}
+
+impl Default for PayslipSpecs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payroll::{BrazilTaxCalculator, SouthAfricaTaxCalculator};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_south_africa_payslip_shows_uif_and_sdl() {
+        let result = SouthAfricaTaxCalculator::new().calculate(dec!(40000), 35);
+        let spec = PayslipSpecs::new().for_country("za");
+
+        let csv = spec.render_csv(&result.statutory_lines()).unwrap();
+        let labels: Vec<&str> = csv.lines().map(|l| l.split(',').next().unwrap()).collect();
+        assert_eq!(labels, ["PAYE", "UIF (Employee)", "UIF (Employer)", "Skills Development Levy"]);
+        assert!(csv.contains("UIF (Employee),177.12\n"));
+
+        // Lines built only from the generic payslip fields fail the check
+        let generic = vec![StatutoryLine::employee("PAYE", "PAYE", result.monthly_paye)];
+        assert_eq!(
+            spec.validate(&generic),
+            Err(PayslipSpecError::MissingStatutoryLines {
+                country_code: "ZA".to_string(),
+                missing: vec!["UIF_EMPLOYEE".to_string(), "UIF_EMPLOYER".to_string(), "SDL".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_brazil_payslip_shows_inss_and_fgts() {
+        // Below the IRRF exemption, so IRRF is zero but still shown
+        let result = BrazilTaxCalculator::new().calculate(dec!(2000), 0);
+        let spec = PayslipSpecs::new().for_country("BR");
+
+        let csv = spec.render_csv(&result.statutory_lines()).unwrap();
+        assert!(csv.starts_with("INSS,"));
+        assert!(csv.contains("IRRF,0"));
+        assert!(csv.contains("FGTS,160.00\n"));
+
+        let without_fgts: Vec<StatutoryLine> =
+            result.statutory_lines().into_iter().filter(|l| l.code != "FGTS").collect();
+        assert!(spec.render_csv(&without_fgts).is_err());
+        // No spec, nothing required
+        assert!(PayslipSpecs::new().for_country("KE").validate(&[]).is_ok());
+    }
+}
@@ -0,0 +1,127 @@
+//! Pre-flight Checks
+//!
+//! Anomalies an approver should look at before a run is approved and paid:
+//! net pay at or below zero or under the net pay floor, net pay that moved
+//! sharply since the employee's previous run, bank details the payment file
+//! can't use, and employees left out because their country has no
+//! calculator. Findings are
+//! tagged by severity; errors would pay someone wrongly or not at all.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::models::{EmployeeSalary, PayrollItem};
+use super::net_pay_floor::NetPayFloorOutcome;
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreflightSeverity {
+    /// Worth a look; may well be intended
+    Warning,
+    /// Would pay the employee wrongly or not at all
+    Error,
+}
+
+/// What a finding is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreflightCheck {
+    NonPositiveNetPay,
+    BelowNetPayFloor,
+    NetPayChange,
+    MissingBankDetails,
+    UnsupportedCountry,
+}
+
+/// One anomaly found in a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreflightFinding {
+    pub severity: PreflightSeverity,
+    pub check: PreflightCheck,
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub message: String,
+}
+
+/// Thresholds for the pre-flight checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreflightRules {
+    /// Net pay change against the previous run, as a fraction, above which
+    /// it is flagged
+    pub max_net_pay_change: Decimal,
+}
+
+impl Default for PreflightRules {
+    fn default() -> Self {
+        Self { max_net_pay_change: dec!(0.25) }
+    }
+}
+
+impl PreflightRules {
+    /// Findings for one employee's payslip, compared with their net pay in
+    /// the previous run when there was one
+    pub fn check_item(&self, employee: &EmployeeSalary, item: &PayrollItem, previous_net: Option<Decimal>) -> Vec<PreflightFinding> {
+        let finding = |severity, check, message: String| PreflightFinding {
+            severity,
+            check,
+            employee_id: item.employee_id,
+            employee_name: employee.employee_name.clone(),
+            message,
+        };
+        let mut findings = Vec::new();
+
+        if item.net_pay <= Decimal::ZERO {
+            findings.push(finding(
+                PreflightSeverity::Error,
+                PreflightCheck::NonPositiveNetPay,
+                format!("net pay is {}", item.net_pay),
+            ));
+        }
+        if let Some(previous) = previous_net.filter(|p| !p.is_zero()) {
+            let change = (item.net_pay - previous) / previous.abs();
+            if change.abs() > self.max_net_pay_change {
+                findings.push(finding(
+                    PreflightSeverity::Warning,
+                    PreflightCheck::NetPayChange,
+                    format!("net pay changed {}% from {} to {}", (change * dec!(100)).round_dp(1), previous, item.net_pay),
+                ));
+            }
+        }
+        let blank = |field: &Option<String>| field.as_deref().is_none_or(|value| value.trim().is_empty());
+        if blank(&item.bank_name) || blank(&item.account_number) {
+            findings.push(finding(
+                PreflightSeverity::Error,
+                PreflightCheck::MissingBankDetails,
+                "bank name or account number is missing".to_string(),
+            ));
+        }
+
+        findings
+    }
+
+    /// Finding for take-home left under the net pay floor by deductions that
+    /// can't be cut back
+    pub fn below_net_pay_floor(&self, employee: &EmployeeSalary, outcome: &NetPayFloorOutcome) -> Option<PreflightFinding> {
+        (outcome.shortfall > Decimal::ZERO).then(|| PreflightFinding {
+            severity: PreflightSeverity::Warning,
+            check: PreflightCheck::BelowNetPayFloor,
+            employee_id: employee.employee_id,
+            employee_name: employee.employee_name.clone(),
+            message: format!("net pay {} is {} under the floor of {}", outcome.net_pay, outcome.shortfall, outcome.floor),
+        })
+    }
+
+    /// Finding for an employee skipped because their country isn't supported
+    pub fn unsupported_country(&self, employee: &EmployeeSalary) -> PreflightFinding {
+        PreflightFinding {
+            severity: PreflightSeverity::Error,
+            check: PreflightCheck::UnsupportedCountry,
+            employee_id: employee.employee_id,
+            employee_name: employee.employee_name.clone(),
+            message: format!("no payroll calculator for {}; employee is not in the run", employee.country_code),
+        }
+    }
+}
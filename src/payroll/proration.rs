@@ -0,0 +1,59 @@
+//! Period Proration
+//!
+//! Fraction of a pay period something was in effect, on a calendar-day
+//! basis with both ends inclusive. Salary, allowances, and benefit
+//! contributions that start or stop mid-period all use this so the
+//! fractions on a payslip agree with each other.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+/// Calendar days in `start..=end`; zero when `end` is before `start`
+pub fn inclusive_days(start: NaiveDate, end: NaiveDate) -> i64 {
+    ((end - start).num_days() + 1).max(0)
+}
+
+/// Fraction of `period_start..=period_end` covered by `active_from..=active_until`
+///
+/// `None` for `active_until` means open-ended. Returns one for full
+/// coverage and zero when the ranges don't overlap.
+pub fn active_fraction(
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    active_from: NaiveDate,
+    active_until: Option<NaiveDate>,
+) -> Decimal {
+    let period_days = inclusive_days(period_start, period_end);
+    if period_days == 0 {
+        return Decimal::ZERO;
+    }
+
+    let from = active_from.max(period_start);
+    let until = active_until.map_or(period_end, |until| until.min(period_end));
+    let active_days = inclusive_days(from, until);
+    if active_days >= period_days {
+        return Decimal::ONE;
+    }
+    Decimal::from(active_days) / Decimal::from(period_days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn d(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_active_fraction() {
+        // April has 30 days
+        assert_eq!(active_fraction(d(4, 1), d(4, 30), d(1, 1), None), Decimal::ONE);
+        assert_eq!(active_fraction(d(4, 1), d(4, 30), d(4, 16), None), dec!(0.5));
+        assert_eq!(active_fraction(d(4, 1), d(4, 30), d(3, 1), Some(d(4, 15))), dec!(0.5));
+        assert_eq!(active_fraction(d(4, 1), d(4, 30), d(4, 11), Some(d(4, 20))), dec!(10) / dec!(30));
+        assert_eq!(active_fraction(d(4, 1), d(4, 30), d(5, 1), None), Decimal::ZERO);
+        assert_eq!(active_fraction(d(4, 1), d(4, 30), d(1, 1), Some(d(3, 31))), Decimal::ZERO);
+    }
+}
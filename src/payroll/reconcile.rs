@@ -0,0 +1,78 @@
+//! Gross-to-Net Reconciliation
+//!
+//! Every payslip must satisfy gross = net + deductions, where deductions
+//! are the itemised lines (tax, pension, NHF, loans, and everything under
+//! `other_deductions`) rather than the stored total. A line off by more
+//! than the tolerance points at a calculator or posting bug; so does a run
+//! whose aggregate is off even when each line is within tolerance.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::models::PayrollItem;
+use super::tax_override::TaxOverride;
+
+/// A payslip, or the run as a whole, that does not reconcile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReconciliationDiscrepancy {
+    /// `None` for the run aggregate
+    pub employee_id: Option<Uuid>,
+    pub gross_pay: Decimal,
+    pub net_pay: Decimal,
+    pub deductions: Decimal,
+    /// gross - (net + deductions)
+    pub delta: Decimal,
+}
+
+/// Gross-to-net check for one run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub payroll_run_id: Uuid,
+    pub tolerance: Decimal,
+    pub total_gross: Decimal,
+    pub total_net: Decimal,
+    pub total_deductions: Decimal,
+    pub discrepancies: Vec<ReconciliationDiscrepancy>,
+    /// Manual tax overrides applied to the run's payslips
+    #[serde(default)]
+    pub tax_overrides: Vec<TaxOverride>,
+}
+
+impl ReconciliationReport {
+    pub fn is_reconciled(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+/// Check each payslip within `tolerance`, and the run total within
+/// `tolerance` per payslip
+pub fn reconcile(payroll_run_id: Uuid, items: &[PayrollItem], tolerance: Decimal) -> ReconciliationReport {
+    let check = |employee_id, gross_pay: Decimal, net_pay: Decimal, deductions: Decimal, tolerance: Decimal| {
+        let delta = gross_pay - net_pay - deductions;
+        (delta.abs() > tolerance).then_some(ReconciliationDiscrepancy { employee_id, gross_pay, net_pay, deductions, delta })
+    };
+
+    let mut discrepancies: Vec<ReconciliationDiscrepancy> = items
+        .iter()
+        .filter_map(|item| {
+            check(Some(item.employee_id), item.gross_pay, item.net_pay, item.itemised_deductions(), tolerance)
+        })
+        .collect();
+
+    let total_gross: Decimal = items.iter().map(|i| i.gross_pay).sum();
+    let total_net: Decimal = items.iter().map(|i| i.net_pay).sum();
+    let total_deductions: Decimal = items.iter().map(PayrollItem::itemised_deductions).sum();
+    let run_tolerance = tolerance * Decimal::from(items.len().max(1));
+    discrepancies.extend(check(None, total_gross, total_net, total_deductions, run_tolerance));
+
+    ReconciliationReport {
+        payroll_run_id,
+        tolerance,
+        total_gross,
+        total_net,
+        total_deductions,
+        discrepancies,
+        tax_overrides: Vec::new(),
+    }
+}
@@ -0,0 +1,130 @@
+//! Recurring Deductions
+//!
+//! Standing deductions an employee has agreed to, such as union dues,
+//! payroll giving, or parking, taken every run between their start and end
+//! dates. Each is a flat amount or a percentage of gross pay. Pre-tax
+//! deductions come off pay before income tax is worked out; post-tax ones
+//! are voluntary and rank after every repayment, so they only take what the
+//! protected earnings floor leaves once garnishments, loans, and advances
+//! are paid.
+
+use std::sync::Arc;
+use chrono::NaiveDate;
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Payslip line for recurring deductions in `other_deductions`
+pub const RECURRING_DEDUCTION_LINE: &str = "recurring";
+
+/// How much a recurring deduction takes each run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "value")]
+pub enum DeductionAmount {
+    Flat(Decimal),
+    /// Percent of gross pay, e.g. 1.5 for 1.5%
+    PercentOfGross(Decimal),
+}
+
+impl DeductionAmount {
+    pub fn for_gross(&self, gross_pay: Decimal) -> Decimal {
+        match self {
+            DeductionAmount::Flat(amount) => *amount,
+            DeductionAmount::PercentOfGross(percent) => gross_pay * percent / Decimal::ONE_HUNDRED,
+        }
+    }
+}
+
+/// A standing deduction taken every run within its active window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecurringDeduction {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub name: String,
+    pub amount: DeductionAmount,
+    /// Taken before income tax rather than from net pay
+    pub pre_tax: bool,
+    pub active_from: NaiveDate,
+    /// Last day it applies; open-ended when `None`
+    pub active_to: Option<NaiveDate>,
+}
+
+impl RecurringDeduction {
+    pub fn new(employee_id: Uuid, name: impl Into<String>, amount: DeductionAmount, pre_tax: bool, active_from: NaiveDate) -> Self {
+        Self { id: Uuid::new_v4(), employee_id, name: name.into(), amount, pre_tax, active_from, active_to: None }
+    }
+
+    pub fn until(mut self, active_to: NaiveDate) -> Self {
+        self.active_to = Some(active_to);
+        self
+    }
+
+    /// Applies to a run whose period overlaps the active window
+    pub fn is_active(&self, period_start: NaiveDate, period_end: NaiveDate) -> bool {
+        self.active_from <= period_end && self.active_to.is_none_or(|to| to >= period_start)
+    }
+}
+
+/// Recurring deductions per employee
+#[derive(Debug, Clone, Default)]
+pub struct RecurringDeductions {
+    // In real implementation, backed by the recurring_deductions table
+    deductions: Arc<DashMap<Uuid, Vec<RecurringDeduction>>>,
+}
+
+impl RecurringDeductions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&self, deduction: RecurringDeduction) {
+        self.deductions.entry(deduction.employee_id).or_default().push(deduction);
+    }
+
+    pub fn for_employee(&self, employee_id: Uuid) -> Vec<RecurringDeduction> {
+        self.deductions.get(&employee_id).map(|d| d.clone()).unwrap_or_default()
+    }
+
+    /// Deductions applying to a period, pre-tax or post-tax, in the order
+    /// they were added
+    pub fn active(&self, employee_id: Uuid, period_start: NaiveDate, period_end: NaiveDate, pre_tax: bool) -> Vec<RecurringDeduction> {
+        self.deductions
+            .get(&employee_id)
+            .map(|all| {
+                all.iter().filter(|d| d.pre_tax == pre_tax && d.is_active(period_start, period_end)).cloned().collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn d(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_active_window_and_amount() {
+        let employee_id = Uuid::new_v4();
+        let dues = RecurringDeduction::new(employee_id, "Union dues", DeductionAmount::PercentOfGross(dec!(1.5)), false, d(3, 15))
+            .until(d(5, 31));
+
+        assert!(!dues.is_active(d(2, 1), d(2, 29)));
+        assert!(dues.is_active(d(3, 1), d(3, 31)));
+        assert!(dues.is_active(d(5, 1), d(5, 31)));
+        assert!(!dues.is_active(d(6, 1), d(6, 30)));
+        assert_eq!(dues.amount.for_gross(dec!(400_000)), dec!(6000));
+        assert_eq!(DeductionAmount::Flat(dec!(2500)).for_gross(dec!(400_000)), dec!(2500));
+
+        let store = RecurringDeductions::new();
+        store.add(dues);
+        store.add(RecurringDeduction::new(employee_id, "Pension top-up", DeductionAmount::Flat(dec!(10_000)), true, d(1, 1)));
+        assert_eq!(store.active(employee_id, d(4, 1), d(4, 30), false).len(), 1);
+        assert_eq!(store.active(employee_id, d(4, 1), d(4, 30), true)[0].name, "Pension top-up");
+        assert!(store.active(employee_id, d(6, 1), d(6, 30), false).is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+//! Payroll Registry
+//!
+//! Aggregates the regional tax-engine registries into a single source of
+//! truth for payroll coverage.
+
+use std::collections::{BTreeMap, BTreeSet};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AsiaPacificRegistry, CentralEasternEuropeRegistry, DevelopedAsiaRegistry,
+    EasternEuropeNonEuRegistry, MiddleEastRegistry, NorthAmericaRegistry,
+    SouthAmericaRegistry, SouthernAfricaRegistry, SouthernEuropeRegistry,
+    WestAfricaTaxRegistry, WesternEuropeExtendedRegistry,
+};
+
+/// (code, name, currency) as listed by each regional registry
+type CountryEntry = (&'static str, &'static str, &'static str);
+
+/// Shape of a country's income tax schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxStructure {
+    Progressive,
+    Flat,
+    NoIncomeTax,
+}
+
+/// Payroll capabilities for a country
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountryCapabilities {
+    pub tax_structure: TaxStructure,
+    pub has_13th_salary: bool,
+    pub uses_sepa: bool,
+}
+
+/// Supported country
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountryInfo {
+    pub code: String,
+    pub name: String,
+    pub currency: String,
+    pub region: String,
+    pub capabilities: CountryCapabilities,
+}
+
+/// Top-level payroll registry
+pub struct PayrollRegistry;
+
+impl PayrollRegistry {
+    /// (region, code, name, currency) for every regional registry entry
+    pub fn regional_entries() -> Vec<(&'static str, &'static str, &'static str, &'static str)> {
+        let regions: [(&'static str, Vec<CountryEntry>); 11] = [
+            ("west_africa", WestAfricaTaxRegistry::supported_countries()),
+            ("southern_africa", SouthernAfricaRegistry::supported_countries()),
+            ("south_america", SouthAmericaRegistry::supported_countries()),
+            ("north_america", NorthAmericaRegistry::supported_countries()),
+            ("middle_east", MiddleEastRegistry::supported_countries()),
+            ("western_europe", WesternEuropeExtendedRegistry::supported_countries()),
+            ("southern_europe", SouthernEuropeRegistry::supported_countries()),
+            ("central_eastern_europe", CentralEasternEuropeRegistry::supported_countries()),
+            ("europe_east_non_eu", EasternEuropeNonEuRegistry::supported_countries()),
+            ("developed_asia", DevelopedAsiaRegistry::supported_countries()),
+            ("asia_pacific", AsiaPacificRegistry::supported_countries()),
+        ];
+
+        regions
+            .into_iter()
+            .flat_map(|(region, countries)| {
+                countries.into_iter().map(move |(code, name, currency)| (region, code, name, currency))
+            })
+            .collect()
+    }
+
+    /// Every supported country, sorted by code. If two regions claim the
+    /// same code the first region listed wins; see `duplicate_codes`.
+    pub fn all_countries() -> Vec<CountryInfo> {
+        let mut countries: BTreeMap<&'static str, CountryInfo> = BTreeMap::new();
+
+        for (region, code, name, currency) in Self::regional_entries() {
+            countries.entry(code).or_insert_with(|| CountryInfo {
+                code: code.to_string(),
+                name: name.to_string(),
+                currency: currency.to_string(),
+                region: region.to_string(),
+                capabilities: Self::capabilities(region, code),
+            });
+        }
+
+        countries.into_values().collect()
+    }
+
+    /// Country codes claimed by more than one region, with the regions claiming them
+    pub fn duplicate_codes() -> Vec<(&'static str, Vec<&'static str>)> {
+        let mut claims: BTreeMap<&'static str, Vec<&'static str>> = BTreeMap::new();
+        for (region, code, _, _) in Self::regional_entries() {
+            claims.entry(code).or_default().push(region);
+        }
+        claims.into_iter().filter(|(_, regions)| regions.len() > 1).collect()
+    }
+
+    /// Distinct ISO country codes with a tax engine
+    pub fn country_codes() -> BTreeSet<&'static str> {
+        Self::regional_entries().into_iter().map(|(_, code, _, _)| code).collect()
+    }
+
+    /// ISO currency a country's payroll is run in
+    pub fn currency_for(country_code: &str) -> Option<&'static str> {
+        Self::regional_entries()
+            .into_iter()
+            .find(|(_, code, _, _)| code.eq_ignore_ascii_case(country_code))
+            .map(|(_, _, _, currency)| currency)
+    }
+
+    pub fn country_count() -> usize {
+        Self::country_codes().len()
+    }
+
+    fn capabilities(region: &str, code: &str) -> CountryCapabilities {
+        let tax_structure = match region {
+            "middle_east" if MiddleEastRegistry::is_gcc(code) => TaxStructure::NoIncomeTax,
+            "central_eastern_europe" if CentralEasternEuropeRegistry::has_flat_tax(code) => TaxStructure::Flat,
+            "europe_east_non_eu" if EasternEuropeNonEuRegistry::has_flat_tax(code) => TaxStructure::Flat,
+            _ => TaxStructure::Progressive,
+        };
+
+        let has_13th_salary = match region {
+            "south_america" => SouthAmericaRegistry::has_thirteenth_salary(code),
+            // Sonderzahlungen, tredicesima, pagas extra, subsídio de Natal, 13th-month pay law
+            _ => matches!(code, "AT" | "IT" | "ES" | "PT" | "GR" | "PH"),
+        };
+
+        let uses_sepa = match region {
+            "western_europe" => WesternEuropeExtendedRegistry::uses_sepa(code),
+            "southern_europe" => SouthernEuropeRegistry::uses_sepa(code),
+            "central_eastern_europe" => CentralEasternEuropeRegistry::uses_sepa(code),
+            _ => false,
+        };
+
+        CountryCapabilities { tax_structure, has_13th_salary, uses_sepa }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_region_claims_the_same_country() {
+        let duplicates = PayrollRegistry::duplicate_codes();
+        assert!(duplicates.is_empty(), "countries claimed by several regions: {:?}", duplicates);
+    }
+
+    #[test]
+    fn test_all_countries() {
+        let countries = PayrollRegistry::all_countries();
+        let codes: BTreeSet<&str> = countries.iter().map(|c| c.code.as_str()).collect();
+        assert_eq!(codes.len(), countries.len());
+        assert_eq!(countries.len(), PayrollRegistry::country_count());
+        for code in ["CH", "NG", "JP", "US"] {
+            assert!(codes.contains(code), "missing {}", code);
+        }
+
+        let find = |code: &str| countries.iter().find(|c| c.code == code).unwrap();
+        assert!(find("CH").capabilities.uses_sepa);
+        assert_eq!(find("AE").capabilities.tax_structure, TaxStructure::NoIncomeTax);
+        assert_eq!(find("EE").capabilities.tax_structure, TaxStructure::Flat);
+        assert!(find("BR").capabilities.has_13th_salary);
+        assert_eq!(find("JP").currency, "JPY");
+    }
+}
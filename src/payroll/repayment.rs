@@ -0,0 +1,251 @@
+//! Repayment Schedules
+//!
+//! Loans and garnishments recovered from net pay over several runs. Each
+//! run deducts the per-period amount (only the residual in the last period)
+//! until nothing remains, then the schedule stops on its own.
+//!
+//! Deductions are taken in priority order, court-ordered garnishments
+//! before employer loans, and never take net pay below the protected
+//! earnings floor; whatever does not fit is simply recovered later. What a
+//! run deducted is recorded against that run, so recalculating a draft run
+//! replaces its earlier deduction instead of taking a second one.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Payslip line for garnishments in `other_deductions`
+pub const GARNISHMENT_LINE: &str = "garnishment";
+
+/// What is being recovered, in deduction priority order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepaymentKind {
+    /// Court or agency order; taken first
+    Garnishment,
+    /// Employer loan
+    Loan,
+    /// Off-cycle salary advance
+    SalaryAdvance,
+    /// Overpayment a clawback couldn't recover in its own run
+    Clawback,
+}
+
+/// A balance recovered over several pay periods
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepaymentSchedule {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub kind: RepaymentKind,
+    pub principal: Decimal,
+    pub per_period: Decimal,
+    pub remaining: Decimal,
+    /// Amount taken by each payroll run
+    #[serde(default)]
+    pub deductions: BTreeMap<Uuid, Decimal>,
+}
+
+impl RepaymentSchedule {
+    pub fn new(employee_id: Uuid, kind: RepaymentKind, principal: Decimal, per_period: Decimal) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            employee_id,
+            kind,
+            principal,
+            per_period,
+            remaining: principal,
+            deductions: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.remaining <= Decimal::ZERO
+    }
+
+    /// What `payroll_run_id` would take before the floor applies: the
+    /// per-period amount, or the residual in the final period
+    pub fn due(&self, payroll_run_id: Uuid) -> Decimal {
+        let outstanding = self.remaining + self.deductions.get(&payroll_run_id).copied().unwrap_or_default();
+        self.per_period.min(outstanding).max(Decimal::ZERO)
+    }
+
+    /// Record what a run deducted, replacing anything it deducted before
+    fn record(&mut self, payroll_run_id: Uuid, amount: Decimal) {
+        if amount.is_zero() {
+            self.deductions.remove(&payroll_run_id);
+        } else {
+            self.deductions.insert(payroll_run_id, amount);
+        }
+        self.remaining = self.principal - self.deductions.values().sum::<Decimal>();
+    }
+}
+
+/// Net pay a run must leave the employee, the greater of a fixed amount
+/// and a share of net pay before repayments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ProtectedEarnings {
+    pub minimum_net: Decimal,
+    pub share_of_net: Decimal,
+}
+
+impl ProtectedEarnings {
+    pub fn floor(&self, net_before_repayments: Decimal) -> Decimal {
+        self.minimum_net.max(net_before_repayments * self.share_of_net)
+    }
+}
+
+/// One schedule's deduction in a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepaymentDeduction {
+    pub schedule_id: Uuid,
+    pub kind: RepaymentKind,
+    pub due: Decimal,
+    pub amount: Decimal,
+}
+
+impl RepaymentDeduction {
+    /// Held back by the protected earnings floor
+    pub fn shortfall(&self) -> Decimal {
+        self.due - self.amount
+    }
+}
+
+/// Repayment schedules per employee
+#[derive(Debug, Clone, Default)]
+pub struct RepaymentSchedules {
+    // In real implementation, backed by the repayment_schedules table
+    schedules: Arc<DashMap<Uuid, Vec<RepaymentSchedule>>>,
+}
+
+impl RepaymentSchedules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&self, schedule: RepaymentSchedule) {
+        self.schedules.entry(schedule.employee_id).or_default().push(schedule);
+    }
+
+    pub fn for_employee(&self, employee_id: Uuid) -> Vec<RepaymentSchedule> {
+        self.schedules.get(&employee_id).map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Drop a schedule, e.g. one a draft run set up before it was recalculated
+    pub fn remove(&self, employee_id: Uuid, schedule_id: Uuid) {
+        if let Some(mut schedules) = self.schedules.get_mut(&employee_id) {
+            schedules.retain(|s| s.id != schedule_id);
+        }
+    }
+
+    /// Take this run's repayments from `net_before_repayments`, highest
+    /// priority first, without going below the protected floor
+    pub fn deduct(
+        &self,
+        payroll_run_id: Uuid,
+        employee_id: Uuid,
+        net_before_repayments: Decimal,
+        protected: &ProtectedEarnings,
+    ) -> Vec<RepaymentDeduction> {
+        let Some(mut schedules) = self.schedules.get_mut(&employee_id) else {
+            return Vec::new();
+        };
+        schedules.sort_by_key(|s| s.kind);
+
+        let mut available = (net_before_repayments - protected.floor(net_before_repayments)).max(Decimal::ZERO);
+        let mut deductions = Vec::new();
+        for schedule in schedules.iter_mut() {
+            let due = schedule.due(payroll_run_id);
+            if due.is_zero() && !schedule.deductions.contains_key(&payroll_run_id) {
+                continue;
+            }
+            let amount = due.min(available);
+            available -= amount;
+            schedule.record(payroll_run_id, amount);
+            deductions.push(RepaymentDeduction { schedule_id: schedule.id, kind: schedule.kind, due, amount });
+        }
+        deductions
+    }
+
+    /// Undo a run's deductions, e.g. when the run is cancelled
+    pub fn release_run(&self, payroll_run_id: Uuid) {
+        for mut schedules in self.schedules.iter_mut() {
+            for schedule in schedules.iter_mut() {
+                if schedule.deductions.contains_key(&payroll_run_id) {
+                    schedule.record(payroll_run_id, Decimal::ZERO);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_repayment_completes_with_residual() {
+        let store = RepaymentSchedules::new();
+        let employee_id = Uuid::new_v4();
+        store.add(RepaymentSchedule::new(employee_id, RepaymentKind::Loan, dec!(100000), dec!(30000)));
+
+        let mut taken = Vec::new();
+        for _ in 0..5 {
+            let run_id = Uuid::new_v4();
+            let amounts: Decimal = store
+                .deduct(run_id, employee_id, dec!(500000), &ProtectedEarnings::default())
+                .iter()
+                .map(|d| d.amount)
+                .sum();
+            taken.push(amounts);
+        }
+
+        assert_eq!(taken, [dec!(30000), dec!(30000), dec!(30000), dec!(10000), dec!(0)]);
+        let schedule = &store.for_employee(employee_id)[0];
+        assert!(schedule.is_complete());
+        assert_eq!(schedule.remaining, Decimal::ZERO);
+        assert_eq!(schedule.deductions.len(), 4);
+    }
+
+    #[test]
+    fn test_recalculating_a_run_replaces_its_deduction() {
+        let store = RepaymentSchedules::new();
+        let employee_id = Uuid::new_v4();
+        store.add(RepaymentSchedule::new(employee_id, RepaymentKind::Loan, dec!(50000), dec!(20000)));
+        let run_id = Uuid::new_v4();
+
+        store.deduct(run_id, employee_id, dec!(500000), &ProtectedEarnings::default());
+        store.deduct(run_id, employee_id, dec!(500000), &ProtectedEarnings::default());
+        assert_eq!(store.for_employee(employee_id)[0].remaining, dec!(30000));
+
+        store.release_run(run_id);
+        assert_eq!(store.for_employee(employee_id)[0].remaining, dec!(50000));
+    }
+
+    #[test]
+    fn test_floor_limits_deduction_in_priority_order() {
+        let store = RepaymentSchedules::new();
+        let employee_id = Uuid::new_v4();
+        store.add(RepaymentSchedule::new(employee_id, RepaymentKind::Loan, dec!(60000), dec!(20000)));
+        store.add(RepaymentSchedule::new(employee_id, RepaymentKind::Garnishment, dec!(90000), dec!(30000)));
+        let protected = ProtectedEarnings { minimum_net: dec!(80000), share_of_net: Decimal::ZERO };
+
+        // 120,000 net leaves 40,000 above the floor: garnishment in full, loan in part
+        let deductions = store.deduct(Uuid::new_v4(), employee_id, dec!(120000), &protected);
+        assert_eq!(deductions[0].kind, RepaymentKind::Garnishment);
+        assert_eq!(deductions[0].amount, dec!(30000));
+        assert_eq!(deductions[1].kind, RepaymentKind::Loan);
+        assert_eq!(deductions[1].amount, dec!(10000));
+        assert_eq!(deductions[1].shortfall(), dec!(10000));
+
+        let loan = store.for_employee(employee_id).into_iter().find(|s| s.kind == RepaymentKind::Loan).unwrap();
+        assert_eq!(loan.remaining, dec!(50000));
+
+        // Net at or under the floor takes nothing
+        let deductions = store.deduct(Uuid::new_v4(), employee_id, dec!(75000), &protected);
+        assert!(deductions.iter().all(|d| d.amount.is_zero()));
+    }
+}
@@ -0,0 +1,219 @@
+//! Payroll Run Repository
+//!
+//! Storage for payroll runs with atomic status transitions. A transition is
+//! a compare-and-set on the current status, so two approvers (or an approve
+//! racing a processing failure) cannot both succeed.
+
+use std::sync::{Arc, Mutex};
+use chrono::Utc;
+use dashmap::DashMap;
+use uuid::Uuid;
+
+use super::models::{PayrollRun, PayrollRunStatus};
+use super::service::PayrollError;
+
+/// Compare-and-set used by the Postgres implementation.
+/// Zero rows returned means the status precondition failed.
+pub const TRANSITION_STATUS_SQL: &str = "\
+UPDATE payroll_runs SET status = $3, updated_at = NOW() \
+WHERE id = $1 AND status = $2 \
+RETURNING *";
+
+/// Payroll run store
+#[derive(Debug, Clone, Default)]
+pub struct PayrollRunRepository {
+    // In real implementation, backed by the payroll_runs table
+    runs: Arc<DashMap<Uuid, PayrollRun>>,
+    // Serializes the overlap check with the insert, as the exclusion
+    // constraint on payroll_runs does
+    create_lock: Arc<Mutex<()>>,
+}
+
+impl PayrollRunRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, run: PayrollRun) {
+        self.runs.insert(run.id, run);
+    }
+
+    /// Insert a new run unless one of the same tenant and legal entity
+    /// already covers any of its days. Failed and cancelled runs paid
+    /// nothing, so they don't block a new run for their period.
+    pub fn create(&self, run: PayrollRun) -> Result<(), PayrollError> {
+        let _guard = self.create_lock.lock().unwrap();
+        let overlapping = self.runs.iter().find(|existing| {
+            existing.tenant_id == run.tenant_id
+                && existing.legal_entity_id == run.legal_entity_id
+                && !matches!(existing.status, PayrollRunStatus::Failed | PayrollRunStatus::Cancelled)
+                && existing.period_start <= run.period_end
+                && run.period_start <= existing.period_end
+        });
+        if let Some(existing) = overlapping {
+            return Err(PayrollError::OverlappingRun(existing.id));
+        }
+        self.runs.insert(run.id, run);
+        Ok(())
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<PayrollRun> {
+        self.runs.get(&id).map(|r| r.clone())
+    }
+
+    /// Runs currently in `status`
+    pub fn with_status(&self, status: PayrollRunStatus) -> Vec<PayrollRun> {
+        self.runs.iter().filter(|r| r.status == status).map(|r| r.clone()).collect()
+    }
+
+    /// Move a run from `expected` to `next`, returning the updated run.
+    ///
+    /// Fails with `InvalidTransition` if the move is not allowed at all and
+    /// with `StatusConflict` if the run is no longer in `expected`.
+    pub fn transition(
+        &self,
+        id: Uuid,
+        expected: PayrollRunStatus,
+        next: PayrollRunStatus,
+    ) -> Result<PayrollRun, PayrollError> {
+        self.transition_with(id, expected, next, |_| {})
+    }
+
+    /// `transition` that also applies `update` in the same step, so fields
+    /// written with the new status (approver, totals) can't land on a run
+    /// that has meanwhile moved on
+    pub fn transition_with<F: FnOnce(&mut PayrollRun)>(
+        &self,
+        id: Uuid,
+        expected: PayrollRunStatus,
+        next: PayrollRunStatus,
+        update: F,
+    ) -> Result<PayrollRun, PayrollError> {
+        if !expected.can_transition_to(next) {
+            return Err(PayrollError::InvalidTransition { from: expected, to: next });
+        }
+        self.update_if(id, expected, |run| {
+            update(run);
+            run.status = next;
+        })
+    }
+
+    /// Apply `update` to a run only while it is still in `expected`.
+    ///
+    /// Fails with `StatusConflict` if the run is no longer in `expected`.
+    pub fn update_if<F: FnOnce(&mut PayrollRun)>(
+        &self,
+        id: Uuid,
+        expected: PayrollRunStatus,
+        update: F,
+    ) -> Result<PayrollRun, PayrollError> {
+        // The shard write lock makes check-and-update one step, like the
+        // WHERE clause in TRANSITION_STATUS_SQL
+        let mut run = self.runs.get_mut(&id).ok_or(PayrollError::NotFound(id))?;
+        if run.status != expected {
+            return Err(PayrollError::StatusConflict { id, expected, actual: run.status });
+        }
+
+        update(&mut run);
+        run.updated_at = Utc::now();
+        Ok(run.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::sync::Barrier;
+    use std::thread;
+
+    fn pending_run(repo: &PayrollRunRepository) -> Uuid {
+        let mut run = PayrollRun::new(
+            Uuid::new_v4(),
+            "March 2024".to_string(),
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(),
+        );
+        run.status = PayrollRunStatus::PendingApproval;
+        let id = run.id;
+        repo.insert(run);
+        id
+    }
+
+    #[test]
+    fn test_transition_table() {
+        use PayrollRunStatus::*;
+        assert!(Draft.can_transition_to(Processing));
+        assert!(Processing.can_transition_to(Failed));
+        assert!(PendingApproval.can_transition_to(Approved));
+        assert!(!Processing.can_transition_to(Approved));
+        assert!(!Approved.can_transition_to(Failed));
+        assert!(Paid.allowed_transitions().is_empty());
+
+        let repo = PayrollRunRepository::new();
+        let id = pending_run(&repo);
+        assert!(matches!(
+            repo.transition(id, PendingApproval, Paid),
+            Err(PayrollError::InvalidTransition { .. })
+        ));
+    }
+
+    #[test]
+    fn test_concurrent_approvals_exactly_one_wins() {
+        let repo = PayrollRunRepository::new();
+        let id = pending_run(&repo);
+        let barrier = Arc::new(Barrier::new(8));
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let (repo, barrier) = (repo.clone(), barrier.clone());
+                thread::spawn(move || {
+                    // Half approve, half cancel
+                    let next = if i % 2 == 0 { PayrollRunStatus::Approved } else { PayrollRunStatus::Cancelled };
+                    barrier.wait();
+                    repo.transition(id, PayrollRunStatus::PendingApproval, next)
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        let winners: Vec<_> = results.iter().filter_map(|r| r.as_ref().ok()).collect();
+
+        assert_eq!(winners.len(), 1);
+        assert!(results
+            .iter()
+            .filter(|r| r.is_err())
+            .all(|r| matches!(r, Err(PayrollError::StatusConflict { actual, .. }) if *actual == winners[0].status)));
+        assert_eq!(repo.get(id).unwrap().status, winners[0].status);
+    }
+
+    #[test]
+    fn test_approve_and_fail_cannot_both_apply() {
+        let repo = PayrollRunRepository::new();
+        let id = pending_run(&repo);
+        repo.transition(id, PayrollRunStatus::PendingApproval, PayrollRunStatus::Draft).unwrap();
+        repo.transition(id, PayrollRunStatus::Draft, PayrollRunStatus::Processing).unwrap();
+
+        let barrier = Arc::new(Barrier::new(2));
+        let attempts = [
+            (PayrollRunStatus::Processing, PayrollRunStatus::Failed),
+            (PayrollRunStatus::Processing, PayrollRunStatus::PendingApproval),
+        ];
+        let handles: Vec<_> = attempts
+            .into_iter()
+            .map(|(from, to)| {
+                let (repo, barrier) = (repo.clone(), barrier.clone());
+                thread::spawn(move || {
+                    barrier.wait();
+                    repo.transition(id, from, to).is_ok()
+                })
+            })
+            .collect();
+
+        let wins = handles.into_iter().map(|h| h.join().unwrap()).filter(|ok| *ok).count();
+        assert_eq!(wins, 1);
+        // Whichever won, the run can no longer be approved and failed
+        let status = repo.get(id).unwrap().status;
+        assert!(matches!(status, PayrollRunStatus::Failed | PayrollRunStatus::PendingApproval));
+    }
+}
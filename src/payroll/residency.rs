@@ -0,0 +1,254 @@
+//! Tax Residency
+//!
+//! Whether an employee is taxed as a resident depends on the days they were
+//! present in the country during the tax year, usually against a 183-day
+//! threshold. Countries with split-year treatment count the days in any
+//! twelve months that start or end in the tax year, so a long stay that
+//! straddles the year end still counts, and tax the year of arrival or
+//! departure as resident only for the part of the year spent living
+//! there; elsewhere residency covers the whole year or none of it. The
+//! resulting status selects resident or non-resident rates in calculators
+//! that distinguish them.
+
+use std::collections::HashMap;
+use chrono::{Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::proration::inclusive_days;
+
+/// Tax residency for a tax year
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResidencyStatus {
+    #[default]
+    Resident,
+    NonResident,
+}
+
+/// Day-count test for one country
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResidencyRule {
+    /// Days present in the tax year at which someone becomes resident
+    pub threshold_days: u32,
+    /// Residency starts on arrival and ends on departure within the year,
+    /// and the threshold applies to any twelve months starting or ending in it
+    pub split_year: bool,
+}
+
+impl Default for ResidencyRule {
+    /// The common 183-day rule, whole year
+    fn default() -> Self {
+        Self { threshold_days: 183, split_year: false }
+    }
+}
+
+/// Time spent in the country; both ends inclusive, open while still there
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Presence {
+    pub arrived: NaiveDate,
+    pub departed: Option<NaiveDate>,
+}
+
+impl Presence {
+    pub fn new(arrived: NaiveDate, departed: Option<NaiveDate>) -> Self {
+        Self { arrived, departed }
+    }
+
+    fn covers(&self, date: NaiveDate) -> bool {
+        self.arrived <= date && self.departed.is_none_or(|departed| date <= departed)
+    }
+    /// Days present between `from` and `until`, both inclusive
+    fn days_within(&self, from: NaiveDate, until: NaiveDate) -> i64 {
+        let (from, until) = (self.arrived.max(from), self.departed.map_or(until, |departed| departed.min(until)));
+        if from <= until { inclusive_days(from, until) } else { 0 }
+    }
+}
+
+fn days_within(presence: &[Presence], from: NaiveDate, until: NaiveDate) -> u32 {
+    presence.iter().map(|p| p.days_within(from, until)).sum::<i64>() as u32
+}
+
+/// Outcome of the day-count test for a tax year
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResidencyDetermination {
+    pub tax_year: i32,
+    pub status: ResidencyStatus,
+    pub days_present: u32,
+    /// First resident day when split-year treatment starts residency on arrival
+    pub resident_from: Option<NaiveDate>,
+    /// Last resident day when split-year treatment ends residency on departure
+    pub resident_until: Option<NaiveDate>,
+}
+
+impl ResidencyDetermination {
+    /// Status on a given day of the tax year
+    pub fn status_on(&self, date: NaiveDate) -> ResidencyStatus {
+        let in_resident_part = self.resident_from.is_none_or(|from| date >= from)
+            && self.resident_until.is_none_or(|until| date <= until);
+        if self.status == ResidencyStatus::Resident && in_resident_part {
+            ResidencyStatus::Resident
+        } else {
+            ResidencyStatus::NonResident
+        }
+    }
+}
+
+/// Residency rules per country, falling back to `ResidencyRule::default`
+#[derive(Debug, Clone)]
+pub struct ResidencyDeterminer {
+    countries: HashMap<String, ResidencyRule>,
+    default: ResidencyRule,
+}
+
+impl Default for ResidencyDeterminer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResidencyDeterminer {
+    /// Built-in statutory rules
+    pub fn new() -> Self {
+        let mut determiner = Self { countries: HashMap::new(), default: ResidencyRule::default() };
+        // IRS Code art. 16: residency from the first day of presence, lost from the last
+        determiner.set("PT", ResidencyRule { threshold_days: 183, split_year: true });
+        // Income Tax Act s.2: 183 days in the calendar year
+        determiner.set("SG", ResidencyRule { threshold_days: 183, split_year: false });
+        // Income Tax Law s.2: more than 183 days; the 60-day route needs ties we don't record
+        determiner.set("CY", ResidencyRule { threshold_days: 184, split_year: false });
+        determiner
+    }
+
+    pub fn set(&mut self, country_code: &str, rule: ResidencyRule) -> &mut Self {
+        self.countries.insert(country_code.to_ascii_uppercase(), rule);
+        self
+    }
+
+    pub fn for_country(&self, country_code: &str) -> ResidencyRule {
+        self.countries.get(&country_code.to_ascii_uppercase()).copied().unwrap_or(self.default)
+    }
+
+    /// Apply `country_code`'s day-count test to the calendar tax year
+    pub fn determine(&self, country_code: &str, tax_year: i32, presence: &[Presence]) -> ResidencyDetermination {
+        let rule = self.for_country(country_code);
+        let year_start = NaiveDate::from_ymd_opt(tax_year, 1, 1).expect("valid tax year");
+        let year_end = NaiveDate::from_ymd_opt(tax_year, 12, 31).expect("valid tax year");
+
+        let in_year: Vec<(NaiveDate, NaiveDate)> = presence
+            .iter()
+            .map(|p| (p.arrived.max(year_start), p.departed.map_or(year_end, |d| d.min(year_end))))
+            .filter(|(from, until)| from <= until)
+            .collect();
+        let days_present = days_within(presence, year_start, year_end);
+
+        // Split year: also the twelve months from each arrival and up to each departure in the year
+        let most_in_twelve_months = if rule.split_year {
+            in_year
+                .iter()
+                .flat_map(|(from, until)| {
+                    let after_arrival = days_within(presence, *from, *from + Months::new(12) - Days::new(1));
+                    let before_departure = days_within(presence, *until - Months::new(12) + Days::new(1), *until);
+                    [after_arrival, before_departure]
+                })
+                .fold(days_present, u32::max)
+        } else {
+            days_present
+        };
+
+        let status = if most_in_twelve_months >= rule.threshold_days {
+            ResidencyStatus::Resident
+        } else {
+            ResidencyStatus::NonResident
+        };
+        let (mut resident_from, mut resident_until) = (None, None);
+        if rule.split_year && status == ResidencyStatus::Resident {
+            let present_on = |date| presence.iter().any(|p| p.covers(date));
+            if !present_on(year_start) {
+                resident_from = in_year.iter().map(|(from, _)| *from).min();
+            }
+            if !present_on(year_end) {
+                resident_until = in_year.iter().map(|(_, until)| *until).max();
+            }
+        }
+
+        ResidencyDetermination { tax_year, status, days_present, resident_from, resident_until }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_183_day_threshold_crossing() {
+        let determiner = ResidencyDeterminer::new();
+
+        // 1 January to 1 July 2024 is 183 days (leap year)
+        let stay = [Presence::new(d(2023, 9, 1), Some(d(2024, 7, 1)))];
+        let crossed = determiner.determine("SG", 2024, &stay);
+        assert_eq!(crossed.days_present, 183);
+        assert_eq!(crossed.status, ResidencyStatus::Resident);
+        assert_eq!(crossed.status_on(d(2024, 12, 1)), ResidencyStatus::Resident);
+
+        let one_short = [Presence::new(d(2023, 9, 1), Some(d(2024, 6, 30)))];
+        assert_eq!(determiner.determine("SG", 2024, &one_short).status, ResidencyStatus::NonResident);
+
+        // Several trips add up
+        let trips = [
+            Presence::new(d(2024, 1, 1), Some(d(2024, 3, 31))),
+            Presence::new(d(2024, 6, 1), Some(d(2024, 8, 31))),
+        ];
+        let trips = determiner.determine("SG", 2024, &trips);
+        assert_eq!((trips.days_present, trips.status), (183, ResidencyStatus::Resident));
+
+        // Cyprus needs more than 183
+        assert_eq!(determiner.determine("CY", 2024, &stay).status, ResidencyStatus::NonResident);
+    }
+
+    #[test]
+    fn test_split_year_arrival() {
+        let determiner = ResidencyDeterminer::new();
+        let arrival = [Presence::new(d(2024, 3, 1), None)];
+
+        // Portugal: resident from the day of arrival
+        let pt = determiner.determine("PT", 2024, &arrival);
+        assert_eq!(pt.status, ResidencyStatus::Resident);
+        assert_eq!(pt.days_present, 306);
+        assert_eq!((pt.resident_from, pt.resident_until), (Some(d(2024, 3, 1)), None));
+        assert_eq!(pt.status_on(d(2024, 2, 15)), ResidencyStatus::NonResident);
+        assert_eq!(pt.status_on(d(2024, 3, 1)), ResidencyStatus::Resident);
+
+        // Singapore has no split year: resident for all of it
+        let sg = determiner.determine("SG", 2024, &arrival);
+        assert_eq!((sg.resident_from, sg.resident_until), (None, None));
+        assert_eq!(sg.status_on(d(2024, 2, 15)), ResidencyStatus::Resident);
+
+        // A short stay never reaches the threshold
+        let visit = determiner.determine("PT", 2024, &[Presence::new(d(2024, 9, 1), Some(d(2024, 12, 20)))]);
+        assert_eq!(visit.status, ResidencyStatus::NonResident);
+        assert_eq!(visit.status_on(d(2024, 10, 1)), ResidencyStatus::NonResident);
+
+        // Arriving late but staying on: 183 days within the twelve months from arrival
+        let late = determiner.determine("PT", 2024, &[Presence::new(d(2024, 9, 1), None)]);
+        assert_eq!((late.status, late.days_present), (ResidencyStatus::Resident, 122));
+        assert_eq!(late.status_on(d(2024, 8, 31)), ResidencyStatus::NonResident);
+        assert_eq!(late.status_on(d(2024, 10, 1)), ResidencyStatus::Resident);
+        assert_eq!(determiner.determine("SG", 2024, &[Presence::new(d(2024, 9, 1), None)]).status, ResidencyStatus::NonResident);
+
+        // Departure year: resident until the day of leaving
+        let departure = determiner.determine("PT", 2025, &[Presence::new(d(2020, 1, 1), Some(d(2025, 8, 15)))]);
+        assert_eq!((departure.resident_from, departure.resident_until), (None, Some(d(2025, 8, 15))));
+        assert_eq!(departure.status_on(d(2025, 9, 1)), ResidencyStatus::NonResident);
+
+        // A long-term resident leaving early in the year stays resident until departure
+        let early = determiner.determine("PT", 2025, &[Presence::new(d(2020, 1, 1), Some(d(2025, 3, 31)))]);
+        assert_eq!((early.status, early.days_present), (ResidencyStatus::Resident, 90));
+        assert_eq!((early.resident_from, early.resident_until), (None, Some(d(2025, 3, 31))));
+        assert_eq!(early.status_on(d(2025, 2, 1)), ResidencyStatus::Resident);
+        assert_eq!(early.status_on(d(2025, 4, 1)), ResidencyStatus::NonResident);
+    }
+}
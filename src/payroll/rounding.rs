@@ -0,0 +1,210 @@
+//! Money Rounding
+//!
+//! One place that decides how payroll amounts are rounded. A tenant picks
+//! half-up or banker's rounding (half-even) once, and every payslip line and
+//! report total in a run goes through the same `MoneyRounding`, so a run
+//! never mixes modes.
+//!
+//! Precision comes from the currency: ISO 4217 gives yen and won no minor
+//! unit, and the Gulf dinars three, so a tenant's mode is applied at the
+//! minor unit of each amount's currency.
+//!
+//! Tax engines additionally follow the authority's own rule through
+//! `TaxRounding`: some round every step (each bracket's tax, the income tax
+//! a surtax is levied on), others only the final amount.
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+use super::registry::PayrollRegistry;
+
+/// ISO 4217 minor unit: decimal places in amounts of `currency`
+pub fn minor_unit(currency: &str) -> u32 {
+    match currency.to_ascii_uppercase().as_str() {
+        "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF" | "UGX" | "VND" | "VUV"
+        | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        _ => 2,
+    }
+}
+
+/// How a midpoint (…5) is rounded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// 2.5 → 3, -2.5 → -3
+    #[default]
+    HalfUp,
+    /// Banker's rounding: 2.5 → 2, 3.5 → 4
+    HalfEven,
+    /// Truncate toward zero: 2.9 → 2, -2.9 → -2
+    Down,
+}
+
+impl RoundingMode {
+    fn strategy(self) -> RoundingStrategy {
+        match self {
+            Self::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            Self::HalfEven => RoundingStrategy::MidpointNearestEven,
+            Self::Down => RoundingStrategy::ToZero,
+        }
+    }
+}
+
+/// Tenant money-rounding settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoneyRounding {
+    pub mode: RoundingMode,
+    /// Minor-unit precision, e.g. 2 for cents, 0 for yen
+    pub decimal_places: u32,
+}
+
+impl Default for MoneyRounding {
+    fn default() -> Self {
+        Self { mode: RoundingMode::default(), decimal_places: 2 }
+    }
+}
+
+impl MoneyRounding {
+    pub fn new(mode: RoundingMode, decimal_places: u32) -> Self {
+        Self { mode, decimal_places }
+    }
+
+    /// `mode` at `currency`'s minor unit
+    pub fn for_currency(mode: RoundingMode, currency: &str) -> Self {
+        Self::new(mode, minor_unit(currency))
+    }
+
+    /// Same mode at `currency`'s minor unit
+    pub fn in_currency(self, currency: &str) -> Self {
+        Self::for_currency(self.mode, currency)
+    }
+
+    /// Same mode at the minor unit of the currency `country_code` pays in;
+    /// unchanged for a country without a registered currency
+    pub fn in_country(self, country_code: &str) -> Self {
+        PayrollRegistry::currency_for(country_code).map_or(self, |currency| self.in_currency(currency))
+    }
+
+    pub fn round(&self, amount: Decimal) -> Decimal {
+        amount.round_dp_with_strategy(self.decimal_places, self.mode.strategy())
+    }
+}
+
+/// A tax authority's rounding rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaxRounding {
+    /// Applied to each bracket's tax and to every amount a later step is
+    /// computed from; `None` keeps full precision until the final amount
+    pub intermediate_rounding: Option<MoneyRounding>,
+    pub final_rounding: MoneyRounding,
+}
+
+impl Default for TaxRounding {
+    fn default() -> Self {
+        Self::final_only(MoneyRounding::default())
+    }
+}
+
+impl TaxRounding {
+    pub fn final_only(final_rounding: MoneyRounding) -> Self {
+        Self { intermediate_rounding: None, final_rounding }
+    }
+
+    /// Rule the country's authority publishes
+    pub fn for_country(country_code: &str) -> Self {
+        match country_code.to_ascii_uppercase().as_str() {
+            // Whole yen; the surtax is levied on the unrounded income tax
+            "JP" => Self::final_only(MoneyRounding::new(RoundingMode::HalfEven, 0)),
+            // 원 미만 절사: every computed amount drops fractional won, and
+            // local income tax is 10% of the truncated income tax
+            "KR" => Self {
+                intermediate_rounding: Some(MoneyRounding::new(RoundingMode::Down, 0)),
+                final_rounding: MoneyRounding::new(RoundingMode::Down, 0),
+            },
+            // IRD assesses salaries tax in whole dollars, fractions dropped
+            "HK" => Self::final_only(MoneyRounding::new(RoundingMode::Down, 0)),
+            // Withholding and insurance premiums are in whole NT dollars
+            "TW" => Self::final_only(MoneyRounding::new(RoundingMode::HalfUp, 0)),
+            code => Self::final_only(MoneyRounding::default().in_country(code)),
+        }
+    }
+
+    /// Round an intermediate amount, if the authority requires it
+    pub fn step(&self, amount: Decimal) -> Decimal {
+        self.intermediate_rounding.map_or(amount, |rounding| rounding.round(amount))
+    }
+
+    /// Round a reported amount
+    pub fn finish(&self, amount: Decimal) -> Decimal {
+        self.final_rounding.round(amount)
+    }
+}
+
+/// Tax on `income` over `(upper bound, rate)` brackets, each bracket's tax
+/// passed through `rounding.step`. The total is not final-rounded.
+pub fn progressive_tax(brackets: &[(Decimal, Decimal)], income: Decimal, rounding: &TaxRounding) -> Decimal {
+    let mut tax = Decimal::ZERO;
+    let mut prev = Decimal::ZERO;
+    for (max, rate) in brackets {
+        if income <= prev { break; }
+        tax += rounding.step((income.min(*max) - prev) * rate);
+        prev = *max;
+    }
+    tax
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_midpoint_at_whole_units() {
+        let half_even = MoneyRounding::new(RoundingMode::HalfEven, 0);
+        let half_up = MoneyRounding::new(RoundingMode::HalfUp, 0);
+
+        assert_eq!(half_even.round(dec!(2.5)), dec!(2));
+        assert_eq!(half_up.round(dec!(2.5)), dec!(3));
+        assert_eq!(half_even.round(dec!(3.5)), dec!(4));
+        assert_eq!(half_up.round(dec!(-2.5)), dec!(-3));
+    }
+
+    #[test]
+    fn test_midpoint_at_cents() {
+        let half_even = MoneyRounding::new(RoundingMode::HalfEven, 2);
+        let half_up = MoneyRounding::default();
+
+        assert_eq!(half_even.round(dec!(10.025)), dec!(10.02));
+        assert_eq!(half_up.round(dec!(10.025)), dec!(10.03));
+        // Non-midpoints agree
+        assert_eq!(half_even.round(dec!(10.026)), half_up.round(dec!(10.026)));
+    }
+
+    #[test]
+    fn test_intermediate_vs_final_rounding() {
+        let brackets = [(dec!(100), dec!(0.076)), (dec!(200), dec!(0.146))];
+        let step_rounded = TaxRounding::for_country("KR");
+        let final_rounded = TaxRounding::final_only(step_rounded.final_rounding);
+
+        // 7.6 + 14.6: truncating each bracket loses a won the final-only sum keeps
+        assert_eq!(step_rounded.finish(progressive_tax(&brackets, dec!(200), &step_rounded)), dec!(21));
+        assert_eq!(final_rounded.finish(progressive_tax(&brackets, dec!(200), &final_rounded)), dec!(22));
+        assert_eq!(TaxRounding::for_country("US").step(dec!(7.6)), dec!(7.6));
+    }
+
+    #[test]
+    fn test_precision_follows_currency_minor_unit() {
+        let half_up = MoneyRounding::default();
+
+        assert_eq!(half_up.in_currency("JPY").round(dec!(123456.5)), dec!(123457));
+        assert_eq!(half_up.in_currency("krw").round(dec!(99.49)), dec!(99));
+        assert_eq!(half_up.in_currency("KWD").round(dec!(1234.5675)), dec!(1234.568));
+        assert_eq!(half_up.in_currency("BHD").round(dec!(0.0004)), dec!(0.000));
+        assert_eq!(half_up.in_currency("NGN").round(dec!(10.025)), dec!(10.03));
+        assert_eq!(MoneyRounding::new(RoundingMode::HalfEven, 2).in_currency("OMR").mode, RoundingMode::HalfEven);
+
+        assert_eq!(TaxRounding::for_country("BH").finish(dec!(12.3456)), dec!(12.346));
+        assert_eq!(TaxRounding::for_country("VN").finish(dec!(12.5)), dec!(13));
+    }
+}
@@ -0,0 +1,60 @@
+//! Salary Records
+//!
+//! Each employee's salary, allowances, and bank details as HR last set
+//! them. Processing loads its inputs from here by employee id rather than
+//! taking them from the request, so a caller can't change what someone is
+//! paid, or which account it goes to, by editing the request body.
+
+use std::sync::Arc;
+use dashmap::DashMap;
+use uuid::Uuid;
+
+use super::models::EmployeeSalary;
+
+/// Salary records by tenant and employee
+#[derive(Debug, Clone, Default)]
+pub struct SalaryRecords {
+    // In real implementation, backed by the employee_salaries table
+    records: Arc<DashMap<(Uuid, Uuid), EmployeeSalary>>,
+}
+
+impl SalaryRecords {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set an employee's salary record, replacing any earlier one
+    pub fn upsert(&self, tenant_id: Uuid, salary: EmployeeSalary) {
+        self.records.insert((tenant_id, salary.employee_id), salary);
+    }
+
+    pub fn get(&self, tenant_id: Uuid, employee_id: Uuid) -> Option<EmployeeSalary> {
+        self.records.get(&(tenant_id, employee_id)).map(|s| s.clone())
+    }
+
+    /// Every record in the tenant, by employee code
+    pub fn for_tenant(&self, tenant_id: Uuid) -> Vec<EmployeeSalary> {
+        let mut salaries: Vec<_> = self
+            .records
+            .iter()
+            .filter(|entry| entry.key().0 == tenant_id)
+            .map(|entry| entry.value().clone())
+            .collect();
+        salaries.sort_by(|a, b| a.employee_code.cmp(&b.employee_code));
+        salaries
+    }
+
+    /// Records for `employee_ids`, or the whole tenant when `None`. Fails
+    /// with the ids that have no record in the tenant.
+    pub fn load(&self, tenant_id: Uuid, employee_ids: Option<&[Uuid]>) -> Result<Vec<EmployeeSalary>, Vec<Uuid>> {
+        let Some(employee_ids) = employee_ids else {
+            return Ok(self.for_tenant(tenant_id));
+        };
+        let (found, missing): (Vec<_>, Vec<_>) =
+            employee_ids.iter().map(|id| self.get(tenant_id, *id).ok_or(*id)).partition(Result::is_ok);
+        if !missing.is_empty() {
+            return Err(missing.into_iter().filter_map(Result::err).collect());
+        }
+        Ok(found.into_iter().filter_map(Result::ok).collect())
+    }
+}
@@ -2,16 +2,51 @@
 //!
 //! Business logic for payroll processing with Nigerian compliance.
 
-use std::collections::HashMap;
-use chrono::Utc;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use chrono::{DateTime, NaiveDate, Utc};
+use dashmap::DashMap;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use uuid::Uuid;
 
+use crate::compliance::{ActorType, AuditAction, AuditLog, AuditLogStore};
+use crate::domain::aggregates::LegalEntities;
+use crate::domain::services::EmployeeRecords;
 use super::{
     models::*,
+    annualization::{Annualization, ExtraPeriodPolicy},
+    advance::{AdvanceTaxTreatment, SalaryAdvance, SalaryAdvances, SALARY_ADVANCE_LINE},
+    budget::{BudgetVariance, Departments},
+    africa_mobile_gateway::PaymentRequest,
+    calendar::PayrollCalendar,
+    clawback::{
+        withholding_reversal, Clawback, ClawbackPolicy, Clawbacks, CLAWBACK_CARRIED_FORWARD_LINE, CLAWBACK_LINE,
+        CLAWBACK_RECOVERY_LINE,
+    },
+    disbursement::{self, Disbursed, Disbursement, DisbursementChannel, DisbursementLedger, PaymentFileLine},
+    fx::{FxError, FxRates},
+    gl::{self, GlAccountMap, GlJournal},
+    jobs::{PayrollJob, PayrollJobStatus, PayrollJobs, PROCESSING_BATCH_SIZE},
+    net_pay_floor::{DeferredDeduction, NetPayFloorLedger, NetPayFloorOutcome, NetPayFloors},
     tax_calculator::NigerianTaxCalculator,
+    tax_override::{TaxOverride, TaxOverrideStatus, TaxOverrides},
     pension::PensionCalculator,
+    preflight::{PreflightFinding, PreflightRules},
+    proration::active_fraction,
+    reconcile::{self, ReconciliationReport},
+    recurring::{RecurringDeductions, RECURRING_DEDUCTION_LINE},
+    repayment::{ProtectedEarnings, RepaymentKind, RepaymentSchedule, RepaymentSchedules, GARNISHMENT_LINE},
+    repository::PayrollRunRepository,
+    registry::PayrollRegistry,
+    rounding::MoneyRounding,
+    salary_records::SalaryRecords,
+    social_security::SocialSecurityProrations,
+    statutory_report::{StatutoryReport, StatutoryReportFormats},
+    south_africa::SouthAfricaTaxCalculator,
+    europe_east_noneu::RussiaTaxCalculator,
+    tax_tables::TaxTables,
+    ytd::{YtdStore, YtdSummary},
 };
 
 /// Payroll processing errors
@@ -29,14 +64,38 @@ pub enum PayrollError {
     #[error("No employees found for payroll processing")]
     NoEmployees,
     
+    #[error("No payroll calculator registered for country: {0}")]
+    UnsupportedCountry(String),
+    
     #[error("Employee {0} has no salary configuration")]
     NoSalaryConfig(Uuid),
     
+    #[error("Payroll run cannot move from {from:?} to {to:?}")]
+    InvalidTransition { from: PayrollRunStatus, to: PayrollRunStatus },
+    
+    #[error("Payroll run {id} status changed concurrently: expected {expected:?}, found {actual:?}")]
+    StatusConflict { id: Uuid, expected: PayrollRunStatus, actual: PayrollRunStatus },
+    
+    #[error("Department not found: {0}")]
+    DepartmentNotFound(Uuid),
+    
+    #[error("Pay period overlaps payroll run {0}")]
+    OverlappingRun(Uuid),
+    
     #[error("Database error: {0}")]
     Database(String),
     
     #[error("Validation error: {0}")]
     Validation(String),
+    
+    #[error(transparent)]
+    ExchangeRate(#[from] FxError),
+    
+    #[error("Tax override not found: {0}")]
+    TaxOverrideNotFound(Uuid),
+    
+    #[error("Tax override must be approved by someone other than its requester")]
+    TaxOverrideSelfApproval,
 }
 
 /// Payroll Service
@@ -44,6 +103,39 @@ pub enum PayrollError {
 pub struct PayrollService {
     tax_calculator: NigerianTaxCalculator,
     pension_calculator: PensionCalculator,
+    ytd: YtdStore,
+    tax_tables: TaxTables,
+    runs: PayrollRunRepository,
+    // In real implementation, payroll items and their salary inputs are persisted per run
+    run_items: Arc<DashMap<Uuid, Vec<PayrollItem>>>,
+    run_inputs: Arc<DashMap<Uuid, Vec<EmployeeSalary>>>,
+    // In real implementation, the tenant's public holiday table
+    holidays: Arc<DashMap<String, Vec<NaiveDate>>>,
+    disbursements: DisbursementLedger,
+    jobs: PayrollJobs,
+    repayments: RepaymentSchedules,
+    advances: SalaryAdvances,
+    recurring: RecurringDeductions,
+    protected_earnings: ProtectedEarnings,
+    net_pay_floors: NetPayFloors,
+    net_pay_floor_ledger: NetPayFloorLedger,
+    departments: Departments,
+    legal_entities: LegalEntities,
+    statutory_formats: StatutoryReportFormats,
+    fx_rates: FxRates,
+    social_security: SocialSecurityProrations,
+    /// Periods weekly-cycle pay is annualized over in years with an extra pay date
+    extra_period_policy: ExtraPeriodPolicy,
+    tax_overrides: TaxOverrides,
+    clawbacks: Clawbacks,
+    clawback_policy: ClawbackPolicy,
+    salaries: SalaryRecords,
+    audit: AuditLogStore,
+    preflight: PreflightRules,
+    /// Largest gross-to-net difference per payslip that `reconcile` accepts;
+    /// one unit of the last rounded place when unset
+    reconciliation_tolerance: Option<Decimal>,
+    rounding: MoneyRounding,
 }
 
 impl Default for PayrollService {
@@ -57,9 +149,160 @@ impl PayrollService {
         Self {
             tax_calculator: NigerianTaxCalculator::new(),
             pension_calculator: PensionCalculator::new(),
+            ytd: YtdStore::new(),
+            tax_tables: TaxTables::new(),
+            runs: PayrollRunRepository::new(),
+            run_items: Arc::new(DashMap::new()),
+            run_inputs: Arc::new(DashMap::new()),
+            holidays: Arc::new(DashMap::new()),
+            disbursements: DisbursementLedger::new(),
+            jobs: PayrollJobs::new(),
+            repayments: RepaymentSchedules::new(),
+            advances: SalaryAdvances::new(),
+            recurring: RecurringDeductions::new(),
+            protected_earnings: ProtectedEarnings::default(),
+            net_pay_floors: NetPayFloors::new(),
+            net_pay_floor_ledger: NetPayFloorLedger::new(),
+            departments: Departments::new(),
+            legal_entities: LegalEntities::new(),
+            statutory_formats: StatutoryReportFormats::new(),
+            fx_rates: FxRates::new(),
+            social_security: SocialSecurityProrations::new(),
+            extra_period_policy: ExtraPeriodPolicy::default(),
+            tax_overrides: TaxOverrides::new(),
+            clawbacks: Clawbacks::new(),
+            clawback_policy: ClawbackPolicy::default(),
+            salaries: SalaryRecords::new(),
+            audit: AuditLogStore::new(),
+            preflight: PreflightRules::default(),
+            reconciliation_tolerance: None,
+            rounding: MoneyRounding::default(),
         }
     }
 
+    /// Use the tenant's rounding mode for every amount this service produces,
+    /// at the minor unit of the amount's currency
+    pub fn with_rounding(mut self, rounding: MoneyRounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn rounding(&self) -> MoneyRounding {
+        self.rounding
+    }
+
+    /// Net pay that loan and garnishment repayments must leave untouched
+    pub fn with_protected_earnings(mut self, protected_earnings: ProtectedEarnings) -> Self {
+        self.protected_earnings = protected_earnings;
+        self
+    }
+
+    /// Legal minimum take-home per country, applied alongside protected earnings
+    pub fn with_net_pay_floors(mut self, net_pay_floors: NetPayFloors) -> Self {
+        self.net_pay_floors = net_pay_floors;
+        self
+    }
+
+    /// Voluntary deductions deferred and shortfalls left by the net pay floor, per run
+    pub fn net_pay_floor_ledger(&self) -> &NetPayFloorLedger {
+        &self.net_pay_floor_ledger
+    }
+
+    /// Companies runs can be scoped to, shared with the employee service
+    pub fn with_legal_entities(mut self, legal_entities: LegalEntities) -> Self {
+        self.legal_entities = legal_entities;
+        self
+    }
+
+    /// Dated exchange rates for reporting runs in another currency
+    pub fn with_fx_rates(mut self, fx_rates: FxRates) -> Self {
+        self.fx_rates = fx_rates;
+        self
+    }
+
+    pub fn fx_rates(&self) -> &FxRates {
+        &self.fx_rates
+    }
+
+    /// Statutory return layout per country of registration
+    pub fn with_statutory_report_formats(mut self, statutory_formats: StatutoryReportFormats) -> Self {
+        self.statutory_formats = statutory_formats;
+        self
+    }
+
+    /// How a new starter's first period is treated for social security, per country
+    pub fn with_social_security_proration(mut self, social_security: SocialSecurityProrations) -> Self {
+        self.social_security = social_security;
+        self
+    }
+
+    /// Annualize over the nominal, actual, or smoothed count of pay periods
+    pub fn with_extra_period_policy(mut self, extra_period_policy: ExtraPeriodPolicy) -> Self {
+        self.extra_period_policy = extra_period_policy;
+        self
+    }
+
+    /// How clawbacks reverse withholding and recover what a run can't absorb
+    pub fn with_clawback_policy(mut self, clawback_policy: ClawbackPolicy) -> Self {
+        self.clawback_policy = clawback_policy;
+        self
+    }
+
+    pub fn clawbacks(&self) -> &Clawbacks {
+        &self.clawbacks
+    }
+
+    /// Salary and bank details processing loads by employee id
+    pub fn salaries(&self) -> &SalaryRecords {
+        &self.salaries
+    }
+
+    pub fn with_audit_log(mut self, audit: AuditLogStore) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    pub fn audit_log(&self) -> &AuditLogStore {
+        &self.audit
+    }
+
+    pub fn tax_overrides(&self) -> &TaxOverrides {
+        &self.tax_overrides
+    }
+
+    /// Union dues, payroll giving, and other standing deductions taken each run
+    pub fn recurring_deductions(&self) -> &RecurringDeductions {
+        &self.recurring
+    }
+
+    /// Thresholds for the checks `preflight` runs before approval
+    pub fn with_preflight_rules(mut self, preflight: PreflightRules) -> Self {
+        self.preflight = preflight;
+        self
+    }
+
+    pub fn with_reconciliation_tolerance(mut self, tolerance: Decimal) -> Self {
+        self.reconciliation_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Loan and garnishment schedules deducted by each run
+    pub fn repayments(&self) -> &RepaymentSchedules {
+        &self.repayments
+    }
+
+    /// Register public holidays that move check dates in `country_code`
+    pub fn add_holidays(&self, country_code: &str, dates: impl IntoIterator<Item = NaiveDate>) {
+        self.holidays.entry(country_code.to_ascii_uppercase()).or_default().extend(dates);
+    }
+
+    /// Country calendar with the registered holidays
+    pub fn calendar(&self, country_code: &str) -> PayrollCalendar {
+        let country_code = country_code.to_ascii_uppercase();
+        let holidays = self.holidays.get(&country_code).map(|h| h.clone()).unwrap_or_default();
+        PayrollCalendar::for_country(&country_code).with_holidays(holidays)
+    }
+
     /// Create a new payroll run
     pub fn create_payroll_run(
         &self,
@@ -73,6 +316,12 @@ impl PayrollService {
             ));
         }
 
+        if let Some(entity_id) = request.legal_entity_id {
+            if self.legal_entities.for_tenant(tenant_id, entity_id).is_none() {
+                return Err(PayrollError::Validation(format!("Legal entity not found: {}", entity_id)));
+            }
+        }
+
         let mut run = PayrollRun::new(
             tenant_id,
             request.name,
@@ -80,65 +329,417 @@ impl PayrollService {
             request.period_end,
         );
         run.notes = request.notes;
+        run.legal_entity_id = request.legal_entity_id;
+        self.runs.create(run.clone())?;
 
         Ok(run)
     }
 
+    /// Countries this service has a registered calculator for
+    pub fn supports_country(&self, country_code: &str) -> bool {
+        // Nigerian PAYE/PenCom, South African PAYE/UIF and Russian NDFL are
+        // wired into payslip generation
+        ["NG", "ZA", "RU"].iter().any(|code| country_code.eq_ignore_ascii_case(code))
+    }
+
     /// Process payroll for all employees
     /// 
     /// This calculates gross pay, all deductions, and net pay for each employee.
+    /// Employees in a country without a registered calculator are skipped and
+    /// reported rather than failing the whole run.
     pub fn process_payroll(
         &self,
         payroll_run: &mut PayrollRun,
         employees: Vec<EmployeeSalary>,
         processor_id: Uuid,
-    ) -> Result<Vec<PayrollItem>, PayrollError> {
+    ) -> Result<PayrollProcessingResult, PayrollError> {
         if !payroll_run.can_be_processed() {
             return Err(PayrollError::NotDraft);
         }
 
+        let employees = self.scope_to_entity(payroll_run, employees)?;
+        if employees.is_empty() {
+            return Err(PayrollError::NoEmployees);
+        }
+
+        let (items, skipped) = self.calculate_items(payroll_run, &employees)?;
+
+        if items.is_empty() {
+            return Err(self.nothing_to_pay(&skipped));
+        }
+
+        self.finish_processing(payroll_run, PayrollRunStatus::Draft, &items, employees, processor_id)?;
+
+        Ok(PayrollProcessingResult { items, skipped })
+    }
+
+    /// Queue a stored draft run for processing on a worker and return the
+    /// job tracking it. The run is `Processing` until the worker finishes.
+    /// Must be called from within a Tokio runtime.
+    pub fn start_processing(
+        &self,
+        run_id: Uuid,
+        employees: Vec<EmployeeSalary>,
+        processor_id: Uuid,
+    ) -> Result<PayrollJob, PayrollError> {
+        let run = self.runs.get(run_id).ok_or(PayrollError::NotFound(run_id))?;
+        let employees = self.scope_to_entity(&run, employees)?;
         if employees.is_empty() {
             return Err(PayrollError::NoEmployees);
         }
+        // Claims the run, so a second request can't process it twice
+        let run = self.runs.transition(run_id, PayrollRunStatus::Draft, PayrollRunStatus::Processing)?;
+        let job = self.jobs.start(run.tenant_id, run_id, employees.len());
+        // Noted now so other work can tell who the run is paying
+        self.run_inputs.insert(run_id, employees.clone());
+
+        let service = self.clone();
+        let job_id = job.id;
+        tokio::task::spawn_blocking(move || {
+            match service.process_in_batches(run, employees, processor_id, job_id) {
+                Ok(()) => service.jobs.complete(job_id),
+                Err(e) => {
+                    tracing::error!(run_id = %run_id, job_id = %job_id, error = %e, "payroll processing failed");
+                    if let Err(e) = service.runs.transition(run_id, PayrollRunStatus::Processing, PayrollRunStatus::Failed) {
+                        tracing::error!(run_id = %run_id, error = %e, "could not mark payroll run failed");
+                    }
+                    service.jobs.fail(job_id, e.to_string());
+                }
+            }
+        });
+
+        Ok(job)
+    }
+
+    /// Fail runs left in `Processing` by a worker that died: those whose
+    /// job stopped without settling the run, or that started before
+    /// `started_before` and are still going. Returns the runs failed.
+    pub fn recover_stuck_runs(&self, started_before: DateTime<Utc>) -> Vec<Uuid> {
+        let mut recovered = Vec::new();
+        for run in self.runs.with_status(PayrollRunStatus::Processing) {
+            let job = self.jobs.for_run(run.id);
+            let stuck = job.as_ref().is_none_or(|job| {
+                job.status != PayrollJobStatus::Processing || job.created_at < started_before
+            });
+            if !stuck {
+                continue;
+            }
+            // A worker finishing meanwhile wins the CAS and keeps its result
+            if self.runs.transition(run.id, PayrollRunStatus::Processing, PayrollRunStatus::Failed).is_err() {
+                continue;
+            }
+            tracing::warn!(run_id = %run.id, "payroll run stuck in processing marked failed");
+            if let Some(job) = job.filter(|job| job.status == PayrollJobStatus::Processing) {
+                self.jobs.fail(job.id, "processing did not finish".to_string());
+            }
+            recovered.push(run.id);
+        }
+        recovered
+    }
+
+    /// Keep the employees of the run's legal entity, taxed under the
+    /// entity's country. Runs without an entity take only the employees
+    /// not assigned to one, so nobody is paid by two runs.
+    fn scope_to_entity(&self, payroll_run: &PayrollRun, employees: Vec<EmployeeSalary>) -> Result<Vec<EmployeeSalary>, PayrollError> {
+        let Some(entity_id) = payroll_run.legal_entity_id else {
+            return Ok(employees.into_iter().filter(|e| e.legal_entity_id.is_none()).collect());
+        };
+        let entity = self
+            .legal_entities
+            .for_tenant(payroll_run.tenant_id, entity_id)
+            .ok_or_else(|| PayrollError::Validation(format!("Legal entity not found: {}", entity_id)))?;
+        Ok(employees
+            .into_iter()
+            .filter(|e| e.legal_entity_id == Some(entity.id))
+            .map(|e| EmployeeSalary { country_code: entity.country.clone(), ..e })
+            .collect())
+    }
+
+    /// Processing jobs started by `start_processing`
+    pub fn jobs(&self) -> &PayrollJobs {
+        &self.jobs
+    }
+
+    /// Worker side of `start_processing`
+    fn process_in_batches(
+        &self,
+        mut payroll_run: PayrollRun,
+        employees: Vec<EmployeeSalary>,
+        processor_id: Uuid,
+        job_id: Uuid,
+    ) -> Result<(), PayrollError> {
+        let mut items = Vec::with_capacity(employees.len());
+        let mut skipped = Vec::new();
+        for batch in employees.chunks(PROCESSING_BATCH_SIZE) {
+            let (batch_items, batch_skipped) = self.calculate_items(&payroll_run, batch)?;
+            items.extend(batch_items);
+            skipped.extend(batch_skipped);
+            self.jobs.advance(job_id, batch.len());
+        }
+        if items.is_empty() {
+            return Err(self.nothing_to_pay(&skipped));
+        }
+
+        self.finish_processing(&mut payroll_run, PayrollRunStatus::Processing, &items, employees, processor_id)
+    }
+
+    /// Move the run from `expected` to pending approval with its totals,
+    /// then store the calculated items and inputs
+    fn finish_processing(
+        &self,
+        payroll_run: &mut PayrollRun,
+        expected: PayrollRunStatus,
+        items: &[PayrollItem],
+        employees: Vec<EmployeeSalary>,
+        processor_id: Uuid,
+    ) -> Result<(), PayrollError> {
+        let processed = self.runs.transition_with(payroll_run.id, expected, PayrollRunStatus::PendingApproval, |run| {
+            apply_totals(run, items);
+            run.processed_by = Some(processor_id);
+            run.processed_at = Some(Utc::now());
+            run.run_date = Some(Utc::now());
+        })?;
+        self.store_items(&processed, items);
+        self.run_inputs.insert(processed.id, employees);
+        *payroll_run = processed;
+        Ok(())
+    }
+
+    /// Recompute runs that are not yet approved after a tax table for
+    /// `country` and `tax_year` has changed.
+    ///
+    /// Draft and pending-approval runs with at least one employee taxed under
+    /// that table get fresh lines and totals; approved and paid runs are left
+    /// untouched, including one approved while it was being recalculated.
+    /// Recalculation starts from the stored salary inputs each time, so
+    /// calling this again is harmless.
+    pub fn recalculate_draft_runs(&self, country: &str, tax_year: i32) -> Result<Vec<PayrollRun>, PayrollError> {
+        let affected: Vec<Uuid> = self
+            .run_inputs
+            .iter()
+            .filter(|entry| {
+                self.runs.get(*entry.key()).is_some_and(|run| {
+                    matches!(run.status, PayrollRunStatus::Draft | PayrollRunStatus::PendingApproval)
+                        && TaxTables::tax_year_for(country, run.period_end) == tax_year
+                        && entry.value().iter().any(|e| e.country_code.eq_ignore_ascii_case(country))
+                })
+            })
+            .map(|entry| *entry.key())
+            .collect();
+
+        let mut updated = Vec::with_capacity(affected.len());
+        for run_id in affected {
+            let run = self.runs.get(run_id).ok_or(PayrollError::NotFound(run_id))?;
+            let employees = self.run_inputs.get(&run_id).map(|e| e.clone()).unwrap_or_default();
+
+            let (items, _) = self.calculate_items(&run, &employees)?;
+            let run = match self.runs.update_if(run_id, run.status, |stored| apply_totals(stored, &items)) {
+                Ok(run) => run,
+                Err(PayrollError::StatusConflict { actual, .. }) => {
+                    tracing::warn!(run_id = %run_id, status = ?actual, "payroll run moved on during recalculation; left as is");
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            self.ytd.remove_run(run_id);
+            self.store_items(&run, &items);
+
+            tracing::info!(run_id = %run_id, country, tax_year, "recalculated payroll run after tax table update");
+            updated.push(run);
+        }
+
+        Ok(updated)
+    }
+
+    /// Stored snapshot of a processed run
+    pub fn payroll_run(&self, run_id: Uuid) -> Option<PayrollRun> {
+        self.runs.get(run_id)
+    }
+
+    /// Tax tables used for payslip calculation
+    pub fn tax_tables(&self) -> &TaxTables {
+        &self.tax_tables
+    }
 
+    /// Payslips for every employee with a registered calculator, plus those skipped
+    fn calculate_items(
+        &self,
+        payroll_run: &PayrollRun,
+        employees: &[EmployeeSalary],
+    ) -> Result<(Vec<PayrollItem>, Vec<SkippedEmployee>), PayrollError> {
         let mut items = Vec::with_capacity(employees.len());
-        let mut total_gross = Decimal::ZERO;
-        let mut total_deductions = Decimal::ZERO;
-        let mut total_net = Decimal::ZERO;
-        let mut total_employer_contributions = Decimal::ZERO;
+        let mut skipped = Vec::new();
 
         for employee in employees {
-            let item = self.calculate_payslip(payroll_run.id, &employee)?;
-            
-            total_gross += item.gross_pay;
-            total_deductions += item.total_deductions;
-            total_net += item.net_pay;
-            total_employer_contributions += item.pension_employer;
-            
-            items.push(item);
+            let onboarding = match &employee.onboarding_outstanding {
+                None => Some("Onboarding status unknown".to_string()),
+                Some(outstanding) if !outstanding.is_empty() => {
+                    Some(format!("Onboarding incomplete: {}", outstanding.join(", ")))
+                }
+                Some(_) => None,
+            };
+            if let Some(reason) = onboarding {
+                skipped.push(SkippedEmployee {
+                    employee_id: employee.employee_id,
+                    employee_name: employee.employee_name.clone(),
+                    country_code: employee.country_code.clone(),
+                    reason,
+                });
+                continue;
+            }
+            match self.calculate_payslip(payroll_run, employee) {
+                Ok(item) => {
+                    let item = self.apply_tax_override(payroll_run, &employee.country_code, item);
+                    let item = self.apply_clawbacks(payroll_run, &employee.country_code, item);
+                    items.push(self.deduct_from_net(payroll_run, &employee.country_code, item));
+                }
+                Err(e @ PayrollError::UnsupportedCountry(_)) => {
+                    tracing::warn!(employee_id = %employee.employee_id, error = %e, "skipping employee");
+                    skipped.push(SkippedEmployee {
+                        employee_id: employee.employee_id,
+                        employee_name: employee.employee_name.clone(),
+                        country_code: employee.country_code.clone(),
+                        reason: e.to_string(),
+                    });
+                }
+                Err(e) => return Err(e),
+            }
         }
 
-        // Update payroll run totals
-        payroll_run.total_employees = items.len() as i32;
-        payroll_run.total_gross = total_gross;
-        payroll_run.total_deductions = total_deductions;
-        payroll_run.total_net = total_net;
-        payroll_run.total_employer_contributions = total_employer_contributions;
-        payroll_run.status = PayrollRunStatus::PendingApproval;
-        payroll_run.processed_by = Some(processor_id);
-        payroll_run.processed_at = Some(Utc::now());
-        payroll_run.run_date = Some(Utc::now());
-        payroll_run.updated_at = Utc::now();
+        Ok((items, skipped))
+    }
 
-        Ok(items)
+    /// Why a run with only skipped employees can't go ahead
+    fn nothing_to_pay(&self, skipped: &[SkippedEmployee]) -> PayrollError {
+        match skipped.iter().find(|s| !self.supports_country(&s.country_code)) {
+            Some(s) => PayrollError::UnsupportedCountry(s.country_code.clone()),
+            None => PayrollError::NoEmployees,
+        }
     }
 
-    /// Calculate individual payslip
-    fn calculate_payslip(
+    /// Take scheduled repayments, then post-tax recurring deductions, from a
+    /// payslip's net pay without going below the net pay floor. Voluntary
+    /// deductions cut back to fit are recorded as deferred, and any gap left
+    /// under the floor by deductions that can't be cut as a shortfall.
+    fn deduct_from_net(&self, payroll_run: &PayrollRun, country_code: &str, mut item: PayrollItem) -> PayrollItem {
+        let legal_minimum = self.net_pay_floors.for_country(country_code).unwrap_or_default();
+        let floor = self.protected_earnings.floor(item.net_pay).max(legal_minimum);
+        let protected = ProtectedEarnings { minimum_net: floor, share_of_net: Decimal::ZERO };
+        let deductions = self.repayments.deduct(item.payroll_run_id, item.employee_id, item.net_pay, &protected);
+        let total_for = |kind| deductions.iter().filter(|d| d.kind == kind).map(|d| d.amount).sum::<Decimal>();
+        let loans = total_for(RepaymentKind::Loan);
+        let garnishments = total_for(RepaymentKind::Garnishment);
+        let advances = total_for(RepaymentKind::SalaryAdvance);
+        let clawbacks = total_for(RepaymentKind::Clawback);
+
+        item.loan_repayment += loans;
+        if !garnishments.is_zero() {
+            item.other_deductions[GARNISHMENT_LINE] = serde_json::json!(garnishments);
+        }
+        if !advances.is_zero() {
+            item.other_deductions[SALARY_ADVANCE_LINE] = serde_json::json!(advances);
+        }
+        if !clawbacks.is_zero() {
+            item.other_deductions[CLAWBACK_RECOVERY_LINE] = serde_json::json!(clawbacks);
+        }
+        item.total_deductions += loans + garnishments + advances + clawbacks;
+        item.net_pay -= loans + garnishments + advances + clawbacks;
+
+        // Voluntary deductions rank after every repayment
+        let mut available = (item.net_pay - floor).max(Decimal::ZERO);
+        let mut deferred = Vec::new();
+        let post_tax: Vec<(String, Decimal)> = self
+            .recurring_lines(payroll_run, item.employee_id, country_code, item.gross_pay, false)
+            .into_iter()
+            .map(|(name, due)| {
+                let amount = due.min(available);
+                available -= amount;
+                if amount < due {
+                    deferred.push(DeferredDeduction { name: name.clone(), due, taken: amount, deferred: due - amount });
+                }
+                (name, amount)
+            })
+            .collect();
+        let taken: Decimal = post_tax.iter().map(|(_, amount)| amount).sum();
+        add_recurring_lines(&mut item.other_deductions, &post_tax);
+        item.total_deductions += taken;
+        item.net_pay -= taken;
+
+        self.net_pay_floor_ledger.record(NetPayFloorOutcome {
+            payroll_run_id: item.payroll_run_id,
+            employee_id: item.employee_id,
+            floor,
+            net_pay: item.net_pay,
+            deferred,
+            shortfall: (floor - item.net_pay).max(Decimal::ZERO),
+        });
+        item
+    }
+
+    /// Recurring deductions due from a payslip, rounded, by name
+    fn recurring_lines(
         &self,
-        payroll_run_id: Uuid,
-        employee: &EmployeeSalary,
-    ) -> Result<PayrollItem, PayrollError> {
+        payroll_run: &PayrollRun,
+        employee_id: Uuid,
+        country_code: &str,
+        gross_pay: Decimal,
+        pre_tax: bool,
+    ) -> Vec<(String, Decimal)> {
+        let rounding = self.rounding_for(country_code);
+        self.recurring
+            .active(employee_id, payroll_run.period_start, payroll_run.period_end, pre_tax)
+            .into_iter()
+            .map(|d| (d.name, rounding.round(d.amount.for_gross(gross_pay))))
+            .collect()
+    }
+
+    /// Periods an employee's pay is annualized over, counting the pay dates
+    /// in the calendar year of the run's period end
+    fn annualization_for(&self, payroll_run: &PayrollRun, employee: &EmployeeSalary) -> Annualization {
+        Annualization::for_pay_date(
+            &self.calendar(&employee.country_code),
+            &employee.pay_frequency,
+            payroll_run.period_end,
+            self.extra_period_policy,
+        )
+        .with_decimal_places(self.rounding_for(&employee.country_code).decimal_places)
+    }
+
+    /// Tenant rounding at the minor unit of the currency `country_code` pays
+    /// in; engines and calculators outside the service take it from here
+    pub fn rounding_for(&self, country_code: &str) -> MoneyRounding {
+        self.rounding.in_country(country_code)
+    }
+
+    /// Store a run's items; they count towards YTD once the run is paid
+    fn store_items(&self, payroll_run: &PayrollRun, items: &[PayrollItem]) {
+        self.run_items.insert(payroll_run.id, items.to_vec());
+    }
+
+    /// Calculate individual payslip
+    fn calculate_payslip(&self, payroll_run: &PayrollRun, employee: &EmployeeSalary) -> Result<PayrollItem, PayrollError> {
+        if !self.supports_country(&employee.country_code) {
+            return Err(PayrollError::UnsupportedCountry(employee.country_code.clone()));
+        }
+        let payroll_run_id = payroll_run.id;
+        // A starter who joined mid-period is paid for the days employed.
+        // Contributions are on that prorated pay, except in schemes owed on a
+        // full period's pay for any day of cover.
+        let earned = employee.start_date.map_or(Decimal::ONE, |start_date| {
+            active_fraction(payroll_run.period_start, payroll_run.period_end, start_date, None)
+        });
+        let rounding = self.rounding_for(&employee.country_code);
+        let full_period = employee;
+        let employee = &full_period.prorated(earned, |amount| rounding.round(amount));
+        let contribution_base =
+            if !earned.is_zero() && self.social_security.owes_full_period(&employee.country_code) { full_period } else { employee };
+        if employee.country_code.eq_ignore_ascii_case("ZA") {
+            return Ok(self.calculate_za_payslip(payroll_run, employee, contribution_base));
+        }
+        if employee.country_code.eq_ignore_ascii_case("RU") {
+            return Ok(self.calculate_ru_payslip(payroll_run, employee));
+        }
+
         // Calculate gross pay
         let gross_pay = employee.basic_salary
             + employee.housing_allowance
@@ -148,23 +749,40 @@ impl PayrollService {
 
         // Calculate pension (based on Basic + Housing + Transport)
         let pension_calc = self.pension_calculator.calculate(
-            employee.basic_salary,
-            employee.housing_allowance,
-            employee.transport_allowance,
+            contribution_base.basic_salary,
+            contribution_base.housing_allowance,
+            contribution_base.transport_allowance,
         );
 
-        // Calculate PAYE tax (monthly)
-        let tax_calc = self.tax_calculator.calculate_monthly_paye(
-            gross_pay,
+        // Pre-tax recurring deductions come off taxable pay
+        let pre_tax = self.recurring_lines(payroll_run, employee.employee_id, &employee.country_code, gross_pay, true);
+        let pre_tax_total: Decimal = pre_tax.iter().map(|(_, amount)| amount).sum();
+
+        // Calculate PAYE tax for the employee's pay period
+        let annualization = self.annualization_for(payroll_run, employee);
+        let tax_calc = self.tax_calculator.calculate_annualized_paye(
+            gross_pay - pre_tax_total,
             pension_calc.employee_contribution,
             pension_calc.nhf_contribution,
+            &annualization,
         );
 
+        // Round each line once; totals are sums of rounded lines
+        let round = |amount| rounding.round(amount);
+        let gross_pay = round(gross_pay);
+        let paye_tax = round(tax_calc.period_tax);
+        let pension_employee = round(pension_calc.employee_contribution);
+        let pension_employer = round(pension_calc.employer_contribution);
+        let nhf_deduction = round(pension_calc.nhf_contribution);
+        let loan_repayment = round(annualization.monthly_to_period(employee.loan_monthly_repayment));
+
         // Calculate total deductions
-        let total_deductions = tax_calc.monthly_tax
-            + pension_calc.employee_contribution
-            + pension_calc.nhf_contribution
-            + employee.loan_monthly_repayment;
+        let total_deductions = paye_tax
+            + pension_employee
+            + nhf_deduction
+            + loan_repayment
+            + employee.benefit_employee_total()
+            + pre_tax_total;
 
         // Calculate net pay
         let net_pay = gross_pay - total_deductions;
@@ -182,13 +800,13 @@ impl PayrollService {
             other_allowances: employee.other_allowances.clone(),
             gross_pay,
             
-            paye_tax: tax_calc.monthly_tax,
-            pension_employee: pension_calc.employee_contribution,
-            pension_employer: pension_calc.employer_contribution,
-            nhf_deduction: pension_calc.nhf_contribution,
+            paye_tax,
+            pension_employee,
+            pension_employer,
+            nhf_deduction,
             
-            loan_repayment: employee.loan_monthly_repayment,
-            other_deductions: serde_json::json!({}),
+            loan_repayment,
+            other_deductions: other_deductions(serde_json::json!({}), employee, &pre_tax),
             total_deductions,
             
             net_pay,
@@ -197,107 +815,839 @@ impl PayrollService {
             account_number: employee.account_number.clone(),
             account_name: employee.account_name.clone(),
             
+            department_id: employee.department_id,
+            
+            employer_contributions: employer_contributions(&[("pension", pension_employer)], employee, rounding),
+            
             created_at: Utc::now(),
         })
     }
 
-    /// Approve payroll run
-    pub fn approve_payroll(
+    /// South African payslip: PAYE from the tax year's table plus employee
+    /// UIF on `contribution_base`
+    fn calculate_za_payslip(
         &self,
-        payroll_run: &mut PayrollRun,
-        approver_id: Uuid,
-    ) -> Result<(), PayrollError> {
-        if !payroll_run.can_be_approved() {
-            return Err(PayrollError::CannotApprove);
-        }
+        payroll_run: &PayrollRun,
+        employee: &EmployeeSalary,
+        contribution_base: &EmployeeSalary,
+    ) -> PayrollItem {
+        let gross_pay = employee.basic_salary
+            + employee.housing_allowance
+            + employee.transport_allowance
+            + employee.meal_allowance
+            + employee.utility_allowance;
 
-        payroll_run.status = PayrollRunStatus::Approved;
-        payroll_run.approved_by = Some(approver_id);
-        payroll_run.approved_at = Some(Utc::now());
-        payroll_run.updated_at = Utc::now();
+        let pre_tax = self.recurring_lines(payroll_run, employee.employee_id, &employee.country_code, gross_pay, true);
+        let pre_tax_total: Decimal = pre_tax.iter().map(|(_, amount)| amount).sum();
 
-        Ok(())
-    }
+        let config = self.tax_tables.south_africa(TaxTables::tax_year_for("ZA", payroll_run.period_end));
+        // Age-based rebates need a date of birth, which salary records don't carry
+        let calculator = SouthAfricaTaxCalculator::with_config(config);
+        // PAYE on pay after pre-tax deductions; the skills levy is on full remuneration
+        let annualization = self.annualization_for(payroll_run, employee);
+        let tax = calculator.calculate_annualized(gross_pay - pre_tax_total, 0, &annualization);
+        let sdl = calculator.calculate_annualized(gross_pay, 0, &annualization).sdl;
+        let uif_base = contribution_base.basic_salary
+            + contribution_base.housing_allowance
+            + contribution_base.transport_allowance
+            + contribution_base.meal_allowance
+            + contribution_base.utility_allowance;
+        let uif = calculator.uif_annualized(uif_base, &annualization);
 
-    /// Mark payroll as paid
-    pub fn mark_as_paid(
-        &self,
-        payroll_run: &mut PayrollRun,
-    ) -> Result<(), PayrollError> {
-        if payroll_run.status != PayrollRunStatus::Approved {
-            return Err(PayrollError::Validation(
-                "Payroll must be approved before marking as paid".to_string()
-            ));
-        }
+        let rounding = self.rounding_for(&employee.country_code);
+        let round = |amount| rounding.round(amount);
+        let gross_pay = round(gross_pay);
+        let paye_tax = round(tax.monthly_paye);
+        let uif_employee = round(uif);
+        let employer = [("uif", round(uif)), ("sdl", round(sdl))];
+        let loan_repayment = round(annualization.monthly_to_period(employee.loan_monthly_repayment));
+        let total_deductions = paye_tax
+            + uif_employee
+            + loan_repayment
+            + employee.benefit_employee_total()
+            + pre_tax_total;
 
-        payroll_run.status = PayrollRunStatus::Paid;
-        payroll_run.updated_at = Utc::now();
+        PayrollItem {
+            id: Uuid::new_v4(),
+            payroll_run_id: payroll_run.id,
+            employee_id: employee.employee_id,
 
-        Ok(())
-    }
+            basic_salary: employee.basic_salary,
+            housing_allowance: employee.housing_allowance,
+            transport_allowance: employee.transport_allowance,
+            meal_allowance: employee.meal_allowance,
+            utility_allowance: employee.utility_allowance,
+            other_allowances: employee.other_allowances.clone(),
+            gross_pay,
 
-    /// Generate pension schedule for PFA remittance
-    pub fn generate_pension_schedule(
-        &self,
-        items: &[PayrollItem],
-        employee_details: &HashMap<Uuid, EmployeeSalary>,
-        period: &str,
-    ) -> Vec<PensionSchedule> {
-        // Group by PFA
-        let mut by_pfa: HashMap<String, Vec<PensionScheduleEntry>> = HashMap::new();
+            paye_tax,
+            pension_employee: Decimal::ZERO,
+            pension_employer: Decimal::ZERO,
+            nhf_deduction: Decimal::ZERO,
 
-        for item in items {
-            if let Some(employee) = employee_details.get(&item.employee_id) {
-                let pfa = "Default PFA".to_string(); // Would come from employee record
-                
-                let entry = PensionScheduleEntry {
-                    employee_name: employee.employee_name.clone(),
-                    pension_pin: employee.pension_pin.clone(),
-                    rsa_number: None,
-                    employee_contribution: item.pension_employee,
-                    employer_contribution: item.pension_employer,
-                    total: item.pension_employee + item.pension_employer,
-                };
+            loan_repayment,
+            other_deductions: other_deductions(serde_json::json!({ "uif": uif_employee }), employee, &pre_tax),
+            total_deductions,
 
-                by_pfa.entry(pfa).or_default().push(entry);
-            }
-        }
+            net_pay: gross_pay - total_deductions,
 
-        // Create schedules
-        by_pfa.into_iter().map(|(pfa_name, entries)| {
-            let total_employee: Decimal = entries.iter().map(|e| e.employee_contribution).sum();
-            let total_employer: Decimal = entries.iter().map(|e| e.employer_contribution).sum();
+            bank_name: employee.bank_name.clone(),
+            account_number: employee.account_number.clone(),
+            account_name: employee.account_name.clone(),
 
-            PensionSchedule {
-                period: period.to_string(),
-                pfa_name,
-                entries,
-                total_employee,
-                total_employer,
-                grand_total: total_employee + total_employer,
-            }
-        }).collect()
-    }
+            department_id: employee.department_id,
 
-    /// Calculate tax preview without creating payroll
-    pub fn calculate_tax_preview(
-        &self,
-        monthly_gross: Decimal,
-    ) -> TaxPreviewResponse {
-        let pension_calc = self.pension_calculator.calculate(
-            monthly_gross * dec!(0.60), // Assume 60% is basic
-            monthly_gross * dec!(0.25), // 25% housing
-            monthly_gross * dec!(0.15), // 15% transport
-        );
+            employer_contributions: employer_contributions(&employer, employee, rounding),
 
-        let tax_calc = self.tax_calculator.calculate_monthly_paye(
-            monthly_gross,
-            pension_calc.employee_contribution,
-            pension_calc.nhf_contribution,
-        );
+            created_at: Utc::now(),
+        }
+    }
 
-        let total_deductions = tax_calc.monthly_tax
-            + pension_calc.employee_contribution
+    /// Russian payslip: NDFL at 13%, or 15% on pay taking the year past
+    /// 5M RUB counting earlier paid runs, plus employer insurance
+    fn calculate_ru_payslip(&self, payroll_run: &PayrollRun, employee: &EmployeeSalary) -> PayrollItem {
+        let gross_pay = employee.basic_salary
+            + employee.housing_allowance
+            + employee.transport_allowance
+            + employee.meal_allowance
+            + employee.utility_allowance;
+
+        let pre_tax = self.recurring_lines(payroll_run, employee.employee_id, &employee.country_code, gross_pay, true);
+        let pre_tax_total: Decimal = pre_tax.iter().map(|(_, amount)| amount).sum();
+
+        let ytd_income = self.ytd.gross_before(employee.employee_id, payroll_run.period_end);
+        let tax = RussiaTaxCalculator::calculate(gross_pay - pre_tax_total, ytd_income);
+
+        let rounding = self.rounding_for(&employee.country_code);
+        let round = |amount| rounding.round(amount);
+        let gross_pay = round(gross_pay);
+        let paye_tax = round(tax.ndfl);
+        let employer = [("pfr", round(tax.pfr_employer)), ("fss", round(tax.fss_employer)), ("foms", round(tax.foms_employer))];
+        let annualization = self.annualization_for(payroll_run, employee);
+        let loan_repayment = round(annualization.monthly_to_period(employee.loan_monthly_repayment));
+        let total_deductions = paye_tax + loan_repayment + employee.benefit_employee_total() + pre_tax_total;
+
+        PayrollItem {
+            id: Uuid::new_v4(),
+            payroll_run_id: payroll_run.id,
+            employee_id: employee.employee_id,
+
+            basic_salary: employee.basic_salary,
+            housing_allowance: employee.housing_allowance,
+            transport_allowance: employee.transport_allowance,
+            meal_allowance: employee.meal_allowance,
+            utility_allowance: employee.utility_allowance,
+            other_allowances: employee.other_allowances.clone(),
+            gross_pay,
+
+            paye_tax,
+            pension_employee: Decimal::ZERO,
+            pension_employer: Decimal::ZERO,
+            nhf_deduction: Decimal::ZERO,
+
+            loan_repayment,
+            other_deductions: other_deductions(serde_json::json!({}), employee, &pre_tax),
+            total_deductions,
+
+            net_pay: gross_pay - total_deductions,
+
+            bank_name: employee.bank_name.clone(),
+            account_number: employee.account_number.clone(),
+            account_name: employee.account_name.clone(),
+
+            department_id: employee.department_id,
+
+            employer_contributions: employer_contributions(&employer, employee, rounding),
+
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Total employer cost (gross + employer contributions) per department
+    /// for a processed run, converted at `exchange_rate` into `currency`
+    pub fn employer_cost_by_department(
+        &self,
+        run_id: Uuid,
+        currency: &str,
+        exchange_rate: Decimal,
+    ) -> Result<DepartmentCostReport, PayrollError> {
+        if exchange_rate <= Decimal::ZERO {
+            return Err(PayrollError::Validation("Exchange rate must be positive".to_string()));
+        }
+        let items = self.run_items.get(&run_id).ok_or(PayrollError::NotFound(run_id))?;
+
+        let mut by_department: BTreeMap<String, Decimal> = BTreeMap::new();
+        for item in items.iter() {
+            let department = item
+                .department_id
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| DepartmentCostReport::UNASSIGNED.to_string());
+            *by_department.entry(department).or_default() += item.employer_cost() * exchange_rate;
+        }
+        let rounding = self.rounding.in_currency(currency);
+        for cost in by_department.values_mut() {
+            *cost = rounding.round(*cost);
+        }
+
+        Ok(DepartmentCostReport {
+            payroll_run_id: run_id,
+            currency: currency.to_string(),
+            exchange_rate,
+            total: by_department.values().sum(),
+            by_department,
+        })
+    }
+
+    /// `employer_cost_by_department` at the rate on file for the run's
+    /// period end, so a June run converts at June's rate
+    pub fn employer_cost_by_department_at_run_rate(
+        &self,
+        run_id: Uuid,
+        currency: &str,
+    ) -> Result<DepartmentCostReport, PayrollError> {
+        let run = self.runs.get(run_id).ok_or(PayrollError::NotFound(run_id))?;
+        let pay_currencies: std::collections::BTreeSet<&str> = self
+            .run_items
+            .get(&run_id)
+            .ok_or(PayrollError::NotFound(run_id))?
+            .iter()
+            .map(|item| self.item_currency(run_id, item.employee_id))
+            .collect::<Result<_, _>>()?;
+        if pay_currencies.len() != 1 {
+            return Err(PayrollError::Validation(format!(
+                "Run pays in {} currencies; convert each legal entity's run separately",
+                pay_currencies.len()
+            )));
+        }
+        let pay_currency = pay_currencies.into_iter().next().unwrap_or_default();
+        let rate = self.fx_rates.rate_as_of(pay_currency, currency, run.period_end)?;
+        self.employer_cost_by_department(run_id, currency, rate.rate)
+    }
+
+    /// Pay `amount` to an employee now, off-cycle, and recover it in equal
+    /// instalments from their next `recover_over_periods` runs. Tax is
+    /// withheld only where the employee's country taxes advances when paid;
+    /// elsewhere the regular runs tax the wages as usual. The net payment
+    /// is recorded in the disbursement ledger under the advance's id. Only
+    /// countries whose payslips the service calculates can be advanced,
+    /// since those runs recover it.
+    pub fn salary_advance(
+        &self,
+        employee: &EmployeeSalary,
+        amount: Decimal,
+        recover_over_periods: u32,
+        channel: DisbursementChannel,
+    ) -> Result<SalaryAdvance, PayrollError> {
+        if amount <= Decimal::ZERO {
+            return Err(PayrollError::Validation("Advance amount must be positive".to_string()));
+        }
+        if recover_over_periods == 0 {
+            return Err(PayrollError::Validation("Advance must be recovered over at least one period".to_string()));
+        }
+
+        if !self.supports_country(&employee.country_code) {
+            return Err(PayrollError::UnsupportedCountry(employee.country_code.clone()));
+        }
+        let rounding = self.rounding_for(&employee.country_code);
+        let currency = PayrollRegistry::currency_for(&employee.country_code)
+            .ok_or_else(|| PayrollError::UnsupportedCountry(employee.country_code.clone()))?;
+        let tax_treatment = AdvanceTaxTreatment::for_country(&employee.country_code);
+        let tax_withheld = rounding.round(tax_treatment.tax_on(amount));
+        let net_paid = amount - tax_withheld;
+
+        // Round instalments up so the last period never leaves a residual
+        let per_period = (amount / Decimal::from(recover_over_periods))
+            .round_dp_with_strategy(rounding.decimal_places, rust_decimal::RoundingStrategy::AwayFromZero);
+        let schedule = RepaymentSchedule::new(employee.employee_id, RepaymentKind::SalaryAdvance, amount, per_period);
+        let id = Uuid::new_v4();
+        let disbursed = self.disbursements.disburse_once(id, employee.employee_id, net_paid, currency, channel);
+        let advance = SalaryAdvance {
+            id,
+            employee_id: employee.employee_id,
+            amount,
+            currency: currency.to_string(),
+            tax_treatment,
+            tax_withheld,
+            net_paid,
+            disbursement_id: disbursed.record().id,
+            recover_over_periods,
+            repayment_schedule_id: schedule.id,
+            paid_at: Utc::now(),
+        };
+        self.repayments.add(schedule);
+        self.advances.record(advance.clone());
+        Ok(advance)
+    }
+
+    /// General ledger journal for an advance paid by `salary_advance`
+    pub fn advance_journal(&self, advance_id: Uuid, accounts: &GlAccountMap) -> Result<GlJournal, PayrollError> {
+        let advance = self
+            .advances
+            .get(advance_id)
+            .ok_or_else(|| PayrollError::Validation(format!("Salary advance not found: {}", advance_id)))?;
+        Ok(gl::advance_journal(&advance, accounts))
+    }
+
+    pub fn advances(&self) -> &SalaryAdvances {
+        &self.advances
+    }
+
+    /// Department tree with payroll budgets
+    pub fn departments(&self) -> &Departments {
+        &self.departments
+    }
+
+    /// Budgeted vs actual employer cost for a department in a processed
+    /// run, rolled up over its sub-departments
+    pub fn budget_variance(&self, department_id: Uuid, run_id: Uuid) -> Result<BudgetVariance, PayrollError> {
+        let department = self.departments.get(department_id).ok_or(PayrollError::DepartmentNotFound(department_id))?;
+        let items = self.run_items.get(&run_id).ok_or(PayrollError::NotFound(run_id))?;
+        Ok(self.departments.variance(&department, &items))
+    }
+
+    /// Employer-side contributions per employee and in aggregate for a
+    /// processed run. Every employee lists every contribution type in the
+    /// run, so a country without a given contribution reports zero.
+    pub fn employer_contributions_report(&self, run_id: Uuid) -> Result<EmployerContributionsReport, PayrollError> {
+        let items = self.run_items.get(&run_id).ok_or(PayrollError::NotFound(run_id))?;
+        let inputs = self.run_inputs.get(&run_id).map(|e| e.clone()).unwrap_or_default();
+        let employee = |id: Uuid| inputs.iter().find(|e| e.employee_id == id);
+
+        let contribution_types: Vec<String> = items
+            .iter()
+            .flat_map(|item| item.employer_contributions.keys().cloned())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let zeroed: BTreeMap<String, Decimal> =
+            contribution_types.iter().map(|code| (code.clone(), Decimal::ZERO)).collect();
+
+        let mut totals = zeroed.clone();
+        let employees: Vec<EmployerContributionLine> = items
+            .iter()
+            .map(|item| {
+                let mut contributions = zeroed.clone();
+                contributions.extend(item.employer_contributions.clone());
+                for (code, amount) in &contributions {
+                    *totals.entry(code.clone()).or_default() += amount;
+                }
+                let salary = employee(item.employee_id);
+                EmployerContributionLine {
+                    employee_id: item.employee_id,
+                    employee_name: salary.map(|e| e.employee_name.clone()).unwrap_or_default(),
+                    country_code: salary.map(|e| e.country_code.clone()).unwrap_or_default(),
+                    total: contributions.values().sum(),
+                    contributions,
+                }
+            })
+            .collect();
+
+        Ok(EmployerContributionsReport {
+            payroll_run_id: run_id,
+            contribution_types,
+            total: totals.values().sum(),
+            employees,
+            totals,
+        })
+    }
+
+    /// Anomalies in a processed run for approvers to review before approval,
+    /// most severe first. Net pay is compared with each employee's latest
+    /// earlier run that was not cancelled or failed.
+    pub fn preflight(&self, run_id: Uuid) -> Result<Vec<PreflightFinding>, PayrollError> {
+        let run = self.runs.get(run_id).ok_or(PayrollError::NotFound(run_id))?;
+        let items = self.run_items.get(&run_id).map(|items| items.clone()).ok_or(PayrollError::NotFound(run_id))?;
+        let inputs = self.run_inputs.get(&run_id).map(|e| e.clone()).unwrap_or_default();
+
+        let earlier: Vec<(NaiveDate, Vec<PayrollItem>)> = self
+            .run_items
+            .iter()
+            .filter(|entry| *entry.key() != run_id)
+            .filter_map(|entry| {
+                let previous = self.runs.get(*entry.key())?;
+                let counts = !matches!(previous.status, PayrollRunStatus::Cancelled | PayrollRunStatus::Failed);
+                (counts && previous.period_end < run.period_start).then(|| (previous.period_end, entry.value().clone()))
+            })
+            .collect();
+        let previous_net = |employee_id: Uuid| {
+            earlier
+                .iter()
+                .filter_map(|(period_end, items)| {
+                    items.iter().find(|i| i.employee_id == employee_id).map(|i| (*period_end, i.net_pay))
+                })
+                .max_by_key(|(period_end, _)| *period_end)
+                .map(|(_, net)| net)
+        };
+
+        let mut findings = Vec::new();
+        for employee in &inputs {
+            match items.iter().find(|item| item.employee_id == employee.employee_id) {
+                Some(item) => {
+                    findings.extend(self.preflight.check_item(employee, item, previous_net(employee.employee_id)));
+                    if let Some(outcome) = self.net_pay_floor_ledger.get(run_id, item.employee_id) {
+                        findings.extend(self.preflight.below_net_pay_floor(employee, &outcome));
+                    }
+                }
+                None if !self.supports_country(&employee.country_code) => {
+                    findings.push(self.preflight.unsupported_country(employee))
+                }
+                None => {}
+            }
+        }
+        findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+        Ok(findings)
+    }
+
+    /// Check gross = net + itemised deductions for every payslip in a
+    /// processed run and for the run as a whole
+    pub fn reconcile(&self, run_id: Uuid) -> Result<ReconciliationReport, PayrollError> {
+        let items = self.run_items.get(&run_id).ok_or(PayrollError::NotFound(run_id))?;
+        let tolerance =
+            self.reconciliation_tolerance.unwrap_or_else(|| Decimal::new(1, self.rounding.decimal_places));
+        let mut report = reconcile::reconcile(run_id, &items, tolerance);
+        report.tax_overrides = self.tax_overrides.applied_for_run(run_id);
+        if !report.is_reconciled() {
+            tracing::warn!(run_id = %run_id, discrepancies = report.discrepancies.len(), "payroll run does not reconcile");
+        }
+        Ok(report)
+    }
+
+    /// General ledger journal for a processed run, one balanced set of
+    /// lines per payroll currency. Fails rather than guess the currency of
+    /// an employee without a salary record or in a country without one.
+    pub fn gl_journal(&self, run_id: Uuid, accounts: &GlAccountMap) -> Result<GlJournal, PayrollError> {
+        let items = self.run_items.get(&run_id).ok_or(PayrollError::NotFound(run_id))?;
+        let inputs = self.run_inputs.get(&run_id).map(|e| e.clone()).unwrap_or_default();
+
+        let lines = items
+            .iter()
+            .map(|item| {
+                let input = inputs.iter().find(|e| e.employee_id == item.employee_id).ok_or_else(|| {
+                    PayrollError::Validation(format!("No salary record for employee {} in payroll run {}", item.employee_id, run_id))
+                })?;
+                Ok((payroll_currency(&input.country_code)?, item))
+            })
+            .collect::<Result<Vec<_>, PayrollError>>()?;
+        Ok(gl::build_journal(run_id, lines, accounts))
+    }
+
+    /// A legal entity's statutory return for runs ending in the period.
+    /// Only approved and paid runs are filed.
+    pub fn statutory_report(
+        &self,
+        legal_entity_id: Uuid,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> Result<StatutoryReport, PayrollError> {
+        let entity = self
+            .legal_entities
+            .get(legal_entity_id)
+            .ok_or_else(|| PayrollError::Validation(format!("Legal entity not found: {}", legal_entity_id)))?;
+        let format = self
+            .statutory_formats
+            .for_country(&entity.country)
+            .ok_or_else(|| PayrollError::UnsupportedCountry(entity.country.clone()))?;
+
+        let mut runs: Vec<PayrollRun> = self
+            .run_items
+            .iter()
+            .filter_map(|entry| self.runs.get(*entry.key()))
+            .filter(|run| {
+                run.legal_entity_id == Some(legal_entity_id)
+                    && matches!(run.status, PayrollRunStatus::Approved | PayrollRunStatus::Paid)
+                    && run.period_end >= period_start
+                    && run.period_end <= period_end
+            })
+            .collect();
+        runs.sort_by_key(|run| (run.period_end, run.created_at));
+
+        let payslips: Vec<(PayrollItem, Option<EmployeeSalary>)> = runs
+            .iter()
+            .flat_map(|run| {
+                let items = self.run_items.get(&run.id).map(|i| i.clone()).unwrap_or_default();
+                let inputs = self.run_inputs.get(&run.id).map(|i| i.clone()).unwrap_or_default();
+                items.into_iter().map(move |item| {
+                    let salary = inputs.iter().find(|e| e.employee_id == item.employee_id).cloned();
+                    (item, salary)
+                })
+            })
+            .collect();
+
+        Ok(StatutoryReport::for_period(
+            &entity,
+            period_start,
+            period_end,
+            format,
+            payslips.iter().map(|(item, salary)| (item, salary.as_ref())),
+        ))
+    }
+
+    /// Net-pay currency for an employee in a processed run. Paying in a
+    /// guessed currency is worse than not paying, so there is no default.
+    fn item_currency(&self, run_id: Uuid, employee_id: Uuid) -> Result<&'static str, PayrollError> {
+        let country_code = self
+            .run_inputs
+            .get(&run_id)
+            .and_then(|inputs| inputs.iter().find(|e| e.employee_id == employee_id).map(|e| e.country_code.clone()))
+            .ok_or_else(|| {
+                PayrollError::Validation(format!("No salary record for employee {} in payroll run {}", employee_id, run_id))
+            })?;
+        payroll_currency(&country_code)
+    }
+
+    /// Record one employee's net pay from an approved run as disbursed,
+    /// returning the existing record if it already was
+    pub fn disburse_once(
+        &self,
+        tenant_id: Uuid,
+        run_id: Uuid,
+        employee_id: Uuid,
+        channel: DisbursementChannel,
+    ) -> Result<Disbursed, PayrollError> {
+        // Another tenant's run is reported as missing, not forbidden
+        let run = self
+            .runs
+            .get(run_id)
+            .filter(|run| run.tenant_id == tenant_id)
+            .ok_or(PayrollError::NotFound(run_id))?;
+        if !matches!(run.status, PayrollRunStatus::Approved | PayrollRunStatus::Paid) {
+            return Err(PayrollError::Validation(
+                "Payroll must be approved before disbursement".to_string()
+            ));
+        }
+        let net_pay = self
+            .run_items
+            .get(&run_id)
+            .and_then(|items| items.iter().find(|item| item.employee_id == employee_id).map(|item| item.net_pay))
+            .ok_or_else(|| PayrollError::Validation(format!("Employee {} is not in payroll run {}", employee_id, run_id)))?;
+
+        let currency = self.item_currency(run_id, employee_id)?;
+        Ok(self.disbursements.disburse_once(run_id, employee_id, net_pay, currency, channel))
+    }
+
+    /// Bank payment file for employees in an approved run whose payment
+    /// isn't confirmed. Regenerating it after a failed upload repeats the
+    /// pending lines under their original references, so the bank can
+    /// reject any that did go through; confirmed payments are left out.
+    pub fn payment_file(&self, tenant_id: Uuid, run_id: Uuid) -> Result<String, PayrollError> {
+        let items = self.run_items.get(&run_id).map(|items| items.clone()).ok_or(PayrollError::NotFound(run_id))?;
+
+        let mut paid = Vec::new();
+        for item in items.iter().filter(|item| item.account_number.is_some()) {
+            let disbursed = self.disburse_once(tenant_id, run_id, item.employee_id, DisbursementChannel::BankTransfer)?;
+            if disbursed.needs_sending() {
+                paid.push((item, disbursed.record().clone()));
+            }
+        }
+
+        let lines: Vec<PaymentFileLine<'_>> = paid
+            .iter()
+            .map(|(item, disbursement)| PaymentFileLine {
+                disbursement,
+                bank_name: item.bank_name.as_deref().unwrap_or_default(),
+                account_number: item.account_number.as_deref().unwrap_or_default(),
+                account_name: item.account_name.as_deref().unwrap_or_default(),
+            })
+            .collect();
+        Ok(disbursement::render_payment_file(&lines))
+    }
+
+    /// Mobile money request paying one employee, or `None` if their net pay
+    /// from this run is confirmed paid. A pending payment is requested again
+    /// under its original reference, which the provider deduplicates.
+    pub fn mobile_money_payment(
+        &self,
+        tenant_id: Uuid,
+        run_id: Uuid,
+        employee_id: Uuid,
+        recipient: &MobileMoneyDisbursementRequest,
+    ) -> Result<Option<PaymentRequest>, PayrollError> {
+        let disbursed = self.disburse_once(tenant_id, run_id, employee_id, DisbursementChannel::MobileMoney)?;
+        if !disbursed.needs_sending() {
+            return Ok(None);
+        }
+        Ok(Some(disbursed.record().payment_request(
+            &recipient.phone_number,
+            &recipient.recipient_name,
+            &recipient.country,
+            recipient.provider.clone(),
+        )))
+    }
+
+    /// Mark employees' payments from a run confirmed by the bank or
+    /// provider, so they are never sent again
+    pub fn confirm_disbursements(
+        &self,
+        tenant_id: Uuid,
+        run_id: Uuid,
+        employee_ids: &[Uuid],
+    ) -> Result<Vec<Disbursement>, PayrollError> {
+        self.runs
+            .get(run_id)
+            .filter(|run| run.tenant_id == tenant_id)
+            .ok_or(PayrollError::NotFound(run_id))?;
+        if let Some(missing) = employee_ids.iter().find(|id| self.disbursements.get(run_id, **id).is_none()) {
+            return Err(PayrollError::Validation(format!("Employee {} has no disbursement in payroll run {}", missing, run_id)));
+        }
+        Ok(employee_ids.iter().filter_map(|id| self.disbursements.confirm(run_id, *id)).collect())
+    }
+
+    /// Ledger of payments sent for processed runs
+    pub fn disbursements(&self) -> &DisbursementLedger {
+        &self.disbursements
+    }
+
+    /// Year-to-date totals across processed runs
+    pub fn ytd_summary(&self, employee_id: Uuid, year: i32) -> YtdSummary {
+        self.ytd.summary(employee_id, year)
+    }
+
+    /// YTD store shared with cap-aware calculators
+    pub fn ytd(&self) -> &YtdStore {
+        &self.ytd
+    }
+
+    /// Ask for a manual tax override on a draft run. It changes the
+    /// employee's payslip when the run is processed, once approved.
+    pub fn request_tax_override(&self, tax_override: TaxOverride) -> Result<TaxOverride, PayrollError> {
+        let run = self.runs.get(tax_override.run_id).ok_or(PayrollError::NotFound(tax_override.run_id))?;
+        if !run.is_draft() {
+            return Err(PayrollError::NotDraft);
+        }
+        self.tax_overrides.request(tax_override.clone());
+        self.audit.append(
+            AuditLog::new(
+                run.tenant_id,
+                "tax_override",
+                tax_override.id,
+                AuditAction::Create,
+                Some(tax_override.requested_by),
+                ActorType::User,
+            )
+            .with_changes(serde_json::Value::Null, serde_json::to_value(&tax_override).unwrap_or_default()),
+        );
+        Ok(tax_override)
+    }
+
+    /// Approve a pending tax override; the requester can't approve their own
+    pub fn approve_tax_override(&self, override_id: Uuid, approver_id: Uuid) -> Result<TaxOverride, PayrollError> {
+        self.decide_tax_override(override_id, approver_id, true)
+    }
+
+    pub fn reject_tax_override(&self, override_id: Uuid, approver_id: Uuid) -> Result<TaxOverride, PayrollError> {
+        self.decide_tax_override(override_id, approver_id, false)
+    }
+
+    fn decide_tax_override(&self, override_id: Uuid, approver_id: Uuid, approve: bool) -> Result<TaxOverride, PayrollError> {
+        let pending = self.tax_overrides.get(override_id).ok_or(PayrollError::TaxOverrideNotFound(override_id))?;
+        if pending.status != TaxOverrideStatus::PendingApproval {
+            return Err(PayrollError::Validation("Tax override has already been decided".to_string()));
+        }
+        if pending.requested_by == approver_id {
+            return Err(PayrollError::TaxOverrideSelfApproval);
+        }
+        let run = self.runs.get(pending.run_id).ok_or(PayrollError::NotFound(pending.run_id))?;
+        if !run.is_draft() {
+            return Err(PayrollError::NotDraft);
+        }
+
+        let decided = self
+            .tax_overrides
+            .decide(override_id, approver_id, approve)
+            .ok_or(PayrollError::TaxOverrideNotFound(override_id))?;
+        self.audit.append(
+            AuditLog::new(run.tenant_id, "tax_override", override_id, AuditAction::Update, Some(approver_id), ActorType::User)
+                .with_changes(serde_json::json!({ "status": pending.status }), serde_json::json!({ "status": decided.status })),
+        );
+        Ok(decided)
+    }
+
+    /// Put an approved override's tax on the payslip, moving net pay and
+    /// total deductions by the difference
+    fn apply_tax_override(&self, payroll_run: &PayrollRun, country_code: &str, mut item: PayrollItem) -> PayrollItem {
+        let Some(tax_override) = self.tax_overrides.approved(payroll_run.id, item.employee_id) else {
+            return item;
+        };
+        let computed = item.paye_tax;
+        let applied = self.rounding_for(country_code).round(tax_override.tax_for(computed));
+        let before = serde_json::json!({ "paye_tax": computed, "net_pay": item.net_pay });
+        item.paye_tax = applied;
+        item.total_deductions += applied - computed;
+        item.net_pay -= applied - computed;
+        self.tax_overrides.mark_applied(tax_override.id, computed, applied);
+
+        let mut entry = AuditLog::new(payroll_run.tenant_id, "payroll_item", item.id, AuditAction::Update, None, ActorType::System)
+            .with_changes(before, serde_json::json!({ "paye_tax": applied, "net_pay": item.net_pay }));
+        entry.metadata = serde_json::json!({ "tax_override_id": tax_override.id, "reason": tax_override.reason });
+        self.audit.append(entry);
+        item
+    }
+
+    /// Recover an overpayment on a draft run, as negative earnings on the
+    /// employee's payslip when the run is processed
+    pub fn record_clawback(&self, clawback: Clawback) -> Result<Clawback, PayrollError> {
+        if clawback.amount <= Decimal::ZERO {
+            return Err(PayrollError::Validation("Clawback amount must be positive".to_string()));
+        }
+        let run = self.runs.get(clawback.run_id).ok_or(PayrollError::NotFound(clawback.run_id))?;
+        if !run.is_draft() {
+            return Err(PayrollError::NotDraft);
+        }
+        self.clawbacks.record(clawback.clone());
+        Ok(clawback)
+    }
+
+    /// Take a run's clawbacks off gross pay, give back the withholding on
+    /// them, and move whatever would leave net pay below zero to a recovery
+    /// schedule for the following runs
+    fn apply_clawbacks(&self, payroll_run: &PayrollRun, country_code: &str, mut item: PayrollItem) -> PayrollItem {
+        let clawbacks = self.clawbacks.for_run(payroll_run.id, item.employee_id);
+        // Recalculating a draft run replaces the schedule it set up before
+        for schedule_id in clawbacks.iter().filter_map(|c| c.recovery_schedule_id) {
+            self.repayments.remove(item.employee_id, schedule_id);
+        }
+        let amount: Decimal = clawbacks.iter().map(|c| c.amount).sum();
+        if amount.is_zero() {
+            return item;
+        }
+        let rounding = self.rounding_for(country_code);
+
+        let reversal = if self.clawback_policy.reverse_withholding {
+            let ytd = self.ytd.summary_before(item.employee_id, payroll_run.period_end);
+            let ytd_tax = ytd.taxes.get("paye").copied().unwrap_or_default() + item.paye_tax;
+            rounding.round(withholding_reversal(amount, ytd.gross + item.gross_pay, ytd_tax))
+        } else {
+            Decimal::ZERO
+        };
+        item.other_allowances[CLAWBACK_LINE] = serde_json::json!(-amount);
+        item.gross_pay -= amount;
+        item.paye_tax -= reversal;
+        item.total_deductions -= reversal;
+        item.net_pay -= amount - reversal;
+
+        let carried = (-item.net_pay).max(Decimal::ZERO);
+        let mut schedule_id = None;
+        if !carried.is_zero() {
+            let periods = Decimal::from(self.clawback_policy.recovery_periods.max(1));
+            let per_period = (carried / periods)
+                .round_dp_with_strategy(rounding.decimal_places, rust_decimal::RoundingStrategy::AwayFromZero);
+            let schedule = RepaymentSchedule::new(item.employee_id, RepaymentKind::Clawback, carried, per_period);
+            schedule_id = Some(schedule.id);
+            self.repayments.add(schedule);
+            item.other_deductions[CLAWBACK_CARRIED_FORWARD_LINE] = serde_json::json!(-carried);
+            item.total_deductions -= carried;
+            item.net_pay += carried;
+        }
+        let ids: Vec<Uuid> = clawbacks.iter().map(|c| c.id).collect();
+        self.clawbacks.set_recovery_schedule(&ids, schedule_id);
+        item
+    }
+
+    /// Approve payroll run
+    pub fn approve_payroll(
+        &self,
+        payroll_run: &mut PayrollRun,
+        approver_id: Uuid,
+    ) -> Result<(), PayrollError> {
+        if !payroll_run.can_be_approved() {
+            return Err(PayrollError::CannotApprove);
+        }
+
+        *payroll_run = self.runs.transition_with(
+            payroll_run.id,
+            PayrollRunStatus::PendingApproval,
+            PayrollRunStatus::Approved,
+            |run| {
+                run.approved_by = Some(approver_id);
+                run.approved_at = Some(Utc::now());
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Mark payroll as paid
+    pub fn mark_as_paid(
+        &self,
+        payroll_run: &mut PayrollRun,
+    ) -> Result<(), PayrollError> {
+        if !payroll_run.status.can_transition_to(PayrollRunStatus::Paid) {
+            return Err(PayrollError::Validation(
+                "Payroll must be approved before marking as paid".to_string()
+            ));
+        }
+
+        *payroll_run = self.runs.transition(payroll_run.id, PayrollRunStatus::Approved, PayrollRunStatus::Paid)?;
+        // Only pay actually made counts towards YTD
+        if let Some(items) = self.run_items.get(&payroll_run.id) {
+            for item in items.iter() {
+                self.ytd.record_item(item, payroll_run.period_end);
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate pension schedule for PFA remittance
+    pub fn generate_pension_schedule(
+        &self,
+        items: &[PayrollItem],
+        employee_details: &HashMap<Uuid, EmployeeSalary>,
+        period: &str,
+    ) -> Vec<PensionSchedule> {
+        // Group by PFA
+        let mut by_pfa: HashMap<String, Vec<PensionScheduleEntry>> = HashMap::new();
+
+        for item in items {
+            if let Some(employee) = employee_details.get(&item.employee_id) {
+                let pfa = "Default PFA".to_string(); // Would come from employee record
+                
+                let entry = PensionScheduleEntry {
+                    employee_name: employee.employee_name.clone(),
+                    pension_pin: employee.pension_pin.clone(),
+                    rsa_number: None,
+                    employee_contribution: item.pension_employee,
+                    employer_contribution: item.pension_employer,
+                    total: item.pension_employee + item.pension_employer,
+                };
+
+                by_pfa.entry(pfa).or_default().push(entry);
+            }
+        }
+
+        // Create schedules
+        by_pfa.into_iter().map(|(pfa_name, entries)| {
+            let total_employee: Decimal = entries.iter().map(|e| e.employee_contribution).sum();
+            let total_employer: Decimal = entries.iter().map(|e| e.employer_contribution).sum();
+
+            PensionSchedule {
+                period: period.to_string(),
+                pfa_name,
+                entries,
+                total_employee,
+                total_employer,
+                grand_total: total_employee + total_employer,
+            }
+        }).collect()
+    }
+
+    /// Calculate tax preview without creating payroll
+    pub fn calculate_tax_preview(
+        &self,
+        monthly_gross: Decimal,
+    ) -> TaxPreviewResponse {
+        let pension_calc = self.pension_calculator.calculate(
+            monthly_gross * dec!(0.60), // Assume 60% is basic
+            monthly_gross * dec!(0.25), // 25% housing
+            monthly_gross * dec!(0.15), // 15% transport
+        );
+
+        let tax_calc = self.tax_calculator.calculate_monthly_paye(
+            monthly_gross,
+            pension_calc.employee_contribution,
+            pension_calc.nhf_contribution,
+        );
+
+        let total_deductions = tax_calc.monthly_tax
+            + pension_calc.employee_contribution
             + pension_calc.nhf_contribution;
 
         TaxPreviewResponse {
@@ -313,108 +1663,1092 @@ impl PayrollService {
             effective_tax_rate: tax_calc.effective_rate,
         }
     }
-}
+}
+
+/// Payroll history follows the survivor when duplicate employee records
+/// are merged: payslips, the salary inputs behind them, and YTD totals
+impl EmployeeRecords for PayrollService {
+    fn check_reparent(&self, from: Uuid, _to: Uuid) -> Result<(), String> {
+        let processing = self.run_inputs.iter().find(|entry| {
+            entry.value().iter().any(|e| e.employee_id == from)
+                && self.runs.get(*entry.key()).is_some_and(|run| run.status == PayrollRunStatus::Processing)
+        });
+        match processing {
+            Some(entry) => Err(format!("payroll run {} is being processed", entry.key())),
+            None => Ok(()),
+        }
+    }
+
+    fn reparent(&self, from: Uuid, to: Uuid) -> usize {
+        let mut moved = 0;
+        for mut items in self.run_items.iter_mut() {
+            for item in items.iter_mut().filter(|item| item.employee_id == from) {
+                item.employee_id = to;
+                moved += 1;
+            }
+        }
+        for mut inputs in self.run_inputs.iter_mut() {
+            for input in inputs.iter_mut().filter(|input| input.employee_id == from) {
+                input.employee_id = to;
+                moved += 1;
+            }
+        }
+        moved + self.ytd.reparent(from, to)
+    }
+}
+
+/// Tax preview response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxPreviewResponse {
+    pub gross_monthly: Decimal,
+    pub gross_annual: Decimal,
+    pub paye_monthly: Decimal,
+    pub paye_annual: Decimal,
+    pub pension_employee: Decimal,
+    pub pension_employer: Decimal,
+    pub nhf: Decimal,
+    pub total_deductions: Decimal,
+    pub net_monthly: Decimal,
+    pub effective_tax_rate: Decimal,
+}
+
+use serde::{Deserialize, Serialize};
+
+/// Currency payroll is paid in for a country; a country without one has no payroll
+fn payroll_currency(country_code: &str) -> Result<&'static str, PayrollError> {
+    PayrollRegistry::currency_for(country_code).ok_or_else(|| PayrollError::UnsupportedCountry(country_code.to_string()))
+}
+
+/// Set a run's totals from its calculated items
+fn apply_totals(payroll_run: &mut PayrollRun, items: &[PayrollItem]) {
+    payroll_run.total_employees = items.len() as i32;
+    payroll_run.total_gross = items.iter().map(|i| i.gross_pay).sum();
+    payroll_run.total_deductions = items.iter().map(|i| i.total_deductions).sum();
+    payroll_run.total_net = items.iter().map(|i| i.net_pay).sum();
+    payroll_run.total_employer_contributions = items.iter().map(|i| i.employer_contribution_total()).sum();
+}
+
+/// Payslip "other deductions", with benefit contributions listed under
+/// `benefits` when there are any
+fn other_deductions(mut lines: serde_json::Value, employee: &EmployeeSalary, recurring: &[(String, Decimal)]) -> serde_json::Value {
+    if !employee.benefit_deductions.is_empty() {
+        lines["benefits"] = serde_json::json!(employee.benefit_deductions);
+    }
+    add_recurring_lines(&mut lines, recurring);
+    lines
+}
+
+/// Recurring deductions by name under `recurring`, skipping any that took nothing
+fn add_recurring_lines(lines: &mut serde_json::Value, recurring: &[(String, Decimal)]) {
+    for (name, amount) in recurring.iter().filter(|(_, amount)| !amount.is_zero()) {
+        lines[RECURRING_DEDUCTION_LINE][name] = serde_json::json!(amount);
+    }
+}
+
+/// Employer contributions by type, plus the employer share of any
+/// benefit contributions under `benefits`
+fn employer_contributions(
+    statutory: &[(&str, Decimal)],
+    employee: &EmployeeSalary,
+    rounding: MoneyRounding,
+) -> BTreeMap<String, Decimal> {
+    let mut contributions: BTreeMap<String, Decimal> =
+        statutory.iter().map(|(code, amount)| (code.to_string(), *amount)).collect();
+    if !employee.benefit_deductions.is_empty() {
+        let benefits = employee.benefit_deductions.iter().map(|b| b.employer_amount).sum();
+        contributions.insert("benefits".to_string(), rounding.round(benefits));
+    }
+    contributions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::payroll::budget::Department;
+    use crate::payroll::rounding::RoundingMode;
+    use crate::payroll::disbursement::DisbursementStatus;
+    use crate::payroll::gl::JournalLine;
+    use crate::domain::value_objects::PayFrequency;
+
+    fn create_test_employee() -> EmployeeSalary {
+        EmployeeSalary {
+            employee_id: Uuid::new_v4(),
+            employee_name: "Test Employee".to_string(),
+            employee_code: "EMP001".to_string(),
+            department_id: None,
+            legal_entity_id: None,
+            country_code: "NG".to_string(),
+            basic_salary: dec!(250_000),
+            housing_allowance: dec!(100_000),
+            transport_allowance: dec!(50_000),
+            meal_allowance: dec!(20_000),
+            utility_allowance: dec!(10_000),
+            other_allowances: serde_json::json!({}),
+            bank_name: Some("GTBank".to_string()),
+            account_number: Some("0123456789".to_string()),
+            account_name: Some("Test Employee".to_string()),
+            tin: Some("12345678-0001".to_string()),
+            pension_pin: Some("PEN123456".to_string()),
+            nhf_number: Some("NHF123456".to_string()),
+            loan_balance: Decimal::ZERO,
+            loan_monthly_repayment: Decimal::ZERO,
+            benefit_deductions: vec![],
+            start_date: None,
+            pay_frequency: PayFrequency::Monthly,
+            onboarding_outstanding: Some(vec![]),
+        }
+    }
+
+    #[test]
+    fn test_create_payroll_run() {
+        let service = PayrollService::new();
+        let tenant_id = Uuid::new_v4();
+        
+        let request = CreatePayrollRunRequest {
+            name: "January 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+
+        let run = service.create_payroll_run(tenant_id, request).unwrap();
+        
+        assert_eq!(run.status, PayrollRunStatus::Draft);
+        assert_eq!(run.total_employees, 0);
+    }
+
+    #[test]
+    fn test_process_payroll() {
+        let service = PayrollService::new();
+        let tenant_id = Uuid::new_v4();
+        
+        let request = CreatePayrollRunRequest {
+            name: "January 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+
+        let mut run = service.create_payroll_run(tenant_id, request).unwrap();
+        let employees = vec![create_test_employee()];
+        let processor_id = Uuid::new_v4();
+
+        let items = service.process_payroll(&mut run, employees, processor_id).unwrap().items;
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(run.total_employees, 1);
+        assert!(run.total_gross > Decimal::ZERO);
+        assert!(run.total_net > Decimal::ZERO);
+        assert!(run.total_net < run.total_gross);
+        assert_eq!(run.status, PayrollRunStatus::PendingApproval);
+        
+        // Verify deductions
+        let item = &items[0];
+        assert!(item.paye_tax > Decimal::ZERO);
+        assert!(item.pension_employee > Decimal::ZERO);
+        assert!(item.nhf_deduction > Decimal::ZERO);
+        
+        println!("Gross: ₦{}", item.gross_pay);
+        println!("PAYE: ₦{}", item.paye_tax);
+        println!("Pension (Employee): ₦{}", item.pension_employee);
+        println!("Pension (Employer): ₦{}", item.pension_employer);
+        println!("NHF: ₦{}", item.nhf_deduction);
+        println!("Total Deductions: ₦{}", item.total_deductions);
+        println!("Net Pay: ₦{}", item.net_pay);
+    }
+
+    #[tokio::test]
+    async fn test_background_processing_failure_marks_run_failed() {
+        let service = PayrollService::new();
+        let request = CreatePayrollRunRequest {
+            name: "January 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+        let mut abroad = create_test_employee();
+        abroad.country_code = "XX".to_string();
+
+        let job = service.start_processing(run.id, vec![abroad], Uuid::new_v4()).unwrap();
+        assert_eq!(job.status, PayrollJobStatus::Processing);
+        let job = loop {
+            let job = service.jobs().get(job.id).unwrap();
+            if job.status != PayrollJobStatus::Processing {
+                break job;
+            }
+            tokio::task::yield_now().await;
+        };
+
+        assert_eq!(job.status, PayrollJobStatus::Failed);
+        assert!(job.error.unwrap().contains("XX"));
+        assert_eq!(service.payroll_run(run.id).unwrap().status, PayrollRunStatus::Failed);
+        assert!(matches!(service.start_processing(Uuid::new_v4(), vec![create_test_employee()], Uuid::new_v4()), Err(PayrollError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_recover_stuck_runs() {
+        let service = PayrollService::new();
+        let start = |name: &str, month| {
+            let request = CreatePayrollRunRequest {
+                name: name.to_string(),
+                period_start: NaiveDate::from_ymd_opt(2024, month, 1).unwrap(),
+                period_end: NaiveDate::from_ymd_opt(2024, month, 28).unwrap(),
+                notes: None,
+                legal_entity_id: None,
+            };
+            let run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+            service.runs.transition(run.id, PayrollRunStatus::Draft, PayrollRunStatus::Processing).unwrap();
+            run.id
+        };
+        // One worker still going, one that never recorded a job
+        let running = start("January", 1);
+        let job = service.jobs.start(Uuid::new_v4(), running, 1);
+        let orphaned = start("February", 2);
+
+        let an_hour_ago = Utc::now() - chrono::Duration::hours(1);
+        assert_eq!(service.recover_stuck_runs(an_hour_ago), vec![orphaned]);
+        assert_eq!(service.payroll_run(orphaned).unwrap().status, PayrollRunStatus::Failed);
+        assert_eq!(service.payroll_run(running).unwrap().status, PayrollRunStatus::Processing);
+
+        // Still processing past the deadline
+        assert_eq!(service.recover_stuck_runs(Utc::now() + chrono::Duration::seconds(1)), vec![running]);
+        assert_eq!(service.payroll_run(running).unwrap().status, PayrollRunStatus::Failed);
+        assert_eq!(service.jobs().get(job.id).unwrap().status, PayrollJobStatus::Failed);
+        assert!(service.recover_stuck_runs(Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn test_prorated_benefit_is_deducted() {
+        let service = PayrollService::new();
+        let mut run = PayrollRun::new(
+            Uuid::new_v4(),
+            "June 2024".to_string(),
+            NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(),
+        );
+        service.runs.insert(run.clone());
+        let without = service.calculate_payslip(&run, &create_test_employee()).unwrap();
+
+        let mut employee = create_test_employee();
+        employee.benefit_deductions.push(crate::benefits::BenefitDeduction {
+            benefit_plan_id: Uuid::new_v4(),
+            plan_name: "Hygeia Gold".to_string(),
+            fraction: dec!(0.7),
+            employee_amount: dec!(10500),
+            employer_amount: dec!(31500),
+        });
+        let item = service.process_payroll(&mut run, vec![employee], Uuid::new_v4()).unwrap().items.remove(0);
+
+        assert_eq!(item.total_deductions, without.total_deductions + dec!(10500));
+        assert_eq!(item.net_pay, without.net_pay - dec!(10500));
+        assert_eq!(item.other_deductions["benefits"][0]["employee_amount"], "10500");
+    }
+
+    #[test]
+    fn test_mid_month_starter_social_security_is_prorated() {
+        let run = PayrollRun::new(
+            Uuid::new_v4(),
+            "June 2024".to_string(),
+            NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(),
+        );
+        let full = create_test_employee();
+        let starter = EmployeeSalary { start_date: NaiveDate::from_ymd_opt(2024, 6, 16), ..full.clone() };
+
+        let service = PayrollService::new();
+        let full_item = service.calculate_payslip(&run, &full).unwrap();
+        let starter_item = service.calculate_payslip(&run, &starter).unwrap();
+        assert_eq!(starter_item.pension_employee, full_item.pension_employee / dec!(2));
+        assert_eq!(starter_item.pension_employer, full_item.pension_employer / dec!(2));
+        assert_eq!(starter_item.nhf_deduction, full_item.nhf_deduction / dec!(2));
+        // Pay follows the days employed, and contributions follow pay
+        assert_eq!(starter_item.gross_pay, full_item.gross_pay / dec!(2));
+        assert_eq!(starter_item.basic_salary, full_item.basic_salary / dec!(2));
+        assert!(starter_item.paye_tax < full_item.paye_tax);
+
+        // UIF is on remuneration paid, so it follows the days worked too
+        let za = |e: &EmployeeSalary| EmployeeSalary {
+            country_code: "ZA".to_string(),
+            basic_salary: dec!(10_000),
+            housing_allowance: Decimal::ZERO,
+            transport_allowance: Decimal::ZERO,
+            meal_allowance: Decimal::ZERO,
+            utility_allowance: Decimal::ZERO,
+            ..e.clone()
+        };
+        let za_full = service.calculate_payslip(&run, &za(&full)).unwrap();
+        let za_starter = service.calculate_payslip(&run, &za(&starter)).unwrap();
+        assert_eq!(za_full.other_deductions["uif"], serde_json::json!(dec!(100.00)));
+        assert_eq!(za_starter.other_deductions["uif"], serde_json::json!(dec!(50.00)));
+        assert_eq!(za_starter.gross_pay, dec!(5_000));
+        // Employer UIF and the skills levy both fall with the prorated pay
+        assert_eq!(za_starter.employer_contribution_total(), za_full.employer_contribution_total() - dec!(100));
+
+        // A tenant whose scheme is owed in full for any month of cover
+        let mut rules = SocialSecurityProrations::new();
+        rules.set("NG", crate::payroll::SocialSecurityProration::FullPeriod);
+        let service = PayrollService::new().with_social_security_proration(rules);
+        let all_or_nothing = service.calculate_payslip(&run, &starter).unwrap();
+        assert_eq!(all_or_nothing.pension_employee, full_item.pension_employee);
+        assert_eq!(all_or_nothing.nhf_deduction, full_item.nhf_deduction);
+        assert_eq!(all_or_nothing.gross_pay, starter_item.gross_pay);
+
+        // Starting after the period earns and owes nothing
+        let late = EmployeeSalary { start_date: NaiveDate::from_ymd_opt(2024, 7, 1), ..full.clone() };
+        let late_item = service.calculate_payslip(&run, &late).unwrap();
+        assert_eq!((late_item.gross_pay, late_item.pension_employee), (Decimal::ZERO, Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_rounding_mode_applies_to_whole_run() {
+        let period = CreatePayrollRunRequest {
+            name: "January 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let cost_for = |mode| {
+            let service = PayrollService::new().with_rounding(MoneyRounding::new(mode, 2));
+            let mut run = service.create_payroll_run(Uuid::new_v4(), period.clone()).unwrap();
+            let items = service.process_payroll(&mut run, vec![create_test_employee()], Uuid::new_v4()).unwrap().items;
+
+            let item = &items[0];
+            for line in [item.gross_pay, item.paye_tax, item.pension_employee, item.nhf_deduction, item.net_pay] {
+                assert!(line.scale() <= 2, "{} not rounded to cents", line);
+            }
+            assert_eq!(item.total_deductions, item.paye_tax + item.pension_employee + item.nhf_deduction);
+
+            // 470,000 employer cost × 0.0000055 = 2.585
+            service.employer_cost_by_department(run.id, "USD", dec!(0.0000055)).unwrap().total
+        };
+
+        assert_eq!(cost_for(RoundingMode::HalfEven), dec!(2.58));
+        assert_eq!(cost_for(RoundingMode::HalfUp), dec!(2.59));
+    }
+
+    #[test]
+    fn test_unsupported_country_is_skipped() {
+        let service = PayrollService::new();
+        let request = CreatePayrollRunRequest {
+            name: "January 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+
+        let mut employees: Vec<EmployeeSalary> = (0..49).map(|_| create_test_employee()).collect();
+        let mut abroad = create_test_employee();
+        abroad.country_code = "XX".to_string();
+        let abroad_id = abroad.employee_id;
+        employees.push(abroad);
+
+        let result = service.process_payroll(&mut run, employees, Uuid::new_v4()).unwrap();
+
+        assert_eq!(result.items.len(), 49);
+        assert_eq!(run.total_employees, 49);
+        assert_eq!(run.status, PayrollRunStatus::PendingApproval);
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].employee_id, abroad_id);
+        assert_eq!(result.skipped[0].country_code, "XX");
+        
+        // A batch with nothing payable still fails
+        let mut empty_run = PayrollRun::new(Uuid::new_v4(), "Feb".to_string(), run.period_start, run.period_end);
+        let mut only_abroad = create_test_employee();
+        only_abroad.country_code = "XX".to_string();
+        let err = service.process_payroll(&mut empty_run, vec![only_abroad], Uuid::new_v4()).unwrap_err();
+        assert!(matches!(err, PayrollError::UnsupportedCountry(code) if code == "XX"));
+        assert_eq!(empty_run.status, PayrollRunStatus::Draft);
+    }
+
+    #[test]
+    fn test_ytd_matches_payroll_lines() {
+        let service = PayrollService::new();
+        let employee = create_test_employee();
+        let mut lines = vec![];
+
+        for month in 1..=3 {
+            let request = CreatePayrollRunRequest {
+                name: format!("2024-{:02} Payroll", month),
+                period_start: NaiveDate::from_ymd_opt(2024, month, 1).unwrap(),
+                period_end: NaiveDate::from_ymd_opt(2024, month, 28).unwrap(),
+                notes: None,
+                legal_entity_id: None,
+            };
+            let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+            let mut salary = employee.clone();
+            salary.basic_salary += Decimal::from(month * 10_000);
+            let items = service.process_payroll(&mut run, vec![salary], Uuid::new_v4()).unwrap().items;
+            // Processed and approved, but only paid runs count
+            service.approve_payroll(&mut run, Uuid::new_v4()).unwrap();
+            assert_eq!(service.ytd_summary(employee.employee_id, 2024).periods, lines.len());
+            service.mark_as_paid(&mut run).unwrap();
+            lines.extend(items);
+        }
+
+        let ytd = service.ytd_summary(employee.employee_id, 2024);
+        assert_eq!(ytd.periods, 3);
+        assert_eq!(ytd.gross, lines.iter().map(|i| i.gross_pay).sum::<Decimal>());
+        assert_eq!(ytd.net, lines.iter().map(|i| i.net_pay).sum::<Decimal>());
+        assert_eq!(ytd.taxes["paye"], lines.iter().map(|i| i.paye_tax).sum::<Decimal>());
+        assert_eq!(ytd.taxes["nhf"], lines.iter().map(|i| i.nhf_deduction).sum::<Decimal>());
+        assert_eq!(service.ytd_summary(employee.employee_id, 2023).periods, 0);
+    }
+
+    #[test]
+    fn test_preflight_flags_pay_jump_and_missing_bank_details() {
+        let service = PayrollService::new();
+        let run_for = |month: u32| {
+            let request = CreatePayrollRunRequest {
+                name: format!("2024-{:02} Payroll", month),
+                period_start: NaiveDate::from_ymd_opt(2024, month, 1).unwrap(),
+                period_end: NaiveDate::from_ymd_opt(2024, month, 28).unwrap(),
+                notes: None,
+                legal_entity_id: None,
+            };
+            service.create_payroll_run(Uuid::new_v4(), request).unwrap()
+        };
+        let steady = create_test_employee();
+        let promoted = EmployeeSalary { employee_id: Uuid::new_v4(), employee_name: "Promoted".to_string(), ..steady.clone() };
+        let mut june = run_for(6);
+        service.process_payroll(&mut june, vec![steady.clone(), promoted.clone()], Uuid::new_v4()).unwrap();
+
+        let promoted = EmployeeSalary { basic_salary: dec!(750_000), ..promoted };
+        let no_bank = EmployeeSalary {
+            employee_id: Uuid::new_v4(),
+            employee_name: "No Bank".to_string(),
+            account_number: None,
+            ..steady.clone()
+        };
+        let kenyan = EmployeeSalary { employee_id: Uuid::new_v4(), country_code: "KE".to_string(), ..steady.clone() };
+        let mut july = run_for(7);
+        service
+            .process_payroll(&mut july, vec![steady.clone(), promoted.clone(), no_bank.clone(), kenyan.clone()], Uuid::new_v4())
+            .unwrap();
+
+        let findings = service.preflight(july.id).unwrap();
+        let flagged = |employee_id: Uuid| findings.iter().filter(|f| f.employee_id == employee_id).map(|f| f.check).collect::<Vec<_>>();
+        assert!(flagged(steady.employee_id).is_empty());
+        assert_eq!(flagged(promoted.employee_id), vec![crate::payroll::PreflightCheck::NetPayChange]);
+        assert_eq!(flagged(no_bank.employee_id), vec![crate::payroll::PreflightCheck::MissingBankDetails]);
+        assert_eq!(flagged(kenyan.employee_id), vec![crate::payroll::PreflightCheck::UnsupportedCountry]);
+        assert_eq!(findings.len(), 3);
+        // Errors come first
+        assert_eq!(findings.last().unwrap().severity, crate::payroll::PreflightSeverity::Warning);
+
+        // A looser threshold lets the raise through
+        let lenient = PreflightRules { max_net_pay_change: dec!(2) };
+        let june_item = service.run_items.get(&june.id).unwrap()[1].clone();
+        let july_item = service.run_items.get(&july.id).unwrap()[1].clone();
+        assert!(lenient.check_item(&promoted, &july_item, Some(june_item.net_pay)).is_empty());
+
+        assert!(matches!(service.preflight(Uuid::new_v4()), Err(PayrollError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_percentage_dues_deducted_only_within_active_window() {
+        use crate::payroll::recurring::{DeductionAmount, RecurringDeduction};
+
+        let service = PayrollService::new();
+        let employee = create_test_employee();
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        service.recurring_deductions().add(
+            RecurringDeduction::new(employee.employee_id, "Union dues", DeductionAmount::PercentOfGross(dec!(2)), false, date(7, 1))
+                .until(date(8, 31)),
+        );
+
+        let mut deducted = Vec::new();
+        for month in [6, 7, 8, 9] {
+            let request = CreatePayrollRunRequest {
+                name: format!("2024-{:02} Payroll", month),
+                period_start: date(month, 1),
+                period_end: date(month, 28),
+                notes: None,
+                legal_entity_id: None,
+            };
+            let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+            let without = service.calculate_payslip(&run, &employee).unwrap();
+            let item = service.process_payroll(&mut run, vec![employee.clone()], Uuid::new_v4()).unwrap().items.remove(0);
+
+            // Post-tax: tax is unchanged, net pay carries the dues
+            assert_eq!(item.paye_tax, without.paye_tax);
+            deducted.push(without.net_pay - item.net_pay);
+        }
+        // 2% of 430,000 gross in July and August only
+        assert_eq!(deducted, [Decimal::ZERO, dec!(8600), dec!(8600), Decimal::ZERO]);
+
+        // Pre-tax deductions lower PAYE as well as net pay
+        let run = PayrollRun::new(Uuid::new_v4(), "July 2024".to_string(), date(7, 1), date(7, 31));
+        let before = service.calculate_payslip(&run, &employee).unwrap();
+        service.recurring_deductions().add(
+            RecurringDeduction::new(employee.employee_id, "Voluntary pension", DeductionAmount::Flat(dec!(50_000)), true, date(1, 1)),
+        );
+        let after = service.calculate_payslip(&run, &employee).unwrap();
+        assert!(after.paye_tax < before.paye_tax);
+        assert_eq!(after.other_deductions[RECURRING_DEDUCTION_LINE]["Voluntary pension"], serde_json::json!(dec!(50_000)));
+        assert_eq!(after.net_pay, before.net_pay - dec!(50_000) + (before.paye_tax - after.paye_tax));
+    }
+
+    #[test]
+    fn test_voluntary_deductions_deferred_to_honor_net_pay_floor() {
+        use crate::payroll::preflight::PreflightCheck;
+        use crate::payroll::recurring::{DeductionAmount, RecurringDeduction};
+
+        let employee = create_test_employee();
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        let probe = PayrollRun::new(Uuid::new_v4(), "July 2024".to_string(), date(7, 1), date(7, 31));
+        let net_before = PayrollService::new().calculate_payslip(&probe, &employee).unwrap().net_pay;
+
+        let mut floors = NetPayFloors::new();
+        floors.set("ng", net_before - dec!(10_000));
+        let service = PayrollService::new().with_net_pay_floors(floors);
+        for (name, amount) in [("Union dues", dec!(6_000)), ("Payroll giving", dec!(8_000))] {
+            service.recurring_deductions().add(
+                RecurringDeduction::new(employee.employee_id, name, DeductionAmount::Flat(amount), false, date(1, 1)),
+            );
+        }
+        // Statutory deductions alone take this one under the floor
+        let low_paid = EmployeeSalary {
+            employee_id: Uuid::new_v4(),
+            basic_salary: dec!(60_000),
+            housing_allowance: dec!(20_000),
+            ..employee.clone()
+        };
+
+        let request = CreatePayrollRunRequest {
+            name: "July 2024 Payroll".to_string(),
+            period_start: date(7, 1),
+            period_end: date(7, 31),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+        let items = service.process_payroll(&mut run, vec![employee.clone(), low_paid.clone()], Uuid::new_v4()).unwrap().items;
+        let item = items.iter().find(|i| i.employee_id == employee.employee_id).unwrap();
+
+        // Dues fit in full; giving is cut to what is left above the floor
+        assert_eq!(item.net_pay, net_before - dec!(10_000));
+        assert_eq!(item.other_deductions[RECURRING_DEDUCTION_LINE]["Union dues"], serde_json::json!(dec!(6_000)));
+        assert_eq!(item.other_deductions[RECURRING_DEDUCTION_LINE]["Payroll giving"], serde_json::json!(dec!(4_000.00)));
+        let outcome = service.net_pay_floor_ledger().get(run.id, employee.employee_id).unwrap();
+        assert_eq!(
+            outcome.deferred,
+            vec![DeferredDeduction { name: "Payroll giving".into(), due: dec!(8_000), taken: dec!(4_000), deferred: dec!(4_000) }]
+        );
+        assert!(outcome.shortfall.is_zero());
+
+        let findings = service.preflight(run.id).unwrap();
+        let below: Vec<_> = findings.iter().filter(|f| f.check == PreflightCheck::BelowNetPayFloor).collect();
+        assert_eq!(below.len(), 1);
+        assert_eq!(below[0].employee_id, low_paid.employee_id);
+        assert!(service.net_pay_floor_ledger().get(run.id, low_paid.employee_id).unwrap().shortfall > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_weekly_and_monthly_paid_owe_same_annual_tax() {
+        let service = PayrollService::new();
+        let run = PayrollRun::new(
+            Uuid::new_v4(),
+            "2024".to_string(),
+            NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 7, 31).unwrap(),
+        );
+        // 6,240,000 a year either way
+        let per_period = |basic, pay_frequency| EmployeeSalary {
+            basic_salary: basic,
+            housing_allowance: Decimal::ZERO,
+            transport_allowance: Decimal::ZERO,
+            meal_allowance: Decimal::ZERO,
+            utility_allowance: Decimal::ZERO,
+            pay_frequency,
+            loan_monthly_repayment: dec!(5_200),
+            ..create_test_employee()
+        };
+        let monthly = per_period(dec!(520_000), PayFrequency::Monthly);
+        let weekly = per_period(dec!(120_000), PayFrequency::Weekly);
+
+        for country in ["NG", "ZA"] {
+            let monthly = EmployeeSalary { country_code: country.to_string(), ..monthly.clone() };
+            let weekly = EmployeeSalary { country_code: country.to_string(), ..weekly.clone() };
+            let monthly = service.calculate_payslip(&run, &monthly).unwrap();
+            let weekly = service.calculate_payslip(&run, &weekly).unwrap();
+            let (monthly_tax, weekly_tax) = (monthly.paye_tax * dec!(12), weekly.paye_tax * dec!(52));
+            // Equal up to each payslip's rounding to the cent
+            assert!((monthly_tax - weekly_tax).abs() <= dec!(0.26), "{}: {} vs {}", country, monthly_tax, weekly_tax);
+            // The monthly loan instalment is taken a week's worth at a time
+            assert_eq!((monthly.loan_repayment, weekly.loan_repayment), (dec!(5_200), dec!(1_200)));
+        }
+    }
+
+    #[test]
+    fn test_27_period_biweekly_year_withholds_annual_liability() {
+        use crate::payroll::annualization::{Annualization, ExtraPeriodPolicy};
+
+        let employee = EmployeeSalary { pay_frequency: PayFrequency::BiWeekly, ..create_test_employee() };
+        // Fortnightly from Friday 1 January 2021: 27 paydays
+        let first = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        let runs: Vec<PayrollRun> = (0..27)
+            .map(|n| {
+                let period_end = first + chrono::Duration::days(14 * n);
+                PayrollRun::new(Uuid::new_v4(), format!("Fortnight {}", n + 1), period_end - chrono::Duration::days(13), period_end)
+            })
+            .collect();
+
+        let pension = PensionCalculator::new().calculate(dec!(250_000), dec!(100_000), dec!(50_000));
+        let year = Annualization { periods: dec!(27), ..Annualization::nominal(&PayFrequency::BiWeekly) };
+        let liability = NigerianTaxCalculator::new()
+            .calculate_annualized_paye(dec!(430_000), pension.employee_contribution, pension.nhf_contribution, &year)
+            .annual_tax;
+
+        let withheld = |service: PayrollService| -> Vec<Decimal> {
+            runs.iter().map(|run| service.calculate_payslip(run, &employee).unwrap().paye_tax).collect()
+        };
+        let nominal = withheld(PayrollService::new().with_extra_period_policy(ExtraPeriodPolicy::Nominal));
+        let actual = withheld(PayrollService::new());
+        let smoothed = withheld(PayrollService::new().with_extra_period_policy(ExtraPeriodPolicy::Smoothed));
+
+        assert!(nominal.iter().sum::<Decimal>() < liability);
+        assert!((actual.iter().sum::<Decimal>() - liability).abs() <= dec!(0.27));
+        assert_eq!(smoothed.iter().sum::<Decimal>(), liability.round_dp(2));
+        assert_eq!(smoothed[0], actual[0]);
+    }
+
+    #[test]
+    fn test_employer_cost_by_department() {
+        let service = PayrollService::new();
+        let request = CreatePayrollRunRequest {
+            name: "January 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+
+        let (eng, ops) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut employees = vec![create_test_employee(), create_test_employee(), create_test_employee(), create_test_employee()];
+        employees[0].department_id = Some(eng);
+        employees[1].department_id = Some(eng);
+        employees[2].department_id = Some(ops);
+
+        service.process_payroll(&mut run, employees, Uuid::new_v4()).unwrap();
+        let report = service.employer_cost_by_department(run.id, "NGN", Decimal::ONE).unwrap();
+
+        // Gross 430,000 + employer pension 10% of (250k + 100k + 50k) = 470,000 each
+        assert_eq!(report.by_department[&eng.to_string()], dec!(940_000));
+        assert_eq!(report.by_department[&ops.to_string()], dec!(470_000));
+        assert_eq!(report.by_department[DepartmentCostReport::UNASSIGNED], dec!(470_000));
+        assert_eq!(report.total, run.total_gross + run.total_employer_contributions);
+
+        let usd = service.employer_cost_by_department(run.id, "USD", dec!(0.00065)).unwrap();
+        assert_eq!(usd.by_department[&ops.to_string()], dec!(305.50));
+
+        assert!(matches!(
+            service.employer_cost_by_department(Uuid::new_v4(), "NGN", Decimal::ONE),
+            Err(PayrollError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_budget_variance_rolls_up_departments() {
+        let service = PayrollService::new();
+        let request = CreatePayrollRunRequest {
+            name: "January 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+
+        // Technology has no budget of its own; its teams are budgeted separately
+        let technology = Department::new("Technology", None, None);
+        let platform = Department::new("Platform", Some(technology.id), Some(dec!(1_000_000)));
+        let data = Department::new("Data", Some(technology.id), Some(dec!(400_000)));
+        for department in [&technology, &platform, &data] {
+            service.departments().upsert(department.clone());
+        }
+
+        let mut employees = vec![create_test_employee(), create_test_employee(), create_test_employee(), create_test_employee()];
+        employees[0].department_id = Some(platform.id);
+        employees[1].department_id = Some(platform.id);
+        employees[2].department_id = Some(data.id);
+        employees[3].department_id = Some(technology.id);
+        service.process_payroll(&mut run, employees, Uuid::new_v4()).unwrap();
+
+        // 470,000 employer cost per employee
+        let under = service.budget_variance(platform.id, run.id).unwrap();
+        assert_eq!((under.budgeted, under.actual, under.variance), (dec!(1_000_000), dec!(940_000), dec!(60_000)));
+        assert_eq!(under.variance_percent, Some(dec!(6)));
+        assert!(!under.over_budget);
+
+        let over = service.budget_variance(data.id, run.id).unwrap();
+        assert_eq!(over.variance, dec!(-70_000));
+        assert_eq!(over.variance_percent, Some(dec!(-17.5)));
+        assert!(over.over_budget);
+
+        let rollup = service.budget_variance(technology.id, run.id).unwrap();
+        assert_eq!(rollup.budgeted, dec!(1_400_000));
+        assert_eq!(rollup.actual, dec!(1_880_000));
+        assert!(rollup.over_budget);
+        assert_eq!(rollup.children.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), ["Data", "Platform"]);
+
+        assert!(matches!(
+            service.budget_variance(Uuid::new_v4(), run.id),
+            Err(PayrollError::DepartmentNotFound(_))
+        ));
+    }
 
-/// Tax preview response
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TaxPreviewResponse {
-    pub gross_monthly: Decimal,
-    pub gross_annual: Decimal,
-    pub paye_monthly: Decimal,
-    pub paye_annual: Decimal,
-    pub pension_employee: Decimal,
-    pub pension_employer: Decimal,
-    pub nhf: Decimal,
-    pub total_deductions: Decimal,
-    pub net_monthly: Decimal,
-    pub effective_tax_rate: Decimal,
-}
+    #[test]
+    fn test_reconcile_reports_inconsistent_line() {
+        use crate::payroll::recurring::{DeductionAmount, RecurringDeduction};
 
-use serde::{Deserialize, Serialize};
+        let service = PayrollService::new();
+        let request = CreatePayrollRunRequest {
+            name: "June 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+        let nigerian = create_test_employee();
+        let mut with_benefit = EmployeeSalary { employee_id: Uuid::new_v4(), ..nigerian.clone() };
+        with_benefit.benefit_deductions.push(crate::benefits::BenefitDeduction {
+            benefit_plan_id: Uuid::new_v4(),
+            plan_name: "Hygeia Gold".to_string(),
+            fraction: Decimal::ONE,
+            employee_amount: dec!(15000),
+            employer_amount: dec!(45000),
+        });
+        let south_african = EmployeeSalary { employee_id: Uuid::new_v4(), country_code: "ZA".to_string(), ..nigerian.clone() };
+        service.repayments().add(RepaymentSchedule::new(nigerian.employee_id, RepaymentKind::Garnishment, dec!(50_000), dec!(10_000)));
+        service.recurring_deductions().add(RecurringDeduction::new(
+            with_benefit.employee_id,
+            "Union dues",
+            DeductionAmount::PercentOfGross(dec!(1.25)),
+            false,
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        ));
+        service
+            .process_payroll(&mut run, vec![nigerian.clone(), with_benefit.clone(), south_african], Uuid::new_v4())
+            .unwrap();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use chrono::NaiveDate;
+        let clean = service.reconcile(run.id).unwrap();
+        assert!(clean.is_reconciled(), "{:?}", clean.discrepancies);
+        assert_eq!(clean.total_gross, clean.total_net + clean.total_deductions);
 
-    fn create_test_employee() -> EmployeeSalary {
-        EmployeeSalary {
-            employee_id: Uuid::new_v4(),
-            employee_name: "Test Employee".to_string(),
-            employee_code: "EMP001".to_string(),
-            basic_salary: dec!(250_000),
-            housing_allowance: dec!(100_000),
-            transport_allowance: dec!(50_000),
-            meal_allowance: dec!(20_000),
-            utility_allowance: dec!(10_000),
-            other_allowances: serde_json::json!({}),
-            bank_name: Some("GTBank".to_string()),
-            account_number: Some("0123456789".to_string()),
-            account_name: Some("Test Employee".to_string()),
-            tin: Some("12345678-0001".to_string()),
-            pension_pin: Some("PEN123456".to_string()),
-            nhf_number: Some("NHF123456".to_string()),
-            loan_balance: Decimal::ZERO,
-            loan_monthly_repayment: Decimal::ZERO,
+        // A payslip whose net pay was posted wrong
+        service.run_items.get_mut(&run.id).unwrap()[1].net_pay += dec!(7.50);
+        let report = service.reconcile(run.id).unwrap();
+        assert_eq!(report.discrepancies.len(), 2);
+        assert_eq!(report.discrepancies[0].employee_id, Some(with_benefit.employee_id));
+        assert_eq!(report.discrepancies[0].delta, dec!(-7.50));
+        assert_eq!(report.discrepancies[1].employee_id, None);
+
+        // Within a looser tolerance it passes
+        let lenient = service.clone().with_reconciliation_tolerance(dec!(10));
+        assert!(lenient.reconcile(run.id).unwrap().is_reconciled());
+        assert!(matches!(service.reconcile(Uuid::new_v4()), Err(PayrollError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_approved_tax_override_changes_payslip_and_is_audited() {
+        use crate::compliance::AuditFilter;
+        use crate::payroll::tax_override::{TaxOverride, TaxOverrideKind};
+
+        let service = PayrollService::new();
+        let request = CreatePayrollRunRequest {
+            name: "June 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+        let employee = create_test_employee();
+        let other = EmployeeSalary { employee_id: Uuid::new_v4(), ..employee.clone() };
+        let computed = service.calculate_payslip(&run, &employee).unwrap();
+
+        let (clerk, manager) = (Uuid::new_v4(), Uuid::new_v4());
+        let correction = TaxOverride::new(employee.employee_id, run.id, TaxOverrideKind::Adjust, dec!(12_500), "2023 PAYE under-deduction", clerk);
+        let correction = service.request_tax_override(correction).unwrap();
+        assert!(matches!(service.approve_tax_override(correction.id, clerk), Err(PayrollError::TaxOverrideSelfApproval)));
+        // Court-ordered tax for the other employee is never approved, so never applied
+        let refused = TaxOverride::new(other.employee_id, run.id, TaxOverrideKind::Replace, dec!(0), "Court order", clerk);
+        let refused = service.request_tax_override(refused).unwrap();
+        service.reject_tax_override(refused.id, manager).unwrap();
+        service.approve_tax_override(correction.id, manager).unwrap();
+
+        let result = service.process_payroll(&mut run, vec![employee.clone(), other.clone()], Uuid::new_v4()).unwrap();
+        let item = result.items.iter().find(|i| i.employee_id == employee.employee_id).unwrap();
+        assert_eq!(item.paye_tax, computed.paye_tax + dec!(12_500));
+        assert_eq!(item.net_pay, computed.net_pay - dec!(12_500));
+        let untouched = result.items.iter().find(|i| i.employee_id == other.employee_id).unwrap();
+        assert_eq!(untouched.paye_tax, computed.paye_tax);
+
+        let report = service.reconcile(run.id).unwrap();
+        assert!(report.is_reconciled(), "{:?}", report.discrepancies);
+        assert_eq!(report.tax_overrides.len(), 1);
+        assert_eq!(report.tax_overrides[0].computed_tax, Some(computed.paye_tax));
+        assert_eq!(report.tax_overrides[0].applied_tax, Some(item.paye_tax));
+
+        let trail = service.audit_log().query(&AuditFilter { tenant_id: Some(run.tenant_id), ..Default::default() }, None, 100);
+        let override_entries = trail.entries.iter().filter(|e| e.entity_id == correction.id).count();
+        assert_eq!(override_entries, 2);
+        let applied = trail.entries.iter().find(|e| e.entity_type == "payroll_item").unwrap();
+        assert_eq!(applied.entity_id, item.id);
+        assert_eq!(applied.metadata["reason"], "2023 PAYE under-deduction");
+
+        // Processed runs take no more overrides
+        let late = TaxOverride::new(employee.employee_id, run.id, TaxOverrideKind::Replace, dec!(1), "Late", clerk);
+        assert!(matches!(service.request_tax_override(late), Err(PayrollError::NotDraft)));
+    }
+
+    #[test]
+    fn test_russian_ndfl_uses_paid_ytd() {
+        let service = PayrollService::new();
+        let tenant_id = Uuid::new_v4();
+        let mut employee = create_test_employee();
+        employee.country_code = "RU".to_string();
+        employee.basic_salary = dec!(2_000_000);
+        (employee.housing_allowance, employee.transport_allowance) = (Decimal::ZERO, Decimal::ZERO);
+        (employee.meal_allowance, employee.utility_allowance) = (Decimal::ZERO, Decimal::ZERO);
+        let month = |m: u32| CreatePayrollRunRequest {
+            name: format!("2024-{:02} Payroll", m),
+            period_start: NaiveDate::from_ymd_opt(2024, m, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, m, 28).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut ndfl = vec![];
+        for m in 1..=3 {
+            let mut run = service.create_payroll_run(tenant_id, month(m)).unwrap();
+            ndfl.push(service.process_payroll(&mut run, vec![employee.clone()], Uuid::new_v4()).unwrap().items[0].paye_tax);
+            service.approve_payroll(&mut run, Uuid::new_v4()).unwrap();
+            service.mark_as_paid(&mut run).unwrap();
         }
+
+        // 4M paid by March: 1M of March's pay is at 13%, the rest at 15%
+        assert_eq!(ndfl, vec![dec!(260_000), dec!(260_000), dec!(130_000) + dec!(150_000)]);
+        // April only sees YTD from paid runs
+        let mut april = service.create_payroll_run(tenant_id, month(4)).unwrap();
+        let item = service.process_payroll(&mut april, vec![employee.clone()], Uuid::new_v4()).unwrap().items.remove(0);
+        assert_eq!(item.paye_tax, dec!(300_000));
+        assert_eq!(item.employer_contributions["pfr"], dec!(440_000));
+        let mut may = service.create_payroll_run(tenant_id, month(5)).unwrap();
+        assert_eq!(service.process_payroll(&mut may, vec![employee], Uuid::new_v4()).unwrap().items[0].paye_tax, dec!(300_000));
     }
 
     #[test]
-    fn test_create_payroll_run() {
+    fn test_clawback_reduces_ytd_and_recovers_negative_net() {
+        use crate::payroll::clawback::{Clawback, CLAWBACK_CARRIED_FORWARD_LINE, CLAWBACK_RECOVERY_LINE};
+
         let service = PayrollService::new();
         let tenant_id = Uuid::new_v4();
-        
+        let employee = create_test_employee();
+        let month = |m: u32, last: u32| CreatePayrollRunRequest {
+            name: format!("2024-{:02} Payroll", m),
+            period_start: NaiveDate::from_ymd_opt(2024, m, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, m, last).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+
+        let mut january = service.create_payroll_run(tenant_id, month(1, 31)).unwrap();
+        let paid = service.process_payroll(&mut january, vec![employee.clone()], Uuid::new_v4()).unwrap().items.remove(0);
+        service.approve_payroll(&mut january, Uuid::new_v4()).unwrap();
+        service.mark_as_paid(&mut january).unwrap();
+
+        // February recovers more than a month's pay
+        let mut february = service.create_payroll_run(tenant_id, month(2, 29)).unwrap();
+        let overpaid = paid.gross_pay + dec!(100_000);
+        service.record_clawback(Clawback::new(employee.employee_id, february.id, overpaid, "January bonus paid twice")).unwrap();
+        assert!(matches!(
+            service.record_clawback(Clawback::new(employee.employee_id, february.id, dec!(0), "Nothing")),
+            Err(PayrollError::Validation(_))
+        ));
+        let item = service.process_payroll(&mut february, vec![employee.clone()], Uuid::new_v4()).unwrap().items.remove(0);
+
+        assert_eq!(item.gross_pay, paid.gross_pay - overpaid);
+        assert_eq!(item.other_allowances["clawback"], serde_json::json!(-overpaid));
+        // Withholding given back at the year's effective rate, capped by what was withheld
+        let reversal = paid.paye_tax - item.paye_tax;
+        assert_eq!(reversal, service.rounding().round(overpaid * paid.paye_tax / paid.gross_pay));
+        assert_eq!(item.net_pay, Decimal::ZERO);
+        let carried = -serde_json::from_value::<Decimal>(item.other_deductions[CLAWBACK_CARRIED_FORWARD_LINE].clone()).unwrap();
+        assert!(carried > Decimal::ZERO);
+        assert!(service.reconcile(february.id).unwrap().is_reconciled());
+        service.approve_payroll(&mut february, Uuid::new_v4()).unwrap();
+        service.mark_as_paid(&mut february).unwrap();
+
+        let ytd = service.ytd_summary(employee.employee_id, 2024);
+        assert_eq!(ytd.gross, paid.gross_pay * dec!(2) - overpaid);
+        assert_eq!(ytd.taxes["paye"], paid.paye_tax + item.paye_tax);
+
+        let schedule = &service.repayments().for_employee(employee.employee_id)[0];
+        assert_eq!((schedule.kind, schedule.principal), (RepaymentKind::Clawback, carried));
+
+        // March recovers the rest from net pay
+        let mut march = service.create_payroll_run(tenant_id, month(3, 31)).unwrap();
+        let recovered = service.process_payroll(&mut march, vec![employee.clone()], Uuid::new_v4()).unwrap().items.remove(0);
+        assert_eq!(recovered.other_deductions[CLAWBACK_RECOVERY_LINE], serde_json::json!(carried));
+        assert_eq!(recovered.net_pay, paid.net_pay - carried);
+        assert!(service.repayments().for_employee(employee.employee_id)[0].is_complete());
+    }
+
+    #[test]
+    fn test_gl_journal_balances_per_currency() {
+        let service = PayrollService::new();
         let request = CreatePayrollRunRequest {
-            name: "January 2024 Payroll".to_string(),
-            period_start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
-            period_end: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            name: "April 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 4, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 4, 30).unwrap(),
             notes: None,
+            legal_entity_id: None,
         };
+        let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
 
-        let run = service.create_payroll_run(tenant_id, request).unwrap();
-        
-        assert_eq!(run.status, PayrollRunStatus::Draft);
-        assert_eq!(run.total_employees, 0);
+        let mut lagos = create_test_employee();
+        lagos.loan_monthly_repayment = dec!(15_000);
+        lagos.benefit_deductions.push(crate::benefits::BenefitDeduction {
+            benefit_plan_id: Uuid::new_v4(),
+            plan_name: "Hygeia Gold".to_string(),
+            fraction: Decimal::ONE,
+            employee_amount: dec!(10_000),
+            employer_amount: dec!(30_000),
+        });
+        let mut joburg = create_test_employee();
+        joburg.employee_id = Uuid::new_v4();
+        joburg.country_code = "ZA".to_string();
+        let items = service.process_payroll(&mut run, vec![lagos, joburg], Uuid::new_v4()).unwrap().items;
+
+        let mut accounts = GlAccountMap::default();
+        accounts.other_deductions.insert("uif".to_string(), "2130".to_string());
+        accounts.other_deductions.insert("benefits".to_string(), "2140".to_string());
+        let journal = service.gl_journal(run.id, &accounts).unwrap();
+
+        assert!(journal.is_balanced());
+        assert_eq!(journal.balances().keys().collect::<Vec<_>>(), ["NGN", "ZAR"]);
+
+        let credit = |currency: &str, account: &str| -> Decimal {
+            journal.lines_for(currency).filter(|l| l.account == account).map(|l| l.credit).sum()
+        };
+        let (ng, za) = (&items[0], &items[1]);
+        assert_eq!(credit("NGN", "2100"), ng.paye_tax);
+        assert_eq!(credit("NGN", "2110"), ng.pension_employee + ng.pension_employer);
+        assert_eq!(credit("NGN", "1300"), dec!(15_000));
+        // Employee and employer shares of the health plan
+        assert_eq!(credit("NGN", "2140"), dec!(40_000));
+        assert_eq!(credit("NGN", "2200"), ng.net_pay);
+        assert_eq!(credit("ZAR", "2100"), za.paye_tax);
+        // Employee and employer UIF both owed to the UIF account
+        assert_eq!(credit("ZAR", "2130"), za.total_deductions - za.paye_tax + za.employer_contributions["uif"]);
+        let employer: Decimal = journal.lines_for("ZAR").filter(|l| l.account == "6100").map(|l| l.debit).sum();
+        assert_eq!(employer, za.employer_contribution_total());
+        assert_eq!(credit("ZAR", "2200"), za.net_pay);
+        let wages: Decimal = journal.lines_for("ZAR").filter(|l| l.account == "6000").map(|l| l.debit).sum();
+        assert_eq!(wages, za.gross_pay);
+
+        // No currency to post in: refused, not labelled NGN
+        service.run_inputs.get_mut(&run.id).unwrap()[1].country_code = "XX".to_string();
+        assert!(matches!(service.gl_journal(run.id, &accounts), Err(PayrollError::UnsupportedCountry(c)) if c == "XX"));
+        service.run_inputs.get_mut(&run.id).unwrap().pop();
+        assert!(matches!(service.gl_journal(run.id, &accounts), Err(PayrollError::Validation(_))));
     }
 
     #[test]
-    fn test_process_payroll() {
+    fn test_employer_contributions_report_across_countries() {
         let service = PayrollService::new();
-        let tenant_id = Uuid::new_v4();
-        
         let request = CreatePayrollRunRequest {
-            name: "January 2024 Payroll".to_string(),
-            period_start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
-            period_end: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            name: "April 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 4, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 4, 30).unwrap(),
             notes: None,
+            legal_entity_id: None,
         };
+        let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+        let lagos = create_test_employee();
+        let mut joburg = create_test_employee();
+        joburg.employee_id = Uuid::new_v4();
+        joburg.country_code = "ZA".to_string();
+        let items = service.process_payroll(&mut run, vec![lagos, joburg], Uuid::new_v4()).unwrap().items;
 
-        let mut run = service.create_payroll_run(tenant_id, request).unwrap();
-        let employees = vec![create_test_employee()];
-        let processor_id = Uuid::new_v4();
+        let report = service.employer_contributions_report(run.id).unwrap();
+        assert_eq!(report.contribution_types, ["pension", "sdl", "uif"]);
 
-        let items = service.process_payroll(&mut run, employees, processor_id).unwrap();
+        let (ng, za) = (&report.employees[0], &report.employees[1]);
+        assert_eq!(ng.country_code, "NG");
+        // 10% of basic + housing + transport
+        assert_eq!(ng.contributions["pension"], dec!(40_000));
+        assert_eq!(ng.contributions["uif"], Decimal::ZERO);
+        assert_eq!(ng.contributions["sdl"], Decimal::ZERO);
 
-        assert_eq!(items.len(), 1);
-        assert_eq!(run.total_employees, 1);
-        assert!(run.total_gross > Decimal::ZERO);
-        assert!(run.total_net > Decimal::ZERO);
-        assert!(run.total_net < run.total_gross);
-        assert_eq!(run.status, PayrollRunStatus::PendingApproval);
-        
-        // Verify deductions
-        let item = &items[0];
-        assert!(item.paye_tax > Decimal::ZERO);
-        assert!(item.pension_employee > Decimal::ZERO);
-        assert!(item.nhf_deduction > Decimal::ZERO);
-        
-        println!("Gross: ₦{}", item.gross_pay);
-        println!("PAYE: ₦{}", item.paye_tax);
-        println!("Pension (Employee): ₦{}", item.pension_employee);
-        println!("Pension (Employer): ₦{}", item.pension_employer);
-        println!("NHF: ₦{}", item.nhf_deduction);
-        println!("Total Deductions: ₦{}", item.total_deductions);
-        println!("Net Pay: ₦{}", item.net_pay);
+        assert_eq!(za.contributions["pension"], Decimal::ZERO);
+        // UIF 1% of gross capped at R17,712; SDL 1% of gross
+        assert_eq!(za.contributions["uif"], dec!(177.12));
+        assert_eq!(za.contributions["sdl"], dec!(4_300));
+        assert_eq!(za.total, dec!(4_477.12));
+
+        assert_eq!(report.totals["pension"], dec!(40_000));
+        assert_eq!(report.total, run.total_employer_contributions);
+        assert_eq!(report.total, items.iter().map(|i| i.employer_contribution_total()).sum::<Decimal>());
+        assert!(matches!(service.employer_contributions_report(Uuid::new_v4()), Err(PayrollError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_recalculate_draft_runs_after_za_bracket_update() {
+        let service = PayrollService::new();
+        let za_run = |name: &str| {
+            let request = CreatePayrollRunRequest {
+                name: name.to_string(),
+                period_start: NaiveDate::from_ymd_opt(2024, 4, 1).unwrap(),
+                period_end: NaiveDate::from_ymd_opt(2024, 4, 30).unwrap(),
+                notes: None,
+                legal_entity_id: None,
+            };
+            service.create_payroll_run(Uuid::new_v4(), request).unwrap()
+        };
+        let mut employee = create_test_employee();
+        employee.country_code = "ZA".to_string();
+
+        let mut draft = za_run("April 2024 (entity A)");
+        let mut completed = za_run("April 2024 (entity B)");
+        service.process_payroll(&mut draft, vec![employee.clone()], Uuid::new_v4()).unwrap();
+        service.process_payroll(&mut completed, vec![employee.clone()], Uuid::new_v4()).unwrap();
+        service.approve_payroll(&mut completed, Uuid::new_v4()).unwrap();
+        service.mark_as_paid(&mut completed).unwrap();
+        assert_eq!(draft.total_deductions, completed.total_deductions);
+
+        // Corrected 2024/2025 table: every bracket one point higher
+        let mut config = service.tax_tables().south_africa(2024);
+        for bracket in &mut config.brackets {
+            bracket.rate += dec!(0.01);
+        }
+        service.tax_tables().update_south_africa(2024, config);
+
+        let updated = service.recalculate_draft_runs("ZA", 2024).unwrap();
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].id, draft.id);
+        assert!(updated[0].total_deductions > draft.total_deductions);
+        assert_eq!(updated[0].total_net, updated[0].total_gross - updated[0].total_deductions);
+        assert_eq!(service.payroll_run(completed.id).unwrap().total_deductions, completed.total_deductions);
+
+        // Safe to re-run: same totals, and YTD holds only the paid run
+        let again = service.recalculate_draft_runs("ZA", 2024).unwrap();
+        assert_eq!(again[0].total_deductions, updated[0].total_deductions);
+        assert_eq!(service.ytd_summary(employee.employee_id, 2024).periods, 1);
+
+        // Other years and countries are not touched
+        assert!(service.recalculate_draft_runs("ZA", 2023).unwrap().is_empty());
+        assert!(service.recalculate_draft_runs("NG", 2024).unwrap().is_empty());
     }
 
     #[test]
@@ -427,6 +2761,7 @@ mod tests {
             period_start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             period_end: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
             notes: None,
+            legal_entity_id: None,
         };
 
         let mut run = service.create_payroll_run(tenant_id, request).unwrap();
@@ -440,6 +2775,43 @@ mod tests {
         
         assert_eq!(run.status, PayrollRunStatus::Approved);
         assert!(run.approved_by.is_some());
+        assert_eq!(service.payroll_run(run.id).unwrap().approved_by, Some(approver_id));
+
+        // A second approver holding a stale copy can't overwrite the first
+        let mut stale = run.clone();
+        stale.status = PayrollRunStatus::PendingApproval;
+        assert!(matches!(
+            service.approve_payroll(&mut stale, Uuid::new_v4()),
+            Err(PayrollError::StatusConflict { actual: PayrollRunStatus::Approved, .. })
+        ));
+        assert_eq!(service.payroll_run(run.id).unwrap().approved_by, Some(approver_id));
+
+        service.mark_as_paid(&mut run).unwrap();
+        assert_eq!(service.payroll_run(run.id).unwrap().status, PayrollRunStatus::Paid);
+    }
+
+    #[test]
+    fn test_recalculation_leaves_run_approved_meanwhile() {
+        let service = PayrollService::new();
+        let request = CreatePayrollRunRequest {
+            name: "January 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+        service.process_payroll(&mut run, vec![create_test_employee()], Uuid::new_v4()).unwrap();
+        let approved = service.runs.transition(run.id, PayrollRunStatus::PendingApproval, PayrollRunStatus::Approved).unwrap();
+
+        // Totals written against a stale status are refused
+        assert!(matches!(
+            service.runs.update_if(run.id, PayrollRunStatus::PendingApproval, |r| r.total_net = Decimal::ZERO),
+            Err(PayrollError::StatusConflict { .. })
+        ));
+        assert!(service.recalculate_draft_runs("NG", 2024).unwrap().is_empty());
+        assert_eq!(service.payroll_run(run.id).unwrap().total_net, approved.total_net);
+        assert_eq!(service.payroll_run(run.id).unwrap().status, PayrollRunStatus::Approved);
     }
 
     #[test]
@@ -459,4 +2831,398 @@ mod tests {
         println!("Net: ₦{}", preview.net_monthly);
         println!("Effective Rate: {}%", preview.effective_tax_rate);
     }
+
+    #[test]
+    fn test_overlapping_run_rejected_for_same_tenant_and_entity() {
+        let service = PayrollService::new();
+        let tenant_id = Uuid::new_v4();
+        let request = |start: (u32, u32), end: (u32, u32)| CreatePayrollRunRequest {
+            name: "Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, start.0, start.1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, end.0, end.1).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let may = service.create_payroll_run(tenant_id, request((5, 1), (5, 31))).unwrap();
+
+        let overlap = service.create_payroll_run(tenant_id, request((5, 15), (6, 14)));
+        assert!(matches!(overlap, Err(PayrollError::OverlappingRun(id)) if id == may.id));
+        // Adjacent periods and other tenants are fine
+        assert!(service.create_payroll_run(tenant_id, request((6, 1), (6, 30))).is_ok());
+        assert!(service.create_payroll_run(Uuid::new_v4(), request((5, 1), (5, 31))).is_ok());
+
+        // A cancelled run no longer holds its period
+        service.runs.transition(may.id, PayrollRunStatus::Draft, PayrollRunStatus::Cancelled).unwrap();
+        assert!(service.create_payroll_run(tenant_id, request((5, 1), (5, 31))).is_ok());
+    }
+
+    #[test]
+    fn test_payment_file_never_pays_twice() {
+        let service = PayrollService::new();
+        let request = CreatePayrollRunRequest {
+            name: "May 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 5, 31).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let tenant_id = Uuid::new_v4();
+        let mut run = service.create_payroll_run(tenant_id, request).unwrap();
+        let employee = create_test_employee();
+        let employee_id = employee.employee_id;
+        let items = service.process_payroll(&mut run, vec![employee], Uuid::new_v4()).unwrap().items;
+
+        assert!(service.payment_file(tenant_id, run.id).is_err(), "unapproved run must not be paid");
+        service.approve_payroll(&mut run, Uuid::new_v4()).unwrap();
+
+        assert!(matches!(service.payment_file(Uuid::new_v4(), run.id), Err(PayrollError::NotFound(_))));
+        let file = service.payment_file(tenant_id, run.id).unwrap();
+        let paid = service.disbursements().get(run.id, employee_id).unwrap();
+        assert_eq!(file.lines().count(), 2);
+        assert!(file.contains(&paid.reference));
+        assert!(file.contains(&format!("GTBank,0123456789,Test Employee,{},NGN", items[0].net_pay)));
+
+        // The upload failed: regenerating repeats the unconfirmed line as is
+        let again = service.payment_file(tenant_id, run.id).unwrap();
+        assert_eq!(again, file);
+
+        // Once the bank confirms, replays through either channel send nothing
+        assert!(service.confirm_disbursements(Uuid::new_v4(), run.id, &[employee_id]).is_err());
+        assert!(service.confirm_disbursements(tenant_id, run.id, &[Uuid::new_v4()]).is_err());
+        let confirmed = service.confirm_disbursements(tenant_id, run.id, &[employee_id]).unwrap();
+        assert_eq!(confirmed[0].status, DisbursementStatus::Confirmed);
+        assert_eq!(service.payment_file(tenant_id, run.id).unwrap().lines().count(), 1);
+        let recipient = MobileMoneyDisbursementRequest {
+            phone_number: "+2348031234567".to_string(),
+            recipient_name: "Test Employee".to_string(),
+            country: "NG".to_string(),
+            provider: None,
+        };
+        let mobile = service.mobile_money_payment(tenant_id, run.id, employee_id, &recipient).unwrap();
+        assert!(mobile.is_none());
+        let paid = service.disbursements().get(run.id, employee_id).unwrap();
+        let replay = service.disburse_once(tenant_id, run.id, employee_id, DisbursementChannel::MobileMoney).unwrap();
+        assert_eq!(replay, Disbursed::Existing(paid));
+        assert_eq!(service.disbursements().len(), 1);
+
+        // No payroll currency for the employee's country: nothing is paid
+        let (colleague, mut salary) = (Uuid::new_v4(), service.run_inputs.get(&run.id).unwrap()[0].clone());
+        salary.employee_id = colleague;
+        salary.country_code = "XX".to_string();
+        service.run_inputs.get_mut(&run.id).unwrap().push(salary);
+        let mut item = items[0].clone();
+        item.employee_id = colleague;
+        service.run_items.get_mut(&run.id).unwrap().push(item);
+        let result = service.disburse_once(tenant_id, run.id, colleague, DisbursementChannel::BankTransfer);
+        assert!(matches!(result, Err(PayrollError::UnsupportedCountry(c)) if c == "XX"));
+        assert_eq!(service.disbursements().len(), 1);
+    }
+
+    #[test]
+    fn test_repayments_deducted_above_protected_floor() {
+        let service = PayrollService::new()
+            .with_protected_earnings(ProtectedEarnings { minimum_net: dec!(300_000), share_of_net: Decimal::ZERO });
+        let request = CreatePayrollRunRequest {
+            name: "July 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 7, 31).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+        let employee = create_test_employee();
+        let employee_id = employee.employee_id;
+        service.repayments().add(crate::payroll::RepaymentSchedule::new(employee_id, RepaymentKind::Garnishment, dec!(40_000), dec!(20_000)));
+        service.repayments().add(crate::payroll::RepaymentSchedule::new(employee_id, RepaymentKind::Loan, dec!(500_000), dec!(100_000)));
+
+        let item = service.process_payroll(&mut run, vec![employee], Uuid::new_v4()).unwrap().items.remove(0);
+
+        assert_eq!(item.net_pay, dec!(300_000));
+        assert_eq!(item.other_deductions[GARNISHMENT_LINE], serde_json::json!(dec!(20_000)));
+        assert!(item.loan_repayment > Decimal::ZERO && item.loan_repayment < dec!(100_000));
+        assert_eq!(item.gross_pay - item.total_deductions, item.net_pay);
+        assert!(service.gl_journal(run.id, &GlAccountMap::default()).unwrap().is_balanced());
+
+        // Recalculating the draft takes the same amounts, not a second helping
+        service.recalculate_draft_runs("NG", 2024).unwrap();
+        let loan = service.repayments().for_employee(employee_id).into_iter().find(|s| s.kind == RepaymentKind::Loan).unwrap();
+        assert_eq!(loan.remaining, dec!(500_000) - item.loan_repayment);
+    }
+
+    #[test]
+    fn test_salary_advance_recovered_without_extra_tax() {
+        let service = PayrollService::new();
+        let employee = create_test_employee();
+        let colleague = EmployeeSalary { employee_id: Uuid::new_v4(), ..employee.clone() };
+
+        let advance = service.salary_advance(&employee, dec!(60_000), 2, DisbursementChannel::BankTransfer).unwrap();
+        assert_eq!(service.advances().for_employee(employee.employee_id)[0], advance);
+        assert!(service.salary_advance(&employee, dec!(60_000), 0, DisbursementChannel::BankTransfer).is_err());
+
+        // Paid in full, untaxed, and booked to the advances receivable
+        assert_eq!((advance.tax_withheld, advance.net_paid), (Decimal::ZERO, dec!(60_000)));
+        let payment = service.disbursements().get(advance.id, employee.employee_id).unwrap();
+        assert_eq!((payment.id, payment.amount, payment.currency.as_str()), (advance.disbursement_id, dec!(60_000), "NGN"));
+        let accounts = GlAccountMap::default();
+        let journal = service.advance_journal(advance.id, &accounts).unwrap();
+        assert!(journal.is_balanced());
+        let receivable = |journal: &GlJournal| {
+            journal.lines.iter().filter(|l| l.account == accounts.advances_receivable).map(JournalLine::amount).sum::<Decimal>()
+        };
+        assert_eq!(receivable(&journal), dec!(60_000));
+
+        let mut recovered = Vec::new();
+        for month in [8, 9, 10] {
+            let request = CreatePayrollRunRequest {
+                name: format!("2024-{:02} Payroll", month),
+                period_start: NaiveDate::from_ymd_opt(2024, month, 1).unwrap(),
+                period_end: NaiveDate::from_ymd_opt(2024, month, 28).unwrap(),
+                notes: None,
+                legal_entity_id: None,
+            };
+            let mut run = service.create_payroll_run(Uuid::new_v4(), request).unwrap();
+            let items = service.process_payroll(&mut run, vec![employee.clone(), colleague.clone()], Uuid::new_v4()).unwrap().items;
+            let (mine, theirs) = (&items[0], &items[1]);
+
+            // Same gross, same tax as a colleague without an advance
+            assert_eq!(mine.gross_pay, theirs.gross_pay);
+            assert_eq!(mine.paye_tax, theirs.paye_tax);
+            recovered.push(theirs.net_pay - mine.net_pay);
+            assert_eq!(mine.other_deductions.get(SALARY_ADVANCE_LINE).is_some(), month < 10);
+            // Recoveries clear the receivable
+            assert_eq!(receivable(&service.gl_journal(run.id, &accounts).unwrap()), -(theirs.net_pay - mine.net_pay));
+        }
+
+        assert_eq!(recovered, [dec!(30_000), dec!(30_000), Decimal::ZERO]);
+        let schedule = &service.repayments().for_employee(employee.employee_id)[0];
+        assert_eq!(schedule.id, advance.repayment_schedule_id);
+        assert!(schedule.is_complete());
+    }
+
+    #[test]
+    fn test_salary_advance_refused_where_runs_cannot_recover_it() {
+        let service = PayrollService::new();
+
+        for country in ["US", "XX", ""] {
+            let employee = EmployeeSalary { country_code: country.to_string(), ..create_test_employee() };
+            let result = service.salary_advance(&employee, dec!(1_000), 2, DisbursementChannel::BankTransfer);
+            assert!(matches!(result, Err(PayrollError::UnsupportedCountry(c)) if c == country));
+            assert!(service.advances().for_employee(employee.employee_id).is_empty());
+            assert!(service.repayments().for_employee(employee.employee_id).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_run_for_one_legal_entity_excludes_another() {
+        use crate::domain::aggregates::LegalEntity;
+
+        let tenant_id = Uuid::new_v4();
+        let lagos = LegalEntity::new(tenant_id, "Acme Nigeria Ltd", "ng", "TIN-10293847");
+        let joburg = LegalEntity::new(tenant_id, "Acme South Africa (Pty) Ltd", "ZA", "7001234567");
+        let entities = LegalEntities::new();
+        entities.add(lagos.clone());
+        entities.add(joburg.clone());
+        let service = PayrollService::new().with_legal_entities(entities);
+
+        let in_entity = |entity: &LegalEntity| EmployeeSalary {
+            employee_id: Uuid::new_v4(),
+            legal_entity_id: Some(entity.id),
+            ..create_test_employee()
+        };
+        let (ada, tunde) = (in_entity(&lagos), in_entity(&lagos));
+        // Record still says NG; the employing entity decides
+        let thabo = in_entity(&joburg);
+        let everyone = vec![ada.clone(), tunde.clone(), thabo.clone()];
+
+        let request = |legal_entity_id| CreatePayrollRunRequest {
+            name: "June 2024 Payroll".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(),
+            notes: None,
+            legal_entity_id,
+        };
+        let mut ng_run = service.create_payroll_run(tenant_id, request(Some(lagos.id))).unwrap();
+        let ng_items = service.process_payroll(&mut ng_run, everyone.clone(), Uuid::new_v4()).unwrap().items;
+        let mut paid: Vec<_> = ng_items.iter().map(|i| i.employee_id).collect();
+        paid.sort();
+        let mut expected = vec![ada.employee_id, tunde.employee_id];
+        expected.sort();
+        assert_eq!(paid, expected);
+        assert!(ng_items.iter().all(|i| i.other_deductions.get("uif").is_none() && i.nhf_deduction > Decimal::ZERO));
+
+        let mut za_run = service.create_payroll_run(tenant_id, request(Some(joburg.id))).unwrap();
+        let za_items = service.process_payroll(&mut za_run, everyone, Uuid::new_v4()).unwrap().items;
+        assert_eq!(za_items.len(), 1);
+        assert_eq!(za_items[0].employee_id, thabo.employee_id);
+        assert!(za_items[0].other_deductions.get("uif").is_some());
+        assert_eq!(za_items[0].nhf_deduction, Decimal::ZERO);
+
+        // Another tenant's entity can't be paid from this one
+        let err = service.create_payroll_run(Uuid::new_v4(), request(Some(lagos.id))).unwrap_err();
+        assert!(matches!(err, PayrollError::Validation(_)));
+
+        // A run without an entity leaves entity staff to their entity's run
+        let mut unscoped = service.create_payroll_run(tenant_id, request(None)).unwrap();
+        let err = service.process_payroll(&mut unscoped, vec![ada.clone(), thabo.clone()], Uuid::new_v4()).unwrap_err();
+        assert!(matches!(err, PayrollError::NoEmployees));
+        let contractor = create_test_employee();
+        let items = service.process_payroll(&mut unscoped, vec![ada.clone(), contractor.clone()], Uuid::new_v4()).unwrap().items;
+        assert_eq!(items.iter().map(|i| i.employee_id).collect::<Vec<_>>(), [contractor.employee_id]);
+
+        // A run whose entity has gone is refused rather than paying everyone
+        let mut orphaned = PayrollRun::new(tenant_id, "June 2024 Payroll".to_string(), request(None).period_start, request(None).period_end);
+        orphaned.legal_entity_id = Some(Uuid::new_v4());
+        let err = service.process_payroll(&mut orphaned, vec![ada, contractor], Uuid::new_v4()).unwrap_err();
+        assert!(matches!(err, PayrollError::Validation(_)));
+    }
+
+    #[test]
+    fn test_emp201_totals_match_payslips() {
+        use crate::domain::aggregates::LegalEntity;
+        use crate::payroll::StatutoryReportFormat;
+
+        let tenant_id = Uuid::new_v4();
+        let joburg = LegalEntity::new(tenant_id, "Acme South Africa (Pty) Ltd", "ZA", "7001234567");
+        let lagos = LegalEntity::new(tenant_id, "Acme Nigeria Ltd", "NG", "TIN-10293847");
+        let entities = LegalEntities::new();
+        entities.add(joburg.clone());
+        entities.add(lagos.clone());
+        let service = PayrollService::new().with_legal_entities(entities);
+
+        let employee = |entity: &LegalEntity, basic| EmployeeSalary {
+            employee_id: Uuid::new_v4(),
+            legal_entity_id: Some(entity.id),
+            basic_salary: basic,
+            ..create_test_employee()
+        };
+        let za_staff = vec![employee(&joburg, dec!(18_000)), employee(&joburg, dec!(45_000)), employee(&joburg, dec!(120_000))];
+        let june = |day| NaiveDate::from_ymd_opt(2024, 6, day).unwrap();
+        let request = |legal_entity_id, first, last| CreatePayrollRunRequest {
+            name: format!("June 2024 Payroll, {} to {}", first, last),
+            period_start: june(first),
+            period_end: june(last),
+            notes: None,
+            legal_entity_id,
+        };
+        let approved_run = |entity: &LegalEntity, first, last, staff: Vec<EmployeeSalary>| {
+            let mut run = service.create_payroll_run(tenant_id, request(Some(entity.id), first, last)).unwrap();
+            let items = service.process_payroll(&mut run, staff, Uuid::new_v4()).unwrap().items;
+            service.approve_payroll(&mut run, Uuid::new_v4()).unwrap();
+            items
+        };
+        let items = approved_run(&joburg, 1, 15, za_staff.clone());
+        // Another entity's run and a run still awaiting approval stay off the return
+        approved_run(&lagos, 1, 30, vec![employee(&lagos, dec!(300_000))]);
+        let mut pending = service.create_payroll_run(tenant_id, request(Some(joburg.id), 16, 30)).unwrap();
+        service.process_payroll(&mut pending, za_staff, Uuid::new_v4()).unwrap();
+
+        let report = service.statutory_report(joburg.id, june(1), june(30)).unwrap();
+        assert_eq!(report.format, StatutoryReportFormat::Emp201);
+        assert_eq!(report.tax_registration, "7001234567");
+        assert_eq!(report.payroll_run_ids.len(), 1);
+        assert!(report.schedule.is_empty());
+
+        let sum = |f: &dyn Fn(&PayrollItem) -> Decimal| items.iter().map(f).sum::<Decimal>();
+        let paye = sum(&|i| i.paye_tax);
+        let uif_employee = sum(&|i| serde_json::from_value::<Decimal>(i.other_deductions["uif"].clone()).unwrap());
+        let uif_employer = sum(&|i| i.employer_contributions["uif"]);
+        let sdl = sum(&|i| i.employer_contributions["sdl"]);
+        assert!(paye > Decimal::ZERO && uif_employee > Decimal::ZERO && sdl > Decimal::ZERO);
+
+        assert_eq!(report.line("paye").unwrap().employee_amount, paye);
+        assert_eq!(report.line("paye").unwrap().employer_amount, Decimal::ZERO);
+        assert_eq!(report.line("sdl").unwrap().total, sdl);
+        let uif = report.line("uif").unwrap();
+        assert_eq!((uif.employee_amount, uif.employer_amount), (uif_employee, uif_employer));
+        assert_eq!(report.total_liability, paye + uif_employee + uif_employer + sdl);
+
+        // The Nigerian entity files a per-employee PAYE schedule
+        let ng = service.statutory_report(lagos.id, june(1), june(30)).unwrap();
+        assert_eq!(ng.format, StatutoryReportFormat::PayeSchedule);
+        assert_eq!(ng.schedule.len(), 1);
+        assert_eq!(ng.schedule[0].tax, ng.line("paye").unwrap().total);
+    }
+
+    #[test]
+    fn test_incomplete_onboarding_excludes_employee_from_run() {
+        use crate::domain::aggregates::{DocumentType, Employee, EmployeeDocument};
+        use crate::domain::value_objects::EmployeeId;
+
+        let period = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let mut hire = Employee::hire(EmployeeId::new(2024, 7), "Chidi", "Okeke", "chidi@company.com", "Analyst", period);
+        for (doc_type, code) in [(DocumentType::Contract, "contract_signed"), (DocumentType::TaxForm, "tax_forms")] {
+            hire.attach_document(EmployeeDocument { id: code.to_string(), doc_type, name: code.to_string(), uploaded_at: Utc::now() });
+            hire.complete_onboarding_item(code, "hr-1").unwrap();
+        }
+        let salary = |employee: &Employee| EmployeeSalary {
+            employee_id: employee.id().parse().unwrap(),
+            employee_name: "Chidi Okeke".to_string(),
+            onboarding_outstanding: Some(employee.onboarding_outstanding()),
+            ..create_test_employee()
+        };
+        let colleague = create_test_employee();
+        let service = PayrollService::new();
+        let request = || CreatePayrollRunRequest {
+            name: "June 2024 Payroll".to_string(),
+            period_start: period,
+            period_end: NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(),
+            notes: None,
+            legal_entity_id: None,
+        };
+
+        let mut run = service.create_payroll_run(Uuid::new_v4(), request()).unwrap();
+        let result = service.process_payroll(&mut run, vec![colleague.clone(), salary(&hire)], Uuid::new_v4()).unwrap();
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].employee_id, colleague.employee_id);
+        assert_eq!(result.skipped[0].reason, "Onboarding incomplete: bank_details");
+
+        // Alone in a run, nobody is left to pay
+        let mut alone = service.create_payroll_run(Uuid::new_v4(), request()).unwrap();
+        assert!(matches!(service.process_payroll(&mut alone, vec![salary(&hire)], Uuid::new_v4()), Err(PayrollError::NoEmployees)));
+
+        // Without a known onboarding status nobody is paid
+        let unknown = EmployeeSalary { onboarding_outstanding: None, ..colleague.clone() };
+        let mut unsure = service.create_payroll_run(Uuid::new_v4(), request()).unwrap();
+        let result = service.process_payroll(&mut unsure, vec![unknown, salary(&hire)], Uuid::new_v4());
+        assert!(matches!(result, Err(PayrollError::NoEmployees)));
+
+        hire.complete_onboarding_item("bank_details", "payroll-1").unwrap();
+        let mut next = service.create_payroll_run(Uuid::new_v4(), request()).unwrap();
+        let result = service.process_payroll(&mut next, vec![colleague, salary(&hire)], Uuid::new_v4()).unwrap();
+        assert_eq!(result.items.len(), 2);
+        assert!(result.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_department_costs_convert_at_run_period_rate() {
+        let fx_rates = FxRates::new();
+        let d = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        fx_rates.record("NGN", "USD", d(6, 3), dec!(0.00068)).unwrap();
+        fx_rates.record("NGN", "USD", d(7, 1), dec!(0.00064)).unwrap();
+        let service = PayrollService::new().with_fx_rates(fx_rates);
+
+        let request = CreatePayrollRunRequest {
+            name: "June 2024 Payroll".to_string(),
+            period_start: d(6, 1),
+            period_end: d(6, 30),
+            notes: None,
+            legal_entity_id: None,
+        };
+        let mut june = service.create_payroll_run(Uuid::new_v4(), request.clone()).unwrap();
+        service.process_payroll(&mut june, vec![create_test_employee()], Uuid::new_v4()).unwrap();
+
+        // 470,000 employer cost at June's rate, not July's
+        let report = service.employer_cost_by_department_at_run_rate(june.id, "USD").unwrap();
+        assert_eq!(report.exchange_rate, dec!(0.00068));
+        assert_eq!(report.total, dec!(319.60));
+
+        let mut may = service
+            .create_payroll_run(Uuid::new_v4(), CreatePayrollRunRequest { period_start: d(5, 1), period_end: d(5, 31), ..request })
+            .unwrap();
+        service.process_payroll(&mut may, vec![create_test_employee()], Uuid::new_v4()).unwrap();
+        assert!(matches!(
+            service.employer_cost_by_department_at_run_rate(may.id, "USD"),
+            Err(PayrollError::ExchangeRate(FxError::BeforeHistory { .. }))
+        ));
+    }
 }
@@ -0,0 +1,411 @@
+//! Severance Calculators
+//!
+//! Statutory termination payments by country. Each calculator takes the
+//! employee's pay, dates, and how the employment ended, and returns the
+//! amount with a line per component. `SeveranceCalculators` picks the
+//! calculator for a country and falls back to a tenure-based formula where
+//! no statutory rule is registered. When the employer ends employment with
+//! immediate effect it also adds pay in lieu of the notice owed.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use super::middle_east::UAETaxCalculator;
+use super::notice::NoticePeriods;
+use super::rounding::MoneyRounding;
+use crate::domain::aggregates::tenure_years;
+
+/// Component code for notice paid instead of worked
+pub const PAY_IN_LIEU_OF_NOTICE: &str = "pay_in_lieu_of_notice";
+
+/// How the employment ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminationType {
+    /// Dismissal without cause, redundancy, or end of contract
+    EmployerInitiated,
+    Resignation,
+    /// Summary dismissal for misconduct
+    ForCause,
+}
+
+/// Severance errors
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SeveranceError {
+    #[error("Termination date {termination_date} is before hire date {hire_date}")]
+    InvalidTerminationDate { hire_date: NaiveDate, termination_date: NaiveDate },
+}
+
+/// Facts a severance calculation needs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeveranceInput {
+    /// Monthly pay the statute bases severance on (basic salary in the Gulf)
+    pub monthly_salary: Decimal,
+    pub hire_date: NaiveDate,
+    pub termination_date: NaiveDate,
+    pub termination_type: TerminationType,
+    /// Brazil: FGTS account balance, when known
+    #[serde(default)]
+    pub fgts_balance: Option<Decimal>,
+    /// Employment ends without the notice period being worked
+    #[serde(default)]
+    pub immediate: bool,
+}
+
+impl SeveranceInput {
+    pub fn service_years(&self) -> Result<Decimal, SeveranceError> {
+        if self.termination_date < self.hire_date {
+            return Err(SeveranceError::InvalidTerminationDate {
+                hire_date: self.hire_date,
+                termination_date: self.termination_date,
+            });
+        }
+        Ok(tenure_years(self.hire_date, self.termination_date))
+    }
+}
+
+/// One line of a severance payment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeveranceComponent {
+    pub code: String,
+    pub amount: Decimal,
+}
+
+/// Severance owed on termination
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeveranceResult {
+    pub country_code: String,
+    pub service_years: Decimal,
+    pub components: Vec<SeveranceComponent>,
+    pub total: Decimal,
+}
+
+impl SeveranceResult {
+    fn new(country_code: &str, service_years: Decimal, components: Vec<(&str, Decimal)>) -> Self {
+        let rounding = MoneyRounding::default();
+        let components: Vec<SeveranceComponent> = components
+            .into_iter()
+            .map(|(code, amount)| SeveranceComponent { code: code.to_string(), amount: rounding.round(amount) })
+            .collect();
+        let total = components.iter().map(|c| c.amount).sum();
+        Self { country_code: country_code.to_string(), service_years, components, total }
+    }
+}
+
+/// Statutory severance for one country
+pub trait SeveranceCalculator: Send + Sync {
+    fn calculate(&self, country_code: &str, input: &SeveranceInput) -> Result<SeveranceResult, SeveranceError>;
+
+    /// Whether the result already pays the notice period
+    fn includes_notice(&self) -> bool {
+        false
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// UAE END-OF-SERVICE GRATUITY
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// UAE gratuity: 21 days' basic per year for the first five years, 30 days
+/// per year after. Resignation before five years pays half, before one
+/// year nothing; dismissal for cause forfeits it.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UaeGratuity;
+
+impl SeveranceCalculator for UaeGratuity {
+    fn calculate(&self, country_code: &str, input: &SeveranceInput) -> Result<SeveranceResult, SeveranceError> {
+        let years = input.service_years()?;
+        let gratuity = match input.termination_type {
+            TerminationType::ForCause => Decimal::ZERO,
+            termination_type => UAETaxCalculator::new().calculate_gratuity(
+                input.monthly_salary,
+                years,
+                termination_type == TerminationType::Resignation,
+            ),
+        };
+        Ok(SeveranceResult::new(country_code, years, vec![("end_of_service_gratuity", gratuity)]))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// BRAZIL
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Brazil dismissal without just cause (Lei 12.506/2011, Lei 8.036/90):
+/// indemnified notice of 30 days plus 3 per full year (max 90), and a 40%
+/// fine on the FGTS balance. The balance is estimated from 8% monthly
+/// deposits when not supplied. Resignation and just cause pay neither.
+#[derive(Debug, Clone, Copy)]
+pub struct BrazilSeverance {
+    pub fgts_fine_rate: Decimal,
+    pub fgts_deposit_rate: Decimal,
+}
+
+impl Default for BrazilSeverance {
+    fn default() -> Self {
+        Self { fgts_fine_rate: dec!(0.40), fgts_deposit_rate: dec!(0.08) }
+    }
+}
+
+impl SeveranceCalculator for BrazilSeverance {
+    fn calculate(&self, country_code: &str, input: &SeveranceInput) -> Result<SeveranceResult, SeveranceError> {
+        let years = input.service_years()?;
+        if input.termination_type != TerminationType::EmployerInitiated {
+            return Ok(SeveranceResult::new(country_code, years, vec![]));
+        }
+
+        let notice_days = (dec!(30) + dec!(3) * years.floor()).min(dec!(90));
+        let notice = input.monthly_salary / dec!(30) * notice_days;
+
+        let fgts_balance = input
+            .fgts_balance
+            .unwrap_or_else(|| input.monthly_salary * self.fgts_deposit_rate * (years * dec!(12)).floor());
+        let fgts_fine = fgts_balance * self.fgts_fine_rate;
+
+        Ok(SeveranceResult::new(
+            country_code,
+            years,
+            vec![("aviso_previo_indenizado", notice), ("multa_fgts", fgts_fine)],
+        ))
+    }
+
+    fn includes_notice(&self) -> bool {
+        true
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// GENERIC TENURE-BASED
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Weeks of pay per year of service, paid on employer-initiated
+/// termination once the qualifying period is met
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TenureSeverance {
+    pub weeks_per_year: Decimal,
+    /// Service needed before anything is owed
+    pub qualifying_years: Decimal,
+    /// Cap on total weeks paid
+    pub max_weeks: Option<Decimal>,
+}
+
+impl Default for TenureSeverance {
+    /// One week per year after a year's service, uncapped
+    fn default() -> Self {
+        Self { weeks_per_year: dec!(1), qualifying_years: dec!(1), max_weeks: None }
+    }
+}
+
+impl SeveranceCalculator for TenureSeverance {
+    fn calculate(&self, country_code: &str, input: &SeveranceInput) -> Result<SeveranceResult, SeveranceError> {
+        let years = input.service_years()?;
+        if input.termination_type != TerminationType::EmployerInitiated || years < self.qualifying_years {
+            return Ok(SeveranceResult::new(country_code, years, vec![]));
+        }
+
+        let mut weeks = self.weeks_per_year * years;
+        if let Some(max_weeks) = self.max_weeks {
+            weeks = weeks.min(max_weeks);
+        }
+        let weekly_pay = input.monthly_salary * dec!(12) / dec!(52);
+        Ok(SeveranceResult::new(country_code, years, vec![("severance_pay", weekly_pay * weeks)]))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// SELECTION
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Severance calculator per country, with a tenure-based fallback
+#[derive(Clone)]
+pub struct SeveranceCalculators {
+    by_country: HashMap<String, Arc<dyn SeveranceCalculator>>,
+    fallback: Arc<dyn SeveranceCalculator>,
+    notice: NoticePeriods,
+}
+
+impl Default for SeveranceCalculators {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SeveranceCalculators {
+    /// Built-in statutory rules (AE, BR) and the default tenure formula
+    pub fn new() -> Self {
+        let mut calculators = Self {
+            by_country: HashMap::new(),
+            fallback: Arc::new(TenureSeverance::default()),
+            notice: NoticePeriods::new(),
+        };
+        calculators.register("AE", UaeGratuity);
+        calculators.register("BR", BrazilSeverance::default());
+        calculators
+    }
+
+    pub fn register(&mut self, country_code: &str, calculator: impl SeveranceCalculator + 'static) {
+        self.by_country.insert(country_code.to_ascii_uppercase(), Arc::new(calculator));
+    }
+
+    /// Formula for countries without a registered rule
+    pub fn with_fallback(mut self, calculator: impl SeveranceCalculator + 'static) -> Self {
+        self.fallback = Arc::new(calculator);
+        self
+    }
+
+    /// Notice rules used for pay in lieu
+    pub fn with_notice_periods(mut self, notice: NoticePeriods) -> Self {
+        self.notice = notice;
+        self
+    }
+
+    pub fn has_statutory_rule(&self, country_code: &str) -> bool {
+        self.by_country.contains_key(&country_code.to_ascii_uppercase())
+    }
+
+    pub fn calculate(&self, country_code: &str, input: &SeveranceInput) -> Result<SeveranceResult, SeveranceError> {
+        let country_code = country_code.to_ascii_uppercase();
+        let calculator = self.by_country.get(&country_code).unwrap_or(&self.fallback);
+        let mut result = calculator.calculate(&country_code, input)?;
+
+        let pays_in_lieu = input.immediate && input.termination_type == TerminationType::EmployerInitiated;
+        if pays_in_lieu && !calculator.includes_notice() {
+            let notice = self
+                .notice
+                .for_country(&country_code)
+                .notice_for(input.hire_date, input.termination_date, result.service_years);
+            let amount = MoneyRounding::default().round(notice.pay(input.monthly_salary));
+            if !amount.is_zero() {
+                result.components.push(SeveranceComponent { code: PAY_IN_LIEU_OF_NOTICE.to_string(), amount });
+                result.total += amount;
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(years_ago: i32, termination_type: TerminationType) -> SeveranceInput {
+        let termination_date = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        SeveranceInput {
+            monthly_salary: dec!(15_000),
+            hire_date: NaiveDate::from_ymd_opt(2024 - years_ago, 6, 30).unwrap(),
+            termination_date,
+            termination_type,
+            fgts_balance: None,
+            immediate: false,
+        }
+    }
+
+    #[test]
+    fn test_uae_gratuity_three_years() {
+        let result = SeveranceCalculators::new()
+            .calculate("ae", &input(3, TerminationType::EmployerInitiated))
+            .unwrap();
+
+        assert_eq!(result.service_years, dec!(3));
+        // 500/day × 21 days × 3 years
+        assert_eq!(result.total, dec!(31_500));
+
+        let resigned = SeveranceCalculators::new().calculate("AE", &input(3, TerminationType::Resignation)).unwrap();
+        assert_eq!(resigned.total, dec!(15_750));
+    }
+
+    #[test]
+    fn test_uae_gratuity_seven_years() {
+        let result = SeveranceCalculators::new()
+            .calculate("AE", &input(7, TerminationType::EmployerInitiated))
+            .unwrap();
+
+        // 500/day × (21 × 5 + 30 × 2)
+        assert_eq!(result.total, dec!(82_500));
+    }
+
+    #[test]
+    fn test_brazil_dismissal() {
+        let mut dismissed = input(4, TerminationType::EmployerInitiated);
+        dismissed.fgts_balance = Some(dec!(60_000));
+        let result = SeveranceCalculators::new().calculate("BR", &dismissed).unwrap();
+
+        // 42 days' notice and 40% of the FGTS balance
+        assert_eq!(result.components[0].amount, dec!(21_000));
+        assert_eq!(result.components[1].amount, dec!(24_000));
+        assert_eq!(result.total, dec!(45_000));
+
+        let resigned = SeveranceCalculators::new().calculate("BR", &input(4, TerminationType::Resignation)).unwrap();
+        assert_eq!(resigned.total, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_generic_tenure_formula() {
+        let calculators = SeveranceCalculators::new().with_fallback(TenureSeverance {
+            weeks_per_year: dec!(2),
+            qualifying_years: dec!(2),
+            max_weeks: Some(dec!(20)),
+        });
+        assert!(!calculators.has_statutory_rule("GB"));
+
+        // 2 weeks × 6 years = 12 weeks of 15,000 × 12 / 52
+        let result = calculators.calculate("GB", &input(6, TerminationType::EmployerInitiated)).unwrap();
+        assert_eq!(result.total, dec!(41_538.46));
+
+        // Capped at 20 weeks
+        let capped = calculators.calculate("GB", &input(15, TerminationType::EmployerInitiated)).unwrap();
+        assert_eq!(capped.total, dec!(69_230.77));
+
+        let short = calculators.calculate("GB", &input(1, TerminationType::EmployerInitiated)).unwrap();
+        assert_eq!(short.total, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_pay_in_lieu_of_notice_scales_with_tenure() {
+        let calculators = SeveranceCalculators::new();
+        let immediate = |years| SeveranceInput { immediate: true, ..input(years, TerminationType::EmployerInitiated) };
+        let notice = |result: &SeveranceResult| {
+            result.components.iter().find(|c| c.code == PAY_IN_LIEU_OF_NOTICE).map(|c| c.amount)
+        };
+
+        // Nigeria, 1 year: one week of 15,000 × 12 / 52
+        let junior = calculators.calculate("NG", &immediate(1)).unwrap();
+        assert_eq!(notice(&junior), Some(dec!(3_461.54)));
+
+        // 6 years: one month, on top of the tenure severance
+        let senior = calculators.calculate("NG", &immediate(6)).unwrap();
+        assert_eq!(notice(&senior), Some(dec!(15_000)));
+        assert_eq!(senior.total, senior.components[0].amount + dec!(15_000));
+
+        // Worked notice and resignations pay nothing in lieu
+        assert_eq!(notice(&calculators.calculate("NG", &input(6, TerminationType::EmployerInitiated)).unwrap()), None);
+        let resigned = SeveranceInput { immediate: true, ..input(6, TerminationType::Resignation) };
+        assert_eq!(notice(&calculators.calculate("NG", &resigned).unwrap()), None);
+        // Brazil's indemnified notice is already part of its severance
+        assert_eq!(notice(&calculators.calculate("BR", &immediate(4)).unwrap()), None);
+    }
+
+    #[test]
+    fn test_notice_during_probation() {
+        let period = NoticePeriods::new().for_country("NG").clone();
+        let hired = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+
+        let notice = period.notice_for(hired, NaiveDate::from_ymd_opt(2024, 6, 15).unwrap(), dec!(0.2));
+        assert_eq!(notice, crate::payroll::notice::NoticeLength::days(dec!(1)));
+        assert_eq!(notice.pay(dec!(15_000)), dec!(500));
+    }
+
+    #[test]
+    fn test_termination_before_hire_is_rejected() {
+        let mut bad = input(3, TerminationType::EmployerInitiated);
+        bad.termination_date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        assert!(matches!(
+            SeveranceCalculators::new().calculate("AE", &bad),
+            Err(SeveranceError::InvalidTerminationDate { .. })
+        ));
+    }
+}
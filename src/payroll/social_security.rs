@@ -0,0 +1,82 @@
+//! Partial-Period Social Security
+//!
+//! How much of a period's social-security base a new starter contributes
+//! on. A starter is paid for the calendar days employed (see `proration`),
+//! and most schemes take contributions from that prorated pay. Some are
+//! owed on a full period's pay for any month of coverage, however few days
+//! that is.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// How a partial period is treated for social-security contributions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SocialSecurityProration {
+    /// Base scales with the calendar days employed in the period
+    ActiveDays,
+    /// A full period's contribution is owed for any day employed
+    FullPeriod,
+}
+
+/// Proration rules per country, falling back to `ActiveDays`
+#[derive(Debug, Clone)]
+pub struct SocialSecurityProrations {
+    countries: HashMap<String, SocialSecurityProration>,
+    default: SocialSecurityProration,
+}
+
+impl Default for SocialSecurityProrations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SocialSecurityProrations {
+    /// Built-in statutory rules
+    pub fn new() -> Self {
+        let mut rules = Self { countries: HashMap::new(), default: SocialSecurityProration::ActiveDays };
+        // Health and pension insurance premiums are charged from the month coverage is acquired
+        rules.set("JP", SocialSecurityProration::FullPeriod);
+        // SSS contributions follow the monthly salary credit, not days worked
+        rules.set("PH", SocialSecurityProration::FullPeriod);
+        rules
+    }
+
+    pub fn set(&mut self, country_code: &str, rule: SocialSecurityProration) -> &mut Self {
+        self.countries.insert(country_code.to_ascii_uppercase(), rule);
+        self
+    }
+
+    pub fn for_country(&self, country_code: &str) -> SocialSecurityProration {
+        self.countries.get(&country_code.to_ascii_uppercase()).copied().unwrap_or(self.default)
+    }
+
+    /// Whether a starter owes a full period's contributions for any day
+    /// employed in it, rather than contributions on the pay they earned
+    pub fn owes_full_period(&self, country_code: &str) -> bool {
+        self.for_country(country_code) == SocialSecurityProration::FullPeriod
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_period_countries() {
+        let rules = SocialSecurityProrations::new();
+        assert!(!rules.owes_full_period("NG"));
+        assert!(rules.owes_full_period("jp"));
+        assert!(rules.owes_full_period("PH"));
+    }
+
+    #[test]
+    fn test_country_rule_override() {
+        let mut rules = SocialSecurityProrations::new();
+        rules.set("ng", SocialSecurityProration::FullPeriod).set("JP", SocialSecurityProration::ActiveDays);
+
+        assert!(rules.owes_full_period("NG"));
+        assert!(!rules.owes_full_period("JP"));
+    }
+}
@@ -11,6 +11,9 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::value_objects::PayFrequency;
+use super::annualization::Annualization;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // SOUTH AFRICA TAX CALCULATOR
 // ═══════════════════════════════════════════════════════════════════════════
@@ -63,6 +66,7 @@ pub struct TaxBracket {
 }
 
 /// South Africa tax calculator
+#[derive(Debug, Clone)]
 pub struct SouthAfricaTaxCalculator {
     config: SouthAfricaConfig,
 }
@@ -76,8 +80,40 @@ impl SouthAfricaTaxCalculator {
         Self { config }
     }
     
+    /// UIF owed by each of employee and employer on a month's remuneration (capped at ceiling)
+    pub fn uif(&self, remuneration: Decimal) -> Decimal {
+        self.uif_for_period(remuneration, &PayFrequency::Monthly)
+    }
+    
+    /// UIF on one pay period's remuneration, capped at the monthly ceiling
+    /// prorated to the period
+    pub fn uif_for_period(&self, remuneration: Decimal, frequency: &PayFrequency) -> Decimal {
+        self.uif_annualized(remuneration, &Annualization::nominal(frequency))
+    }
+
+    /// UIF on one pay period's remuneration, with the annual ceiling spread
+    /// over the periods in `annualization`
+    pub fn uif_annualized(&self, remuneration: Decimal, annualization: &Annualization) -> Decimal {
+        let ceiling = annualization.monthly_to_period(self.config.uif_ceiling);
+        remuneration.min(ceiling) * self.config.uif_rate
+    }
+
     pub fn calculate(&self, gross_monthly: Decimal, age: u8) -> TaxResult {
-        let gross_annual = gross_monthly * dec!(12);
+        self.calculate_for_period(gross_monthly, age, &PayFrequency::Monthly)
+    }
+    
+    /// PAYE, UIF, and SDL on one pay period's remuneration. Pay is
+    /// annualized over `frequency`'s periods per year for the brackets and
+    /// the UIF ceiling is prorated to the period; the result's monthly
+    /// fields then hold per-period amounts.
+    pub fn calculate_for_period(&self, gross_monthly: Decimal, age: u8, frequency: &PayFrequency) -> TaxResult {
+        self.calculate_annualized(gross_monthly, age, &Annualization::nominal(frequency))
+    }
+
+    /// PAYE, UIF, and SDL on one pay period's remuneration, annualized over
+    /// the periods in `annualization`, e.g. the 27 of a long biweekly year
+    pub fn calculate_annualized(&self, gross_monthly: Decimal, age: u8, annualization: &Annualization) -> TaxResult {
+        let gross_annual = annualization.annualize(gross_monthly);
         
         // Calculate annual tax using brackets
         let tax_before_rebates = self.calculate_bracket_tax(gross_annual);
@@ -88,12 +124,10 @@ impl SouthAfricaTaxCalculator {
         if age >= 75 { total_rebates += self.config.tertiary_rebate; }
         
         let annual_paye = (tax_before_rebates - total_rebates).max(Decimal::ZERO);
-        let monthly_paye = annual_paye / dec!(12);
+        let monthly_paye = annualization.period_share(annual_paye);
         
-        // UIF (capped at ceiling)
-        let uif_base = gross_monthly.min(self.config.uif_ceiling);
-        let uif_employee = uif_base * self.config.uif_rate;
-        let uif_employer = uif_base * self.config.uif_rate;
+        let uif_employee = self.uif_annualized(gross_monthly, annualization);
+        let uif_employer = uif_employee;
         
         // SDL (employer only, if payroll > threshold)
         let sdl = gross_monthly * self.config.sdl_rate;
@@ -196,13 +230,19 @@ impl ZimbabweTaxCalculator {
     }
     
     pub fn calculate_usd(&self, gross_monthly: Decimal) -> TaxResult {
+        self.calculate_usd_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// USD PAYE and NSSA on one pay period's pay, taxed on the monthly
+    /// table at its monthly equivalent
+    pub fn calculate_usd_annualized(&self, gross_monthly: Decimal, annualization: &Annualization) -> TaxResult {
         // NSSA
         let nssa_employee = gross_monthly * self.config.nssa_rate;
         let nssa_employer = gross_monthly * self.config.nssa_rate;
         
         // PAYE on taxable (gross - NSSA)
-        let taxable = gross_monthly - nssa_employee;
-        let paye = self.calculate_progressive_tax(taxable, &self.config.usd_brackets);
+        let taxable = annualization.monthly_equivalent(gross_monthly - nssa_employee);
+        let paye = annualization.monthly_to_period(self.calculate_progressive_tax(taxable, &self.config.usd_brackets));
         
         // AIDS Levy (3% of PAYE)
         let aids_levy = paye * self.config.aids_levy_rate;
@@ -217,7 +257,7 @@ impl ZimbabweTaxCalculator {
             country_code: "ZW".to_string(),
             currency: "USD".to_string(),
             gross_monthly,
-            gross_annual: gross_monthly * dec!(12),
+            gross_annual: annualization.annualize(gross_monthly),
             monthly_paye: paye,
             uif_employee: nssa_employee,
             uif_employer: nssa_employer,
@@ -306,8 +346,14 @@ impl ZambiaTaxCalculator {
     }
     
     pub fn calculate(&self, gross_monthly: Decimal) -> TaxResult {
+        self.calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// PAYE, NAPSA, and NHIMA on one pay period's pay, with the annual NAPSA
+    /// ceiling spread over the periods in `annualization`
+    pub fn calculate_annualized(&self, gross_monthly: Decimal, annualization: &Annualization) -> TaxResult {
         // NAPSA (capped)
-        let napsa_base = (gross_monthly * dec!(12)).min(self.config.napsa_ceiling) / dec!(12);
+        let napsa_base = gross_monthly.min(self.config.napsa_ceiling / annualization.periods);
         let napsa_employee = napsa_base * self.config.napsa_rate;
         let napsa_employer = napsa_base * self.config.napsa_rate;
         
@@ -316,7 +362,7 @@ impl ZambiaTaxCalculator {
         let nhima_employer = gross_monthly * self.config.nhima_rate;
         
         // PAYE
-        let paye = self.calculate_progressive_tax(gross_monthly);
+        let paye = annualization.monthly_to_period(self.calculate_progressive_tax(annualization.monthly_equivalent(gross_monthly)));
         
         let total_employee = paye + napsa_employee + nhima_employee;
         let total_employer = napsa_employer + nhima_employer;
@@ -325,7 +371,7 @@ impl ZambiaTaxCalculator {
             country_code: "ZM".to_string(),
             currency: "ZMW".to_string(),
             gross_monthly,
-            gross_annual: gross_monthly * dec!(12),
+            gross_annual: annualization.annualize(gross_monthly),
             monthly_paye: paye,
             uif_employee: napsa_employee,
             uif_employer: napsa_employer,
@@ -422,12 +468,18 @@ impl AngolaTaxCalculator {
     }
     
     pub fn calculate(&self, gross_monthly: Decimal) -> TaxResult {
+        self.calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// IRT and INSS on one pay period's pay, taxed on the monthly table at
+    /// its monthly equivalent
+    pub fn calculate_annualized(&self, gross_monthly: Decimal, annualization: &Annualization) -> TaxResult {
         // INSS
         let inss_employee = gross_monthly * self.config.inss_employee_rate;
         let inss_employer = gross_monthly * self.config.inss_employer_rate;
         
         // IRT (on gross, INSS not deductible)
-        let irt = self.calculate_progressive_tax(gross_monthly);
+        let irt = annualization.monthly_to_period(self.calculate_progressive_tax(annualization.monthly_equivalent(gross_monthly)));
         
         let total_employee = irt + inss_employee;
         let total_employer = inss_employer;
@@ -436,7 +488,7 @@ impl AngolaTaxCalculator {
             country_code: "AO".to_string(),
             currency: "AOA".to_string(),
             gross_monthly,
-            gross_annual: gross_monthly * dec!(12),
+            gross_annual: annualization.annualize(gross_monthly),
             monthly_paye: irt,
             uif_employee: inss_employee,
             uif_employer: inss_employer,
@@ -533,6 +585,18 @@ impl SouthernAfricaRegistry {
 mod tests {
     use super::*;
     
+    #[test]
+    fn test_weekly_and_monthly_pay_same_annual_paye() {
+        let calc = SouthAfricaTaxCalculator::new();
+        let monthly = calc.calculate(dec!(26_000), 35);
+        let weekly = calc.calculate_for_period(dec!(6_000), 35, &PayFrequency::Weekly);
+        
+        assert_eq!(weekly.gross_annual, monthly.gross_annual);
+        assert_eq!((weekly.monthly_paye * dec!(52)).round_dp(2), (monthly.monthly_paye * dec!(12)).round_dp(2));
+        // The UIF ceiling is prorated too, so both pay the capped amount a year
+        assert_eq!((weekly.uif_employee * dec!(52)).round_dp(2), (monthly.uif_employee * dec!(12)).round_dp(2));
+    }
+    
     #[test]
     fn test_south_africa_calculator() {
         let calc = SouthAfricaTaxCalculator::new();
@@ -11,6 +11,9 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::value_objects::PayFrequency;
+use super::annualization::Annualization;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // BRAZIL TAX CALCULATOR
 // ═══════════════════════════════════════════════════════════════════════════
@@ -178,6 +181,18 @@ impl ArgentinaTaxCalculator {
     }
     
     pub fn calculate(&self, gross_monthly: Decimal, has_spouse: bool, children: u8) -> TaxResult {
+        self.calculate_annualized(gross_monthly, has_spouse, children, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// Aportes and Ganancias on one pay period's pay, annualized over the
+    /// periods in `annualization` plus the aguinaldo's month
+    pub fn calculate_annualized(
+        &self,
+        gross_monthly: Decimal,
+        has_spouse: bool,
+        children: u8,
+        annualization: &Annualization,
+    ) -> TaxResult {
         // Aportes (employee contributions)
         let jubilacion = gross_monthly * self.config.jubilacion_rate;
         let obra_social = gross_monthly * self.config.obra_social_rate;
@@ -188,13 +203,14 @@ impl ArgentinaTaxCalculator {
         let employer = gross_monthly * self.config.employer_total;
         
         // Ganancias calculation (simplified)
-        let gross_annual = gross_monthly * dec!(13); // Include aguinaldo
+        let with_aguinaldo = |amount| annualization.annualize(amount) + annualization.monthly_equivalent(amount);
+        let gross_annual = with_aguinaldo(gross_monthly);
         let family_deductions = Decimal::from(has_spouse as u8) * dec!(2_911_135) 
                               + Decimal::from(children) * dec!(1_468_096);
-        let taxable = (gross_annual - self.config.mni_annual - family_deductions - (total_aportes * dec!(13))).max(Decimal::ZERO);
+        let taxable = (gross_annual - self.config.mni_annual - family_deductions - with_aguinaldo(total_aportes)).max(Decimal::ZERO);
         
         let annual_ganancias = self.calculate_ganancias(taxable);
-        let monthly_ganancias = annual_ganancias / dec!(12);
+        let monthly_ganancias = annualization.period_share(annual_ganancias);
         
         // Aguinaldo (SAC) provision
         let aguinaldo = gross_monthly / dec!(12);
@@ -402,6 +418,12 @@ impl PeruTaxCalculator {
     }
     
     pub fn calculate(&self, gross_monthly: Decimal, uses_afp: bool) -> TaxResult {
+        self.calculate_annualized(gross_monthly, uses_afp, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// Pension and 5ta Categoría on one pay period's pay, annualized over the
+    /// periods in `annualization` plus the two gratificaciones
+    pub fn calculate_annualized(&self, gross_monthly: Decimal, uses_afp: bool, annualization: &Annualization) -> TaxResult {
         // Pension (ONP or AFP)
         let pension_rate = if uses_afp { self.config.afp_rate } else { self.config.onp_rate };
         let pension = gross_monthly * pension_rate;
@@ -410,11 +432,11 @@ impl PeruTaxCalculator {
         let essalud = gross_monthly * self.config.essalud_rate;
         
         // 5ta Categoría (income tax)
-        let gross_annual = gross_monthly * dec!(14); // +2 gratificaciones
+        let gross_annual = annualization.annualize(gross_monthly) + annualization.monthly_equivalent(gross_monthly) * dec!(2); // +2 gratificaciones
         let exemption = self.config.uit * dec!(7);
-        let taxable = (gross_annual - exemption - (pension * dec!(12))).max(Decimal::ZERO);
+        let taxable = (gross_annual - exemption - annualization.annualize(pension)).max(Decimal::ZERO);
         let annual_ir = self.calculate_quinta(taxable);
-        let monthly_ir = annual_ir / dec!(12);
+        let monthly_ir = annualization.period_share(annual_ir);
         
         // Gratificaciones (July + December)
         let gratificacion = gross_monthly / dec!(6);
@@ -12,6 +12,9 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use super::residency::ResidencyStatus;
+use super::trace::{percent, CalcStep, CalcTrace};
+
 // ═══════════════════════════════════════════════════════════════════════════
 // SPAIN (ES) - 19 COMUNIDADES AUTÓNOMAS
 // ═══════════════════════════════════════════════════════════════════════════
@@ -88,7 +91,7 @@ impl SpanishTaxCalculator {
             SpanishSpecialRegime::BeckhamLaw => self.calculate_beckham(gross_annual),
             SpanishSpecialRegime::CeutaMelilla => {
                 let mut result = self.calculate_standard(gross_annual);
-                result.cuota_liquida = result.cuota_liquida * dec!(0.50);
+                result.cuota_liquida *= dec!(0.50);
                 result
             }
             _ => self.calculate_standard(gross_annual),
@@ -244,8 +247,15 @@ impl ItalianTaxCalculator {
     }
     
     pub fn calculate(&self, gross_annual: Decimal) -> ItalianTaxResult {
+        self.calculate_with_trace(gross_annual, false)
+    }
+    
+    /// Calculate, optionally recording each bracket and credit in `trace`
+    pub fn calculate_with_trace(&self, gross_annual: Decimal, trace: bool) -> ItalianTaxResult {
+        let mut steps = CalcTrace::new(trace);
+        
         // IRPEF (3 brackets: 23%, 35%, 43%)
-        let irpef_lorda = self.calculate_irpef(gross_annual);
+        let irpef_lorda = self.calculate_irpef(gross_annual, &mut steps);
         
         // Detrazioni
         let detrazione_lavoro = self.calculate_detrazione_lavoro(gross_annual);
@@ -253,10 +263,24 @@ impl ItalianTaxCalculator {
         let detrazioni = detrazione_lavoro + detrazione_coniuge;
         
         let irpef_netta = (irpef_lorda - detrazioni).max(Decimal::ZERO);
+        // Credits cannot take IRPEF below zero
+        let lavoro_applied = detrazione_lavoro.min(irpef_lorda);
+        steps.credit(format!("Detrazione lavoro dipendente {}", detrazione_lavoro.round_dp(2)), lavoro_applied);
+        if detrazione_coniuge > Decimal::ZERO {
+            steps.credit("Detrazione coniuge a carico", detrazione_coniuge.min(irpef_lorda - lavoro_applied));
+        }
         
         // Addizionale regionale + comunale
         let regionale = gross_annual * self.regione.regional_rate();
         let comunale = gross_annual * self.comune_rate;
+        steps.charge(
+            format!("Addizionale regionale {} on {}", percent(self.regione.regional_rate()), gross_annual),
+            gross_annual, self.regione.regional_rate(), regionale,
+        );
+        steps.charge(
+            format!("Addizionale comunale {} on {}", percent(self.comune_rate), gross_annual),
+            gross_annual, self.comune_rate, comunale,
+        );
         
         let total = irpef_netta + regionale + comunale;
         
@@ -269,10 +293,11 @@ impl ItalianTaxCalculator {
             addizionale_comunale: comunale,
             imposta_totale: total,
             aliquota_effettiva: if gross_annual > Decimal::ZERO { total / gross_annual * dec!(100) } else { Decimal::ZERO },
+            trace: steps.into_steps(),
         }
     }
     
-    fn calculate_irpef(&self, income: Decimal) -> Decimal {
+    fn calculate_irpef(&self, income: Decimal, steps: &mut CalcTrace) -> Decimal {
         let brackets: [(Decimal, Decimal); 3] = [
             (dec!(28000), dec!(0.23)), (dec!(50000), dec!(0.35)), (dec!(999999999), dec!(0.43)),
         ];
@@ -280,7 +305,12 @@ impl ItalianTaxCalculator {
         let mut prev = Decimal::ZERO;
         for (max, rate) in brackets {
             if income <= prev { break; }
-            tax += (income.min(max) - prev) * rate;
+            let slice = income.min(max) - prev;
+            tax += slice * rate;
+            steps.charge(
+                format!("IRPEF {} on {} ({} – {})", percent(rate), slice, prev, income.min(max)),
+                slice, rate, slice * rate,
+            );
             prev = max;
         }
         tax
@@ -304,6 +334,8 @@ pub struct ItalianTaxResult {
     pub addizionale_comunale: Decimal,
     pub imposta_totale: Decimal,
     pub aliquota_effettiva: Decimal,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trace: Vec<CalcStep>,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -344,14 +376,42 @@ pub struct PortugueseTaxCalculator {
     pub ss: PortugueseSocialSecurity,
     pub is_casado: bool,
     pub num_dependentes: u8,
+    /// Tax residency for the year; non-residents pay a flat 25% and cannot use NHR
+    pub residency: ResidencyStatus,
 }
 
 impl PortugueseTaxCalculator {
     pub fn new() -> Self {
-        Self { nhr: None, ss: PortugueseSocialSecurity::default(), is_casado: false, num_dependentes: 0 }
+        Self {
+            nhr: None,
+            ss: PortugueseSocialSecurity::default(),
+            is_casado: false,
+            num_dependentes: 0,
+            residency: ResidencyStatus::Resident,
+        }
+    }
+    
+    /// Select resident or non-resident rates from a residency determination
+    pub fn with_residency(mut self, residency: ResidencyStatus) -> Self {
+        self.residency = residency;
+        self
     }
     
     pub fn calculate(&self, gross_annual: Decimal) -> PortugueseTaxResult {
+        // CIRS art. 71: employment income of non-residents, 25% on the gross
+        if self.residency == ResidencyStatus::NonResident {
+            let tax = gross_annual * dec!(0.25);
+            return PortugueseTaxResult {
+                rendimento_bruto: gross_annual,
+                rendimento_coletavel: gross_annual,
+                coleta: tax,
+                deducoes: Decimal::ZERO,
+                imposto: tax,
+                taxa_efetiva: if gross_annual > Decimal::ZERO { dec!(25) } else { Decimal::ZERO },
+                taxa_marginal: dec!(25),
+            };
+        }
+        
         let deducao_especifica = dec!(4104);
         let rendimento_coletavel = (gross_annual - deducao_especifica).max(Decimal::ZERO);
         
@@ -538,7 +598,7 @@ impl MaltaTaxCalculator {
         
         let mut tax = Decimal::ZERO;
         let mut prev = exempt;
-        for (max, rate, subtract) in brackets {
+        for (max, rate, _subtract) in brackets {
             if gross_annual <= prev { break; }
             let bracket_income = gross_annual.min(max) - prev;
             tax += bracket_income * rate;
@@ -626,11 +686,19 @@ impl CyprusSocialInsurance {
 pub struct CyprusTaxCalculator {
     pub non_dom: Option<CyprusNonDom>,
     pub si: CyprusSocialInsurance,
+    /// Tax residency for the year; non-dom status only exists for residents
+    pub residency: ResidencyStatus,
 }
 
 impl CyprusTaxCalculator {
     pub fn new() -> Self {
-        Self { non_dom: None, si: CyprusSocialInsurance::default() }
+        Self { non_dom: None, si: CyprusSocialInsurance::default(), residency: ResidencyStatus::Resident }
+    }
+    
+    /// Select resident or non-resident treatment from a residency determination
+    pub fn with_residency(mut self, residency: ResidencyStatus) -> Self {
+        self.residency = residency;
+        self
     }
     
     pub fn calculate(&self, gross_annual: Decimal) -> CyprusTaxResult {
@@ -641,7 +709,8 @@ impl CyprusTaxCalculator {
             income: gross_annual,
             tax,
             effective_rate: if gross_annual > Decimal::ZERO { tax / gross_annual * dec!(100) } else { Decimal::ZERO },
-            is_non_dom: self.non_dom.as_ref().map(|n| n.is_non_dom).unwrap_or(false),
+            is_non_dom: self.residency == ResidencyStatus::Resident
+                && self.non_dom.as_ref().map(|n| n.is_non_dom).unwrap_or(false),
         }
     }
     
@@ -720,6 +789,27 @@ mod tests {
         let result = calc.calculate(dec!(40000));
         assert!(result.irpef_netta > Decimal::ZERO);
         assert!(result.addizionale_regionale > Decimal::ZERO);
+        assert!(result.trace.is_empty());
+    }
+    
+    #[test]
+    fn test_italy_trace_reconciles() {
+        let mut calc = ItalianTaxCalculator::new(ItalianRegione::Lazio);
+        calc.has_coniuge = true;
+        
+        for gross in [dec!(12000), dec!(40000), dec!(95000)] {
+            let result = calc.calculate_with_trace(gross, true);
+            let sum: Decimal = result.trace.iter().map(|s| s.amount).sum();
+            
+            assert_eq!(sum, result.imposta_totale);
+            assert_eq!(result.trace.last().unwrap().running_total, result.imposta_totale);
+            assert_eq!(result.imposta_totale, calc.calculate(gross).imposta_totale);
+        }
+        
+        let result = calc.calculate_with_trace(dec!(40000), true);
+        let brackets: Vec<_> = result.trace.iter().filter(|s| s.description.starts_with("IRPEF")).collect();
+        assert_eq!(brackets.len(), 2);
+        assert_eq!(brackets[1].description, "IRPEF 35% on 12000 (28000 – 40000)");
     }
     
     #[test]
@@ -737,6 +827,29 @@ mod tests {
         assert_eq!(result.taxa_marginal, dec!(20));
     }
     
+    #[test]
+    fn test_portugal_and_cyprus_non_residents() {
+        use crate::payroll::residency::{Presence, ResidencyDeterminer};
+        
+        let d = |m, day| chrono::NaiveDate::from_ymd_opt(2024, m, day).unwrap();
+        let visit = [Presence::new(d(3, 1), Some(d(6, 30)))];
+        let determiner = ResidencyDeterminer::new();
+        
+        let pt = determiner.determine("PT", 2024, &visit);
+        assert_eq!(pt.status, ResidencyStatus::NonResident);
+        let mut calc = PortugueseTaxCalculator::new().with_residency(pt.status);
+        calc.nhr = Some(PortugueseNHR { is_eligible: true, flat_rate: dec!(0.20), remaining_years: 10 });
+        let result = calc.calculate(dec!(50000));
+        assert_eq!((result.imposto, result.taxa_marginal), (dec!(12500.00), dec!(25)));
+        
+        let cy = determiner.determine("CY", 2024, &visit);
+        let mut calc = CyprusTaxCalculator::new().with_residency(cy.status);
+        calc.non_dom = Some(CyprusNonDom { is_non_dom: true, ..CyprusNonDom::default() });
+        assert!(!calc.calculate(dec!(40000)).is_non_dom);
+        calc.residency = ResidencyStatus::Resident;
+        assert!(calc.calculate(dec!(40000)).is_non_dom);
+    }
+    
     #[test]
     fn test_greece_tax() {
         let calc = GreekTaxCalculator::new();
@@ -0,0 +1,214 @@
+//! Statutory Reports
+//!
+//! Periodic returns an employer files for each legal entity: tax withheld
+//! and social contributions by type, laid out the way the country's
+//! authority asks for them. Every figure is summed from the payslips of the
+//! entity's approved runs in the period, so the report reconciles to the
+//! runs' line items by construction.
+
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::aggregates::LegalEntity;
+use super::models::{EmployeeSalary, PayrollItem};
+
+/// Layout of a statutory return
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatutoryReportFormat {
+    /// SARS EMP201 monthly employer declaration: PAYE, SDL, and UIF
+    Emp201,
+    /// Nigerian state PAYE schedule: tax per employee, with the pension and
+    /// NHF remittances for the month
+    PayeSchedule,
+}
+
+impl StatutoryReportFormat {
+    /// Contribution types on the return, by code and label
+    pub fn lines(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Emp201 => &[
+                ("paye", "Pay-As-You-Earn"),
+                ("sdl", "Skills Development Levy"),
+                ("uif", "Unemployment Insurance Fund"),
+            ],
+            Self::PayeSchedule => &[
+                ("paye", "PAYE"),
+                ("pension", "Pension (PenCom)"),
+                ("nhf", "National Housing Fund"),
+            ],
+        }
+    }
+
+    /// Whether the return lists each employee
+    pub fn has_schedule(self) -> bool {
+        matches!(self, Self::PayeSchedule)
+    }
+}
+
+/// Return format per country of registration
+#[derive(Debug, Clone)]
+pub struct StatutoryReportFormats {
+    countries: HashMap<String, StatutoryReportFormat>,
+}
+
+impl Default for StatutoryReportFormats {
+    fn default() -> Self {
+        let mut formats = Self { countries: HashMap::new() };
+        formats
+            .set("ZA", StatutoryReportFormat::Emp201)
+            .set("NG", StatutoryReportFormat::PayeSchedule);
+        formats
+    }
+}
+
+impl StatutoryReportFormats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, country_code: &str, format: StatutoryReportFormat) -> &mut Self {
+        self.countries.insert(country_code.to_uppercase(), format);
+        self
+    }
+
+    pub fn for_country(&self, country_code: &str) -> Option<StatutoryReportFormat> {
+        self.countries.get(&country_code.to_uppercase()).copied()
+    }
+}
+
+/// One contribution type on a return
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatutoryReportLine {
+    pub code: String,
+    pub label: String,
+    /// Withheld from pay
+    pub employee_amount: Decimal,
+    /// Paid by the employer on top of pay
+    pub employer_amount: Decimal,
+    pub total: Decimal,
+}
+
+/// One employee on a schedule return
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatutoryScheduleRow {
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub tin: Option<String>,
+    pub gross_pay: Decimal,
+    pub tax: Decimal,
+}
+
+/// A legal entity's statutory return for one period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatutoryReport {
+    pub legal_entity_id: Uuid,
+    pub entity_name: String,
+    pub tax_registration: String,
+    pub country: String,
+    pub format: StatutoryReportFormat,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub payroll_run_ids: Vec<Uuid>,
+    pub lines: Vec<StatutoryReportLine>,
+    /// Per-employee rows in payslip order; empty unless the format has a schedule
+    pub schedule: Vec<StatutoryScheduleRow>,
+    /// Amount due to the authority across all lines
+    pub total_liability: Decimal,
+}
+
+impl StatutoryReport {
+    /// Sum `payslips` (with the salary inputs they were calculated from)
+    /// into `entity`'s return for the period
+    pub fn for_period<'a>(
+        entity: &LegalEntity,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        format: StatutoryReportFormat,
+        payslips: impl IntoIterator<Item = (&'a PayrollItem, Option<&'a EmployeeSalary>)>,
+    ) -> Self {
+        let mut lines: Vec<StatutoryReportLine> = format
+            .lines()
+            .iter()
+            .map(|(code, label)| StatutoryReportLine {
+                code: code.to_string(),
+                label: label.to_string(),
+                employee_amount: Decimal::ZERO,
+                employer_amount: Decimal::ZERO,
+                total: Decimal::ZERO,
+            })
+            .collect();
+        let mut payroll_run_ids = Vec::new();
+        let mut schedule = Vec::new();
+
+        for (item, salary) in payslips {
+            if !payroll_run_ids.contains(&item.payroll_run_id) {
+                payroll_run_ids.push(item.payroll_run_id);
+            }
+            for line in &mut lines {
+                line.employee_amount += employee_amount(item, &line.code);
+                line.employer_amount += employer_amount(item, &line.code);
+            }
+            if format.has_schedule() {
+                schedule.push(StatutoryScheduleRow {
+                    employee_id: item.employee_id,
+                    employee_name: salary.map(|s| s.employee_name.clone()).unwrap_or_default(),
+                    tin: salary.and_then(|s| s.tin.clone()),
+                    gross_pay: item.gross_pay,
+                    tax: item.paye_tax,
+                });
+            }
+        }
+
+        for line in &mut lines {
+            line.total = line.employee_amount + line.employer_amount;
+        }
+        let total_liability = lines.iter().map(|l| l.total).sum();
+
+        Self {
+            legal_entity_id: entity.id,
+            entity_name: entity.name.clone(),
+            tax_registration: entity.tax_registration.clone(),
+            country: entity.country.clone(),
+            format,
+            period_start,
+            period_end,
+            payroll_run_ids,
+            lines,
+            schedule,
+            total_liability,
+        }
+    }
+
+    pub fn line(&self, code: &str) -> Option<&StatutoryReportLine> {
+        self.lines.iter().find(|l| l.code == code)
+    }
+}
+
+/// Employee share of `code` on a payslip
+fn employee_amount(item: &PayrollItem, code: &str) -> Decimal {
+    match code {
+        "paye" => item.paye_tax,
+        "pension" => item.pension_employee,
+        "nhf" => item.nhf_deduction,
+        code => item
+            .other_deduction_amounts()
+            .into_iter()
+            .find(|(key, _)| key == code)
+            .map(|(_, amount)| amount)
+            .unwrap_or_default(),
+    }
+}
+
+/// Employer share of `code` on a payslip
+fn employer_amount(item: &PayrollItem, code: &str) -> Decimal {
+    match item.employer_contributions.get(code) {
+        Some(amount) => *amount,
+        // Items stored before contributions were itemized carry only the pension
+        None if code == "pension" && item.employer_contributions.is_empty() => item.pension_employer,
+        None => Decimal::ZERO,
+    }
+}
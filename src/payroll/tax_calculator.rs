@@ -7,6 +7,10 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::value_objects::PayFrequency;
+use super::annualization::Annualization;
+use super::pension::PensionCalculation;
+
 /// Nigerian PAYE Tax Bands (2024)
 /// 
 /// Annual income is taxed progressively:
@@ -22,6 +26,18 @@ pub struct TaxBand {
     pub rate: Decimal,
 }
 
+/// Income the Consolidated Relief Allowance is computed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CraBasis {
+    /// Gross income before any relief (pre-Finance Act 2020 practice)
+    GrossIncome,
+    /// Gross income less tax-exempt pension and NHF contributions
+    /// (PITA s.33(1) as amended by the Finance Act 2020)
+    #[default]
+    GrossLessStatutoryReliefs,
+}
+
 /// Nigerian PAYE Tax Calculator
 #[derive(Debug, Clone)]
 pub struct NigerianTaxCalculator {
@@ -31,6 +47,7 @@ pub struct NigerianTaxCalculator {
     cra_fixed: Decimal,
     cra_percentage: Decimal,
     cra_min_percentage: Decimal,
+    cra_basis: CraBasis,
 }
 
 impl Default for NigerianTaxCalculator {
@@ -53,9 +70,27 @@ impl NigerianTaxCalculator {
             cra_fixed: dec!(200_000),
             cra_percentage: dec!(0.20),
             cra_min_percentage: dec!(0.01),
+            cra_basis: CraBasis::default(),
         }
     }
 
+    /// Use a different CRA basis, e.g. to reproduce pre-2020 computations
+    pub fn with_cra_basis(mut self, cra_basis: CraBasis) -> Self {
+        self.cra_basis = cra_basis;
+        self
+    }
+
+    pub fn cra_basis(&self) -> CraBasis {
+        self.cra_basis
+    }
+
+    /// Consolidated Relief Allowance for a CRA base:
+    /// higher of ₦200,000 or 1% of the base, plus 20% of the base
+    pub fn consolidated_relief(&self, cra_base: Decimal) -> Decimal {
+        let cra_higher = (cra_base * self.cra_min_percentage).max(self.cra_fixed);
+        cra_higher + cra_base * self.cra_percentage
+    }
+
     /// Calculate annual PAYE tax
     /// 
     /// # Arguments
@@ -71,25 +106,21 @@ impl NigerianTaxCalculator {
         pension_contribution: Decimal,
         nhf_contribution: Decimal,
     ) -> TaxCalculation {
-        // Step 1: Calculate Consolidated Relief Allowance (CRA)
-        let cra_percentage_amount = gross_annual * self.cra_percentage;
-        let cra_min_amount = gross_annual * self.cra_min_percentage;
-        let cra_higher = if cra_min_amount > self.cra_fixed {
-            cra_min_amount
-        } else {
-            self.cra_fixed
-        };
-        let total_cra = cra_percentage_amount + cra_higher;
-
-        // Step 2: Calculate taxable income
-        let total_exemptions = total_cra + pension_contribution + nhf_contribution;
-        let taxable_income = if gross_annual > total_exemptions {
-            gross_annual - total_exemptions
-        } else {
-            Decimal::ZERO
+        // Step 1: Mandatory pension and NHF are tax-exempt and come off first
+        let statutory_reliefs = pension_contribution + nhf_contribution;
+
+        // Step 2: Consolidated Relief Allowance (CRA) on the configured basis
+        let cra_base = match self.cra_basis {
+            CraBasis::GrossIncome => gross_annual,
+            CraBasis::GrossLessStatutoryReliefs => (gross_annual - statutory_reliefs).max(Decimal::ZERO),
         };
+        let total_cra = self.consolidated_relief(cra_base);
+
+        // Step 3: Taxable income
+        let total_exemptions = total_cra + statutory_reliefs;
+        let taxable_income = (gross_annual - total_exemptions).max(Decimal::ZERO);
 
-        // Step 3: Apply progressive tax bands
+        // Step 4: Apply progressive tax bands
         let mut remaining = taxable_income;
         let mut total_tax = Decimal::ZERO;
         let mut band_breakdown = Vec::new();
@@ -127,6 +158,7 @@ impl NigerianTaxCalculator {
             taxable_income,
             annual_tax: total_tax,
             monthly_tax: total_tax / dec!(12),
+            period_tax: total_tax / dec!(12),
             effective_rate: if gross_annual > Decimal::ZERO {
                 (total_tax / gross_annual) * dec!(100)
             } else {
@@ -136,6 +168,16 @@ impl NigerianTaxCalculator {
         }
     }
 
+    /// Calculate annual PAYE using the reliefs from a `PensionCalculator` result,
+    /// so tax and pension always agree on the contributions deducted
+    pub fn calculate_annual_with_pension(
+        &self,
+        gross_annual: Decimal,
+        pension: &PensionCalculation,
+    ) -> TaxCalculation {
+        self.calculate_annual_paye(gross_annual, pension.employee_contribution, pension.nhf_contribution)
+    }
+
     /// Calculate monthly PAYE tax
     pub fn calculate_monthly_paye(
         &self,
@@ -143,12 +185,34 @@ impl NigerianTaxCalculator {
         pension_monthly: Decimal,
         nhf_monthly: Decimal,
     ) -> TaxCalculation {
-        let mut calc = self.calculate_annual_paye(
-            gross_monthly * dec!(12),
-            pension_monthly * dec!(12),
-            nhf_monthly * dec!(12),
-        );
-        calc.monthly_tax = calc.annual_tax / dec!(12);
+        self.calculate_period_paye(gross_monthly, pension_monthly, nhf_monthly, &PayFrequency::Monthly)
+    }
+
+    /// Calculate PAYE for one pay period: the period's amounts are
+    /// annualized over `frequency`'s periods per year and the annual tax is
+    /// spread back over them into `period_tax`
+    pub fn calculate_period_paye(
+        &self,
+        gross: Decimal,
+        pension: Decimal,
+        nhf: Decimal,
+        frequency: &PayFrequency,
+    ) -> TaxCalculation {
+        self.calculate_annualized_paye(gross, pension, nhf, &Annualization::nominal(frequency))
+    }
+
+    /// Calculate PAYE for one pay period annualized over the periods in
+    /// `annualization`, e.g. the 27 of a long biweekly year
+    pub fn calculate_annualized_paye(
+        &self,
+        gross: Decimal,
+        pension: Decimal,
+        nhf: Decimal,
+        annualization: &Annualization,
+    ) -> TaxCalculation {
+        let annual = |amount| annualization.annualize(amount);
+        let mut calc = self.calculate_annual_paye(annual(gross), annual(pension), annual(nhf));
+        calc.period_tax = annualization.period_share(calc.annual_tax);
         calc
     }
 }
@@ -164,6 +228,10 @@ pub struct TaxCalculation {
     pub taxable_income: Decimal,
     pub annual_tax: Decimal,
     pub monthly_tax: Decimal,
+    /// Tax for one pay period of the frequency calculated for; a month
+    /// unless calculated per period
+    #[serde(default)]
+    pub period_tax: Decimal,
     /// Effective tax rate as percentage
     pub effective_rate: Decimal,
     pub band_breakdown: Vec<TaxBandResult>,
@@ -181,6 +249,7 @@ pub struct TaxBandResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::payroll::pension::PensionCalculator;
 
     #[test]
     fn test_paye_calculation_3m_salary() {
@@ -246,4 +315,97 @@ mod tests {
         assert!(result.band_breakdown.len() >= 5);
         assert!(result.effective_rate > dec!(10)); // Should be significant
     }
+
+    // Worked examples follow the FIRS PAYE computation order: pension and NHF
+    // relief, CRA on the reduced gross, then the graduated bands.
+
+    #[test]
+    fn test_paye_worked_example_1_2m() {
+        let calculator = NigerianTaxCalculator::new();
+        let result = calculator.calculate_annual_paye(dec!(1_200_000), dec!(96_000), Decimal::ZERO);
+
+        // CRA = 200,000 + 20% × 1,104,000 = 420,800
+        assert_eq!(result.consolidated_relief, dec!(420_800));
+        assert_eq!(result.taxable_income, dec!(683_200));
+        // 21,000 + 33,000 + 15% × 83,200
+        assert_eq!(result.annual_tax, dec!(66_480));
+    }
+
+    #[test]
+    fn test_paye_worked_example_3m() {
+        let calculator = NigerianTaxCalculator::new();
+        let result = calculator.calculate_annual_paye(dec!(3_000_000), dec!(240_000), Decimal::ZERO);
+
+        // CRA = 200,000 + 20% × 2,760,000 = 752,000
+        assert_eq!(result.consolidated_relief, dec!(752_000));
+        assert_eq!(result.taxable_income, dec!(2_008_000));
+        // 21,000 + 33,000 + 75,000 + 95,000 + 21% × 408,000
+        assert_eq!(result.annual_tax, dec!(309_680));
+
+        // Pre-2020 basis computes CRA on the full gross
+        let legacy = NigerianTaxCalculator::new().with_cra_basis(CraBasis::GrossIncome);
+        let result = legacy.calculate_annual_paye(dec!(3_000_000), dec!(240_000), Decimal::ZERO);
+        assert_eq!(result.consolidated_relief, dec!(800_000));
+        assert_eq!(result.annual_tax, dec!(299_600));
+    }
+
+    #[test]
+    fn test_paye_worked_example_20m() {
+        let calculator = NigerianTaxCalculator::new();
+        let result = calculator.calculate_annual_paye(dec!(20_000_000), dec!(1_600_000), Decimal::ZERO);
+
+        // 1% of 18.4M is below ₦200,000, so the fixed amount applies
+        assert_eq!(result.consolidated_relief, dec!(3_880_000));
+        assert_eq!(result.taxable_income, dec!(14_520_000));
+        // 560,000 on the first 3.2M + 24% × 11,320,000
+        assert_eq!(result.annual_tax, dec!(3_276_800));
+    }
+
+    #[test]
+    fn test_paye_worked_example_50m_uses_one_percent() {
+        let calculator = NigerianTaxCalculator::new();
+        let result = calculator.calculate_annual_paye(dec!(50_000_000), dec!(4_000_000), Decimal::ZERO);
+
+        // 1% × 46M = 460,000 exceeds ₦200,000
+        assert_eq!(result.consolidated_relief, dec!(9_660_000));
+        assert_eq!(result.annual_tax, dec!(8_513_600));
+    }
+
+    #[test]
+    fn test_composes_with_pension_calculator() {
+        let calculator = NigerianTaxCalculator::new();
+        let pension = PensionCalculator::new().calculate_annual(dec!(1_800_000), dec!(720_000), dec!(480_000));
+        assert_eq!(pension.employee_contribution, dec!(240_000));
+
+        let result = calculator.calculate_annual_with_pension(dec!(3_000_000), &pension);
+        assert_eq!(result.pension_relief, dec!(240_000));
+        // NHF: 2.5% × 1.8M basic = 45,000, also exempt before CRA
+        assert_eq!(result.nhf_relief, dec!(45_000));
+        assert_eq!(result.consolidated_relief, dec!(200_000) + dec!(2_715_000) * dec!(0.20));
+        assert_eq!(
+            result.taxable_income,
+            dec!(3_000_000) - dec!(240_000) - dec!(45_000) - result.consolidated_relief
+        );
+    }
+    
+    #[test]
+    fn test_same_annual_tax_whatever_the_pay_frequency() {
+        let calculator = NigerianTaxCalculator::new();
+        let annual = calculator.calculate_annual_paye(dec!(6_240_000), dec!(499_200), dec!(0));
+        
+        for (frequency, periods) in [
+            (PayFrequency::Monthly, dec!(12)),
+            (PayFrequency::Weekly, dec!(52)),
+            (PayFrequency::FourWeekly, dec!(13)),
+        ] {
+            let result = calculator.calculate_period_paye(
+                dec!(6_240_000) / periods,
+                dec!(499_200) / periods,
+                Decimal::ZERO,
+                &frequency,
+            );
+            assert_eq!(result.annual_tax, annual.annual_tax, "{:?}", frequency);
+            assert_eq!(result.period_tax * periods, annual.annual_tax, "{:?}", frequency);
+        }
+    }
 }
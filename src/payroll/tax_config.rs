@@ -0,0 +1,282 @@
+//! Tax Config Files
+//!
+//! Loads tax tables from a directory of versioned JSON files so budget-day
+//! changes ship as config instead of a release. Each file covers one
+//! country and tax year and overlays the compiled defaults: allowances
+//! override code by code, and a South African table replaces brackets and
+//! rebates for its year.
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "country_code": "ZA",
+//!   "tax_year": 2025,
+//!   "allowances": {},
+//!   "south_africa": { "tax_year": "2025/2026", "brackets": [...], ... }
+//! }
+//! ```
+//!
+//! A directory is validated as a whole before anything is applied, so a
+//! bad file leaves the running tables untouched. Calculations read a copy
+//! of their table, so a reload never changes one mid-run. Reload overlays
+//! only: deleting a file takes effect on restart.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::south_africa::SouthAfricaConfig;
+use super::tax_parameters::TaxParameters;
+use super::tax_tables::TaxTables;
+
+/// Directory read at startup and on SIGHUP
+pub const DEFAULT_TAX_CONFIG_DIR: &str = "config/tax";
+
+/// Schema version this build understands
+pub const TAX_CONFIG_SCHEMA_VERSION: u32 = 1;
+
+/// Tax config loading errors
+#[derive(Debug, thiserror::Error)]
+pub enum TaxConfigError {
+    #[error("Cannot read tax config {path}: {source}")]
+    Io { path: String, source: std::io::Error },
+
+    #[error("Malformed tax config {path}: {message}")]
+    Malformed { path: String, message: String },
+
+    #[error("Invalid tax config {path}: {message}")]
+    Invalid { path: String, message: String },
+}
+
+/// One country and tax year's overrides
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaxConfigFile {
+    pub schema_version: u32,
+    pub country_code: String,
+    pub tax_year: i32,
+    /// Allowance overrides by code, on top of `TaxParameters::builtin`
+    #[serde(default)]
+    pub allowances: BTreeMap<String, Decimal>,
+    /// Full PAYE table; only valid for ZA
+    #[serde(default)]
+    pub south_africa: Option<SouthAfricaConfig>,
+}
+
+impl TaxConfigFile {
+    /// Parse and validate one file's contents
+    pub fn parse(path: &str, contents: &str) -> Result<Self, TaxConfigError> {
+        let file: Self = serde_json::from_str(contents)
+            .map_err(|e| TaxConfigError::Malformed { path: path.to_string(), message: e.to_string() })?;
+        file.validate()
+            .map_err(|message| TaxConfigError::Invalid { path: path.to_string(), message })?;
+        Ok(file)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.schema_version != TAX_CONFIG_SCHEMA_VERSION {
+            return Err(format!(
+                "schema_version {} is not supported (expected {})",
+                self.schema_version, TAX_CONFIG_SCHEMA_VERSION
+            ));
+        }
+        if self.country_code.len() != 2 || !self.country_code.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("country_code {:?} is not a two-letter code", self.country_code));
+        }
+        if !(2000..=2100).contains(&self.tax_year) {
+            return Err(format!("tax_year {} is out of range", self.tax_year));
+        }
+        if let Some((code, _)) = self.allowances.iter().find(|(_, amount)| amount.is_sign_negative()) {
+            return Err(format!("allowance {} is negative", code));
+        }
+        if let Some(config) = &self.south_africa {
+            if !self.country_code.eq_ignore_ascii_case("ZA") {
+                return Err("south_africa table given for a country other than ZA".to_string());
+            }
+            validate_brackets(config)?;
+        }
+        Ok(())
+    }
+
+    pub fn parameters(&self) -> TaxParameters {
+        TaxParameters::with_overrides(&self.country_code, self.tax_year, self.allowances.clone())
+    }
+}
+
+fn validate_brackets(config: &SouthAfricaConfig) -> Result<(), String> {
+    let brackets = &config.brackets;
+    if brackets.is_empty() {
+        return Err("south_africa.brackets is empty".to_string());
+    }
+    for (i, bracket) in brackets.iter().enumerate() {
+        if bracket.rate < Decimal::ZERO || bracket.rate > Decimal::ONE {
+            return Err(format!("bracket {} rate {} is not between 0 and 1", i, bracket.rate));
+        }
+        if bracket.max.is_some_and(|max| max < bracket.min) {
+            return Err(format!("bracket {} ends before it starts", i));
+        }
+        if bracket.max.is_none() && i + 1 != brackets.len() {
+            return Err(format!("bracket {} is open-ended but not last", i));
+        }
+        if i > 0 && bracket.min <= brackets[i - 1].min {
+            return Err(format!("bracket {} is not in ascending order", i));
+        }
+    }
+    Ok(())
+}
+
+/// Reads tax config files from a directory
+#[derive(Debug, Clone)]
+pub struct TaxConfigLoader {
+    dir: PathBuf,
+}
+
+impl Default for TaxConfigLoader {
+    fn default() -> Self {
+        Self::new(DEFAULT_TAX_CONFIG_DIR)
+    }
+}
+
+impl TaxConfigLoader {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Every `*.json` file in the directory, in name order. Any unreadable,
+    /// malformed, or invalid file fails the whole load, as does a second
+    /// file for the same country and year.
+    pub fn load(&self) -> Result<Vec<TaxConfigFile>, TaxConfigError> {
+        let io_error = |path: &Path| {
+            let path = path.display().to_string();
+            move |source| TaxConfigError::Io { path, source }
+        };
+
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&self.dir)
+            .map_err(io_error(&self.dir))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        let mut seen = HashSet::new();
+        let mut files = Vec::with_capacity(paths.len());
+        for path in paths {
+            let contents = std::fs::read_to_string(&path).map_err(io_error(&path))?;
+            let display = path.display().to_string();
+            let file = TaxConfigFile::parse(&display, &contents)?;
+            if !seen.insert((file.country_code.to_ascii_uppercase(), file.tax_year)) {
+                return Err(TaxConfigError::Invalid {
+                    path: display,
+                    message: format!("{} {} is already configured by another file", file.country_code, file.tax_year),
+                });
+            }
+            files.push(file);
+        }
+        Ok(files)
+    }
+
+    /// Load the directory and apply it to `tables`, returning how many
+    /// files were applied. On error `tables` is left as it was.
+    pub fn reload_into(&self, tables: &TaxTables) -> Result<usize, TaxConfigError> {
+        let files = self.load()?;
+        tables.apply_config(&files);
+        Ok(files.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("tax-config-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, name: &str, contents: &str) {
+            std::fs::write(self.0.join(name), contents).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn za_2025(top_rate: &str) -> String {
+        let mut config = serde_json::to_value(SouthAfricaConfig::default()).unwrap();
+        config["tax_year"] = "2025/2026".into();
+        config["brackets"][6]["rate"] = top_rate.into();
+        serde_json::json!({
+            "schema_version": 1,
+            "country_code": "ZA",
+            "tax_year": 2025,
+            "south_africa": config,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_file_overrides_brackets_and_allowances() {
+        let dir = TempDir::new();
+        dir.write("za-2025.json", &za_2025("0.47"));
+        dir.write("ie-2025.json", r#"{"schema_version": 1, "country_code": "IE", "tax_year": 2025,
+            "allowances": {"personal_credit": "2000"}}"#);
+        dir.write("README.md", "not config");
+
+        let tables = TaxTables::new();
+        assert_eq!(TaxConfigLoader::new(&dir.0).reload_into(&tables).unwrap(), 2);
+
+        let za = tables.south_africa(2025);
+        assert_eq!(za.tax_year, "2025/2026");
+        assert_eq!(za.brackets[6].rate, dec!(0.47));
+        // Other years keep the compiled table
+        assert_eq!(tables.south_africa(2024).brackets[6].rate, dec!(0.45));
+
+        let ie = tables.parameters("IE", 2025);
+        assert_eq!(ie.amount("personal_credit"), dec!(2000));
+        assert_eq!(ie.amount("employee_credit"), dec!(1875));
+    }
+
+    #[test]
+    fn test_invalid_file_rejected_without_applying() {
+        let dir = TempDir::new();
+        dir.write("za-2025.json", &za_2025("0.47"));
+        let tables = TaxTables::new();
+        let loader = TaxConfigLoader::new(&dir.0);
+        loader.reload_into(&tables).unwrap();
+
+        // A rate above 100% fails validation; the earlier table stays live
+        dir.write("za-2025.json", &za_2025("4.5"));
+        let err = loader.reload_into(&tables).unwrap_err();
+        assert!(matches!(err, TaxConfigError::Invalid { .. }), "{err}");
+        assert!(err.to_string().contains("za-2025.json"));
+        assert_eq!(tables.south_africa(2025).brackets[6].rate, dec!(0.47));
+
+        dir.write("za-2025.json", "{\"schema_version\": 1, \"country_code\": ");
+        assert!(matches!(loader.reload_into(&tables), Err(TaxConfigError::Malformed { .. })));
+
+        dir.write("za-2025.json", r#"{"schema_version": 2, "country_code": "ZA", "tax_year": 2025}"#);
+        assert!(matches!(loader.reload_into(&tables), Err(TaxConfigError::Invalid { .. })));
+
+        dir.write("za-2025.json", r#"{"schema_version": 1, "country_code": "ZA", "tax_yaer": 2025}"#);
+        assert!(matches!(loader.reload_into(&tables), Err(TaxConfigError::Malformed { .. })));
+
+        assert!(matches!(
+            TaxConfigLoader::new(dir.0.join("missing")).load(),
+            Err(TaxConfigError::Io { .. })
+        ));
+        assert_eq!(tables.south_africa(2025).brackets[6].rate, dec!(0.47));
+    }
+}
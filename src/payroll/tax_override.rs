@@ -0,0 +1,150 @@
+//! Tax Overrides
+//!
+//! A manual correction to one employee's tax on one run, for cases the
+//! engine can't know about: a court order, or a correction carried over
+//! from a prior year. An override either replaces the computed tax or adds
+//! to it, and only applies once someone other than the requester approves
+//! it. Applied overrides keep the tax the engine computed, so reconciliation
+//! can show both.
+
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How an override changes the computed tax
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxOverrideKind {
+    /// Withhold `amount` instead of the computed tax
+    Replace,
+    /// Withhold the computed tax plus `amount`, which may be negative
+    Adjust,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxOverrideStatus {
+    PendingApproval,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaxOverride {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub run_id: Uuid,
+    pub kind: TaxOverrideKind,
+    pub amount: Decimal,
+    pub reason: String,
+    pub status: TaxOverrideStatus,
+    pub requested_by: Uuid,
+    pub decided_by: Option<Uuid>,
+    pub decided_at: Option<DateTime<Utc>>,
+    /// Engine's tax on the payslip the override was applied to
+    pub computed_tax: Option<Decimal>,
+    /// Tax withheld after the override
+    pub applied_tax: Option<Decimal>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaxOverride {
+    pub fn new(
+        employee_id: Uuid,
+        run_id: Uuid,
+        kind: TaxOverrideKind,
+        amount: Decimal,
+        reason: impl Into<String>,
+        requested_by: Uuid,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            employee_id,
+            run_id,
+            kind,
+            amount,
+            reason: reason.into(),
+            status: TaxOverrideStatus::PendingApproval,
+            requested_by,
+            decided_by: None,
+            decided_at: None,
+            computed_tax: None,
+            applied_tax: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Tax to withhold in place of `computed`, never below zero
+    pub fn tax_for(&self, computed: Decimal) -> Decimal {
+        match self.kind {
+            TaxOverrideKind::Replace => self.amount,
+            TaxOverrideKind::Adjust => computed + self.amount,
+        }
+        .max(Decimal::ZERO)
+    }
+}
+
+/// Overrides by id
+#[derive(Debug, Clone, Default)]
+pub struct TaxOverrides {
+    // In real implementation, backed by the payroll_tax_overrides table
+    overrides: Arc<DashMap<Uuid, TaxOverride>>,
+}
+
+impl TaxOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store an override, replacing any pending one for the same employee
+    /// and run
+    pub fn request(&self, tax_override: TaxOverride) {
+        self.overrides.retain(|_, o| {
+            !(o.run_id == tax_override.run_id
+                && o.employee_id == tax_override.employee_id
+                && o.status == TaxOverrideStatus::PendingApproval)
+        });
+        self.overrides.insert(tax_override.id, tax_override);
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<TaxOverride> {
+        self.overrides.get(&id).map(|o| o.clone())
+    }
+
+    /// Record the approver's decision on a pending override
+    pub fn decide(&self, id: Uuid, decided_by: Uuid, approve: bool) -> Option<TaxOverride> {
+        let mut entry = self.overrides.get_mut(&id)?;
+        entry.status = if approve { TaxOverrideStatus::Approved } else { TaxOverrideStatus::Rejected };
+        entry.decided_by = Some(decided_by);
+        entry.decided_at = Some(Utc::now());
+        Some(entry.clone())
+    }
+
+    /// The approved override for an employee on a run, latest decision first
+    pub fn approved(&self, run_id: Uuid, employee_id: Uuid) -> Option<TaxOverride> {
+        self.overrides
+            .iter()
+            .filter(|o| o.run_id == run_id && o.employee_id == employee_id && o.status == TaxOverrideStatus::Approved)
+            .max_by_key(|o| o.decided_at)
+            .map(|o| o.clone())
+    }
+
+    /// Note the computed and withheld tax on an applied override
+    pub fn mark_applied(&self, id: Uuid, computed_tax: Decimal, applied_tax: Decimal) {
+        if let Some(mut entry) = self.overrides.get_mut(&id) {
+            entry.computed_tax = Some(computed_tax);
+            entry.applied_tax = Some(applied_tax);
+        }
+    }
+
+    /// Overrides applied to a run's payslips
+    pub fn applied_for_run(&self, run_id: Uuid) -> Vec<TaxOverride> {
+        let mut applied: Vec<_> =
+            self.overrides.iter().filter(|o| o.run_id == run_id && o.applied_tax.is_some()).map(|o| o.clone()).collect();
+        applied.sort_by_key(|o| o.created_at);
+        applied
+    }
+}
@@ -0,0 +1,102 @@
+//! Tax Parameters
+//!
+//! Annual credits and allowances per country and tax year, kept as data so
+//! budget-day changes are a parameter update rather than an engine change.
+//! The built-in values are the ones the engines shipped with; a loaded
+//! set overrides them code by code.
+
+use std::collections::BTreeMap;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+/// Ireland: personal tax credit (single, widowed, single parent)
+pub const IE_PERSONAL_CREDIT: &str = "personal_credit";
+/// Ireland: married / civil partner personal tax credit
+pub const IE_MARRIED_PERSONAL_CREDIT: &str = "married_personal_credit";
+/// Ireland: single person child carer credit
+pub const IE_SINGLE_PARENT_CREDIT: &str = "single_parent_child_carer_credit";
+/// Ireland: employee (PAYE) tax credit
+pub const IE_EMPLOYEE_CREDIT: &str = "employee_credit";
+
+/// Japan: basic deduction (基礎控除), also used for residence tax
+pub const JP_BASIC_DEDUCTION: &str = "basic_deduction";
+/// Japan: deduction per dependent (扶養控除)
+pub const JP_DEPENDENT_DEDUCTION: &str = "dependent_deduction";
+
+/// Taiwan: standard deduction, single filer
+pub const TW_STANDARD_DEDUCTION: &str = "standard_deduction";
+/// Taiwan: personal exemption per person (taxpayer and each dependent)
+pub const TW_PERSONAL_EXEMPTION: &str = "personal_exemption";
+
+/// Annual credits and allowances for one country and tax year
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaxParameters {
+    pub country_code: String,
+    pub tax_year: i32,
+    /// Annual amounts by code (see the `*_CREDIT` / `*_DEDUCTION` constants)
+    pub allowances: BTreeMap<String, Decimal>,
+}
+
+impl TaxParameters {
+    /// Built-in parameters; countries without any are empty
+    pub fn builtin(country_code: &str, tax_year: i32) -> Self {
+        let country_code = country_code.to_ascii_uppercase();
+        let amounts: &[(&str, Decimal)] = match country_code.as_str() {
+            "IE" => &[
+                (IE_PERSONAL_CREDIT, dec!(1875)),
+                (IE_MARRIED_PERSONAL_CREDIT, dec!(3750)),
+                (IE_SINGLE_PARENT_CREDIT, dec!(1750)),
+                (IE_EMPLOYEE_CREDIT, dec!(1875)),
+            ],
+            "JP" => &[
+                (JP_BASIC_DEDUCTION, dec!(480000)),
+                (JP_DEPENDENT_DEDUCTION, dec!(380000)),
+            ],
+            "TW" => &[
+                (TW_STANDARD_DEDUCTION, dec!(124000)),
+                (TW_PERSONAL_EXEMPTION, dec!(92000)),
+            ],
+            _ => &[],
+        };
+        Self {
+            country_code,
+            tax_year,
+            allowances: amounts.iter().map(|(code, amount)| (code.to_string(), *amount)).collect(),
+        }
+    }
+
+    /// Built-in parameters with `overrides` applied on top, so a loaded
+    /// file only needs the amounts that changed
+    pub fn with_overrides(country_code: &str, tax_year: i32, overrides: BTreeMap<String, Decimal>) -> Self {
+        let mut parameters = Self::builtin(country_code, tax_year);
+        parameters.allowances.extend(overrides);
+        parameters
+    }
+
+    /// Annual amount for `code`; zero when the country has no such allowance
+    pub fn amount(&self, code: &str) -> Decimal {
+        self.allowances.get(code).copied().unwrap_or_default()
+    }
+
+    pub fn with(mut self, code: &str, amount: Decimal) -> Self {
+        self.allowances.insert(code.to_string(), amount);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_keep_other_builtins() {
+        let overrides = BTreeMap::from([(IE_PERSONAL_CREDIT.to_string(), dec!(2000))]);
+        let parameters = TaxParameters::with_overrides("ie", 2025, overrides);
+
+        assert_eq!(parameters.country_code, "IE");
+        assert_eq!(parameters.amount(IE_PERSONAL_CREDIT), dec!(2000));
+        assert_eq!(parameters.amount(IE_EMPLOYEE_CREDIT), dec!(1875));
+        assert_eq!(parameters.amount("no_such_credit"), Decimal::ZERO);
+    }
+}
@@ -0,0 +1,75 @@
+//! Tax Tables
+//!
+//! Versioned tax tables per country and tax year used by `PayrollService`.
+//! Replacing a table (e.g. corrected brackets) is followed by
+//! `PayrollService::recalculate_draft_runs` for the affected year.
+
+use std::sync::Arc;
+use chrono::{Datelike, NaiveDate};
+use dashmap::DashMap;
+
+use super::south_africa::SouthAfricaConfig;
+use super::tax_config::TaxConfigFile;
+use super::tax_parameters::TaxParameters;
+
+/// Tax tables keyed by tax year
+#[derive(Debug, Clone, Default)]
+pub struct TaxTables {
+    // In real implementation, loaded from the tax_tables table
+    south_africa: Arc<DashMap<i32, SouthAfricaConfig>>,
+    /// Credits and allowances keyed by (country code, tax year)
+    parameters: Arc<DashMap<(String, i32), TaxParameters>>,
+}
+
+impl TaxTables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tax year a pay date falls in. South Africa's year runs 1 March to
+    /// end of February and is named by its starting year (2024 = 2024/2025);
+    /// everywhere else uses the calendar year.
+    pub fn tax_year_for(country_code: &str, date: NaiveDate) -> i32 {
+        if country_code.eq_ignore_ascii_case("ZA") && date.month() < 3 {
+            date.year() - 1
+        } else {
+            date.year()
+        }
+    }
+
+    /// South African PAYE table for a tax year, defaulting to the built-in table
+    pub fn south_africa(&self, tax_year: i32) -> SouthAfricaConfig {
+        self.south_africa
+            .get(&tax_year)
+            .map(|c| c.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn update_south_africa(&self, tax_year: i32, config: SouthAfricaConfig) {
+        self.south_africa.insert(tax_year, config);
+    }
+
+    /// Credits and allowances for a country and tax year, defaulting to the built-in values
+    pub fn parameters(&self, country_code: &str, tax_year: i32) -> TaxParameters {
+        self.parameters
+            .get(&(country_code.to_ascii_uppercase(), tax_year))
+            .map(|p| p.clone())
+            .unwrap_or_else(|| TaxParameters::builtin(country_code, tax_year))
+    }
+
+    pub fn update_parameters(&self, parameters: TaxParameters) {
+        let key = (parameters.country_code.to_ascii_uppercase(), parameters.tax_year);
+        self.parameters.insert(key, parameters);
+    }
+
+    /// Overlay loaded config files; each table is swapped in whole, so a
+    /// reader sees either the old table or the new one
+    pub fn apply_config(&self, files: &[TaxConfigFile]) {
+        for file in files {
+            self.update_parameters(file.parameters());
+            if let Some(config) = &file.south_africa {
+                self.update_south_africa(file.tax_year, config.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Calculation Trace
+//!
+//! Step-by-step explanation of how a tax figure was reached, for payslip
+//! queries and audits. Step amounts always sum to the final running total,
+//! so a trace doubles as a regression oracle for the engine that built it.
+//!
+//! Only the Italian (IRPEF with regional and municipal surcharges) and
+//! Japanese (monthly withholding) engines build traces. Other engines
+//! report their figures without steps; `ensure_traced` refuses those
+//! countries rather than hand back an empty trace.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Countries whose engines build a calculation trace
+pub const TRACED_COUNTRIES: [&str; 2] = ["IT", "JP"];
+
+/// Trace errors
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TraceError {
+    #[error("No calculation trace for {0}; only the IT and JP engines build one")]
+    NotTraced(String),
+}
+
+/// Check that a country's engine builds a trace before asking it for one
+pub fn ensure_traced(country_code: &str) -> Result<(), TraceError> {
+    if TRACED_COUNTRIES.iter().any(|code| code.eq_ignore_ascii_case(country_code)) {
+        Ok(())
+    } else {
+        Err(TraceError::NotTraced(country_code.to_string()))
+    }
+}
+
+/// One line of a calculation trace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalcStep {
+    /// Human-readable explanation, e.g. "IRPEF 35% on 22000.00 (28000 – 50000)"
+    pub description: String,
+    /// Amount the rate was applied to (zero for fixed credits)
+    pub base: Decimal,
+    pub rate: Option<Decimal>,
+    /// Signed contribution to the total; credits are negative
+    pub amount: Decimal,
+    pub running_total: Decimal,
+}
+
+/// Collects steps when tracing is enabled, and is a no-op otherwise
+#[derive(Debug, Clone, Default)]
+pub struct CalcTrace {
+    enabled: bool,
+    total: Decimal,
+    steps: Vec<CalcStep>,
+}
+
+impl CalcTrace {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, ..Default::default() }
+    }
+
+    /// Record a rate applied to a base
+    pub fn charge(&mut self, description: impl Into<String>, base: Decimal, rate: Decimal, amount: Decimal) {
+        self.push(description.into(), base, Some(rate), amount);
+    }
+
+    /// Record a credit or deduction subtracted from the tax (pass a positive amount)
+    pub fn credit(&mut self, description: impl Into<String>, amount: Decimal) {
+        self.push(description.into(), Decimal::ZERO, None, -amount);
+    }
+
+    fn push(&mut self, description: String, base: Decimal, rate: Option<Decimal>, amount: Decimal) {
+        if !self.enabled {
+            return;
+        }
+        self.total += amount;
+        self.steps.push(CalcStep {
+            description,
+            base,
+            rate,
+            amount,
+            running_total: self.total,
+        });
+    }
+
+    pub fn into_steps(self) -> Vec<CalcStep> {
+        self.steps
+    }
+}
+
+/// Format a rate as a percentage for step descriptions
+pub fn percent(rate: Decimal) -> String {
+    format!("{}%", (rate * Decimal::ONE_HUNDRED).normalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_running_total() {
+        let mut trace = CalcTrace::new(true);
+        trace.charge("10% band", dec!(1000), dec!(0.10), dec!(100));
+        trace.credit("Personal credit", dec!(30));
+
+        let steps = trace.into_steps();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1].amount, dec!(-30));
+        assert_eq!(steps[1].running_total, dec!(70));
+        assert_eq!(percent(dec!(0.235)), "23.5%");
+    }
+
+    #[test]
+    fn test_untraced_countries_refused() {
+        assert_eq!(ensure_traced("it"), Ok(()));
+        assert_eq!(ensure_traced("JP"), Ok(()));
+        assert_eq!(ensure_traced("NG"), Err(TraceError::NotTraced("NG".to_string())));
+    }
+
+    #[test]
+    fn test_disabled_trace_is_empty() {
+        let mut trace = CalcTrace::new(false);
+        trace.charge("10% band", dec!(1000), dec!(0.10), dec!(100));
+        assert!(trace.into_steps().is_empty());
+    }
+}
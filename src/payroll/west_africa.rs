@@ -7,7 +7,6 @@
 
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use chrono::NaiveDate;
 
 /// Tax bracket for progressive tax calculation
 #[derive(Debug, Clone)]
@@ -11,6 +11,12 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::value_objects::PayFrequency;
+use super::annualization::Annualization;
+use super::tax_parameters::{
+    TaxParameters, IE_EMPLOYEE_CREDIT, IE_MARRIED_PERSONAL_CREDIT, IE_PERSONAL_CREDIT, IE_SINGLE_PARENT_CREDIT,
+};
+
 // ═══════════════════════════════════════════════════════════════════════════
 // SWITZERLAND (CH) - 26 CANTONS
 // ═══════════════════════════════════════════════════════════════════════════
@@ -299,15 +305,22 @@ impl AustrianTaxCalculator {
     }
     
     pub fn calculate(&self, gross_monthly: Decimal) -> AustrianTaxResult {
-        let gross_annual = gross_monthly * dec!(14); // 14 salaries!
+        self.calculate_annualized(gross_monthly, &Annualization::nominal(&PayFrequency::Monthly))
+    }
+
+    /// Tax on one pay period's pay, annualized over the periods in
+    /// `annualization` plus the two Sonderzahlungen, each a month's pay
+    pub fn calculate_annualized(&self, gross_monthly: Decimal, annualization: &Annualization) -> AustrianTaxResult {
+        let with_sonderzahlungen = |amount| annualization.annualize(amount) + annualization.monthly_equivalent(amount) * dec!(2);
+        let gross_annual = with_sonderzahlungen(gross_monthly); // 14 salaries!
         
         // Social insurance (capped)
-        let sv_base = gross_monthly.min(self.si.hoechstbeitragsgrundlage);
+        let sv_base = gross_monthly.min(annualization.monthly_to_period(self.si.hoechstbeitragsgrundlage));
         let sv_employee = sv_base * (self.si.krankenversicherung_an + self.si.pensionsversicherung_an + 
             self.si.arbeitslosenversicherung_an + self.si.arbeiterkammerumlage + self.si.wohnbaufoerderungsbeitrag);
         
         // Income tax (7 brackets)
-        let taxable = gross_annual - sv_employee * dec!(14);
+        let taxable = gross_annual - with_sonderzahlungen(sv_employee);
         let base_tax = self.calculate_brackets(taxable);
         
         // Credits
@@ -316,7 +329,8 @@ impl AustrianTaxCalculator {
         let tax_after_credits = (base_tax - verkehrsabsetzbetrag - familienbonus).max(Decimal::ZERO);
         
         // Sonderzahlungen tax (6%)
-        let sonderzahlungen = Sonderzahlungen { urlaubsgeld: gross_monthly, weihnachtsgeld: gross_monthly };
+        let month = annualization.monthly_equivalent(gross_monthly);
+        let sonderzahlungen = Sonderzahlungen { urlaubsgeld: month, weihnachtsgeld: month };
         let sonder_tax = sonderzahlungen.calculate_tax();
         
         let total_tax = tax_after_credits + sonder_tax;
@@ -328,7 +342,9 @@ impl AustrianTaxCalculator {
             income_tax_annual: tax_after_credits,
             sonderzahlungen_tax: sonder_tax,
             familienbonus,
-            net_monthly: gross_monthly - sv_employee - (total_tax / dec!(14)),
+            // Each payment bears tax in proportion to its share of the year's pay
+            net_monthly: gross_monthly - sv_employee
+                - if gross_annual > Decimal::ZERO { total_tax * gross_monthly / gross_annual } else { Decimal::ZERO },
             effective_rate: if gross_annual > Decimal::ZERO { total_tax / gross_annual * dec!(100) } else { Decimal::ZERO },
         }
     }
@@ -485,11 +501,23 @@ pub struct IrishTaxCalculator {
     pub marital_status: IrishMaritalStatus,
     pub is_single_income: bool,
     pub prsi_class: PRSIClass,
+    /// Tax credits for the year
+    pub parameters: TaxParameters,
 }
 
 impl IrishTaxCalculator {
     pub fn new(marital_status: IrishMaritalStatus) -> Self {
-        Self { marital_status, is_single_income: true, prsi_class: PRSIClass::A }
+        Self {
+            marital_status,
+            is_single_income: true,
+            prsi_class: PRSIClass::A,
+            parameters: TaxParameters::builtin("IE", 2024),
+        }
+    }
+    
+    pub fn with_parameters(mut self, parameters: TaxParameters) -> Self {
+        self.parameters = parameters;
+        self
     }
     
     pub fn calculate(&self, gross_annual: Decimal) -> IrishTaxResult {
@@ -509,12 +537,15 @@ impl IrishTaxCalculator {
         let income_tax_gross = standard + higher;
         
         // Tax credits
+        let params = &self.parameters;
         let personal = match self.marital_status {
-            IrishMaritalStatus::Married | IrishMaritalStatus::CivilPartner => dec!(3750),
-            IrishMaritalStatus::SingleParent => dec!(1875) + dec!(1750), // + SPCCC
-            _ => dec!(1875),
+            IrishMaritalStatus::Married | IrishMaritalStatus::CivilPartner => params.amount(IE_MARRIED_PERSONAL_CREDIT),
+            IrishMaritalStatus::SingleParent => {
+                params.amount(IE_PERSONAL_CREDIT) + params.amount(IE_SINGLE_PARENT_CREDIT)
+            }
+            _ => params.amount(IE_PERSONAL_CREDIT),
         };
-        let employee_credit = dec!(1875);
+        let employee_credit = params.amount(IE_EMPLOYEE_CREDIT);
         let total_credits = personal + employee_credit;
         let income_tax = (income_tax_gross - total_credits).max(Decimal::ZERO);
         
@@ -716,6 +747,22 @@ mod tests {
         assert!(result.prsi > Decimal::ZERO);
     }
     
+    #[test]
+    fn test_ireland_credits_from_parameters() {
+        // Built-in credits reproduce the original figures: €42,000 at 20% + €18,000 at 40% less credits
+        let single = IrishTaxCalculator::new(IrishMaritalStatus::Single).calculate(dec!(60000));
+        assert_eq!(single.income_tax_gross, dec!(15600));
+        assert_eq!(single.tax_credits, dec!(3750));
+        assert_eq!(single.income_tax, dec!(11850));
+        let single_parent = IrishTaxCalculator::new(IrishMaritalStatus::SingleParent).calculate(dec!(60000));
+        assert_eq!(single_parent.tax_credits, dec!(5500));
+        
+        let raised = TaxParameters::builtin("IE", 2025).with(IE_PERSONAL_CREDIT, dec!(2000));
+        let result = IrishTaxCalculator::new(IrishMaritalStatus::Single).with_parameters(raised).calculate(dec!(60000));
+        assert_eq!(result.tax_credits, dec!(3875));
+        assert_eq!(result.income_tax, dec!(11725));
+    }
+    
     #[test]
     fn test_liechtenstein_vaduz() {
         let calc = LiechtensteinTaxCalculator::new(LiechtensteinGemeinde::vaduz());
@@ -0,0 +1,167 @@
+//! Work Location Allocation
+//!
+//! Mobile workers owe state or provincial tax where the work is done. A
+//! `WorkLocationAllocation` splits annual wages across jurisdictions by
+//! workdays or by percentage; each jurisdiction then withholds its own tax
+//! on its share. Reciprocity agreements let a resident of one jurisdiction
+//! working in another be taxed at home instead, so the work jurisdiction's
+//! share is reassigned to the residence before tax is worked out.
+
+use std::collections::{BTreeMap, HashSet};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::rounding::MoneyRounding;
+
+/// Allocation errors
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WorkLocationError {
+    #[error("Invalid work location allocation: {0}")]
+    InvalidAllocation(String),
+
+    #[error("No tax tables for jurisdiction {0}")]
+    UnsupportedJurisdiction(String),
+}
+
+/// How an allocation's weights are expressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AllocationBasis {
+    /// Workdays in each jurisdiction
+    Days,
+    /// Percent of wages, summing to 100
+    Percent,
+}
+
+/// Wages split across states or provinces
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkLocationAllocation {
+    pub basis: AllocationBasis,
+    /// Jurisdiction code (e.g. "NY", "ON") and its days or percent
+    pub locations: Vec<(String, Decimal)>,
+}
+
+impl WorkLocationAllocation {
+    pub fn by_days(days: &[(&str, Decimal)]) -> Self {
+        Self::new(AllocationBasis::Days, days)
+    }
+
+    pub fn by_percent(percents: &[(&str, Decimal)]) -> Self {
+        Self::new(AllocationBasis::Percent, percents)
+    }
+
+    fn new(basis: AllocationBasis, weights: &[(&str, Decimal)]) -> Self {
+        Self {
+            basis,
+            locations: weights.iter().map(|(code, weight)| (code.to_ascii_uppercase(), *weight)).collect(),
+        }
+    }
+
+    /// Percentages must add up to 100 and no weight may be negative
+    pub fn validate(&self) -> Result<(), WorkLocationError> {
+        let invalid = |reason: String| Err(WorkLocationError::InvalidAllocation(reason));
+        if self.locations.iter().any(|(_, weight)| weight.is_sign_negative()) {
+            return invalid("weights cannot be negative".to_string());
+        }
+        let total: Decimal = self.locations.iter().map(|(_, weight)| *weight).sum();
+        match self.basis {
+            AllocationBasis::Percent if total != Decimal::ONE_HUNDRED => {
+                invalid(format!("percentages add up to {}, not 100", total))
+            }
+            _ if total.is_zero() => invalid("no days or percentages".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Split `amount` by weight, after reassigning reciprocal jurisdictions
+    /// to `residence`. Shares are rounded and the last absorbs the
+    /// remainder, so they always add up to `amount`.
+    pub fn apportion(
+        &self,
+        amount: Decimal,
+        residence: &str,
+        reciprocity: &ReciprocityAgreements,
+    ) -> Result<Vec<(String, Decimal)>, WorkLocationError> {
+        self.validate()?;
+
+        let mut weights: BTreeMap<String, Decimal> = BTreeMap::new();
+        for (code, weight) in &self.locations {
+            let taxed_in = if reciprocity.covers(residence, code) { residence.to_ascii_uppercase() } else { code.clone() };
+            *weights.entry(taxed_in).or_default() += weight;
+        }
+        weights.retain(|_, weight| !weight.is_zero());
+
+        let total: Decimal = weights.values().sum();
+        let rounding = MoneyRounding::default();
+        let count = weights.len();
+        let mut allocated = Decimal::ZERO;
+        Ok(weights
+            .into_iter()
+            .enumerate()
+            .map(|(i, (code, weight))| {
+                let share = if i + 1 == count { amount - allocated } else { rounding.round(amount * weight / total) };
+                allocated += share;
+                (code, share)
+            })
+            .collect())
+    }
+}
+
+/// Pairs of (residence, work jurisdiction) where wages are taxed only by
+/// the residence
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReciprocityAgreements {
+    pairs: HashSet<(String, String)>,
+}
+
+impl ReciprocityAgreements {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Residents of `residence` working in `work` are taxed at home
+    pub fn add(mut self, residence: &str, work: &str) -> Self {
+        self.pairs.insert((residence.to_ascii_uppercase(), work.to_ascii_uppercase()));
+        self
+    }
+
+    /// Agreements run both ways
+    pub fn add_mutual(self, a: &str, b: &str) -> Self {
+        self.add(a, b).add(b, a)
+    }
+
+    pub fn covers(&self, residence: &str, work: &str) -> bool {
+        self.pairs.contains(&(residence.to_ascii_uppercase(), work.to_ascii_uppercase()))
+    }
+}
+
+/// Tax withheld for one state or province
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JurisdictionWithholding {
+    pub jurisdiction: String,
+    pub wages: Decimal,
+    pub income_tax: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_apportion_sums_to_amount() {
+        let allocation = WorkLocationAllocation::by_days(&[("ny", dec!(1)), ("ca", dec!(1)), ("il", dec!(1))]);
+        let shares = allocation.apportion(dec!(100000), "NY", &ReciprocityAgreements::new()).unwrap();
+
+        assert_eq!(shares.len(), 3);
+        assert_eq!(shares.iter().map(|(_, wages)| *wages).sum::<Decimal>(), dec!(100000));
+        assert_eq!(shares[0], ("CA".to_string(), dec!(33333.33)));
+
+        let bad = WorkLocationAllocation::by_percent(&[("NY", dec!(60)), ("CA", dec!(30))]);
+        assert!(bad.validate().is_err());
+        assert_eq!(
+            bad.apportion(dec!(100000), "NY", &ReciprocityAgreements::new()),
+            Err(WorkLocationError::InvalidAllocation("percentages add up to 90, not 100".to_string())),
+        );
+    }
+}
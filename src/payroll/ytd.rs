@@ -0,0 +1,180 @@
+//! Year-to-Date Aggregates
+//!
+//! Per-employee running totals of gross, each tax/contribution type, and net
+//! pay across a calendar year. Cap- and threshold-aware calculators (Russia's
+//! 5M RUB NDFL bracket, US Social Security wage base) read their YTD from here.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::{Datelike, NaiveDate};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::models::PayrollItem;
+
+/// One payroll line contributing to YTD totals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YtdLine {
+    pub payroll_run_id: Uuid,
+    pub pay_date: NaiveDate,
+    pub gross: Decimal,
+    /// Amount per tax or contribution type, e.g. "paye", "pension_employee"
+    pub taxes: BTreeMap<String, Decimal>,
+    pub net: Decimal,
+}
+
+impl YtdLine {
+    /// Line for a payroll item; income tax is recorded as "paye" whatever
+    /// the country calls it
+    pub fn from_item(item: &PayrollItem, pay_date: NaiveDate) -> Self {
+        let taxes = [
+            ("paye", item.paye_tax),
+            ("pension_employee", item.pension_employee),
+            ("nhf", item.nhf_deduction),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+
+        Self {
+            payroll_run_id: item.payroll_run_id,
+            pay_date,
+            gross: item.gross_pay,
+            taxes,
+            net: item.net_pay,
+        }
+    }
+}
+
+/// YTD summary for an employee and year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YtdSummary {
+    pub employee_id: Uuid,
+    pub year: i32,
+    pub periods: usize,
+    pub gross: Decimal,
+    pub taxes: BTreeMap<String, Decimal>,
+    pub net: Decimal,
+}
+
+/// In-memory YTD store keyed by employee
+#[derive(Debug, Clone, Default)]
+pub struct YtdStore {
+    // In real implementation, summed from payroll_items in the database
+    lines: Arc<DashMap<Uuid, Vec<YtdLine>>>,
+}
+
+impl YtdStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, employee_id: Uuid, line: YtdLine) {
+        self.lines.entry(employee_id).or_default().push(line);
+    }
+
+    pub fn record_item(&self, item: &PayrollItem, pay_date: NaiveDate) {
+        self.record(item.employee_id, YtdLine::from_item(item, pay_date));
+    }
+
+    /// Drop every line from a payroll run, e.g. before it is recalculated
+    pub fn remove_run(&self, payroll_run_id: Uuid) {
+        for mut lines in self.lines.iter_mut() {
+            lines.retain(|line| line.payroll_run_id != payroll_run_id);
+        }
+    }
+
+    /// Move every line held for `from` onto `to`, returning the lines moved
+    pub fn reparent(&self, from: Uuid, to: Uuid) -> usize {
+        let Some((_, lines)) = self.lines.remove(&from) else {
+            return 0;
+        };
+        let moved = lines.len();
+        self.lines.entry(to).or_default().extend(lines);
+        moved
+    }
+
+    /// Totals across every line paid in `year`
+    pub fn summary(&self, employee_id: Uuid, year: i32) -> YtdSummary {
+        self.summarize(employee_id, year, |line| line.pay_date.year() == year)
+    }
+
+    /// Gross paid earlier in the same calendar year as `pay_date`,
+    /// i.e. the YTD a calculator should see for that pay date
+    pub fn gross_before(&self, employee_id: Uuid, pay_date: NaiveDate) -> Decimal {
+        self.summary_before(employee_id, pay_date).gross
+    }
+
+    /// Totals paid earlier in the same calendar year as `pay_date`
+    pub fn summary_before(&self, employee_id: Uuid, pay_date: NaiveDate) -> YtdSummary {
+        self.summarize(employee_id, pay_date.year(), |line| {
+            line.pay_date.year() == pay_date.year() && line.pay_date < pay_date
+        })
+    }
+
+    fn summarize<F: Fn(&YtdLine) -> bool>(&self, employee_id: Uuid, year: i32, include: F) -> YtdSummary {
+        let mut summary = YtdSummary {
+            employee_id,
+            year,
+            periods: 0,
+            gross: Decimal::ZERO,
+            taxes: BTreeMap::new(),
+            net: Decimal::ZERO,
+        };
+
+        if let Some(lines) = self.lines.get(&employee_id) {
+            for line in lines.iter().filter(|l| include(l)) {
+                summary.periods += 1;
+                summary.gross += line.gross;
+                summary.net += line.net;
+                for (tax, amount) in &line.taxes {
+                    *summary.taxes.entry(tax.clone()).or_default() += amount;
+                }
+            }
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payroll::RussiaTaxCalculator;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_russia_high_bracket_after_ytd_crosses_threshold() {
+        let store = YtdStore::new();
+        let employee = Uuid::new_v4();
+        let gross = dec!(900000);
+
+        let mut ndfl = vec![];
+        for month in 1..=7 {
+            let pay_date = NaiveDate::from_ymd_opt(2024, month, 25).unwrap();
+            let result = RussiaTaxCalculator::calculate(gross, store.gross_before(employee, pay_date));
+            ndfl.push(result.ndfl);
+
+            store.record(employee, YtdLine {
+                payroll_run_id: Uuid::new_v4(),
+                pay_date,
+                gross,
+                taxes: BTreeMap::from([("ndfl".to_string(), result.ndfl)]),
+                net: result.net_pay,
+            });
+        }
+
+        // 5 × 900k = 4.5M; the sixth month takes YTD past 5M, so its last
+        // 400k is the first pay taxed at 15%
+        assert_eq!(ndfl[..5], [gross * dec!(0.13); 5]);
+        assert_eq!(ndfl[5], dec!(500000) * dec!(0.13) + dec!(400000) * dec!(0.15));
+        assert_eq!(ndfl[6], gross * dec!(0.15));
+
+        // A new year starts from zero
+        assert_eq!(store.gross_before(employee, NaiveDate::from_ymd_opt(2025, 1, 25).unwrap()), Decimal::ZERO);
+        assert_eq!(store.summary(employee, 2024).taxes["ndfl"], dec!(117000) * dec!(5) + dec!(125000) + dec!(135000));
+    }
+}
@@ -2,7 +2,6 @@
 
 use chrono::Utc;
 use rust_decimal::Decimal;
-use rust_decimal_macros::dec;
 use uuid::Uuid;
 
 use super::models::*;
@@ -154,6 +153,7 @@ impl PerformanceService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rust_decimal_macros::dec;
 
     #[test]
     fn test_calculate_final_rating() {
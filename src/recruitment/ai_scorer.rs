@@ -124,7 +124,7 @@ impl AiCvScorer {
         &self,
         analyses: &mut [(uuid::Uuid, CvAnalysis)],
     ) {
-        analyses.sort_by(|a, b| b.1.score.cmp(&a.1.score));
+        analyses.sort_by_key(|a| std::cmp::Reverse(a.1.score));
     }
 }
 
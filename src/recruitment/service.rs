@@ -125,17 +125,17 @@ impl RecruitmentService {
         request: MoveStageRequest,
     ) -> Result<(), RecruitmentError> {
         // Validate stage transition
-        let valid = match (&application.stage, &request.new_stage) {
-            (ApplicationStage::Received, ApplicationStage::Screening) => true,
-            (ApplicationStage::Received, ApplicationStage::Rejected) => true,
-            (ApplicationStage::Screening, ApplicationStage::Interview) => true,
-            (ApplicationStage::Screening, ApplicationStage::Rejected) => true,
-            (ApplicationStage::Interview, ApplicationStage::Offer) => true,
-            (ApplicationStage::Interview, ApplicationStage::Rejected) => true,
-            (ApplicationStage::Offer, ApplicationStage::Hired) => true,
-            (ApplicationStage::Offer, ApplicationStage::Rejected) => true,
-            _ => false,
-        };
+        let valid = matches!(
+            (&application.stage, &request.new_stage),
+            (ApplicationStage::Received, ApplicationStage::Screening)
+                | (ApplicationStage::Received, ApplicationStage::Rejected)
+                | (ApplicationStage::Screening, ApplicationStage::Interview)
+                | (ApplicationStage::Screening, ApplicationStage::Rejected)
+                | (ApplicationStage::Interview, ApplicationStage::Offer)
+                | (ApplicationStage::Interview, ApplicationStage::Rejected)
+                | (ApplicationStage::Offer, ApplicationStage::Hired)
+                | (ApplicationStage::Offer, ApplicationStage::Rejected)
+        );
 
         if !valid {
             return Err(RecruitmentError::InvalidStageTransition);
@@ -0,0 +1,168 @@
+//! Self-Service API Handlers
+//!
+//! The caller's `AuthContext` is expected in request extensions, inserted by
+//! the authentication layer.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::auth::AuthContext;
+use crate::features::{Feature, FeatureFlags};
+use super::models::*;
+use super::service::{SelfServiceError, SelfServiceService};
+
+/// API Response wrapper
+#[derive(Debug, Serialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: Option<T>,
+    pub error: Option<String>,
+}
+
+impl<T: Serialize> ApiResponse<T> {
+    pub fn success(data: T) -> Self {
+        Self { success: true, data: Some(data), error: None }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self { success: false, data: None, error: Some(message.into()) }
+    }
+}
+
+/// Shared self-service state
+#[derive(Clone, Default)]
+pub struct SelfServiceAppState {
+    pub self_service: SelfServiceService,
+    pub features: FeatureFlags,
+}
+
+fn error_response(error: SelfServiceError) -> Response {
+    let status = match &error {
+        SelfServiceError::Forbidden | SelfServiceError::SelfApproval | SelfServiceError::RestrictedField(_) => {
+            StatusCode::FORBIDDEN
+        }
+        SelfServiceError::EmployeeNotFound(_) | SelfServiceError::ApprovalNotFound(_) => StatusCode::NOT_FOUND,
+    };
+    (status, Json(ApiResponse::<()>::error(error.to_string()))).into_response()
+}
+
+/// Update the caller's own profile
+///
+/// PATCH /api/v1/employees/:employee_id/profile
+pub async fn update_profile(
+    State(state): State<SelfServiceAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(employee_id): Path<Uuid>,
+    Json(request): Json<ProfileUpdateRequest>,
+) -> Response {
+    if let Some(disabled) = state.features.disabled_response(auth.tenant_id, Feature::SelfService) {
+        return disabled;
+    }
+    match state.self_service.update_own_profile(&auth, employee_id, request) {
+        Ok(outcome) if outcome.pending_approval.is_empty() => Json(ApiResponse::success(outcome)).into_response(),
+        Ok(outcome) => (StatusCode::ACCEPTED, Json(ApiResponse::success(outcome))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Queued profile changes in the caller's tenant, with proposed values (HR)
+///
+/// GET /api/v1/profile-approvals
+pub async fn list_profile_approvals(
+    State(state): State<SelfServiceAppState>,
+    Extension(auth): Extension<AuthContext>,
+) -> Response {
+    match state.self_service.pending_approvals(&auth) {
+        Ok(pending) => Json(ApiResponse::success(pending)).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Approve a queued profile change (HR)
+///
+/// POST /api/v1/profile-approvals/:id/approve
+pub async fn approve_profile_change(
+    State(state): State<SelfServiceAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    match state.self_service.approve_change(&auth, id) {
+        Ok(approval) => Json(ApiResponse::success(approval)).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Reject a queued profile change (HR)
+///
+/// POST /api/v1/profile-approvals/:id/reject
+pub async fn reject_profile_change(
+    State(state): State<SelfServiceAppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    match state.self_service.reject_change(&auth, id) {
+        Ok(approval) => Json(ApiResponse::success(approval)).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Self-service routes
+pub fn self_service_routes() -> axum::Router<SelfServiceAppState> {
+    use axum::routing::{get, patch, post};
+
+    axum::Router::new()
+        .route("/employees/:employee_id/profile", patch(update_profile))
+        .route("/profile-approvals", get(list_profile_approvals))
+        .route("/profile-approvals/:id/approve", post(approve_profile_change))
+        .route("/profile-approvals/:id/reject", post(reject_profile_change))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+    use crate::domain::aggregates::Employee;
+    use crate::domain::value_objects::EmployeeId;
+    use axum::{body::Body, http::Request};
+    use chrono::NaiveDate;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_restricted_field_is_forbidden() {
+        let state = SelfServiceAppState::default();
+        let (tenant_id, id) = (Uuid::new_v4(), Uuid::new_v4());
+        let hired = NaiveDate::from_ymd_opt(2024, 1, 8).unwrap();
+        let employee = Employee::hire(EmployeeId::new(2024, 4), "Ada", "Obi", "ada@company.com", "Analyst", hired);
+        state.self_service.add_employee(tenant_id, id, employee);
+
+        let auth = AuthContext {
+            user_id: Uuid::new_v4(),
+            tenant_id,
+            employee_id: Some(id),
+            role: Role::Employee,
+            permissions: Role::Employee.permissions(),
+            department_id: None,
+        };
+        let app = self_service_routes().layer(Extension(auth)).with_state(state);
+        let patch = |body: &str| {
+            Request::builder()
+                .method("PATCH")
+                .uri(format!("/employees/{}/profile", id))
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(patch(r#"{"phone":"+2348030000000"}"#)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(patch(r#"{"job_title":"Director"}"#)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,13 @@
+//! Employee Self-Service Module
+//!
+//! Lets employees edit their own profile. Only allowlisted fields can be
+//! changed directly; sensitive fields such as bank details go to an HR
+//! approval queue, and everything else (pay, title, reporting line) is
+//! refused.
+
+pub mod models;
+pub mod service;
+pub mod handlers;
+
+pub use models::*;
+pub use service::{SelfServiceError, SelfServicePolicy, SelfServiceService};
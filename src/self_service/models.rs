@@ -0,0 +1,106 @@
+//! Self-Service Models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::aggregates::{AddressInfo, BankDetails, EmergencyContact, EmployeeChange};
+use crate::domain::value_objects::PayRate;
+
+/// Profile fields a self-service request can touch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileField {
+    Phone,
+    PersonalEmail,
+    Address,
+    EmergencyContacts,
+    BankDetails,
+    JobTitle,
+    PayRate,
+    DepartmentId,
+    ManagerId,
+    WorkEmail,
+}
+
+impl std::fmt::Display for ProfileField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = serde_json::to_value(self).ok().and_then(|v| v.as_str().map(str::to_string)).unwrap_or_default();
+        write!(f, "{}", name)
+    }
+}
+
+/// Self-service profile update; absent fields are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileUpdateRequest {
+    pub phone: Option<String>,
+    pub personal_email: Option<String>,
+    pub address: Option<AddressInfo>,
+    pub emergency_contacts: Option<Vec<EmergencyContact>>,
+    pub bank_details: Option<BankDetails>,
+    pub job_title: Option<String>,
+    pub pay_rate: Option<PayRate>,
+    pub department_id: Option<String>,
+    pub manager_id: Option<String>,
+    pub work_email: Option<String>,
+}
+
+impl ProfileUpdateRequest {
+    /// Fields present in the request, each with the change it makes
+    pub fn changes(&self) -> Vec<(ProfileField, Option<EmployeeChange>)> {
+        let mut changes = Vec::new();
+        if let Some(phone) = &self.phone {
+            changes.push((ProfileField::Phone, Some(EmployeeChange::Phone(Some(phone.clone())))));
+        }
+        if let Some(email) = &self.personal_email {
+            changes.push((ProfileField::PersonalEmail, Some(EmployeeChange::PersonalEmail(Some(email.clone())))));
+        }
+        if let Some(address) = &self.address {
+            changes.push((ProfileField::Address, Some(EmployeeChange::Address(address.clone()))));
+        }
+        if let Some(contacts) = &self.emergency_contacts {
+            changes.push((ProfileField::EmergencyContacts, Some(EmployeeChange::EmergencyContacts(contacts.clone()))));
+        }
+        if let Some(details) = &self.bank_details {
+            changes.push((ProfileField::BankDetails, Some(EmployeeChange::BankDetails(details.clone()))));
+        }
+        if let Some(title) = &self.job_title {
+            changes.push((ProfileField::JobTitle, Some(EmployeeChange::JobTitle(title.clone()))));
+        }
+        if self.pay_rate.is_some() {
+            // Pay changes go through compensation review, never an EmployeeChange
+            changes.push((ProfileField::PayRate, None));
+        }
+        if let Some(department) = &self.department_id {
+            changes.push((ProfileField::DepartmentId, Some(EmployeeChange::Department(Some(department.clone())))));
+        }
+        if let Some(manager) = &self.manager_id {
+            changes.push((ProfileField::ManagerId, Some(EmployeeChange::Manager(Some(manager.clone())))));
+        }
+        if let Some(email) = &self.work_email {
+            changes.push((ProfileField::WorkEmail, Some(EmployeeChange::WorkEmail(email.clone()))));
+        }
+        changes
+    }
+}
+
+/// Sensitive change waiting for HR
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileChangeApproval {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub employee_id: Uuid,
+    pub field: ProfileField,
+    pub requested_by: Uuid,
+    pub requested_at: DateTime<Utc>,
+    /// The proposed value, so the reviewer sees what they are approving
+    pub change: EmployeeChange,
+}
+
+/// What happened to a self-service request
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProfileUpdateOutcome {
+    pub applied: Vec<ProfileField>,
+    /// Approval ids for fields queued for HR
+    pub pending_approval: Vec<Uuid>,
+}
@@ -0,0 +1,313 @@
+//! Self-Service Service
+//!
+//! Field allowlist enforcement and the approval queue for sensitive changes.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use chrono::Utc;
+use dashmap::DashMap;
+use uuid::Uuid;
+
+use crate::auth::{AuthContext, Permission};
+use crate::domain::aggregates::Employee;
+use super::models::*;
+
+/// Self-service errors
+#[derive(Debug, thiserror::Error)]
+pub enum SelfServiceError {
+    #[error("Self-service updates are only allowed on your own profile")]
+    Forbidden,
+
+    #[error("You can't approve your own profile change")]
+    SelfApproval,
+
+    #[error("Field cannot be changed through self-service: {0}")]
+    RestrictedField(ProfileField),
+
+    #[error("Employee not found: {0}")]
+    EmployeeNotFound(Uuid),
+
+    #[error("Approval not found: {0}")]
+    ApprovalNotFound(Uuid),
+}
+
+/// Which fields employees may change themselves
+#[derive(Debug, Clone)]
+pub struct SelfServicePolicy {
+    /// Applied immediately
+    pub editable: HashSet<ProfileField>,
+    /// Accepted but held for HR approval
+    pub requires_approval: HashSet<ProfileField>,
+}
+
+impl Default for SelfServicePolicy {
+    fn default() -> Self {
+        Self {
+            editable: HashSet::from([
+                ProfileField::Phone,
+                ProfileField::PersonalEmail,
+                ProfileField::Address,
+                ProfileField::EmergencyContacts,
+            ]),
+            requires_approval: HashSet::from([ProfileField::BankDetails]),
+        }
+    }
+}
+
+/// Self-service profile updates
+#[derive(Clone)]
+pub struct SelfServiceService {
+    policy: SelfServicePolicy,
+    // In real implementation, employees come from the employee repository
+    employees: Arc<DashMap<(Uuid, Uuid), Employee>>,
+    approvals: Arc<DashMap<Uuid, ProfileChangeApproval>>,
+}
+
+impl Default for SelfServiceService {
+    fn default() -> Self {
+        Self::new(SelfServicePolicy::default())
+    }
+}
+
+impl SelfServiceService {
+    pub fn new(policy: SelfServicePolicy) -> Self {
+        Self { policy, employees: Arc::new(DashMap::new()), approvals: Arc::new(DashMap::new()) }
+    }
+
+    pub fn add_employee(&self, tenant_id: Uuid, employee_id: Uuid, employee: Employee) {
+        self.employees.insert((tenant_id, employee_id), employee);
+    }
+
+    pub fn employee(&self, tenant_id: Uuid, employee_id: Uuid) -> Option<Employee> {
+        self.employees.get(&(tenant_id, employee_id)).map(|e| e.clone())
+    }
+
+    /// Apply the caller's own profile update. The whole request is refused
+    /// if any field is outside the policy, so nothing is half-applied.
+    pub fn update_own_profile(
+        &self,
+        auth: &AuthContext,
+        employee_id: Uuid,
+        request: ProfileUpdateRequest,
+    ) -> Result<ProfileUpdateOutcome, SelfServiceError> {
+        if auth.employee_id != Some(employee_id) {
+            return Err(SelfServiceError::Forbidden);
+        }
+
+        let changes = request.changes();
+        if let Some((field, _)) = changes.iter().find(|(field, _)| {
+            !self.policy.editable.contains(field) && !self.policy.requires_approval.contains(field)
+        }) {
+            return Err(SelfServiceError::RestrictedField(*field));
+        }
+
+        let mut employee = self
+            .employees
+            .get_mut(&(auth.tenant_id, employee_id))
+            .ok_or(SelfServiceError::EmployeeNotFound(employee_id))?;
+        let mut outcome = ProfileUpdateOutcome::default();
+
+        for (field, change) in changes {
+            let Some(change) = change else { continue };
+            if self.policy.editable.contains(&field) {
+                employee.update(change, None);
+                outcome.applied.push(field);
+            } else {
+                let approval = ProfileChangeApproval {
+                    id: Uuid::new_v4(),
+                    tenant_id: auth.tenant_id,
+                    employee_id,
+                    field,
+                    requested_by: auth.user_id,
+                    requested_at: Utc::now(),
+                    change,
+                };
+                outcome.pending_approval.push(approval.id);
+                self.approvals.insert(approval.id, approval);
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Pending sensitive changes in the caller's tenant, with their
+    /// proposed values, for HR review
+    pub fn pending_approvals(&self, auth: &AuthContext) -> Result<Vec<ProfileChangeApproval>, SelfServiceError> {
+        if !auth.has_permission(Permission::EmployeeUpdate) {
+            return Err(SelfServiceError::Forbidden);
+        }
+        let mut pending: Vec<_> = self
+            .approvals
+            .iter()
+            .filter(|a| a.tenant_id == auth.tenant_id)
+            .map(|a| a.value().clone())
+            .collect();
+        pending.sort_by_key(|a| a.requested_at);
+        Ok(pending)
+    }
+
+    /// The caller's tenant's queued change; another tenant's is reported
+    /// as missing
+    fn tenant_approval(&self, auth: &AuthContext, approval_id: Uuid) -> Result<ProfileChangeApproval, SelfServiceError> {
+        self.approvals
+            .get(&approval_id)
+            .filter(|a| a.tenant_id == auth.tenant_id)
+            .map(|a| a.clone())
+            .ok_or(SelfServiceError::ApprovalNotFound(approval_id))
+    }
+
+    /// Apply a queued change (HR). Nobody approves a change to their own
+    /// profile or one they requested, and the change stays queued unless
+    /// it is applied.
+    pub fn approve_change(&self, auth: &AuthContext, approval_id: Uuid) -> Result<ProfileChangeApproval, SelfServiceError> {
+        if !auth.has_permission(Permission::EmployeeUpdate) {
+            return Err(SelfServiceError::Forbidden);
+        }
+        let approval = self.tenant_approval(auth, approval_id)?;
+        if approval.requested_by == auth.user_id || auth.employee_id == Some(approval.employee_id) {
+            return Err(SelfServiceError::SelfApproval);
+        }
+        let mut employee = self
+            .employees
+            .get_mut(&(approval.tenant_id, approval.employee_id))
+            .ok_or(SelfServiceError::EmployeeNotFound(approval.employee_id))?;
+        // Whoever removes it applies it, so a concurrent approval can't apply it twice
+        let (_, approval) = self.approvals.remove(&approval_id).ok_or(SelfServiceError::ApprovalNotFound(approval_id))?;
+        employee.update(approval.change.clone(), None);
+        Ok(approval)
+    }
+
+    /// Drop a queued change (HR)
+    pub fn reject_change(&self, auth: &AuthContext, approval_id: Uuid) -> Result<ProfileChangeApproval, SelfServiceError> {
+        if !auth.has_permission(Permission::EmployeeUpdate) {
+            return Err(SelfServiceError::Forbidden);
+        }
+        self.tenant_approval(auth, approval_id)?;
+        self.approvals.remove(&approval_id).map(|(_, a)| a).ok_or(SelfServiceError::ApprovalNotFound(approval_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+    use crate::domain::aggregates::BankDetails;
+    use crate::domain::value_objects::{EmployeeId, PayFrequency, PayRate};
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    fn auth(tenant_id: Uuid, role: Role, employee_id: Option<Uuid>) -> AuthContext {
+        AuthContext {
+            user_id: Uuid::new_v4(),
+            tenant_id,
+            employee_id,
+            role,
+            permissions: role.permissions(),
+            department_id: None,
+        }
+    }
+
+    fn service_with_employee() -> (SelfServiceService, Uuid, Uuid) {
+        let service = SelfServiceService::default();
+        let (tenant_id, id) = (Uuid::new_v4(), Uuid::new_v4());
+        let hired = NaiveDate::from_ymd_opt(2024, 1, 8).unwrap();
+        let mut employee = Employee::hire(EmployeeId::new(2024, 3), "Ada", "Obi", "ada@company.com", "Analyst", hired);
+        employee.set_compensation(PayRate::salary(dec!(500000), "NGN", PayFrequency::Monthly), hired);
+        service.add_employee(tenant_id, id, employee);
+        (service, tenant_id, id)
+    }
+
+    #[test]
+    fn test_employee_changes_own_phone() {
+        let (service, tenant_id, id) = service_with_employee();
+        let request = ProfileUpdateRequest { phone: Some("+2348030000000".into()), ..Default::default() };
+
+        let outcome = service.update_own_profile(&auth(tenant_id, Role::Employee, Some(id)), id, request).unwrap();
+
+        assert_eq!(outcome.applied, vec![ProfileField::Phone]);
+        assert_eq!(service.employee(tenant_id, id).unwrap().personal().phone.as_deref(), Some("+2348030000000"));
+    }
+
+    #[test]
+    fn test_employee_cannot_change_pay_rate() {
+        let (service, tenant_id, id) = service_with_employee();
+        let request = ProfileUpdateRequest {
+            phone: Some("+2348030000000".into()),
+            pay_rate: Some(PayRate::salary(dec!(900000), "NGN", PayFrequency::Monthly)),
+            ..Default::default()
+        };
+
+        let err = service.update_own_profile(&auth(tenant_id, Role::Employee, Some(id)), id, request).unwrap_err();
+
+        assert!(matches!(err, SelfServiceError::RestrictedField(ProfileField::PayRate)));
+        // Nothing applied, including the allowed phone change
+        let employee = service.employee(tenant_id, id).unwrap();
+        assert!(employee.personal().phone.is_none());
+        assert_eq!(employee.compensation().pay_rate.as_ref().unwrap().amount(), dec!(500000));
+
+        // Someone else's profile is off limits even for allowed fields
+        let phone_only = ProfileUpdateRequest { phone: Some("+2348030000000".into()), ..Default::default() };
+        let other = auth(tenant_id, Role::Employee, Some(Uuid::new_v4()));
+        assert!(matches!(service.update_own_profile(&other, id, phone_only), Err(SelfServiceError::Forbidden)));
+    }
+
+    #[test]
+    fn test_bank_details_wait_for_approval() {
+        let (service, tenant_id, id) = service_with_employee();
+        let details = BankDetails {
+            bank_name: "GTBank".into(),
+            account_number: "0123456789".into(),
+            account_name: "Ada Obi".into(),
+        };
+        let request = ProfileUpdateRequest { bank_details: Some(details.clone()), ..Default::default() };
+
+        let outcome = service.update_own_profile(&auth(tenant_id, Role::Employee, Some(id)), id, request).unwrap();
+        assert!(outcome.applied.is_empty());
+        assert_eq!(outcome.pending_approval.len(), 1);
+        assert!(service.employee(tenant_id, id).unwrap().compensation().bank_details.is_none());
+
+        // Employees can't approve their own change
+        let approval_id = outcome.pending_approval[0];
+        assert!(service.approve_change(&auth(tenant_id, Role::Employee, Some(id)), approval_id).is_err());
+
+        // The reviewer sees the proposed account before approving
+        let hr = auth(tenant_id, Role::HrManager, None);
+        let pending = service.pending_approvals(&hr).unwrap();
+        let shown = serde_json::to_value(&pending[0]).unwrap();
+        assert_eq!(shown["change"]["bank_details"]["account_number"], "0123456789");
+
+        service.approve_change(&hr, approval_id).unwrap();
+        assert_eq!(service.employee(tenant_id, id).unwrap().compensation().bank_details, Some(details));
+        assert!(service.pending_approvals(&hr).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_approval_guards() {
+        let (service, tenant_id, id) = service_with_employee();
+        let details = BankDetails {
+            bank_name: "GTBank".into(),
+            account_number: "0123456789".into(),
+            account_name: "Ada Obi".into(),
+        };
+        let request = ProfileUpdateRequest { bank_details: Some(details.clone()), ..Default::default() };
+        let outcome = service.update_own_profile(&auth(tenant_id, Role::Employee, Some(id)), id, request).unwrap();
+        let approval_id = outcome.pending_approval[0];
+
+        // An HR manager can't approve a change to their own profile
+        let own = auth(tenant_id, Role::HrManager, Some(id));
+        assert!(matches!(service.approve_change(&own, approval_id), Err(SelfServiceError::SelfApproval)));
+
+        // Another tenant's HR neither sees nor decides it
+        let outsider = auth(Uuid::new_v4(), Role::HrManager, None);
+        assert!(service.pending_approvals(&outsider).unwrap().is_empty());
+        assert!(matches!(service.approve_change(&outsider, approval_id), Err(SelfServiceError::ApprovalNotFound(_))));
+        assert!(matches!(service.reject_change(&outsider, approval_id), Err(SelfServiceError::ApprovalNotFound(_))));
+
+        // A failed approval leaves the change queued
+        let hr = auth(tenant_id, Role::HrManager, None);
+        service.employees.remove(&(tenant_id, id));
+        assert!(matches!(service.approve_change(&hr, approval_id), Err(SelfServiceError::EmployeeNotFound(_))));
+        assert_eq!(service.pending_approvals(&hr).unwrap().len(), 1);
+        assert!(service.employee(tenant_id, id).is_none());
+    }
+}
@@ -4,10 +4,17 @@
 //! - Connectivity is intermittent or expensive
 //! - Feature phones are still common
 //! - USSD provides reliable offline access
+//!
+//! Outgoing messages go through `SmsSender`, so the gateway (Africa's
+//! Talking, Twilio, …) is pluggable; the default sender drops messages.
 
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::domain::value_objects::LocaleFormatter;
+
 /// USSD codes by country for HR operations
 #[derive(Debug, Clone)]
 pub struct UssdCodes {
@@ -123,6 +130,7 @@ pub struct SmsTemplates {
 /// Localized SMS templates
 pub struct SmsTemplateRegistry {
     templates: HashMap<String, SmsTemplates>,
+    formatter: LocaleFormatter,
 }
 
 impl SmsTemplateRegistry {
@@ -174,13 +182,71 @@ impl SmsTemplateRegistry {
             salary_credit: "OpenSASE: Mshahara wa {currency}{amount} umewekwa kwa {period}.".to_string(),
         });
         
-        Self { templates }
+        Self { templates, formatter: LocaleFormatter::new() }
+    }
+    
+    pub fn with_formatter(mut self, formatter: LocaleFormatter) -> Self {
+        self.formatter = formatter;
+        self
+    }
+    
+    pub fn formatter(&self) -> &LocaleFormatter {
+        &self.formatter
     }
     
     pub fn get_templates(&self, language: &str) -> &SmsTemplates {
         self.templates.get(language).unwrap_or_else(|| self.templates.get("en").unwrap())
     }
     
+    /// Template language for a locale tag ("fr-CI" -> "fr")
+    fn language(locale: &str) -> String {
+        locale.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase()
+    }
+    
+    /// Payslip SMS in `locale` ("fr-CI"), with the amount and currency
+    /// written the local way
+    pub fn format_localized_payslip_sms(
+        &self,
+        locale: &str,
+        period: &str,
+        currency: &str,
+        amount: Decimal,
+        decimal_places: u32,
+        ussd: &str,
+    ) -> String {
+        let amount = self.formatter.format_money(locale, amount, currency, decimal_places);
+        self.format_payslip_sms(&Self::language(locale), period, "", &amount, ussd)
+    }
+    
+    /// Leave approval SMS in `locale` with dates written the local way
+    pub fn format_localized_leave_approved_sms(
+        &self,
+        locale: &str,
+        leave_type: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> String {
+        self.format_leave_approved_sms(
+            &Self::language(locale),
+            leave_type,
+            &self.formatter.format_date(locale, start_date),
+            &self.formatter.format_date(locale, end_date),
+        )
+    }
+    
+    /// Salary credit SMS in `locale`, with the amount written the local way
+    pub fn format_localized_salary_credit_sms(
+        &self,
+        locale: &str,
+        currency: &str,
+        amount: Decimal,
+        decimal_places: u32,
+        period: &str,
+    ) -> String {
+        let amount = self.formatter.format_money(locale, amount, currency, decimal_places);
+        self.format_salary_credit_sms(&Self::language(locale), "", &amount, period)
+    }
+    
     pub fn format_payslip_sms(
         &self, 
         language: &str, 
@@ -211,6 +277,18 @@ impl SmsTemplateRegistry {
             .replace("{end_date}", end_date)
     }
     
+    pub fn format_leave_rejected_sms(
+        &self,
+        language: &str,
+        leave_type: &str,
+        reason: &str,
+    ) -> String {
+        self.get_templates(language)
+            .leave_rejected
+            .replace("{leave_type}", leave_type)
+            .replace("{reason}", reason)
+    }
+    
     pub fn format_salary_credit_sms(
         &self,
         language: &str,
@@ -232,6 +310,31 @@ impl Default for SmsTemplateRegistry {
     }
 }
 
+/// SMS delivery errors
+#[derive(Debug, thiserror::Error)]
+pub enum SmsError {
+    #[error("SMS gateway error: {0}")]
+    Gateway(String),
+    
+    #[error("Invalid phone number: {0}")]
+    InvalidNumber(String),
+}
+
+/// Outgoing SMS gateway
+pub trait SmsSender: Send + Sync {
+    fn send(&self, phone: &str, message: &str) -> Result<(), SmsError>;
+}
+
+/// Sender that drops every message; used until a gateway is configured
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopSmsSender;
+
+impl SmsSender for NoopSmsSender {
+    fn send(&self, _phone: &str, _message: &str) -> Result<(), SmsError> {
+        Ok(())
+    }
+}
+
 /// Sync status for offline-first operations
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SyncStatus {
@@ -300,4 +403,27 @@ mod tests {
         let sms = registry.format_payslip_sms("unknown", "Jan 2024", "₦", "500,000", "*400*3#");
         assert!(sms.contains("OpenSASE"));
     }
+    
+    #[test]
+    fn test_localized_sms_formatting() {
+        use rust_decimal_macros::dec;
+        
+        let registry = SmsTemplateRegistry::new();
+        let (start, end) = (NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2024, 2, 5).unwrap());
+        
+        let sms = registry.format_localized_payslip_sms("en-NG", "Jan 2024", "₦", dec!(500000), 2, "*400*3#");
+        assert!(sms.contains("Net: ₦500,000.00."));
+        
+        let sms = registry.format_localized_payslip_sms("fr-CI", "Janv. 2024", "FCFA", dec!(250000), 0, "*144#");
+        assert!(sms.contains("Net: 250\u{202f}000 FCFA."));
+        assert!(sms.contains("Bulletin de paie"));
+        
+        let sms = registry.format_localized_leave_approved_sms("en-US", "Annual", start, end);
+        assert!(sms.contains("from 02/01/2024 to 02/05/2024"));
+        let sms = registry.format_localized_leave_approved_sms("fr-CI", "annuel", start, end);
+        assert!(sms.contains("du 01/02/2024 au 05/02/2024"));
+        
+        let sms = registry.format_localized_salary_credit_sms("de-CH", "CHF", dec!(6250.5), 2, "Feb 2024");
+        assert!(sms.contains("Salary of CHF 6'250.50 credited"));
+    }
 }
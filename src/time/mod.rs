@@ -0,0 +1,416 @@
+//! Time Module
+//!
+//! Ingestion of clock punches exported by biometric terminals and time
+//! clocks. Punches are matched to employees by employee number, paired
+//! into in/out shifts, and turned into payable hours by a `TimePolicy`.
+//!
+//! Expected CSV layout (header required, extra columns ignored):
+//!
+//! ```text
+//! employee_number,timestamp,direction
+//! EMP-2024-00001,2024-03-04 08:58:12,IN
+//! EMP-2024-00001,2024-03-04T17:03:40,OUT
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{NaiveDateTime, Timelike};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::compliance::{enforce_overtime_caps, OvertimeFinding, OvertimeLedger, OvertimeRules};
+use crate::domain::aggregates::Employee;
+
+/// Row-level import errors
+#[derive(Debug, Clone, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeImportError {
+    #[error("Missing column: {0}")]
+    MissingColumn(String),
+
+    #[error("Malformed row: {0}")]
+    MalformedRow(String),
+
+    #[error("Unknown employee: {0}")]
+    UnknownEmployee(String),
+
+    #[error("Malformed timestamp: {0}")]
+    MalformedTimestamp(String),
+
+    #[error("Unknown punch direction: {0}")]
+    UnknownDirection(String),
+
+    #[error("Unmatched {0} punch")]
+    UnmatchedPunch(PunchDirection),
+
+    #[error("Shift of {0} hours exceeds the policy maximum")]
+    ShiftTooLong(Decimal),
+}
+
+/// Clock in or out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PunchDirection {
+    In,
+    Out,
+}
+
+impl std::fmt::Display for PunchDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::In => write!(f, "clock-in"),
+            Self::Out => write!(f, "clock-out"),
+        }
+    }
+}
+
+impl PunchDirection {
+    /// Devices variously emit IN/OUT, I/O, or 0/1
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "IN" | "I" | "0" | "CHECKIN" => Some(Self::In),
+            "OUT" | "O" | "1" | "CHECKOUT" => Some(Self::Out),
+            _ => None,
+        }
+    }
+}
+
+/// How punches become payable hours
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimePolicy {
+    /// Punches snap to the nearest multiple of this many minutes; 0 = exact
+    pub rounding_minutes: u32,
+    /// Longer shifts are rejected as a probable missed punch
+    pub max_shift_hours: Decimal,
+}
+
+impl Default for TimePolicy {
+    fn default() -> Self {
+        Self { rounding_minutes: 15, max_shift_hours: Decimal::from(16) }
+    }
+}
+
+impl TimePolicy {
+    /// Snap a punch to the rounding grid
+    pub fn round_punch(&self, at: NaiveDateTime) -> NaiveDateTime {
+        if self.rounding_minutes == 0 {
+            return at;
+        }
+        let grid = i64::from(self.rounding_minutes) * 60;
+        let seconds = i64::from(at.num_seconds_from_midnight());
+        let snapped = (seconds + grid / 2) / grid * grid;
+        at.date().and_hms_opt(0, 0, 0).unwrap() + chrono::Duration::seconds(snapped)
+    }
+
+    /// Payable hours between two punches, to two decimal places
+    pub fn finalize(&self, clock_in: NaiveDateTime, clock_out: NaiveDateTime) -> Result<Decimal, TimeImportError> {
+        let minutes = (self.round_punch(clock_out) - self.round_punch(clock_in)).num_minutes().max(0);
+        let hours = (Decimal::from(minutes) / Decimal::from(60)).round_dp(2);
+        if hours > self.max_shift_hours {
+            return Err(TimeImportError::ShiftTooLong(hours));
+        }
+        Ok(hours)
+    }
+}
+
+/// A finalized shift
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeEntry {
+    /// Aggregate id of the matched employee
+    pub employee_id: String,
+    pub employee_number: String,
+    pub clock_in: NaiveDateTime,
+    pub clock_out: NaiveDateTime,
+    pub hours: Decimal,
+}
+
+impl TimeEntry {
+    /// Stable id for the shift: the same punches imported again give the
+    /// same id
+    pub fn id(&self) -> String {
+        format!("{}@{}", self.employee_id, self.clock_in.format("%Y-%m-%dT%H:%M:%S"))
+    }
+}
+
+/// Outcome for one CSV data row (line numbers are 1-based, header is line 1)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RowStatus {
+    Imported,
+    Duplicate,
+    Error(TimeImportError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowResult {
+    pub line: usize,
+    pub status: RowStatus,
+}
+
+/// Import result: per-row outcomes plus the shifts built from good punches
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimeImport {
+    pub rows: Vec<RowResult>,
+    pub entries: Vec<TimeEntry>,
+    /// Overtime cap findings, when imported through `import_timesheet`
+    #[serde(default)]
+    pub overtime: Vec<OvertimeFinding>,
+}
+
+impl TimeImport {
+    pub fn error_count(&self) -> usize {
+        self.rows.iter().filter(|r| matches!(r.status, RowStatus::Error(_))).count()
+    }
+}
+
+struct Punch {
+    line: usize,
+    at: NaiveDateTime,
+    direction: PunchDirection,
+}
+
+fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.naive_local())
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok())
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok())
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").ok())
+}
+
+/// Parse device punches and finalize them into shifts.
+///
+/// A repeated punch (same employee and timestamp) is reported as a
+/// duplicate and ignored. A punch that can't be paired (in without out or
+/// vice versa) is reported as an error on its row.
+pub fn import_entries(csv: &str, employees: &[Employee], policy: &TimePolicy) -> TimeImport {
+    let mut import = TimeImport::default();
+    let mut lines = csv.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+
+    let Some((_, header)) = lines.next() else { return import };
+    let columns: Vec<String> = header.split(',').map(|c| c.trim().to_ascii_lowercase()).collect();
+    let position = |name: &str| columns.iter().position(|c| c == name);
+    let (Some(number_col), Some(time_col), Some(direction_col)) =
+        (position("employee_number"), position("timestamp"), position("direction"))
+    else {
+        let missing = ["employee_number", "timestamp", "direction"]
+            .into_iter()
+            .find(|c| position(c).is_none())
+            .unwrap_or_default();
+        import.rows.push(RowResult { line: 1, status: RowStatus::Error(TimeImportError::MissingColumn(missing.into())) });
+        return import;
+    };
+
+    let by_number: HashMap<String, &Employee> =
+        employees.iter().map(|e| (e.employee_id().to_string(), e)).collect();
+    let mut seen: HashSet<(String, NaiveDateTime)> = HashSet::new();
+    let mut punches: BTreeMap<String, Vec<Punch>> = BTreeMap::new();
+    let mut statuses: BTreeMap<usize, RowStatus> = BTreeMap::new();
+
+    for (index, raw) in lines {
+        let line = index + 1;
+        let fields: Vec<&str> = raw.split(',').map(str::trim).collect();
+        let field = |col: usize| fields.get(col).copied();
+
+        let parsed = (|| {
+            let (Some(number), Some(timestamp), Some(direction)) = (field(number_col), field(time_col), field(direction_col)) else {
+                return Err(TimeImportError::MalformedRow(raw.to_string()));
+            };
+            if !by_number.contains_key(number) {
+                return Err(TimeImportError::UnknownEmployee(number.to_string()));
+            }
+            let at = parse_timestamp(timestamp).ok_or_else(|| TimeImportError::MalformedTimestamp(timestamp.to_string()))?;
+            let direction = PunchDirection::parse(direction).ok_or_else(|| TimeImportError::UnknownDirection(direction.to_string()))?;
+            Ok((number.to_string(), at, direction))
+        })();
+
+        match parsed {
+            Err(e) => {
+                statuses.insert(line, RowStatus::Error(e));
+            }
+            Ok((number, at, direction)) => {
+                if !seen.insert((number.clone(), at)) {
+                    statuses.insert(line, RowStatus::Duplicate);
+                    continue;
+                }
+                punches.entry(number).or_default().push(Punch { line, at, direction });
+            }
+        }
+    }
+
+    for (number, mut employee_punches) in punches {
+        employee_punches.sort_by_key(|p| p.at);
+        let employee_id = by_number[&number].id().to_string();
+        let mut open: Option<Punch> = None;
+
+        for punch in employee_punches {
+            match (punch.direction, open.take()) {
+                (PunchDirection::In, previous) => {
+                    if let Some(dangling) = previous {
+                        statuses.insert(dangling.line, RowStatus::Error(TimeImportError::UnmatchedPunch(PunchDirection::In)));
+                    }
+                    open = Some(punch);
+                }
+                (PunchDirection::Out, None) => {
+                    statuses.insert(punch.line, RowStatus::Error(TimeImportError::UnmatchedPunch(PunchDirection::Out)));
+                }
+                (PunchDirection::Out, Some(clock_in)) => match policy.finalize(clock_in.at, punch.at) {
+                    Ok(hours) => {
+                        statuses.insert(clock_in.line, RowStatus::Imported);
+                        statuses.insert(punch.line, RowStatus::Imported);
+                        import.entries.push(TimeEntry {
+                            employee_id: employee_id.clone(),
+                            employee_number: number.clone(),
+                            clock_in: clock_in.at,
+                            clock_out: punch.at,
+                            hours,
+                        });
+                    }
+                    Err(e) => {
+                        statuses.insert(clock_in.line, RowStatus::Error(e.clone()));
+                        statuses.insert(punch.line, RowStatus::Error(e));
+                    }
+                },
+            }
+        }
+        if let Some(dangling) = open {
+            statuses.insert(dangling.line, RowStatus::Error(TimeImportError::UnmatchedPunch(PunchDirection::In)));
+        }
+    }
+
+    import.rows = statuses.into_iter().map(|(line, status)| RowResult { line, status }).collect();
+    import
+}
+
+/// Import punches for employees in `country_code` and apply the
+/// country's overtime caps. Shifts rejected by a cap are left out of
+/// `entries`; accepted overtime is recorded in `ledger` by shift id, so
+/// importing the same file again doesn't count it twice.
+pub fn import_timesheet(
+    csv: &str,
+    employees: &[Employee],
+    policy: &TimePolicy,
+    country_code: &str,
+    rules: &OvertimeRules,
+    ledger: &OvertimeLedger,
+) -> TimeImport {
+    let mut import = import_entries(csv, employees, policy);
+    import.overtime = enforce_overtime_caps(&mut import, country_code, rules, ledger);
+    import
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::EmployeeId;
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    fn staff() -> Vec<Employee> {
+        let hired = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        vec![
+            Employee::hire(EmployeeId::new(2024, 1), "Ada", "Obi", "ada@example.com", "Technician", hired),
+            Employee::hire(EmployeeId::new(2024, 2), "Tunde", "Bello", "tunde@example.com", "Technician", hired),
+        ]
+    }
+
+    fn csv(rows: &[(&EmployeeId, &str, &str)]) -> String {
+        let mut out = String::from("employee_number,timestamp,direction,device\n");
+        for (number, at, direction) in rows {
+            out.push_str(&format!("{},{},{},gate-1\n", number, at, direction));
+        }
+        out
+    }
+
+    #[test]
+    fn test_valid_import_finalizes_hours() {
+        let employees = staff();
+        let (ada, tunde) = (employees[0].employee_id(), employees[1].employee_id());
+        let file = csv(&[
+            (ada, "2024-03-04 08:58:12", "IN"),
+            (tunde, "2024-03-04T09:00:00", "0"),
+            (ada, "2024-03-04 17:03:40", "OUT"),
+            (tunde, "2024-03-04T13:30:00+01:00", "1"),
+        ]);
+
+        let import = import_entries(&file, &employees, &TimePolicy::default());
+
+        assert_eq!(import.error_count(), 0);
+        assert_eq!(import.rows.len(), 4);
+        assert!(import.rows.iter().all(|r| r.status == RowStatus::Imported));
+        assert_eq!(import.entries.len(), 2);
+
+        let ada_shift = import.entries.iter().find(|e| e.employee_number == ada.to_string()).unwrap();
+        // 08:58 → 09:00 and 17:03 → 17:00 on the 15-minute grid
+        assert_eq!(ada_shift.hours, dec!(8));
+        assert_eq!(ada_shift.employee_id, employees[0].id());
+        let tunde_shift = import.entries.iter().find(|e| e.employee_number == tunde.to_string()).unwrap();
+        assert_eq!(tunde_shift.hours, dec!(4.5));
+    }
+
+    #[test]
+    fn test_unknown_employee_and_bad_timestamp_rows() {
+        let employees = staff();
+        let ada = employees[0].employee_id();
+        let stranger = EmployeeId::new(2019, 77);
+        let file = csv(&[
+            (ada, "2024-03-04 09:00:00", "IN"),
+            (&stranger, "2024-03-04 09:00:00", "IN"),
+            (ada, "04/03/2024 17:00", "OUT"),
+        ]);
+
+        let import = import_entries(&file, &employees, &TimePolicy::default());
+
+        assert_eq!(import.rows[1], RowResult {
+            line: 3,
+            status: RowStatus::Error(TimeImportError::UnknownEmployee(stranger.to_string())),
+        });
+        assert_eq!(import.rows[2].status, RowStatus::Error(TimeImportError::MalformedTimestamp("04/03/2024 17:00".into())));
+        // The clock-in is left without its clock-out
+        assert_eq!(import.rows[0].status, RowStatus::Error(TimeImportError::UnmatchedPunch(PunchDirection::In)));
+        assert!(import.entries.is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_punches_deduped() {
+        let employees = staff();
+        let ada = employees[0].employee_id();
+        // Terminals often resend their buffer after reconnecting
+        let file = csv(&[
+            (ada, "2024-03-04 09:00:00", "IN"),
+            (ada, "2024-03-04 09:00:00", "IN"),
+            (ada, "2024-03-04 17:00:00", "OUT"),
+            (ada, "2024-03-04 17:00:00", "OUT"),
+        ]);
+
+        let import = import_entries(&file, &employees, &TimePolicy::default());
+
+        let statuses: Vec<_> = import.rows.iter().map(|r| r.status.clone()).collect();
+        assert_eq!(statuses, [RowStatus::Imported, RowStatus::Duplicate, RowStatus::Imported, RowStatus::Duplicate]);
+        assert_eq!(import.entries.len(), 1);
+        assert_eq!(import.entries[0].hours, dec!(8));
+    }
+
+    #[test]
+    fn test_reimported_timesheet_counts_overtime_once() {
+        let employees = staff();
+        let ada = employees[0].employee_id();
+        // Five 11-hour days in Vietnam: 15 hours over the 40-hour week
+        let days: Vec<(String, String)> = (1..=5)
+            .map(|d| (format!("2024-04-0{d} 08:00:00"), format!("2024-04-0{d} 19:00:00")))
+            .collect();
+        let rows: Vec<_> = days.iter().flat_map(|(i, o)| [(ada, i.as_str(), "IN"), (ada, o.as_str(), "OUT")]).collect();
+        let file = csv(&rows);
+        let (rules, ledger) = (OvertimeRules::new(), OvertimeLedger::new());
+        ledger.add(employees[0].id(), 2024, dec!(180));
+
+        let import = import_timesheet(&file, &employees, &TimePolicy::default(), "VN", &rules, &ledger);
+        assert_eq!(import.entries.len(), 5);
+        assert_eq!(ledger.hours(employees[0].id(), 2024), dec!(195));
+        assert_eq!(import.overtime[0].status, crate::compliance::OvertimeCapStatus::Approaching);
+
+        // The terminal's buffer is uploaded again after a failed sync
+        let again = import_timesheet(&file, &employees, &TimePolicy::default(), "VN", &rules, &ledger);
+        assert_eq!(ledger.hours(employees[0].id(), 2024), dec!(195));
+        assert_eq!(again.overtime[0].overtime_hours, dec!(195));
+    }
+}
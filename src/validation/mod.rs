@@ -0,0 +1,202 @@
+//! Request Validation
+//!
+//! `Validator` collects every field error in a request instead of stopping
+//! at the first, and `ValidJson` runs a request type's `Validate` impl as
+//! part of extraction so handlers only see valid input. Failures come back
+//! as `422 Unprocessable Entity` with the full list of `FieldError`s.
+
+use axum::{
+    async_trait,
+    extract::{FromRequest, Request},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// One problem with one field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// Request field name, dotted for nested fields
+    pub field: String,
+    /// Machine-readable code, e.g. "required", "invalid_email"
+    pub code: String,
+    pub message: String,
+}
+
+/// Every field error found in a request
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationErrors {
+    pub errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn single(field: &str, code: &str, message: impl Into<String>) -> Self {
+        Self { errors: vec![FieldError { field: field.into(), code: code.into(), message: message.into() }] }
+    }
+
+    pub fn has_field(&self, field: &str) -> bool {
+        self.errors.iter().any(|e| e.field == field)
+    }
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields: Vec<&str> = self.errors.iter().map(|e| e.field.as_str()).collect();
+        write!(f, "Validation failed: {}", fields.join(", "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// 422 response body
+#[derive(Debug, Serialize)]
+struct ValidationResponse<'a> {
+    success: bool,
+    error: &'static str,
+    errors: &'a [FieldError],
+}
+
+impl IntoResponse for ValidationErrors {
+    fn into_response(self) -> Response {
+        let body = ValidationResponse { success: false, error: "Validation failed", errors: &self.errors };
+        (StatusCode::UNPROCESSABLE_ENTITY, Json(body)).into_response()
+    }
+}
+
+/// Request types that can check themselves
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+/// Accumulates field errors across a whole request
+#[derive(Debug, Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an error when `ok` is false
+    pub fn check(&mut self, ok: bool, field: &str, code: &str, message: impl Into<String>) -> &mut Self {
+        if !ok {
+            self.errors.push(FieldError { field: field.into(), code: code.into(), message: message.into() });
+        }
+        self
+    }
+
+    /// Non-blank string
+    pub fn required(&mut self, field: &str, value: &str) -> &mut Self {
+        self.check(!value.trim().is_empty(), field, "required", format!("{} is required", field))
+    }
+
+    /// Plausible email address (one `@`, non-empty local part, dotted domain)
+    pub fn email(&mut self, field: &str, value: &str) -> &mut Self {
+        let valid = match value.trim().split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !domain.contains('@')
+                    && domain.split('.').count() >= 2
+                    && domain.split('.').all(|part| !part.is_empty())
+                    && !value.contains(char::is_whitespace)
+            }
+            None => false,
+        };
+        self.check(valid, field, "invalid_email", format!("{} must be a valid email address", field))
+    }
+
+    pub fn max_length(&mut self, field: &str, value: &str, max: usize) -> &mut Self {
+        self.check(value.chars().count() <= max, field, "too_long", format!("{} must be at most {} characters", field, max))
+    }
+
+    pub fn positive(&mut self, field: &str, value: Decimal) -> &mut Self {
+        self.check(value > Decimal::ZERO, field, "not_positive", format!("{} must be greater than zero", field))
+    }
+
+    /// `end` on or after `start`; the error is reported against `end_field`
+    pub fn date_order(&mut self, end_field: &str, start: NaiveDate, end: NaiveDate) -> &mut Self {
+        self.check(end >= start, end_field, "before_start", format!("{} must not be before the start date", end_field))
+    }
+
+    pub fn finish(&mut self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors { errors: std::mem::take(&mut self.errors) })
+        }
+    }
+}
+
+/// JSON body extractor that also runs `Validate`. Malformed JSON is
+/// rejected as usual; well-formed but invalid bodies get a 422 listing
+/// every field error.
+pub struct ValidJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await.map_err(IntoResponse::into_response)?;
+        value.validate().map_err(IntoResponse::into_response)?;
+        Ok(Self(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::CreateEmployeeRequest;
+    use axum::{body::Body, http::Request, routing::post, Router};
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_validator_collects_all_errors() {
+        let errors = Validator::new()
+            .required("first_name", " ")
+            .email("work_email", "ada@example")
+            .positive("amount", Decimal::ZERO)
+            .email("personal_email", "ada@example.com")
+            .finish()
+            .unwrap_err();
+
+        let fields: Vec<&str> = errors.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["first_name", "work_email", "amount"]);
+        assert_eq!(errors.errors[1].code, "invalid_email");
+    }
+
+    #[tokio::test]
+    async fn test_employee_create_reports_every_error() {
+        async fn create(ValidJson(request): ValidJson<CreateEmployeeRequest>) -> StatusCode {
+            let _ = request;
+            StatusCode::CREATED
+        }
+        let app: Router = Router::new().route("/employees", post(create));
+
+        let body = r#"{"first_name":"","last_name":"Obi","work_email":"not-an-email","job_title":"Analyst","hire_date":"2024-01-08"}"#;
+        let request = Request::builder()
+            .method("POST")
+            .uri("/employees")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let errors: Vec<FieldError> = serde_json::from_value(json["errors"].clone()).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| e.field == "first_name" && e.code == "required"));
+        assert!(errors.iter().any(|e| e.field == "work_email" && e.code == "invalid_email"));
+    }
+}